@@ -0,0 +1,70 @@
+//! Typed client for the Orbital Intents API
+//!
+//! Generated from `openapi.json`, which the API writes with
+//! `cargo run -p intents-api --bin openapi`. Don't edit the types by hand;
+//! regenerate the document when the API changes.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = intents_api_client::with_api_key("https://api.example.com", "oak_...")?;
+//! let intent = client.get_intent_by_id("0x1234...").await?;
+//! println!("{}", intent.status);
+//! # Ok(())
+//! # }
+//! ```
+
+progenitor::generate_api!(
+    spec = "openapi.json",
+    interface = Positional,
+    tags = Merged,
+);
+
+use reqwest::header::{HeaderMap, HeaderValue, InvalidHeaderValue, AUTHORIZATION};
+
+/// Header programmatic clients send their API key in
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Errors building an authenticated client
+#[derive(Debug)]
+pub enum ClientBuildError {
+    InvalidCredential(InvalidHeaderValue),
+    Http(reqwest::Error),
+}
+
+impl std::fmt::Display for ClientBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCredential(e) => write!(f, "invalid credential: {}", e),
+            Self::Http(e) => write!(f, "failed to build HTTP client: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientBuildError {}
+
+/// Client authenticating with an API key
+pub fn with_api_key(base_url: &str, api_key: &str) -> Result<Client, ClientBuildError> {
+    let mut value = HeaderValue::from_str(api_key).map_err(ClientBuildError::InvalidCredential)?;
+    value.set_sensitive(true);
+    with_header(base_url, API_KEY_HEADER, value)
+}
+
+/// Client authenticating with a JWT from `/api/v1/auth`
+pub fn with_bearer_token(base_url: &str, token: &str) -> Result<Client, ClientBuildError> {
+    let mut value =
+        HeaderValue::from_str(&format!("Bearer {}", token)).map_err(ClientBuildError::InvalidCredential)?;
+    value.set_sensitive(true);
+    with_header(base_url, AUTHORIZATION.as_str(), value)
+}
+
+fn with_header(base_url: &str, name: &'static str, value: HeaderValue) -> Result<Client, ClientBuildError> {
+    let mut headers = HeaderMap::new();
+    headers.insert(name, value);
+
+    let http = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .map_err(ClientBuildError::Http)?;
+
+    Ok(Client::new_with_client(base_url, http))
+}
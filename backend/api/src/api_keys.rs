@@ -0,0 +1,167 @@
+//! API keys for programmatic clients
+//!
+//! Keys are random secrets shown once and stored as SHA-256 hashes. A key
+//! authenticates as its owner, limited to the scopes it was issued with,
+//! and is rate limited by its tier rather than per IP.
+
+use chrono::{DateTime, Utc};
+use ethers::types::Address;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    database::ApiKeyDb,
+    error::{validation_error, ApiError, Result},
+    models::{ApiKeyRecord, Claims},
+};
+
+pub const SCOPES: &[&str] = &["read", "submit", "admin"];
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+const KEY_PREFIX: &str = "oak_";
+/// Characters of the key kept in the clear to tell keys apart
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+/// A freshly generated key; only `hash` and `prefix` are stored
+pub struct IssuedKey {
+    pub key: String,
+    pub prefix: String,
+    pub hash: String,
+}
+
+impl IssuedKey {
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let key = format!("{}{}", KEY_PREFIX, hex::encode(bytes));
+
+        Self {
+            prefix: key[..DISPLAY_PREFIX_LEN].to_string(),
+            hash: hash_key(&key),
+            key,
+        }
+    }
+}
+
+pub fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Set on requests authenticated with an API key
+#[derive(Debug, Clone)]
+pub struct ApiKeyContext {
+    pub id: Uuid,
+    pub tier: String,
+    pub scopes: Vec<String>,
+}
+
+/// Scope a request needs: admin routes need "admin", reads need "read" and
+/// everything else "submit"
+pub fn required_scope(method: &str, path: &str) -> &'static str {
+    if path.starts_with("/admin") || path.starts_with("/api/v1/admin") {
+        "admin"
+    } else if matches!(method, "GET" | "HEAD" | "OPTIONS") {
+        "read"
+    } else {
+        "submit"
+    }
+}
+
+pub fn validate_scopes(scopes: &[String]) -> Result<()> {
+    if scopes.is_empty() {
+        return Err(validation_error("An API key needs at least one scope"));
+    }
+    match scopes.iter().find(|scope| !SCOPES.contains(&scope.as_str())) {
+        Some(unknown) => Err(validation_error(format!("Unknown API key scope: {}", unknown))),
+        None => Ok(()),
+    }
+}
+
+/// Role a key acts with: its owner's, except that admin and operator
+/// rights also need the admin scope
+pub fn key_role(owner_role: &str, scopes: &[String]) -> &'static str {
+    let admin_scope = scopes.iter().any(|scope| scope == "admin");
+    match owner_role {
+        "admin" if admin_scope => "admin",
+        "operator" if admin_scope => "operator",
+        "solver" => "solver",
+        _ => "user",
+    }
+}
+
+/// Look up a presented key and check it may make this request
+pub async fn authenticate(db: &PgPool, key: &str, method: &str, path: &str) -> Result<(Claims, ApiKeyContext)> {
+    if !key.starts_with(KEY_PREFIX) {
+        return Err(ApiError::Authentication("Invalid API key".to_string()));
+    }
+
+    let record = ApiKeyDb::find_live_key(db, &hash_key(key))
+        .await?
+        .ok_or_else(|| ApiError::Authentication("Invalid or expired API key".to_string()))?;
+
+    let scope = required_scope(method, path);
+    if !record.scopes.iter().any(|granted| granted == scope) {
+        return Err(ApiError::Authorization(format!("API key lacks the {} scope", scope)));
+    }
+
+    Ok((claims_for(&record), ApiKeyContext {
+        id: record.id,
+        tier: record.tier.clone(),
+        scopes: record.scopes.clone(),
+    }))
+}
+
+fn claims_for(record: &ApiKeyRecord) -> Claims {
+    let now = Utc::now();
+    let exp = record.expires_at.unwrap_or(DateTime::<Utc>::MAX_UTC).min(now + chrono::Duration::days(365));
+
+    Claims {
+        sub: record.owner_address.clone(),
+        exp: exp.timestamp() as usize,
+        iat: now.timestamp() as usize,
+        role: key_role(&record.owner_role, &record.scopes).to_string(),
+    }
+}
+
+/// Whether `caller` may manage the key: its owner, or an admin
+pub fn can_manage(record: &ApiKeyRecord, caller: Address, claims: &Claims) -> bool {
+    record.owner_address == format!("{:#x}", caller) || claims.role == "admin"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issued_keys_hash_and_prefix() {
+        let issued = IssuedKey::generate();
+        assert!(issued.key.starts_with(KEY_PREFIX));
+        assert_eq!(issued.key.len(), KEY_PREFIX.len() + 64);
+        assert!(issued.key.starts_with(&issued.prefix));
+        assert_eq!(issued.hash, hash_key(&issued.key));
+        assert_ne!(issued.hash, IssuedKey::generate().hash);
+    }
+
+    #[test]
+    fn test_scopes_and_roles() {
+        assert_eq!(required_scope("GET", "/api/v1/intents"), "read");
+        assert_eq!(required_scope("POST", "/api/v1/intents"), "submit");
+        assert_eq!(required_scope("GET", "/api/v1/admin/stats"), "admin");
+        assert_eq!(required_scope("DELETE", "/admin/solvers/0x1"), "admin");
+
+        let scopes = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(validate_scopes(&scopes(&["read", "submit"])).is_ok());
+        assert!(validate_scopes(&scopes(&["write"])).is_err());
+        assert!(validate_scopes(&[]).is_err());
+
+        assert_eq!(key_role("admin", &scopes(&["read", "admin"])), "admin");
+        assert_eq!(key_role("admin", &scopes(&["read"])), "user");
+        assert_eq!(key_role("user", &scopes(&["admin"])), "user");
+        assert_eq!(key_role("solver", &scopes(&["submit"])), "solver");
+        assert_eq!(key_role("operator", &scopes(&["admin"])), "operator");
+        assert_eq!(key_role("operator", &scopes(&["read"])), "user");
+    }
+}
@@ -0,0 +1,92 @@
+//! Audit trail of privileged API actions
+//!
+//! Every authenticated call that changes something sensitive (cancelling
+//! an intent, operator and admin actions, API key management, solver
+//! status changes) is written to the append-only `audit_log` table with
+//! who made it, from where, a hash of what they sent and how it ended.
+//! Bodies are hashed rather than stored, so the log can show what was sent
+//! without holding secrets or personal data. A trigger rejects updates and
+//! deletes on the table.
+
+use axum::http::{Method, StatusCode};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// A privileged call about to be written to the log
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub actor: String,
+    pub actor_role: String,
+    pub api_key_id: Option<Uuid>,
+    pub ip: Option<String>,
+    pub action: &'static str,
+    pub method: String,
+    pub path: String,
+    pub payload_hash: Option<String>,
+    pub status_code: u16,
+    pub outcome: &'static str,
+}
+
+/// What a call does, if it is one that gets audited. Paths are matched
+/// with or without the `/api/v1` prefix.
+pub fn action(method: &Method, path: &str) -> Option<&'static str> {
+    if !matches!(*method, Method::POST | Method::PUT | Method::PATCH | Method::DELETE) {
+        return None;
+    }
+    let path = path.strip_prefix("/api/v1").unwrap_or(path);
+    let segments: Vec<&str> = path.trim_end_matches('/').split('/').skip(1).collect();
+
+    match segments.as_slice() {
+        ["admin", ..] => Some("admin"),
+        ["api-keys", ..] => Some("api_key"),
+        ["intents", _, "cancel"] | [_, "cancel"] => Some("intent.cancel"),
+        ["solver", _, "status" | "deactivate"] | [_, "status" | "deactivate"] => Some("solver.status"),
+        _ => None,
+    }
+}
+
+/// SHA-256 of the request body; `None` for calls without one
+pub fn payload_hash(body: &[u8]) -> Option<String> {
+    (!body.is_empty()).then(|| hex::encode(Sha256::digest(body)))
+}
+
+pub fn outcome(status: StatusCode) -> &'static str {
+    if status.is_success() || status.is_redirection() {
+        "success"
+    } else if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        "denied"
+    } else {
+        "failure"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_privileged_actions() {
+        assert_eq!(action(&Method::POST, "/api/v1/intents/0xabc/cancel"), Some("intent.cancel"));
+        assert_eq!(action(&Method::POST, "/0xabc/cancel"), Some("intent.cancel"));
+        assert_eq!(action(&Method::POST, "/api/v1/admin/intake/pause"), Some("admin"));
+        assert_eq!(action(&Method::DELETE, "/api-keys/7f0c"), Some("api_key"));
+        assert_eq!(action(&Method::PUT, "/api/v1/solver/0xdef/status"), Some("solver.status"));
+
+        assert_eq!(action(&Method::GET, "/api/v1/admin/status"), None);
+        assert_eq!(action(&Method::POST, "/api/v1/intents"), None);
+        assert_eq!(action(&Method::POST, "/api/v1/intents/quote"), None);
+    }
+
+    #[test]
+    fn test_payload_hash_and_outcome() {
+        assert_eq!(payload_hash(b""), None);
+        assert_eq!(
+            payload_hash(b"{}").unwrap(),
+            "44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+        );
+
+        assert_eq!(outcome(StatusCode::NO_CONTENT), "success");
+        assert_eq!(outcome(StatusCode::FORBIDDEN), "denied");
+        assert_eq!(outcome(StatusCode::UNPROCESSABLE_ENTITY), "failure");
+    }
+}
@@ -180,11 +180,23 @@ pub fn validate_auth_challenge(
     Ok(())
 }
 
+// Admin endpoints operators may use; key and webhook management stay admin-only
+const OPERATOR_ENDPOINTS: &[&str] = &[
+    "/admin/status",
+    "/admin/intake/",
+    "/admin/queues/",
+    "/admin/intents/",
+    "/admin/rate-limits/",
+    "/admin/dead-letters",
+    "/admin/cache/",
+];
+
 // Role management
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum UserRole {
     User,
     Solver,
+    Operator,
     Admin,
 }
 
@@ -200,6 +212,7 @@ impl UserRole {
         match self {
             UserRole::User => "user",
             UserRole::Solver => "solver",
+            UserRole::Operator => "operator",
             UserRole::Admin => "admin",
         }
     }
@@ -208,6 +221,7 @@ impl UserRole {
         match s {
             "user" => Ok(UserRole::User),
             "solver" => Ok(UserRole::Solver),
+            "operator" => Ok(UserRole::Operator),
             "admin" => Ok(UserRole::Admin),
             _ => Err(crate::error::validation_error("Invalid role")),
         }
@@ -218,9 +232,19 @@ impl UserRole {
             UserRole::Admin => true, // Admin can access everything
             UserRole::Solver => {
                 // Solvers can access solver-specific endpoints
-                endpoint.contains("/solver/") || 
-                endpoint.contains("/intents") ||
-                method == "GET" // Solvers can read most data
+                !endpoint.contains("/admin/") && (
+                    endpoint.contains("/solver/") || 
+                    endpoint.contains("/intents") ||
+                    method == "GET" // Solvers can read most data
+                )
+            }
+            UserRole::Operator => {
+                // Operators run the engine but don't manage credentials
+                if endpoint.contains("/admin/") {
+                    OPERATOR_ENDPOINTS.iter().any(|prefix| endpoint.contains(prefix))
+                } else {
+                    !endpoint.contains("/solver/admin")
+                }
             }
             UserRole::User => {
                 // Users can access user-specific endpoints
@@ -348,4 +372,17 @@ mod tests {
         let is_valid = verify_ethereum_signature(message, &signature_hex, address).unwrap();
         assert!(is_valid);
     }
+    
+    #[test]
+    fn test_admin_endpoint_roles() {
+        let drain = "/api/v1/admin/queues/drain";
+        let keys = "/api/v1/admin/api-keys";
+        
+        assert!(UserRole::Admin.can_access_endpoint(keys, "POST"));
+        assert!(UserRole::Operator.can_access_endpoint(drain, "POST"));
+        assert!(UserRole::Operator.can_access_endpoint("/api/v1/admin/dead-letters", "GET"));
+        assert!(!UserRole::Operator.can_access_endpoint(keys, "POST"));
+        assert!(!UserRole::Solver.can_access_endpoint("/api/v1/admin/status", "GET"));
+        assert!(!UserRole::User.can_access_endpoint(drain, "POST"));
+    }
 }
\ No newline at end of file
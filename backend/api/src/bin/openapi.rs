@@ -0,0 +1,15 @@
+//! Writes the OpenAPI document the typed client is generated from
+//!
+//! Usage: cargo run -p intents-api --bin openapi [output path]
+
+use std::path::PathBuf;
+
+fn main() -> std::io::Result<()> {
+    let path = std::env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../api-client/openapi.json")
+    });
+
+    std::fs::write(&path, intents_api::openapi::spec_json() + "\n")?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
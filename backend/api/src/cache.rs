@@ -40,8 +40,10 @@ impl CacheKeys {
         format!("price:{}:{:#x}", chain_id, token)
     }
     
-    pub fn rate_limit(identifier: &str) -> String {
-        format!("rate_limit:{}", identifier)
+    /// One window's count; the braces keep a caller's windows in one
+    /// cluster slot so the limit script can read both
+    pub fn rate_limit(identifier: &str, window: u64) -> String {
+        format!("rate_limit:{{{}}}:{}", identifier, window)
     }
     
     pub fn analytics_cache() -> String {
@@ -55,6 +57,37 @@ impl CacheKeys {
     pub fn pending_intents() -> String {
         "intents:pending".to_string()
     }
+
+    pub fn solver_verification(address: Address) -> String {
+        format!("solver:verification:{:#x}", address)
+    }
+
+    pub fn idempotency(caller: &str, key: &str) -> String {
+        format!("idempotency:{}:{}", caller, key)
+    }
+
+    /// Seq counter of one WebSocket channel, shared by every instance
+    pub fn ws_seq(channel: &str) -> String {
+        format!("ws:seq:{}", channel)
+    }
+
+    /// Pub/sub channel carrying one shard of WebSocket traffic
+    pub fn ws_shard(shard: u32) -> String {
+        format!("ws:shard:{}", shard)
+    }
+
+    /// Held by the one instance producing market data tickers
+    pub fn market_data_lease() -> String {
+        "market_data:producer".to_string()
+    }
+
+    pub fn http_cache_generation() -> String {
+        "http_cache:generation".to_string()
+    }
+
+    pub fn http_response(generation: u64, entry_id: &str) -> String {
+        format!("http_cache:{}:{}", generation, entry_id)
+    }
 }
 
 // Cache service
@@ -105,6 +138,24 @@ impl CacheService {
         }
     }
 
+    /// Set `key` only if it is unset, returning whether it was set
+    pub async fn set_nx<T: Serialize>(&mut self, key: &str, value: &T, ttl: Duration) -> Result<bool> {
+        let serialized = serde_json::to_string(value)
+            .map_err(|e| crate::error::internal_error(format!("Failed to serialize value: {}", e)))?;
+        
+        let set: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(serialized)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl.as_secs())
+            .query_async(&mut self.connection)
+            .await
+            .map_err(|e| crate::error::ApiError::Redis(e.to_string()))?;
+        
+        Ok(set.is_some())
+    }
+
     pub async fn delete(&mut self, key: &str) -> Result<()> {
         self.connection.del(key)
             .await
@@ -248,61 +299,6 @@ impl CacheService {
         self.get(&key).await
     }
 
-    // Rate limiting
-    pub async fn check_rate_limit(
-        &mut self,
-        identifier: &str,
-        limit: u32,
-        window: Duration,
-    ) -> Result<RateLimitInfo> {
-        let key = CacheKeys::rate_limit(identifier);
-        
-        // Get current count
-        let current_count: Option<u32> = self.get(&key).await?;
-        let now = chrono::Utc::now();
-        
-        match current_count {
-            Some(count) if count >= limit => {
-                // Rate limit exceeded
-                let ttl: i64 = self.connection.ttl(&key)
-                    .await
-                    .map_err(|e| crate::error::ApiError::Redis(e.to_string()))?;
-                
-                Ok(RateLimitInfo {
-                    requests_remaining: 0,
-                    reset_time: now + chrono::Duration::seconds(ttl),
-                    window_size: limit,
-                })
-            }
-            Some(count) => {
-                // Increment counter
-                let new_count: u32 = self.connection.incr(&key, 1)
-                    .await
-                    .map_err(|e| crate::error::ApiError::Redis(e.to_string()))?;
-                
-                let ttl: i64 = self.connection.ttl(&key)
-                    .await
-                    .map_err(|e| crate::error::ApiError::Redis(e.to_string()))?;
-                
-                Ok(RateLimitInfo {
-                    requests_remaining: limit.saturating_sub(new_count),
-                    reset_time: now + chrono::Duration::seconds(ttl),
-                    window_size: limit,
-                })
-            }
-            None => {
-                // First request in window
-                self.set(&key, &1u32, Some(window)).await?;
-                
-                Ok(RateLimitInfo {
-                    requests_remaining: limit - 1,
-                    reset_time: now + chrono::Duration::from_std(window).unwrap(),
-                    window_size: limit,
-                })
-            }
-        }
-    }
-
     // Pending intents cache
     pub async fn cache_pending_intents(
         &mut self,
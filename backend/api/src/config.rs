@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
 use tokio::fs;
 use eyre::Result;
@@ -8,17 +9,287 @@ use ethers::types::Address;
 pub struct Config {
     pub server_address: String,
     pub database_url: String,
+    /// Indexer database, for pool analytics; defaults to `database_url`
+    #[serde(default)]
+    pub indexer_database_url: Option<String>,
     pub redis_url: String,
     pub jwt_secret: String,
     pub rate_limit: RateLimitConfig,
     pub chains: Vec<ChainConfig>,
     pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub engine_store: EngineStoreConfig,
+    /// Where the intents engine checkpoints queued work on shutdown
+    #[serde(default = "default_engine_checkpoint_path")]
+    pub engine_checkpoint_path: String,
+    /// Seconds to let in-flight executions finish before checkpointing
+    #[serde(default = "default_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64,
+    #[serde(default)]
+    pub quoting: QuoteConfig,
+    #[serde(default)]
+    pub webhooks: WebhookConfig,
+    #[serde(default)]
+    pub api_keys: ApiKeyConfig,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    /// Where the solver gRPC gateway listens; off when unset
+    #[serde(default)]
+    pub grpc_address: Option<String>,
+    #[serde(default)]
+    pub websocket: WebSocketConfig,
+    #[serde(default)]
+    pub market_data: MarketDataConfig,
+    #[serde(default)]
+    pub database: DatabaseConfig,
+}
+
+fn default_engine_checkpoint_path() -> String {
+    "engine-checkpoint.json".to_string()
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+    30
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimitConfig {
+    /// Per signed-in user; API keys use their tier's limit instead
     pub requests_per_minute: u64,
     pub burst_size: u64,
+    /// Per IP for unauthenticated requests; `requests_per_minute` when unset
+    #[serde(default)]
+    pub anonymous_requests_per_minute: Option<u64>,
+}
+
+impl RateLimitConfig {
+    pub fn anonymous_limit(&self) -> u64 {
+        self.anonymous_requests_per_minute.unwrap_or(self.requests_per_minute)
+    }
+}
+
+/// Fan-out of draft intents to solver quote endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuoteConfig {
+    /// How long to wait for each solver before quoting without it
+    pub solver_timeout_ms: u64,
+    /// Upper bound on how long any returned quote stays valid
+    pub quote_ttl_secs: u64,
+    /// Solvers asked per request, best reputation first
+    pub max_solvers: usize,
+}
+
+impl Default for QuoteConfig {
+    fn default() -> Self {
+        Self {
+            solver_timeout_ms: 1_500,
+            quote_ttl_secs: 30,
+            max_solvers: 20,
+        }
+    }
+}
+
+/// Delivery of webhook events to subscriber endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    /// Attempts before a delivery is given up on
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubled for each one after
+    pub base_backoff_secs: u64,
+    pub max_backoff_secs: u64,
+    pub timeout_ms: u64,
+    pub poll_interval_ms: u64,
+    /// Deliveries sent per poll
+    pub batch_size: u32,
+    /// Accept plain-HTTP endpoints, for local development only
+    pub allow_http: bool,
+    /// Subscriptions per user
+    pub max_subscriptions: u32,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 8,
+            base_backoff_secs: 10,
+            max_backoff_secs: 3_600,
+            timeout_ms: 5_000,
+            poll_interval_ms: 1_000,
+            batch_size: 50,
+            allow_http: false,
+            max_subscriptions: 10,
+        }
+    }
+}
+
+/// Where the intents engine keeps intent state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngineStoreConfig {
+    /// Keep intents in Postgres so pending ones survive a restart; otherwise
+    /// they are held in memory and lost with the process
+    pub durable: bool,
+    /// Engine database; defaults to `database_url`
+    pub database_url: Option<String>,
+}
+
+impl Default for EngineStoreConfig {
+    fn default() -> Self {
+        Self {
+            durable: true,
+            database_url: None,
+        }
+    }
+}
+
+/// Postgres pool size and query instrumentation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    pub max_connections: u32,
+    /// Queries taking longer are logged; 0 turns the log off
+    pub slow_query_ms: u64,
+    /// How often pool utilization gauges are sampled
+    pub pool_metrics_interval_secs: u64,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 20,
+            slow_query_ms: 500,
+            pool_metrics_interval_secs: 15,
+        }
+    }
+}
+
+/// Fan-out of WebSocket messages between API instances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebSocketConfig {
+    /// Relay messages through Redis so subscribers on every instance get
+    /// them; only a single instance can run without it
+    pub backplane_enabled: bool,
+    /// Redis channels messages are spread over, each read on its own
+    /// connection; a WebSocket channel always maps to the same one
+    pub backplane_shards: u32,
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        Self {
+            backplane_enabled: true,
+            backplane_shards: 4,
+        }
+    }
+}
+
+/// Tickers pushed to the `market_data` WebSocket channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarketDataConfig {
+    pub enabled: bool,
+    /// How often new swaps are read and tickers sent
+    pub interval_ms: u64,
+    /// Indexer rows read per query
+    pub batch_size: u64,
+}
+
+impl Default for MarketDataConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_ms: 5_000,
+            batch_size: 5_000,
+        }
+    }
+}
+
+/// Email and Telegram notifications to users
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationConfig {
+    /// Email is unavailable unless a relay is configured
+    pub smtp: Option<SmtpConfig>,
+    /// Telegram is unavailable without a bot token
+    pub telegram_bot_token: Option<String>,
+    /// Attempts before a notification is given up on
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubled for each one after
+    pub base_backoff_secs: u64,
+    pub max_backoff_secs: u64,
+    pub timeout_ms: u64,
+    pub poll_interval_ms: u64,
+    /// Notifications sent per poll
+    pub batch_size: u32,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            smtp: None,
+            telegram_bot_token: None,
+            max_attempts: 5,
+            base_backoff_secs: 30,
+            max_backoff_secs: 3_600,
+            timeout_ms: 10_000,
+            poll_interval_ms: 2_000,
+            batch_size: 50,
+        }
+    }
+}
+
+/// SMTP relay, reached over STARTTLS
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Sender, e.g. "Orbital Intents <notifications@example.com>"
+    pub from: String,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+/// Keys issued to programmatic clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiKeyConfig {
+    /// Requests per minute allowed by each rate-limit tier
+    pub tiers: HashMap<String, u32>,
+    /// Tier given to keys unless an admin picks another
+    pub default_tier: String,
+    /// Live keys per user
+    pub max_keys_per_user: u32,
+    /// Longest grace period a rotated key can keep working for
+    pub max_grace_period_secs: u64,
+}
+
+impl ApiKeyConfig {
+    pub fn requests_per_minute(&self, tier: &str) -> Option<u32> {
+        self.tiers.get(tier).copied()
+    }
+}
+
+impl Default for ApiKeyConfig {
+    fn default() -> Self {
+        Self {
+            tiers: HashMap::from([
+                ("standard".to_string(), 300),
+                ("pro".to_string(), 1_200),
+                ("enterprise".to_string(), 6_000),
+            ]),
+            default_tier: "standard".to_string(),
+            max_keys_per_user: 10,
+            max_grace_period_secs: 7 * 24 * 3_600,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +302,9 @@ pub struct ChainConfig {
     pub bridge_contract: Address,
     pub confirmation_blocks: u64,
     pub gas_price_multiplier: f64,
+    /// Orbital pool the internal quoter prices against on this chain
+    #[serde(default)]
+    pub orbital_pool_id: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,11 +319,13 @@ impl Default for Config {
         Self {
             server_address: "0.0.0.0:8080".to_string(),
             database_url: "postgresql://localhost/intents".to_string(),
+            indexer_database_url: None,
             redis_url: "redis://localhost:6379".to_string(),
             jwt_secret: "your-secret-key".to_string(),
             rate_limit: RateLimitConfig {
                 requests_per_minute: 100,
                 burst_size: 20,
+                anonymous_requests_per_minute: None,
             },
             chains: vec![
                 ChainConfig {
@@ -61,6 +337,7 @@ impl Default for Config {
                     bridge_contract: "0x0000000000000000000000000000000000000000".parse().unwrap(),
                     confirmation_blocks: 3,
                     gas_price_multiplier: 1.2,
+                    orbital_pool_id: None,
                 },
             ],
             metrics: MetricsConfig {
@@ -68,6 +345,17 @@ impl Default for Config {
                 endpoint: "/metrics".to_string(),
                 update_interval_secs: 15,
             },
+            engine_store: EngineStoreConfig::default(),
+            engine_checkpoint_path: default_engine_checkpoint_path(),
+            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            quoting: QuoteConfig::default(),
+            webhooks: WebhookConfig::default(),
+            api_keys: ApiKeyConfig::default(),
+            notifications: NotificationConfig::default(),
+            grpc_address: None,
+            websocket: WebSocketConfig::default(),
+            market_data: MarketDataConfig::default(),
+            database: DatabaseConfig::default(),
         }
     }
 }
@@ -86,10 +374,18 @@ impl Config {
             config.server_address = addr;
         }
 
+        if let Ok(addr) = env::var("GRPC_ADDRESS") {
+            config.grpc_address = Some(addr);
+        }
+
         if let Ok(db_url) = env::var("DATABASE_URL") {
             config.database_url = db_url;
         }
 
+        if let Ok(indexer_db_url) = env::var("INDEXER_DATABASE_URL") {
+            config.indexer_database_url = Some(indexer_db_url);
+        }
+
         if let Ok(redis_url) = env::var("REDIS_URL") {
             config.redis_url = redis_url;
         }
@@ -107,6 +403,10 @@ impl Config {
             config.rate_limit.burst_size = burst.parse().unwrap_or(20);
         }
 
+        if let Ok(rpm) = env::var("RATE_LIMIT_ANONYMOUS_RPM") {
+            config.rate_limit.anonymous_requests_per_minute = rpm.parse().ok();
+        }
+
         // Chain configurations from env
         if let Ok(holesky_rpc) = env::var("HOLESKY_RPC_URL") {
             if let Some(chain) = config.chains.iter_mut().find(|c| c.chain_id == 17000) {
@@ -130,6 +430,70 @@ impl Config {
             }
         }
 
+        if let Ok(durable) = env::var("ENGINE_STORE_DURABLE") {
+            config.engine_store.durable = durable.parse().unwrap_or(true);
+        }
+
+        if let Ok(url) = env::var("ENGINE_DATABASE_URL") {
+            config.engine_store.database_url = Some(url);
+        }
+
+        if let Ok(path) = env::var("ENGINE_CHECKPOINT_PATH") {
+            config.engine_checkpoint_path = path;
+        }
+
+        if let Ok(timeout) = env::var("SHUTDOWN_TIMEOUT_SECS") {
+            config.shutdown_timeout_secs = timeout.parse().unwrap_or(30);
+        }
+
+        if let Ok(pool_id) = env::var("ORBITAL_POOL_ID") {
+            if let Some(chain) = config.chains.iter_mut().find(|c| c.chain_id == 17000) {
+                chain.orbital_pool_id = pool_id.parse().ok();
+            }
+        }
+
+        if let Ok(timeout) = env::var("QUOTE_SOLVER_TIMEOUT_MS") {
+            config.quoting.solver_timeout_ms = timeout.parse().unwrap_or(1_500);
+        }
+
+        if let (Ok(host), Ok(from)) = (env::var("SMTP_HOST"), env::var("SMTP_FROM")) {
+            config.notifications.smtp = Some(SmtpConfig {
+                host,
+                port: env::var("SMTP_PORT").ok().and_then(|port| port.parse().ok()).unwrap_or_else(default_smtp_port),
+                username: env::var("SMTP_USERNAME").ok(),
+                password: env::var("SMTP_PASSWORD").ok(),
+                from,
+            });
+        }
+
+        if let Ok(token) = env::var("TELEGRAM_BOT_TOKEN") {
+            config.notifications.telegram_bot_token = Some(token);
+        }
+
+        if let Ok(enabled) = env::var("WS_BACKPLANE_ENABLED") {
+            config.websocket.backplane_enabled = enabled.parse().unwrap_or(true);
+        }
+
+        if let Ok(shards) = env::var("WS_BACKPLANE_SHARDS") {
+            config.websocket.backplane_shards = shards.parse().unwrap_or(4);
+        }
+
+        if let Ok(enabled) = env::var("MARKET_DATA_ENABLED") {
+            config.market_data.enabled = enabled.parse().unwrap_or(true);
+        }
+
+        if let Ok(interval) = env::var("MARKET_DATA_INTERVAL_MS") {
+            config.market_data.interval_ms = interval.parse().unwrap_or(5_000);
+        }
+
+        if let Ok(max_connections) = env::var("DATABASE_MAX_CONNECTIONS") {
+            config.database.max_connections = max_connections.parse().unwrap_or(20);
+        }
+
+        if let Ok(threshold) = env::var("DATABASE_SLOW_QUERY_MS") {
+            config.database.slow_query_ms = threshold.parse().unwrap_or(500);
+        }
+
         Ok(config)
     }
 
@@ -188,6 +188,28 @@ pub fn create_solver_registration_message(
     message.as_bytes().to_vec()
 }
 
+/// Challenge a solver signs to prove it controls its on-chain address
+pub fn create_solver_verification_message(
+    solver_address: Address,
+    nonce: U256,
+    issued_at: u64,
+) -> Vec<u8> {
+    let message = format!(
+        "Orbital Intents Solver Verification\n\
+         Solver Address: {:#x}\n\
+         Nonce: {}\n\
+         Issued At: {}\n\
+         \n\
+         By signing this message, I confirm that I control the solver address\n\
+         and the quote endpoint registered for it.",
+        solver_address,
+        nonce,
+        issued_at
+    );
+
+    message.as_bytes().to_vec()
+}
+
 /// Verify typed data signature (EIP-712)
 pub fn verify_typed_data_signature<T: Encode>(
     domain: &Eip712Domain,
@@ -427,6 +449,22 @@ mod tests {
         assert!(message_str.contains("30 bps"));
     }
     
+    #[tokio::test]
+    async fn test_solver_verification_message() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let message = create_solver_verification_message(wallet.address(), U256::from(42), 1_700_000_000);
+
+        let message_str = String::from_utf8(message.clone()).unwrap();
+        assert!(message_str.contains(&format!("{:#x}", wallet.address())));
+        assert!(message_str.contains("Nonce: 42"));
+
+        let signature = wallet.sign_message(&message).await.unwrap().to_string();
+        assert!(verify_signature(&message, &signature, wallet.address()).unwrap());
+        // A different nonce is a different challenge
+        let other = create_solver_verification_message(wallet.address(), U256::from(43), 1_700_000_000);
+        assert!(!verify_signature(&other, &signature, wallet.address()).unwrap());
+    }
+
     #[tokio::test]
     async fn test_dual_signature_verification() {
         // Create a test wallet
@@ -1,22 +1,40 @@
-use sqlx::{PgPool, postgres::PgPoolOptions, migrate::MigrateDatabase, Postgres};
-use crate::{error::Result, models::*};
+use sqlx::{PgPool, postgres::{PgConnectOptions, PgPoolOptions}, migrate::MigrateDatabase, ConnectOptions, Postgres, QueryBuilder};
+use crate::{
+    config::DatabaseConfig,
+    db_metrics::Observe,
+    error::Result,
+    models::*,
+    pagination::{push_page, Cursor, SortOrder},
+};
 use ethers::types::{Address, U256, H256};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use std::str::FromStr;
+use futures_util::stream::{BoxStream, StreamExt};
 
 // Database connection
-pub async fn create_pool(database_url: &str) -> Result<PgPool> {
+pub async fn create_pool(database_url: &str, config: &DatabaseConfig) -> Result<PgPool> {
     // Create database if it doesn't exist
     if !Postgres::database_exists(database_url).await.unwrap_or(false) {
         Postgres::create_database(database_url).await
             .map_err(|e| crate::error::internal_error(format!("Failed to create database: {}", e)))?;
     }
 
+    // sqlx logs the SQL of slow statements, including ones made outside
+    // this module; those made here are also timed by statement name
+    let slow_query = std::time::Duration::from_millis(config.slow_query_ms);
+    crate::db_metrics::set_slow_query_threshold(slow_query);
+    let mut options = PgConnectOptions::from_str(database_url)
+        .map_err(|e| crate::error::internal_error(format!("Invalid database URL: {}", e)))?
+        .log_statements(log::LevelFilter::Debug);
+    if config.slow_query_ms > 0 {
+        options = options.log_slow_statements(log::LevelFilter::Warn, slow_query);
+    }
+
     let pool = PgPoolOptions::new()
-        .max_connections(20)
+        .max_connections(config.max_connections)
         .acquire_timeout(std::time::Duration::from_secs(30))
-        .connect(database_url)
+        .connect_with(options)
         .await
         .map_err(|e| crate::error::internal_error(format!("Failed to connect to database: {}", e)))?;
 
@@ -103,6 +121,219 @@ async fn create_tables(pool: &PgPool) -> Result<()> {
     .await
     .map_err(|e| crate::error::internal_error(format!("Failed to create intent_executions table: {}", e)))?;
 
+    sqlx::query("ALTER TABLE solvers ADD COLUMN IF NOT EXISTS quote_url TEXT")
+        .execute(pool)
+        .await
+        .map_err(|e| crate::error::internal_error(format!("Failed to add solvers.quote_url: {}", e)))?;
+
+    // Solver profiles. Solvers registered before verification existed
+    // signed the registration message, which counts as verified.
+    sqlx::query(r#"
+        ALTER TABLE solvers
+            ADD COLUMN IF NOT EXISTS supported_tokens TEXT[] NOT NULL DEFAULT '{}',
+            ADD COLUMN IF NOT EXISTS name TEXT,
+            ADD COLUMN IF NOT EXISTS website TEXT,
+            ADD COLUMN IF NOT EXISTS paused BOOLEAN NOT NULL DEFAULT false,
+            ADD COLUMN IF NOT EXISTS verified_at TIMESTAMPTZ DEFAULT NOW()
+    "#)
+    .execute(pool)
+    .await
+    .map_err(|e| crate::error::internal_error(format!("Failed to add solver profile columns: {}", e)))?;
+
+    sqlx::query("ALTER TABLE intents ADD COLUMN IF NOT EXISTS quote_id UUID")
+        .execute(pool)
+        .await
+        .map_err(|e| crate::error::internal_error(format!("Failed to add intents.quote_id: {}", e)))?;
+
+    // Quote requests and the quotes each one received, kept to measure how
+    // executions compare with what was quoted
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS quote_requests (
+            id UUID PRIMARY KEY,
+            source_chain_id BIGINT NOT NULL,
+            dest_chain_id BIGINT NOT NULL,
+            source_token VARCHAR(42) NOT NULL,
+            dest_token VARCHAR(42) NOT NULL,
+            source_amount TEXT NOT NULL,
+            min_dest_amount TEXT,
+            user_address VARCHAR(42),
+            solvers_asked INTEGER NOT NULL,
+            quotes_received INTEGER NOT NULL,
+            best_dest_amount TEXT,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+        )
+    "#)
+    .execute(pool)
+    .await
+    .map_err(|e| crate::error::internal_error(format!("Failed to create quote_requests table: {}", e)))?;
+
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS quotes (
+            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+            quote_id UUID NOT NULL REFERENCES quote_requests(id) ON DELETE CASCADE,
+            source VARCHAR(20) NOT NULL,
+            solver_address VARCHAR(42),
+            dest_amount TEXT NOT NULL,
+            estimated_gas TEXT,
+            estimated_time_secs BIGINT,
+            expires_at TIMESTAMPTZ NOT NULL,
+            latency_ms BIGINT NOT NULL,
+            is_best BOOLEAN NOT NULL DEFAULT false
+        )
+    "#)
+    .execute(pool)
+    .await
+    .map_err(|e| crate::error::internal_error(format!("Failed to create quotes table: {}", e)))?;
+
+    // Webhook subscriptions and every delivery made to them
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS webhook_subscriptions (
+            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+            owner_address VARCHAR(42) NOT NULL,
+            url TEXT NOT NULL,
+            secret TEXT NOT NULL,
+            events TEXT[] NOT NULL,
+            all_subjects BOOLEAN NOT NULL DEFAULT false,
+            is_active BOOLEAN NOT NULL DEFAULT true,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+        )
+    "#)
+    .execute(pool)
+    .await
+    .map_err(|e| crate::error::internal_error(format!("Failed to create webhook_subscriptions table: {}", e)))?;
+
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS webhook_deliveries (
+            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+            subscription_id UUID NOT NULL REFERENCES webhook_subscriptions(id) ON DELETE CASCADE,
+            event_id UUID NOT NULL,
+            event_type VARCHAR(50) NOT NULL,
+            payload JSONB NOT NULL,
+            status VARCHAR(20) NOT NULL DEFAULT 'pending',
+            attempts INTEGER NOT NULL DEFAULT 0,
+            next_attempt_at TIMESTAMPTZ DEFAULT NOW(),
+            last_status_code INTEGER,
+            last_error TEXT,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+            delivered_at TIMESTAMPTZ
+        )
+    "#)
+    .execute(pool)
+    .await
+    .map_err(|e| crate::error::internal_error(format!("Failed to create webhook_deliveries table: {}", e)))?;
+
+    // API keys are stored by hash; usage is metered per hour
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS api_keys (
+            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+            owner_address VARCHAR(42) NOT NULL,
+            owner_role VARCHAR(20) NOT NULL,
+            name VARCHAR(100) NOT NULL,
+            prefix VARCHAR(20) NOT NULL,
+            key_hash VARCHAR(64) UNIQUE NOT NULL,
+            scopes TEXT[] NOT NULL,
+            tier VARCHAR(50) NOT NULL,
+            expires_at TIMESTAMPTZ,
+            last_used_at TIMESTAMPTZ,
+            revoked_at TIMESTAMPTZ,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+        )
+    "#)
+    .execute(pool)
+    .await
+    .map_err(|e| crate::error::internal_error(format!("Failed to create api_keys table: {}", e)))?;
+
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS api_key_usage (
+            key_id UUID NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+            period_start TIMESTAMPTZ NOT NULL,
+            requests BIGINT NOT NULL DEFAULT 0,
+            errors BIGINT NOT NULL DEFAULT 0,
+            rate_limited BIGINT NOT NULL DEFAULT 0,
+            PRIMARY KEY (key_id, period_start)
+        )
+    "#)
+    .execute(pool)
+    .await
+    .map_err(|e| crate::error::internal_error(format!("Failed to create api_key_usage table: {}", e)))?;
+
+    // Where each user wants notifications, and the queue of those to send
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS notification_channels (
+            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+            owner_address VARCHAR(42) NOT NULL,
+            channel VARCHAR(20) NOT NULL,
+            target TEXT NOT NULL,
+            events TEXT[] NOT NULL,
+            is_active BOOLEAN NOT NULL DEFAULT true,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+            updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+            UNIQUE (owner_address, channel)
+        )
+    "#)
+    .execute(pool)
+    .await
+    .map_err(|e| crate::error::internal_error(format!("Failed to create notification_channels table: {}", e)))?;
+
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS notification_deliveries (
+            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+            channel_id UUID NOT NULL REFERENCES notification_channels(id) ON DELETE CASCADE,
+            event_type VARCHAR(50) NOT NULL,
+            payload JSONB NOT NULL,
+            status VARCHAR(20) NOT NULL DEFAULT 'pending',
+            attempts INTEGER NOT NULL DEFAULT 0,
+            next_attempt_at TIMESTAMPTZ DEFAULT NOW(),
+            last_error TEXT,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+            sent_at TIMESTAMPTZ
+        )
+    "#)
+    .execute(pool)
+    .await
+    .map_err(|e| crate::error::internal_error(format!("Failed to create notification_deliveries table: {}", e)))?;
+
+    // Privileged API actions, append-only
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS audit_log (
+            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+            occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+            actor VARCHAR(42) NOT NULL,
+            actor_role VARCHAR(20) NOT NULL,
+            api_key_id UUID,
+            ip TEXT,
+            action VARCHAR(50) NOT NULL,
+            method VARCHAR(10) NOT NULL,
+            path TEXT NOT NULL,
+            payload_hash VARCHAR(64),
+            status_code INTEGER NOT NULL,
+            outcome VARCHAR(20) NOT NULL
+        )
+    "#)
+    .execute(pool)
+    .await
+    .map_err(|e| crate::error::internal_error(format!("Failed to create audit_log table: {}", e)))?;
+
+    sqlx::query(r#"
+        CREATE OR REPLACE FUNCTION audit_log_append_only() RETURNS trigger AS $$
+        BEGIN
+            RAISE EXCEPTION 'audit_log is append-only';
+        END;
+        $$ LANGUAGE plpgsql
+    "#)
+    .execute(pool)
+    .await
+    .map_err(|e| crate::error::internal_error(format!("Failed to create audit_log trigger function: {}", e)))?;
+
+    sqlx::query(r#"
+        CREATE OR REPLACE TRIGGER audit_log_append_only
+            BEFORE UPDATE OR DELETE OR TRUNCATE ON audit_log
+            FOR EACH STATEMENT EXECUTE FUNCTION audit_log_append_only()
+    "#)
+    .execute(pool)
+    .await
+    .map_err(|e| crate::error::internal_error(format!("Failed to create audit_log trigger: {}", e)))?;
+
     // Create indexes
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_intents_user_address ON intents(user_address)")
         .execute(pool).await.ok();
@@ -110,10 +341,45 @@ async fn create_tables(pool: &PgPool) -> Result<()> {
         .execute(pool).await.ok();
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_intents_created_at ON intents(created_at)")
         .execute(pool).await.ok();
+    // Keyset pagination orders by (column, id)
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_intents_created_at_id ON intents(created_at, id)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_intents_updated_at_id ON intents(updated_at, id)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_intents_user_created_at_id ON intents(user_address, created_at, id)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_intents_chain_pair ON intents(source_chain_id, dest_chain_id)")
+        .execute(pool).await.ok();
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_solvers_address ON solvers(address)")
         .execute(pool).await.ok();
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_solvers_is_active ON solvers(is_active)")
         .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_solvers_reputation_id ON solvers(reputation_score, id)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_solvers_registered_at_id ON solvers(registered_at, id)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_intents_quote_id ON intents(quote_id)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_quotes_quote_id ON quotes(quote_id)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_quote_requests_created_at ON quote_requests(created_at)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_webhook_subscriptions_owner ON webhook_subscriptions(owner_address)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries(next_attempt_at) WHERE status = 'pending'")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_subscription ON webhook_deliveries(subscription_id, created_at)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_api_keys_owner ON api_keys(owner_address)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_notification_deliveries_due ON notification_deliveries(next_attempt_at) WHERE status = 'pending'")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_notification_deliveries_channel ON notification_deliveries(channel_id, created_at)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_audit_log_occurred_at_id ON audit_log(occurred_at, id)")
+        .execute(pool).await.ok();
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_audit_log_actor ON audit_log(actor, occurred_at)")
+        .execute(pool).await.ok();
 
     Ok(())
 }
@@ -130,8 +396,8 @@ impl IntentDb {
         let record = sqlx::query_as::<_, IntentRecord>(r#"
             INSERT INTO intents (
                 intent_id, source_chain_id, dest_chain_id, source_token, dest_token,
-                source_amount, min_dest_amount, deadline, user_address, status
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                source_amount, min_dest_amount, deadline, user_address, status, quote_id
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             RETURNING *
         "#)
         .bind(format!("{:#x}", intent_id))
@@ -144,7 +410,9 @@ impl IntentDb {
         .bind(request.deadline)
         .bind(format!("{:#x}", request.user_address))
         .bind("pending")
+        .bind(request.quote_id)
         .fetch_one(pool)
+        .observe("intents.insert_intent")
         .await?;
 
         Ok(record)
@@ -159,39 +427,77 @@ impl IntentDb {
         )
         .bind(format!("{:#x}", intent_id))
         .fetch_optional(pool)
+        .observe("intents.get_intent_by_id")
         .await?;
 
         Ok(record)
     }
 
-    pub async fn get_intents_by_user(
+    /// One page of intents matching `filter`, plus one extra row when more
+    /// follow
+    pub async fn list_intents(
         pool: &PgPool,
-        user_address: Address,
-        pagination: &PaginationParams,
-    ) -> Result<(Vec<IntentRecord>, u64)> {
-        let offset = (pagination.page.unwrap_or(1) - 1) * pagination.limit.unwrap_or(20);
-        let limit = pagination.limit.unwrap_or(20);
+        filter: &IntentFilter,
+        sort_column: &'static str,
+        order: SortOrder,
+        cursor: Option<Cursor>,
+        limit: u64,
+    ) -> Result<Vec<IntentRecord>> {
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM intents WHERE TRUE");
 
-        let records = sqlx::query_as::<_, IntentRecord>(r#"
-            SELECT * FROM intents 
-            WHERE user_address = $1 
-            ORDER BY created_at DESC 
-            LIMIT $2 OFFSET $3
-        "#)
-        .bind(format!("{:#x}", user_address))
-        .bind(limit as i64)
-        .bind(offset as i64)
-        .fetch_all(pool)
-        .await?;
+        let statuses = filter.statuses();
+        if !statuses.is_empty() {
+            query.push(" AND status = ANY(").push_bind(statuses).push(")");
+        }
+        if let Some(chain_id) = filter.source_chain_id {
+            query.push(" AND source_chain_id = ").push_bind(chain_id as i64);
+        }
+        if let Some(chain_id) = filter.dest_chain_id {
+            query.push(" AND dest_chain_id = ").push_bind(chain_id as i64);
+        }
+        if let Some(token) = filter.token {
+            let token = format!("{:#x}", token);
+            query.push(" AND (source_token = ").push_bind(token.clone())
+                .push(" OR dest_token = ").push_bind(token).push(")");
+        }
+        if let Some(user) = filter.user {
+            query.push(" AND user_address = ").push_bind(format!("{:#x}", user));
+        }
+        if let Some(after) = filter.created_after {
+            query.push(" AND created_at >= ").push_bind(after);
+        }
+        if let Some(before) = filter.created_before {
+            query.push(" AND created_at < ").push_bind(before);
+        }
+        if let Some(min_amount) = &filter.min_amount {
+            let min_amount = string_to_u256(min_amount)?;
+            query.push(" AND source_amount::numeric >= ").push_bind(min_amount.to_string()).push("::numeric");
+        }
 
-        let total: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM intents WHERE user_address = $1"
-        )
-        .bind(format!("{:#x}", user_address))
-        .fetch_one(pool)
-        .await?;
+        push_page(&mut query, sort_column, order, cursor, limit);
 
-        Ok((records, total.0 as u64))
+        let records = query.build_query_as::<IntentRecord>().fetch_all(pool).observe("intents.list_intents").await?;
+        Ok(records)
+    }
+
+    /// Intents created in [from, to), oldest first, optionally only one
+    /// user's. Rows are read as the stream is polled, for exports.
+    pub fn stream_created_between(
+        pool: &PgPool,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        user: Option<Address>,
+    ) -> BoxStream<'_, Result<IntentRecord>> {
+        sqlx::query_as::<_, IntentRecord>(
+            "SELECT * FROM intents WHERE created_at >= $1 AND created_at < $2 \
+             AND ($3::TEXT IS NULL OR user_address = $3) ORDER BY created_at, id"
+        )
+        .bind(from)
+        .bind(to)
+        .bind(user.map(|user| format!("{:#x}", user)))
+        .fetch(pool)
+        .map(|record| Ok(record?))
+        .boxed()
     }
 
     pub async fn update_intent_status(
@@ -226,6 +532,7 @@ impl IntentDb {
         .bind(fees_paid.map(|fees| fees.to_string()))
         .bind(error_message)
         .execute(pool)
+        .observe("intents.update_intent_status")
         .await?;
 
         Ok(())
@@ -243,6 +550,7 @@ impl IntentDb {
         "#)
         .bind(limit as i64)
         .fetch_all(pool)
+        .observe("intents.get_pending_intents")
         .await?;
 
         Ok(records)
@@ -258,18 +566,27 @@ impl SolverDb {
         request: &SolverRegistrationRequest,
     ) -> Result<SolverRecord> {
         let supported_chains: Vec<i64> = request.supported_chains.iter().map(|&c| c as i64).collect();
+        let supported_tokens: Vec<String> = request.supported_tokens.iter().map(|t| format!("{:#x}", t)).collect();
         
+        // The registration signature proves control of the address
         let record = sqlx::query_as::<_, SolverRecord>(r#"
             INSERT INTO solvers (
-                address, bond_amount, supported_chains, fee_rate
-            ) VALUES ($1, $2, $3, $4)
+                address, bond_amount, supported_chains, fee_rate, contact_info, quote_url,
+                supported_tokens, name, website, verified_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW())
             RETURNING *
         "#)
         .bind(format!("{:#x}", request.solver_address))
         .bind(request.bond_amount.to_string())
         .bind(&supported_chains)
         .bind(request.fee_rate)
+        .bind(&request.contact_info)
+        .bind(&request.quote_url)
+        .bind(&supported_tokens)
+        .bind(&request.name)
+        .bind(&request.website)
         .fetch_one(pool)
+        .observe("solvers.register_solver")
         .await?;
 
         Ok(record)
@@ -284,11 +601,48 @@ impl SolverDb {
         )
         .bind(format!("{:#x}", address))
         .fetch_optional(pool)
+        .observe("solvers.get_solver_by_address")
         .await?;
 
         Ok(record)
     }
 
+    /// One page of active, unslashed solvers matching `filter`, plus one
+    /// extra row when more follow
+    pub async fn list_solvers(
+        pool: &PgPool,
+        filter: &SolverFilter,
+        sort_column: &'static str,
+        order: SortOrder,
+        cursor: Option<Cursor>,
+        limit: u64,
+    ) -> Result<Vec<SolverRecord>> {
+        let mut query = QueryBuilder::<Postgres>::new(
+            "SELECT * FROM solvers WHERE is_active = true AND is_slashed = false AND paused = false"
+        );
+
+        if let Some(chain_id) = filter.chain_id {
+            query.push(" AND ").push_bind(chain_id as i64).push(" = ANY(supported_chains)");
+        }
+        if let Some(min_reputation) = filter.min_reputation {
+            query.push(" AND reputation_score >= ").push_bind(min_reputation);
+        }
+        if let Some(max_fee_rate) = filter.max_fee_rate {
+            query.push(" AND fee_rate <= ").push_bind(max_fee_rate);
+        }
+        if let Some(after) = filter.registered_after {
+            query.push(" AND registered_at >= ").push_bind(after);
+        }
+        if let Some(before) = filter.registered_before {
+            query.push(" AND registered_at < ").push_bind(before);
+        }
+
+        push_page(&mut query, sort_column, order, cursor, limit);
+
+        let records = query.build_query_as::<SolverRecord>().fetch_all(pool).observe("solvers.list_solvers").await?;
+        Ok(records)
+    }
+
     pub async fn get_active_solvers(
         pool: &PgPool,
         chain_id: Option<u64>,
@@ -296,7 +650,7 @@ impl SolverDb {
         let query = if let Some(chain_id) = chain_id {
             sqlx::query_as::<_, SolverRecord>(r#"
                 SELECT * FROM solvers 
-                WHERE is_active = true AND is_slashed = false 
+                WHERE is_active = true AND is_slashed = false AND paused = false
                 AND $1 = ANY(supported_chains)
                 ORDER BY reputation_score DESC
             "#)
@@ -304,12 +658,39 @@ impl SolverDb {
         } else {
             sqlx::query_as::<_, SolverRecord>(r#"
                 SELECT * FROM solvers 
-                WHERE is_active = true AND is_slashed = false 
+                WHERE is_active = true AND is_slashed = false AND paused = false
                 ORDER BY reputation_score DESC
             "#)
         };
 
-        let records = query.fetch_all(pool).await?;
+        let records = query.fetch_all(pool).observe("solvers.get_active_solvers").await?;
+        Ok(records)
+    }
+
+    /// Active, verified solvers that quote `token` on `chain_id`, best
+    /// reputation first
+    pub async fn get_quoting_solvers(
+        pool: &PgPool,
+        chain_id: u64,
+        token: Address,
+        limit: usize,
+    ) -> Result<Vec<SolverRecord>> {
+        let records = sqlx::query_as::<_, SolverRecord>(r#"
+            SELECT * FROM solvers
+            WHERE is_active = true AND is_slashed = false AND paused = false
+            AND quote_url IS NOT NULL AND verified_at IS NOT NULL
+            AND $1 = ANY(supported_chains)
+            AND (cardinality(supported_tokens) = 0 OR $2 = ANY(supported_tokens))
+            ORDER BY reputation_score DESC
+            LIMIT $3
+        "#)
+        .bind(chain_id as i64)
+        .bind(format!("{:#x}", token))
+        .bind(limit as i64)
+        .fetch_all(pool)
+        .observe("solvers.get_quoting_solvers")
+        .await?;
+
         Ok(records)
     }
 
@@ -336,11 +717,86 @@ impl SolverDb {
         .bind(total_volume.to_string())
         .bind(reputation_score)
         .execute(pool)
+        .observe("solvers.update_solver_reputation")
         .await?;
 
         Ok(())
     }
 
+    /// Apply a solver's own profile changes. A new quote URL clears
+    /// `verified_at` until the solver signs a fresh challenge.
+    pub async fn update_profile(
+        pool: &PgPool,
+        address: Address,
+        update: &SolverUpdateRequest,
+    ) -> Result<Option<SolverRecord>> {
+        let supported_chains: Option<Vec<i64>> = update
+            .supported_chains
+            .as_ref()
+            .map(|chains| chains.iter().map(|&c| c as i64).collect());
+        let supported_tokens: Option<Vec<String>> = update
+            .supported_tokens
+            .as_ref()
+            .map(|tokens| tokens.iter().map(|t| format!("{:#x}", t)).collect());
+
+        let record = sqlx::query_as::<_, SolverRecord>(r#"
+            UPDATE solvers SET
+                fee_rate = COALESCE($2, fee_rate),
+                contact_info = COALESCE($3, contact_info),
+                paused = COALESCE(NOT $4, paused),
+                verified_at = CASE
+                    WHEN $5::TEXT IS NOT NULL AND $5 IS DISTINCT FROM quote_url THEN NULL
+                    ELSE verified_at
+                END,
+                quote_url = COALESCE($5, quote_url),
+                supported_chains = COALESCE($6, supported_chains),
+                supported_tokens = COALESCE($7, supported_tokens),
+                name = COALESCE($8, name),
+                website = COALESCE($9, website)
+            WHERE address = $1
+            RETURNING *
+        "#)
+        .bind(format!("{:#x}", address))
+        .bind(update.fee_rate)
+        .bind(&update.contact_info)
+        .bind(update.is_active)
+        .bind(&update.quote_url)
+        .bind(&supported_chains)
+        .bind(&supported_tokens)
+        .bind(&update.name)
+        .bind(&update.website)
+        .fetch_optional(pool)
+        .observe("solvers.update_profile")
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn set_paused(pool: &PgPool, address: Address, paused: bool) -> Result<Option<SolverRecord>> {
+        let record = sqlx::query_as::<_, SolverRecord>(
+            "UPDATE solvers SET paused = $2 WHERE address = $1 RETURNING *"
+        )
+        .bind(format!("{:#x}", address))
+        .bind(paused)
+        .fetch_optional(pool)
+        .observe("solvers.set_paused")
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn mark_verified(pool: &PgPool, address: Address) -> Result<Option<SolverRecord>> {
+        let record = sqlx::query_as::<_, SolverRecord>(
+            "UPDATE solvers SET verified_at = NOW() WHERE address = $1 RETURNING *"
+        )
+        .bind(format!("{:#x}", address))
+        .fetch_optional(pool)
+        .observe("solvers.mark_verified")
+        .await?;
+
+        Ok(record)
+    }
+
     pub async fn slash_solver(
         pool: &PgPool,
         address: Address,
@@ -357,12 +813,695 @@ impl SolverDb {
         .bind(format!("{:#x}", address))
         .bind(reason)
         .execute(pool)
+        .observe("solvers.slash_solver")
         .await?;
 
         Ok(())
     }
 }
 
+// Quote analytics
+pub struct QuoteDb;
+
+impl QuoteDb {
+    pub async fn record_quotes(
+        pool: &PgPool,
+        request: &QuoteIntentRequest,
+        response: &QuoteResponse,
+    ) -> Result<()> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query(r#"
+            INSERT INTO quote_requests (
+                id, source_chain_id, dest_chain_id, source_token, dest_token, source_amount,
+                min_dest_amount, user_address, solvers_asked, quotes_received, best_dest_amount, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        "#)
+        .bind(response.quote_id)
+        .bind(request.source_chain_id as i64)
+        .bind(request.dest_chain_id as i64)
+        .bind(format!("{:#x}", request.source_token))
+        .bind(format!("{:#x}", request.dest_token))
+        .bind(request.source_amount.to_string())
+        .bind(request.min_dest_amount.map(|amount| amount.to_string()))
+        .bind(request.user_address.map(|addr| format!("{:#x}", addr)))
+        .bind(response.solvers_asked as i32)
+        .bind(response.quotes.len() as i32)
+        .bind(response.best.as_ref().map(|quote| quote.dest_amount.to_string()))
+        .bind(response.created_at)
+        .execute(&mut *tx)
+        .observe("quotes.record_request")
+        .await?;
+
+        for (rank, quote) in response.quotes.iter().enumerate() {
+            sqlx::query(r#"
+                INSERT INTO quotes (
+                    quote_id, source, solver_address, dest_amount, estimated_gas,
+                    estimated_time_secs, expires_at, latency_ms, is_best
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#)
+            .bind(response.quote_id)
+            .bind(&quote.source)
+            .bind(quote.solver_address.map(|addr| format!("{:#x}", addr)))
+            .bind(quote.dest_amount.to_string())
+            .bind(quote.estimated_gas.map(|gas| gas.to_string()))
+            .bind(quote.estimated_time_secs.map(|secs| secs as i64))
+            .bind(quote.expires_at)
+            .bind(quote.latency_ms as i64)
+            .bind(rank == 0)
+            .execute(&mut *tx)
+            .observe("quotes.record_quote")
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+// Webhook subscriptions and deliveries
+pub struct WebhookDb;
+
+impl WebhookDb {
+    pub async fn create_subscription(
+        pool: &PgPool,
+        owner: Address,
+        request: &CreateWebhookRequest,
+        secret: &str,
+    ) -> Result<WebhookRecord> {
+        let record = sqlx::query_as::<_, WebhookRecord>(r#"
+            INSERT INTO webhook_subscriptions (owner_address, url, secret, events, all_subjects)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+        "#)
+        .bind(format!("{:#x}", owner))
+        .bind(&request.url)
+        .bind(secret)
+        .bind(&request.events)
+        .bind(request.all_subjects)
+        .fetch_one(pool)
+        .observe("webhooks.create_subscription")
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn list_subscriptions(pool: &PgPool, owner: Address) -> Result<Vec<WebhookRecord>> {
+        let records = sqlx::query_as::<_, WebhookRecord>(
+            "SELECT * FROM webhook_subscriptions WHERE owner_address = $1 ORDER BY created_at DESC"
+        )
+        .bind(format!("{:#x}", owner))
+        .fetch_all(pool)
+        .observe("webhooks.list_subscriptions")
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn get_subscription(pool: &PgPool, id: Uuid) -> Result<Option<WebhookRecord>> {
+        let record = sqlx::query_as::<_, WebhookRecord>("SELECT * FROM webhook_subscriptions WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .observe("webhooks.get_subscription")
+            .await?;
+
+        Ok(record)
+    }
+
+    pub async fn delete_subscription(pool: &PgPool, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM webhook_subscriptions WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .observe("webhooks.delete_subscription")
+            .await?;
+
+        Ok(())
+    }
+
+    /// One pending delivery per active subscription that wants the event and
+    /// either belongs to `subject` or watches every subject
+    pub async fn enqueue_deliveries(
+        pool: &PgPool,
+        subject: Address,
+        payload: &crate::webhooks::WebhookPayload,
+    ) -> Result<u64> {
+        let result = sqlx::query(r#"
+            INSERT INTO webhook_deliveries (subscription_id, event_id, event_type, payload)
+            SELECT id, $1, $2, $3 FROM webhook_subscriptions
+            WHERE is_active = true
+            AND $2 = ANY(events)
+            AND (owner_address = $4 OR all_subjects = true)
+        "#)
+        .bind(payload.event_id)
+        .bind(&payload.event_type)
+        .bind(serde_json::to_value(payload)?)
+        .bind(format!("{:#x}", subject))
+        .execute(pool)
+        .observe("webhooks.enqueue_deliveries")
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Lease up to `limit` due deliveries; rows locked by another instance
+    /// are skipped
+    pub async fn claim_due_deliveries(
+        pool: &PgPool,
+        limit: u32,
+        lease: std::time::Duration,
+    ) -> Result<Vec<DueDelivery>> {
+        let records = sqlx::query_as::<_, DueDelivery>(r#"
+            WITH due AS (
+                SELECT id FROM webhook_deliveries
+                WHERE status = 'pending' AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE webhook_deliveries d
+            SET next_attempt_at = NOW() + make_interval(secs => $2)
+            FROM due, webhook_subscriptions s
+            WHERE d.id = due.id AND s.id = d.subscription_id
+            RETURNING d.id, d.event_type, d.payload, d.attempts, s.url, s.secret
+        "#)
+        .bind(limit as i64)
+        .bind(lease.as_secs_f64())
+        .fetch_all(pool)
+        .observe("webhooks.claim_due_deliveries")
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn mark_delivered(
+        pool: &PgPool,
+        id: Uuid,
+        attempts: u32,
+        status_code: Option<u16>,
+    ) -> Result<()> {
+        sqlx::query(r#"
+            UPDATE webhook_deliveries SET
+                status = 'delivered',
+                attempts = $2,
+                last_status_code = $3,
+                last_error = NULL,
+                next_attempt_at = NULL,
+                delivered_at = NOW()
+            WHERE id = $1
+        "#)
+        .bind(id)
+        .bind(attempts as i32)
+        .bind(status_code.map(i32::from))
+        .execute(pool)
+        .observe("webhooks.mark_delivered")
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn schedule_retry(
+        pool: &PgPool,
+        id: Uuid,
+        attempts: u32,
+        status_code: Option<u16>,
+        error: &str,
+        delay: std::time::Duration,
+    ) -> Result<()> {
+        sqlx::query(r#"
+            UPDATE webhook_deliveries SET
+                attempts = $2,
+                last_status_code = $3,
+                last_error = $4,
+                next_attempt_at = NOW() + make_interval(secs => $5)
+            WHERE id = $1
+        "#)
+        .bind(id)
+        .bind(attempts as i32)
+        .bind(status_code.map(i32::from))
+        .bind(error)
+        .bind(delay.as_secs_f64())
+        .execute(pool)
+        .observe("webhooks.schedule_retry")
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn mark_failed(
+        pool: &PgPool,
+        id: Uuid,
+        attempts: u32,
+        status_code: Option<u16>,
+        error: &str,
+    ) -> Result<()> {
+        sqlx::query(r#"
+            UPDATE webhook_deliveries SET
+                status = 'failed',
+                attempts = $2,
+                last_status_code = $3,
+                last_error = $4,
+                next_attempt_at = NULL
+            WHERE id = $1
+        "#)
+        .bind(id)
+        .bind(attempts as i32)
+        .bind(status_code.map(i32::from))
+        .bind(error)
+        .execute(pool)
+        .observe("webhooks.mark_failed")
+        .await?;
+
+        Ok(())
+    }
+
+    /// Most recent deliveries to a subscription, optionally of one status
+    pub async fn list_deliveries(
+        pool: &PgPool,
+        subscription_id: Uuid,
+        status: Option<&str>,
+        limit: u64,
+    ) -> Result<Vec<WebhookDeliveryRecord>> {
+        let records = sqlx::query_as::<_, WebhookDeliveryRecord>(r#"
+            SELECT * FROM webhook_deliveries
+            WHERE subscription_id = $1 AND ($2::TEXT IS NULL OR status = $2)
+            ORDER BY created_at DESC
+            LIMIT $3
+        "#)
+        .bind(subscription_id)
+        .bind(status)
+        .bind(limit as i64)
+        .fetch_all(pool)
+        .observe("webhooks.list_deliveries")
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Queue a failed delivery to be sent again now
+    pub async fn redeliver(pool: &PgPool, subscription_id: Uuid, delivery_id: Uuid) -> Result<bool> {
+        let result = sqlx::query(r#"
+            UPDATE webhook_deliveries SET status = 'pending', next_attempt_at = NOW(), attempts = 0
+            WHERE id = $1 AND subscription_id = $2 AND status = 'failed'
+        "#)
+        .bind(delivery_id)
+        .bind(subscription_id)
+        .execute(pool)
+        .observe("webhooks.redeliver")
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+// Notification channels and deliveries
+pub struct NotificationDb;
+
+impl NotificationDb {
+    /// Create or replace the owner's settings for one channel
+    pub async fn upsert_channel(
+        pool: &PgPool,
+        owner: Address,
+        channel: &str,
+        request: &UpdateNotificationChannelRequest,
+    ) -> Result<NotificationChannelRecord> {
+        let record = sqlx::query_as::<_, NotificationChannelRecord>(r#"
+            INSERT INTO notification_channels (owner_address, channel, target, events, is_active)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (owner_address, channel) DO UPDATE SET
+                target = EXCLUDED.target,
+                events = EXCLUDED.events,
+                is_active = EXCLUDED.is_active,
+                updated_at = NOW()
+            RETURNING *
+        "#)
+        .bind(format!("{:#x}", owner))
+        .bind(channel)
+        .bind(request.target.trim())
+        .bind(&request.events)
+        .bind(request.enabled.unwrap_or(true))
+        .fetch_one(pool)
+        .observe("notifications.upsert_channel")
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn list_channels(pool: &PgPool, owner: Address) -> Result<Vec<NotificationChannelRecord>> {
+        let records = sqlx::query_as::<_, NotificationChannelRecord>(
+            "SELECT * FROM notification_channels WHERE owner_address = $1 ORDER BY channel"
+        )
+        .bind(format!("{:#x}", owner))
+        .fetch_all(pool)
+        .observe("notifications.list_channels")
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Pending notifications on the channel are dropped with it
+    pub async fn delete_channel(pool: &PgPool, owner: Address, channel: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM notification_channels WHERE owner_address = $1 AND channel = $2")
+            .bind(format!("{:#x}", owner))
+            .bind(channel)
+            .execute(pool)
+            .observe("notifications.delete_channel")
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// One pending notification per active channel of `subject` that wants
+    /// the event
+    pub async fn enqueue(
+        pool: &PgPool,
+        subject: Address,
+        event_type: &str,
+        payload: &serde_json::Value,
+    ) -> Result<u64> {
+        let result = sqlx::query(r#"
+            INSERT INTO notification_deliveries (channel_id, event_type, payload)
+            SELECT id, $1, $2 FROM notification_channels
+            WHERE is_active = true
+            AND $1 = ANY(events)
+            AND owner_address = $3
+        "#)
+        .bind(event_type)
+        .bind(payload)
+        .bind(format!("{:#x}", subject))
+        .execute(pool)
+        .observe("notifications.enqueue")
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Lease up to `limit` due notifications; rows locked by another
+    /// instance are skipped
+    pub async fn claim_due(
+        pool: &PgPool,
+        limit: u32,
+        lease: std::time::Duration,
+    ) -> Result<Vec<DueNotification>> {
+        let records = sqlx::query_as::<_, DueNotification>(r#"
+            WITH due AS (
+                SELECT id FROM notification_deliveries
+                WHERE status = 'pending' AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE notification_deliveries d
+            SET next_attempt_at = NOW() + make_interval(secs => $2)
+            FROM due, notification_channels c
+            WHERE d.id = due.id AND c.id = d.channel_id
+            RETURNING d.id, d.event_type, d.payload, d.attempts, c.channel, c.target
+        "#)
+        .bind(limit as i64)
+        .bind(lease.as_secs_f64())
+        .fetch_all(pool)
+        .observe("notifications.claim_due")
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn mark_sent(pool: &PgPool, id: Uuid, attempts: u32) -> Result<()> {
+        sqlx::query(r#"
+            UPDATE notification_deliveries SET
+                status = 'sent',
+                attempts = $2,
+                last_error = NULL,
+                next_attempt_at = NULL,
+                sent_at = NOW()
+            WHERE id = $1
+        "#)
+        .bind(id)
+        .bind(attempts as i32)
+        .execute(pool)
+        .observe("notifications.mark_sent")
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn schedule_retry(
+        pool: &PgPool,
+        id: Uuid,
+        attempts: u32,
+        error: &str,
+        delay: std::time::Duration,
+    ) -> Result<()> {
+        sqlx::query(r#"
+            UPDATE notification_deliveries SET
+                attempts = $2,
+                last_error = $3,
+                next_attempt_at = NOW() + make_interval(secs => $4)
+            WHERE id = $1
+        "#)
+        .bind(id)
+        .bind(attempts as i32)
+        .bind(error)
+        .bind(delay.as_secs_f64())
+        .execute(pool)
+        .observe("notifications.schedule_retry")
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn mark_failed(pool: &PgPool, id: Uuid, attempts: u32, error: &str) -> Result<()> {
+        sqlx::query(r#"
+            UPDATE notification_deliveries SET
+                status = 'failed',
+                attempts = $2,
+                last_error = $3,
+                next_attempt_at = NULL
+            WHERE id = $1
+        "#)
+        .bind(id)
+        .bind(attempts as i32)
+        .bind(error)
+        .execute(pool)
+        .observe("notifications.mark_failed")
+        .await?;
+
+        Ok(())
+    }
+
+    /// The owner's most recent notifications across all channels
+    pub async fn list_deliveries(pool: &PgPool, owner: Address, limit: u64) -> Result<Vec<NotificationDeliveryRecord>> {
+        let records = sqlx::query_as::<_, NotificationDeliveryRecord>(r#"
+            SELECT d.id, c.channel, d.event_type, d.status, d.attempts, d.last_error, d.created_at, d.sent_at
+            FROM notification_deliveries d
+            JOIN notification_channels c ON c.id = d.channel_id
+            WHERE c.owner_address = $1
+            ORDER BY d.created_at DESC
+            LIMIT $2
+        "#)
+        .bind(format!("{:#x}", owner))
+        .bind(limit as i64)
+        .fetch_all(pool)
+        .observe("notifications.list_deliveries")
+        .await?;
+
+        Ok(records)
+    }
+}
+
+pub struct ApiKeyDb;
+
+impl ApiKeyDb {
+    pub async fn create_key(
+        pool: &PgPool,
+        owner: Address,
+        owner_role: &str,
+        request: &CreateApiKeyRequest,
+        tier: &str,
+        issued: &crate::api_keys::IssuedKey,
+    ) -> Result<ApiKeyRecord> {
+        let expires_at = request
+            .expires_in_days
+            .map(|days| Utc::now() + chrono::Duration::days(days as i64));
+
+        let record = sqlx::query_as::<_, ApiKeyRecord>(r#"
+            INSERT INTO api_keys (owner_address, owner_role, name, prefix, key_hash, scopes, tier, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+        "#)
+        .bind(format!("{:#x}", owner))
+        .bind(owner_role)
+        .bind(&request.name)
+        .bind(&issued.prefix)
+        .bind(&issued.hash)
+        .bind(&request.scopes)
+        .bind(tier)
+        .bind(expires_at)
+        .fetch_one(pool)
+        .observe("api_keys.create_key")
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn list_keys(pool: &PgPool, owner: Address) -> Result<Vec<ApiKeyRecord>> {
+        let records = sqlx::query_as::<_, ApiKeyRecord>(
+            "SELECT * FROM api_keys WHERE owner_address = $1 ORDER BY created_at DESC"
+        )
+        .bind(format!("{:#x}", owner))
+        .fetch_all(pool)
+        .observe("api_keys.list_keys")
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Keys that are neither revoked nor expired
+    pub async fn count_live_keys(pool: &PgPool, owner: Address) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar(r#"
+            SELECT COUNT(*) FROM api_keys
+            WHERE owner_address = $1 AND revoked_at IS NULL AND (expires_at IS NULL OR expires_at > NOW())
+        "#)
+        .bind(format!("{:#x}", owner))
+        .fetch_one(pool)
+        .observe("api_keys.count_live_keys")
+        .await?;
+
+        Ok(count)
+    }
+
+    pub async fn get_key(pool: &PgPool, id: Uuid) -> Result<Option<ApiKeyRecord>> {
+        let record = sqlx::query_as::<_, ApiKeyRecord>("SELECT * FROM api_keys WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .observe("api_keys.get_key")
+            .await?;
+
+        Ok(record)
+    }
+
+    /// The live key with this hash, if any
+    pub async fn find_live_key(pool: &PgPool, key_hash: &str) -> Result<Option<ApiKeyRecord>> {
+        let record = sqlx::query_as::<_, ApiKeyRecord>(r#"
+            SELECT * FROM api_keys
+            WHERE key_hash = $1 AND revoked_at IS NULL AND (expires_at IS NULL OR expires_at > NOW())
+        "#)
+        .bind(key_hash)
+        .fetch_optional(pool)
+        .observe("api_keys.find_live_key")
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn revoke_key(pool: &PgPool, id: Uuid) -> Result<()> {
+        sqlx::query("UPDATE api_keys SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL")
+            .bind(id)
+            .execute(pool)
+            .observe("api_keys.revoke_key")
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_tier(pool: &PgPool, id: Uuid, tier: &str) -> Result<Option<ApiKeyRecord>> {
+        let record = sqlx::query_as::<_, ApiKeyRecord>("UPDATE api_keys SET tier = $2 WHERE id = $1 RETURNING *")
+            .bind(id)
+            .bind(tier)
+            .fetch_optional(pool)
+            .observe("api_keys.set_tier")
+            .await?;
+
+        Ok(record)
+    }
+
+    /// Issue a replacement with the same name, scopes, tier and expiry. The
+    /// old key stops working after `grace_period`, or at once if that is zero.
+    pub async fn rotate_key(
+        pool: &PgPool,
+        id: Uuid,
+        issued: &crate::api_keys::IssuedKey,
+        grace_period: std::time::Duration,
+    ) -> Result<ApiKeyRecord> {
+        let mut tx = pool.begin().await?;
+
+        let record = sqlx::query_as::<_, ApiKeyRecord>(r#"
+            INSERT INTO api_keys (owner_address, owner_role, name, prefix, key_hash, scopes, tier, expires_at)
+            SELECT owner_address, owner_role, name, $2, $3, scopes, tier, expires_at
+            FROM api_keys WHERE id = $1
+            RETURNING *
+        "#)
+        .bind(id)
+        .bind(&issued.prefix)
+        .bind(&issued.hash)
+        .fetch_one(&mut *tx)
+        .observe("api_keys.rotate_key")
+        .await?;
+
+        if grace_period.is_zero() {
+            sqlx::query("UPDATE api_keys SET revoked_at = NOW() WHERE id = $1")
+                .bind(id)
+                .execute(&mut *tx)
+                .observe("api_keys.rotate_key.revoke")
+                .await?;
+        } else {
+            sqlx::query(r#"
+                UPDATE api_keys
+                SET expires_at = LEAST(COALESCE(expires_at, 'infinity'), NOW() + make_interval(secs => $2))
+                WHERE id = $1
+            "#)
+            .bind(id)
+            .bind(grace_period.as_secs_f64())
+            .execute(&mut *tx)
+            .observe("api_keys.rotate_key.expire")
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(record)
+    }
+
+    /// Count one request against the key's current hour
+    pub async fn record_usage(pool: &PgPool, key_id: Uuid, error: bool, rate_limited: bool) -> Result<()> {
+        sqlx::query(r#"
+            INSERT INTO api_key_usage (key_id, period_start, requests, errors, rate_limited)
+            VALUES ($1, date_trunc('hour', NOW()), 1, $2, $3)
+            ON CONFLICT (key_id, period_start) DO UPDATE SET
+                requests = api_key_usage.requests + 1,
+                errors = api_key_usage.errors + EXCLUDED.errors,
+                rate_limited = api_key_usage.rate_limited + EXCLUDED.rate_limited
+        "#)
+        .bind(key_id)
+        .bind(error as i64)
+        .bind(rate_limited as i64)
+        .execute(pool)
+        .observe("api_keys.record_usage")
+        .await?;
+
+        sqlx::query("UPDATE api_keys SET last_used_at = NOW() WHERE id = $1")
+            .bind(key_id)
+            .execute(pool)
+            .observe("api_keys.record_usage.last_used")
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn usage_since(pool: &PgPool, key_id: Uuid, since: DateTime<Utc>) -> Result<Vec<ApiKeyUsageRecord>> {
+        let records = sqlx::query_as::<_, ApiKeyUsageRecord>(r#"
+            SELECT period_start, requests, errors, rate_limited FROM api_key_usage
+            WHERE key_id = $1 AND period_start >= $2
+            ORDER BY period_start
+        "#)
+        .bind(key_id)
+        .bind(since)
+        .fetch_all(pool)
+        .observe("api_keys.usage_since")
+        .await?;
+
+        Ok(records)
+    }
+}
+
 // Helper functions for type conversions
 pub fn string_to_h256(s: &str) -> Result<H256> {
     H256::from_str(s).map_err(|e| crate::error::validation_error(format!("Invalid H256: {}", e)))
@@ -423,5 +1562,78 @@ pub fn solver_record_to_response(record: SolverRecord) -> Result<SolverResponse>
         is_slashed: record.is_slashed,
         last_activity: record.last_activity,
         registered_at: record.registered_at,
+        status: record.status(),
+        supported_tokens: record
+            .supported_tokens
+            .iter()
+            .map(|token| string_to_address(token))
+            .collect::<Result<Vec<_>>>()?,
+        name: record.name,
+        website: record.website,
+        quote_url: record.quote_url,
+        verified_at: record.verified_at,
     })
-}
\ No newline at end of file
+}
+
+pub struct AuditDb;
+
+impl AuditDb {
+    pub async fn record(pool: &PgPool, entry: &crate::audit::AuditEntry) -> Result<()> {
+        sqlx::query(r#"
+            INSERT INTO audit_log (actor, actor_role, api_key_id, ip, action, method, path, payload_hash, status_code, outcome)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "#)
+        .bind(&entry.actor)
+        .bind(&entry.actor_role)
+        .bind(entry.api_key_id)
+        .bind(&entry.ip)
+        .bind(entry.action)
+        .bind(&entry.method)
+        .bind(&entry.path)
+        .bind(&entry.payload_hash)
+        .bind(entry.status_code as i32)
+        .bind(entry.outcome)
+        .execute(pool)
+        .observe("audit_log.record")
+        .await?;
+
+        Ok(())
+    }
+
+    /// One page of entries matching `filter`, plus one extra row when more
+    /// follow
+    pub async fn list(
+        pool: &PgPool,
+        filter: &AuditLogFilter,
+        order: SortOrder,
+        cursor: Option<Cursor>,
+        limit: u64,
+    ) -> Result<Vec<AuditLogRecord>> {
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM audit_log WHERE TRUE");
+
+        if let Some(actor) = filter.actor {
+            query.push(" AND actor = ").push_bind(format!("{:#x}", actor));
+        }
+        if let Some(action) = &filter.action {
+            query.push(" AND action = ").push_bind(action.clone());
+        }
+        if let Some(outcome) = &filter.outcome {
+            query.push(" AND outcome = ").push_bind(outcome.clone());
+        }
+        if let Some(api_key_id) = filter.api_key_id {
+            query.push(" AND api_key_id = ").push_bind(api_key_id);
+        }
+        if let Some(after) = filter.occurred_after {
+            query.push(" AND occurred_at >= ").push_bind(after);
+        }
+        if let Some(before) = filter.occurred_before {
+            query.push(" AND occurred_at < ").push_bind(before);
+        }
+
+        push_page(&mut query, "occurred_at", order, cursor, limit);
+
+        let records = query.build_query_as::<AuditLogRecord>().fetch_all(pool).observe("audit_log.list").await?;
+
+        Ok(records)
+    }
+}
@@ -0,0 +1,160 @@
+//! Postgres pool and query metrics
+//!
+//! Every query in `database` is wrapped with [`Observe::observe`], which
+//! records its latency in the `db_query_duration_seconds` histogram, tagged
+//! with a statement name such as `intents.get_intent_by_id` and whether it
+//! failed, and logs it when it is slower than the configured threshold.
+//! sqlx logs the SQL of slow statements too, which also covers queries made
+//! outside `database`. Pool utilization is sampled on an interval into
+//! gauges labelled by pool. Everything is exported through the Prometheus
+//! recorder installed at startup.
+
+use sqlx::PgPool;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
+};
+
+pub const QUERY_DURATION_METRIC: &str = "db_query_duration_seconds";
+
+/// Histogram buckets for query latency, in seconds
+pub const QUERY_DURATION_BUCKETS: &[f64] = &[0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+static SLOW_QUERY_MS: AtomicU64 = AtomicU64::new(500);
+
+pub fn set_slow_query_threshold(threshold: Duration) {
+    SLOW_QUERY_MS.store(threshold.as_millis() as u64, Ordering::Relaxed);
+}
+
+pub fn slow_query_threshold() -> Duration {
+    Duration::from_millis(SLOW_QUERY_MS.load(Ordering::Relaxed))
+}
+
+/// Times a query future under a statement name
+pub trait Observe: Sized {
+    fn observe(self, statement: &'static str) -> Observed<Self>;
+}
+
+impl<F, T, E> Observe for F
+where
+    F: Future<Output = std::result::Result<T, E>>,
+{
+    fn observe(self, statement: &'static str) -> Observed<Self> {
+        Observed {
+            query: Box::pin(self),
+            statement,
+            started: Instant::now(),
+        }
+    }
+}
+
+pub struct Observed<F> {
+    query: Pin<Box<F>>,
+    statement: &'static str,
+    started: Instant,
+}
+
+impl<F, T, E> Future for Observed<F>
+where
+    F: Future<Output = std::result::Result<T, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let result = ready!(self.query.as_mut().poll(cx));
+        record(self.statement, self.started.elapsed(), result.is_ok());
+        Poll::Ready(result)
+    }
+}
+
+fn record(statement: &'static str, elapsed: Duration, ok: bool) {
+    let outcome = if ok { "ok" } else { "error" };
+    metrics::histogram!(QUERY_DURATION_METRIC, "statement" => statement, "outcome" => outcome)
+        .record(elapsed.as_secs_f64());
+
+    if is_slow(elapsed, slow_query_threshold()) {
+        metrics::counter!("db_slow_queries_total", "statement" => statement).increment(1);
+        tracing::warn!(
+            statement,
+            elapsed_ms = elapsed.as_millis() as u64,
+            outcome,
+            "Slow query"
+        );
+    }
+}
+
+/// A zero threshold turns the slow query log off
+pub fn is_slow(elapsed: Duration, threshold: Duration) -> bool {
+    !threshold.is_zero() && elapsed >= threshold
+}
+
+/// Connections in use, idle and allowed, and the share of the limit in use
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolUsage {
+    pub active: u32,
+    pub idle: u32,
+    pub max: u32,
+}
+
+impl PoolUsage {
+    pub fn of(pool: &PgPool) -> Self {
+        let size = pool.size();
+        let idle = (pool.num_idle() as u32).min(size);
+        Self {
+            active: size - idle,
+            idle,
+            max: pool.options().get_max_connections(),
+        }
+    }
+
+    pub fn utilization(&self) -> f64 {
+        if self.max == 0 {
+            0.0
+        } else {
+            self.active as f64 / self.max as f64
+        }
+    }
+}
+
+/// Sample `pool` into the pool gauges every `interval`, for the life of the
+/// process
+pub async fn sample_pool(name: &'static str, pool: PgPool, interval: Duration) {
+    let mut ticks = tokio::time::interval(interval.max(Duration::from_secs(1)));
+    loop {
+        ticks.tick().await;
+        let usage = PoolUsage::of(&pool);
+        metrics::gauge!("db_pool_connections", "pool" => name, "state" => "active").set(usage.active as f64);
+        metrics::gauge!("db_pool_connections", "pool" => name, "state" => "idle").set(usage.idle as f64);
+        metrics::gauge!("db_pool_max_connections", "pool" => name).set(usage.max as f64);
+        metrics::gauge!("db_pool_utilization_ratio", "pool" => name).set(usage.utilization());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slow_query_threshold() {
+        let threshold = Duration::from_millis(500);
+        assert!(!is_slow(Duration::from_millis(499), threshold));
+        assert!(is_slow(Duration::from_millis(500), threshold));
+        assert!(!is_slow(Duration::from_secs(60), Duration::ZERO));
+
+        let usage = PoolUsage { active: 5, idle: 3, max: 20 };
+        assert_eq!(usage.utilization(), 0.25);
+        assert_eq!(PoolUsage { active: 0, idle: 0, max: 0 }.utilization(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_observe_passes_results_through() {
+        let ok: std::result::Result<u32, String> = async { Ok(7) }.observe("test.ok").await;
+        assert_eq!(ok, Ok(7));
+
+        let failed: std::result::Result<u32, String> = async { Err("boom".to_string()) }.observe("test.error").await;
+        assert_eq!(failed, Err("boom".to_string()));
+    }
+}
@@ -0,0 +1,521 @@
+//! Bulk exports of historical data as CSV or Parquet
+//!
+//! Exports can cover months of rows, so nothing is collected up front. A
+//! producer task encodes rows as Postgres returns them and hands the
+//! encoded chunks to the response body through a small bounded channel.
+//! When the client reads slowly the channel fills, the producer stops
+//! polling its query and Postgres stops sending rows, so an export holds a
+//! few chunks in memory however large it is. Parquet is written one row
+//! group at a time for the same reason.
+//!
+//! Headers go out before the first row is read, so a failure partway
+//! through can't change the status. The body is aborted instead, and the
+//! client sees a broken transfer rather than a file that silently ends
+//! early.
+
+use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray, TimestampMicrosecondArray};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use axum::{
+    body::Body,
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use futures_util::stream::{self, Stream, StreamExt};
+use intents_indexer::market::SwapRecord;
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+use serde::Deserialize;
+use std::{
+    io::Write,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    error::{internal_error, validation_error, Result},
+    models::IntentRecord,
+};
+
+/// Longest date range one export may cover
+pub const MAX_RANGE_DAYS: i64 = 366;
+
+// CSV is sent once this much has been encoded
+const CSV_CHUNK_SIZE: usize = 64 * 1024;
+// Rows per Parquet row group, and so per chunk
+const ROW_GROUP_SIZE: usize = 8192;
+// Encoded chunks waiting for the client before the producer pauses
+const CHANNEL_CAPACITY: usize = 4;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+}
+
+/// Query parameters shared by every export
+#[derive(Debug, Deserialize)]
+pub struct ExportParams {
+    /// Inclusive
+    pub from: DateTime<Utc>,
+    /// Exclusive
+    pub to: DateTime<Utc>,
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+impl ExportParams {
+    pub fn validate(&self) -> Result<()> {
+        if self.to <= self.from {
+            return Err(validation_error("`to` must be after `from`"));
+        }
+        if self.to - self.from > Duration::days(MAX_RANGE_DAYS) {
+            return Err(validation_error(format!("Exports cover at most {} days", MAX_RANGE_DAYS)));
+        }
+        Ok(())
+    }
+
+    /// e.g. `intents-20240501-20240601.csv`
+    pub fn filename(&self, name: &str) -> String {
+        format!(
+            "{}-{}-{}.{}",
+            name,
+            self.from.format("%Y%m%d"),
+            self.to.format("%Y%m%d"),
+            self.format.extension()
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Text,
+    Integer,
+    Timestamp,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Column {
+    pub name: &'static str,
+    pub kind: ColumnType,
+}
+
+const fn column(name: &'static str, kind: ColumnType) -> Column {
+    Column { name, kind }
+}
+
+/// One cell. Token amounts are decimal strings, as they don't fit in 64
+/// bits.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Text(Option<String>),
+    Integer(Option<i64>),
+    Timestamp(Option<DateTime<Utc>>),
+}
+
+impl Value {
+    fn text(value: impl ToString) -> Self {
+        Value::Text(Some(value.to_string()))
+    }
+
+    fn csv(&self) -> String {
+        match self {
+            Value::Text(value) => value.as_deref().map(csv_escape).unwrap_or_default(),
+            Value::Integer(value) => value.map(|value| value.to_string()).unwrap_or_default(),
+            Value::Timestamp(value) => value
+                .map(|value| value.to_rfc3339_opts(SecondsFormat::Micros, true))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Quote a CSV field if it needs it (RFC 4180)
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// A row type that can be exported
+pub trait ExportRow: Send + 'static {
+    const COLUMNS: &'static [Column];
+
+    /// One value per column, in column order
+    fn values(self) -> Vec<Value>;
+}
+
+impl ExportRow for IntentRecord {
+    const COLUMNS: &'static [Column] = &[
+        column("intent_id", ColumnType::Text),
+        column("user_address", ColumnType::Text),
+        column("status", ColumnType::Text),
+        column("source_chain_id", ColumnType::Integer),
+        column("dest_chain_id", ColumnType::Integer),
+        column("source_token", ColumnType::Text),
+        column("dest_token", ColumnType::Text),
+        column("source_amount", ColumnType::Text),
+        column("min_dest_amount", ColumnType::Text),
+        column("actual_dest_amount", ColumnType::Text),
+        column("solver_address", ColumnType::Text),
+        column("execution_tx_hash", ColumnType::Text),
+        column("gas_used", ColumnType::Text),
+        column("fees_paid", ColumnType::Text),
+        column("error_message", ColumnType::Text),
+        column("deadline", ColumnType::Timestamp),
+        column("created_at", ColumnType::Timestamp),
+        column("updated_at", ColumnType::Timestamp),
+    ];
+
+    fn values(self) -> Vec<Value> {
+        vec![
+            Value::Text(Some(self.intent_id)),
+            Value::Text(Some(self.user_address)),
+            Value::Text(Some(self.status)),
+            Value::Integer(Some(self.source_chain_id)),
+            Value::Integer(Some(self.dest_chain_id)),
+            Value::Text(Some(self.source_token)),
+            Value::Text(Some(self.dest_token)),
+            Value::Text(Some(self.source_amount)),
+            Value::Text(Some(self.min_dest_amount)),
+            Value::Text(self.actual_dest_amount),
+            Value::Text(self.solver_address),
+            Value::Text(self.execution_tx_hash),
+            Value::Text(self.gas_used),
+            Value::Text(self.fees_paid),
+            Value::Text(self.error_message),
+            Value::Timestamp(Some(self.deadline)),
+            Value::Timestamp(Some(self.created_at)),
+            Value::Timestamp(Some(self.updated_at)),
+        ]
+    }
+}
+
+impl ExportRow for SwapRecord {
+    const COLUMNS: &'static [Column] = &[
+        column("chain_id", ColumnType::Integer),
+        column("block_number", ColumnType::Integer),
+        column("transaction_hash", ColumnType::Text),
+        column("log_index", ColumnType::Integer),
+        column("timestamp", ColumnType::Timestamp),
+        column("pool_id", ColumnType::Text),
+        column("trader", ColumnType::Text),
+        column("token_in", ColumnType::Text),
+        column("token_out", ColumnType::Text),
+        column("amount_in", ColumnType::Text),
+        column("amount_out", ColumnType::Text),
+        column("decimals_in", ColumnType::Integer),
+        column("decimals_out", ColumnType::Integer),
+    ];
+
+    fn values(self) -> Vec<Value> {
+        let address = |address: Option<ethers::types::Address>| Value::Text(address.map(|address| format!("{:#x}", address)));
+        vec![
+            Value::Integer(Some(self.chain_id as i64)),
+            Value::Integer(Some(self.block_number as i64)),
+            Value::text(format!("{:#x}", self.transaction_hash)),
+            Value::Integer(Some(self.log_index as i64)),
+            Value::Timestamp(Some(self.timestamp)),
+            Value::Text(Some(self.pool_id)),
+            Value::text(format!("{:#x}", self.trader)),
+            address(self.token_in),
+            address(self.token_out),
+            Value::text(self.amount_in),
+            Value::text(self.amount_out),
+            Value::Integer(self.decimals_in.map(i64::from)),
+            Value::Integer(self.decimals_out.map(i64::from)),
+        ]
+    }
+}
+
+/// Lets the Parquet writer's output be taken a row group at a time
+#[derive(Debug, Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn parquet_error(error: impl std::fmt::Display) -> crate::error::ApiError {
+    internal_error(format!("Failed to write Parquet export: {}", error))
+}
+
+/// Turns rows into chunks of the output file
+pub enum Encoder {
+    Csv(Vec<u8>),
+    Parquet {
+        writer: ArrowWriter<SharedBuffer>,
+        output: SharedBuffer,
+        schema: SchemaRef,
+        columns: &'static [Column],
+        rows: Vec<Vec<Value>>,
+    },
+}
+
+impl Encoder {
+    pub fn new(format: ExportFormat, columns: &'static [Column]) -> Result<Self> {
+        match format {
+            ExportFormat::Csv => {
+                let header: Vec<&str> = columns.iter().map(|column| column.name).collect();
+                Ok(Encoder::Csv(format!("{}\r\n", header.join(",")).into_bytes()))
+            }
+            ExportFormat::Parquet => {
+                let schema = Arc::new(Schema::new(
+                    columns
+                        .iter()
+                        .map(|column| {
+                            let data_type = match column.kind {
+                                ColumnType::Text => DataType::Utf8,
+                                ColumnType::Integer => DataType::Int64,
+                                ColumnType::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                            };
+                            Field::new(column.name, data_type, true)
+                        })
+                        .collect::<Vec<_>>(),
+                ));
+                let properties = WriterProperties::builder()
+                    .set_compression(Compression::SNAPPY)
+                    .set_max_row_group_size(ROW_GROUP_SIZE)
+                    .build();
+                let output = SharedBuffer::default();
+                let writer = ArrowWriter::try_new(output.clone(), schema.clone(), Some(properties)).map_err(parquet_error)?;
+                Ok(Encoder::Parquet {
+                    writer,
+                    output,
+                    schema,
+                    columns,
+                    rows: Vec::with_capacity(ROW_GROUP_SIZE),
+                })
+            }
+        }
+    }
+
+    /// Add a row; returns a chunk when one is ready to send
+    pub fn push(&mut self, values: Vec<Value>) -> Result<Option<Vec<u8>>> {
+        match self {
+            Encoder::Csv(buffer) => {
+                let line: Vec<String> = values.iter().map(Value::csv).collect();
+                buffer.extend_from_slice(line.join(",").as_bytes());
+                buffer.extend_from_slice(b"\r\n");
+                Ok((buffer.len() >= CSV_CHUNK_SIZE).then(|| std::mem::take(buffer)))
+            }
+            Encoder::Parquet { writer, output, schema, columns, rows } => {
+                rows.push(values);
+                if rows.len() < ROW_GROUP_SIZE {
+                    return Ok(None);
+                }
+                let batch = record_batch(schema, columns, &std::mem::take(rows))?;
+                writer.write(&batch).map_err(parquet_error)?;
+                writer.flush().map_err(parquet_error)?;
+                Ok(Some(output.take()))
+            }
+        }
+    }
+
+    /// Whatever is left, including the Parquet footer
+    pub fn finish(self) -> Result<Vec<u8>> {
+        match self {
+            Encoder::Csv(buffer) => Ok(buffer),
+            Encoder::Parquet { mut writer, output, schema, columns, rows } => {
+                if !rows.is_empty() {
+                    writer.write(&record_batch(&schema, columns, &rows)?).map_err(parquet_error)?;
+                }
+                writer.close().map_err(parquet_error)?;
+                Ok(output.take())
+            }
+        }
+    }
+}
+
+/// Rows transposed into Arrow columns
+fn record_batch(schema: &SchemaRef, columns: &[Column], rows: &[Vec<Value>]) -> Result<RecordBatch> {
+    let arrays: Vec<ArrayRef> = columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| -> ArrayRef {
+            let cells = rows.iter().map(move |row| row.get(index));
+            match column.kind {
+                ColumnType::Text => Arc::new(StringArray::from_iter(cells.map(|cell| match cell {
+                    Some(Value::Text(value)) => value.as_deref(),
+                    _ => None,
+                }))),
+                ColumnType::Integer => Arc::new(Int64Array::from_iter(cells.map(|cell| match cell {
+                    Some(Value::Integer(value)) => *value,
+                    _ => None,
+                }))),
+                ColumnType::Timestamp => Arc::new(
+                    TimestampMicrosecondArray::from_iter(cells.map(|cell| match cell {
+                        Some(Value::Timestamp(value)) => value.map(|value| value.timestamp_micros()),
+                        _ => None,
+                    }))
+                    .with_timezone("UTC"),
+                ),
+            }
+        })
+        .collect();
+    RecordBatch::try_new(schema.clone(), arrays).map_err(parquet_error)
+}
+
+type Chunk = std::result::Result<Vec<u8>, std::io::Error>;
+
+/// The producing side of an export started with [`start`]
+pub struct ExportSink<R> {
+    format: ExportFormat,
+    chunks: mpsc::Sender<Chunk>,
+    rows: PhantomData<fn(R)>,
+}
+
+/// The response for an export, and the sink its rows go into. Run the sink
+/// on its own task with the query that produces the rows.
+pub fn start<R: ExportRow>(params: &ExportParams, name: &str) -> (ExportSink<R>, Response) {
+    let (chunks, receiver) = mpsc::channel::<Chunk>(CHANNEL_CAPACITY);
+    let body = Body::from_stream(stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    }));
+
+    let mut response = body.into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(params.format.content_type()));
+    if let Ok(value) = HeaderValue::from_str(&format!("attachment; filename=\"{}\"", params.filename(name))) {
+        headers.insert(header::CONTENT_DISPOSITION, value);
+    }
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+
+    (ExportSink { format: params.format, chunks, rows: PhantomData }, response)
+}
+
+impl<R: ExportRow> ExportSink<R> {
+    /// Encode and send every row, stopping early if the client goes away
+    pub async fn run<S>(self, rows: S)
+    where
+        S: Stream<Item = Result<R>>,
+    {
+        if let Err(e) = self.send_all(rows).await {
+            tracing::error!("Export failed: {}", e);
+            let _ = self.chunks.send(Err(std::io::Error::other(e.to_string()))).await;
+        }
+    }
+
+    async fn send_all<S>(&self, rows: S) -> Result<()>
+    where
+        S: Stream<Item = Result<R>>,
+    {
+        let mut rows = std::pin::pin!(rows);
+        let mut encoder = Encoder::new(self.format, R::COLUMNS)?;
+
+        while let Some(row) = rows.next().await {
+            if let Some(chunk) = encoder.push(row?.values())? {
+                if self.chunks.send(Ok(chunk)).await.is_err() {
+                    tracing::debug!("Export client disconnected");
+                    return Ok(());
+                }
+            }
+        }
+        let _ = self.chunks.send(Ok(encoder.finish()?)).await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Array;
+    use axum::body::Bytes;
+    use chrono::TimeZone;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    const COLUMNS: &[Column] = &[
+        column("name", ColumnType::Text),
+        column("count", ColumnType::Integer),
+        column("at", ColumnType::Timestamp),
+    ];
+
+    fn row(name: Option<&str>, count: i64) -> Vec<Value> {
+        vec![
+            Value::Text(name.map(str::to_string)),
+            Value::Integer(Some(count)),
+            Value::Timestamp(Some(Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap())),
+        ]
+    }
+
+    #[test]
+    fn test_csv_encoding() {
+        let mut encoder = Encoder::new(ExportFormat::Csv, COLUMNS).unwrap();
+        assert!(encoder.push(row(Some("plain"), 1)).unwrap().is_none());
+        assert!(encoder.push(row(Some("say \"hi\", twice"), 2)).unwrap().is_none());
+        assert!(encoder.push(row(None, 3)).unwrap().is_none());
+
+        let csv = String::from_utf8(encoder.finish().unwrap()).unwrap();
+        assert_eq!(
+            csv,
+            "name,count,at\r\n\
+             plain,1,2024-05-01T12:00:00.000000Z\r\n\
+             \"say \"\"hi\"\", twice\",2,2024-05-01T12:00:00.000000Z\r\n\
+             ,3,2024-05-01T12:00:00.000000Z\r\n"
+        );
+
+        let params = ExportParams {
+            from: Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+            to: Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap(),
+            format: ExportFormat::Csv,
+        };
+        assert!(params.validate().is_ok());
+        assert_eq!(params.filename("intents"), "intents-20240501-20240601.csv");
+        assert!(ExportParams { to: params.from, ..params }.validate().is_err());
+    }
+
+    #[test]
+    fn test_parquet_round_trip_across_row_groups() {
+        let mut encoder = Encoder::new(ExportFormat::Parquet, COLUMNS).unwrap();
+        let mut file = Vec::new();
+        for count in 0..(ROW_GROUP_SIZE as i64 + 10) {
+            if let Some(chunk) = encoder.push(row(Some("swap"), count)).unwrap() {
+                file.extend(chunk);
+            }
+        }
+        // The first row group went out before the end
+        assert!(!file.is_empty());
+        file.extend(encoder.finish().unwrap());
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(file)).unwrap();
+        assert_eq!(reader.metadata().num_row_groups(), 2);
+        let batches: Vec<RecordBatch> = reader.build().unwrap().map(|batch| batch.unwrap()).collect();
+        let rows: usize = batches.iter().map(RecordBatch::num_rows).sum();
+        assert_eq!(rows, ROW_GROUP_SIZE + 10);
+
+        let counts = batches.last().unwrap().column(1).as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(counts.value(counts.len() - 1), ROW_GROUP_SIZE as i64 + 9);
+    }
+}
@@ -0,0 +1,677 @@
+//! GraphQL API over intents, pools, solvers and bridge transfers
+//!
+//! Served at `/api/v1/graphql`, with subscriptions over WebSocket at
+//! `/api/v1/graphql/ws`. Intents and solver profiles come from the API
+//! database; executions, solver stats, bridge transfers and pool series come
+//! from the indexer. Nested fields are only resolved when selected, so a
+//! query for intent ids never touches the indexer. Subscriptions relay the
+//! broadcast channels behind the WebSocket and SSE streams.
+
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, ErrorExtensions, Json, Object, Schema, SimpleObject, Subscription,
+};
+use chrono::{DateTime, Duration, Utc};
+use ethers::types::{Address, H256};
+use futures_util::stream::{self, Stream};
+use std::{fmt::Display, str::FromStr};
+use tokio::sync::broadcast::error::RecvError;
+
+use intents_indexer::{
+    journeys::IntentJourney,
+    rollups::Granularity,
+    solvers::SolverStats as IndexedSolverStats,
+    transfers::BridgeTransfer as IndexedBridgeTransfer,
+};
+
+use crate::{
+    auth::{check_permission, extract_user_address},
+    database::{intent_record_to_response, solver_record_to_response, IntentDb, SolverDb},
+    error::ApiError,
+    models::*,
+    pagination::{CursorPage, CursorValue, PageParams, SortOrder},
+    pool_analytics::{self, PoolPoint as PoolSeriesPoint, PoolSummary, Window},
+    websocket::{SubscriptionChannel, WS_MANAGER},
+};
+
+pub type ApiSchema = Schema<Query, EmptyMutation, Subscription>;
+
+type GqlResult<T> = async_graphql::Result<T>;
+
+/// Deep or wide queries are rejected before any resolver runs
+const MAX_DEPTH: usize = 8;
+const MAX_COMPLEXITY: usize = 500;
+
+pub fn build_schema() -> ApiSchema {
+    Schema::build(Query, EmptyMutation, Subscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+/// Same codes as REST errors; storage failures are logged, not returned
+fn gql_error(error: ApiError) -> async_graphql::Error {
+    let (code, message) = match &error {
+        ApiError::Validation(message) | ApiError::BadRequest(message) => ("VALIDATION_ERROR", message.clone()),
+        ApiError::Authentication(message) => ("AUTHENTICATION_ERROR", message.clone()),
+        ApiError::Authorization(message) => ("AUTHORIZATION_ERROR", message.clone()),
+        ApiError::NotFound(message) => ("NOT_FOUND", message.clone()),
+        _ => {
+            tracing::error!("GraphQL resolver failed: {}", error);
+            ("INTERNAL_ERROR", "Internal server error".to_string())
+        }
+    };
+    async_graphql::Error::new(message).extend_with(|_, extensions| extensions.set("code", code))
+}
+
+fn indexer_error(error: impl Display) -> async_graphql::Error {
+    gql_error(ApiError::Internal(format!("Indexer query failed: {}", error)))
+}
+
+fn state<'a>(ctx: &Context<'a>) -> &'a AppState {
+    ctx.data_unchecked::<AppState>()
+}
+
+fn caller<'a>(ctx: &Context<'a>) -> GqlResult<&'a Claims> {
+    ctx.data::<Claims>()
+        .map_err(|_| gql_error(ApiError::Authentication("Authentication required".to_string())))
+}
+
+fn parse_h256(value: &str, what: &str) -> GqlResult<H256> {
+    H256::from_str(value).map_err(|_| gql_error(ApiError::Validation(format!("Invalid {}", what))))
+}
+
+fn parse_address(value: &str) -> GqlResult<Address> {
+    Address::from_str(value).map_err(|_| gql_error(ApiError::Validation("Invalid address".to_string())))
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Intent {
+    pub id: String,
+    pub status: String,
+    pub source_chain_id: u64,
+    pub dest_chain_id: u64,
+    pub source_token: String,
+    pub dest_token: String,
+    pub source_amount: String,
+    pub min_dest_amount: String,
+    pub actual_dest_amount: Option<String>,
+    pub deadline: DateTime<Utc>,
+    pub user: String,
+    pub solver_address: Option<String>,
+    pub execution_tx_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[graphql(skip)]
+    intent_id: H256,
+    #[graphql(skip)]
+    solver: Option<Address>,
+}
+
+impl From<IntentResponse> for Intent {
+    fn from(intent: IntentResponse) -> Self {
+        Self {
+            id: format!("{:#x}", intent.intent_id),
+            status: intent.status,
+            source_chain_id: intent.source_chain_id,
+            dest_chain_id: intent.dest_chain_id,
+            source_token: format!("{:#x}", intent.source_token),
+            dest_token: format!("{:#x}", intent.dest_token),
+            source_amount: intent.source_amount.to_string(),
+            min_dest_amount: intent.min_dest_amount.to_string(),
+            actual_dest_amount: intent.actual_dest_amount.map(|amount| amount.to_string()),
+            deadline: intent.deadline,
+            user: format!("{:#x}", intent.user_address),
+            solver_address: intent.solver_address.map(|address| format!("{:#x}", address)),
+            execution_tx_hash: intent.execution_tx_hash.map(|hash| format!("{:#x}", hash)),
+            created_at: intent.created_at,
+            updated_at: intent.updated_at,
+            intent_id: intent.intent_id,
+            solver: intent.solver_address,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Intent {
+    /// Registry profile of the solver that matched the intent
+    async fn solver(&self, ctx: &Context<'_>) -> GqlResult<Option<Solver>> {
+        match self.solver {
+            Some(address) => load_solver(ctx, address).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Matching and execution as indexed on-chain
+    async fn execution(&self, ctx: &Context<'_>) -> GqlResult<Option<Execution>> {
+        Ok(load_journey(ctx, self.intent_id).await?.map(Execution::from))
+    }
+
+    /// Bridge transfers that carried the intent across chains
+    async fn bridge_transfers(&self, ctx: &Context<'_>) -> GqlResult<Vec<BridgeTransfer>> {
+        let Some(journey) = load_journey(ctx, self.intent_id).await? else {
+            return Ok(Vec::new());
+        };
+
+        let mut transfers = Vec::with_capacity(journey.bridge_legs.len());
+        for leg in &journey.bridge_legs {
+            if let Some(transfer) = state(ctx).indexer.find_bridge_transfer(leg.message_hash).await.map_err(indexer_error)? {
+                transfers.push(transfer.into());
+            }
+        }
+        Ok(transfers)
+    }
+}
+
+async fn load_journey(ctx: &Context<'_>, intent_id: H256) -> GqlResult<Option<IntentJourney>> {
+    state(ctx).indexer.query_intent_journey(intent_id).await.map_err(indexer_error)
+}
+
+async fn load_solver(ctx: &Context<'_>, address: Address) -> GqlResult<Option<Solver>> {
+    let record = SolverDb::get_solver_by_address(&state(ctx).db, address).await.map_err(gql_error)?;
+    record
+        .map(|record| solver_record_to_response(record).map(Solver::from).map_err(gql_error))
+        .transpose()
+}
+
+/// An intent's on-chain progress, from the indexer
+#[derive(SimpleObject)]
+pub struct Execution {
+    pub solver_address: Option<String>,
+    pub source_chain_id: Option<u64>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub matched_at: Option<DateTime<Utc>>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub success: Option<bool>,
+    pub matching_ms: Option<i64>,
+    pub execution_ms: Option<i64>,
+    pub bridging_ms: Option<i64>,
+    pub total_ms: Option<i64>,
+}
+
+impl From<IntentJourney> for Execution {
+    fn from(journey: IntentJourney) -> Self {
+        Self {
+            solver_address: journey.solver.map(|address| format!("{:#x}", address)),
+            source_chain_id: journey.source_chain_id,
+            created_at: journey.created_at,
+            matched_at: journey.matched_at,
+            executed_at: journey.executed_at,
+            success: journey.success,
+            matching_ms: journey.latency.matching_ms,
+            execution_ms: journey.latency.execution_ms,
+            bridging_ms: journey.latency.bridging_ms,
+            total_ms: journey.latency.total_ms,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Solver {
+    pub address: String,
+    pub name: Option<String>,
+    pub website: Option<String>,
+    pub status: SolverStatus,
+    pub reputation_score: f64,
+    pub fee_rate: f64,
+    pub supported_chains: Vec<u64>,
+    pub supported_tokens: Vec<String>,
+    pub success_count: u64,
+    pub failure_count: u64,
+    pub total_volume: String,
+    pub bond_amount: String,
+    pub registered_at: DateTime<Utc>,
+    pub last_activity: DateTime<Utc>,
+    pub verified_at: Option<DateTime<Utc>>,
+    #[graphql(skip)]
+    raw_address: Address,
+}
+
+impl From<SolverResponse> for Solver {
+    fn from(solver: SolverResponse) -> Self {
+        Self {
+            address: format!("{:#x}", solver.address),
+            name: solver.name,
+            website: solver.website,
+            status: solver.status,
+            reputation_score: solver.reputation_score,
+            fee_rate: solver.fee_rate,
+            supported_chains: solver.supported_chains,
+            supported_tokens: solver.supported_tokens.iter().map(|token| format!("{:#x}", token)).collect(),
+            success_count: solver.success_count,
+            failure_count: solver.failure_count,
+            total_volume: solver.total_volume.to_string(),
+            bond_amount: solver.bond_amount.to_string(),
+            registered_at: solver.registered_at,
+            last_activity: solver.last_activity,
+            verified_at: solver.verified_at,
+            raw_address: solver.address,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Solver {
+    /// Fill rate, latency and slippage measured from indexed events
+    async fn stats(&self, ctx: &Context<'_>) -> GqlResult<Option<SolverStats>> {
+        let stats = state(ctx).indexer.solver_stats(self.raw_address).await.map_err(indexer_error)?;
+        Ok(stats.map(SolverStats::from))
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct SolverStats {
+    pub solver: String,
+    pub intents_matched: u64,
+    pub intents_executed: u64,
+    pub intents_succeeded: u64,
+    /// Share of matched intents executed successfully
+    pub fill_rate: f64,
+    pub avg_latency_ms: Option<f64>,
+    /// Positive when the solver delivered less than it quoted
+    pub avg_slippage_bps: Option<f64>,
+    pub slippage_samples: u64,
+    pub slashings: u64,
+    pub slashed_amount: String,
+    pub last_active_at: Option<DateTime<Utc>>,
+}
+
+impl From<IndexedSolverStats> for SolverStats {
+    fn from(stats: IndexedSolverStats) -> Self {
+        Self {
+            fill_rate: stats.fill_rate(),
+            solver: stats.solver,
+            intents_matched: stats.intents_matched,
+            intents_executed: stats.intents_executed,
+            intents_succeeded: stats.intents_succeeded,
+            avg_latency_ms: stats.avg_latency_ms,
+            avg_slippage_bps: stats.avg_slippage_bps,
+            slippage_samples: stats.slippage_samples,
+            slashings: stats.slashings,
+            slashed_amount: stats.slashed_amount.to_string(),
+            last_active_at: stats.last_active_at,
+        }
+    }
+}
+
+#[ComplexObject]
+impl SolverStats {
+    /// Registry profile, if the solver is registered with this API
+    async fn profile(&self, ctx: &Context<'_>) -> GqlResult<Option<Solver>> {
+        match Address::from_str(&self.solver) {
+            Ok(address) => load_solver(ctx, address).await,
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct BridgeTransfer {
+    pub message_hash: String,
+    pub nonce: String,
+    pub source_chain_id: u64,
+    pub destination_chain_id: u64,
+    pub sender: Option<String>,
+    pub recipient: String,
+    pub token: String,
+    pub amount: String,
+    pub source_tx: Option<String>,
+    pub destination_tx: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub received_at: Option<DateTime<Utc>>,
+    /// "pending", "completed", "stuck" or "orphaned"
+    pub status: String,
+    pub latency_ms: Option<i64>,
+}
+
+impl From<IndexedBridgeTransfer> for BridgeTransfer {
+    fn from(transfer: IndexedBridgeTransfer) -> Self {
+        Self {
+            message_hash: format!("{:#x}", transfer.message_hash),
+            nonce: transfer.nonce.to_string(),
+            source_chain_id: transfer.source_chain_id,
+            destination_chain_id: transfer.destination_chain_id,
+            sender: transfer.sender.map(|address| format!("{:#x}", address)),
+            recipient: format!("{:#x}", transfer.recipient),
+            token: format!("{:#x}", transfer.token),
+            amount: transfer.amount.to_string(),
+            source_tx: transfer.source_tx.map(|hash| format!("{:#x}", hash)),
+            destination_tx: transfer.destination_tx.map(|hash| format!("{:#x}", hash)),
+            sent_at: transfer.sent_at,
+            received_at: transfer.received_at,
+            status: transfer.status.as_str().to_string(),
+            latency_ms: transfer.latency_ms,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Pool {
+    pub chain_id: u64,
+    pub pool_id: String,
+    pub tvl: String,
+    pub volume_24h: String,
+    pub volume_7d: String,
+    pub fees_24h: String,
+    pub fees_7d: String,
+    pub apr_7d: f64,
+}
+
+impl From<PoolSummary> for Pool {
+    fn from(summary: PoolSummary) -> Self {
+        Self {
+            chain_id: summary.chain_id,
+            pool_id: summary.pool_id,
+            tvl: summary.tvl.to_string(),
+            volume_24h: summary.volume_24h.to_string(),
+            volume_7d: summary.volume_7d.to_string(),
+            fees_24h: summary.fees_24h.to_string(),
+            fees_7d: summary.fees_7d.to_string(),
+            apr_7d: summary.apr_7d,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Pool {
+    /// TVL, volume, fees and APR per bucket; `granularity` is hourly or
+    /// daily and `interval` one of 24h, 7d, 30d, 90d or 1y
+    async fn series(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default_with = "\"hourly\".to_string()")] granularity: String,
+        #[graphql(default_with = "\"7d\".to_string()")] interval: String,
+    ) -> GqlResult<Vec<PoolPoint>> {
+        let granularity = pool_analytics::parse_granularity(Some(&granularity)).map_err(gql_error)?;
+        let window = Window::parse(&interval).map_err(gql_error)?;
+        window.check_granularity(granularity).map_err(gql_error)?;
+
+        let now = Utc::now();
+        let series = pool_analytics::load_series(&state(ctx).indexer, granularity, now - window.duration(), now)
+            .await
+            .map_err(gql_error)?;
+        Ok(series
+            .into_iter()
+            .find(|series| series.chain_id == self.chain_id && series.pool_id == self.pool_id)
+            .map(|series| series.points.into_iter().map(PoolPoint::from).collect())
+            .unwrap_or_default())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct PoolPoint {
+    pub bucket_start: DateTime<Utc>,
+    pub tvl: String,
+    pub volume: String,
+    pub fees: String,
+    pub swaps: u64,
+    pub apr: f64,
+}
+
+impl From<PoolSeriesPoint> for PoolPoint {
+    fn from(point: PoolSeriesPoint) -> Self {
+        Self {
+            bucket_start: point.bucket_start,
+            tvl: point.tvl.to_string(),
+            volume: point.volume.to_string(),
+            fees: point.fees.to_string(),
+            swaps: point.swaps,
+            apr: point.apr,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct IntentConnection {
+    pub nodes: Vec<Intent>,
+    /// Pass as `after` to fetch the next page
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    async fn intent(&self, ctx: &Context<'_>, id: String) -> GqlResult<Option<Intent>> {
+        let intent_id = parse_h256(&id, "intent id")?;
+        let Some(record) = IntentDb::get_intent_by_id(&state(ctx).db, intent_id).await.map_err(gql_error)? else {
+            return Ok(None);
+        };
+        let intent = intent_record_to_response(record).map_err(gql_error)?;
+
+        // Same rule as REST: a signed-in caller sees other users' intents
+        // only with the wildcard read permission
+        if let Ok(claims) = ctx.data::<Claims>() {
+            if extract_user_address(claims).map_err(gql_error)? != intent.user_address {
+                check_permission(claims, "/api/v1/intents/*", "GET").map_err(gql_error)?;
+            }
+        }
+        Ok(Some(intent.into()))
+    }
+
+    /// Newest first; by default the caller's own intents
+    async fn intents(
+        &self,
+        ctx: &Context<'_>,
+        user: Option<String>,
+        #[graphql(desc = "Comma-separated, e.g. \"pending,matched\"")] status: Option<String>,
+        source_chain_id: Option<u64>,
+        dest_chain_id: Option<u64>,
+        first: Option<u64>,
+        after: Option<String>,
+    ) -> GqlResult<IntentConnection> {
+        let claims = caller(ctx)?;
+        let caller_address = extract_user_address(claims).map_err(gql_error)?;
+
+        // Other users' intents need the wildcard read permission, as in REST
+        let user = match user {
+            Some(user) => parse_address(&user)?,
+            None => caller_address,
+        };
+        if user != caller_address {
+            check_permission(claims, "/api/v1/intents/*", "GET").map_err(gql_error)?;
+        }
+
+        let filter = IntentFilter {
+            status,
+            source_chain_id,
+            dest_chain_id,
+            user: Some(user),
+            ..Default::default()
+        };
+        let page = PageParams {
+            cursor: after,
+            limit: first,
+            sort_by: None,
+            sort_order: SortOrder::Desc,
+        };
+        let limit = page.limit();
+        let records = IntentDb::list_intents(
+            &state(ctx).db,
+            &filter,
+            "created_at",
+            page.sort_order,
+            page.cursor("created_at").map_err(gql_error)?,
+            limit,
+        )
+        .await
+        .map_err(gql_error)?;
+
+        let page = CursorPage::from_rows(
+            records,
+            limit,
+            "created_at",
+            |record| (CursorValue::Time(record.created_at), record.id),
+            intent_record_to_response,
+        )
+        .map_err(gql_error)?;
+
+        Ok(IntentConnection {
+            nodes: page.data.into_iter().map(Intent::from).collect(),
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+        })
+    }
+
+    async fn solver(&self, ctx: &Context<'_>, address: String) -> GqlResult<Option<Solver>> {
+        load_solver(ctx, parse_address(&address)?).await
+    }
+
+    /// Active solvers, best reputation first
+    async fn solvers(&self, ctx: &Context<'_>, chain_id: Option<u64>, first: Option<u64>) -> GqlResult<Vec<Solver>> {
+        let filter = SolverFilter {
+            chain_id,
+            ..Default::default()
+        };
+        let page = PageParams {
+            limit: first,
+            ..Default::default()
+        };
+        let mut records = SolverDb::list_solvers(&state(ctx).db, &filter, "reputation_score", SortOrder::Desc, None, page.limit())
+            .await
+            .map_err(gql_error)?;
+        // Drop the look-ahead row fetched for pagination
+        records.truncate(page.limit() as usize);
+
+        records
+            .into_iter()
+            .map(|record| solver_record_to_response(record).map(Solver::from).map_err(gql_error))
+            .collect()
+    }
+
+    /// Solvers ranked by indexed fill rate and volume
+    async fn solver_leaderboard(&self, ctx: &Context<'_>, first: Option<u64>) -> GqlResult<Vec<SolverStats>> {
+        let limit = first.unwrap_or(20).clamp(1, 100);
+        let stats = state(ctx).indexer.solver_leaderboard(limit).await.map_err(indexer_error)?;
+        Ok(stats.into_iter().map(SolverStats::from).collect())
+    }
+
+    /// Every pool with indexed activity over the last week
+    async fn pools(&self, ctx: &Context<'_>) -> GqlResult<Vec<Pool>> {
+        let now = Utc::now();
+        let series = pool_analytics::load_series(&state(ctx).indexer, Granularity::Hourly, now - Duration::days(7), now)
+            .await
+            .map_err(gql_error)?;
+        Ok(series
+            .iter()
+            .map(|series| Pool::from(pool_analytics::summarize(series, now)))
+            .collect())
+    }
+
+    async fn bridge_transfer(&self, ctx: &Context<'_>, message_hash: String) -> GqlResult<Option<BridgeTransfer>> {
+        let hash = parse_h256(&message_hash, "message hash")?;
+        let transfer = state(ctx).indexer.find_bridge_transfer(hash).await.map_err(indexer_error)?;
+        Ok(transfer.map(BridgeTransfer::from))
+    }
+
+    /// Transfers sent or received by `account`, by default the caller
+    async fn bridge_transfers(
+        &self,
+        ctx: &Context<'_>,
+        account: Option<String>,
+        first: Option<u64>,
+    ) -> GqlResult<Vec<BridgeTransfer>> {
+        let account = match account {
+            Some(account) => parse_address(&account)?,
+            None => extract_user_address(caller(ctx)?).map_err(gql_error)?,
+        };
+        let limit = first.unwrap_or(20).clamp(1, 100);
+        let transfers = state(ctx).indexer.bridge_transfers_for(account, limit).await.map_err(indexer_error)?;
+        Ok(transfers.into_iter().map(BridgeTransfer::from).collect())
+    }
+}
+
+/// A message from one of the broadcast channels
+#[derive(SimpleObject)]
+pub struct ChannelMessage {
+    pub message_type: String,
+    /// Position in the channel
+    pub seq: Option<u64>,
+    pub timestamp: DateTime<Utc>,
+    pub data: Json<serde_json::Value>,
+}
+
+impl From<WebSocketMessage> for ChannelMessage {
+    fn from(message: WebSocketMessage) -> Self {
+        Self {
+            message_type: message.message_type,
+            seq: message.seq,
+            timestamp: message.timestamp,
+            data: Json(message.data),
+        }
+    }
+}
+
+/// Live messages on `channel`; a lagging subscriber skips what it missed
+async fn channel_messages(channel: SubscriptionChannel) -> impl Stream<Item = ChannelMessage> {
+    let (receiver, _) = WS_MANAGER.subscribe_from(&channel, None).await;
+    stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(message) => return Some((ChannelMessage::from(message), receiver)),
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::debug!("GraphQL subscriber lagged, {} messages dropped", missed);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+pub struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    /// Status updates for one intent
+    async fn intent_updates(&self, ctx: &Context<'_>, intent_id: String) -> GqlResult<impl Stream<Item = ChannelMessage>> {
+        caller(ctx)?;
+        let intent_id = parse_h256(&intent_id, "intent id")?;
+        Ok(channel_messages(SubscriptionChannel::IntentUpdates(intent_id)).await)
+    }
+
+    /// Every newly submitted intent
+    async fn new_intents(&self) -> impl Stream<Item = ChannelMessage> {
+        channel_messages(SubscriptionChannel::NewIntents).await
+    }
+
+    async fn market_data(&self) -> impl Stream<Item = ChannelMessage> {
+        channel_messages(SubscriptionChannel::MarketData).await
+    }
+
+    /// Updates about the caller's own solver
+    async fn solver_updates(&self, ctx: &Context<'_>, address: String) -> GqlResult<impl Stream<Item = ChannelMessage>> {
+        let address = parse_address(&address)?;
+        if extract_user_address(caller(ctx)?).map_err(gql_error)? != address {
+            return Err(gql_error(ApiError::Authorization("Not your solver".to_string())));
+        }
+        Ok(channel_messages(SubscriptionChannel::SolverUpdates(address)).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_exposes_nested_types() {
+        let sdl = build_schema().sdl();
+        for field in ["bridgeTransfers", "execution", "solverLeaderboard", "series", "intentUpdates", "newIntents"] {
+            assert!(sdl.contains(field), "missing {}", field);
+        }
+        assert!(sdl.contains("type SolverStats"));
+    }
+
+    #[test]
+    fn test_errors_hide_internal_details() {
+        let error = gql_error(ApiError::Internal("connection refused at 10.0.0.3".to_string()));
+        assert_eq!(error.message, "Internal server error");
+
+        let error = gql_error(ApiError::Validation("Invalid address".to_string()));
+        assert_eq!(error.message, "Invalid address");
+        assert!(error.extensions.is_some());
+    }
+}
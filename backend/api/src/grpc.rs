@@ -0,0 +1,348 @@
+//! gRPC gateway for solvers
+//!
+//! Serves `proto/solver.proto` on its own port next to the HTTP API, for
+//! solver operators who want protobuf and HTTP/2 streams instead of JSON
+//! and WebSocket. It reads the same broadcast channels and writes the same
+//! tables, so intents reported here look the same to REST, WebSocket and
+//! webhook consumers. Only registered solvers that are active may call it.
+
+use chrono::{DateTime, Utc};
+use ethers::types::{Address, H256, U256};
+use futures_util::stream::{self, Stream};
+use serde::Deserialize;
+use std::{net::SocketAddr, pin::Pin, str::FromStr};
+use tokio::sync::broadcast::error::RecvError;
+use tonic::{metadata::MetadataMap, Request, Response, Status};
+
+use intents_engine::intent::Intent;
+
+use crate::{
+    api_keys::{self, API_KEY_HEADER},
+    auth::{extract_user_address, validate_jwt},
+    database::{intent_record_to_response, string_to_u256, IntentDb, SolverDb},
+    error::ApiError,
+    handlers::update_intent_status_and_broadcast,
+    models::{AppState, SolverRecord, SolverStatus, WebSocketMessage},
+    websocket::{broadcast_solver_quote, SubscriptionChannel, WS_MANAGER},
+};
+
+pub mod proto {
+    tonic::include_proto!("intents.solver.v1");
+}
+
+use proto::{
+    solver_gateway_server::{SolverGateway, SolverGatewayServer},
+    ExecutionAck, ExecutionReport, IntentEvent, StreamIntentsRequest, SubmitQuoteRequest, SubmitQuoteResponse,
+};
+
+/// Paths API keys are scoped against, as `api_keys::required_scope` sees them
+const STREAM_INTENTS_PATH: &str = "/intents.solver.v1.SolverGateway/StreamIntents";
+const SUBMIT_QUOTE_PATH: &str = "/intents.solver.v1.SolverGateway/SubmitQuote";
+const REPORT_EXECUTION_PATH: &str = "/intents.solver.v1.SolverGateway/ReportExecution";
+
+/// Serve the gateway on `address` until the process is told to stop
+pub async fn serve(state: AppState, address: SocketAddr) {
+    tracing::info!("Solver gRPC gateway listening on {}", address);
+
+    let result = tonic::transport::Server::builder()
+        .add_service(SolverGatewayServer::new(SolverGatewayService { state }))
+        .serve_with_shutdown(address, crate::shutdown_signal())
+        .await;
+    if let Err(e) = result {
+        tracing::error!("Solver gRPC gateway stopped: {}", e);
+    }
+}
+
+/// Same split as HTTP error responses; storage failures are logged, not
+/// returned
+fn status(error: ApiError) -> Status {
+    match error {
+        ApiError::Validation(message) | ApiError::BadRequest(message) => Status::invalid_argument(message),
+        ApiError::Authentication(message) => Status::unauthenticated(message),
+        ApiError::Jwt(_) => Status::unauthenticated("Invalid or expired token"),
+        ApiError::Authorization(message) => Status::permission_denied(message),
+        ApiError::NotFound(message) => Status::not_found(message),
+        ApiError::Conflict(message) => Status::failed_precondition(message),
+        ApiError::RateLimit => Status::resource_exhausted("Rate limit exceeded"),
+        ApiError::ServiceUnavailable(message) => Status::unavailable(message),
+        error => {
+            tracing::error!("Solver gRPC call failed: {}", error);
+            Status::internal("Internal server error")
+        }
+    }
+}
+
+fn parse_h256(value: &str, field: &str) -> Result<H256, Status> {
+    H256::from_str(value).map_err(|_| Status::invalid_argument(format!("Invalid {}", field)))
+}
+
+fn parse_amount(value: &str, field: &str) -> Result<U256, Status> {
+    string_to_u256(value).map_err(|_| Status::invalid_argument(format!("{} must be a decimal amount in wei", field)))
+}
+
+fn parse_optional_amount(value: Option<&str>, field: &str) -> Result<Option<U256>, Status> {
+    value.map(|value| parse_amount(value, field)).transpose()
+}
+
+pub struct SolverGatewayService {
+    state: AppState,
+}
+
+impl SolverGatewayService {
+    /// The calling solver, from a bearer JWT or an API key. `method` is
+    /// what the call would be over HTTP, for API key scopes.
+    async fn authenticate(&self, metadata: &MetadataMap, method: &str, path: &str) -> Result<SolverRecord, Status> {
+        let claims = match metadata.get(API_KEY_HEADER).and_then(|value| value.to_str().ok()) {
+            Some(key) => api_keys::authenticate(&self.state.db, key, method, path).await.map_err(status)?.0,
+            None => {
+                let token = metadata
+                    .get("authorization")
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.strip_prefix("Bearer "))
+                    .ok_or_else(|| Status::unauthenticated("Missing authorization or x-api-key metadata"))?;
+                validate_jwt(token, &self.state.config.jwt_secret).map_err(status)?
+            }
+        };
+
+        let address = extract_user_address(&claims).map_err(status)?;
+        let solver = SolverDb::get_solver_by_address(&self.state.db, address)
+            .await
+            .map_err(status)?
+            .ok_or_else(|| Status::permission_denied("Caller is not a registered solver"))?;
+        match solver.status() {
+            SolverStatus::Active => Ok(solver),
+            other => Err(Status::permission_denied(format!("Solver status is {:?}", other))),
+        }
+    }
+}
+
+type IntentStream = Pin<Box<dyn Stream<Item = Result<IntentEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl SolverGateway for SolverGatewayService {
+    type StreamIntentsStream = IntentStream;
+
+    async fn stream_intents(
+        &self,
+        request: Request<StreamIntentsRequest>,
+    ) -> Result<Response<Self::StreamIntentsStream>, Status> {
+        let solver = self.authenticate(request.metadata(), "GET", STREAM_INTENTS_PATH).await?;
+        let filter = request.into_inner();
+        tracing::debug!("Solver {} streaming intents over gRPC", solver.address);
+
+        let (receiver, _) = WS_MANAGER.subscribe_from(&SubscriptionChannel::NewIntents, None).await;
+        let stream = stream::unfold((receiver, filter, 0u64), |(mut receiver, filter, mut skipped)| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(message) => {
+                        let Some(event) = intent_event(&message, skipped) else { continue };
+                        if !matches_filter(&filter, &event) {
+                            continue;
+                        }
+                        return Some((Ok(event), (receiver, filter, 0)));
+                    }
+                    Err(RecvError::Lagged(missed)) => skipped += missed,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn submit_quote(
+        &self,
+        request: Request<SubmitQuoteRequest>,
+    ) -> Result<Response<SubmitQuoteResponse>, Status> {
+        let solver = self.authenticate(request.metadata(), "POST", SUBMIT_QUOTE_PATH).await?;
+        let quote = request.into_inner();
+        let intent_id = parse_h256(&quote.intent_id, "intent_id")?;
+        let dest_amount = parse_amount(&quote.dest_amount, "dest_amount")?;
+        let estimated_gas = parse_optional_amount(quote.estimated_gas.as_deref(), "estimated_gas")?;
+
+        let now = Utc::now();
+        let expires_at = match quote.expires_at {
+            Some(secs) => Some(
+                DateTime::from_timestamp(secs, 0)
+                    .filter(|expires_at| *expires_at > now)
+                    .ok_or_else(|| Status::invalid_argument("expires_at must be in the future"))?,
+            ),
+            None => None,
+        };
+
+        let record = IntentDb::get_intent_by_id(&self.state.db, intent_id)
+            .await
+            .map_err(status)?
+            .ok_or_else(|| Status::not_found("Intent not found"))?;
+        if record.status != "pending" {
+            return Err(Status::failed_precondition(format!("Intent is {}, not open for quotes", record.status)));
+        }
+        let intent = intent_record_to_response(record).map_err(status)?;
+        if intent.deadline <= now {
+            return Err(Status::failed_precondition("Intent deadline has passed"));
+        }
+        if !solver.supported_chains.contains(&(intent.source_chain_id as i64)) {
+            return Err(Status::failed_precondition("Solver does not support the intent's source chain"));
+        }
+        if dest_amount < intent.min_dest_amount {
+            return Err(Status::invalid_argument("Quote is below the intent's min_dest_amount"));
+        }
+
+        let solver_address = Address::from_str(&solver.address).map_err(|_| Status::internal("Invalid solver address"))?;
+        self.state
+            .indexer
+            .record_quote(intent_id, solver_address, dest_amount)
+            .await
+            .map_err(|e| status(ApiError::Internal(format!("Failed to record quote: {}", e))))?;
+
+        broadcast_solver_quote(intent_id, serde_json::json!({
+            "intent_id": intent_id,
+            "solver_address": solver_address,
+            "dest_amount": dest_amount.to_string(),
+            "estimated_gas": estimated_gas.map(|gas| gas.to_string()),
+            "estimated_time_secs": quote.estimated_time_secs,
+            "expires_at": expires_at,
+        })).await;
+
+        Ok(Response::new(SubmitQuoteResponse { quoted_at: now.timestamp() }))
+    }
+
+    async fn report_execution(
+        &self,
+        request: Request<ExecutionReport>,
+    ) -> Result<Response<ExecutionAck>, Status> {
+        let solver = self.authenticate(request.metadata(), "POST", REPORT_EXECUTION_PATH).await?;
+        let report = request.into_inner();
+        let intent_id = parse_h256(&report.intent_id, "intent_id")?;
+        let tx_hash = report.tx_hash.as_deref().map(|hash| parse_h256(hash, "tx_hash")).transpose()?;
+        let actual_dest_amount = parse_optional_amount(report.actual_dest_amount.as_deref(), "actual_dest_amount")?;
+        let gas_used = parse_optional_amount(report.gas_used.as_deref(), "gas_used")?;
+        let fees_paid = parse_optional_amount(report.fees_paid.as_deref(), "fees_paid")?;
+
+        if report.success && (tx_hash.is_none() || actual_dest_amount.is_none()) {
+            return Err(Status::invalid_argument("A successful execution needs tx_hash and actual_dest_amount"));
+        }
+
+        let record = IntentDb::get_intent_by_id(&self.state.db, intent_id)
+            .await
+            .map_err(status)?
+            .ok_or_else(|| Status::not_found("Intent not found"))?;
+        if !matches!(record.status.as_str(), "pending" | "matched") {
+            return Err(Status::failed_precondition(format!("Intent is already {}", record.status)));
+        }
+        // Once matched, only the matched solver may report
+        if record.solver_address.as_ref().is_some_and(|matched| *matched != solver.address) {
+            return Err(Status::permission_denied("Intent is matched to another solver"));
+        }
+
+        let solver_address = Address::from_str(&solver.address).map_err(|_| Status::internal("Invalid solver address"))?;
+        let (new_status, step) = if report.success {
+            ("completed", "Executed")
+        } else {
+            ("failed", "Execution failed")
+        };
+        let error = if report.success {
+            None
+        } else {
+            Some(report.error.unwrap_or_else(|| "Execution failed".to_string()))
+        };
+
+        IntentDb::update_intent_status(
+            &self.state.db,
+            intent_id,
+            new_status,
+            Some(solver_address),
+            tx_hash,
+            actual_dest_amount,
+            gas_used,
+            fees_paid,
+            error.clone(),
+        )
+        .await
+        .map_err(status)?;
+
+        let details = serde_json::json!({
+            "solver_address": solver_address,
+            "execution_tx_hash": tx_hash,
+            "actual_dest_amount": actual_dest_amount.map(|amount| amount.to_string()),
+            "error": error,
+        });
+        update_intent_status_and_broadcast(&self.state, intent_id, new_status, step, 100.0, Some(details))
+            .await
+            .map_err(status)?;
+
+        tracing::info!("Solver {} reported intent {:?} as {}", solver.address, intent_id, new_status);
+        Ok(Response::new(ExecutionAck { status: new_status.to_string() }))
+    }
+}
+
+/// A `new_intent` broadcast as sent to gRPC clients
+fn intent_event(message: &WebSocketMessage, skipped: u64) -> Option<IntentEvent> {
+    #[derive(Deserialize)]
+    struct NewIntent {
+        intent_id: H256,
+        intent: Intent,
+    }
+
+    let NewIntent { intent_id, intent } = serde_json::from_value(message.data.clone()).ok()?;
+    Some(IntentEvent {
+        intent_id: format!("{:#x}", intent_id),
+        user: format!("{:#x}", intent.user),
+        source_chain_id: intent.source_chain_id,
+        dest_chain_id: intent.dest_chain_id,
+        source_token: format!("{:#x}", intent.source_token),
+        dest_token: format!("{:#x}", intent.dest_token),
+        source_amount: intent.source_amount.to_string(),
+        min_dest_amount: intent.min_dest_amount.to_string(),
+        deadline: intent.deadline,
+        seq: message.seq.unwrap_or_default(),
+        skipped,
+    })
+}
+
+fn matches_filter(filter: &StreamIntentsRequest, event: &IntentEvent) -> bool {
+    (filter.source_chain_ids.is_empty() || filter.source_chain_ids.contains(&event.source_chain_id))
+        && (filter.dest_chain_ids.is_empty() || filter.dest_chain_ids.contains(&event.dest_chain_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intent_event_from_broadcast() {
+        let intent = Intent {
+            source_chain_id: 1,
+            dest_chain_id: 10,
+            source_amount: U256::from(1_000),
+            ..Default::default()
+        };
+        let message = WebSocketMessage {
+            message_type: "new_intent".to_string(),
+            data: serde_json::json!({ "intent_id": H256::repeat_byte(0xab), "intent": intent }),
+            timestamp: Utc::now(),
+            seq: Some(7),
+            channel: None,
+        };
+
+        let event = intent_event(&message, 3).unwrap();
+        assert_eq!(event.intent_id, format!("{:#x}", H256::repeat_byte(0xab)));
+        assert_eq!(event.source_amount, "1000");
+        assert_eq!((event.seq, event.skipped), (7, 3));
+
+        let filter = StreamIntentsRequest { source_chain_ids: vec![1], dest_chain_ids: vec![] };
+        assert!(matches_filter(&filter, &event));
+        let filter = StreamIntentsRequest { source_chain_ids: vec![], dest_chain_ids: vec![137] };
+        assert!(!matches_filter(&filter, &event));
+    }
+
+    #[test]
+    fn test_errors_map_to_grpc_codes() {
+        assert_eq!(status(ApiError::Validation("bad".to_string())).code(), tonic::Code::InvalidArgument);
+        assert_eq!(status(ApiError::Authorization("no".to_string())).code(), tonic::Code::PermissionDenied);
+
+        let internal = status(ApiError::Internal("connection refused at 10.0.0.3".to_string()));
+        assert_eq!(internal.code(), tonic::Code::Internal);
+        assert_eq!(internal.message(), "Internal server error");
+    }
+}
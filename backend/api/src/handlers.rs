@@ -61,6 +61,23 @@ pub async fn update_intent_status_and_broadcast(
     
     broadcast_intent_update(intent_id, update_msg).await;
     
+    // Webhooks go to the intent's owner
+    let event = match status {
+        "matched" => Some("intent.matched"),
+        "completed" | "executed" => Some("intent.executed"),
+        "failed" => Some("intent.failed"),
+        "cancelled" => Some("intent.cancelled"),
+        _ => None,
+    };
+    if let Some(event) = event {
+        if let Some(record) = crate::database::IntentDb::get_intent_by_id(&state.db, intent_id).await? {
+            let response = crate::database::intent_record_to_response(record)?;
+            let data = serde_json::to_value(&response)?;
+            crate::notifications::publish(&state.db, event, response.user_address, &data).await;
+            crate::webhooks::publish(&state.db, event, response.user_address, data).await;
+        }
+    }
+    
     Ok(())
 }
 
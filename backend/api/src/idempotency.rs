@@ -0,0 +1,152 @@
+//! Idempotency keys for intent submission and cancellation
+//!
+//! A client that times out can't tell whether its intent was accepted, so
+//! it may send an `Idempotency-Key` header and retry with the same key. The
+//! first response for a (caller, key) pair is kept in Redis and returned
+//! for every retry instead of running the request again. Reusing a key for
+//! a different request is rejected, as is a retry that arrives while the
+//! first attempt is still running. Server errors are not stored, so those
+//! requests can be retried with the same key.
+
+use axum::{
+    body::Body,
+    http::{header, HeaderValue, Method, StatusCode},
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+use crate::error::{validation_error, Result};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// Set on responses replayed from an earlier request
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+/// How long a stored response answers retries
+pub const RESPONSE_TTL: Duration = Duration::from_secs(24 * 3_600);
+/// How long an unfinished request holds its key, in case the instance
+/// handling it dies
+pub const IN_PROGRESS_TTL: Duration = Duration::from_secs(60);
+
+const MAX_KEY_LEN: usize = 255;
+
+/// Intent submission and cancellation, with or without the `/api/v1`
+/// prefix
+pub fn applies_to(method: &Method, path: &str) -> bool {
+    if method != Method::POST {
+        return false;
+    }
+    let path = path.strip_prefix("/api/v1/intents").unwrap_or(path);
+    match path.trim_end_matches('/').split('/').collect::<Vec<_>>().as_slice() {
+        [""] => true,
+        ["", intent_id, "cancel"] => !intent_id.is_empty(),
+        _ => false,
+    }
+}
+
+pub fn validate_key(key: &str) -> Result<()> {
+    if key.is_empty() || key.len() > MAX_KEY_LEN || !key.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err(validation_error(format!(
+            "{} must be 1-{} printable ASCII characters",
+            IDEMPOTENCY_KEY_HEADER, MAX_KEY_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Identifies the request a key was first used for
+pub fn fingerprint(method: &Method, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b" ");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum IdempotencyEntry {
+    InProgress { fingerprint: String },
+    Complete(StoredResponse),
+}
+
+impl IdempotencyEntry {
+    pub fn fingerprint(&self) -> &str {
+        match self {
+            Self::InProgress { fingerprint } => fingerprint,
+            Self::Complete(response) => &response.fingerprint,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredResponse {
+    pub fingerprint: String,
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+impl StoredResponse {
+    /// Only text bodies are kept; every response these endpoints send is JSON
+    pub fn capture(fingerprint: String, status: StatusCode, content_type: Option<&HeaderValue>, body: &[u8]) -> Option<Self> {
+        Some(Self {
+            fingerprint,
+            status: status.as_u16(),
+            content_type: content_type.and_then(|value| value.to_str().ok()).map(str::to_string),
+            body: String::from_utf8(body.to_vec()).ok()?,
+        })
+    }
+
+    pub fn replay(self) -> Response {
+        let mut response = Response::new(Body::from(self.body));
+        *response.status_mut() = StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK);
+        if let Some(value) = self.content_type.and_then(|value| HeaderValue::from_str(&value).ok()) {
+            response.headers_mut().insert(header::CONTENT_TYPE, value);
+        }
+        response.headers_mut().insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_applies_to_intent_mutations() {
+        assert!(applies_to(&Method::POST, "/api/v1/intents"));
+        assert!(applies_to(&Method::POST, "/api/v1/intents/"));
+        assert!(applies_to(&Method::POST, "/api/v1/intents/0xabc/cancel"));
+        assert!(applies_to(&Method::POST, "/"));
+        assert!(applies_to(&Method::POST, "/0xabc/cancel"));
+        assert!(!applies_to(&Method::GET, "/api/v1/intents"));
+        assert!(!applies_to(&Method::POST, "/api/v1/intents/quote"));
+        assert!(!applies_to(&Method::POST, "/api/v1/webhooks"));
+
+        assert!(validate_key("5f1c9a3e-retry").is_ok());
+        assert!(validate_key("").is_err());
+        assert!(validate_key("has space").is_err());
+        assert!(validate_key(&"k".repeat(256)).is_err());
+    }
+
+    #[test]
+    fn test_fingerprint_and_replay() {
+        let first = fingerprint(&Method::POST, "/api/v1/intents", br#"{"source_amount":"1"}"#);
+        assert_eq!(first, fingerprint(&Method::POST, "/api/v1/intents", br#"{"source_amount":"1"}"#));
+        assert_ne!(first, fingerprint(&Method::POST, "/api/v1/intents", br#"{"source_amount":"2"}"#));
+
+        let content_type = HeaderValue::from_static("application/json");
+        let stored = StoredResponse::capture(first.clone(), StatusCode::OK, Some(&content_type), b"{}").unwrap();
+        assert_eq!(IdempotencyEntry::Complete(stored.clone()).fingerprint(), first);
+        assert!(StoredResponse::capture(first, StatusCode::OK, None, &[0xff, 0xfe]).is_none());
+
+        let response = stored.replay();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(response.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+    }
+}
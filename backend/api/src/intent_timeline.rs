@@ -0,0 +1,350 @@
+//! Lifecycle timeline of a single intent
+//!
+//! No one source has the whole story. The API's record holds what was asked
+//! for and the final amounts, the engine's audit log holds every decision
+//! (matching, execution attempts, bridge dispatches, status changes) and
+//! the indexer holds what actually landed on chain, including each bridge
+//! leg's transactions on both sides. `build` merges the three into one
+//! ordered list of events. The indexer trails the chain, so its events show
+//! up a few blocks after the engine's.
+
+use chrono::{DateTime, TimeZone, Utc};
+use ethers::types::H256;
+use futures_util::future::join_all;
+use intents_engine::audit::{AuditEvent, AuditEventKind, IntentReplay};
+use intents_indexer::{
+    journeys::IntentJourney,
+    storage::IndexerStorage,
+    transfers::{BridgeTransfer, TransferStatus},
+};
+
+use crate::{
+    database::{string_to_address, string_to_h256, string_to_u256},
+    error::Result,
+    models::{IntentRecord, IntentTimeline, TimelineBridgeLeg, TimelineEvent},
+};
+
+/// What the indexer knows about an intent: its journey and the transfers
+/// behind each bridge leg. Indexer errors leave the timeline without them
+/// rather than failing it.
+pub async fn load_onchain(indexer: &IndexerStorage, intent_id: H256) -> (Option<IntentJourney>, Vec<BridgeTransfer>) {
+    let journey = match indexer.query_intent_journey(intent_id).await {
+        Ok(journey) => journey,
+        Err(e) => {
+            tracing::warn!("Failed to load journey for intent {:#x}: {}", intent_id, e);
+            return (None, Vec::new());
+        }
+    };
+    let Some(journey) = journey else {
+        return (None, Vec::new());
+    };
+
+    let transfers = join_all(journey.bridge_legs.iter().map(|leg| indexer.find_bridge_transfer(leg.message_hash))).await;
+    let transfers = transfers
+        .into_iter()
+        .filter_map(|transfer| match transfer {
+            Ok(transfer) => transfer,
+            Err(e) => {
+                tracing::warn!("Failed to load bridge transfer for intent {:#x}: {}", intent_id, e);
+                None
+            }
+        })
+        .collect();
+    (Some(journey), transfers)
+}
+
+fn event(stage: &str, timestamp: DateTime<Utc>, source: &str) -> TimelineEvent {
+    TimelineEvent {
+        stage: stage.to_string(),
+        timestamp,
+        source: source.to_string(),
+        chain_id: None,
+        tx_hash: None,
+        detail: None,
+    }
+}
+
+fn status_name(status: impl std::fmt::Debug) -> String {
+    format!("{:?}", status).to_lowercase()
+}
+
+/// One engine audit event on the timeline
+fn engine_event(audit: &AuditEvent) -> TimelineEvent {
+    let timestamp = Utc.timestamp_opt(audit.timestamp as i64, 0).single().unwrap_or_default();
+    let engine = |stage: &str| event(stage, timestamp, "engine");
+
+    match &audit.kind {
+        AuditEventKind::ValidationFailed { reason } => TimelineEvent {
+            detail: Some(reason.to_string()),
+            ..engine("rejected")
+        },
+        AuditEventKind::Queued { .. } => engine("queued"),
+        AuditEventKind::Matched { source_chain_id, .. } => TimelineEvent {
+            chain_id: Some(*source_chain_id),
+            ..engine("matched")
+        },
+        AuditEventKind::Simulated { success, revert_reason, .. } => TimelineEvent {
+            detail: if *success { None } else { revert_reason.clone().or_else(|| Some("Simulation failed".to_string())) },
+            ..engine("simulated")
+        },
+        AuditEventKind::ExecutionAttempt { step, attempt, error } => TimelineEvent {
+            detail: Some(match error {
+                Some(error) => format!("{} (attempt {}) failed: {}", step, attempt, error),
+                None => format!("{} (attempt {})", step, attempt),
+            }),
+            ..engine("execution_attempt")
+        },
+        AuditEventKind::AssetsLocked { chain_id, tx_hash, .. } => TimelineEvent {
+            chain_id: Some(*chain_id),
+            tx_hash: Some(*tx_hash),
+            ..engine("assets_locked")
+        },
+        AuditEventKind::BridgeDispatched { chain_id, dest_chain_id, tx_hash, message_id } => TimelineEvent {
+            chain_id: Some(*chain_id),
+            tx_hash: Some(*tx_hash),
+            detail: Some(format!("Message {:#x} to chain {}", message_id, dest_chain_id)),
+            ..engine("bridge_dispatched")
+        },
+        AuditEventKind::StatusChanged { to, reason, .. } => TimelineEvent {
+            detail: reason.clone(),
+            ..engine(&status_name(to))
+        },
+    }
+}
+
+/// Merge the API record, the engine's replay and what the indexer saw into
+/// one timeline
+pub fn build(
+    record: &IntentRecord,
+    replay: Option<&IntentReplay>,
+    journey: Option<&IntentJourney>,
+    transfers: &[BridgeTransfer],
+) -> Result<IntentTimeline> {
+    let source_chain_id = record.source_chain_id as u64;
+    let execution_tx = record.execution_tx_hash.as_deref().map(string_to_h256).transpose()?;
+
+    let mut events = vec![TimelineEvent {
+        chain_id: Some(source_chain_id),
+        ..event("submitted", record.created_at, "api")
+    }];
+    events.extend(replay.iter().flat_map(|replay| replay.events.iter().map(engine_event)));
+
+    if let Some(journey) = journey {
+        let chain_id = journey.source_chain_id.or(Some(source_chain_id));
+        let onchain = [
+            ("created", journey.created_at, journey.user),
+            ("solver_matched", journey.matched_at, journey.solver),
+            ("executed", journey.executed_at, journey.solver),
+        ];
+        for (stage, at, account) in onchain {
+            if let Some(at) = at {
+                events.push(TimelineEvent {
+                    chain_id,
+                    detail: account.map(|account| format!("{:#x}", account)),
+                    ..event(stage, at, "indexer")
+                });
+            }
+        }
+    }
+
+    let bridge_legs: Vec<TimelineBridgeLeg> = journey
+        .map(|journey| journey.bridge_legs.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .map(|leg| {
+            let transfer = transfers.iter().find(|transfer| transfer.message_hash == leg.message_hash);
+            let status = match transfer {
+                Some(transfer) => status_name(transfer.status),
+                None if leg.withdrawn_at.is_some() => status_name(TransferStatus::Completed),
+                None => status_name(TransferStatus::Pending),
+            };
+            TimelineBridgeLeg {
+                message_hash: leg.message_hash,
+                source_chain_id: leg.source_chain_id,
+                destination_chain_id: leg.destination_chain_id,
+                source_tx: transfer.and_then(|transfer| transfer.source_tx),
+                destination_tx: transfer.and_then(|transfer| transfer.destination_tx),
+                amount: transfer.map(|transfer| transfer.amount),
+                sent_at: leg.deposited_at,
+                received_at: leg.withdrawn_at,
+                status,
+            }
+        })
+        .collect();
+
+    for leg in &bridge_legs {
+        let detail = Some(format!("Message {:#x}", leg.message_hash));
+        if let Some(at) = leg.sent_at {
+            events.push(TimelineEvent {
+                chain_id: Some(leg.source_chain_id),
+                tx_hash: leg.source_tx,
+                detail: detail.clone(),
+                ..event("bridge_sent", at, "indexer")
+            });
+        }
+        if let Some(at) = leg.received_at {
+            events.push(TimelineEvent {
+                chain_id: Some(leg.destination_chain_id),
+                tx_hash: leg.destination_tx,
+                detail,
+                ..event("bridge_received", at, "indexer")
+            });
+        }
+    }
+    // Stable, so events with the same timestamp keep their source's order
+    events.sort_by_key(|event| event.timestamp);
+
+    let destination_tx = if bridge_legs.is_empty() {
+        execution_tx
+    } else {
+        bridge_legs.iter().max_by_key(|leg| leg.received_at).and_then(|leg| leg.destination_tx)
+    };
+
+    Ok(IntentTimeline {
+        intent_id: string_to_h256(&record.intent_id)?,
+        status: replay
+            .and_then(|replay| replay.status)
+            .map(status_name)
+            .unwrap_or_else(|| record.status.clone()),
+        user_address: string_to_address(&record.user_address)?,
+        solver_address: match &record.solver_address {
+            Some(solver) => Some(string_to_address(solver)?),
+            None => journey.and_then(|journey| journey.solver),
+        },
+        source_chain_id,
+        dest_chain_id: record.dest_chain_id as u64,
+        source_token: string_to_address(&record.source_token)?,
+        dest_token: string_to_address(&record.dest_token)?,
+        source_amount: string_to_u256(&record.source_amount)?,
+        min_dest_amount: string_to_u256(&record.min_dest_amount)?,
+        final_dest_amount: record.actual_dest_amount.as_deref().map(string_to_u256).transpose()?,
+        fees_paid: record.fees_paid.as_deref().map(string_to_u256).transpose()?,
+        destination_tx,
+        created_at: record.created_at,
+        completed_at: journey.and_then(IntentJourney::completed_at),
+        bridge_legs,
+        events,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use ethers::types::{Address, U256};
+    use intents_engine::{intent::IntentStatus, lifecycle::Causer};
+    use intents_indexer::journeys::{BridgeLeg, PhaseLatency};
+    use uuid::Uuid;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap() + Duration::minutes(minutes)
+    }
+
+    fn record() -> IntentRecord {
+        IntentRecord {
+            id: Uuid::nil(),
+            intent_id: format!("{:#x}", H256::repeat_byte(1)),
+            source_chain_id: 1,
+            dest_chain_id: 10,
+            source_token: format!("{:#x}", Address::repeat_byte(2)),
+            dest_token: format!("{:#x}", Address::repeat_byte(3)),
+            source_amount: "1000".to_string(),
+            min_dest_amount: "990".to_string(),
+            actual_dest_amount: Some("995".to_string()),
+            deadline: at(60),
+            user_address: format!("{:#x}", Address::repeat_byte(4)),
+            solver_address: None,
+            status: "pending".to_string(),
+            execution_tx_hash: Some(format!("{:#x}", H256::repeat_byte(5))),
+            created_at: at(0),
+            updated_at: at(0),
+            gas_used: None,
+            fees_paid: None,
+            error_message: None,
+        }
+    }
+
+    fn audit(minutes: i64, kind: AuditEventKind) -> AuditEvent {
+        AuditEvent {
+            sequence: 0,
+            intent_id: H256::repeat_byte(1),
+            timestamp: at(minutes).timestamp() as u64,
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_record_only_timeline() {
+        let timeline = build(&record(), None, None, &[]).unwrap();
+        assert_eq!(timeline.status, "pending");
+        assert_eq!(timeline.events.len(), 1);
+        assert_eq!(timeline.events[0].stage, "submitted");
+        assert_eq!(timeline.final_dest_amount, Some(U256::from(995)));
+        // Nothing bridged, so delivery is the execution itself
+        assert_eq!(timeline.destination_tx, Some(H256::repeat_byte(5)));
+        assert!(timeline.completed_at.is_none());
+    }
+
+    #[test]
+    fn test_merges_engine_and_indexer_in_order() {
+        let solver = Address::repeat_byte(6);
+        let message_hash = H256::repeat_byte(7);
+        let replay = IntentReplay::from_events(
+            H256::repeat_byte(1),
+            vec![
+                audit(1, AuditEventKind::Matched { source_chain_id: 1, dest_chain_id: 10, reserved: U256::from(995) }),
+                audit(3, AuditEventKind::BridgeDispatched { chain_id: 1, dest_chain_id: 10, tx_hash: H256::repeat_byte(8), message_id: message_hash }),
+                audit(9, AuditEventKind::StatusChanged { from: IntentStatus::Executing, to: IntentStatus::Settled, causer: Causer::Executor, reason: None }),
+            ],
+        );
+        let journey = IntentJourney {
+            intent_id: H256::repeat_byte(1),
+            user: Some(Address::repeat_byte(4)),
+            solver: Some(solver),
+            source_chain_id: Some(1),
+            created_at: Some(at(0)),
+            matched_at: Some(at(2)),
+            executed_at: Some(at(4)),
+            success: Some(true),
+            bridge_legs: vec![BridgeLeg {
+                message_hash,
+                source_chain_id: 1,
+                destination_chain_id: 10,
+                deposited_at: Some(at(4)),
+                withdrawn_at: Some(at(8)),
+            }],
+            latency: PhaseLatency::default(),
+        };
+        let transfer = BridgeTransfer {
+            message_hash,
+            nonce: U256::one(),
+            source_chain_id: 1,
+            destination_chain_id: 10,
+            sender: Some(Address::repeat_byte(4)),
+            recipient: Address::repeat_byte(4),
+            token: Address::repeat_byte(3),
+            amount: U256::from(995),
+            source_tx: Some(H256::repeat_byte(8)),
+            destination_tx: Some(H256::repeat_byte(9)),
+            sent_at: Some(at(4)),
+            received_at: Some(at(8)),
+            status: TransferStatus::Completed,
+            latency_ms: Some(240_000),
+        };
+
+        let timeline = build(&record(), Some(&replay), Some(&journey), &[transfer]).unwrap();
+        assert_eq!(timeline.status, "settled");
+        assert_eq!(timeline.solver_address, Some(solver));
+        assert_eq!(timeline.destination_tx, Some(H256::repeat_byte(9)));
+        assert_eq!(timeline.completed_at, Some(at(8)));
+        assert_eq!(timeline.bridge_legs[0].status, "completed");
+        assert_eq!(timeline.bridge_legs[0].source_tx, Some(H256::repeat_byte(8)));
+
+        let stages: Vec<&str> = timeline.events.iter().map(|event| event.stage.as_str()).collect();
+        assert_eq!(
+            stages,
+            ["submitted", "created", "matched", "solver_matched", "bridge_dispatched", "executed", "bridge_sent", "bridge_received", "settled"]
+        );
+        assert!(timeline.events.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+    }
+}
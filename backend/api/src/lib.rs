@@ -3,14 +3,33 @@ pub mod handlers;
 pub mod middleware;
 pub mod models;
 pub mod database;
+pub mod db_metrics;
 pub mod cache;
 pub mod websocket;
+pub mod ws_backplane;
 pub mod websocket_benchmarks;
 pub mod metrics;
 pub mod auth;
 pub mod error;
 pub mod config;
 pub mod crypto;
+pub mod pagination;
+pub mod quotes;
+pub mod simulation;
+pub mod webhooks;
+pub mod notifications;
+pub mod api_keys;
+pub mod audit;
+pub mod idempotency;
+pub mod export;
+pub mod intent_timeline;
+pub mod openapi;
+pub mod pool_analytics;
+pub mod market_data;
+pub mod rate_limit;
+pub mod response_cache;
+pub mod graphql;
+pub mod grpc;
 
 pub use config::Config;
 pub use error::{ApiError, Result};
@@ -23,35 +42,116 @@ use tower_http::{
     compression::CompressionLayer,
     timeout::TimeoutLayer,
 };
-use std::time::Duration;
-use metrics_exporter_prometheus::PrometheusBuilder;
+use std::{path::Path, sync::Arc, time::Duration};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
 
 pub async fn create_app(config: Config) -> Result<Router> {
+    let (app, _) = build_app(config).await?;
+    Ok(app)
+}
+
+/// Build the router along with the engine it drives, so the caller can shut
+/// the engine down cleanly
+pub async fn build_app(config: Config) -> Result<(Router, Arc<intents_engine::IntentsEngine>)> {
     // Initialize metrics
     let prometheus_handle = PrometheusBuilder::new()
-        .install()
+        .set_buckets_for_metric(
+            Matcher::Full(db_metrics::QUERY_DURATION_METRIC.to_string()),
+            db_metrics::QUERY_DURATION_BUCKETS,
+        )
+        .expect("query duration buckets are not empty")
+        .install_recorder()
         .expect("failed to install Prometheus recorder");
 
     // Initialize database
-    let db_pool = database::create_pool(&config.database_url).await?;
+    let db_pool = database::create_pool(&config.database_url, &config.database).await?;
     database::run_migrations(&db_pool).await?;
+    let pool_metrics_interval = Duration::from_secs(config.database.pool_metrics_interval_secs);
+    tokio::spawn(db_metrics::sample_pool("api", db_pool.clone(), pool_metrics_interval));
 
     // Initialize Redis
     let redis_client = cache::create_client(&config.redis_url).await?;
 
-    // Initialize intents engine
-    let intents_engine = intents_engine::IntentsEngine::new(config.chains.clone()).await
+    // Relay WebSocket broadcasts between instances
+    if config.websocket.backplane_enabled {
+        ws_backplane::start(&config, redis_client.clone()).await?;
+    }
+
+    // Initialize intents engine, auditing its decisions to the API database
+    let audit_store = intents_engine::audit::PostgresAuditStore::connect(&config.database_url).await
+        .map_err(|e| ApiError::Internal(format!("Failed to open engine audit log: {}", e)))?;
+    let engine_store: Arc<dyn intents_engine::store::StateStore> = if config.engine_store.durable {
+        let url = config.engine_store.database_url.as_deref().unwrap_or(&config.database_url);
+        let store = intents_engine::store::PostgresStore::connect(url).await
+            .map_err(|e| ApiError::Internal(format!("Failed to open engine state store: {}", e)))?;
+        Arc::new(store)
+    } else {
+        tracing::warn!("Intents engine state is held in memory; pending intents are lost on restart");
+        Arc::new(intents_engine::store::MemoryStore::new())
+    };
+    // Intents recovered from the store are queued here; resuming from the
+    // checkpoint below skips any it has already queued
+    let intents_engine = intents_engine::IntentsEngine::with_stores(
+        config.chains.clone(),
+        engine_store,
+        Arc::new(audit_store),
+    ).await
         .map_err(|e| ApiError::Internal(format!("Failed to initialize intents engine: {}", e)))?;
+    let intents_engine = Arc::new(intents_engine);
+
+    // Pick up work checkpointed by a previous shutdown
+    match intents_engine.resume_from_checkpoint(Path::new(&config.engine_checkpoint_path)).await {
+        Ok(true) => tracing::info!("Resumed intents engine from {}", config.engine_checkpoint_path),
+        Ok(false) => {}
+        Err(e) => tracing::error!("Failed to resume from checkpoint: {}", e),
+    }
+
+    let quotes = Arc::new(quotes::QuoteAggregator::new(&config)?);
+    let simulator = Arc::new(simulation::IntentSimulator::new(&config).await?);
+
+    // Deliver queued webhook events in the background
+    let webhook_dispatcher = webhooks::WebhookDispatcher::new(db_pool.clone(), config.webhooks.clone())?;
+    tokio::spawn(webhook_dispatcher.run());
+
+    // Email and Telegram notifications go out the same way
+    let notification_dispatcher = notifications::NotificationDispatcher::new(db_pool.clone(), config.notifications.clone())?;
+    tokio::spawn(notification_dispatcher.run());
+
+    let indexer_url = config.indexer_database_url.as_deref().unwrap_or(&config.database_url);
+    let indexer = intents_indexer::storage::IndexerStorage::new(indexer_url).await
+        .map_err(|e| ApiError::Internal(format!("Failed to connect to indexer database: {}", e)))?;
+    let indexer = Arc::new(indexer);
+    tokio::spawn(db_metrics::sample_pool("indexer", indexer.pool().clone(), pool_metrics_interval));
+
+    // Tickers for the market data channel, from indexed swaps
+    if config.market_data.enabled {
+        tokio::spawn(market_data::run(indexer.clone(), redis_client.clone(), config.market_data.clone()));
+    }
+
+    // Cached analytics go stale whenever the indexer writes new rollups
+    tokio::spawn(response_cache::watch_rollups(indexer_url.to_string(), redis_client.clone()));
 
     // Create application state
     let app_state = models::AppState {
         db: db_pool,
         redis: redis_client,
-        intents_engine,
+        intents_engine: intents_engine.clone(),
+        quotes,
+        simulator,
+        indexer,
+        graphql: graphql::build_schema(),
         config: config.clone(),
         prometheus_handle,
     };
 
+    // Solvers can also connect over gRPC, on a port of its own
+    if let Some(address) = &config.grpc_address {
+        let address = address
+            .parse()
+            .map_err(|e| ApiError::Internal(format!("Invalid gRPC address {}: {}", address, e)))?;
+        tokio::spawn(grpc::serve(app_state.clone(), address));
+    }
+
     // Build middleware stack
     let middleware = ServiceBuilder::new()
         .layer(TraceLayer::new_for_http())
@@ -61,13 +161,27 @@ pub async fn create_app(config: Config) -> Result<Router> {
             .allow_origin(Any)
             .allow_methods(Any)
             .allow_headers(Any))
+        // Authenticate first so the rate limiter can tell callers and API
+        // key tiers apart
+        .layer(axum_middleware::from_fn_with_state(
+            app_state.clone(),
+            middleware::auth
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            app_state.clone(),
+            middleware::audit
+        ))
         .layer(axum_middleware::from_fn_with_state(
             app_state.clone(),
             middleware::rate_limit
         ))
         .layer(axum_middleware::from_fn_with_state(
             app_state.clone(),
-            middleware::auth
+            middleware::idempotency
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            app_state.clone(),
+            middleware::response_cache
         ));
 
     // Build router
@@ -76,15 +190,50 @@ pub async fn create_app(config: Config) -> Result<Router> {
         .merge(routes::solver::routes())
         .merge(routes::analytics::routes())
         .merge(routes::health::routes())
+        .nest("/admin", routes::admin::routes())
+        .nest("/webhooks", routes::webhooks::routes())
+        .nest("/api-keys", routes::api_keys::routes())
+        .nest("/notifications", routes::notifications::routes())
+        .nest("/stream", routes::stream::routes())
+        .nest("/graphql", routes::graphql::routes())
+        .nest("/export", routes::export::routes())
         .route("/ws", axum::routing::get(websocket::websocket_handler))
+        .merge(openapi::routes())
         .layer(middleware)
         .with_state(app_state);
 
-    Ok(app)
+    Ok((app, intents_engine))
+}
+
+/// Resolves on Ctrl+C or SIGTERM
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("Shutdown signal received");
 }
 
 pub async fn start_server(config: Config) -> Result<()> {
-    let app = create_app(config.clone()).await?;
+    let (app, intents_engine) = build_app(config.clone()).await?;
     
     // Start WebSocket health monitoring
     websocket::start_health_monitoring().await;
@@ -97,8 +246,17 @@ pub async fn start_server(config: Config) -> Result<()> {
     tracing::info!("Server starting on {}", config.server_address);
     
     axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
         .await
         .map_err(|e| ApiError::Internal(format!("Server error: {}", e)))?;
 
+    intents_engine
+        .shutdown(
+            Duration::from_secs(config.shutdown_timeout_secs),
+            Some(Path::new(&config.engine_checkpoint_path)),
+        )
+        .await
+        .map_err(|e| ApiError::Internal(format!("Engine shutdown failed: {}", e)))?;
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,335 @@
+//! Tickers for the `market_data` WebSocket channel
+//!
+//! Built from the swaps and liquidity changes the indexer records. The
+//! producer keeps the last 24 hours of them in memory and on every tick
+//! reads whatever the indexer has added since, then broadcasts a ticker for
+//! each pair that traded, or whose pool's liquidity changed, since the
+//! tick before. The first tick loads the whole window, so it sends every
+//! active pair.
+//!
+//! A pair's base token is the lower of its two addresses and prices are
+//! the base token's in quote tokens. Tickers already reach every instance
+//! through the WebSocket backplane, so only the instance holding a short
+//! lease in Redis produces them.
+
+use chrono::{DateTime, Duration, Utc};
+use ethers::types::{Address, U256};
+use intents_indexer::{
+    enrichment::to_units,
+    market::{LiquidityChange, SwapTrade},
+    storage::IndexerStorage,
+};
+use redis::{aio::MultiplexedConnection, Script};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet, VecDeque},
+    sync::{Arc, OnceLock},
+};
+use uuid::Uuid;
+
+use crate::{
+    cache::CacheKeys,
+    config::MarketDataConfig,
+    models::{MarketDataMessage, OrderflowStats},
+    websocket::broadcast_market_data,
+};
+
+const WINDOW_HOURS: i64 = 24;
+
+/// KEYS: the lease. ARGV: this instance, lease length in ms. Takes the
+/// lease if it is free and renews it if this instance already holds it.
+const LEASE_SCRIPT: &str = r#"
+local holder = redis.call('GET', KEYS[1])
+if holder == ARGV[1] then
+    redis.call('PEXPIRE', KEYS[1], ARGV[2])
+    return 1
+end
+if holder then
+    return 0
+end
+redis.call('SET', KEYS[1], ARGV[1], 'PX', ARGV[2])
+return 1
+"#;
+
+fn lease_script() -> &'static Script {
+    static SCRIPT: OnceLock<Script> = OnceLock::new();
+    SCRIPT.get_or_init(|| Script::new(LEASE_SCRIPT))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PairKey {
+    pub chain_id: u64,
+    pub pool_id: String,
+    pub base: Address,
+    pub quote: Address,
+}
+
+impl PairKey {
+    pub fn of(swap: &SwapTrade) -> Self {
+        Self {
+            chain_id: swap.chain_id,
+            pool_id: swap.pool_id.clone(),
+            base: swap.token_in.min(swap.token_out),
+            quote: swap.token_in.max(swap.token_out),
+        }
+    }
+}
+
+/// Base and quote amounts of a swap, in wei and in whole tokens
+fn sides(swap: &SwapTrade, base: Address) -> (U256, f64, f64) {
+    if swap.token_out == base {
+        (swap.amount_out, to_units(swap.amount_out, swap.decimals_out), to_units(swap.amount_in, swap.decimals_in))
+    } else {
+        (swap.amount_in, to_units(swap.amount_in, swap.decimals_in), to_units(swap.amount_out, swap.decimals_out))
+    }
+}
+
+/// Ticker for one pair from its swaps in the window, oldest first
+pub fn ticker(key: &PairKey, swaps: &[&SwapTrade], liquidity_changes: u64) -> Option<MarketDataMessage> {
+    let price = |swap: &SwapTrade| {
+        let (_, base, quote) = sides(swap, key.base);
+        (base > 0.0).then(|| quote / base)
+    };
+    let first = swaps.iter().find_map(|swap| price(swap))?;
+    let last_swap = swaps.last()?;
+    let last = price(last_swap).unwrap_or(first);
+
+    let mut buy_volume = U256::zero();
+    let mut sell_volume = U256::zero();
+    let mut buys = 0;
+    let mut traders = HashSet::new();
+    for swap in swaps {
+        let (amount, _, _) = sides(swap, key.base);
+        if swap.token_out == key.base {
+            buys += 1;
+            buy_volume = buy_volume.saturating_add(amount);
+        } else {
+            sell_volume = sell_volume.saturating_add(amount);
+        }
+        traders.insert(swap.trader);
+    }
+
+    let bought = to_units(buy_volume, 0);
+    let sold = to_units(sell_volume, 0);
+    let imbalance = if bought + sold > 0.0 { (bought - sold) / (bought + sold) } else { 0.0 };
+
+    Some(MarketDataMessage {
+        chain_id: key.chain_id,
+        pool_id: key.pool_id.clone(),
+        token_pair: (key.base, key.quote),
+        price: last,
+        volume_24h: buy_volume.saturating_add(sell_volume),
+        price_change_24h: if first > 0.0 { (last - first) / first } else { 0.0 },
+        orderflow: OrderflowStats {
+            swaps: swaps.len() as u64,
+            buys,
+            sells: swaps.len() as u64 - buys,
+            buy_volume,
+            sell_volume,
+            imbalance,
+            unique_traders: traders.len() as u64,
+            liquidity_changes,
+        },
+        last_trade_at: last_swap.timestamp,
+    })
+}
+
+/// The last 24 hours of swaps and liquidity changes, and what changed
+/// since tickers were last taken
+#[derive(Debug, Default)]
+pub struct MarketWindow {
+    swaps: VecDeque<SwapTrade>,
+    liquidity: VecDeque<LiquidityChange>,
+    swap_seq: i64,
+    liquidity_seq: i64,
+    changed_pairs: BTreeSet<PairKey>,
+    changed_pools: BTreeSet<(u64, String)>,
+}
+
+impl MarketWindow {
+    pub fn add_swaps(&mut self, swaps: Vec<SwapTrade>) {
+        for swap in swaps {
+            self.swap_seq = self.swap_seq.max(swap.seq);
+            self.changed_pairs.insert(PairKey::of(&swap));
+            self.swaps.push_back(swap);
+        }
+    }
+
+    pub fn add_liquidity(&mut self, changes: Vec<LiquidityChange>) {
+        for change in changes {
+            self.liquidity_seq = self.liquidity_seq.max(change.seq);
+            self.changed_pools.insert((change.chain_id, change.pool_id.clone()));
+            self.liquidity.push_back(change);
+        }
+    }
+
+    /// Read everything the indexer added since the last poll
+    pub async fn poll(&mut self, indexer: &IndexerStorage, now: DateTime<Utc>, batch_size: u64) -> intents_indexer::Result<()> {
+        let since = now - Duration::hours(WINDOW_HOURS);
+        loop {
+            let swaps = indexer.swaps_after(self.swap_seq, since, batch_size).await?;
+            let done = (swaps.len() as u64) < batch_size;
+            self.add_swaps(swaps);
+            if done {
+                break;
+            }
+        }
+        loop {
+            let changes = indexer.liquidity_after(self.liquidity_seq, since, batch_size).await?;
+            let done = (changes.len() as u64) < batch_size;
+            self.add_liquidity(changes);
+            if done {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Tickers for every pair that changed since the last call, after
+    /// dropping what has left the window
+    pub fn take_tickers(&mut self, now: DateTime<Utc>) -> Vec<MarketDataMessage> {
+        let cutoff = now - Duration::hours(WINDOW_HOURS);
+        self.swaps.retain(|swap| swap.timestamp >= cutoff);
+        self.liquidity.retain(|change| change.timestamp >= cutoff);
+
+        let changed_pairs = std::mem::take(&mut self.changed_pairs);
+        let changed_pools = std::mem::take(&mut self.changed_pools);
+
+        let mut pairs: BTreeMap<PairKey, Vec<&SwapTrade>> = BTreeMap::new();
+        for swap in &self.swaps {
+            let key = PairKey::of(swap);
+            if changed_pairs.contains(&key) || changed_pools.contains(&(key.chain_id, key.pool_id.clone())) {
+                pairs.entry(key).or_default().push(swap);
+            }
+        }
+
+        let mut liquidity_changes: BTreeMap<(u64, &str), u64> = BTreeMap::new();
+        for change in &self.liquidity {
+            *liquidity_changes.entry((change.chain_id, change.pool_id.as_str())).or_default() += 1;
+        }
+
+        pairs
+            .into_iter()
+            .filter_map(|(key, mut swaps)| {
+                swaps.sort_by_key(|swap| (swap.timestamp, swap.seq));
+                let changes = liquidity_changes.get(&(key.chain_id, key.pool_id.as_str())).copied().unwrap_or(0);
+                ticker(&key, &swaps, changes)
+            })
+            .collect()
+    }
+}
+
+/// Whether this instance holds the producer lease, taking or renewing it.
+/// The lease outlives a few ticks, so a stalled producer is replaced.
+async fn hold_lease(redis: &mut MultiplexedConnection, instance: Uuid, interval_ms: u64) -> redis::RedisResult<bool> {
+    let held: i32 = lease_script()
+        .key(CacheKeys::market_data_lease())
+        .arg(instance.to_string())
+        .arg(interval_ms.saturating_mul(3))
+        .invoke_async(redis)
+        .await?;
+    Ok(held == 1)
+}
+
+/// Send tickers at `config.interval_ms` for the life of the process, while
+/// this instance holds the producer lease
+pub async fn run(indexer: Arc<IndexerStorage>, mut redis: MultiplexedConnection, config: MarketDataConfig) {
+    let instance = Uuid::new_v4();
+    let interval_ms = config.interval_ms.max(100);
+    let mut window = MarketWindow::default();
+    let mut interval = tokio::time::interval(std::time::Duration::from_millis(interval_ms));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        interval.tick().await;
+        match hold_lease(&mut redis, instance, interval_ms).await {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(e) => {
+                tracing::warn!("Failed to check the market data lease: {}", e);
+                continue;
+            }
+        }
+
+        let now = Utc::now();
+        if let Err(e) = window.poll(&indexer, now, config.batch_size.max(1)).await {
+            tracing::warn!("Failed to read market events from the indexer: {}", e);
+            continue;
+        }
+        for message in window.take_tickers(now) {
+            broadcast_market_data(message).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn address(byte: u8) -> Address {
+        Address::repeat_byte(byte)
+    }
+
+    fn swap(seq: i64, minutes: i64, token_in: u8, amount_in: u64, amount_out: u64) -> SwapTrade {
+        SwapTrade {
+            seq,
+            chain_id: 1,
+            pool_id: "7".to_string(),
+            trader: address(0xa0 + seq as u8),
+            token_in: address(token_in),
+            token_out: address(if token_in == 1 { 2 } else { 1 }),
+            amount_in: U256::from(amount_in),
+            amount_out: U256::from(amount_out),
+            decimals_in: 0,
+            decimals_out: 0,
+            timestamp: Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap() + Duration::minutes(minutes),
+        }
+    }
+
+    #[test]
+    fn test_ticker_prices_and_orderflow() {
+        // Sell 100 base for 100 quote, then buy 50 base for 55 quote
+        let sell = swap(1, 0, 1, 100, 100);
+        let buy = swap(2, 10, 2, 55, 50);
+        let key = PairKey::of(&sell);
+        assert_eq!((key.base, key.quote), (address(1), address(2)));
+
+        let ticker = ticker(&key, &[&sell, &buy], 3).unwrap();
+        assert!((ticker.price - 1.1).abs() < 1e-9);
+        assert!((ticker.price_change_24h - 0.1).abs() < 1e-9);
+        assert_eq!(ticker.volume_24h, U256::from(150));
+        assert_eq!((ticker.orderflow.buys, ticker.orderflow.sells), (1, 1));
+        assert_eq!(ticker.orderflow.buy_volume, U256::from(50));
+        assert!((ticker.orderflow.imbalance + 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(ticker.orderflow.unique_traders, 2);
+        assert_eq!(ticker.orderflow.liquidity_changes, 3);
+        assert_eq!(ticker.last_trade_at, buy.timestamp);
+    }
+
+    #[test]
+    fn test_window_sends_changed_pairs_once() {
+        let mut window = MarketWindow::default();
+        window.add_swaps(vec![swap(1, 0, 1, 100, 100), swap(2, 10, 2, 55, 50)]);
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 1, 0, 0).unwrap();
+
+        assert_eq!(window.take_tickers(now).len(), 1);
+        assert!(window.take_tickers(now).is_empty());
+
+        // Liquidity in the pool resends its pairs
+        let change = LiquidityChange {
+            seq: 3,
+            chain_id: 1,
+            pool_id: "7".to_string(),
+            provider: address(9),
+            timestamp: now,
+        };
+        window.add_liquidity(vec![change.clone()]);
+        let tickers = window.take_tickers(now);
+        assert_eq!(tickers[0].orderflow.liquidity_changes, 1);
+        assert_eq!((window.swap_seq, window.liquidity_seq), (2, 3));
+
+        // A day later the swaps have left the window
+        window.add_liquidity(vec![LiquidityChange { seq: 4, timestamp: now + Duration::days(1), ..change }]);
+        assert!(window.take_tickers(now + Duration::days(1)).is_empty());
+    }
+}
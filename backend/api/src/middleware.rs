@@ -1,17 +1,25 @@
 use axum::{
+    body::Body,
     extract::{Request, State},
-    http::{HeaderMap, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
-use std::time::Duration;
 use crate::{
     models::AppState,
     error::{ApiError, Result},
     auth::validate_jwt,
-    cache::CacheService,
+    cache::{CacheKeys, CacheService},
+    api_keys::{self, ApiKeyContext, API_KEY_HEADER},
+    database::{ApiKeyDb, AuditDb},
+    audit::{self, AuditEntry},
+    idempotency::{self, IdempotencyEntry, StoredResponse, IDEMPOTENCY_KEY_HEADER},
+    rate_limit,
+    response_cache::{self, CachedResponse},
 };
 
+const MAX_CONTENT_LENGTH: usize = 1024 * 1024; // 1MB
+
 // Authentication middleware
 pub async fn auth(
     State(state): State<AppState>,
@@ -25,6 +33,30 @@ pub async fn auth(
         return Ok(next.run(request).await);
     }
 
+    // Programmatic clients authenticate with an API key instead of a JWT
+    if let Some(key) = headers.get(API_KEY_HEADER).and_then(|header| header.to_str().ok()) {
+        let method = request.method().to_string();
+        let (claims, key_context) = api_keys::authenticate(&state.db, key, &method, path).await?;
+        let key_id = key_context.id;
+        request.extensions_mut().insert(claims);
+        request.extensions_mut().insert(key_context);
+
+        let response = next.run(request).await;
+
+        // Meter usage off the request path
+        let status = response.status();
+        let db = state.db.clone();
+        tokio::spawn(async move {
+            let rate_limited = status == StatusCode::TOO_MANY_REQUESTS;
+            let error = !rate_limited && (status.is_client_error() || status.is_server_error());
+            if let Err(e) = ApiKeyDb::record_usage(&db, key_id, error, rate_limited).await {
+                tracing::warn!("Failed to record usage of API key {}: {}", key_id, e);
+            }
+        });
+
+        return Ok(response);
+    }
+
     // Extract Bearer token
     let auth_header = headers
         .get("authorization")
@@ -48,6 +80,51 @@ pub async fn auth(
     Ok(next.run(request).await)
 }
 
+// Audit trail of privileged actions; runs right after auth so the actor is
+// known and attempts the rate limiter turns away are recorded too
+pub async fn audit(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response> {
+    let Some(action) = audit::action(request.method(), request.uri().path()) else {
+        return Ok(next.run(request).await);
+    };
+    let Some(claims) = request.extensions().get::<crate::models::Claims>().cloned() else {
+        return Ok(next.run(request).await);
+    };
+    let api_key_id = request.extensions().get::<ApiKeyContext>().map(|key| key.id);
+    let ip = client_ip(request.headers(), &request);
+    
+    let (parts, body) = request.into_parts();
+    let body = axum::body::to_bytes(body, MAX_CONTENT_LENGTH)
+        .await
+        .map_err(|_| ApiError::BadRequest("Failed to read request body".to_string()))?;
+    let payload_hash = audit::payload_hash(&body);
+    let method = parts.method.to_string();
+    let path = parts.uri.path().to_string();
+    
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    
+    let entry = AuditEntry {
+        actor: claims.sub,
+        actor_role: claims.role,
+        api_key_id,
+        ip,
+        action,
+        method,
+        path,
+        payload_hash,
+        status_code: response.status().as_u16(),
+        outcome: audit::outcome(response.status()),
+    };
+    if let Err(e) = AuditDb::record(&state.db, &entry).await {
+        tracing::error!("Failed to write audit log entry {:?}: {}", entry, e);
+    }
+    
+    Ok(response)
+}
+
 // Rate limiting middleware
 pub async fn rate_limit(
     State(state): State<AppState>,
@@ -55,41 +132,170 @@ pub async fn rate_limit(
     request: Request,
     next: Next,
 ) -> Result<Response> {
-    // Get client identifier (IP or user ID if authenticated)
+    // Get client identifier (API key, user, or IP)
     let client_id = get_client_identifier(&headers, &request);
     
-    // Create cache service
-    let mut cache = CacheService::new(state.redis.clone());
+    // API keys are limited by their tier, users and anonymous IPs by their own limits
+    let limit = match request.extensions().get::<ApiKeyContext>() {
+        Some(key) => state.config.api_keys.requests_per_minute(&key.tier).unwrap_or_else(|| {
+            tracing::warn!("API key {} has unknown tier {}", key.id, key.tier);
+            state.config.rate_limit.requests_per_minute as u32
+        }),
+        None if client_id.starts_with("user:") => state.config.rate_limit.requests_per_minute as u32,
+        None => state.config.rate_limit.anonymous_limit() as u32,
+    };
+    
+    // Counted in Redis so every instance shares the budget. If Redis is
+    // down, serve the request unlimited rather than fail it.
+    let mut redis = state.redis.clone();
+    let decision = match rate_limit::check(&mut redis, &client_id, limit, rate_limit::WINDOW).await {
+        Ok(decision) => decision,
+        Err(e) => {
+            tracing::warn!("Rate limit check failed, allowing request: {}", e);
+            return Ok(next.run(request).await);
+        }
+    };
+    
+    let mut response = if decision.allowed {
+        next.run(request).await
+    } else {
+        ApiError::RateLimit.into_response()
+    };
+    rate_limit::apply_headers(response.headers_mut(), &decision, rate_limit::WINDOW);
+    
+    Ok(response)
+}
+
+// Idempotency-Key support for intent submission and cancellation; runs
+// after auth so keys are scoped to the caller
+pub async fn idempotency(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response> {
+    let key = match request.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        Some(key) if idempotency::applies_to(request.method(), request.uri().path()) => {
+            key.to_str().map_err(|_| ApiError::BadRequest(format!("Invalid {} header", IDEMPOTENCY_KEY_HEADER)))?.to_string()
+        }
+        _ => return Ok(next.run(request).await),
+    };
+    let Some(caller) = request.extensions().get::<crate::models::Claims>().map(|claims| claims.sub.clone()) else {
+        return Ok(next.run(request).await);
+    };
+    idempotency::validate_key(&key)?;
     
-    // Check rate limit
-    let rate_limit_info = cache.check_rate_limit(
-        &client_id,
-        state.config.rate_limit.requests_per_minute as u32,
-        Duration::from_secs(60),
+    let (parts, body) = request.into_parts();
+    let body = axum::body::to_bytes(body, MAX_CONTENT_LENGTH)
+        .await
+        .map_err(|_| ApiError::BadRequest("Failed to read request body".to_string()))?;
+    let fingerprint = idempotency::fingerprint(&parts.method, parts.uri.path(), &body);
+    
+    let cache_key = CacheKeys::idempotency(&caller, &key);
+    let mut cache = CacheService::new(state.redis.clone());
+    let claimed = cache.set_nx(
+        &cache_key,
+        &IdempotencyEntry::InProgress { fingerprint: fingerprint.clone() },
+        idempotency::IN_PROGRESS_TTL,
     ).await?;
     
-    if rate_limit_info.requests_remaining == 0 {
-        return Err(ApiError::RateLimit);
+    if !claimed {
+        return match cache.get::<IdempotencyEntry>(&cache_key).await? {
+            Some(entry) if entry.fingerprint() != fingerprint => Err(ApiError::Validation(format!(
+                "{} was already used for a different request",
+                IDEMPOTENCY_KEY_HEADER
+            ))),
+            Some(IdempotencyEntry::Complete(stored)) => Ok(stored.replay()),
+            _ => Err(ApiError::Conflict(
+                "A request with this Idempotency-Key is still being processed".to_string()
+            )),
+        };
     }
     
-    // Add rate limit headers to response
-    let mut response = next.run(request).await;
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
     
-    let headers_mut = response.headers_mut();
-    headers_mut.insert(
-        "X-RateLimit-Limit",
-        rate_limit_info.window_size.to_string().parse().unwrap(),
-    );
-    headers_mut.insert(
-        "X-RateLimit-Remaining",
-        rate_limit_info.requests_remaining.to_string().parse().unwrap(),
-    );
-    headers_mut.insert(
-        "X-RateLimit-Reset",
-        rate_limit_info.reset_time.timestamp().to_string().parse().unwrap(),
-    );
+    // Let the client retry server errors with the same key
+    if response.status().is_server_error() {
+        cache.delete(&cache_key).await.ok();
+        return Ok(response);
+    }
     
-    Ok(response)
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_CONTENT_LENGTH).await {
+        Ok(body) => body,
+        Err(e) => {
+            cache.delete(&cache_key).await.ok();
+            return Err(ApiError::Internal(format!("Failed to read response body: {}", e)));
+        }
+    };
+    
+    match StoredResponse::capture(fingerprint, parts.status, parts.headers.get(header::CONTENT_TYPE), &body) {
+        Some(stored) => {
+            if let Err(e) = cache.set(&cache_key, &IdempotencyEntry::Complete(stored), Some(idempotency::RESPONSE_TTL)).await {
+                tracing::warn!("Failed to store response for idempotency key {}: {}", key, e);
+                cache.delete(&cache_key).await.ok();
+            }
+        }
+        None => {
+            cache.delete(&cache_key).await.ok();
+        }
+    }
+    
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+// Shared cache for analytics reads. Runs after auth, so protected routes
+// are only served to authenticated callers; their responses don't depend
+// on who is asking.
+pub async fn response_cache(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response> {
+    if !response_cache::applies_to(request.method(), request.uri().path()) {
+        return Ok(next.run(request).await);
+    }
+    
+    // A Redis outage shouldn't take analytics down with it
+    let mut redis = state.redis.clone();
+    let generation = match response_cache::current_generation(&mut redis).await {
+        Ok(generation) => generation,
+        Err(e) => {
+            tracing::warn!("Response cache unavailable, bypassing: {}", e);
+            return Ok(next.run(request).await);
+        }
+    };
+    let entry_id = response_cache::entry_id(request.uri().path(), request.uri().query());
+    let cache_key = CacheKeys::http_response(generation, &entry_id);
+    let mut cache = CacheService::new(redis);
+    
+    if let Ok(Some(cached)) = cache.get::<CachedResponse>(&cache_key).await {
+        return Ok(cached.respond(request.headers()));
+    }
+    
+    let request_headers = request.headers().clone();
+    let visibility = if request.extensions().get::<crate::models::Claims>().is_some() { "private" } else { "public" };
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return Ok(response);
+    }
+    
+    let Some(max_age) = response_cache::max_age(response.headers().get(header::CACHE_CONTROL)) else {
+        return Ok(response);
+    };
+    let (parts, body) = response.into_parts();
+    let body = axum::body::to_bytes(body, response_cache::MAX_BODY_SIZE)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to read response body: {}", e)))?;
+    
+    let cache_control = format!("{}, max-age={}", visibility, max_age);
+    let Some(cached) = CachedResponse::capture(cache_control, parts.headers.get(header::CONTENT_TYPE), &body) else {
+        return Ok(Response::from_parts(parts, Body::from(body)));
+    };
+    if let Err(e) = cache.set(&cache_key, &cached, Some(std::time::Duration::from_secs(max_age))).await {
+        tracing::warn!("Failed to cache response {}: {}", entry_id, e);
+    }
+    
+    Ok(cached.respond(&request_headers))
 }
 
 // CORS middleware (handled by tower-http, but we can add custom logic here)
@@ -163,47 +369,69 @@ pub async fn security_headers(
 
 // Helper functions
 fn is_public_endpoint(path: &str) -> bool {
+    // API docs, Swagger UI, the public market data stream and pool series
+    if path.starts_with(crate::openapi::DOCS_PATH)
+        || path == "/api/v1/stream/market"
+        || path.starts_with("/api/v1/analytics/pools/")
+    {
+        return true;
+    }
+    
     matches!(path,
         "/health" |
         "/health/ready" |
         "/health/live" |
+        "/healthz" |
+        "/readyz" |
         "/metrics" |
         "/api/v1/analytics/public" |
+        "/api/v1/analytics/pools" |
         "/api/v1/chains" |
         "/api/v1/tokens/prices" |
         "/ws" | // WebSocket endpoint (auth handled separately)
+        "/graphql/ws" | "/api/v1/graphql/ws" | // Authenticated in connection_init
         "/api/v1/intents" if path.ends_with("/status") // Public intent status
     )
 }
 
 fn get_client_identifier(headers: &HeaderMap, request: &Request) -> String {
+    // Each API key has its own budget
+    if let Some(key) = request.extensions().get::<ApiKeyContext>() {
+        return format!("key:{}", key.id);
+    }
+    
     // Try to get user ID from JWT claims in extensions
     if let Some(claims) = request.extensions().get::<crate::models::Claims>() {
         return format!("user:{}", claims.sub);
     }
     
     // Fall back to IP address
+    match client_ip(headers, request) {
+        Some(ip) => format!("ip:{}", ip),
+        None => "unknown".to_string(),
+    }
+}
+
+fn client_ip(headers: &HeaderMap, request: &Request) -> Option<String> {
     if let Some(forwarded_for) = headers.get("x-forwarded-for") {
         if let Ok(forwarded_str) = forwarded_for.to_str() {
             if let Some(ip) = forwarded_str.split(',').next() {
-                return format!("ip:{}", ip.trim());
+                return Some(ip.trim().to_string());
             }
         }
     }
     
     if let Some(real_ip) = headers.get("x-real-ip") {
         if let Ok(ip_str) = real_ip.to_str() {
-            return format!("ip:{}", ip_str);
+            return Some(ip_str.to_string());
         }
     }
     
     // Fall back to connection info (this might not be available in all setups)
-    if let Some(connect_info) = request.extensions().get::<axum::extract::ConnectInfo<std::net::SocketAddr>>() {
-        return format!("ip:{}", connect_info.ip());
-    }
-    
-    // Default fallback
-    "unknown".to_string()
+    request
+        .extensions()
+        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
+        .map(|connect_info| connect_info.ip().to_string())
 }
 
 // Metrics middleware
@@ -260,15 +488,13 @@ pub async fn request_size_limit(
     request: Request,
     next: Next,
 ) -> Result<Response> {
-    const MAX_CONTENT_LENGTH: u64 = 1024 * 1024; // 1MB
-    
     if let Some(content_length) = headers.get("content-length") {
         let length: u64 = content_length.to_str()
             .map_err(|_| ApiError::BadRequest("Invalid content-length header".to_string()))?
             .parse()
             .map_err(|_| ApiError::BadRequest("Invalid content-length value".to_string()))?;
         
-        if length > MAX_CONTENT_LENGTH {
+        if length > MAX_CONTENT_LENGTH as u64 {
             return Err(ApiError::BadRequest(
                 format!("Request body too large. Maximum size is {} bytes", MAX_CONTENT_LENGTH)
             ));
@@ -287,7 +513,7 @@ pub async fn api_versioning(
     let path = request.uri().path();
     
     // Ensure API version is supported
-    if path.starts_with("/api/") {
+    if path.starts_with("/api/") && !path.starts_with(crate::openapi::DOCS_PATH) {
         if !path.starts_with("/api/v1/") {
             return Err(ApiError::BadRequest(
                 "Unsupported API version. Please use /api/v1/".to_string()
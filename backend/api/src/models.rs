@@ -3,13 +3,15 @@ use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use redis::aio::MultiplexedConnection;
 use ethers::types::{Address, U256, H256};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use metrics_exporter_prometheus::PrometheusHandle;
 
-use crate::config::Config;
+use crate::{config::Config, graphql::ApiSchema, quotes::QuoteAggregator, simulation::IntentSimulator};
 use intents_engine::IntentsEngine;
+use intents_indexer::storage::IndexerStorage;
 
 // Application state
 #[derive(Clone, FromRef)]
@@ -17,50 +19,159 @@ pub struct AppState {
     pub db: PgPool,
     pub redis: MultiplexedConnection,
     pub intents_engine: Arc<IntentsEngine>,
+    pub quotes: Arc<QuoteAggregator>,
+    pub simulator: Arc<IntentSimulator>,
+    /// Read side of the indexer database, for rollup-backed analytics
+    pub indexer: Arc<IndexerStorage>,
+    pub graphql: ApiSchema,
     pub config: Config,
     pub prometheus_handle: PrometheusHandle,
 }
 
 // Request/Response models
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SubmitIntentRequest {
     pub source_chain_id: u64,
     pub dest_chain_id: u64,
+    #[schema(value_type = String)]
     pub source_token: Address,
+    #[schema(value_type = String)]
     pub dest_token: Address,
+    #[schema(value_type = String)]
     pub source_amount: U256,
+    #[schema(value_type = String)]
     pub min_dest_amount: U256,
     pub deadline: DateTime<Utc>,
+    #[schema(value_type = String)]
     pub user_address: Address,
     pub signature: String,
+    #[schema(value_type = String)]
     pub nonce: U256,
+    #[schema(value_type = Option<String>)]
     pub max_gas_price: Option<U256>,
     pub slippage_tolerance: Option<f64>, // e.g., 0.01 for 1%
+    /// Quote request this intent was priced from, for fill-quality analytics
+    #[serde(default)]
+    pub quote_id: Option<Uuid>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A draft intent to collect quotes for
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QuoteIntentRequest {
+    pub source_chain_id: u64,
+    pub dest_chain_id: u64,
+    #[schema(value_type = String)]
+    pub source_token: Address,
+    #[schema(value_type = String)]
+    pub dest_token: Address,
+    #[schema(value_type = String)]
+    pub source_amount: U256,
+    /// Quotes paying out less than this are left out
+    #[schema(value_type = Option<String>)]
+    pub min_dest_amount: Option<U256>,
+    #[schema(value_type = Option<String>)]
+    pub user_address: Option<Address>,
+}
+
+/// Expected result of a draft intent; see `simulation`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SimulationResponse {
+    #[schema(value_type = String)]
+    pub expected_output: U256,
+    /// False if the request's `min_dest_amount` would not be met
+    pub meets_min_dest_amount: bool,
+    /// Summed over the hops it could be measured for
+    pub price_impact_bps: u32,
+    /// Taken by bridge hops, in the bridged tokens
+    #[schema(value_type = String)]
+    pub bridge_fee: U256,
+    #[schema(value_type = String)]
+    pub estimated_gas: U256,
+    pub estimated_completion_secs: u64,
+    pub route: Vec<SimulatedHop>,
+    pub simulated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SimulatedHop {
+    /// OrbitalAMM, UniswapV3, SushiSwap, Curve or Bridge
+    pub protocol: String,
+    pub chain_id: u64,
+    pub pool: String,
+    #[schema(value_type = String)]
+    pub token_in: Address,
+    #[schema(value_type = String)]
+    pub token_out: Address,
+    #[schema(value_type = String)]
+    pub amount_in: U256,
+    #[schema(value_type = String)]
+    pub amount_out: U256,
+    /// Known for hops priced against live orbital pool state
+    pub price_impact_bps: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IntentQuote {
+    /// "solver" or "orbital"
+    pub source: String,
+    #[schema(value_type = Option<String>)]
+    pub solver_address: Option<Address>,
+    #[schema(value_type = String)]
+    pub dest_amount: U256,
+    #[schema(value_type = Option<String>)]
+    pub estimated_gas: Option<U256>,
+    pub estimated_time_secs: Option<u64>,
+    pub expires_at: DateTime<Utc>,
+    pub latency_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuoteResponse {
+    /// Pass back as `quote_id` when submitting the intent
+    pub quote_id: Uuid,
+    pub best: Option<IntentQuote>,
+    /// Every quote received in time, best first
+    pub quotes: Vec<IntentQuote>,
+    pub solvers_asked: u32,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct IntentResponse {
+    #[schema(value_type = String)]
     pub intent_id: H256,
     pub status: String,
     pub source_chain_id: u64,
     pub dest_chain_id: u64,
+    #[schema(value_type = String)]
     pub source_token: Address,
+    #[schema(value_type = String)]
     pub dest_token: Address,
+    #[schema(value_type = String)]
     pub source_amount: U256,
+    #[schema(value_type = String)]
     pub min_dest_amount: U256,
+    #[schema(value_type = Option<String>)]
     pub actual_dest_amount: Option<U256>,
     pub deadline: DateTime<Utc>,
+    #[schema(value_type = String)]
     pub user_address: Address,
+    #[schema(value_type = Option<String>)]
     pub solver_address: Option<Address>,
+    #[schema(value_type = Option<String>)]
     pub execution_tx_hash: Option<H256>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[schema(value_type = Option<String>)]
     pub gas_used: Option<U256>,
+    #[schema(value_type = Option<String>)]
     pub fees_paid: Option<U256>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct IntentStatusResponse {
+    #[schema(value_type = String)]
     pub intent_id: H256,
     pub status: String,
     pub progress: IntentProgress,
@@ -68,7 +179,7 @@ pub struct IntentStatusResponse {
     pub error_message: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct IntentProgress {
     pub current_step: String,
     pub steps_completed: u32,
@@ -76,30 +187,165 @@ pub struct IntentProgress {
     pub percentage: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Everything known about an intent's progress, from the API, the engine's
+/// audit log and the indexer
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IntentTimeline {
+    #[schema(value_type = String)]
+    pub intent_id: H256,
+    pub status: String,
+    #[schema(value_type = String)]
+    pub user_address: Address,
+    #[schema(value_type = Option<String>)]
+    pub solver_address: Option<Address>,
+    pub source_chain_id: u64,
+    pub dest_chain_id: u64,
+    #[schema(value_type = String)]
+    pub source_token: Address,
+    #[schema(value_type = String)]
+    pub dest_token: Address,
+    #[schema(value_type = String)]
+    pub source_amount: U256,
+    #[schema(value_type = String)]
+    pub min_dest_amount: U256,
+    #[schema(value_type = Option<String>)]
+    pub final_dest_amount: Option<U256>,
+    #[schema(value_type = Option<String>)]
+    pub fees_paid: Option<U256>,
+    /// Delivery on the destination chain: the last bridge leg's receipt, or
+    /// the execution transaction when nothing was bridged
+    #[schema(value_type = Option<String>)]
+    pub destination_tx: Option<H256>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub bridge_legs: Vec<TimelineBridgeLeg>,
+    /// Oldest first
+    pub events: Vec<TimelineEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TimelineEvent {
+    pub stage: String,
+    pub timestamp: DateTime<Utc>,
+    /// `api`, `engine` or `indexer`
+    pub source: String,
+    pub chain_id: Option<u64>,
+    #[schema(value_type = Option<String>)]
+    pub tx_hash: Option<H256>,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TimelineBridgeLeg {
+    #[schema(value_type = String)]
+    pub message_hash: H256,
+    pub source_chain_id: u64,
+    pub destination_chain_id: u64,
+    #[schema(value_type = Option<String>)]
+    pub source_tx: Option<H256>,
+    #[schema(value_type = Option<String>)]
+    pub destination_tx: Option<H256>,
+    #[schema(value_type = Option<String>)]
+    pub amount: Option<U256>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub received_at: Option<DateTime<Utc>>,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SolverRegistrationRequest {
+    #[schema(value_type = String)]
     pub solver_address: Address,
+    #[schema(value_type = String)]
     pub bond_amount: U256,
     pub supported_chains: Vec<u64>,
     pub fee_rate: f64, // Basis points, e.g., 30 = 0.3%
     pub contact_info: Option<String>,
+    /// Endpoint draft intents are POSTed to for quotes
+    #[serde(default)]
+    pub quote_url: Option<String>,
+    /// Tokens the solver quotes for; empty means any token
+    #[serde(default)]
+    #[schema(value_type = Vec<String>)]
+    pub supported_tokens: Vec<Address>,
+    /// Display name shown in solver listings
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub website: Option<String>,
     pub signature: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Profile changes a solver makes to itself; unset fields are left alone
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct SolverUpdateRequest {
+    pub fee_rate: Option<f64>,
+    pub contact_info: Option<String>,
+    /// Kept for older clients: false pauses the solver, true resumes it
+    pub is_active: Option<bool>,
+    /// Changing the endpoint requires verifying the address again
+    pub quote_url: Option<String>,
+    pub supported_chains: Option<Vec<u64>>,
+    #[schema(value_type = Option<Vec<String>>)]
+    pub supported_tokens: Option<Vec<Address>>,
+    pub name: Option<String>,
+    pub website: Option<String>,
+}
+
+/// Solvers pause themselves to stop receiving quote requests and matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, async_graphql::Enum)]
+#[serde(rename_all = "snake_case")]
+pub enum SolverStatus {
+    Active,
+    Paused,
+    /// Deactivated by an admin
+    Inactive,
+    Slashed,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SolverStatusRequest {
+    /// `active` or `paused`
+    pub status: SolverStatus,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SolverVerificationChallenge {
+    pub message: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SolverVerificationRequest {
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SolverResponse {
+    #[schema(value_type = String)]
     pub address: Address,
+    #[schema(value_type = String)]
     pub bond_amount: U256,
     pub supported_chains: Vec<u64>,
     pub reputation_score: f64,
     pub success_count: u64,
     pub failure_count: u64,
+    #[schema(value_type = String)]
     pub total_volume: U256,
     pub fee_rate: f64,
     pub is_active: bool,
     pub is_slashed: bool,
     pub last_activity: DateTime<Utc>,
     pub registered_at: DateTime<Utc>,
+    pub status: SolverStatus,
+    #[schema(value_type = Vec<String>)]
+    pub supported_tokens: Vec<Address>,
+    pub name: Option<String>,
+    pub website: Option<String>,
+    pub quote_url: Option<String>,
+    /// When the solver last proved control of its address; quote requests
+    /// only go to verified solvers
+    pub verified_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -144,7 +390,7 @@ pub struct SolverPerformance {
     pub total_volume: U256,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct HealthResponse {
     pub status: String,
     pub timestamp: DateTime<Utc>,
@@ -156,56 +402,68 @@ pub struct HealthResponse {
     pub chains: Vec<ChainHealth>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct HealthCheck {
     pub status: String,
     pub latency_ms: Option<u64>,
     pub error_message: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ChainHealth {
     pub chain_id: u64,
     pub chain_name: String,
     pub status: String,
     pub block_number: Option<u64>,
     pub latency_ms: Option<u64>,
+    #[schema(value_type = Option<String>)]
     pub gas_price: Option<U256>,
+    #[serde(default)]
+    pub error_message: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PaginationParams {
-    pub page: Option<u64>,
-    pub limit: Option<u64>,
-    pub sort_by: Option<String>,
-    pub sort_order: Option<String>, // "asc" or "desc"
+// List filters, combined with `pagination::PageParams`
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IntentFilter {
+    /// Comma-separated, e.g. "pending,matched"
+    pub status: Option<String>,
+    pub source_chain_id: Option<u64>,
+    pub dest_chain_id: Option<u64>,
+    /// Matches either side of the swap
+    #[param(value_type = Option<String>)]
+    pub token: Option<Address>,
+    #[param(value_type = Option<String>)]
+    pub user: Option<Address>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    /// Minimum source amount, in wei as a decimal string
+    pub min_amount: Option<String>,
 }
 
-impl Default for PaginationParams {
-    fn default() -> Self {
-        Self {
-            page: Some(1),
-            limit: Some(20),
-            sort_by: Some("created_at".to_string()),
-            sort_order: Some("desc".to_string()),
-        }
+impl IntentFilter {
+    pub fn statuses(&self) -> Vec<String> {
+        self.status
+            .as_deref()
+            .map(|status| {
+                status
+                    .split(',')
+                    .map(|s| s.trim().to_lowercase())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PaginatedResponse<T> {
-    pub data: Vec<T>,
-    pub pagination: PaginationMeta,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PaginationMeta {
-    pub current_page: u64,
-    pub per_page: u64,
-    pub total_items: u64,
-    pub total_pages: u64,
-    pub has_next: bool,
-    pub has_prev: bool,
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SolverFilter {
+    pub chain_id: Option<u64>,
+    pub min_reputation: Option<f64>,
+    pub max_fee_rate: Option<f64>,
+    pub registered_after: Option<DateTime<Utc>>,
+    pub registered_before: Option<DateTime<Utc>>,
 }
 
 // Database models
@@ -248,6 +506,262 @@ pub struct SolverRecord {
     pub last_activity: DateTime<Utc>,
     pub registered_at: DateTime<Utc>,
     pub contact_info: Option<String>,
+    pub quote_url: Option<String>,
+    pub supported_tokens: Vec<String>,
+    pub name: Option<String>,
+    pub website: Option<String>,
+    pub paused: bool,
+    pub verified_at: Option<DateTime<Utc>>,
+}
+
+impl SolverRecord {
+    pub fn status(&self) -> SolverStatus {
+        if self.is_slashed {
+            SolverStatus::Slashed
+        } else if !self.is_active {
+            SolverStatus::Inactive
+        } else if self.paused {
+            SolverStatus::Paused
+        } else {
+            SolverStatus::Active
+        }
+    }
+}
+
+// Webhooks
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub events: Vec<String>,
+    /// Receive events about every user and solver, not just your own
+    /// (admin only)
+    #[serde(default)]
+    pub all_subjects: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WebhookResponse {
+    pub id: Uuid,
+    pub url: String,
+    pub events: Vec<String>,
+    pub all_subjects: bool,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    /// Only returned when the subscription is created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct WebhookRecord {
+    pub id: Uuid,
+    pub owner_address: String,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<String>,
+    pub all_subjects: bool,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookRecord> for WebhookResponse {
+    fn from(record: WebhookRecord) -> Self {
+        Self {
+            id: record.id,
+            url: record.url,
+            events: record.events,
+            all_subjects: record.all_subjects,
+            is_active: record.is_active,
+            created_at: record.created_at,
+            secret: None,
+        }
+    }
+}
+
+/// One event sent, or to be sent, to one subscription
+#[derive(Debug, sqlx::FromRow, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDeliveryRecord {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    /// "pending", "delivered" or "failed"
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// A claimed delivery with the endpoint it goes to
+#[derive(Debug, sqlx::FromRow)]
+pub struct DueDelivery {
+    pub id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+}
+
+// Notifications
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateNotificationChannelRequest {
+    /// Email address, or Telegram chat id
+    pub target: String,
+    /// Any of "intent.executed", "intent.failed" and "solver.slashed"
+    pub events: Vec<String>,
+    /// Defaults to true
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, sqlx::FromRow, Serialize, Deserialize, ToSchema)]
+pub struct NotificationChannelRecord {
+    pub id: Uuid,
+    pub owner_address: String,
+    /// "email" or "telegram"
+    pub channel: String,
+    pub target: String,
+    pub events: Vec<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One notification sent, or to be sent, on one channel
+#[derive(Debug, sqlx::FromRow, Serialize, Deserialize, ToSchema)]
+pub struct NotificationDeliveryRecord {
+    pub id: Uuid,
+    pub channel: String,
+    pub event_type: String,
+    /// "pending", "sent" or "failed"
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+/// A claimed notification with where it goes
+#[derive(Debug, sqlx::FromRow)]
+pub struct DueNotification {
+    pub id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+    pub channel: String,
+    pub target: String,
+}
+
+// API keys
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    /// Any of "read", "submit" and "admin"
+    pub scopes: Vec<String>,
+    /// Rate-limit tier; anything but the default is granted by admins
+    pub tier: Option<String>,
+    pub expires_in_days: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RotateApiKeyRequest {
+    /// How long the old key keeps working, so clients can switch over
+    #[serde(default)]
+    pub grace_period_secs: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyResponse {
+    pub id: Uuid,
+    pub name: String,
+    /// First characters of the key, to tell keys apart
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub tier: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    /// Only returned when the key is created or rotated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+// Audit log entries, as returned to compliance reviews
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AuditLogRecord {
+    pub id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub actor: String,
+    pub actor_role: String,
+    pub api_key_id: Option<Uuid>,
+    pub ip: Option<String>,
+    pub action: String,
+    pub method: String,
+    pub path: String,
+    pub payload_hash: Option<String>,
+    pub status_code: i32,
+    pub outcome: String,
+}
+
+// Audit log filters, combined with `pagination::PageParams`
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditLogFilter {
+    pub actor: Option<Address>,
+    /// "admin", "api_key", "intent.cancel" or "solver.status"
+    pub action: Option<String>,
+    /// "success", "denied" or "failure"
+    pub outcome: Option<String>,
+    pub api_key_id: Option<Uuid>,
+    pub occurred_after: Option<DateTime<Utc>>,
+    pub occurred_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ApiKeyRecord {
+    pub id: Uuid,
+    pub owner_address: String,
+    /// Role of the owner when the key was issued
+    pub owner_role: String,
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    pub tier: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ApiKeyRecord> for ApiKeyResponse {
+    fn from(record: ApiKeyRecord) -> Self {
+        Self {
+            id: record.id,
+            name: record.name,
+            prefix: record.prefix,
+            scopes: record.scopes,
+            tier: record.tier,
+            expires_at: record.expires_at,
+            last_used_at: record.last_used_at,
+            revoked_at: record.revoked_at,
+            created_at: record.created_at,
+            key: None,
+        }
+    }
+}
+
+/// Requests made with a key in one hour
+#[derive(Debug, sqlx::FromRow, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyUsageRecord {
+    pub period_start: DateTime<Utc>,
+    pub requests: i64,
+    pub errors: i64,
+    pub rate_limited: i64,
 }
 
 // WebSocket message types
@@ -256,6 +770,12 @@ pub struct WebSocketMessage {
     pub message_type: String,
     pub data: serde_json::Value,
     pub timestamp: DateTime<Utc>,
+    /// Position in `channel`, set when the message is broadcast. Replays may
+    /// repeat messages, so clients drop any seq they have already seen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -266,28 +786,80 @@ pub struct IntentUpdateMessage {
     pub details: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+// Ticker for one token pair in one pool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MarketDataMessage {
     pub chain_id: u64,
+    pub pool_id: String,
+    /// (base, quote), the lower address first
     pub token_pair: (Address, Address),
+    /// Base token in quote tokens, at the last trade
     pub price: f64,
+    /// Base token traded, in wei
     pub volume_24h: U256,
+    /// Since the first trade in the last 24 hours, as a fraction
     pub price_change_24h: f64,
+    pub orderflow: OrderflowStats,
+    pub last_trade_at: DateTime<Utc>,
+}
+
+// Who traded which way over the last 24 hours; a buy takes the base token
+// out of the pool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderflowStats {
+    pub swaps: u64,
+    pub buys: u64,
+    pub sells: u64,
+    /// Base token bought and sold, in wei
+    pub buy_volume: U256,
+    pub sell_volume: U256,
+    /// Buy volume less sell volume over their sum, from -1 to 1
+    pub imbalance: f64,
+    pub unique_traders: u64,
+    /// Liquidity added to or removed from the pool
+    pub liquidity_changes: u64,
+}
+
+// Operator controls
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EngineStatusResponse {
+    pub intake_paused: bool,
+    pub queue: intents_engine::scheduler::SchedulerMetrics,
+    pub in_flight: usize,
+    pub dead_letters: usize,
+    pub chain_rate_limits: std::collections::BTreeMap<u64, intents_engine::validator::rules::ChainRateLimit>,
 }
 
-// JWT Claims
 #[derive(Debug, Serialize, Deserialize)]
+pub struct DrainQueuesRequest {
+    /// How long to wait for queues to empty; intake stays paused either way
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExpireIntentRequest {
+    pub reason: String,
+}
+
+/// Body of every error response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: ErrorDetail,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorDetail {
+    /// Machine-readable, e.g. "VALIDATION_ERROR"
+    pub code: String,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+// JWT Claims
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String, // Subject (user address)
     pub exp: usize,  // Expiration time
     pub iat: usize,  // Issued at
-    pub role: String, // User role ("user", "solver", "admin")
-}
-
-// Rate limiting
-#[derive(Debug, Clone)]
-pub struct RateLimitInfo {
-    pub requests_remaining: u32,
-    pub reset_time: DateTime<Utc>,
-    pub window_size: u32,
+    pub role: String, // User role ("user", "solver", "operator", "admin")
 }
\ No newline at end of file
@@ -0,0 +1,325 @@
+//! Email and Telegram notifications
+//!
+//! Users pick, per channel, where notifications go and which events they
+//! want. Events are queued to `notification_deliveries` by the request that
+//! caused them, the same way webhook events are; the dispatcher renders
+//! each one from its template when it is sent and retries failures with
+//! backoff, so slow mail servers never hold up a request.
+
+use ethers::types::Address;
+use futures_util::future::join_all;
+use lettre::{
+    message::Mailbox, transport::smtp::authentication::Credentials, AsyncSmtpTransport, AsyncTransport,
+    Message as Email, Tokio1Executor,
+};
+use sqlx::PgPool;
+use std::time::Duration;
+
+use crate::{
+    config::NotificationConfig,
+    database::NotificationDb,
+    error::{validation_error, ApiError, Result},
+    models::DueNotification,
+    webhooks::backoff,
+};
+
+/// Events users can be notified about
+pub const NOTIFICATION_EVENTS: &[&str] = &["intent.executed", "intent.failed", "solver.slashed"];
+
+const TELEGRAM_API: &str = "https://api.telegram.org";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Email,
+    Telegram,
+}
+
+impl Channel {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "email" => Ok(Self::Email),
+            "telegram" => Ok(Self::Telegram),
+            other => Err(validation_error(format!("Unknown notification channel {}; use email or telegram", other))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Telegram => "telegram",
+        }
+    }
+
+    /// An email address, or a numeric Telegram chat id
+    pub fn validate_target(&self, target: &str) -> Result<()> {
+        let target = target.trim();
+        match self {
+            Self::Email => target
+                .parse::<lettre::Address>()
+                .map(|_| ())
+                .map_err(|_| validation_error("Invalid email address")),
+            Self::Telegram => target
+                .parse::<i64>()
+                .map(|_| ())
+                .map_err(|_| validation_error("Telegram target must be a chat id; message the bot to get yours")),
+        }
+    }
+}
+
+pub fn validate_events(events: &[String]) -> Result<()> {
+    if events.is_empty() {
+        return Err(validation_error("Choose at least one event"));
+    }
+    match events.iter().find(|event| !NOTIFICATION_EVENTS.contains(&event.as_str())) {
+        Some(unknown) => Err(validation_error(format!("Unknown notification event: {}", unknown))),
+        None => Ok(()),
+    }
+}
+
+/// A rendered notification
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub subject: String,
+    pub body: String,
+}
+
+/// Subject and body templates; `{field}` is replaced with that field of
+/// the event data
+fn template(event_type: &str) -> (&'static str, &'static str) {
+    match event_type {
+        "intent.executed" => (
+            "Intent executed",
+            "Your intent {intent_id} from chain {source_chain_id} to chain {dest_chain_id} was executed.\n\
+             Received: {actual_dest_amount}\n\
+             Transaction: {execution_tx_hash}",
+        ),
+        "intent.failed" => (
+            "Intent failed",
+            "Your intent {intent_id} from chain {source_chain_id} to chain {dest_chain_id} could not be executed.",
+        ),
+        "solver.slashed" => (
+            "Solver slashed",
+            "Your solver {solver_address} was slashed.\nReason: {reason}",
+        ),
+        _ => ("Notification", "{event}"),
+    }
+}
+
+pub fn render(event_type: &str, data: &serde_json::Value) -> Message {
+    let (subject, body) = template(event_type);
+    Message {
+        subject: fill(subject, event_type, data),
+        body: fill(body, event_type, data),
+    }
+}
+
+fn fill(template: &str, event_type: &str, data: &serde_json::Value) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let field = &rest[start + 1..start + len];
+        match (field, data.get(field)) {
+            ("event", _) => out.push_str(event_type),
+            (_, Some(serde_json::Value::String(value))) => out.push_str(value),
+            (_, Some(serde_json::Value::Null)) | (_, None) => out.push('-'),
+            (_, Some(value)) => out.push_str(&value.to_string()),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Queue `event_type` for each of `subject`'s channels that wants it.
+/// Failures are logged rather than failing the request that raised the
+/// event.
+pub async fn publish(db: &PgPool, event_type: &str, subject: Address, data: &serde_json::Value) {
+    if !NOTIFICATION_EVENTS.contains(&event_type) {
+        return;
+    }
+
+    match NotificationDb::enqueue(db, subject, event_type, data).await {
+        Ok(0) => {}
+        Ok(queued) => tracing::debug!("Queued {} {} notifications for {:#x}", queued, event_type, subject),
+        Err(e) => tracing::error!("Failed to queue {} notifications: {}", event_type, e),
+    }
+}
+
+pub struct NotificationDispatcher {
+    db: PgPool,
+    client: reqwest::Client,
+    mailer: Option<(AsyncSmtpTransport<Tokio1Executor>, Mailbox)>,
+    config: NotificationConfig,
+}
+
+impl NotificationDispatcher {
+    pub fn new(db: PgPool, config: NotificationConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .map_err(|e| ApiError::Internal(format!("Failed to build notification client: {}", e)))?;
+
+        let mailer = match &config.smtp {
+            Some(smtp) => {
+                let mut transport = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host)
+                    .map_err(|e| ApiError::Internal(format!("Invalid SMTP relay {}: {}", smtp.host, e)))?
+                    .port(smtp.port)
+                    .timeout(Some(Duration::from_millis(config.timeout_ms)));
+                if let (Some(username), Some(password)) = (&smtp.username, &smtp.password) {
+                    transport = transport.credentials(Credentials::new(username.clone(), password.clone()));
+                }
+                let from = smtp
+                    .from
+                    .parse::<Mailbox>()
+                    .map_err(|e| ApiError::Internal(format!("Invalid SMTP sender {}: {}", smtp.from, e)))?;
+                Some((transport.build(), from))
+            }
+            None => None,
+        };
+
+        Ok(Self { db, client, mailer, config })
+    }
+
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(Duration::from_millis(self.config.poll_interval_ms.max(100)));
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.deliver_due().await {
+                tracing::warn!("Notification delivery pass failed: {}", e);
+            }
+        }
+    }
+
+    /// Send every notification that is due, returning how many were attempted
+    pub async fn deliver_due(&self) -> Result<usize> {
+        // Claimed rows are leased past the send timeout so another instance
+        // won't pick them up mid-flight
+        let lease = Duration::from_millis(self.config.timeout_ms) * 2;
+        let due = NotificationDb::claim_due(&self.db, self.config.batch_size, lease).await?;
+
+        let attempted = due.len();
+        join_all(due.into_iter().map(|notification| self.deliver(notification))).await;
+        Ok(attempted)
+    }
+
+    async fn deliver(&self, notification: DueNotification) {
+        let message = render(&notification.event_type, &notification.payload);
+        let result = match Channel::parse(&notification.channel) {
+            Ok(Channel::Email) => self.send_email(&notification.target, &message).await,
+            Ok(Channel::Telegram) => self.send_telegram(&notification.target, &message).await,
+            Err(e) => Err(e.to_string()),
+        };
+
+        let attempts = notification.attempts as u32 + 1;
+        let outcome = match result {
+            Ok(()) => NotificationDb::mark_sent(&self.db, notification.id, attempts).await,
+            Err(error) if attempts >= self.config.max_attempts => {
+                tracing::warn!("Notification {} over {} failed for good: {}", notification.id, notification.channel, error);
+                NotificationDb::mark_failed(&self.db, notification.id, attempts, &error).await
+            }
+            Err(error) => {
+                let delay = backoff(
+                    attempts,
+                    Duration::from_secs(self.config.base_backoff_secs),
+                    Duration::from_secs(self.config.max_backoff_secs),
+                );
+                NotificationDb::schedule_retry(&self.db, notification.id, attempts, &error, delay).await
+            }
+        };
+
+        if let Err(e) = outcome {
+            tracing::error!("Failed to record notification {}: {}", notification.id, e);
+        }
+    }
+
+    async fn send_email(&self, to: &str, message: &Message) -> std::result::Result<(), String> {
+        let (mailer, from) = self.mailer.as_ref().ok_or("Email is not configured on this server")?;
+
+        let email = Email::builder()
+            .from(from.clone())
+            .to(to.parse::<Mailbox>().map_err(|e| e.to_string())?)
+            .subject(&message.subject)
+            .body(message.body.clone())
+            .map_err(|e| e.to_string())?;
+
+        mailer.send(email).await.map(|_| ()).map_err(|e| e.to_string())
+    }
+
+    async fn send_telegram(&self, chat_id: &str, message: &Message) -> std::result::Result<(), String> {
+        let token = self
+            .config
+            .telegram_bot_token
+            .as_deref()
+            .ok_or("Telegram is not configured on this server")?;
+
+        let response = self
+            .client
+            .post(format!("{}/bot{}/sendMessage", TELEGRAM_API, token))
+            .json(&serde_json::json!({
+                "chat_id": chat_id,
+                "text": format!("{}\n\n{}", message.subject, message.body),
+            }))
+            .send()
+            .await
+            // The error would include the URL, and with it the bot token
+            .map_err(|e| e.without_url().to_string())?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("Telegram returned HTTP {}", response.status()))
+        }
+    }
+}
+
+/// Whether this server can send over `channel`
+pub fn channel_available(config: &NotificationConfig, channel: Channel) -> bool {
+    match channel {
+        Channel::Email => config.smtp.is_some(),
+        Channel::Telegram => config.telegram_bot_token.is_some(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_fills_templates() {
+        let data = serde_json::json!({
+            "intent_id": "0xabc",
+            "source_chain_id": 1,
+            "dest_chain_id": 10,
+            "actual_dest_amount": "990",
+            "execution_tx_hash": null,
+        });
+        let message = render("intent.executed", &data);
+        assert_eq!(message.subject, "Intent executed");
+        assert!(message.body.starts_with("Your intent 0xabc from chain 1 to chain 10 was executed."));
+        assert!(message.body.contains("Received: 990"));
+        assert!(message.body.ends_with("Transaction: -"));
+
+        assert_eq!(render("intent.created", &data).body, "intent.created");
+        assert_eq!(fill("unterminated {field", "", &data), "unterminated {field");
+    }
+
+    #[test]
+    fn test_channel_validation() {
+        assert_eq!(Channel::parse("email").unwrap(), Channel::Email);
+        assert!(Channel::parse("sms").is_err());
+
+        assert!(Channel::Email.validate_target("alice@example.com").is_ok());
+        assert!(Channel::Email.validate_target("not an email").is_err());
+        assert!(Channel::Telegram.validate_target("-1001234567890").is_ok());
+        assert!(Channel::Telegram.validate_target("@someone").is_err());
+
+        assert!(validate_events(&["intent.failed".to_string()]).is_ok());
+        assert!(validate_events(&["intent.created".to_string()]).is_err());
+        assert!(validate_events(&[]).is_err());
+    }
+}
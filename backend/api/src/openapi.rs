@@ -0,0 +1,160 @@
+//! OpenAPI document for the public API
+//!
+//! Served at `/api/docs/openapi.json` with Swagger UI at `/api/docs`. The
+//! typed client in `backend/api-client` is generated from the same
+//! document; regenerate it with `cargo run -p intents-api --bin openapi`
+//! after changing any annotated route.
+
+use axum::Router;
+use utoipa::{
+    openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::{api_keys::API_KEY_HEADER, models::*, pagination, routes};
+
+pub const DOCS_PATH: &str = "/api/docs";
+pub const SPEC_PATH: &str = "/api/docs/openapi.json";
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Orbital Intents API", description = "Cross-chain intents on the Orbital AMM"),
+    paths(
+        routes::intents::submit_intent,
+        routes::intents::quote_intent,
+        routes::intents::simulate_intent,
+        routes::intents::get_user_intents,
+        routes::intents::get_intent_by_id,
+        routes::intents::get_intent_status,
+        routes::intents::get_intent_timeline,
+        routes::intents::cancel_intent,
+        routes::solver::register_solver,
+        routes::solver::get_solvers,
+        routes::solver::get_solver_by_address,
+        routes::webhooks::create_webhook,
+        routes::webhooks::list_webhooks,
+        routes::webhooks::delete_webhook,
+        routes::webhooks::list_deliveries,
+        routes::api_keys::create_api_key,
+        routes::api_keys::list_api_keys,
+        routes::api_keys::revoke_api_key,
+        routes::api_keys::rotate_api_key,
+        routes::api_keys::get_api_key_usage,
+        routes::notifications::list_channels,
+        routes::notifications::update_channel,
+        routes::notifications::delete_channel,
+        routes::notifications::list_deliveries,
+        routes::export::export_intents,
+        routes::export::export_swaps,
+        routes::health::health_check,
+        routes::health::readiness_check,
+        routes::health::liveness_check,
+    ),
+    components(schemas(
+        SubmitIntentRequest,
+        QuoteIntentRequest,
+        IntentQuote,
+        QuoteResponse,
+        SimulationResponse,
+        SimulatedHop,
+        IntentResponse,
+        IntentStatusResponse,
+        IntentProgress,
+        IntentTimeline,
+        TimelineEvent,
+        TimelineBridgeLeg,
+        SolverRegistrationRequest,
+        SolverResponse,
+        SolverStatus,
+        CreateWebhookRequest,
+        WebhookResponse,
+        WebhookDeliveryRecord,
+        CreateApiKeyRequest,
+        RotateApiKeyRequest,
+        ApiKeyResponse,
+        ApiKeyUsageRecord,
+        UpdateNotificationChannelRequest,
+        NotificationChannelRecord,
+        NotificationDeliveryRecord,
+        HealthResponse,
+        HealthCheck,
+        ChainHealth,
+        ErrorResponse,
+        ErrorDetail,
+        pagination::SortOrder,
+        pagination::IntentPage,
+        pagination::SolverPage,
+    )),
+    modifiers(&SecuritySchemes),
+    tags(
+        (name = "intents", description = "Submit, quote and track intents"),
+        (name = "solvers", description = "Solver registry"),
+        (name = "webhooks", description = "Event subscriptions"),
+        (name = "api-keys", description = "Keys for programmatic access"),
+        (name = "notifications", description = "Email and Telegram notification preferences"),
+        (name = "export", description = "Historical data as CSV or Parquet"),
+        (name = "health"),
+    )
+)]
+pub struct ApiDoc;
+
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new(API_KEY_HEADER))),
+        );
+    }
+}
+
+/// The document as pretty-printed JSON
+pub fn spec_json() -> String {
+    ApiDoc::openapi().to_pretty_json().expect("OpenAPI document serializes")
+}
+
+/// Swagger UI and the raw document
+pub fn routes<S: Clone + Send + Sync + 'static>() -> Router<S> {
+    SwaggerUi::new(DOCS_PATH).url(SPEC_PATH, ApiDoc::openapi()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_covers_documented_routes() {
+        let spec = ApiDoc::openapi();
+        for path in ["/api/v1/intents", "/api/v1/intents/quote", "/api/v1/solver/register", "/api/v1/api-keys/{id}/rotate"] {
+            assert!(spec.paths.paths.contains_key(path), "missing {}", path);
+        }
+
+        let schemes = spec.components.as_ref().unwrap().security_schemes.keys().cloned().collect::<Vec<_>>();
+        assert!(schemes.contains(&"bearer".to_string()));
+        assert!(schemes.contains(&"api_key".to_string()));
+    }
+
+    #[test]
+    fn test_client_spec_is_current() {
+        // The client is generated from a checked-in copy of the document
+        let checked_in: serde_json::Value =
+            serde_json::from_str(include_str!("../../api-client/openapi.json")).unwrap();
+        let current: serde_json::Value = serde_json::from_str(&spec_json()).unwrap();
+
+        let paths = |spec: &serde_json::Value| {
+            spec["paths"].as_object().map(|paths| paths.keys().cloned().collect::<Vec<_>>()).unwrap_or_default()
+        };
+        assert_eq!(
+            paths(&checked_in),
+            paths(&current),
+            "api-client/openapi.json is stale; run `cargo run -p intents-api --bin openapi`"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Cursor pagination for list routes
+//!
+//! Lists are ordered by one indexed column with the row id as tie-breaker,
+//! and a cursor holds both values of the last row returned. The next page
+//! starts strictly after that row, so pages stay stable while rows are
+//! inserted.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    error::{validation_error, Result},
+    models::{IntentResponse, SolverResponse},
+};
+
+pub const DEFAULT_PAGE_SIZE: u64 = 20;
+pub const MAX_PAGE_SIZE: u64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    fn sql(self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        }
+    }
+
+    fn comparison(self) -> &'static str {
+        match self {
+            Self::Asc => ">",
+            Self::Desc => "<",
+        }
+    }
+}
+
+/// Sort column value of the last row on a page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CursorValue {
+    Time(DateTime<Utc>),
+    Number(f64),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    pub sort_by: String,
+    pub value: CursorValue,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    /// Opaque form handed to clients
+    pub fn encode(&self) -> String {
+        hex::encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    pub fn decode(encoded: &str) -> Result<Self> {
+        hex::decode(encoded)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| validation_error("Invalid cursor"))
+    }
+}
+
+/// Page size, order and position shared by every list route
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageParams {
+    pub cursor: Option<String>,
+    pub limit: Option<u64>,
+    pub sort_by: Option<String>,
+    #[serde(default)]
+    pub sort_order: SortOrder,
+}
+
+impl PageParams {
+    pub fn limit(&self) -> u64 {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+    }
+
+    /// The requested sort column, checked against the ones a route allows;
+    /// the first allowed column is the default
+    pub fn sort_column(&self, allowed: &[&'static str]) -> Result<&'static str> {
+        match &self.sort_by {
+            None => Ok(allowed[0]),
+            Some(requested) => allowed
+                .iter()
+                .find(|column| *column == requested)
+                .copied()
+                .ok_or_else(|| validation_error(format!("Cannot sort by {}", requested))),
+        }
+    }
+
+    /// The decoded cursor, which must have been issued for `sort_column`
+    pub fn cursor(&self, sort_column: &str) -> Result<Option<Cursor>> {
+        let Some(encoded) = &self.cursor else { return Ok(None) };
+        let cursor = Cursor::decode(encoded)?;
+        if cursor.sort_by != sort_column {
+            return Err(validation_error("Cursor was issued for a different sort order"));
+        }
+        Ok(Some(cursor))
+    }
+}
+
+/// Append the keyset condition, ordering and limit for one page. The query
+/// must already have a WHERE clause.
+pub fn push_page(
+    query: &mut QueryBuilder<'_, Postgres>,
+    sort_column: &'static str,
+    order: SortOrder,
+    cursor: Option<Cursor>,
+    limit: u64,
+) {
+    if let Some(cursor) = cursor {
+        query.push(format!(" AND ({}, id) {} (", sort_column, order.comparison()));
+        match cursor.value {
+            CursorValue::Time(value) => query.push_bind(value),
+            CursorValue::Number(value) => query.push_bind(value),
+        };
+        query.push(", ").push_bind(cursor.id).push(")");
+    }
+    query.push(format!(" ORDER BY {0} {1}, id {1} LIMIT ", sort_column, order.sql()));
+    // One extra row tells whether another page follows
+    query.push_bind((limit + 1) as i64);
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[aliases(IntentPage = CursorPage<IntentResponse>, SolverPage = CursorPage<SolverResponse>)]
+pub struct CursorPage<T> {
+    pub data: Vec<T>,
+    /// Pass as `cursor` to fetch the next page
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+impl<T> CursorPage<T> {
+    /// Trim the extra row fetched by `push_page` and point the cursor at
+    /// the last row kept
+    pub fn from_rows<R>(
+        mut rows: Vec<R>,
+        limit: u64,
+        sort_column: &str,
+        key: impl Fn(&R) -> (CursorValue, Uuid),
+        convert: impl Fn(R) -> Result<T>,
+    ) -> Result<Self> {
+        let has_more = rows.len() as u64 > limit;
+        rows.truncate(limit as usize);
+
+        let next_cursor = match rows.last() {
+            Some(last) if has_more => {
+                let (value, id) = key(last);
+                Some(Cursor { sort_by: sort_column.to_string(), value, id }.encode())
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            data: rows.into_iter().map(convert).collect::<Result<_>>()?,
+            next_cursor,
+            has_more,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_cursor_round_trip_and_sort_checks() {
+        let cursor = Cursor {
+            sort_by: "created_at".to_string(),
+            value: CursorValue::Time(Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap()),
+            id: Uuid::new_v4(),
+        };
+        let params = PageParams { cursor: Some(cursor.encode()), ..Default::default() };
+
+        assert_eq!(params.cursor("created_at").unwrap(), Some(cursor));
+        assert!(params.cursor("updated_at").is_err());
+        assert!(Cursor::decode("zz").is_err());
+
+        let numeric = Cursor { sort_by: "reputation_score".to_string(), value: CursorValue::Number(0.75), id: Uuid::nil() };
+        assert_eq!(Cursor::decode(&numeric.encode()).unwrap(), numeric);
+
+        assert_eq!(params.sort_column(&["created_at", "updated_at"]).unwrap(), "created_at");
+        let params = PageParams { sort_by: Some("source_amount; DROP".to_string()), ..Default::default() };
+        assert!(params.sort_column(&["created_at"]).is_err());
+    }
+
+    #[test]
+    fn test_page_from_rows() {
+        let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let key = |id: &Uuid| (CursorValue::Number(1.0), *id);
+
+        let page = CursorPage::from_rows(ids.clone(), 2, "reputation_score", key, |id| Ok(id)).unwrap();
+        assert_eq!(page.data, ids[..2]);
+        assert!(page.has_more);
+        assert_eq!(Cursor::decode(page.next_cursor.as_deref().unwrap()).unwrap().id, ids[1]);
+
+        let last = CursorPage::from_rows(ids.clone(), 3, "reputation_score", key, |id| Ok(id)).unwrap();
+        assert!(!last.has_more);
+        assert!(last.next_cursor.is_none());
+
+        assert_eq!(PageParams { limit: Some(1_000), ..Default::default() }.limit(), MAX_PAGE_SIZE);
+    }
+}
@@ -0,0 +1,322 @@
+//! Pool TVL, volume, fee and APR series built from the indexer rollups
+//!
+//! Rollups only have rows for buckets with activity, so series are filled
+//! out here: TVL carries forward from the last bucket that had a value and
+//! volume and fees are zero. Amounts are in pool token units; orbital
+//! pools hold like-valued stablecoins, so they read as dollar values.
+
+use chrono::{DateTime, Duration, Utc};
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use intents_indexer::{
+    rollups::{Granularity, PoolTvlRollup, PoolVolumeRollup},
+    storage::IndexerStorage,
+};
+
+use crate::error::{internal_error, validation_error, Result};
+
+const YEAR_SECS: f64 = 365.0 * 24.0 * 3_600.0;
+
+/// How far back a series reaches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl Window {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "24h" | "1d" => Ok(Self::Day),
+            "7d" => Ok(Self::Week),
+            "30d" => Ok(Self::Month),
+            "90d" => Ok(Self::Quarter),
+            "1y" | "365d" => Ok(Self::Year),
+            other => Err(validation_error(format!("Unknown interval {}; use 24h, 7d, 30d, 90d or 1y", other))),
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Day => Duration::days(1),
+            Self::Week => Duration::days(7),
+            Self::Month => Duration::days(30),
+            Self::Quarter => Duration::days(90),
+            Self::Year => Duration::days(365),
+        }
+    }
+
+    /// Hourly series are capped at 30 days to keep responses small
+    pub fn check_granularity(&self, granularity: Granularity) -> Result<()> {
+        if granularity == Granularity::Hourly && self.duration() > Duration::days(30) {
+            return Err(validation_error("Hourly granularity covers at most 30d; use daily"));
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_granularity(value: Option<&str>) -> Result<Granularity> {
+    match value.unwrap_or("hourly") {
+        "hourly" => Ok(Granularity::Hourly),
+        "daily" => Ok(Granularity::Daily),
+        other => Err(validation_error(format!("Unknown granularity {}; use hourly or daily", other))),
+    }
+}
+
+/// Seconds a response may be cached for: about a tenth of a bucket
+pub fn cache_max_age(granularity: Granularity) -> u64 {
+    match granularity {
+        Granularity::Hourly => 60,
+        Granularity::Daily => 600,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolPoint {
+    pub bucket_start: DateTime<Utc>,
+    pub tvl: U256,
+    pub volume: U256,
+    pub fees: U256,
+    pub swaps: u64,
+    /// Fees in the bucket over TVL, annualized
+    pub apr: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolSeries {
+    pub chain_id: u64,
+    pub pool_id: String,
+    pub granularity: Granularity,
+    pub points: Vec<PoolPoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolSummary {
+    pub chain_id: u64,
+    pub pool_id: String,
+    pub tvl: U256,
+    pub volume_24h: U256,
+    pub volume_7d: U256,
+    pub fees_24h: U256,
+    pub fees_7d: U256,
+    /// Last 7 days of fees over current TVL, annualized
+    pub apr_7d: f64,
+}
+
+type PoolKey = (u64, String);
+
+/// One dense series per pool over `[from, to)`. `base` holds each pool's
+/// last TVL before `from`.
+pub fn build_series(
+    granularity: Granularity,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    volumes: &[PoolVolumeRollup],
+    tvls: &[PoolTvlRollup],
+    base: &[PoolTvlRollup],
+) -> Vec<PoolSeries> {
+    let mut volume_by_bucket: HashMap<PoolKey, BTreeMap<DateTime<Utc>, (U256, U256, u64)>> = HashMap::new();
+    for rollup in volumes {
+        let entry = volume_by_bucket
+            .entry((rollup.chain_id, rollup.pool_id.clone()))
+            .or_default()
+            .entry(rollup.bucket_start)
+            .or_default();
+        // A bucket has one rollup per input token
+        entry.0 = entry.0.saturating_add(rollup.volume_in);
+        entry.1 = entry.1.saturating_add(rollup.fees);
+        entry.2 += rollup.swaps;
+    }
+
+    let mut tvl_by_bucket: HashMap<PoolKey, BTreeMap<DateTime<Utc>, U256>> = HashMap::new();
+    for rollup in tvls {
+        tvl_by_bucket
+            .entry((rollup.chain_id, rollup.pool_id.clone()))
+            .or_default()
+            .insert(rollup.bucket_start, rollup.tvl);
+    }
+    let base: HashMap<PoolKey, U256> = base
+        .iter()
+        .map(|rollup| ((rollup.chain_id, rollup.pool_id.clone()), rollup.tvl))
+        .collect();
+
+    let mut pools: Vec<PoolKey> = volume_by_bucket
+        .keys()
+        .chain(tvl_by_bucket.keys())
+        .chain(base.keys())
+        .cloned()
+        .collect();
+    pools.sort();
+    pools.dedup();
+
+    let step = granularity.length();
+    let start = granularity.bucket_start(from);
+    let bucket_secs = step.num_seconds() as f64;
+
+    pools
+        .into_iter()
+        .map(|key| {
+            let volumes = volume_by_bucket.get(&key);
+            let tvls = tvl_by_bucket.get(&key);
+            let mut tvl = base.get(&key).copied().unwrap_or_default();
+
+            let mut points = Vec::new();
+            let mut bucket = start;
+            while bucket < to {
+                if let Some(value) = tvls.and_then(|tvls| tvls.get(&bucket)) {
+                    tvl = *value;
+                }
+                let (volume, fees, swaps) = volumes
+                    .and_then(|volumes| volumes.get(&bucket))
+                    .copied()
+                    .unwrap_or_default();
+                points.push(PoolPoint {
+                    bucket_start: bucket,
+                    tvl,
+                    volume,
+                    fees,
+                    swaps,
+                    apr: annualized_apr(fees, tvl, bucket_secs),
+                });
+                bucket = bucket + step;
+            }
+
+            PoolSeries {
+                chain_id: key.0,
+                pool_id: key.1,
+                granularity,
+                points,
+            }
+        })
+        .collect()
+}
+
+/// Series for all pools over `[from, to)` from the indexer rollups
+pub async fn load_series(
+    indexer: &IndexerStorage,
+    granularity: Granularity,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<PoolSeries>> {
+    let from = granularity.bucket_start(from);
+    let (volumes, tvls, base) = tokio::try_join!(
+        indexer.pool_volume_rollups(granularity, from, to),
+        indexer.pool_tvl_rollups(granularity, from, to),
+        indexer.latest_pool_tvl(granularity, from),
+    )
+    .map_err(|e| internal_error(format!("Failed to load pool rollups: {}", e)))?;
+
+    Ok(build_series(granularity, from, to, &volumes, &tvls, &base))
+}
+
+/// Headline numbers from an hourly series covering at least the last 7 days
+pub fn summarize(series: &PoolSeries, now: DateTime<Utc>) -> PoolSummary {
+    let sum_since = |since: DateTime<Utc>| {
+        series
+            .points
+            .iter()
+            .filter(|point| point.bucket_start >= since)
+            .fold((U256::zero(), U256::zero()), |(volume, fees), point| {
+                (volume.saturating_add(point.volume), fees.saturating_add(point.fees))
+            })
+    };
+    let (volume_24h, fees_24h) = sum_since(now - Duration::days(1));
+    let (volume_7d, fees_7d) = sum_since(now - Duration::days(7));
+    let tvl = series.points.last().map(|point| point.tvl).unwrap_or_default();
+
+    PoolSummary {
+        chain_id: series.chain_id,
+        pool_id: series.pool_id.clone(),
+        tvl,
+        volume_24h,
+        volume_7d,
+        fees_24h,
+        fees_7d,
+        apr_7d: annualized_apr(fees_7d, tvl, Duration::days(7).num_seconds() as f64),
+    }
+}
+
+/// `fees` earned over `period_secs` on `tvl`, scaled to a year
+pub fn annualized_apr(fees: U256, tvl: U256, period_secs: f64) -> f64 {
+    if tvl.is_zero() || period_secs <= 0.0 {
+        return 0.0;
+    }
+    to_f64(fees) / to_f64(tvl) * (YEAR_SECS / period_secs)
+}
+
+fn to_f64(value: U256) -> f64 {
+    value.to_string().parse().unwrap_or(f64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 6, 1, hour, 0, 0).unwrap()
+    }
+
+    fn volume(hour: u32, token_in: &str, amount: u64) -> PoolVolumeRollup {
+        PoolVolumeRollup {
+            bucket_start: at(hour),
+            chain_id: 1,
+            pool_id: "7".to_string(),
+            token_in: token_in.to_string(),
+            swaps: 1,
+            volume_in: U256::from(amount),
+            volume_out: U256::from(amount),
+            fees: U256::from(amount * 3 / 1_000),
+        }
+    }
+
+    fn tvl(hour: u32, amount: u64) -> PoolTvlRollup {
+        PoolTvlRollup {
+            bucket_start: at(hour),
+            chain_id: 1,
+            pool_id: "7".to_string(),
+            tvl: U256::from(amount),
+            liquidity_added: U256::zero(),
+        }
+    }
+
+    #[test]
+    fn test_series_fills_gaps_and_carries_tvl() {
+        let series = build_series(
+            Granularity::Hourly,
+            at(0),
+            at(4),
+            &[volume(1, "0", 1_000), volume(1, "1", 2_000), volume(3, "0", 500)],
+            &[tvl(2, 2_000_000)],
+            &[tvl(0, 1_000_000)],
+        );
+
+        assert_eq!(series.len(), 1);
+        let points = &series[0].points;
+        assert_eq!(points.len(), 4);
+        assert_eq!(points.iter().map(|p| p.tvl.as_u64()).collect::<Vec<_>>(), vec![1_000_000, 1_000_000, 2_000_000, 2_000_000]);
+        assert_eq!(points[1].volume, U256::from(3_000));
+        assert_eq!(points[1].swaps, 2);
+        assert!(points[0].volume.is_zero() && points[0].apr == 0.0);
+        assert!(points[1].apr > 0.0);
+    }
+
+    #[test]
+    fn test_apr_and_windows() {
+        // 0.1% of TVL in fees over a week is about 5.2% a year
+        let apr = annualized_apr(U256::from(1_000), U256::from(1_000_000), Duration::days(7).num_seconds() as f64);
+        assert!((apr - 0.0521).abs() < 0.001);
+        assert_eq!(annualized_apr(U256::from(1), U256::zero(), 3_600.0), 0.0);
+
+        assert_eq!(Window::parse("7d").unwrap(), Window::Week);
+        assert!(Window::parse("2w").is_err());
+        assert!(Window::Quarter.check_granularity(Granularity::Hourly).is_err());
+        assert!(Window::Quarter.check_granularity(Granularity::Daily).is_ok());
+        assert!(parse_granularity(Some("weekly")).is_err());
+    }
+}
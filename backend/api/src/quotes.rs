@@ -0,0 +1,343 @@
+//! Quote aggregation for draft intents
+//!
+//! A draft intent is sent to every active solver with a quote endpoint, and
+//! priced against the configured orbital pools, in parallel. Whatever
+//! arrives within the solver timeout is ranked by destination amount.
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use ethers::{
+    providers::{Http, Provider},
+    types::{Address, U256},
+};
+use futures_util::future::join_all;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::{collections::HashMap, sync::Arc, time::{Duration, Instant}};
+use uuid::Uuid;
+
+use intents_solver::pool_state::{OrbitalPoolConfig, PoolStateProvider};
+
+use crate::{
+    config::{Config, QuoteConfig},
+    database::{QuoteDb, SolverDb},
+    error::{validation_error, ApiError, Result},
+    models::{IntentQuote, QuoteIntentRequest, QuoteResponse, SolverRecord},
+};
+
+/// Body POSTed to a solver's quote endpoint
+#[derive(Debug, Serialize)]
+struct SolverQuoteRequest<'a> {
+    quote_id: Uuid,
+    #[serde(flatten)]
+    intent: &'a QuoteIntentRequest,
+    /// Replies after this are ignored
+    respond_by: DateTime<Utc>,
+}
+
+/// What a solver's quote endpoint returns
+#[derive(Debug, Deserialize)]
+struct SolverQuoteReply {
+    dest_amount: U256,
+    expires_at: Option<DateTime<Utc>>,
+    estimated_gas: Option<U256>,
+    estimated_time_secs: Option<u64>,
+}
+
+pub struct QuoteAggregator {
+    config: QuoteConfig,
+    client: reqwest::Client,
+    pools: PoolStateProvider,
+}
+
+impl QuoteAggregator {
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.quoting.solver_timeout_ms))
+            .build()
+            .map_err(|e| ApiError::Internal(format!("Failed to build quote client: {}", e)))?;
+
+        Ok(Self {
+            config: config.quoting.clone(),
+            client,
+            pools: pool_state_provider(config)?,
+        })
+    }
+
+    /// Collect and rank quotes for `request`, recording them for fill-quality
+    /// analytics
+    pub async fn quote(&self, db: &PgPool, request: &QuoteIntentRequest) -> Result<QuoteResponse> {
+        validate_quote_request(request)?;
+
+        let quote_id = Uuid::new_v4();
+        let created_at = Utc::now();
+        let solvers = SolverDb::get_quoting_solvers(db, request.source_chain_id, request.source_token, self.config.max_solvers).await?;
+
+        let solver_quotes = join_all(
+            solvers.iter().map(|solver| self.solver_quote(solver, quote_id, request, created_at)),
+        );
+        let (solver_quotes, orbital_quote) = tokio::join!(solver_quotes, self.orbital_quote(request));
+
+        let quotes = rank_quotes(
+            solver_quotes.into_iter().flatten().chain(orbital_quote).collect(),
+            request.min_dest_amount,
+            created_at,
+            created_at + ChronoDuration::seconds(self.config.quote_ttl_secs as i64),
+        );
+
+        let response = QuoteResponse {
+            quote_id,
+            best: quotes.first().cloned(),
+            expires_at: quotes.first().map(|quote| quote.expires_at),
+            quotes,
+            solvers_asked: solvers.len() as u32,
+            created_at,
+        };
+
+        // A quote is still useful to the caller if analytics can't be written
+        if let Err(e) = QuoteDb::record_quotes(db, request, &response).await {
+            tracing::warn!("Failed to record quote {}: {}", quote_id, e);
+        }
+
+        Ok(response)
+    }
+
+    async fn solver_quote(
+        &self,
+        solver: &SolverRecord,
+        quote_id: Uuid,
+        request: &QuoteIntentRequest,
+        created_at: DateTime<Utc>,
+    ) -> Option<IntentQuote> {
+        let url = solver.quote_url.as_deref()?;
+        let started = Instant::now();
+        let body = SolverQuoteRequest {
+            quote_id,
+            intent: request,
+            respond_by: created_at + ChronoDuration::milliseconds(self.config.solver_timeout_ms as i64),
+        };
+
+        let reply = self
+            .client
+            .post(url)
+            .json(&body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        let reply: SolverQuoteReply = match reply {
+            Ok(response) => match response.json().await {
+                Ok(reply) => reply,
+                Err(e) => {
+                    tracing::debug!("Invalid quote from solver {}: {}", solver.address, e);
+                    return None;
+                }
+            },
+            Err(e) => {
+                tracing::debug!("Quote request to solver {} failed: {}", solver.address, e);
+                return None;
+            }
+        };
+
+        Some(IntentQuote {
+            source: "solver".to_string(),
+            solver_address: solver.address.parse().ok(),
+            dest_amount: reply.dest_amount,
+            estimated_gas: reply.estimated_gas,
+            estimated_time_secs: reply.estimated_time_secs,
+            expires_at: reply.expires_at.unwrap_or(DateTime::<Utc>::MAX_UTC),
+            latency_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Price the swap against the orbital pool on the source chain, or on
+    /// the destination chain if only that pool holds both tokens
+    async fn orbital_quote(&self, request: &QuoteIntentRequest) -> Option<IntentQuote> {
+        let started = Instant::now();
+        for chain_id in [request.source_chain_id, request.dest_chain_id] {
+            if !self.pools.has_pool(chain_id) {
+                continue;
+            }
+            let snapshot = match self.pools.snapshot(chain_id).await {
+                Ok(snapshot) => snapshot,
+                Err(e) => {
+                    tracing::debug!("Failed to read orbital pool on chain {}: {}", chain_id, e);
+                    continue;
+                }
+            };
+            let (Some(token_in), Some(token_out)) = (
+                snapshot.token_index(request.source_token),
+                snapshot.token_index(request.dest_token),
+            ) else {
+                continue;
+            };
+            if !snapshot.active {
+                continue;
+            }
+
+            let dest_amount = orbital_amount_out(
+                &snapshot.reserves,
+                token_in,
+                token_out,
+                request.source_amount,
+                snapshot.radius_squared,
+                snapshot.fee_bps,
+            )?;
+            return Some(IntentQuote {
+                source: "orbital".to_string(),
+                solver_address: None,
+                dest_amount,
+                estimated_gas: None,
+                estimated_time_secs: None,
+                expires_at: DateTime::<Utc>::MAX_UTC,
+                latency_ms: started.elapsed().as_millis() as u64,
+            });
+        }
+        None
+    }
+}
+
+/// Reads the orbital pool configured on each chain
+pub fn pool_state_provider(config: &Config) -> Result<PoolStateProvider> {
+    let mut providers = HashMap::new();
+    let mut pools = HashMap::new();
+    for chain in &config.chains {
+        let Some(pool_id) = chain.orbital_pool_id else { continue };
+        let provider = Provider::<Http>::try_from(chain.rpc_url.as_str())
+            .map_err(|e| ApiError::Internal(format!("Invalid RPC URL for chain {}: {}", chain.chain_id, e)))?;
+        providers.insert(chain.chain_id, Arc::new(provider));
+        pools.insert(chain.chain_id, OrbitalPoolConfig {
+            amm: chain.orbital_amm_contract,
+            pool_id: U256::from(pool_id),
+        });
+    }
+    Ok(PoolStateProvider::new(providers, pools))
+}
+
+/// Output of the orbital sphere swap after the pool's fee on the input
+pub fn orbital_amount_out(
+    reserves: &[U256],
+    token_in: usize,
+    token_out: usize,
+    amount_in: U256,
+    radius_squared: U256,
+    fee_bps: U256,
+) -> Option<U256> {
+    let fee_bps = fee_bps.min(U256::from(10_000));
+    let amount_in = amount_in * (U256::from(10_000) - fee_bps) / U256::from(10_000);
+    let reserves: Vec<_> = reserves.iter().map(|&reserve| to_math(reserve)).collect();
+
+    orbital_math::sphere::calculate_amount_out_sphere(
+        &reserves,
+        token_in,
+        token_out,
+        to_math(amount_in),
+        to_math(radius_squared),
+    )
+    .ok()
+    .map(|amount| U256::from_big_endian(&amount.to_be_bytes::<32>()))
+}
+
+fn to_math(value: U256) -> alloy_primitives::U256 {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    alloy_primitives::U256::from_be_bytes(bytes)
+}
+
+/// Drop quotes below `min_dest_amount` or already expired, cap expiries at
+/// `max_expiry` and order best first. Equal amounts go to the faster quote.
+pub fn rank_quotes(
+    mut quotes: Vec<IntentQuote>,
+    min_dest_amount: Option<U256>,
+    now: DateTime<Utc>,
+    max_expiry: DateTime<Utc>,
+) -> Vec<IntentQuote> {
+    for quote in &mut quotes {
+        quote.expires_at = quote.expires_at.min(max_expiry);
+    }
+    quotes.retain(|quote| {
+        quote.expires_at > now && min_dest_amount.map_or(true, |min| quote.dest_amount >= min)
+    });
+    quotes.sort_by(|a, b| b.dest_amount.cmp(&a.dest_amount).then(a.latency_ms.cmp(&b.latency_ms)));
+    quotes
+}
+
+pub fn validate_quote_request(request: &QuoteIntentRequest) -> Result<()> {
+    if request.source_amount.is_zero() {
+        return Err(validation_error("Source amount must be greater than zero"));
+    }
+    if request.source_chain_id == request.dest_chain_id && request.source_token == request.dest_token {
+        return Err(validation_error("Source and destination cannot be the same token on the same chain"));
+    }
+    Ok(())
+}
+
+/// Solver quote endpoints must be absolute http(s) URLs
+pub fn validate_quote_url(url: &str) -> Result<()> {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && parsed.host().is_some() => Ok(()),
+        _ => Err(validation_error("Quote URL must be an absolute http(s) URL")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn quote(dest_amount: u64, latency_ms: u64, expires_at: DateTime<Utc>) -> IntentQuote {
+        IntentQuote {
+            source: "solver".to_string(),
+            solver_address: Some(Address::from_low_u64_be(latency_ms)),
+            dest_amount: U256::from(dest_amount),
+            estimated_gas: None,
+            estimated_time_secs: None,
+            expires_at,
+            latency_ms,
+        }
+    }
+
+    #[test]
+    fn test_rank_quotes_orders_and_filters() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let later = now + ChronoDuration::seconds(60);
+        let max_expiry = now + ChronoDuration::seconds(30);
+
+        let ranked = rank_quotes(
+            vec![
+                quote(900, 10, later),
+                quote(1_000, 50, later),
+                quote(1_000, 20, later),
+                quote(2_000, 5, now),
+                quote(500, 1, later),
+            ],
+            Some(U256::from(800)),
+            now,
+            max_expiry,
+        );
+
+        let order: Vec<(u64, u64)> = ranked.iter().map(|q| (q.dest_amount.as_u64(), q.latency_ms)).collect();
+        assert_eq!(order, vec![(1_000, 20), (1_000, 50), (900, 10)]);
+        assert!(ranked.iter().all(|q| q.expires_at == max_expiry));
+    }
+
+    #[test]
+    fn test_quote_url_and_request_validation() {
+        assert!(validate_quote_url("https://solver.example.com/quote").is_ok());
+        assert!(validate_quote_url("ftp://solver.example.com").is_err());
+        assert!(validate_quote_url("not a url").is_err());
+
+        let mut request = QuoteIntentRequest {
+            source_chain_id: 1,
+            dest_chain_id: 1,
+            source_token: Address::from_low_u64_be(1),
+            dest_token: Address::from_low_u64_be(1),
+            source_amount: U256::from(10),
+            min_dest_amount: None,
+            user_address: None,
+        };
+        assert!(validate_quote_request(&request).is_err());
+        request.dest_chain_id = 10;
+        assert!(validate_quote_request(&request).is_ok());
+        request.source_amount = U256::zero();
+        assert!(validate_quote_request(&request).is_err());
+    }
+}
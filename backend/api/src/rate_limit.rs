@@ -0,0 +1,189 @@
+//! Request rate limiting shared across API instances
+//!
+//! Counts live in Redis, so every instance draws from the same budget and
+//! scaling out doesn't multiply anyone's quota. Limits use a sliding window
+//! counter: the previous window's count is weighted by how much of it still
+//! overlaps the sliding window, which avoids the double burst a fixed window
+//! allows at its edges. Checking and counting happen in one Lua script so
+//! concurrent requests can't both take the last slot.
+
+use axum::http::{HeaderMap, HeaderValue};
+use redis::{aio::MultiplexedConnection, Script};
+use std::{
+    sync::OnceLock,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{cache::CacheKeys, error::{ApiError, Result}};
+
+pub const WINDOW: Duration = Duration::from_secs(60);
+
+/// KEYS: current window, previous window. ARGV: limit, weight of the
+/// previous window, window length in ms. Returns whether the request was
+/// allowed, then the current and previous windows' counts.
+const SLIDING_WINDOW_SCRIPT: &str = r#"
+local current = tonumber(redis.call('GET', KEYS[1]) or '0')
+local previous = tonumber(redis.call('GET', KEYS[2]) or '0')
+local carried = math.floor(previous * tonumber(ARGV[2]) / 1000000)
+if carried + current >= tonumber(ARGV[1]) then
+    return {0, current, previous}
+end
+current = redis.call('INCR', KEYS[1])
+if current == 1 then
+    redis.call('PEXPIRE', KEYS[1], tonumber(ARGV[3]) * 2)
+end
+return {1, current, previous}
+"#;
+
+/// Weights are passed to the script in millionths, as Redis Lua returns
+/// and takes integers more reliably than floats
+const WEIGHT_SCALE: f64 = 1_000_000.0;
+
+fn script() -> &'static Script {
+    static SCRIPT: OnceLock<Script> = OnceLock::new();
+    SCRIPT.get_or_init(|| Script::new(SLIDING_WINDOW_SCRIPT))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Until the current window ends
+    pub reset_after: Duration,
+    /// Until a rejected request would be allowed
+    pub retry_after: Option<Duration>,
+}
+
+/// Where `now` falls: the window's index and how far into it we are
+fn window_position(now: Duration, window: Duration) -> (u64, Duration) {
+    let window_ms = window.as_millis().max(1) as u64;
+    let now_ms = now.as_millis() as u64;
+    (now_ms / window_ms, Duration::from_millis(now_ms % window_ms))
+}
+
+/// Share of the previous window still inside the sliding window
+fn previous_weight(elapsed: Duration, window: Duration) -> f64 {
+    1.0 - elapsed.as_secs_f64() / window.as_secs_f64()
+}
+
+/// Build the decision from the counts the script saw
+pub fn decide(
+    allowed: bool,
+    limit: u32,
+    current: u64,
+    previous: u64,
+    elapsed: Duration,
+    window: Duration,
+) -> RateLimitDecision {
+    let weight = previous_weight(elapsed, window);
+    let used = (previous as f64 * weight).floor() as u64 + current;
+    let reset_after = window.saturating_sub(elapsed);
+
+    let retry_after = (!allowed).then(|| {
+        // The carried-over count shrinks as the window slides; a slot opens
+        // once it falls below what's left after this window's requests.
+        // If this window alone is full, wait for the next one.
+        let room = (limit as u64).saturating_sub(current);
+        if room == 0 || previous == 0 {
+            return reset_after;
+        }
+        let target_weight = room as f64 / previous as f64;
+        let wait = window.as_secs_f64() * (weight - target_weight).max(0.0);
+        Duration::from_secs_f64(wait).min(reset_after)
+    });
+
+    RateLimitDecision {
+        allowed,
+        limit,
+        remaining: (limit as u64).saturating_sub(used) as u32,
+        reset_after,
+        retry_after,
+    }
+}
+
+/// Count a request by `identifier` against `limit` per window
+pub async fn check(
+    redis: &mut MultiplexedConnection,
+    identifier: &str,
+    limit: u32,
+    window: Duration,
+) -> Result<RateLimitDecision> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let (index, elapsed) = window_position(now, window);
+    let weight = previous_weight(elapsed, window);
+
+    let (allowed, current, previous): (u8, u64, u64) = script()
+        .key(CacheKeys::rate_limit(identifier, index))
+        .key(CacheKeys::rate_limit(identifier, index.saturating_sub(1)))
+        .arg(limit)
+        .arg((weight * WEIGHT_SCALE) as u64)
+        .arg(window.as_millis() as u64)
+        .invoke_async(redis)
+        .await
+        .map_err(|e| ApiError::Redis(e.to_string()))?;
+
+    Ok(decide(allowed == 1, limit, current, previous, elapsed, window))
+}
+
+/// The IETF `RateLimit-*` fields, plus `Retry-After` on rejections
+pub fn apply_headers(headers: &mut HeaderMap, decision: &RateLimitDecision, window: Duration) {
+    let mut set = |name: &'static str, value: String| {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(name, value);
+        }
+    };
+    set("RateLimit-Limit", decision.limit.to_string());
+    set("RateLimit-Remaining", decision.remaining.to_string());
+    set("RateLimit-Reset", ceil_secs(decision.reset_after).to_string());
+    set("RateLimit-Policy", format!("{};w={}", decision.limit, window.as_secs()));
+    if let Some(retry_after) = decision.retry_after {
+        set("Retry-After", ceil_secs(retry_after).max(1).to_string());
+    }
+}
+
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_millis().div_ceil(1_000) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sliding_window_counts_previous_window() {
+        let window = Duration::from_secs(60);
+        let (index, elapsed) = window_position(Duration::from_millis(125_500), window);
+        assert_eq!((index, elapsed), (2, Duration::from_millis(5_500)));
+
+        // A quarter into the window, three quarters of the previous 40
+        // requests still count
+        let decision = decide(true, 100, 10, 40, Duration::from_secs(15), window);
+        assert_eq!(decision.remaining, 60);
+        assert_eq!(decision.reset_after, Duration::from_secs(45));
+        assert_eq!(decision.retry_after, None);
+    }
+
+    #[test]
+    fn test_retry_after_when_limited() {
+        let window = Duration::from_secs(60);
+
+        // Full this window: wait for the next one
+        let decision = decide(false, 10, 10, 0, Duration::from_secs(20), window);
+        assert_eq!(decision.remaining, 0);
+        assert_eq!(decision.retry_after, Some(Duration::from_secs(40)));
+
+        // Half of 20 carried over plus 5 now: a slot opens once the carried
+        // count drops below 5, at weight 0.25, 15s later
+        let decision = decide(false, 10, 5, 20, Duration::from_secs(30), window);
+        let retry_after = decision.retry_after.unwrap();
+        assert!((retry_after.as_secs_f64() - 15.0).abs() < 0.01, "{:?}", retry_after);
+
+        let mut headers = HeaderMap::new();
+        apply_headers(&mut headers, &decision, window);
+        assert_eq!(headers["RateLimit-Limit"], "10");
+        assert_eq!(headers["RateLimit-Reset"], "30");
+        assert_eq!(headers["RateLimit-Policy"], "10;w=60");
+        assert_eq!(headers["Retry-After"], "15");
+    }
+}
@@ -0,0 +1,242 @@
+//! Shared HTTP cache for the analytics routes
+//!
+//! Analytics and pool state are read far more often than they change, and
+//! every instance would otherwise rebuild the same responses from the
+//! indexer. Successful GET responses are kept in Redis, keyed by path and
+//! normalized query, and served with an `ETag` so clients can revalidate
+//! with `If-None-Match` and get a 304 instead of the body.
+//!
+//! Entries are scoped to a generation number kept in Redis. Bumping it
+//! drops every entry at once: the indexer announces each rollup run over
+//! Postgres NOTIFY and `watch_rollups` moves the generation to match, and
+//! operators can do the same through the admin API. Old entries are left
+//! to expire.
+
+use axum::http::{header, HeaderMap, HeaderValue, Method, StatusCode};
+use axum::{body::Body, response::Response};
+use intents_indexer::rollups::ROLLUPS_UPDATED_CHANNEL;
+use redis::{aio::MultiplexedConnection, AsyncCommands};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::postgres::PgListener;
+use std::time::Duration;
+
+use crate::{
+    cache::CacheKeys,
+    error::{ApiError, Result},
+};
+
+/// Used when the handler doesn't say how long its response stays fresh
+pub const DEFAULT_MAX_AGE: u64 = 60;
+/// Largest response body the cache will buffer
+pub const MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// GETs on the analytics routes, with or without the `/api/v1` prefix
+pub fn applies_to(method: &Method, path: &str) -> bool {
+    if method != Method::GET {
+        return false;
+    }
+    let path = path.strip_prefix("/api/v1/analytics").unwrap_or(path);
+    match path.trim_end_matches('/').split('/').collect::<Vec<_>>().as_slice() {
+        [""] => true,
+        ["", "public" | "chains" | "tokens" | "solvers" | "volume" | "pools"] => true,
+        ["", "pools", chain_id, pool_id] => !chain_id.is_empty() && !pool_id.is_empty(),
+        _ => false,
+    }
+}
+
+/// Query parameters sorted, so the same request spelled differently shares
+/// an entry
+pub fn normalize_query(query: Option<&str>) -> String {
+    let mut pairs: Vec<&str> = query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .collect();
+    pairs.sort_unstable();
+    pairs.join("&")
+}
+
+/// Where a request's response is kept within its generation
+pub fn entry_id(path: &str, query: Option<&str>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(path.trim_end_matches('/').as_bytes());
+    hasher.update(b"?");
+    hasher.update(normalize_query(query).as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Strong validator over the body
+pub fn etag(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    format!("\"{}\"", hex::encode(&digest[..16]))
+}
+
+/// Whether an `If-None-Match` header covers `etag`. Comparison is weak, as
+/// RFC 9110 asks for this header.
+pub fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+/// `max-age` from a `Cache-Control` header; `None` when the response must
+/// not be stored
+pub fn max_age(cache_control: Option<&HeaderValue>) -> Option<u64> {
+    let Some(value) = cache_control.and_then(|value| value.to_str().ok()) else {
+        return Some(DEFAULT_MAX_AGE);
+    };
+    let mut max_age = DEFAULT_MAX_AGE;
+    for directive in value.split(',').map(str::trim) {
+        if directive.eq_ignore_ascii_case("no-store") || directive.eq_ignore_ascii_case("no-cache") {
+            return None;
+        }
+        if let Some(seconds) = directive.strip_prefix("max-age=") {
+            max_age = seconds.parse().ok()?;
+        }
+    }
+    (max_age > 0).then_some(max_age)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub etag: String,
+    pub cache_control: String,
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+impl CachedResponse {
+    /// Only text bodies are kept; the analytics routes all send JSON
+    pub fn capture(cache_control: String, content_type: Option<&HeaderValue>, body: &[u8]) -> Option<Self> {
+        Some(Self {
+            etag: etag(body),
+            cache_control,
+            content_type: content_type.and_then(|value| value.to_str().ok()).map(str::to_string),
+            body: String::from_utf8(body.to_vec()).ok()?,
+        })
+    }
+
+    /// The stored response, or a bodiless 304 when the client already has it
+    pub fn respond(self, request_headers: &HeaderMap) -> Response {
+        let not_modified = etag_matches(request_headers, &self.etag);
+        let mut response = if not_modified {
+            let mut response = Response::new(Body::empty());
+            *response.status_mut() = StatusCode::NOT_MODIFIED;
+            response
+        } else {
+            Response::new(Body::from(self.body))
+        };
+
+        let headers = response.headers_mut();
+        if let Ok(value) = HeaderValue::from_str(&self.etag) {
+            headers.insert(header::ETAG, value);
+        }
+        if let Ok(value) = HeaderValue::from_str(&self.cache_control) {
+            headers.insert(header::CACHE_CONTROL, value);
+        }
+        if let Some(value) = self.content_type.filter(|_| !not_modified).and_then(|value| HeaderValue::from_str(&value).ok()) {
+            headers.insert(header::CONTENT_TYPE, value);
+        }
+        response
+    }
+}
+
+/// The generation entries are currently stored under
+pub async fn current_generation(redis: &mut MultiplexedConnection) -> Result<u64> {
+    let generation: Option<u64> = redis
+        .get(CacheKeys::http_cache_generation())
+        .await
+        .map_err(|e| ApiError::Redis(e.to_string()))?;
+    Ok(generation.unwrap_or_default())
+}
+
+/// Start a new generation, dropping everything cached before it
+pub async fn invalidate(redis: &mut MultiplexedConnection, generation: u64) -> Result<()> {
+    redis
+        .set::<_, _, ()>(CacheKeys::http_cache_generation(), generation)
+        .await
+        .map_err(|e| ApiError::Redis(e.to_string()))
+}
+
+/// Invalidate the cache whenever the indexer writes new rollups. Runs for
+/// the life of the process, reconnecting after errors.
+pub async fn watch_rollups(indexer_url: String, mut redis: MultiplexedConnection) {
+    loop {
+        if let Err(e) = listen_for_rollups(&indexer_url, &mut redis).await {
+            tracing::warn!("Rollup listener failed, reconnecting: {}", e);
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn listen_for_rollups(indexer_url: &str, redis: &mut MultiplexedConnection) -> Result<()> {
+    let mut listener = PgListener::connect(indexer_url).await?;
+    listener.listen(ROLLUPS_UPDATED_CHANNEL).await?;
+
+    loop {
+        let notification = listener.recv().await?;
+        // The payload is the run's time in ms, which only ever moves forward
+        let generation = notification
+            .payload()
+            .parse()
+            .unwrap_or_else(|_| chrono::Utc::now().timestamp_millis() as u64);
+        invalidate(redis, generation).await?;
+        tracing::debug!("Analytics cache invalidated by rollups at {}", generation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_applies_to_analytics_reads() {
+        assert!(applies_to(&Method::GET, "/api/v1/analytics"));
+        assert!(applies_to(&Method::GET, "/api/v1/analytics/public"));
+        assert!(applies_to(&Method::GET, "/api/v1/analytics/pools/1/0xabc"));
+        assert!(applies_to(&Method::GET, "/pools"));
+        assert!(!applies_to(&Method::POST, "/api/v1/analytics/public"));
+        assert!(!applies_to(&Method::GET, "/api/v1/intents"));
+        assert!(!applies_to(&Method::GET, "/api/v1/analytics/pools/1"));
+
+        assert_eq!(
+            entry_id("/api/v1/analytics/pools/1/0xabc", Some("interval=30d&granularity=daily")),
+            entry_id("/api/v1/analytics/pools/1/0xabc/", Some("granularity=daily&interval=30d&")),
+        );
+        assert_ne!(
+            entry_id("/api/v1/analytics/pools/1/0xabc", Some("interval=30d")),
+            entry_id("/api/v1/analytics/pools/1/0xabc", Some("interval=7d")),
+        );
+
+        assert_eq!(max_age(None), Some(DEFAULT_MAX_AGE));
+        assert_eq!(max_age(Some(&HeaderValue::from_static("public, max-age=300"))), Some(300));
+        assert_eq!(max_age(Some(&HeaderValue::from_static("no-store"))), None);
+        assert_eq!(max_age(Some(&HeaderValue::from_static("max-age=0"))), None);
+    }
+
+    #[test]
+    fn test_revalidation_with_etag() {
+        let content_type = HeaderValue::from_static("application/json");
+        let cached = CachedResponse::capture("public, max-age=60".to_string(), Some(&content_type), br#"{"tvl":"1"}"#).unwrap();
+        assert_eq!(cached.etag, etag(br#"{"tvl":"1"}"#));
+        assert_ne!(cached.etag, etag(br#"{"tvl":"2"}"#));
+
+        let response = cached.clone().respond(&HeaderMap::new());
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ETAG], cached.etag.as_str());
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "public, max-age=60");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(&format!("\"stale\", W/{}", cached.etag)).unwrap());
+        let response = cached.respond(&headers);
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert!(response.headers().get(header::CONTENT_TYPE).is_none());
+    }
+}
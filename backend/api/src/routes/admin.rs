@@ -0,0 +1,273 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post, put},
+    Router,
+};
+use ethers::types::H256;
+use intents_engine::{
+    lifecycle::TransitionRecord,
+    retry::DeadLetter,
+    validator::rules::ChainRateLimit,
+    DrainReport, EngineError,
+};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{
+    models::*,
+    database::{AuditDb, IntentDb},
+    error::{Result, ApiError, validation_error, not_found},
+    auth::check_permission,
+    handlers::update_intent_status_and_broadcast,
+    response_cache,
+    pagination::{CursorPage, CursorValue, PageParams},
+};
+
+const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 30;
+const MAX_DRAIN_TIMEOUT_SECS: u64 = 300;
+
+// Operator routes (admin and operator roles)
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/status", get(get_engine_status))
+        .route("/intake/pause", post(pause_intake))
+        .route("/intake/resume", post(resume_intake))
+        .route("/queues/drain", post(drain_queues))
+        .route("/intents/:intent_id/expire", post(expire_intent))
+        .route("/rate-limits/chains", get(list_chain_rate_limits))
+        .route("/rate-limits/chains/:chain_id", put(set_chain_rate_limit).delete(remove_chain_rate_limit))
+        .route("/dead-letters", get(list_dead_letters))
+        .route("/dead-letters/:intent_id", get(get_dead_letter).delete(resolve_dead_letter))
+        .route("/cache/invalidate", post(invalidate_response_cache))
+        .route("/audit-log", get(list_audit_log))
+}
+
+// Intake, queue and rate-limit state at a glance
+async fn get_engine_status(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<EngineStatusResponse>> {
+    check_permission(&claims, "/api/v1/admin/status", "GET")?;
+    
+    let engine = &state.intents_engine;
+    Ok(Json(EngineStatusResponse {
+        intake_paused: engine.intake_paused(),
+        queue: engine.queue_metrics().await,
+        in_flight: engine.in_flight_count().await,
+        dead_letters: engine.dead_letters().await.len(),
+        chain_rate_limits: engine.chain_rate_limits().limits().into_iter().collect(),
+    }))
+}
+
+// Stop accepting new intents; queued ones still execute
+async fn pause_intake(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<StatusCode> {
+    check_permission(&claims, "/api/v1/admin/intake/pause", "POST")?;
+    
+    state.intents_engine.set_intake_paused(true);
+    tracing::warn!("Intent intake paused by {}", claims.sub);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn resume_intake(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<StatusCode> {
+    check_permission(&claims, "/api/v1/admin/intake/resume", "POST")?;
+    
+    state.intents_engine.set_intake_paused(false);
+    tracing::warn!("Intent intake resumed by {}", claims.sub);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Pause intake and wait for executor queues to empty
+async fn drain_queues(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(request): Json<DrainQueuesRequest>,
+) -> Result<Json<DrainReport>> {
+    check_permission(&claims, "/api/v1/admin/queues/drain", "POST")?;
+    
+    let timeout_secs = request.timeout_secs.unwrap_or(DEFAULT_DRAIN_TIMEOUT_SECS);
+    if timeout_secs > MAX_DRAIN_TIMEOUT_SECS {
+        return Err(validation_error(format!("timeout_secs must be at most {}", MAX_DRAIN_TIMEOUT_SECS)));
+    }
+    
+    tracing::warn!("Executor queues draining, requested by {}", claims.sub);
+    let report = state.intents_engine.drain(Duration::from_secs(timeout_secs)).await;
+    if !report.drained {
+        tracing::warn!("Drain timed out with {} queued and {} in flight", report.queued, report.in_flight);
+    }
+    Ok(Json(report))
+}
+
+// Expire an intent that hasn't started executing
+async fn expire_intent(
+    State(state): State<AppState>,
+    Path(intent_id_str): Path<String>,
+    claims: Claims,
+    Json(request): Json<ExpireIntentRequest>,
+) -> Result<Json<TransitionRecord>> {
+    check_permission(&claims, "/api/v1/admin/intents/*/expire", "POST")?;
+    
+    let intent_id = H256::from_str(&intent_id_str)
+        .map_err(|_| validation_error("Invalid intent ID format"))?;
+    if request.reason.trim().is_empty() {
+        return Err(validation_error("A reason is required"));
+    }
+    
+    let reason = format!("{} (expired by {})", request.reason.trim(), claims.sub);
+    let record = state.intents_engine
+        .expire_intent(intent_id, reason.clone())
+        .await
+        .map_err(|e| match e {
+            EngineError::InvalidTransition { .. } | EngineError::InvalidIntent(_) => ApiError::BadRequest(e.to_string()),
+            e => ApiError::IntentEngine(e.to_string()),
+        })?;
+    
+    IntentDb::update_intent_status(&state.db, intent_id, "expired", None, None, None, None, None, Some(reason)).await?;
+    update_intent_status_and_broadcast(&state, intent_id, "expired", "Expired", 0.0, None).await?;
+    
+    tracing::warn!("Intent {:?} force-expired by {}", intent_id, claims.sub);
+    Ok(Json(record))
+}
+
+async fn list_chain_rate_limits(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<std::collections::BTreeMap<u64, ChainRateLimit>>> {
+    check_permission(&claims, "/api/v1/admin/rate-limits/chains", "GET")?;
+    
+    Ok(Json(state.intents_engine.chain_rate_limits().limits().into_iter().collect()))
+}
+
+// Limit intents accepted from a source chain
+async fn set_chain_rate_limit(
+    State(state): State<AppState>,
+    Path(chain_id): Path<u64>,
+    claims: Claims,
+    Json(limit): Json<ChainRateLimit>,
+) -> Result<Json<ChainRateLimit>> {
+    check_permission(&claims, "/api/v1/admin/rate-limits/chains/*", "PUT")?;
+    
+    if limit.max_intents == 0 || limit.window_secs == 0 {
+        return Err(validation_error("max_intents and window_secs must be positive; pause intake to stop all intents"));
+    }
+    if !state.config.chains.iter().any(|chain| chain.chain_id == chain_id) {
+        return Err(not_found("Chain"));
+    }
+    
+    state.intents_engine.chain_rate_limits().set_limit(chain_id, Some(limit));
+    tracing::info!("Chain {} limited to {} intents per {}s by {}", chain_id, limit.max_intents, limit.window_secs, claims.sub);
+    Ok(Json(limit))
+}
+
+async fn remove_chain_rate_limit(
+    State(state): State<AppState>,
+    Path(chain_id): Path<u64>,
+    claims: Claims,
+) -> Result<StatusCode> {
+    check_permission(&claims, "/api/v1/admin/rate-limits/chains/*", "DELETE")?;
+    
+    state.intents_engine.chain_rate_limits().set_limit(chain_id, None);
+    tracing::info!("Chain {} rate limit lifted by {}", chain_id, claims.sub);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// List intents whose execution failed after all retries
+async fn list_dead_letters(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<DeadLetter>>> {
+    check_permission(&claims, "/api/v1/admin/dead-letters", "GET")?;
+    
+    Ok(Json(state.intents_engine.dead_letters().await))
+}
+
+// Get a single dead-lettered intent
+async fn get_dead_letter(
+    State(state): State<AppState>,
+    Path(intent_id_str): Path<String>,
+    claims: Claims,
+) -> Result<Json<DeadLetter>> {
+    check_permission(&claims, "/api/v1/admin/dead-letters/*", "GET")?;
+    
+    let intent_id = H256::from_str(&intent_id_str)
+        .map_err(|_| validation_error("Invalid intent ID format"))?;
+    
+    state.intents_engine
+        .dead_letters()
+        .await
+        .into_iter()
+        .find(|entry| entry.intent_id == intent_id)
+        .map(Json)
+        .ok_or_else(|| not_found("Dead letter"))
+}
+
+// Remove a dead-lettered intent once it has been handled manually
+async fn resolve_dead_letter(
+    State(state): State<AppState>,
+    Path(intent_id_str): Path<String>,
+    claims: Claims,
+) -> Result<Json<DeadLetter>> {
+    check_permission(&claims, "/api/v1/admin/dead-letters/*", "DELETE")?;
+    
+    let intent_id = H256::from_str(&intent_id_str)
+        .map_err(|_| validation_error("Invalid intent ID format"))?;
+    
+    let entry = state.intents_engine
+        .resolve_dead_letter(intent_id)
+        .await
+        .ok_or_else(|| not_found("Dead letter"))?;
+    
+    tracing::info!("Dead letter for intent {:?} resolved by {}", intent_id, claims.sub);
+    Ok(Json(entry))
+}
+
+// Drop every cached analytics response, e.g. after fixing rollup data by hand
+async fn invalidate_response_cache(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<StatusCode> {
+    check_permission(&claims, "/api/v1/admin/cache/invalidate", "POST")?;
+    
+    let mut redis = state.redis.clone();
+    response_cache::invalidate(&mut redis, chrono::Utc::now().timestamp_millis() as u64).await?;
+    
+    tracing::info!("Response cache invalidated by {}", claims.sub);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Audit trail of privileged actions for compliance reviews, newest first
+// by default (admin only)
+async fn list_audit_log(
+    State(state): State<AppState>,
+    Query(page): Query<PageParams>,
+    Query(filter): Query<AuditLogFilter>,
+    claims: Claims,
+) -> Result<Json<CursorPage<AuditLogRecord>>> {
+    check_permission(&claims, "/api/v1/admin/audit-log", "GET")?;
+    
+    let sort_column = page.sort_column(&["occurred_at"])?;
+    let limit = page.limit();
+    let records = AuditDb::list(
+        &state.db,
+        &filter,
+        page.sort_order,
+        page.cursor(sort_column)?,
+        limit,
+    ).await?;
+    
+    let page = CursorPage::from_rows(
+        records,
+        limit,
+        sort_column,
+        |record| (CursorValue::Time(record.occurred_at), record.id),
+        Ok,
+    )?;
+    Ok(Json(page))
+}
@@ -1,16 +1,20 @@
 use axum::{
-    extract::{Query, State},
-    response::Json,
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Json},
     routing::get,
     Router,
 };
+use chrono::{Duration, Utc};
+use intents_indexer::rollups::Granularity;
 use serde::Deserialize;
 
 use crate::{
     models::{AppState, AnalyticsResponse, Claims},
-    error::Result,
+    error::{Result, not_found},
     metrics::generate_analytics_data,
     cache::CacheService,
+    pool_analytics::{self, PoolSeries, PoolSummary, Window},
 };
 
 // Analytics routes
@@ -22,6 +26,47 @@ pub fn routes() -> Router<AppState> {
         .route("/tokens", get(get_token_analytics))
         .route("/solvers", get(get_solver_analytics))
         .route("/volume", get(get_volume_analytics))
+        .route("/pools", get(get_pool_summaries))
+        .route("/pools/:chain_id/:pool_id", get(get_pool_series))
+}
+
+// TVL, volume, fees and APR for every pool (public)
+async fn get_pool_summaries(State(state): State<AppState>) -> Result<impl IntoResponse> {
+    let max_age = pool_analytics::cache_max_age(Granularity::Hourly);
+
+    let now = Utc::now();
+    let series = pool_analytics::load_series(&state.indexer, Granularity::Hourly, now - Duration::days(7), now).await?;
+    let summaries: Vec<PoolSummary> = series
+        .iter()
+        .map(|series| pool_analytics::summarize(series, now))
+        .collect();
+
+    Ok((cache_headers(max_age), Json(summaries)))
+}
+
+// Time series for one pool (public)
+async fn get_pool_series(
+    State(state): State<AppState>,
+    Path((chain_id, pool_id)): Path<(u64, String)>,
+    Query(params): Query<PoolSeriesQuery>,
+) -> Result<impl IntoResponse> {
+    let granularity = pool_analytics::parse_granularity(params.granularity.as_deref())?;
+    let window = Window::parse(params.interval.as_deref().unwrap_or("7d"))?;
+    window.check_granularity(granularity)?;
+    let max_age = pool_analytics::cache_max_age(granularity);
+
+    let now = Utc::now();
+    let series: PoolSeries = pool_analytics::load_series(&state.indexer, granularity, now - window.duration(), now)
+        .await?
+        .into_iter()
+        .find(|series| series.chain_id == chain_id && series.pool_id == pool_id)
+        .ok_or_else(|| not_found("Pool"))?;
+
+    Ok((cache_headers(max_age), Json(series)))
+}
+
+fn cache_headers(max_age: u64) -> [(header::HeaderName, String); 1] {
+    [(header::CACHE_CONTROL, format!("public, max-age={}", max_age))]
 }
 
 // Main analytics endpoint (requires authentication)
@@ -48,14 +93,7 @@ async fn get_analytics(
 // Public analytics (limited data, no authentication required)
 async fn get_public_analytics(
     State(state): State<AppState>,
-) -> Result<Json<serde_json::Value>> {
-    let mut cache = CacheService::new(state.redis.clone());
-    
-    // Check for cached public analytics
-    if let Some(cached) = cache.get::<serde_json::Value>("public_analytics").await? {
-        return Ok(Json(cached));
-    }
-    
+) -> Result<impl IntoResponse> {
     // Generate public analytics (limited data)
     let total_intents: (i64,) = sqlx::query_as(
         "SELECT COUNT(*) FROM intents"
@@ -92,10 +130,8 @@ async fn get_public_analytics(
         "last_updated": chrono::Utc::now().to_rfc3339()
     });
     
-    // Cache public analytics for 5 minutes
-    cache.set("public_analytics", &public_data, Some(std::time::Duration::from_secs(300))).await.ok();
-    
-    Ok(Json(public_data))
+    // Fresh for 5 minutes in the response cache
+    Ok((cache_headers(300), Json(public_data)))
 }
 
 // Chain-specific analytics
@@ -324,6 +360,13 @@ struct SolverAnalyticsQuery {
     limit: Option<u64>,
 }
 
+#[derive(Deserialize)]
+struct PoolSeriesQuery {
+    granularity: Option<String>,
+    /// How far back: 24h, 7d, 30d, 90d or 1y
+    interval: Option<String>,
+}
+
 #[derive(Deserialize)]
 struct VolumeAnalyticsQuery {
     timeframe: Option<String>,
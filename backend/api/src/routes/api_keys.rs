@@ -0,0 +1,223 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post, put},
+    Router,
+};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    models::*,
+    database::ApiKeyDb,
+    error::{Result, validation_error, not_found},
+    auth::{extract_user_address, check_permission},
+    api_keys::{can_manage, validate_scopes, IssuedKey},
+};
+
+// API key routes
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(create_api_key).get(list_api_keys))
+        .route("/:id", get(get_api_key).delete(revoke_api_key))
+        .route("/:id/rotate", post(rotate_api_key))
+        .route("/:id/usage", get(get_api_key_usage))
+        .route("/:id/tier", put(set_api_key_tier))
+}
+
+// Issue a key; it is only shown in this response
+#[utoipa::path(
+    post, path = "/api/v1/api-keys", tag = "api-keys", request_body = CreateApiKeyRequest,
+    responses((status = 200, body = ApiKeyResponse), (status = 400, body = ErrorResponse)),
+    security(("bearer" = [])),
+)]
+async fn create_api_key(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> Result<Json<ApiKeyResponse>> {
+    let owner = extract_user_address(&claims)?;
+    let config = &state.config.api_keys;
+
+    if request.name.trim().is_empty() || request.name.len() > 100 {
+        return Err(validation_error("Name must be 1-100 characters"));
+    }
+    validate_scopes(&request.scopes)?;
+    // The admin scope only ever grants the owner's own role, so operators
+    // may ask for it too
+    if request.scopes.iter().any(|scope| scope == "admin") {
+        check_permission(&claims, "/api/v1/admin/status", "GET")?;
+    }
+
+    let tier = request.tier.clone().unwrap_or_else(|| config.default_tier.clone());
+    if config.requests_per_minute(&tier).is_none() {
+        return Err(validation_error(format!("Unknown tier: {}", tier)));
+    }
+    if tier != config.default_tier {
+        check_permission(&claims, "/api/v1/admin/api-keys", "POST")?;
+    }
+
+    if ApiKeyDb::count_live_keys(&state.db, owner).await? >= config.max_keys_per_user as i64 {
+        return Err(validation_error(format!(
+            "API key limit reached: max {} per user",
+            config.max_keys_per_user
+        )));
+    }
+
+    let issued = IssuedKey::generate();
+    let record = ApiKeyDb::create_key(&state.db, owner, &claims.role, &request, &tier, &issued).await?;
+
+    tracing::info!("API key {} ({}) issued to {:#x} with {:?}", record.id, record.prefix, owner, record.scopes);
+
+    let mut response = ApiKeyResponse::from(record);
+    response.key = Some(issued.key);
+    Ok(Json(response))
+}
+
+// List the caller's keys, including revoked and expired ones
+#[utoipa::path(
+    get, path = "/api/v1/api-keys", tag = "api-keys",
+    responses((status = 200, body = [ApiKeyResponse])),
+    security(("bearer" = [])),
+)]
+async fn list_api_keys(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<ApiKeyResponse>>> {
+    let owner = extract_user_address(&claims)?;
+
+    let records = ApiKeyDb::list_keys(&state.db, owner).await?;
+    Ok(Json(records.into_iter().map(ApiKeyResponse::from).collect()))
+}
+
+async fn get_api_key(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<ApiKeyResponse>> {
+    let record = managed_key(&state, id, &claims).await?;
+    Ok(Json(ApiKeyResponse::from(record)))
+}
+
+#[utoipa::path(
+    delete, path = "/api/v1/api-keys/{id}", tag = "api-keys",
+    params(("id" = Uuid, Path)),
+    responses((status = 204), (status = 404, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn revoke_api_key(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<StatusCode> {
+    managed_key(&state, id, &claims).await?;
+
+    ApiKeyDb::revoke_key(&state.db, id).await?;
+    tracing::info!("API key {} revoked", id);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Replace a key's secret, keeping the old one valid for a grace period
+#[utoipa::path(
+    post, path = "/api/v1/api-keys/{id}/rotate", tag = "api-keys", request_body = RotateApiKeyRequest,
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiKeyResponse), (status = 404, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn rotate_api_key(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(request): Json<RotateApiKeyRequest>,
+) -> Result<Json<ApiKeyResponse>> {
+    let record = managed_key(&state, id, &claims).await?;
+
+    if record.revoked_at.is_some() || record.expires_at.map_or(false, |expires_at| expires_at <= Utc::now()) {
+        return Err(validation_error("Only live keys can be rotated"));
+    }
+    if request.grace_period_secs > state.config.api_keys.max_grace_period_secs {
+        return Err(validation_error(format!(
+            "Grace period cannot exceed {} seconds",
+            state.config.api_keys.max_grace_period_secs
+        )));
+    }
+
+    let issued = IssuedKey::generate();
+    let grace_period = std::time::Duration::from_secs(request.grace_period_secs);
+    let rotated = ApiKeyDb::rotate_key(&state.db, id, &issued, grace_period).await?;
+
+    tracing::info!("API key {} rotated to {} ({}s grace)", id, rotated.id, request.grace_period_secs);
+
+    let mut response = ApiKeyResponse::from(rotated);
+    response.key = Some(issued.key);
+    Ok(Json(response))
+}
+
+// Hourly usage over the last `days` days
+#[utoipa::path(
+    get, path = "/api/v1/api-keys/{id}/usage", tag = "api-keys",
+    params(("id" = Uuid, Path), UsageQuery),
+    responses((status = 200, body = [ApiKeyUsageRecord]), (status = 404, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn get_api_key_usage(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<UsageQuery>,
+    claims: Claims,
+) -> Result<Json<Vec<ApiKeyUsageRecord>>> {
+    managed_key(&state, id, &claims).await?;
+
+    let days = params.days.unwrap_or(7).clamp(1, 90);
+    let usage = ApiKeyDb::usage_since(&state.db, id, Utc::now() - Duration::days(days as i64)).await?;
+    Ok(Json(usage))
+}
+
+// Move a key to another rate-limit tier (admin only)
+async fn set_api_key_tier(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(request): Json<SetTierRequest>,
+) -> Result<Json<ApiKeyResponse>> {
+    check_permission(&claims, "/api/v1/admin/api-keys/*", "PUT")?;
+
+    if state.config.api_keys.requests_per_minute(&request.tier).is_none() {
+        return Err(validation_error(format!("Unknown tier: {}", request.tier)));
+    }
+
+    let record = ApiKeyDb::set_tier(&state.db, id, &request.tier)
+        .await?
+        .ok_or_else(|| not_found("API key"))?;
+
+    tracing::info!("API key {} moved to tier {}", id, request.tier);
+    Ok(Json(ApiKeyResponse::from(record)))
+}
+
+// The key, if it belongs to the caller or the caller is an admin
+async fn managed_key(state: &AppState, id: Uuid, claims: &Claims) -> Result<ApiKeyRecord> {
+    let record = ApiKeyDb::get_key(&state.db, id)
+        .await?
+        .ok_or_else(|| not_found("API key"))?;
+
+    let caller = extract_user_address(claims)?;
+    if !can_manage(&record, caller, claims) {
+        // Don't reveal other users' keys exist
+        return Err(not_found("API key"));
+    }
+
+    Ok(record)
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct UsageQuery {
+    days: Option<u32>,
+}
+
+#[derive(serde::Deserialize)]
+struct SetTierRequest {
+    tier: String,
+}
@@ -0,0 +1,112 @@
+use axum::{
+    extract::{Query, State},
+    response::Response,
+    routing::get,
+    Router,
+};
+use ethers::types::Address;
+use futures_util::StreamExt;
+use intents_indexer::market::SwapRecord;
+
+use crate::{
+    models::*,
+    database::IntentDb,
+    error::Result,
+    auth::{extract_user_address, check_permission},
+    export::{self, ExportParams},
+};
+
+// Bulk export routes
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/intents", get(export_intents))
+        .route("/swaps", get(export_swaps))
+}
+
+// Whose intents to export
+#[derive(serde::Deserialize)]
+struct IntentExportScope {
+    user: Option<Address>,
+    // Every user's; needs the same permission as the admin intent routes
+    #[serde(default)]
+    all: bool,
+}
+
+// Intents created in the range, by default the authenticated user's own
+#[utoipa::path(
+    get, path = "/api/v1/export/intents", tag = "export",
+    params(
+        ("from" = String, Query, description = "Start of the range (RFC 3339, inclusive)"),
+        ("to" = String, Query, description = "End of the range (RFC 3339, exclusive)"),
+        ("format" = Option<String>, Query, description = "`csv` (default) or `parquet`"),
+        ("user" = Option<String>, Query, description = "Another user's intents; needs the wildcard read permission"),
+        ("all" = Option<bool>, Query, description = "Every user's intents; admins and operators only"),
+    ),
+    responses(
+        (status = 200, description = "CSV or Parquet file, streamed"),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    ),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn export_intents(
+    State(state): State<AppState>,
+    Query(params): Query<ExportParams>,
+    Query(scope): Query<IntentExportScope>,
+    claims: Claims,
+) -> Result<Response> {
+    params.validate()?;
+    let user_address = extract_user_address(&claims)?;
+
+    let user = if scope.all {
+        check_permission(&claims, "/api/v1/admin/intents/", "GET")?;
+        None
+    } else {
+        match scope.user {
+            Some(user) if user != user_address => check_permission(&claims, "/api/v1/intents/*", "GET")?,
+            _ => {}
+        }
+        Some(scope.user.unwrap_or(user_address))
+    };
+
+    let (sink, response) = export::start::<IntentRecord>(&params, "intents");
+    let pool = state.db.clone();
+    tokio::spawn(async move {
+        sink.run(IntentDb::stream_created_between(&pool, params.from, params.to, user)).await;
+    });
+
+    Ok(response)
+}
+
+// Pool swaps indexed in the range, across every chain
+#[utoipa::path(
+    get, path = "/api/v1/export/swaps", tag = "export",
+    params(
+        ("from" = String, Query, description = "Start of the range (RFC 3339, inclusive)"),
+        ("to" = String, Query, description = "End of the range (RFC 3339, exclusive)"),
+        ("format" = Option<String>, Query, description = "`csv` (default) or `parquet`"),
+    ),
+    responses(
+        (status = 200, description = "CSV or Parquet file, streamed"),
+        (status = 400, body = ErrorResponse),
+    ),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn export_swaps(
+    State(state): State<AppState>,
+    Query(params): Query<ExportParams>,
+    _claims: Claims,
+) -> Result<Response> {
+    params.validate()?;
+
+    let (sink, response) = export::start::<SwapRecord>(&params, "swaps");
+    let indexer = state.indexer.clone();
+    tokio::spawn(async move {
+        let swaps = indexer.swaps_between(params.from, params.to);
+        sink.run(swaps.map(|swap| {
+            swap.map_err(|e| crate::error::internal_error(format!("Failed to read swaps: {}", e)))
+        })).await;
+    });
+
+    Ok(response)
+}
@@ -0,0 +1,60 @@
+use async_graphql::{http::ALL_WEBSOCKET_PROTOCOLS, Data};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
+use axum::{
+    extract::{State, WebSocketUpgrade},
+    response::Response,
+    routing::get,
+    Router,
+};
+
+use crate::{
+    models::*,
+    auth::validate_jwt,
+};
+
+// GraphQL routes
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(execute).post(execute))
+        .route("/ws", get(subscribe))
+}
+
+// Queries over GET or POST; resolvers see the state and the caller
+async fn execute(
+    State(state): State<AppState>,
+    claims: Claims,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    let schema = state.graphql.clone();
+    let request = request.into_inner().data(state).data(claims);
+
+    schema.execute(request).await.into()
+}
+
+// Subscriptions over graphql-ws or graphql-transport-ws. Browsers can't set
+// headers on a WebSocket, so the JWT comes in the connection_init payload
+// as `token`; without one only public subscriptions work.
+async fn subscribe(
+    State(state): State<AppState>,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let schema = state.graphql.clone();
+
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            GraphQLWebSocket::new(stream, schema, protocol)
+                .on_connection_init(move |payload| async move {
+                    let mut data = Data::default();
+                    if let Some(token) = payload.get("token").and_then(|token| token.as_str()) {
+                        let claims = validate_jwt(token, &state.config.jwt_secret)
+                            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+                        data.insert(claims);
+                    }
+                    data.insert(state);
+                    Ok(data)
+                })
+                .serve()
+        })
+}
@@ -5,7 +5,12 @@ use axum::{
     routing::get,
     Router,
 };
-use std::time::{SystemTime, UNIX_EPOCH};
+use futures_util::future::join_all;
+use std::{
+    future::Future,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
 
 use crate::{
     models::{AppState, HealthResponse, HealthCheck, ChainHealth},
@@ -13,200 +18,227 @@ use crate::{
     error::Result,
 };
 
+const HEALTHY: &str = "healthy";
+const DEGRADED: &str = "degraded";
+const UNHEALTHY: &str = "unhealthy";
+
+// Longest a single dependency may take before it counts as down
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+static STARTED_AT: OnceLock<Instant> = OnceLock::new();
+
 // Health check routes
 pub fn routes() -> Router<AppState> {
+    STARTED_AT.get_or_init(Instant::now);
+
     Router::new()
         .route("/health", get(health_check))
+        .route("/healthz", get(liveness_check))
+        .route("/readyz", get(readiness_check))
         .route("/health/ready", get(readiness_check))
         .route("/health/live", get(liveness_check))
 }
 
-// Main health check endpoint
+// Every dependency with its latency; always 200 so dashboards can show why
+// the service is degraded
+#[utoipa::path(
+    get, path = "/health", tag = "health",
+    responses((status = 200, body = HealthResponse)),
+)]
 async fn health_check(
     State(state): State<AppState>,
 ) -> Result<Json<HealthResponse>> {
-    let start_time = std::time::Instant::now();
-    
-    // Check database
-    let db_health = check_database_health(&state).await;
-    
-    // Check Redis
-    let redis_health = check_redis_health(&state).await;
-    
-    // Check intent engine
-    let engine_health = check_intent_engine_health(&state).await;
-    
-    // Check chains
-    let chains_health = check_chains_health(&state).await;
-    
-    let uptime = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    
-    let overall_status = if db_health.status == "healthy" && 
-                           redis_health.status == "healthy" && 
-                           engine_health.status == "healthy" {
-        "healthy"
+    Ok(Json(probe_dependencies(&state).await))
+}
+
+// Readiness (for Kubernetes): 503 only when Postgres or Redis is down, as
+// the API can't serve anything without them. Unreachable chains or paused
+// intake leave it ready but degraded.
+#[utoipa::path(
+    get, path = "/readyz", tag = "health",
+    responses(
+        (status = 200, body = HealthResponse, description = "Ready, possibly degraded"),
+        (status = 503, body = HealthResponse, description = "A critical dependency is down"),
+    ),
+)]
+async fn readiness_check(
+    State(state): State<AppState>,
+) -> (StatusCode, Json<HealthResponse>) {
+    let response = probe_dependencies(&state).await;
+    let status = if response.status == UNHEALTHY {
+        StatusCode::SERVICE_UNAVAILABLE
     } else {
-        "unhealthy"
-    };
-    
-    let response = HealthResponse {
-        status: overall_status.to_string(),
-        timestamp: chrono::Utc::now(),
-        version: env!("CARGO_PKG_VERSION").to_string(),
-        uptime,
-        database: db_health,
-        redis: redis_health,
-        intent_engine: engine_health,
-        chains: chains_health,
+        StatusCode::OK
     };
-    
+    (status, Json(response))
+}
+
+// Liveness (for Kubernetes): the process is up and serving. Dependencies
+// are left to readiness so an outage elsewhere doesn't restart every pod.
+#[utoipa::path(
+    get, path = "/healthz", tag = "health",
+    responses((status = 200, description = "Process is serving requests")),
+)]
+async fn liveness_check() -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "status": HEALTHY,
+        "uptime": uptime().as_secs(),
+    }))
+}
+
+fn uptime() -> Duration {
+    STARTED_AT.get().map(Instant::elapsed).unwrap_or_default()
+}
+
+// Probe everything at once, so the slowest dependency bounds the check
+async fn probe_dependencies(state: &AppState) -> HealthResponse {
+    let start = Instant::now();
+
+    let (database, redis, intent_engine, chains) = tokio::join!(
+        check_database_health(state),
+        check_redis_health(state),
+        check_intent_engine_health(state),
+        check_chains_health(state),
+    );
+
+    let status = overall_status(&database, &redis, &intent_engine, &chains);
     tracing::debug!(
-        "Health check completed in {:?}ms, status: {}",
-        start_time.elapsed().as_millis(),
-        overall_status
+        "Health check completed in {}ms, status: {}",
+        start.elapsed().as_millis(),
+        status
     );
-    
-    Ok(Json(response))
+
+    HealthResponse {
+        status: status.to_string(),
+        timestamp: chrono::Utc::now(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        uptime: uptime().as_secs(),
+        database,
+        redis,
+        intent_engine,
+        chains,
+    }
 }
 
-// Readiness check (for Kubernetes)
-async fn readiness_check(
-    State(state): State<AppState>,
-) -> Result<StatusCode> {
-    // Check if all critical services are ready
-    let db_health = check_database_health(&state).await;
-    let redis_health = check_redis_health(&state).await;
-    
-    if db_health.status == "healthy" && redis_health.status == "healthy" {
-        Ok(StatusCode::OK)
+// Postgres and Redis are critical; everything else only degrades service
+fn overall_status(
+    database: &HealthCheck,
+    redis: &HealthCheck,
+    intent_engine: &HealthCheck,
+    chains: &[ChainHealth],
+) -> &'static str {
+    if database.status != HEALTHY || redis.status != HEALTHY {
+        UNHEALTHY
+    } else if intent_engine.status != HEALTHY || chains.iter().any(|chain| chain.status != HEALTHY) {
+        DEGRADED
     } else {
-        Ok(StatusCode::SERVICE_UNAVAILABLE)
+        HEALTHY
     }
 }
 
-// Liveness check (for Kubernetes)
-async fn liveness_check() -> Result<StatusCode> {
-    // Simple check that the service is running
-    Ok(StatusCode::OK)
+// Run a probe under `PROBE_TIMEOUT`, timing it
+async fn timed<T, F>(probe: F) -> (std::result::Result<T, String>, u64)
+where
+    F: Future<Output = std::result::Result<T, String>>,
+{
+    let start = Instant::now();
+    let result = match tokio::time::timeout(PROBE_TIMEOUT, probe).await {
+        Ok(result) => result,
+        Err(_) => Err(format!("Timed out after {}s", PROBE_TIMEOUT.as_secs())),
+    };
+    (result, start.elapsed().as_millis() as u64)
+}
+
+fn health_check_from(result: std::result::Result<(), String>, latency_ms: u64) -> HealthCheck {
+    HealthCheck {
+        status: if result.is_ok() { HEALTHY } else { UNHEALTHY }.to_string(),
+        latency_ms: Some(latency_ms),
+        error_message: result.err(),
+    }
 }
 
 // Database health check
 async fn check_database_health(state: &AppState) -> HealthCheck {
-    let start = std::time::Instant::now();
-    
-    match sqlx::query("SELECT 1").execute(&state.db).await {
-        Ok(_) => HealthCheck {
-            status: "healthy".to_string(),
-            latency_ms: Some(start.elapsed().as_millis() as u64),
-            error_message: None,
-        },
-        Err(e) => HealthCheck {
-            status: "unhealthy".to_string(),
-            latency_ms: Some(start.elapsed().as_millis() as u64),
-            error_message: Some(e.to_string()),
-        },
-    }
+    let (result, latency_ms) = timed(async {
+        sqlx::query("SELECT 1")
+            .execute(&state.db)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    })
+    .await;
+    health_check_from(result, latency_ms)
 }
 
 // Redis health check
 async fn check_redis_health(state: &AppState) -> HealthCheck {
-    let start = std::time::Instant::now();
-    let mut cache = CacheService::new(state.redis.clone());
-    
-    match cache.health_check().await {
-        Ok(true) => HealthCheck {
-            status: "healthy".to_string(),
-            latency_ms: Some(start.elapsed().as_millis() as u64),
-            error_message: None,
-        },
-        Ok(false) => HealthCheck {
-            status: "unhealthy".to_string(),
-            latency_ms: Some(start.elapsed().as_millis() as u64),
-            error_message: Some("Redis health check failed".to_string()),
-        },
-        Err(e) => HealthCheck {
-            status: "unhealthy".to_string(),
-            latency_ms: Some(start.elapsed().as_millis() as u64),
-            error_message: Some(e.to_string()),
-        },
-    }
+    let (result, latency_ms) = timed(async {
+        let mut cache = CacheService::new(state.redis.clone());
+        match cache.health_check().await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err("Redis health check failed".to_string()),
+            Err(e) => Err(e.to_string()),
+        }
+    })
+    .await;
+    health_check_from(result, latency_ms)
 }
 
-// Intent engine health check
+// Intent engine health check: the executor has to answer, and the engine
+// is degraded while intake is paused, as new intents are being turned away
 async fn check_intent_engine_health(state: &AppState) -> HealthCheck {
-    let start = std::time::Instant::now();
-    
-    // TODO: Implement actual health check in intent engine
-    // For now, just check if it's accessible
-    HealthCheck {
-        status: "healthy".to_string(),
-        latency_ms: Some(start.elapsed().as_millis() as u64),
-        error_message: None,
+    let engine = &state.intents_engine;
+    let (result, latency_ms) = timed(async {
+        engine.queue_metrics().await;
+        Ok(())
+    })
+    .await;
+
+    match result {
+        Ok(()) if engine.intake_paused() => HealthCheck {
+            status: DEGRADED.to_string(),
+            latency_ms: Some(latency_ms),
+            error_message: Some("Intent intake is paused".to_string()),
+        },
+        result => health_check_from(result, latency_ms),
     }
 }
 
-// Chain health checks
+// Chain health checks, one RPC call per configured chain
 async fn check_chains_health(state: &AppState) -> Vec<ChainHealth> {
-    let mut chain_healths = Vec::new();
-    
-    for chain_config in &state.config.chains {
-        let start = std::time::Instant::now();
-        
-        // Try to get latest block number
-        let (status, block_number, gas_price, error_message) = 
-            check_single_chain_health(chain_config).await;
-        
-        chain_healths.push(ChainHealth {
+    join_all(state.config.chains.iter().map(|chain_config| async move {
+        let (result, latency_ms) = timed(check_single_chain_health(chain_config)).await;
+        let (status, block_number, gas_price, error_message) = match result {
+            Ok((block_number, gas_price)) => (HEALTHY, Some(block_number), gas_price, None),
+            Err(e) => (UNHEALTHY, None, None, Some(e)),
+        };
+
+        ChainHealth {
             chain_id: chain_config.chain_id,
             chain_name: chain_config.name.clone(),
-            status,
+            status: status.to_string(),
             block_number,
-            latency_ms: Some(start.elapsed().as_millis() as u64),
+            latency_ms: Some(latency_ms),
             gas_price,
-        });
-    }
-    
-    chain_healths
+            error_message,
+        }
+    }))
+    .await
 }
 
 // Check individual chain health
 async fn check_single_chain_health(
     chain_config: &crate::config::ChainConfig,
-) -> (String, Option<u64>, Option<ethers::types::U256>, Option<String>) {
-    use ethers::providers::{Provider, Http};
-    use std::str::FromStr;
-    
-    match Provider::<Http>::try_from(&chain_config.rpc_url) {
-        Ok(provider) => {
-            // Check latest block
-            match provider.get_block_number().await {
-                Ok(block_number) => {
-                    // Try to get gas price
-                    let gas_price = provider.get_gas_price().await.ok();
-                    
-                    (
-                        "healthy".to_string(),
-                        Some(block_number.as_u64()),
-                        gas_price,
-                        None,
-                    )
-                }
-                Err(e) => (
-                    "unhealthy".to_string(),
-                    None,
-                    None,
-                    Some(format!("Failed to get block number: {}", e)),
-                ),
-            }
-        }
-        Err(e) => (
-            "unhealthy".to_string(),
-            None,
-            None,
-            Some(format!("Failed to create provider: {}", e)),
-        ),
-    }
-}
\ No newline at end of file
+) -> std::result::Result<(u64, Option<ethers::types::U256>), String> {
+    use ethers::providers::{Http, Middleware, Provider};
+
+    let provider = Provider::<Http>::try_from(chain_config.rpc_url.as_str())
+        .map_err(|e| format!("Failed to create provider: {}", e))?;
+    let block_number = provider
+        .get_block_number()
+        .await
+        .map_err(|e| format!("Failed to get block number: {}", e))?;
+    let gas_price = provider.get_gas_price().await.ok();
+
+    Ok((block_number.as_u64(), gas_price))
+}
@@ -6,6 +6,7 @@ use axum::{
     Router,
 };
 use ethers::types::{Address, H256};
+use intents_engine::EngineError;
 use std::str::FromStr;
 
 use crate::{
@@ -14,7 +15,10 @@ use crate::{
     cache::CacheService,
     error::{Result, validation_error, not_found},
     auth::{extract_user_address, check_permission},
-    websocket::broadcast_intent_update,
+    pagination::{CursorPage, CursorValue, IntentPage, PageParams},
+    websocket::{broadcast_intent_update, broadcast_new_intent},
+    intent_timeline,
+    webhooks,
 };
 
 // Intent routes
@@ -22,13 +26,23 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/", post(submit_intent))
         .route("/", get(get_user_intents))
+        .route("/quote", post(quote_intent))
+        .route("/simulate", post(simulate_intent))
         .route("/:intent_id", get(get_intent_by_id))
         .route("/:intent_id/status", get(get_intent_status))
+        .route("/:intent_id/history", get(get_intent_history))
+        .route("/:intent_id/timeline", get(get_intent_timeline))
         .route("/:intent_id/cancel", post(cancel_intent))
         .route("/pending", get(get_pending_intents))
 }
 
 // Submit a new intent
+#[utoipa::path(
+    post, path = "/api/v1/intents", tag = "intents", request_body = SubmitIntentRequest,
+    params(("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key return the first response")),
+    responses((status = 200, body = IntentResponse), (status = 400, body = ErrorResponse), (status = 409, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
 async fn submit_intent(
     State(state): State<AppState>,
     Json(request): Json<SubmitIntentRequest>,
@@ -37,10 +51,16 @@ async fn submit_intent(
     validate_submit_intent_request(&request)?;
     
     // Submit to intent engine
+    let engine_intent = convert_to_engine_intent(&request);
     let intent_id = state.intents_engine
-        .submit_intent(convert_to_engine_intent(&request))
+        .submit_intent(engine_intent.clone())
         .await
-        .map_err(|e| crate::error::ApiError::IntentEngine(e.to_string()))?;
+        .map_err(|e| match e {
+            EngineError::IntakePaused | EngineError::ShuttingDown => {
+                crate::error::ApiError::ServiceUnavailable(e.to_string())
+            }
+            e => crate::error::ApiError::IntentEngine(e.to_string()),
+        })?;
     
     // Store in database
     let record = IntentDb::insert_intent(&state.db, intent_id, &request).await?;
@@ -78,6 +98,8 @@ async fn submit_intent(
     };
     
     broadcast_intent_update(intent_id, update_msg).await;
+    broadcast_new_intent(intent_id, &engine_intent).await;
+    webhooks::publish(&state.db, "intent.created", request.user_address, serde_json::to_value(&response)?).await;
     
     tracing::info!(
         "Intent submitted: {:#x} from user {:#x}",
@@ -88,43 +110,97 @@ async fn submit_intent(
     Ok(Json(response))
 }
 
-// Get intents for authenticated user
+// Collect quotes for a draft intent from solvers and the orbital pools
+#[utoipa::path(
+    post, path = "/api/v1/intents/quote", tag = "intents", request_body = QuoteIntentRequest,
+    responses((status = 200, body = QuoteResponse), (status = 400, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn quote_intent(
+    State(state): State<AppState>,
+    Json(request): Json<QuoteIntentRequest>,
+) -> Result<Json<QuoteResponse>> {
+    let response = state.quotes.quote(&state.db, &request).await?;
+    
+    tracing::debug!(
+        "Quote {} for {} -> {}: {} of {} solvers answered",
+        response.quote_id,
+        request.source_chain_id,
+        request.dest_chain_id,
+        response.quotes.iter().filter(|quote| quote.source == "solver").count(),
+        response.solvers_asked
+    );
+    
+    Ok(Json(response))
+}
+
+// Dry-run a draft intent: expected route and output, nothing is stored
+#[utoipa::path(
+    post, path = "/api/v1/intents/simulate", tag = "intents", request_body = QuoteIntentRequest,
+    responses((status = 200, body = SimulationResponse), (status = 400, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn simulate_intent(
+    State(state): State<AppState>,
+    Json(request): Json<QuoteIntentRequest>,
+) -> Result<Json<SimulationResponse>> {
+    let response = state.simulator.simulate(&request).await?;
+    Ok(Json(response))
+}
+
+// List intents, by default the authenticated user's own
+#[utoipa::path(
+    get, path = "/api/v1/intents", tag = "intents", params(PageParams, IntentFilter),
+    responses((status = 200, body = IntentPage)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
 async fn get_user_intents(
     State(state): State<AppState>,
-    Query(pagination): Query<PaginationParams>,
+    Query(page): Query<PageParams>,
+    Query(mut filter): Query<IntentFilter>,
     claims: Claims,
-) -> Result<Json<PaginatedResponse<IntentResponse>>> {
+) -> Result<Json<CursorPage<IntentResponse>>> {
     let user_address = extract_user_address(&claims)?;
     
-    // Get intents from database
-    let (records, total_count) = IntentDb::get_intents_by_user(
+    // Other users' intents need the wildcard read permission
+    match filter.user {
+        Some(user) if user != user_address => check_permission(&claims, "/api/v1/intents/*", "GET")?,
+        Some(_) => {}
+        None => filter.user = Some(user_address),
+    }
+    
+    let sort_column = page.sort_column(INTENT_SORT_COLUMNS)?;
+    let limit = page.limit();
+    let records = IntentDb::list_intents(
         &state.db,
-        user_address,
-        &pagination,
+        &filter,
+        sort_column,
+        page.sort_order,
+        page.cursor(sort_column)?,
+        limit,
     ).await?;
     
-    // Convert to responses
-    let intents: Result<Vec<IntentResponse>> = records
-        .into_iter()
-        .map(intent_record_to_response)
-        .collect();
-    
-    let pagination_meta = PaginationMeta {
-        current_page: pagination.page.unwrap_or(1),
-        per_page: pagination.limit.unwrap_or(20),
-        total_items: total_count,
-        total_pages: (total_count + pagination.limit.unwrap_or(20) - 1) / pagination.limit.unwrap_or(20),
-        has_next: pagination.page.unwrap_or(1) * pagination.limit.unwrap_or(20) < total_count,
-        has_prev: pagination.page.unwrap_or(1) > 1,
-    };
+    let page = CursorPage::from_rows(
+        records,
+        limit,
+        sort_column,
+        |record| {
+            let value = if sort_column == "updated_at" { record.updated_at } else { record.created_at };
+            (CursorValue::Time(value), record.id)
+        },
+        intent_record_to_response,
+    )?;
     
-    Ok(Json(PaginatedResponse {
-        data: intents?,
-        pagination: pagination_meta,
-    }))
+    Ok(Json(page))
 }
 
 // Get specific intent by ID
+#[utoipa::path(
+    get, path = "/api/v1/intents/{intent_id}", tag = "intents",
+    params(("intent_id" = String, Path, description = "Intent hash")),
+    responses((status = 200, body = IntentResponse), (status = 404, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
 async fn get_intent_by_id(
     State(state): State<AppState>,
     Path(intent_id_str): Path<String>,
@@ -154,6 +230,11 @@ async fn get_intent_by_id(
 }
 
 // Get intent status (with caching)
+#[utoipa::path(
+    get, path = "/api/v1/intents/{intent_id}/status", tag = "intents",
+    params(("intent_id" = String, Path, description = "Intent hash")),
+    responses((status = 200, body = IntentStatusResponse), (status = 404, body = ErrorResponse)),
+)]
 async fn get_intent_status(
     State(state): State<AppState>,
     Path(intent_id_str): Path<String>,
@@ -187,7 +268,77 @@ async fn get_intent_status(
     Ok(Json(status_response))
 }
 
+// Replay an intent's audited engine decisions
+async fn get_intent_history(
+    State(state): State<AppState>,
+    Path(intent_id_str): Path<String>,
+) -> Result<Json<intents_engine::audit::IntentReplay>> {
+    let intent_id = H256::from_str(&intent_id_str)
+        .map_err(|_| validation_error("Invalid intent ID format"))?;
+    
+    let replay = state.intents_engine
+        .replay_intent(intent_id)
+        .await
+        .map_err(|e| crate::error::ApiError::IntentEngine(e.to_string()))?;
+    
+    if replay.events.is_empty() {
+        return Err(not_found("Intent"));
+    }
+    
+    Ok(Json(replay))
+}
+
+// Full lifecycle of an intent: submission, matching, each bridge leg and
+// delivery, merged from the engine's audit log and the indexer
+#[utoipa::path(
+    get, path = "/api/v1/intents/{intent_id}/timeline", tag = "intents",
+    params(("intent_id" = String, Path, description = "Intent hash")),
+    responses((status = 200, body = IntentTimeline), (status = 404, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn get_intent_timeline(
+    State(state): State<AppState>,
+    Path(intent_id_str): Path<String>,
+    claims: Option<Claims>,
+) -> Result<Json<IntentTimeline>> {
+    let intent_id = H256::from_str(&intent_id_str)
+        .map_err(|_| validation_error("Invalid intent ID format"))?;
+    
+    let record = IntentDb::get_intent_by_id(&state.db, intent_id)
+        .await?
+        .ok_or_else(|| not_found("Intent"))?;
+    
+    // Same visibility as the intent itself
+    if let Some(claims) = claims {
+        let user_address = extract_user_address(&claims)?;
+        let intent_user = Address::from_str(&record.user_address)
+            .map_err(|_| crate::error::internal_error("Invalid user address in database"))?;
+        
+        if user_address != intent_user {
+            check_permission(&claims, "/api/v1/intents/*", "GET")?;
+        }
+    }
+    
+    let (replay, (journey, transfers)) = tokio::join!(
+        state.intents_engine.replay_intent(intent_id),
+        intent_timeline::load_onchain(&state.indexer, intent_id),
+    );
+    let replay = replay.map_err(|e| crate::error::ApiError::IntentEngine(e.to_string()))?;
+    
+    let timeline = intent_timeline::build(&record, Some(&replay), journey.as_ref(), &transfers)?;
+    Ok(Json(timeline))
+}
+
 // Cancel an intent
+#[utoipa::path(
+    post, path = "/api/v1/intents/{intent_id}/cancel", tag = "intents",
+    params(
+        ("intent_id" = String, Path, description = "Intent hash"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key return the first response"),
+    ),
+    responses((status = 200), (status = 400, body = ErrorResponse), (status = 403, body = ErrorResponse), (status = 409, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
 async fn cancel_intent(
     State(state): State<AppState>,
     Path(intent_id_str): Path<String>,
@@ -250,6 +401,10 @@ async fn cancel_intent(
     };
     
     broadcast_intent_update(intent_id, update_msg).await;
+    webhooks::publish(&state.db, "intent.cancelled", user_address, serde_json::json!({
+        "intent_id": intent_id,
+        "reason": "Cancelled by user"
+    })).await;
     
     tracing::info!(
         "Intent cancelled: {:#x} by user {:#x}",
@@ -360,6 +515,9 @@ fn convert_engine_status_to_response(
     })
 }
 
+// Indexed columns intent lists can be ordered by; the first is the default
+const INTENT_SORT_COLUMNS: &[&str] = &["created_at", "updated_at"];
+
 // Query parameters for pending intents
 #[derive(serde::Deserialize)]
 struct PendingIntentsQuery {
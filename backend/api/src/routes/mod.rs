@@ -3,6 +3,13 @@ pub mod solver;
 pub mod analytics;
 pub mod health;
 pub mod auth;
+pub mod admin;
+pub mod webhooks;
+pub mod api_keys;
+pub mod notifications;
+pub mod stream;
+pub mod graphql;
+pub mod export;
 
 use axum::Router;
 use crate::models::AppState;
@@ -14,5 +21,12 @@ pub fn create_api_router() -> Router<AppState> {
         .nest("/api/v1/solver", solver::routes())
         .nest("/api/v1/analytics", analytics::routes())
         .nest("/api/v1/auth", auth::routes())
+        .nest("/api/v1/admin", admin::routes())
+        .nest("/api/v1/webhooks", webhooks::routes())
+        .nest("/api/v1/api-keys", api_keys::routes())
+        .nest("/api/v1/notifications", notifications::routes())
+        .nest("/api/v1/stream", stream::routes())
+        .nest("/api/v1/graphql", graphql::routes())
+        .nest("/api/v1/export", export::routes())
         .merge(health::routes())
 }
\ No newline at end of file
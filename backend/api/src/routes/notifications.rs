@@ -0,0 +1,115 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, put},
+    Router,
+};
+
+use crate::{
+    models::*,
+    database::NotificationDb,
+    error::{ApiError, Result, not_found},
+    auth::extract_user_address,
+    notifications::{channel_available, validate_events, Channel},
+};
+
+// Notification preference routes
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/channels", get(list_channels))
+        .route("/channels/:channel", put(update_channel).delete(delete_channel))
+        .route("/deliveries", get(list_deliveries))
+}
+
+// The caller's channels and the events each is subscribed to
+#[utoipa::path(
+    get, path = "/api/v1/notifications/channels", tag = "notifications",
+    responses((status = 200, body = [NotificationChannelRecord])),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn list_channels(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<NotificationChannelRecord>>> {
+    let owner = extract_user_address(&claims)?;
+
+    Ok(Json(NotificationDb::list_channels(&state.db, owner).await?))
+}
+
+// Set where a channel delivers to and which events it carries
+#[utoipa::path(
+    put, path = "/api/v1/notifications/channels/{channel}", tag = "notifications",
+    params(("channel" = String, Path, description = "\"email\" or \"telegram\"")),
+    request_body = UpdateNotificationChannelRequest,
+    responses((status = 200, body = NotificationChannelRecord), (status = 400, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn update_channel(
+    State(state): State<AppState>,
+    Path(channel): Path<String>,
+    claims: Claims,
+    Json(request): Json<UpdateNotificationChannelRequest>,
+) -> Result<Json<NotificationChannelRecord>> {
+    let owner = extract_user_address(&claims)?;
+    let channel = Channel::parse(&channel)?;
+
+    if !channel_available(&state.config.notifications, channel) {
+        return Err(ApiError::ServiceUnavailable(format!(
+            "{} notifications are not enabled on this server",
+            channel.as_str()
+        )));
+    }
+    channel.validate_target(&request.target)?;
+    validate_events(&request.events)?;
+
+    let record = NotificationDb::upsert_channel(&state.db, owner, channel.as_str(), &request).await?;
+    tracing::info!("{:#x} set {} notifications for {:?}", owner, channel.as_str(), record.events);
+
+    Ok(Json(record))
+}
+
+#[utoipa::path(
+    delete, path = "/api/v1/notifications/channels/{channel}", tag = "notifications",
+    params(("channel" = String, Path, description = "\"email\" or \"telegram\"")),
+    responses((status = 204), (status = 404, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn delete_channel(
+    State(state): State<AppState>,
+    Path(channel): Path<String>,
+    claims: Claims,
+) -> Result<StatusCode> {
+    let owner = extract_user_address(&claims)?;
+    let channel = Channel::parse(&channel)?;
+
+    if !NotificationDb::delete_channel(&state.db, owner, channel.as_str()).await? {
+        return Err(not_found("Notification channel"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Recent notifications across the caller's channels, newest first
+#[utoipa::path(
+    get, path = "/api/v1/notifications/deliveries", tag = "notifications",
+    params(NotificationDeliveryQuery),
+    responses((status = 200, body = [NotificationDeliveryRecord])),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn list_deliveries(
+    State(state): State<AppState>,
+    Query(params): Query<NotificationDeliveryQuery>,
+    claims: Claims,
+) -> Result<Json<Vec<NotificationDeliveryRecord>>> {
+    let owner = extract_user_address(&claims)?;
+
+    let limit = params.limit.unwrap_or(50).min(200);
+    Ok(Json(NotificationDb::list_deliveries(&state.db, owner, limit).await?))
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct NotificationDeliveryQuery {
+    limit: Option<u64>,
+}
@@ -14,14 +14,18 @@ use crate::{
     cache::CacheService,
     error::{Result, validation_error, not_found},
     auth::{extract_user_address, check_permission},
+    pagination::{CursorPage, CursorValue, PageParams, SolverPage},
     crypto::{
         verify_signature, 
         create_solver_registration_message,
+        create_solver_verification_message,
         create_secure_solver_registration_message,
         verify_message_freshness,
         SignatureRateLimiter,
         generate_secure_nonce
     },
+    notifications,
+    webhooks,
 };
 
 // Solver routes
@@ -33,11 +37,19 @@ pub fn routes() -> Router<AppState> {
         .route("/:address", get(get_solver_by_address))
         .route("/:address/performance", get(get_solver_performance))
         .route("/:address/update", put(update_solver))
+        .route("/:address/status", put(set_solver_status))
+        .route("/:address/verify", post(verify_solver))
+        .route("/:address/verify/message", post(get_verification_message))
         .route("/:address/deactivate", post(deactivate_solver))
         .route("/leaderboard", get(get_solver_leaderboard))
 }
 
 // Register a new solver with enhanced security
+#[utoipa::path(
+    post, path = "/api/v1/solver/register", tag = "solvers", request_body = SolverRegistrationRequest,
+    responses((status = 200, body = SolverResponse), (status = 400, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
 async fn register_solver(
     State(state): State<AppState>,
     Json(request): Json<SolverRegistrationRequest>,
@@ -106,6 +118,7 @@ async fn register_solver(
     cache.cache_solver_reputation(request.solver_address, record.reputation_score).await.ok();
     
     let response = solver_record_to_response(record)?;
+    webhooks::publish(&state.db, "solver.registered", request.solver_address, serde_json::to_value(&response)?).await;
     
     Ok(Json(response))
 }
@@ -148,60 +161,52 @@ async fn get_registration_message(
 }
 
 // Get list of solvers with filtering
+#[utoipa::path(
+    get, path = "/api/v1/solver", tag = "solvers", params(PageParams, SolverFilter),
+    responses((status = 200, body = SolverPage)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
 async fn get_solvers(
     State(state): State<AppState>,
-    Query(params): Query<SolverQuery>,
-) -> Result<Json<PaginatedResponse<SolverResponse>>> {
-    let solvers = SolverDb::get_active_solvers(&state.db, params.chain_id).await?;
-    
-    // Apply filters
-    let mut filtered_solvers = solvers;
-    
-    if let Some(min_reputation) = params.min_reputation {
-        filtered_solvers.retain(|s| s.reputation_score >= min_reputation);
-    }
-    
-    if let Some(max_fee_rate) = params.max_fee_rate {
-        filtered_solvers.retain(|s| s.fee_rate <= max_fee_rate);
-    }
-    
-    // Sort by reputation score
-    filtered_solvers.sort_by(|a, b| b.reputation_score.partial_cmp(&a.reputation_score).unwrap());
-    
-    // Apply pagination
-    let page = params.page.unwrap_or(1);
-    let limit = params.limit.unwrap_or(20).min(100);
-    let offset = (page - 1) * limit;
-    
-    let total_count = filtered_solvers.len() as u64;
-    let paginated_solvers = filtered_solvers
-        .into_iter()
-        .skip(offset as usize)
-        .take(limit as usize)
-        .collect::<Vec<_>>();
-    
-    // Convert to responses
-    let solver_responses: Result<Vec<SolverResponse>> = paginated_solvers
-        .into_iter()
-        .map(solver_record_to_response)
-        .collect();
-    
-    let pagination_meta = PaginationMeta {
-        current_page: page,
-        per_page: limit,
-        total_items: total_count,
-        total_pages: (total_count + limit - 1) / limit,
-        has_next: page * limit < total_count,
-        has_prev: page > 1,
-    };
+    Query(page): Query<PageParams>,
+    Query(filter): Query<SolverFilter>,
+) -> Result<Json<CursorPage<SolverResponse>>> {
+    let sort_column = page.sort_column(SOLVER_SORT_COLUMNS)?;
+    let limit = page.limit();
+    let records = SolverDb::list_solvers(
+        &state.db,
+        &filter,
+        sort_column,
+        page.sort_order,
+        page.cursor(sort_column)?,
+        limit,
+    ).await?;
+    
+    let page = CursorPage::from_rows(
+        records,
+        limit,
+        sort_column,
+        |record| {
+            let value = if sort_column == "registered_at" {
+                CursorValue::Time(record.registered_at)
+            } else {
+                CursorValue::Number(record.reputation_score)
+            };
+            (value, record.id)
+        },
+        solver_record_to_response,
+    )?;
     
-    Ok(Json(PaginatedResponse {
-        data: solver_responses?,
-        pagination: pagination_meta,
-    }))
+    Ok(Json(page))
 }
 
 // Get specific solver by address
+#[utoipa::path(
+    get, path = "/api/v1/solver/{address}", tag = "solvers",
+    params(("address" = String, Path, description = "Solver address")),
+    responses((status = 200, body = SolverResponse), (status = 404, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
 async fn get_solver_by_address(
     State(state): State<AppState>,
     Path(address_str): Path<String>,
@@ -274,48 +279,121 @@ async fn update_solver(
     Path(address_str): Path<String>,
     claims: Claims,
     Json(update_request): Json<SolverUpdateRequest>,
-) -> Result<StatusCode> {
-    let address = Address::from_str(&address_str)
-        .map_err(|_| validation_error("Invalid solver address format"))?;
-    
-    let user_address = extract_user_address(&claims)?;
+) -> Result<Json<SolverResponse>> {
+    let address = own_solver_address(&address_str, &claims)?;
     
-    // Verify that the user is updating their own solver
-    if user_address != address {
-        return Err(crate::error::ApiError::Authorization(
-            "Can only update your own solver settings".to_string()
-        ));
-    }
+    validate_solver_update(&update_request)?;
     
-    // Verify solver exists
-    let _solver = SolverDb::get_solver_by_address(&state.db, address)
+    let record = SolverDb::update_profile(&state.db, address, &update_request)
         .await?
         .ok_or_else(|| not_found("Solver"))?;
     
-    // Update solver settings
-    sqlx::query!(r#"
-        UPDATE solvers SET 
-            fee_rate = COALESCE($2, fee_rate),
-            contact_info = COALESCE($3, contact_info),
-            is_active = COALESCE($4, is_active)
-        WHERE address = $1
-    "#,
-        format!("{:#x}", address),
-        update_request.fee_rate,
-        update_request.contact_info,
-        update_request.is_active
-    )
-    .execute(&state.db)
-    .await?;
-    
     // Invalidate cache
     let mut cache = CacheService::new(state.redis.clone());
     cache.delete(&crate::cache::CacheKeys::solver_reputation(address)).await.ok();
     cache.delete(&crate::cache::CacheKeys::solver_performance(address)).await.ok();
     
-    tracing::info!("Solver updated: {:#x}", address);
+    if record.verified_at.is_none() {
+        tracing::info!("Solver updated: {:#x}, quote URL changed so verification is required", address);
+    } else {
+        tracing::info!("Solver updated: {:#x}", address);
+    }
     
-    Ok(StatusCode::OK)
+    Ok(Json(solver_record_to_response(record)?))
+}
+
+// Pause or resume quote requests and matching (only by the solver themselves)
+async fn set_solver_status(
+    State(state): State<AppState>,
+    Path(address_str): Path<String>,
+    claims: Claims,
+    Json(request): Json<SolverStatusRequest>,
+) -> Result<Json<SolverResponse>> {
+    let address = own_solver_address(&address_str, &claims)?;
+    
+    let paused = match request.status {
+        SolverStatus::Active => false,
+        SolverStatus::Paused => true,
+        _ => return Err(validation_error("Status must be active or paused")),
+    };
+    
+    let record = SolverDb::set_paused(&state.db, address, paused)
+        .await?
+        .ok_or_else(|| not_found("Solver"))?;
+    
+    // Deactivated and slashed solvers stay that way whatever they set
+    tracing::info!("Solver {:#x} set itself {:?}, now {:?}", address, request.status, record.status());
+    
+    Ok(Json(solver_record_to_response(record)?))
+}
+
+// Issue a challenge proving control of the solver address
+async fn get_verification_message(
+    State(state): State<AppState>,
+    Path(address_str): Path<String>,
+    claims: Claims,
+) -> Result<Json<SolverVerificationChallenge>> {
+    let address = own_solver_address(&address_str, &claims)?;
+    
+    SolverDb::get_solver_by_address(&state.db, address)
+        .await?
+        .ok_or_else(|| not_found("Solver"))?;
+    
+    let issued_at = chrono::Utc::now();
+    let message = create_solver_verification_message(
+        address,
+        generate_secure_nonce(),
+        issued_at.timestamp() as u64,
+    );
+    let message = String::from_utf8(message)
+        .map_err(|_| crate::error::ApiError::Internal("Failed to convert message to string".to_string()))?;
+    
+    // One outstanding challenge per solver; asking again replaces it
+    let mut cache = CacheService::new(state.redis.clone());
+    cache.set(
+        &crate::cache::CacheKeys::solver_verification(address),
+        &message,
+        Some(std::time::Duration::from_secs(VERIFICATION_TTL_SECS)),
+    ).await?;
+    
+    Ok(Json(SolverVerificationChallenge {
+        message,
+        expires_at: issued_at + chrono::Duration::seconds(VERIFICATION_TTL_SECS as i64),
+    }))
+}
+
+// Check the signed challenge and mark the solver verified
+async fn verify_solver(
+    State(state): State<AppState>,
+    Path(address_str): Path<String>,
+    claims: Claims,
+    Json(request): Json<SolverVerificationRequest>,
+) -> Result<Json<SolverResponse>> {
+    let address = own_solver_address(&address_str, &claims)?;
+    
+    let key = crate::cache::CacheKeys::solver_verification(address);
+    let mut cache = CacheService::new(state.redis.clone());
+    let message = cache.get::<String>(&key)
+        .await?
+        .ok_or_else(|| validation_error("No pending verification challenge; request a new one"))?;
+    
+    if !verify_signature(message.as_bytes(), &request.signature, address)? {
+        tracing::warn!("Invalid verification signature for solver {:#x}", address);
+        return Err(crate::error::ApiError::Authorization(
+            "Invalid signature for solver verification".to_string()
+        ));
+    }
+    
+    // Challenges are single use
+    cache.delete(&key).await.ok();
+    
+    let record = SolverDb::mark_verified(&state.db, address)
+        .await?
+        .ok_or_else(|| not_found("Solver"))?;
+    
+    tracing::info!("Solver verified: {:#x}", address);
+    
+    Ok(Json(solver_record_to_response(record)?))
 }
 
 // Deactivate solver (admin only)
@@ -332,16 +410,24 @@ async fn deactivate_solver(
     check_permission(&claims, "/api/v1/solver/*/deactivate", "POST")?;
     
     // Deactivate or slash solver
-    if deactivate_request.slash {
+    let event = if deactivate_request.slash {
         SolverDb::slash_solver(&state.db, address, &deactivate_request.reason).await?;
         tracing::warn!("Solver slashed: {:#x} - {}", address, deactivate_request.reason);
+        "solver.slashed"
     } else {
         sqlx::query!("UPDATE solvers SET is_active = false WHERE address = $1", 
                     format!("{:#x}", address))
             .execute(&state.db)
             .await?;
         tracing::info!("Solver deactivated: {:#x}", address);
-    }
+        "solver.deactivated"
+    };
+    let data = serde_json::json!({
+        "solver_address": address,
+        "reason": deactivate_request.reason
+    });
+    notifications::publish(&state.db, event, address, &data).await;
+    webhooks::publish(&state.db, event, address, data).await;
     
     // Invalidate cache
     let mut cache = CacheService::new(state.redis.clone());
@@ -406,6 +492,75 @@ async fn get_solver_leaderboard(
 }
 
 // Helper functions and validation
+
+/// The solver address in the path, if it is the caller's own
+fn own_solver_address(address_str: &str, claims: &Claims) -> Result<Address> {
+    let address = Address::from_str(address_str)
+        .map_err(|_| validation_error("Invalid solver address format"))?;
+    
+    if extract_user_address(claims)? != address {
+        return Err(crate::error::ApiError::Authorization(
+            "Can only manage your own solver".to_string()
+        ));
+    }
+    
+    Ok(address)
+}
+
+fn validate_solver_profile(
+    supported_tokens: Option<&[Address]>,
+    name: Option<&str>,
+    website: Option<&str>,
+) -> Result<()> {
+    if let Some(tokens) = supported_tokens {
+        if tokens.len() > MAX_SUPPORTED_TOKENS {
+            return Err(validation_error(format!("Too many supported tokens (maximum {})", MAX_SUPPORTED_TOKENS)));
+        }
+        if tokens.iter().any(|token| token.is_zero()) {
+            return Err(validation_error("Supported tokens cannot include the zero address"));
+        }
+    }
+    
+    if let Some(name) = name {
+        if name.trim().is_empty() || name.len() > 100 {
+            return Err(validation_error("Name must be 1-100 characters"));
+        }
+    }
+    
+    if let Some(website) = website {
+        match reqwest::Url::parse(website) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+            _ => return Err(validation_error("Website must be an http(s) URL")),
+        }
+    }
+    
+    Ok(())
+}
+
+fn validate_solver_update(update: &SolverUpdateRequest) -> Result<()> {
+    if let Some(fee_rate) = update.fee_rate {
+        if !(0.0..=1000.0).contains(&fee_rate) {
+            return Err(validation_error("Fee rate must be between 0 and 1000 basis points"));
+        }
+    }
+    
+    if let Some(chains) = &update.supported_chains {
+        if chains.is_empty() || chains.len() > 50 || chains.contains(&0) {
+            return Err(validation_error("Supported chains must be 1-50 non-zero chain IDs"));
+        }
+    }
+    
+    if let Some(quote_url) = &update.quote_url {
+        crate::quotes::validate_quote_url(quote_url)?;
+    }
+    
+    validate_solver_profile(
+        update.supported_tokens.as_deref(),
+        update.name.as_deref(),
+        update.website.as_deref(),
+    )
+}
+
 fn validate_solver_registration(request: &SolverRegistrationRequest) -> Result<()> {
     if request.bond_amount.is_zero() {
         return Err(validation_error("Bond amount must be greater than zero"));
@@ -479,7 +634,15 @@ fn validate_solver_registration_enhanced(request: &SolverRegistrationRequest) ->
         return Err(validation_error("Fee rate precision too high (max 2 decimal places)"));
     }
     
-    Ok(())
+    if let Some(quote_url) = &request.quote_url {
+        crate::quotes::validate_quote_url(quote_url)?;
+    }
+    
+    validate_solver_profile(
+        Some(&request.supported_tokens),
+        request.name.as_deref(),
+        request.website.as_deref(),
+    )
 }
 
 /// Extract timestamp from request if embedded in signature or metadata
@@ -566,28 +729,21 @@ async fn is_address_compromised(_address: Address) -> Result<bool> {
     Ok(false)
 }
 
+// Indexed columns solver lists can be ordered by; the first is the default
+const SOLVER_SORT_COLUMNS: &[&str] = &["reputation_score", "registered_at"];
+
+const MAX_SUPPORTED_TOKENS: usize = 200;
+
+// How long a verification challenge can be signed for
+const VERIFICATION_TTL_SECS: u64 = 300;
+
 // Request/Query structs
-#[derive(serde::Deserialize)]
-struct SolverQuery {
-    chain_id: Option<u64>,
-    min_reputation: Option<f64>,
-    max_fee_rate: Option<f64>,
-    page: Option<u64>,
-    limit: Option<u64>,
-}
 
 #[derive(serde::Deserialize)]
 struct PerformanceQuery {
     timeframe: Option<String>,
 }
 
-#[derive(serde::Deserialize)]
-struct SolverUpdateRequest {
-    fee_rate: Option<f64>,
-    contact_info: Option<String>,
-    is_active: Option<bool>,
-}
-
 #[derive(serde::Deserialize)]
 struct DeactivateSolverRequest {
     reason: String,
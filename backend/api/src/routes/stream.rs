@@ -0,0 +1,109 @@
+use axum::{
+    extract::{Path, Query},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::get,
+    Router,
+};
+use futures_util::stream::{self, Stream, StreamExt};
+use std::{convert::Infallible, str::FromStr, time::Duration};
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+use ethers::types::H256;
+
+use crate::{
+    models::*,
+    error::{Result, validation_error},
+    websocket::{Replay, SubscriptionChannel, WS_MANAGER},
+};
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+// Server-Sent Events routes, for clients that can't hold a WebSocket
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/market", get(stream_market))
+        .route("/intents/:intent_id", get(stream_intent))
+}
+
+#[derive(serde::Deserialize)]
+struct StreamParams {
+    /// Same as the Last-Event-ID header, for clients that can't set headers
+    last_event_id: Option<u64>,
+}
+
+// Market data as it is broadcast
+async fn stream_market(
+    headers: HeaderMap,
+    Query(params): Query<StreamParams>,
+) -> Sse<impl Stream<Item = std::result::Result<Event, Infallible>>> {
+    channel_stream(SubscriptionChannel::MarketData, last_event_id(&headers, &params)).await
+}
+
+// Status updates for one intent
+async fn stream_intent(
+    Path(intent_id_str): Path<String>,
+    headers: HeaderMap,
+    Query(params): Query<StreamParams>,
+    _claims: Claims,
+) -> Result<Sse<impl Stream<Item = std::result::Result<Event, Infallible>>>> {
+    let intent_id = H256::from_str(&intent_id_str)
+        .map_err(|_| validation_error("Invalid intent ID format"))?;
+
+    Ok(channel_stream(SubscriptionChannel::IntentUpdates(intent_id), last_event_id(&headers, &params)).await)
+}
+
+// Browsers send Last-Event-ID on reconnect; it is the seq of the last
+// message received
+fn last_event_id(headers: &HeaderMap, params: &StreamParams) -> Option<u64> {
+    headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .or(params.last_event_id)
+}
+
+// Replay what a resuming client missed, then stream live messages. Event
+// ids are channel seqs, so a reconnect picks up where it left off.
+async fn channel_stream(
+    channel: SubscriptionChannel,
+    last_seq: Option<u64>,
+) -> Sse<impl Stream<Item = std::result::Result<Event, Infallible>>> {
+    let (receiver, replay) = WS_MANAGER.subscribe_from(&channel, last_seq).await;
+
+    let replayed = replay.map(replay_events).unwrap_or_default();
+    let live = stream::unfold(receiver, |mut receiver: Receiver<WebSocketMessage>| async move {
+        match receiver.recv().await {
+            Ok(message) => Some((message_event(&message), receiver)),
+            Err(RecvError::Lagged(missed)) => {
+                tracing::debug!("SSE client lagged, {} messages dropped", missed);
+                Some((reset_event("lagged"), receiver))
+            }
+            Err(RecvError::Closed) => None,
+        }
+    });
+
+    let events = stream::iter(replayed).chain(live).map(Ok);
+    Sse::new(events).keep_alive(KeepAlive::new().interval(HEARTBEAT_INTERVAL).text("heartbeat"))
+}
+
+fn replay_events(replay: Replay) -> Vec<Event> {
+    // Messages were dropped from the buffer; the client should refetch state
+    let reset = replay.gap.then(|| reset_event("gap"));
+    reset.into_iter().chain(replay.messages.iter().map(message_event)).collect()
+}
+
+fn message_event(message: &WebSocketMessage) -> Event {
+    let event = Event::default()
+        .event(&message.message_type)
+        .data(serde_json::to_string(message).unwrap_or_default());
+    match message.seq {
+        Some(seq) => event.id(seq.to_string()),
+        None => event,
+    }
+}
+
+fn reset_event(reason: &str) -> Event {
+    Event::default()
+        .event("reset")
+        .data(serde_json::json!({ "reason": reason }).to_string())
+}
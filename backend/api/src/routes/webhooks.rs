@@ -0,0 +1,169 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use uuid::Uuid;
+
+use crate::{
+    models::*,
+    database::WebhookDb,
+    error::{Result, validation_error, not_found},
+    auth::{extract_user_address, check_permission},
+    webhooks::{generate_secret, validate_endpoint, validate_events},
+};
+
+// Webhook routes
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(create_webhook).get(list_webhooks))
+        .route("/:id", get(get_webhook).delete(delete_webhook))
+        .route("/:id/deliveries", get(list_deliveries))
+        .route("/:id/deliveries/:delivery_id/redeliver", post(redeliver))
+}
+
+// Register an endpoint; the signing secret is only shown in this response
+#[utoipa::path(
+    post, path = "/api/v1/webhooks", tag = "webhooks", request_body = CreateWebhookRequest,
+    responses((status = 200, body = WebhookResponse), (status = 400, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn create_webhook(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<Json<WebhookResponse>> {
+    let owner = extract_user_address(&claims)?;
+
+    validate_endpoint(&request.url, state.config.webhooks.allow_http)?;
+    validate_events(&request.events)?;
+
+    if request.all_subjects {
+        check_permission(&claims, "/api/v1/admin/webhooks", "POST")?;
+    }
+
+    let existing = WebhookDb::list_subscriptions(&state.db, owner).await?;
+    if existing.len() >= state.config.webhooks.max_subscriptions as usize {
+        return Err(validation_error(format!(
+            "Webhook limit reached: max {} per user",
+            state.config.webhooks.max_subscriptions
+        )));
+    }
+
+    let secret = generate_secret();
+    let record = WebhookDb::create_subscription(&state.db, owner, &request, &secret).await?;
+
+    tracing::info!("Webhook {} registered by {:#x} for {:?}", record.id, owner, record.events);
+
+    let mut response = WebhookResponse::from(record);
+    response.secret = Some(secret);
+    Ok(Json(response))
+}
+
+// List the caller's webhooks
+#[utoipa::path(
+    get, path = "/api/v1/webhooks", tag = "webhooks",
+    responses((status = 200, body = [WebhookResponse])),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn list_webhooks(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<WebhookResponse>>> {
+    let owner = extract_user_address(&claims)?;
+
+    let records = WebhookDb::list_subscriptions(&state.db, owner).await?;
+    Ok(Json(records.into_iter().map(WebhookResponse::from).collect()))
+}
+
+async fn get_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<WebhookResponse>> {
+    let record = owned_subscription(&state, id, &claims).await?;
+    Ok(Json(WebhookResponse::from(record)))
+}
+
+#[utoipa::path(
+    delete, path = "/api/v1/webhooks/{id}", tag = "webhooks",
+    params(("id" = Uuid, Path)),
+    responses((status = 204), (status = 404, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<StatusCode> {
+    owned_subscription(&state, id, &claims).await?;
+
+    WebhookDb::delete_subscription(&state.db, id).await?;
+    tracing::info!("Webhook {} deleted", id);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Delivery log, newest first
+#[utoipa::path(
+    get, path = "/api/v1/webhooks/{id}/deliveries", tag = "webhooks",
+    params(("id" = Uuid, Path), DeliveryQuery),
+    responses((status = 200, body = [WebhookDeliveryRecord]), (status = 404, body = ErrorResponse)),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+async fn list_deliveries(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<DeliveryQuery>,
+    claims: Claims,
+) -> Result<Json<Vec<WebhookDeliveryRecord>>> {
+    owned_subscription(&state, id, &claims).await?;
+
+    if let Some(status) = params.status.as_deref() {
+        if !matches!(status, "pending" | "delivered" | "failed") {
+            return Err(validation_error("Status must be pending, delivered or failed"));
+        }
+    }
+
+    let limit = params.limit.unwrap_or(50).min(200);
+    let deliveries = WebhookDb::list_deliveries(&state.db, id, params.status.as_deref(), limit).await?;
+    Ok(Json(deliveries))
+}
+
+// Retry a delivery that ran out of attempts
+async fn redeliver(
+    State(state): State<AppState>,
+    Path((id, delivery_id)): Path<(Uuid, Uuid)>,
+    claims: Claims,
+) -> Result<StatusCode> {
+    owned_subscription(&state, id, &claims).await?;
+
+    if !WebhookDb::redeliver(&state.db, id, delivery_id).await? {
+        return Err(not_found("Failed delivery"));
+    }
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+// The subscription, if it belongs to the caller or the caller is an admin
+async fn owned_subscription(state: &AppState, id: Uuid, claims: &Claims) -> Result<WebhookRecord> {
+    let record = WebhookDb::get_subscription(&state.db, id)
+        .await?
+        .ok_or_else(|| not_found("Webhook"))?;
+
+    let caller = extract_user_address(claims)?;
+    if record.owner_address != format!("{:#x}", caller) {
+        check_permission(claims, "/api/v1/admin/webhooks/*", "GET")?;
+    }
+
+    Ok(record)
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct DeliveryQuery {
+    status: Option<String>,
+    limit: Option<u64>,
+}
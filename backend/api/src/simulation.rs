@@ -0,0 +1,281 @@
+//! Dry runs of draft intents
+//!
+//! The solver route optimizer picks the path; every orbital hop on a chain
+//! with a configured pool is then repriced with orbital-math against live
+//! pool state, and later hops are scaled to the repriced amounts. Same-chain
+//! swaps are also priced directly against the orbital pool and the better
+//! of the two routes is returned. Nothing is persisted.
+
+use chrono::Utc;
+use ethers::types::{Address, U256};
+use std::collections::HashMap;
+
+use intents_engine::intent::Intent;
+use intents_solver::{
+    optimizer::{Protocol, Route, RouteOptimizer},
+    pool_state::PoolStateProvider,
+};
+
+use crate::{
+    config::Config,
+    error::{validation_error, ApiError, Result},
+    models::{QuoteIntentRequest, SimulatedHop, SimulationResponse},
+    quotes::{orbital_amount_out, pool_state_provider, validate_quote_request},
+};
+
+/// Gas for a single orbital swap, as the optimizer estimates direct swaps
+const ORBITAL_SWAP_GAS: u64 = 150_000;
+/// Rough block time used to turn confirmations into seconds
+const BLOCK_TIME_SECS: u64 = 12;
+/// Spot price is read from a swap this fraction of the input
+const SPOT_PROBE_DIVISOR: u64 = 10_000;
+
+pub struct IntentSimulator {
+    optimizer: RouteOptimizer,
+    pools: PoolStateProvider,
+    /// Confirmations waited for on each chain
+    confirmations: HashMap<u64, u64>,
+    /// Orbital pool id on each chain that has one
+    orbital_pool_ids: HashMap<u64, u64>,
+}
+
+struct CandidateRoute {
+    hops: Vec<SimulatedHop>,
+    estimated_gas: U256,
+}
+
+impl CandidateRoute {
+    fn output(&self) -> U256 {
+        self.hops.last().map(|hop| hop.amount_out).unwrap_or_default()
+    }
+}
+
+impl IntentSimulator {
+    pub async fn new(config: &Config) -> Result<Self> {
+        let chains: Vec<u64> = config.chains.iter().map(|chain| chain.chain_id).collect();
+        let optimizer = RouteOptimizer::for_chains(&chains)
+            .await
+            .map_err(|e| ApiError::Internal(format!("Failed to initialize route optimizer: {}", e)))?;
+
+        Ok(Self {
+            optimizer,
+            pools: pool_state_provider(config)?,
+            confirmations: config
+                .chains
+                .iter()
+                .map(|chain| (chain.chain_id, chain.confirmation_blocks))
+                .collect(),
+            orbital_pool_ids: config
+                .chains
+                .iter()
+                .filter_map(|chain| chain.orbital_pool_id.map(|pool_id| (chain.chain_id, pool_id)))
+                .collect(),
+        })
+    }
+
+    pub async fn simulate(&self, request: &QuoteIntentRequest) -> Result<SimulationResponse> {
+        validate_quote_request(request)?;
+
+        let mut candidates = Vec::new();
+        if request.source_chain_id == request.dest_chain_id {
+            if let Some(hop) = self.orbital_hop(
+                request.source_chain_id,
+                request.source_token,
+                request.dest_token,
+                request.source_amount,
+            ).await {
+                candidates.push(CandidateRoute {
+                    hops: vec![hop],
+                    estimated_gas: U256::from(ORBITAL_SWAP_GAS),
+                });
+            }
+        }
+
+        // Output is what's being asked for, so don't let the optimizer
+        // reject routes below the caller's minimum
+        let intent = Intent {
+            source_chain_id: request.source_chain_id,
+            dest_chain_id: request.dest_chain_id,
+            source_token: request.source_token,
+            dest_token: request.dest_token,
+            source_amount: request.source_amount,
+            min_dest_amount: U256::zero(),
+            user: request.user_address.unwrap_or_default(),
+            ..Default::default()
+        };
+        match self.optimizer.find_best_route(&intent).await {
+            Ok(route) => candidates.push(self.reprice(route, request.source_amount).await),
+            Err(e) => tracing::debug!("Optimizer found no route for simulation: {}", e),
+        }
+
+        let best = candidates
+            .into_iter()
+            .max_by_key(CandidateRoute::output)
+            .ok_or_else(|| validation_error("No route found between these tokens"))?;
+
+        let expected_output = best.output();
+        Ok(SimulationResponse {
+            expected_output,
+            meets_min_dest_amount: request.min_dest_amount.map_or(true, |min| expected_output >= min),
+            price_impact_bps: best.hops.iter().filter_map(|hop| hop.price_impact_bps).sum(),
+            bridge_fee: bridge_fee(&best.hops),
+            estimated_gas: best.estimated_gas,
+            estimated_completion_secs: self.completion_secs(&best.hops, request),
+            route: best.hops,
+            simulated_at: Utc::now(),
+        })
+    }
+
+    /// Walk the optimizer's hops, pricing orbital hops against live state
+    /// and scaling the rest to the amount actually arriving
+    async fn reprice(&self, route: Route, source_amount: U256) -> CandidateRoute {
+        let mut amount = source_amount;
+        let mut hops = Vec::with_capacity(route.hops.len());
+
+        for hop in route.hops {
+            let live = if hop.protocol == Protocol::OrbitalAMM {
+                self.orbital_hop(hop.chain_id, hop.token_in, hop.token_out, amount).await
+            } else {
+                None
+            };
+            let simulated = live.unwrap_or_else(|| SimulatedHop {
+                protocol: format!("{:?}", hop.protocol),
+                chain_id: hop.chain_id,
+                pool: format!("{:#x}", hop.pool_address),
+                token_in: hop.token_in,
+                token_out: hop.token_out,
+                amount_in: amount,
+                amount_out: scale(hop.amount_out, amount, hop.amount_in),
+                price_impact_bps: None,
+            });
+            amount = simulated.amount_out;
+            hops.push(simulated);
+        }
+
+        CandidateRoute {
+            hops,
+            estimated_gas: route.estimated_gas,
+        }
+    }
+
+    /// Swap through the configured orbital pool on `chain_id`, if it holds
+    /// both tokens and is active
+    async fn orbital_hop(&self, chain_id: u64, token_in: Address, token_out: Address, amount_in: U256) -> Option<SimulatedHop> {
+        if !self.pools.has_pool(chain_id) {
+            return None;
+        }
+        let snapshot = match self.pools.snapshot(chain_id).await {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                tracing::debug!("Failed to read orbital pool on chain {}: {}", chain_id, e);
+                return None;
+            }
+        };
+        let (Some(index_in), Some(index_out)) = (snapshot.token_index(token_in), snapshot.token_index(token_out)) else {
+            return None;
+        };
+        if !snapshot.active {
+            return None;
+        }
+
+        let swap = |amount: U256| orbital_amount_out(
+            &snapshot.reserves,
+            index_in,
+            index_out,
+            amount,
+            snapshot.radius_squared,
+            snapshot.fee_bps,
+        );
+        let amount_out = swap(amount_in)?;
+        let probe_in = (amount_in / U256::from(SPOT_PROBE_DIVISOR)).max(U256::one());
+        let price_impact_bps = swap(probe_in).and_then(|probe_out| price_impact_bps(amount_in, amount_out, probe_in, probe_out));
+
+        Some(SimulatedHop {
+            protocol: format!("{:?}", Protocol::OrbitalAMM),
+            chain_id,
+            pool: format!("orbital:{}", self.orbital_pool_ids.get(&chain_id).copied().unwrap_or_default()),
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
+            price_impact_bps,
+        })
+    }
+
+    /// Confirmations on the source chain, then each bridge's transfer time
+    /// and confirmations on the destination
+    fn completion_secs(&self, hops: &[SimulatedHop], request: &QuoteIntentRequest) -> u64 {
+        let confirmations = |chain_id: u64| self.confirmations.get(&chain_id).copied().unwrap_or(1) * BLOCK_TIME_SECS;
+
+        let mut secs = confirmations(request.source_chain_id);
+        for hop in hops.iter().filter(|hop| hop.protocol == format!("{:?}", Protocol::Bridge)) {
+            secs += self.optimizer.get_bridge_time(hop.chain_id, request.dest_chain_id);
+            secs += confirmations(request.dest_chain_id);
+        }
+        secs
+    }
+}
+
+/// How much worse the executed rate is than the spot rate, in basis points
+pub fn price_impact_bps(amount_in: U256, amount_out: U256, probe_in: U256, probe_out: U256) -> Option<u32> {
+    if amount_in.is_zero() || probe_out.is_zero() {
+        return None;
+    }
+    // (amount_out / amount_in) / (probe_out / probe_in), scaled to bps
+    let ratio = amount_out
+        .checked_mul(probe_in)?
+        .checked_mul(U256::from(10_000))?
+        / amount_in.checked_mul(probe_out)?;
+    Some(10_000u64.saturating_sub(ratio.min(U256::from(10_000)).as_u64()) as u32)
+}
+
+/// `amount` in proportion to `numerator / denominator`
+fn scale(amount: U256, numerator: U256, denominator: U256) -> U256 {
+    if denominator.is_zero() {
+        return U256::zero();
+    }
+    amount.full_mul(numerator).checked_div(denominator.into())
+        .and_then(|scaled| U256::try_from(scaled).ok())
+        .unwrap_or(U256::MAX)
+}
+
+/// Total taken by bridge hops, in the bridged tokens
+fn bridge_fee(hops: &[SimulatedHop]) -> U256 {
+    hops.iter()
+        .filter(|hop| hop.protocol == format!("{:?}", Protocol::Bridge))
+        .fold(U256::zero(), |fee, hop| fee.saturating_add(hop.amount_in.saturating_sub(hop.amount_out)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_impact() {
+        // Spot is 1:1; getting 990 for 1000 is 1% worse
+        let impact = price_impact_bps(U256::from(1_000), U256::from(990), U256::from(10), U256::from(10));
+        assert_eq!(impact, Some(100));
+        // Better than spot counts as no impact
+        assert_eq!(price_impact_bps(U256::from(1_000), U256::from(1_100), U256::one(), U256::one()), Some(0));
+        assert_eq!(price_impact_bps(U256::zero(), U256::zero(), U256::one(), U256::one()), None);
+    }
+
+    #[test]
+    fn test_scale_and_bridge_fee() {
+        assert_eq!(scale(U256::from(900), U256::from(500), U256::from(1_000)), U256::from(450));
+        assert_eq!(scale(U256::from(900), U256::from(500), U256::zero()), U256::zero());
+
+        let hop = |protocol: Protocol, amount_in: u64, amount_out: u64| SimulatedHop {
+            protocol: format!("{:?}", protocol),
+            chain_id: 1,
+            pool: String::new(),
+            token_in: Address::zero(),
+            token_out: Address::zero(),
+            amount_in: U256::from(amount_in),
+            amount_out: U256::from(amount_out),
+            price_impact_bps: None,
+        };
+        let hops = vec![hop(Protocol::OrbitalAMM, 1_000, 990), hop(Protocol::Bridge, 990, 985)];
+        assert_eq!(bridge_fee(&hops), U256::from(5));
+    }
+}
@@ -0,0 +1,237 @@
+//! Webhook delivery
+//!
+//! Events are written to `webhook_deliveries`, one row per subscription that
+//! wants them, in the same request that caused them. The dispatcher claims
+//! due rows, POSTs the signed payload and either marks them delivered or
+//! schedules a retry with exponential backoff until the attempts run out.
+
+use chrono::{DateTime, Utc};
+use ethers::types::Address;
+use futures_util::future::join_all;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{
+    config::WebhookConfig,
+    database::WebhookDb,
+    error::{validation_error, ApiError, Result},
+    models::DueDelivery,
+};
+
+/// Events a subscription can ask for
+pub const EVENT_TYPES: &[&str] = &[
+    "intent.created",
+    "intent.matched",
+    "intent.executed",
+    "intent.failed",
+    "intent.cancelled",
+    "solver.registered",
+    "solver.slashed",
+    "solver.deactivated",
+];
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+pub const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+
+/// Body of every delivery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    /// Same for every subscription the event went to
+    pub event_id: Uuid,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub created_at: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+/// Hex HMAC-SHA256 of `{timestamp}.{body}`
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// `t=<unix seconds>,v1=<signature>`; receivers should reject stale
+/// timestamps to stop replays
+pub fn signature_header(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    format!("t={},v1={}", timestamp, sign(secret, timestamp, body))
+}
+
+/// Wait before retry number `attempt` (1-based), doubling from `base`
+pub fn backoff(attempt: u32, base: Duration, max: Duration) -> Duration {
+    base.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1))).min(max)
+}
+
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+pub fn validate_endpoint(url: &str, allow_http: bool) -> Result<()> {
+    let parsed = reqwest::Url::parse(url).map_err(|_| validation_error("Invalid webhook URL"))?;
+    match parsed.scheme() {
+        "https" => {}
+        "http" if allow_http => {}
+        _ => return Err(validation_error("Webhook URL must use HTTPS")),
+    }
+    if parsed.host().is_none() {
+        return Err(validation_error("Webhook URL must have a host"));
+    }
+    Ok(())
+}
+
+pub fn validate_events(events: &[String]) -> Result<()> {
+    if events.is_empty() {
+        return Err(validation_error("Subscribe to at least one event"));
+    }
+    match events.iter().find(|event| !EVENT_TYPES.contains(&event.as_str())) {
+        Some(unknown) => Err(validation_error(format!("Unknown webhook event: {}", unknown))),
+        None => Ok(()),
+    }
+}
+
+/// Queue `event_type` for every subscription that wants it: those owned by
+/// `subject` and those watching all subjects. Failures are logged rather
+/// than failing the request that raised the event.
+pub async fn publish(db: &PgPool, event_type: &str, subject: Address, data: serde_json::Value) {
+    let payload = WebhookPayload {
+        event_id: Uuid::new_v4(),
+        event_type: event_type.to_string(),
+        created_at: Utc::now(),
+        data,
+    };
+
+    match WebhookDb::enqueue_deliveries(db, subject, &payload).await {
+        Ok(0) => {}
+        Ok(queued) => tracing::debug!("Queued {} deliveries of {} {}", queued, event_type, payload.event_id),
+        Err(e) => tracing::error!("Failed to queue {} webhooks: {}", event_type, e),
+    }
+}
+
+pub struct WebhookDispatcher {
+    db: PgPool,
+    client: reqwest::Client,
+    config: WebhookConfig,
+}
+
+impl WebhookDispatcher {
+    pub fn new(db: PgPool, config: WebhookConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| ApiError::Internal(format!("Failed to build webhook client: {}", e)))?;
+
+        Ok(Self { db, client, config })
+    }
+
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(Duration::from_millis(self.config.poll_interval_ms.max(100)));
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.deliver_due().await {
+                tracing::warn!("Webhook delivery pass failed: {}", e);
+            }
+        }
+    }
+
+    /// Send every delivery that is due, returning how many were attempted
+    pub async fn deliver_due(&self) -> Result<usize> {
+        // Claimed rows are leased past the request timeout so another
+        // instance won't pick them up mid-flight
+        let lease = Duration::from_millis(self.config.timeout_ms) * 2;
+        let due = WebhookDb::claim_due_deliveries(&self.db, self.config.batch_size, lease).await?;
+
+        let attempted = due.len();
+        join_all(due.into_iter().map(|delivery| self.deliver(delivery))).await;
+        Ok(attempted)
+    }
+
+    async fn deliver(&self, delivery: DueDelivery) {
+        let body = delivery.payload.to_string().into_bytes();
+        let timestamp = Utc::now().timestamp();
+
+        let result = self
+            .client
+            .post(&delivery.url)
+            .header("content-type", "application/json")
+            .header(EVENT_HEADER, &delivery.event_type)
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .header(SIGNATURE_HEADER, signature_header(&delivery.secret, timestamp, &body))
+            .body(body)
+            .send()
+            .await;
+
+        let attempts = delivery.attempts as u32 + 1;
+        let (status_code, error) = match result {
+            Ok(response) if response.status().is_success() => (Some(response.status().as_u16()), None),
+            Ok(response) => (Some(response.status().as_u16()), Some(format!("HTTP {}", response.status()))),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        let outcome = match error {
+            None => WebhookDb::mark_delivered(&self.db, delivery.id, attempts, status_code).await,
+            Some(error) if attempts >= self.config.max_attempts => {
+                tracing::warn!("Webhook delivery {} to {} failed for good: {}", delivery.id, delivery.url, error);
+                WebhookDb::mark_failed(&self.db, delivery.id, attempts, status_code, &error).await
+            }
+            Some(error) => {
+                let delay = backoff(
+                    attempts,
+                    Duration::from_secs(self.config.base_backoff_secs),
+                    Duration::from_secs(self.config.max_backoff_secs),
+                );
+                WebhookDb::schedule_retry(&self.db, delivery.id, attempts, status_code, &error, delay).await
+            }
+        };
+
+        if let Err(e) = outcome {
+            tracing::error!("Failed to record webhook delivery {}: {}", delivery.id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_matches_known_vector() {
+        let body = br#"{"type":"intent.executed"}"#;
+        let signature = sign("whsec_test", 1_700_000_000, body);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"whsec_test").unwrap();
+        mac.update(b"1700000000.");
+        mac.update(body);
+        assert_eq!(signature, hex::encode(mac.finalize().into_bytes()));
+
+        assert_eq!(signature_header("whsec_test", 1_700_000_000, body), format!("t=1700000000,v1={}", signature));
+        assert_ne!(sign("whsec_other", 1_700_000_000, body), signature);
+        assert!(generate_secret().starts_with("whsec_"));
+    }
+
+    #[test]
+    fn test_backoff_and_validation() {
+        let base = Duration::from_secs(10);
+        let max = Duration::from_secs(300);
+        assert_eq!(backoff(1, base, max), Duration::from_secs(10));
+        assert_eq!(backoff(3, base, max), Duration::from_secs(40));
+        assert_eq!(backoff(10, base, max), max);
+
+        assert!(validate_endpoint("https://example.com/hooks", false).is_ok());
+        assert!(validate_endpoint("http://example.com/hooks", false).is_err());
+        assert!(validate_endpoint("http://localhost:9000/hooks", true).is_ok());
+        assert!(validate_events(&["intent.executed".to_string()]).is_ok());
+        assert!(validate_events(&["intent.exploded".to_string()]).is_err());
+        assert!(validate_events(&[]).is_err());
+    }
+}
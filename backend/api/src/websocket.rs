@@ -18,13 +18,91 @@ use crate::{
     models::{AppState, WebSocketMessage, IntentUpdateMessage, MarketDataMessage},
     error::Result,
     auth::validate_jwt,
+    ws_backplane::Backplane,
 };
 
+// Messages kept per channel for clients resuming after a reconnect
+const REPLAY_BUFFER_SIZE: usize = 256;
+
 // WebSocket connection parameters
 #[derive(Debug, Deserialize)]
 pub struct WsParams {
     token: Option<String>,
     subscribe: Option<String>, // Comma-separated list of channels
+    /// Comma-separated `channel=seq` pairs, the last message seen on each
+    /// channel before reconnecting
+    last_seq: Option<String>,
+}
+
+/// Recent messages on one channel, numbered in broadcast order
+#[derive(Debug)]
+pub struct ReplayBuffer {
+    next_seq: u64,
+    messages: VecDeque<WebSocketMessage>,
+    capacity: usize,
+}
+
+/// Messages a resuming client missed on one channel
+#[derive(Debug, Default)]
+pub struct Replay {
+    pub messages: Vec<WebSocketMessage>,
+    /// Older messages after `last_seq` have already left the buffer
+    pub gap: bool,
+    /// Seq of the latest message on the channel
+    pub current_seq: u64,
+}
+
+impl ReplayBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            next_seq: 1,
+            messages: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Number `message` and keep it, dropping the oldest once full
+    pub fn push(&mut self, mut message: WebSocketMessage) -> WebSocketMessage {
+        message.seq = Some(self.next_seq);
+        self.insert(message)
+    }
+
+    /// Keep a message numbered elsewhere, e.g. by the backplane
+    pub fn insert(&mut self, message: WebSocketMessage) -> WebSocketMessage {
+        if let Some(seq) = message.seq {
+            self.next_seq = self.next_seq.max(seq + 1);
+        }
+        if self.messages.len() == self.capacity {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(message.clone());
+        message
+    }
+
+    pub fn since(&self, last_seq: u64) -> Replay {
+        let oldest = self.messages.front().and_then(|message| message.seq);
+        Replay {
+            messages: self
+                .messages
+                .iter()
+                .filter(|message| message.seq.is_some_and(|seq| seq > last_seq))
+                .cloned()
+                .collect(),
+            gap: oldest.is_some_and(|oldest| oldest > last_seq + 1),
+            current_seq: self.next_seq - 1,
+        }
+    }
+}
+
+/// Parse `channel=seq,channel=seq`
+fn parse_last_seqs(value: &str) -> Vec<(String, u64)> {
+    value
+        .split(',')
+        .filter_map(|pair| {
+            let (channel, seq) = pair.trim().rsplit_once('=')?;
+            Some((channel.to_string(), seq.parse().ok()?))
+        })
+        .collect()
 }
 
 // WebSocket connection info
@@ -104,6 +182,7 @@ pub enum SubscriptionChannel {
     MarketData,              // general market data
     SolverUpdates(Address),  // solver-specific updates
     SystemAlerts,            // system-wide alerts
+    NewIntents,              // every newly submitted intent, for solvers
 }
 
 impl SubscriptionChannel {
@@ -111,6 +190,7 @@ impl SubscriptionChannel {
         match s {
             "market_data" => Some(Self::MarketData),
             "system_alerts" => Some(Self::SystemAlerts),
+            "new_intents" => Some(Self::NewIntents),
             _ => {
                 if let Some(intent_id) = s.strip_prefix("intent:") {
                     if let Ok(id) = intent_id.parse::<H256>() {
@@ -139,6 +219,7 @@ impl SubscriptionChannel {
             Self::MarketData => "market_data".to_string(),
             Self::SolverUpdates(addr) => format!("solver:{:#x}", addr),
             Self::SystemAlerts => "system_alerts".to_string(),
+            Self::NewIntents => "new_intents".to_string(),
         }
     }
 }
@@ -147,9 +228,12 @@ impl SubscriptionChannel {
 pub struct WebSocketManager {
     connections: Arc<RwLock<HashMap<Uuid, ConnectionInfo>>>,
     broadcasters: Arc<RwLock<HashMap<SubscriptionChannel, broadcast::Sender<WebSocketMessage>>>>,
+    replay_buffers: Arc<RwLock<HashMap<SubscriptionChannel, ReplayBuffer>>>,
     health_monitor: Arc<RwLock<HashMap<Uuid, Instant>>>,
     metrics: Arc<RwLock<WebSocketMetrics>>,
     subscription_limits: SubscriptionLimits,
+    /// Relays broadcasts through Redis when several instances run
+    backplane: std::sync::OnceLock<Backplane>,
 }
 
 #[derive(Debug, Default)]
@@ -168,6 +252,7 @@ impl WebSocketManager {
         Self {
             connections: Arc::new(RwLock::new(HashMap::new())),
             broadcasters: Arc::new(RwLock::new(HashMap::new())),
+            replay_buffers: Arc::new(RwLock::new(HashMap::new())),
             health_monitor: Arc::new(RwLock::new(HashMap::new())),
             metrics: Arc::new(RwLock::new(WebSocketMetrics::default())),
             subscription_limits: SubscriptionLimits {
@@ -182,9 +267,16 @@ impl WebSocketManager {
                     "solver:*".to_string(),
                 ],
             },
+            backplane: std::sync::OnceLock::new(),
         }
     }
     
+    /// Send broadcasts through `backplane` from now on; false if one is
+    /// already attached
+    pub fn attach_backplane(&self, backplane: Backplane) -> bool {
+        self.backplane.set(backplane).is_ok()
+    }
+    
     pub async fn add_connection(&self, conn_info: ConnectionInfo) {
         let mut connections = self.connections.write().await;
         let mut health_monitor = self.health_monitor.write().await;
@@ -220,14 +312,77 @@ impl WebSocketManager {
         }
     }
     
-    pub async fn broadcast_to_channel(&self, channel: SubscriptionChannel, message: WebSocketMessage) {
+    pub async fn broadcast_to_channel(&self, channel: SubscriptionChannel, mut message: WebSocketMessage) {
+        message.channel = Some(channel.to_string());
+        
+        // With a backplane the message comes back through `deliver`, on
+        // this instance as on every other. If Redis is unreachable, at
+        // least reach the subscribers connected here.
+        if let Some(backplane) = self.backplane.get() {
+            match backplane.publish(&channel, &message).await {
+                Ok(_) => return,
+                Err(e) => tracing::warn!("Backplane publish failed, delivering locally only: {}", e),
+            }
+        }
+        
+        self.send_local(channel, message, ReplayBuffer::push).await;
+    }
+    
+    /// Hand a message numbered by the backplane to this instance's
+    /// subscribers
+    pub async fn deliver(&self, channel: SubscriptionChannel, message: WebSocketMessage) {
+        self.send_local(channel, message, ReplayBuffer::insert).await;
+    }
+    
+    async fn send_local(
+        &self,
+        channel: SubscriptionChannel,
+        message: WebSocketMessage,
+        keep: fn(&mut ReplayBuffer, WebSocketMessage) -> WebSocketMessage,
+    ) {
         let broadcaster = self.get_broadcaster(&channel).await;
         
+        // Numbering and sending under one lock keeps seqs in send order
+        let mut replay_buffers = self.replay_buffers.write().await;
+        let buffer = replay_buffers
+            .entry(channel.clone())
+            .or_insert_with(|| ReplayBuffer::new(REPLAY_BUFFER_SIZE));
+        let message = keep(buffer, message);
+        
         if let Err(e) = broadcaster.send(message) {
-            tracing::warn!("Failed to broadcast message to channel {:?}: {}", channel, e);
+            tracing::debug!("No live subscribers on channel {:?}: {}", channel, e);
         }
     }
     
+    /// Messages on `channel` after `last_seq`
+    pub async fn replay(&self, channel: &SubscriptionChannel, last_seq: u64) -> Replay {
+        let replay_buffers = self.replay_buffers.read().await;
+        replay_buffers
+            .get(channel)
+            .map(|buffer| buffer.since(last_seq))
+            .unwrap_or_default()
+    }
+    
+    /// A live receiver for `channel` plus, when `last_seq` is given, what was
+    /// missed since. Subscribing under the replay lock means nothing falls
+    /// between the two.
+    pub async fn subscribe_from(
+        &self,
+        channel: &SubscriptionChannel,
+        last_seq: Option<u64>,
+    ) -> (broadcast::Receiver<WebSocketMessage>, Option<Replay>) {
+        let broadcaster = self.get_broadcaster(channel).await;
+        let replay_buffers = self.replay_buffers.read().await;
+        let receiver = broadcaster.subscribe();
+        let replay = last_seq.map(|last_seq| {
+            replay_buffers
+                .get(channel)
+                .map(|buffer| buffer.since(last_seq))
+                .unwrap_or_default()
+        });
+        (receiver, replay)
+    }
+    
     pub async fn get_connection_count(&self) -> usize {
         let connections = self.connections.read().await;
         connections.len()
@@ -596,6 +751,8 @@ async fn handle_websocket(
             "authenticated": user_address.is_some()
         }),
         timestamp: Utc::now(),
+        seq: None,
+        channel: None,
     };
     
     if let Ok(welcome_json) = serde_json::to_string(&welcome_msg) {
@@ -604,6 +761,27 @@ async fn handle_websocket(
         }
     }
     
+    // Catch a reconnecting client up on what it missed. Live receivers are
+    // already attached, so a message may arrive twice but is never lost.
+    for (channel_str, last_seq) in params.last_seq.as_deref().map(parse_last_seqs).unwrap_or_default() {
+        let Some(channel) = SubscriptionChannel::from_string(&channel_str) else { continue };
+        if !broadcast_receivers.iter().any(|(subscribed, _)| *subscribed == channel) {
+            continue;
+        }
+        
+        let replay = WS_MANAGER.replay(&channel, last_seq).await;
+        if replay.gap {
+            tracing::debug!("WebSocket {} resumed {} past the replay buffer", conn_id, channel_str);
+        }
+        for message in replay.messages {
+            if let Ok(json) = serde_json::to_string(&message) {
+                if sender.send(Message::Text(json)).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+    
     // Spawn task to handle incoming messages
     let ws_manager = &WS_MANAGER;
     let conn_id_clone = conn_id;
@@ -758,6 +936,15 @@ async fn handle_incoming_message(
                             &json
                         ).await
                     }
+                    "resume" => {
+                        // Subscribe and replay what was missed since last_seq
+                        handle_resume_message(
+                            &WS_MANAGER,
+                            conn_id,
+                            user_address,
+                            &json
+                        ).await
+                    }
                     _ => {
                         tracing::warn!("Unknown WebSocket message type: {}", msg_type);
                         Ok(())
@@ -785,7 +972,9 @@ fn can_subscribe_to_channel(
     user_address: Option<Address>,
 ) -> bool {
     match channel {
-        SubscriptionChannel::MarketData | SubscriptionChannel::SystemAlerts => {
+        SubscriptionChannel::MarketData
+        | SubscriptionChannel::SystemAlerts
+        | SubscriptionChannel::NewIntents => {
             // Public channels
             true
         }
@@ -809,6 +998,8 @@ pub async fn broadcast_intent_update(
         message_type: "intent_update".to_string(),
         data: serde_json::to_value(&update).unwrap_or_default(),
         timestamp: Utc::now(),
+        seq: None,
+        channel: None,
     };
     
     // Broadcast to intent-specific channel
@@ -821,6 +1012,47 @@ pub async fn broadcast_intent_update(
     // This would require looking up the intent in the database
 }
 
+/// Announce a newly submitted intent to solvers on the `new_intents` channel
+pub async fn broadcast_new_intent(
+    intent_id: H256,
+    intent: &intents_engine::intent::Intent,
+) {
+    let message = WebSocketMessage {
+        message_type: "new_intent".to_string(),
+        data: serde_json::json!({
+            "intent_id": intent_id,
+            "intent": intent,
+        }),
+        timestamp: Utc::now(),
+        seq: None,
+        channel: None,
+    };
+
+    WS_MANAGER.broadcast_to_channel(
+        SubscriptionChannel::NewIntents,
+        message
+    ).await;
+}
+
+/// A solver's quote for an open intent, sent to the intent's subscribers
+pub async fn broadcast_solver_quote(
+    intent_id: H256,
+    quote: serde_json::Value,
+) {
+    let message = WebSocketMessage {
+        message_type: "solver_quote".to_string(),
+        data: quote,
+        timestamp: Utc::now(),
+        seq: None,
+        channel: None,
+    };
+
+    WS_MANAGER.broadcast_to_channel(
+        SubscriptionChannel::IntentUpdates(intent_id),
+        message
+    ).await;
+}
+
 pub async fn broadcast_market_data(
     data: MarketDataMessage,
 ) {
@@ -828,6 +1060,8 @@ pub async fn broadcast_market_data(
         message_type: "market_data".to_string(),
         data: serde_json::to_value(&data).unwrap_or_default(),
         timestamp: Utc::now(),
+        seq: None,
+        channel: None,
     };
     
     WS_MANAGER.broadcast_to_channel(
@@ -851,6 +1085,8 @@ pub async fn broadcast_system_alert(
         message_type: "system_alert".to_string(),
         data: alert_data,
         timestamp: Utc::now(),
+        seq: None,
+        channel: None,
     };
     
     WS_MANAGER.broadcast_to_channel(
@@ -1040,4 +1276,148 @@ async fn handle_unsubscribe_message(
     ws_manager.send_to_connection(connection_id, response).await?;
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Handle resume message: subscribe to each channel and replay the messages
+/// sent after the client's `last_seq`
+async fn handle_resume_message(
+    ws_manager: &WebSocketManager,
+    connection_id: Uuid,
+    user_address: Option<Address>,
+    msg: &serde_json::Value,
+) -> Result<()> {
+    #[derive(Deserialize)]
+    struct ResumeChannel {
+        channel: String,
+        last_seq: u64,
+    }
+    
+    #[derive(Deserialize)]
+    struct ResumeRequest {
+        channels: Vec<ResumeChannel>,
+    }
+    
+    let resume_request: ResumeRequest = serde_json::from_value(msg.clone())
+        .map_err(|e| crate::error::validation_error(format!("Invalid resume request: {}", e)))?;
+    
+    if resume_request.channels.is_empty() {
+        return Err(crate::error::validation_error("No channels specified"));
+    }
+    
+    if resume_request.channels.len() > 20 {
+        return Err(crate::error::validation_error("Too many channels (max 20)"));
+    }
+    
+    let mut resumed = Vec::new();
+    let mut failed_channels = Vec::new();
+    
+    for request in resume_request.channels {
+        let Some(channel) = SubscriptionChannel::from_string(&request.channel) else {
+            failed_channels.push((request.channel, "Invalid channel format".to_string()));
+            continue;
+        };
+        
+        if !can_subscribe_to_channel(&channel, user_address) {
+            failed_channels.push((request.channel, "Access denied".to_string()));
+            continue;
+        }
+        
+        if let Err(e) = ws_manager.subscribe_to_channel(connection_id, channel.clone()).await {
+            failed_channels.push((request.channel, format!("Subscription failed: {}", e)));
+            continue;
+        }
+        
+        let replay = ws_manager.replay(&channel, request.last_seq).await;
+        let replayed = replay.messages.len();
+        for message in replay.messages {
+            ws_manager.send_to_connection(connection_id, serde_json::to_value(&message)?).await?;
+        }
+        
+        resumed.push(serde_json::json!({
+            "channel": request.channel,
+            "replayed": replayed,
+            "gap": replay.gap,
+            "current_seq": replay.current_seq,
+        }));
+    }
+    
+    // Send confirmation back to client
+    let response = serde_json::json!({
+        "type": "resume_response",
+        "success": !resumed.is_empty(),
+        "resumed": resumed,
+        "failed": failed_channels,
+        "timestamp": Utc::now()
+    });
+    
+    ws_manager.send_to_connection(connection_id, response).await?;
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(n: u64) -> WebSocketMessage {
+        WebSocketMessage {
+            message_type: "intent_update".to_string(),
+            data: serde_json::json!({ "n": n }),
+            timestamp: Utc::now(),
+            seq: None,
+            channel: None,
+        }
+    }
+
+    #[test]
+    fn test_replay_buffer_numbers_and_evicts() {
+        let mut buffer = ReplayBuffer::new(3);
+        for n in 0..5 {
+            buffer.push(message(n));
+        }
+
+        let replay = buffer.since(3);
+        assert_eq!(replay.messages.iter().map(|m| m.seq.unwrap()).collect::<Vec<_>>(), vec![4, 5]);
+        assert!(!replay.gap);
+        assert_eq!(replay.current_seq, 5);
+
+        // Seqs 2..=5 were sent but 2 has been evicted
+        let replay = buffer.since(1);
+        assert_eq!(replay.messages.len(), 3);
+        assert!(replay.gap);
+
+        assert!(buffer.since(5).messages.is_empty());
+
+        // Seqs from the backplane are kept, and local numbering carries on
+        // after them
+        let mut numbered = message(5);
+        numbered.seq = Some(40);
+        assert_eq!(buffer.insert(numbered).seq, Some(40));
+        assert_eq!(buffer.push(message(6)).seq, Some(41));
+        assert_eq!(buffer.since(39).messages.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_from_replays_then_streams() {
+        let manager = WebSocketManager::new();
+        for n in 0..3 {
+            manager.broadcast_to_channel(SubscriptionChannel::MarketData, message(n)).await;
+        }
+
+        let (mut receiver, replay) = manager.subscribe_from(&SubscriptionChannel::MarketData, Some(1)).await;
+        let replay = replay.unwrap();
+        assert_eq!(replay.messages.iter().map(|m| m.seq.unwrap()).collect::<Vec<_>>(), vec![2, 3]);
+
+        manager.broadcast_to_channel(SubscriptionChannel::MarketData, message(3)).await;
+        assert_eq!(receiver.recv().await.unwrap().seq, Some(4));
+
+        let (_, replay) = manager.subscribe_from(&SubscriptionChannel::MarketData, None).await;
+        assert!(replay.is_none());
+    }
+
+    #[test]
+    fn test_parse_last_seqs() {
+        let parsed = parse_last_seqs("intent:0x01=4, market_data=10,bad,new_intents=x");
+        assert_eq!(parsed, vec![("intent:0x01".to_string(), 4), ("market_data".to_string(), 10)]);
+    }
+}
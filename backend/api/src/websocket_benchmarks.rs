@@ -51,6 +51,8 @@ impl WebSocketBenchmarks {
                     "data": format!("test_data_{}", i)
                 }),
                 timestamp: Utc::now(),
+                seq: None,
+                channel: None,
             };
             
             if broadcaster.send(test_message).is_err() {
@@ -0,0 +1,174 @@
+//! Redis pub/sub backplane for WebSocket fan-out
+//!
+//! `WS_MANAGER`'s broadcast channels only reach clients connected to the
+//! same process, so with several API instances a client would miss
+//! anything published elsewhere. With the backplane, a broadcast goes to
+//! Redis instead and every instance, the sender included, relays it to its
+//! own subscribers.
+//!
+//! WebSocket channels are spread over a fixed number of Redis channels by
+//! hash, and each instance reads every shard on its own connection, so a
+//! busy channel only holds up the channels sharing its shard. One channel
+//! always maps to one shard, which keeps its messages in order. Seqs are
+//! assigned in Redis by the same script that publishes, so they are the
+//! same on every instance and a client can resume on any of them.
+
+use futures_util::StreamExt;
+use redis::{aio::MultiplexedConnection, Client, Script};
+use std::{sync::OnceLock, time::Duration};
+
+use crate::{
+    cache::CacheKeys,
+    config::Config,
+    error::{ApiError, Result},
+    models::WebSocketMessage,
+    websocket::{SubscriptionChannel, WS_MANAGER},
+};
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// KEYS: the channel's seq counter. ARGV: shard channel, message JSON.
+/// Numbers and publishes in one step, so seqs go out in order.
+const PUBLISH_SCRIPT: &str = r#"
+local seq = redis.call('INCR', KEYS[1])
+redis.call('PUBLISH', ARGV[1], seq .. ' ' .. ARGV[2])
+return seq
+"#;
+
+fn script() -> &'static Script {
+    static SCRIPT: OnceLock<Script> = OnceLock::new();
+    SCRIPT.get_or_init(|| Script::new(PUBLISH_SCRIPT))
+}
+
+pub struct Backplane {
+    redis: MultiplexedConnection,
+    shards: u32,
+}
+
+impl Backplane {
+    pub fn new(redis: MultiplexedConnection, shards: u32) -> Self {
+        Self { redis, shards: shards.max(1) }
+    }
+
+    /// Number `message` and send it to every instance; returns its seq
+    pub async fn publish(&self, channel: &SubscriptionChannel, message: &WebSocketMessage) -> Result<u64> {
+        let channel = channel.to_string();
+        let payload = serde_json::to_string(message)?;
+
+        script()
+            .key(CacheKeys::ws_seq(&channel))
+            .arg(CacheKeys::ws_shard(shard_for(&channel, self.shards)))
+            .arg(payload)
+            .invoke_async(&mut self.redis.clone())
+            .await
+            .map_err(|e| ApiError::Redis(e.to_string()))
+    }
+}
+
+/// The shard a WebSocket channel's messages travel on (FNV-1a, so every
+/// instance agrees)
+pub fn shard_for(channel: &str, shards: u32) -> u32 {
+    let hash = channel.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    (hash % shards.max(1) as u64) as u32
+}
+
+/// Read a published `<seq> <json>` payload back into a numbered message
+pub fn decode(payload: &str) -> Option<(SubscriptionChannel, WebSocketMessage)> {
+    let (seq, json) = payload.split_once(' ')?;
+    let mut message: WebSocketMessage = serde_json::from_str(json).ok()?;
+    message.seq = Some(seq.parse().ok()?);
+    let channel = SubscriptionChannel::from_string(message.channel.as_deref()?)?;
+    Some((channel, message))
+}
+
+/// Start relaying every shard to this instance's subscribers and route
+/// broadcasts through Redis from now on
+pub async fn start(config: &Config, redis: MultiplexedConnection) -> Result<()> {
+    let client = Client::open(config.redis_url.as_str())
+        .map_err(|e| crate::error::internal_error(format!("Failed to create Redis client: {}", e)))?;
+
+    let shards = config.websocket.backplane_shards.max(1);
+    for shard in 0..shards {
+        tokio::spawn(relay_shard(client.clone(), shard));
+    }
+
+    if !WS_MANAGER.attach_backplane(Backplane::new(redis, shards)) {
+        tracing::warn!("WebSocket backplane was already started");
+    }
+    tracing::info!("WebSocket backplane started with {} shards", shards);
+    Ok(())
+}
+
+/// Runs for the life of the process, resubscribing after errors
+async fn relay_shard(client: Client, shard: u32) {
+    loop {
+        match relay(&client, shard).await {
+            Ok(()) => tracing::warn!("WebSocket backplane shard {} disconnected, resubscribing", shard),
+            Err(e) => tracing::warn!("WebSocket backplane shard {} failed, resubscribing: {}", shard, e),
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn relay(client: &Client, shard: u32) -> redis::RedisResult<()> {
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    pubsub.subscribe(CacheKeys::ws_shard(shard)).await?;
+
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        let payload: String = match message.get_payload() {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::warn!("Unreadable backplane message on shard {}: {}", shard, e);
+                continue;
+            }
+        };
+        match decode(&payload) {
+            Some((channel, message)) => WS_MANAGER.deliver(channel, message).await,
+            None => tracing::warn!("Malformed backplane message on shard {}", shard),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_shards_are_stable_and_in_range() {
+        assert_eq!(shard_for("market_data", 8), shard_for("market_data", 8));
+        assert_eq!(shard_for("market_data", 1), 0);
+        assert_eq!(shard_for("market_data", 0), 0);
+
+        let channels: Vec<String> = (0..64).map(|n| format!("intent:{:#066x}", n)).collect();
+        let used: std::collections::HashSet<u32> = channels.iter().map(|channel| shard_for(channel, 4)).collect();
+        assert!(used.iter().all(|shard| *shard < 4));
+        assert!(used.len() > 1);
+    }
+
+    #[test]
+    fn test_decode_published_payload() {
+        let message = WebSocketMessage {
+            message_type: "market_data".to_string(),
+            data: serde_json::json!({ "price": "1.0" }),
+            timestamp: Utc::now(),
+            seq: None,
+            channel: Some("market_data".to_string()),
+        };
+        let payload = format!("42 {}", serde_json::to_string(&message).unwrap());
+
+        let (channel, decoded) = decode(&payload).unwrap();
+        assert_eq!(channel, SubscriptionChannel::MarketData);
+        assert_eq!(decoded.seq, Some(42));
+        assert_eq!(decoded.data, message.data);
+
+        assert!(decode("42").is_none());
+        assert!(decode("x {}").is_none());
+        let unrouted = WebSocketMessage { channel: None, ..message };
+        assert!(decode(&format!("1 {}", serde_json::to_string(&unrouted).unwrap())).is_none());
+    }
+}
@@ -0,0 +1,269 @@
+use arrow::{
+    array::{ArrayRef, StringArray, TimestampMicrosecondArray, UInt64Array},
+    datatypes::{DataType, Field, Schema, TimeUnit},
+    record_batch::RecordBatch,
+};
+use chrono::{NaiveDate, Utc};
+use object_store::{path::Path, ObjectStore, PutPayload};
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Arc};
+use tokio::time::{interval, Duration};
+
+use crate::{
+    config::RetentionConfig,
+    error::{IndexerError, Result},
+    storage::IndexerStorage,
+    IndexedEvent,
+};
+
+// Events of one chain, day and type, kept in Postgres after the events
+// themselves are archived
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyRollup {
+    pub chain_id: u64,
+    pub day: NaiveDate,
+    pub event_type: String,
+    pub events: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PruneReport {
+    pub events_archived: u64,
+    pub files_written: u64,
+}
+
+pub fn daily_rollups<'a>(events: impl IntoIterator<Item = &'a IndexedEvent>) -> Vec<DailyRollup> {
+    let mut counts: BTreeMap<(u64, NaiveDate, &str), u64> = BTreeMap::new();
+    for event in events {
+        *counts.entry((event.chain_id, event.timestamp.date_naive(), &event.event_type)).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .map(|((chain_id, day, event_type), events)| DailyRollup {
+            chain_id,
+            day,
+            event_type: event_type.to_string(),
+            events,
+        })
+        .collect()
+}
+
+// Hive-style layout so the archive can be queried by chain and date.
+// Named after the first event's sequence number, so re-exporting a batch
+// after a crash overwrites the same file.
+pub fn archive_path(prefix: &Path, chain_id: u64, day: NaiveDate, first_seq: i64) -> Path {
+    prefix
+        .child("indexed_events")
+        .child(format!("chain_id={}", chain_id))
+        .child(format!("date={}", day))
+        .child(format!("part-{:020}.parquet", first_seq))
+}
+
+pub fn encode_parquet(events: &[&IndexedEvent]) -> Result<Vec<u8>> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("chain_id", DataType::UInt64, false),
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("transaction_hash", DataType::Utf8, false),
+        Field::new("transaction_index", DataType::UInt64, false),
+        Field::new("log_index", DataType::UInt64, false),
+        Field::new("event_type", DataType::Utf8, false),
+        Field::new("contract_address", DataType::Utf8, false),
+        Field::new("event_data", DataType::Utf8, false),
+        Field::new("timestamp", DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())), false),
+    ]));
+
+    let strings = |value: fn(&IndexedEvent) -> String| -> ArrayRef {
+        Arc::new(StringArray::from_iter_values(events.iter().map(|event| value(event))))
+    };
+    let numbers = |value: fn(&IndexedEvent) -> u64| -> ArrayRef {
+        Arc::new(UInt64Array::from_iter_values(events.iter().map(|event| value(event))))
+    };
+    let columns = vec![
+        strings(|e| e.id.to_string()),
+        numbers(|e| e.chain_id),
+        numbers(|e| e.block_number),
+        strings(|e| format!("{:#x}", e.transaction_hash)),
+        numbers(|e| e.transaction_index),
+        numbers(|e| e.log_index),
+        strings(|e| e.event_type.clone()),
+        strings(|e| format!("{:#x}", e.contract_address)),
+        strings(|e| e.event_data.to_string()),
+        Arc::new(
+            TimestampMicrosecondArray::from_iter_values(events.iter().map(|e| e.timestamp.timestamp_micros()))
+                .with_timezone("UTC"),
+        ),
+    ];
+
+    let archive_error = |e: &dyn std::fmt::Display| IndexerError::Archive(e.to_string());
+    let batch = RecordBatch::try_new(schema.clone(), columns).map_err(|e| archive_error(&e))?;
+    let properties = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+
+    let mut buffer = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut buffer, schema, Some(properties)).map_err(|e| archive_error(&e))?;
+    writer.write(&batch).map_err(|e| archive_error(&e))?;
+    writer.close().map_err(|e| archive_error(&e))?;
+    Ok(buffer)
+}
+
+// Moves events past the retention window to cold storage
+pub struct Archiver {
+    config: RetentionConfig,
+    storage: Arc<IndexerStorage>,
+    store: Arc<dyn ObjectStore>,
+    prefix: Path,
+}
+
+impl Archiver {
+    pub fn new(config: RetentionConfig, storage: Arc<IndexerStorage>) -> Result<Self> {
+        let url = url::Url::parse(&config.archive_url)
+            .map_err(|e| IndexerError::Config(format!("Invalid archive URL {}: {}", config.archive_url, e)))?;
+        // Credentials come from the usual AWS_* variables
+        let options = std::env::vars()
+            .filter(|(key, _)| key.starts_with("AWS_"))
+            .map(|(key, value)| (key.to_ascii_lowercase(), value));
+        let (store, prefix) = object_store::parse_url_opts(&url, options)
+            .map_err(|e| IndexerError::Config(format!("Unsupported archive URL {}: {}", config.archive_url, e)))?;
+
+        Ok(Self { config, storage, store: Arc::from(store), prefix })
+    }
+
+    pub async fn run(self) {
+        let mut ticks = interval(Duration::from_secs(self.config.interval_secs.max(1)));
+        loop {
+            ticks.tick().await;
+
+            // Keep going while full batches come back
+            loop {
+                match self.prune_once().await {
+                    Ok(report) if report.events_archived > 0 => {
+                        tracing::info!(
+                            "Archived {} events to {} files",
+                            report.events_archived,
+                            report.files_written
+                        );
+                        if report.events_archived < self.config.batch_size {
+                            break;
+                        }
+                    }
+                    Ok(_) => break,
+                    Err(e) => {
+                        tracing::warn!("Event archival failed: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    // Export one batch of expired events, then delete them. The upload goes
+    // first, so a failure leaves the events in Postgres to retry.
+    pub async fn prune_once(&self) -> Result<PruneReport> {
+        let cutoff = Utc::now() - chrono::Duration::days(i64::from(self.config.hot_days));
+        let batch = self.storage.events_before(cutoff, self.config.batch_size).await?;
+        if batch.is_empty() {
+            return Ok(PruneReport::default());
+        }
+
+        let mut files: BTreeMap<(u64, NaiveDate), Vec<&(i64, IndexedEvent)>> = BTreeMap::new();
+        for entry in &batch {
+            files.entry((entry.1.chain_id, entry.1.timestamp.date_naive())).or_default().push(entry);
+        }
+
+        for ((chain_id, day), entries) in &files {
+            let first_seq = entries.iter().map(|(seq, _)| *seq).min().unwrap_or_default();
+            let events: Vec<&IndexedEvent> = entries.iter().map(|(_, event)| event).collect();
+            let path = archive_path(&self.prefix, *chain_id, *day, first_seq);
+            self.store
+                .put(&path, PutPayload::from(encode_parquet(&events)?))
+                .await
+                .map_err(|e| IndexerError::Archive(format!("Failed to upload {}: {}", path, e)))?;
+        }
+
+        let seqs: Vec<i64> = batch.iter().map(|(seq, _)| *seq).collect();
+        let rollups = daily_rollups(batch.iter().map(|(_, event)| event));
+        let archived = self.storage.prune_archived(&seqs, &rollups).await?;
+
+        Ok(PruneReport {
+            events_archived: archived,
+            files_written: files.len() as u64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use ethers::types::{Address, H256};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn event(chain_id: u64, event_type: &str, day: u32) -> IndexedEvent {
+        IndexedEvent {
+            id: uuid::Uuid::new_v4(),
+            chain_id,
+            block_number: 100,
+            transaction_hash: H256::repeat_byte(1),
+            transaction_index: 0,
+            log_index: 3,
+            event_type: event_type.to_string(),
+            contract_address: Address::repeat_byte(2),
+            event_data: serde_json::json!({ "amount": "10" }),
+            timestamp: Utc.with_ymd_and_hms(2024, 1, day, 12, 0, 0).unwrap(),
+            processed: false,
+        }
+    }
+
+    #[test]
+    fn test_rollups_and_paths_by_chain_and_day() {
+        let events = [
+            event(1, "ToroidalSwap", 1),
+            event(1, "ToroidalSwap", 1),
+            event(1, "ToroidalSwap", 2),
+            event(10, "IntentCreated", 1),
+        ];
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let rollup = |chain_id, d, event_type: &str, events| DailyRollup {
+            chain_id,
+            day: day(d),
+            event_type: event_type.to_string(),
+            events,
+        };
+        assert_eq!(
+            daily_rollups(&events),
+            vec![
+                rollup(1, 1, "ToroidalSwap", 2),
+                rollup(1, 2, "ToroidalSwap", 1),
+                rollup(10, 1, "IntentCreated", 1),
+            ]
+        );
+
+        let path = archive_path(&Path::from("archive"), 1, day(2), 42);
+        assert_eq!(
+            path.as_ref(),
+            "archive/indexed_events/chain_id=1/date=2024-01-02/part-00000000000000000042.parquet"
+        );
+    }
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let events = [event(1, "ToroidalSwap", 1), event(1, "LiquidityAdded", 1)];
+        let bytes = encode_parquet(&events.iter().collect::<Vec<_>>()).unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(bytes))
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<RecordBatch> = reader.map(|batch| batch.unwrap()).collect();
+        assert_eq!(batches.iter().map(|batch| batch.num_rows()).sum::<usize>(), 2);
+
+        let types = batches[0]
+            .column_by_name("event_type")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(types.value(1), "LiquidityAdded");
+    }
+}
@@ -0,0 +1,335 @@
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{Filter, U256},
+};
+use futures::{stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Arc};
+use tokio::{
+    sync::Mutex,
+    time::{sleep, Duration, Instant},
+};
+
+use crate::{
+    config::BackfillConfig,
+    error::{IndexerError, Result},
+    events::{EventProcessor, ProcessedLog},
+    storage::IndexerStorage,
+    watchlist::{build_filters, WatchList, WatchedContract},
+    ChainIndexerConfig,
+};
+
+// Inclusive block range, the unit of backfill checkpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockRange {
+    pub from: u64,
+    pub to: u64,
+}
+
+impl BlockRange {
+    fn covers(&self, other: &BlockRange) -> bool {
+        self.from <= other.from && self.to >= other.to
+    }
+}
+
+// Ranges of `range_size` blocks from `start` to `end`, aligned on `start` so
+// the same ranges come back on every run, minus those already checkpointed
+pub fn plan_ranges(start: u64, end: u64, range_size: u64, done: &[BlockRange]) -> Vec<BlockRange> {
+    let range_size = range_size.max(1);
+    let mut ranges = Vec::new();
+    let mut from = start;
+    while from <= end {
+        let range = BlockRange { from, to: from.saturating_add(range_size - 1).min(end) };
+        if !done.iter().any(|d| d.covers(&range)) {
+            ranges.push(range);
+        }
+        from = range.to + 1;
+    }
+    ranges
+}
+
+// Last block of the unbroken run of checkpointed ranges starting at `start`
+pub fn contiguous_end(start: u64, done: &[BlockRange]) -> Option<u64> {
+    let mut sorted = done.to_vec();
+    sorted.sort_by_key(|range| range.from);
+
+    let mut end: Option<u64> = None;
+    for range in sorted {
+        let next = end.map_or(start, |end| end + 1);
+        if range.from > next {
+            break;
+        }
+        if range.to >= next {
+            end = Some(range.to);
+        }
+    }
+    end
+}
+
+// `ranges` sorted, with overlapping and adjacent ones joined
+pub fn merge_ranges(ranges: &[BlockRange]) -> Vec<BlockRange> {
+    let mut sorted = ranges.to_vec();
+    sorted.sort_by_key(|range| range.from);
+
+    let mut merged: Vec<BlockRange> = Vec::new();
+    for range in sorted {
+        match merged.last_mut() {
+            Some(last) if range.from <= last.to.saturating_add(1) => last.to = last.to.max(range.to),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+// Blocks from `start` to `end` that no range in `covered` includes
+pub fn find_gaps(start: u64, end: u64, covered: &[BlockRange]) -> Vec<BlockRange> {
+    let mut gaps = Vec::new();
+    let mut next = start;
+    for range in merge_ranges(covered) {
+        if next > end {
+            break;
+        }
+        if range.to < next {
+            continue;
+        }
+        if range.from > next {
+            gaps.push(BlockRange { from: next, to: (range.from - 1).min(end) });
+        }
+        next = range.to.saturating_add(1);
+    }
+    if next <= end {
+        gaps.push(BlockRange { from: next, to: end });
+    }
+    gaps
+}
+
+// Spaces requests evenly at a fixed rate
+pub struct RateLimiter {
+    interval: Duration,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_second: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / requests_per_second.max(1),
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    pub async fn acquire(&self) {
+        let slot = {
+            let mut next = self.next.lock().await;
+            let slot = (*next).max(Instant::now());
+            *next = slot + self.interval;
+            slot
+        };
+        sleep(slot.saturating_duration_since(Instant::now())).await;
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackfillReport {
+    pub chain_id: u64,
+    pub ranges_completed: u64,
+    pub ranges_skipped: u64,
+    pub events_indexed: u64,
+    // Last block the chain is now indexed to without gaps
+    pub indexed_to: Option<u64>,
+}
+
+// Walks one chain from `start_block` to the confirmed head
+pub struct Backfill {
+    config: ChainIndexerConfig,
+    settings: BackfillConfig,
+    provider: Arc<Provider<Http>>,
+    storage: Arc<IndexerStorage>,
+    event_processor: Arc<EventProcessor>,
+    watch_list: WatchList,
+    limiter: RateLimiter,
+}
+
+impl Backfill {
+    pub fn new(
+        config: ChainIndexerConfig,
+        settings: BackfillConfig,
+        storage: Arc<IndexerStorage>,
+        event_processor: Arc<EventProcessor>,
+        watch_list: WatchList,
+    ) -> Result<Self> {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(&config.rpc_url)
+                .map_err(|e| IndexerError::ProviderError(e.to_string()))?
+        );
+        let limiter = RateLimiter::new(settings.requests_per_second);
+
+        Ok(Self { config, settings, provider, storage, event_processor, watch_list, limiter })
+    }
+
+    pub async fn run(&self) -> Result<BackfillReport> {
+        let chain_id = self.config.chain_id;
+
+        self.limiter.acquire().await;
+        let head = self.provider.get_block_number().await
+            .map_err(|e| IndexerError::ProviderError(e.to_string()))?
+            .as_u64()
+            .saturating_sub(self.config.confirmation_blocks);
+
+        let done = self.storage.completed_backfill_ranges(chain_id).await?;
+        let ranges = plan_ranges(self.config.start_block, head, self.settings.range_size, &done);
+        let total = (head.saturating_sub(self.config.start_block) + 1).div_ceil(self.settings.range_size.max(1));
+        tracing::info!(
+            "Backfilling chain {} from block {} to {}: {} of {} ranges left",
+            chain_id,
+            self.config.start_block,
+            head,
+            ranges.len(),
+            total
+        );
+
+        let mut report = BackfillReport {
+            chain_id,
+            ranges_skipped: total.saturating_sub(ranges.len() as u64),
+            ..Default::default()
+        };
+        let mut completed = stream::iter(ranges)
+            .map(|range| self.index_range(range))
+            .buffer_unordered(self.settings.concurrency.max(1));
+        while let Some((range, events)) = completed.try_next().await? {
+            report.ranges_completed += 1;
+            report.events_indexed += events;
+            tracing::debug!("Backfilled blocks {} to {} on chain {}", range.from, range.to, chain_id);
+        }
+
+        // Let live indexing continue from the end of the gapless backfill
+        let done = self.storage.completed_backfill_ranges(chain_id).await?;
+        report.indexed_to = contiguous_end(self.config.start_block, &done);
+        if let Some(block) = report.indexed_to {
+            self.storage.advance_indexed_block(chain_id, block).await?;
+        }
+        Ok(report)
+    }
+
+    // Fill holes in the chain's block coverage between the first covered
+    // block and the indexed block. Returns the gaps that were filled.
+    pub async fn reconcile(&self) -> Result<Vec<BlockRange>> {
+        let chain_id = self.config.chain_id;
+        let covered = merge_ranges(&self.storage.coverage(chain_id).await?);
+        self.storage.replace_coverage(chain_id, &covered).await?;
+
+        let (Some(first), Some(state)) = (covered.first(), self.storage.get_chain_state(chain_id).await?) else {
+            return Ok(Vec::new());
+        };
+        let gaps = find_gaps(first.from, state.indexed_block, &covered);
+        for gap in &gaps {
+            tracing::warn!("Filling blocks {} to {} missing on chain {}", gap.from, gap.to, chain_id);
+            for range in plan_ranges(gap.from, gap.to, self.settings.range_size, &[]) {
+                self.index_range(range).await?;
+            }
+        }
+        Ok(gaps)
+    }
+
+    // Index one contract from its start block, or from where an earlier run
+    // stopped, to the confirmed head. Its events are added without touching
+    // those of other contracts, and the backfill stops if it is unwatched.
+    pub async fn backfill_contract(&self, contract: &WatchedContract) -> Result<u64> {
+        self.limiter.acquire().await;
+        let head = self.provider.get_block_number().await
+            .map_err(|e| IndexerError::ProviderError(e.to_string()))?
+            .as_u64()
+            .saturating_sub(self.config.confirmation_blocks);
+
+        let from = self.storage.contract_backfilled_to(contract.chain_id, contract.address).await?
+            .map_or(contract.start_block, |block| block + 1)
+            .max(contract.start_block);
+
+        let mut events = 0;
+        for range in plan_ranges(from, head, self.settings.range_size, &[]) {
+            if !self.watch_list.contains(contract.chain_id, contract.address) {
+                break;
+            }
+            let processed = self.fetch_range(build_filters(std::slice::from_ref(contract), range)).await?;
+            events += processed.len() as u64;
+            self.storage.store_contract_range(contract, range, &processed).await?;
+        }
+        Ok(events)
+    }
+
+    async fn index_range(&self, range: BlockRange) -> Result<(BlockRange, u64)> {
+        let processed = self.fetch_range(self.watch_list.filters(self.config.chain_id, range)).await?;
+
+        // Events and checkpoint land together, so an interrupted range is
+        // simply redone
+        let events = processed.len() as u64;
+        self.storage.complete_backfill_range(self.config.chain_id, range, &processed).await?;
+        Ok((range, events))
+    }
+
+    async fn fetch_range(&self, filters: Vec<Filter>) -> Result<Vec<ProcessedLog>> {
+        let mut logs = Vec::new();
+        for filter in &filters {
+            self.limiter.acquire().await;
+            logs.extend(self.provider.get_logs(filter).await
+                .map_err(|e| IndexerError::ProviderError(e.to_string()))?);
+        }
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+        // One block lookup per block with logs, for event timestamps
+        let mut timestamps: BTreeMap<u64, U256> = BTreeMap::new();
+        let mut processed = Vec::new();
+        for log in &logs {
+            let Some(block_number) = log.block_number.map(|number| number.as_u64()) else { continue };
+            if !timestamps.contains_key(&block_number) {
+                self.limiter.acquire().await;
+                let block = self.provider.get_block(block_number).await
+                    .map_err(|e| IndexerError::ProviderError(e.to_string()))?
+                    .ok_or_else(|| IndexerError::DataNotFound(format!("Block {} not found", block_number)))?;
+                timestamps.insert(block_number, block.timestamp);
+            }
+
+            match self.event_processor.process_log(self.config.chain_id, log, timestamps[&block_number]).await {
+                Ok(Some(event)) => processed.push(event),
+                Ok(None) => {}
+                Err(e @ IndexerError::MalformedLog { .. }) => tracing::warn!("Rejected log: {}", e),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(processed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(from: u64, to: u64) -> BlockRange {
+        BlockRange { from, to }
+    }
+
+    #[test]
+    fn test_plan_skips_checkpointed_ranges() {
+        let all = plan_ranges(100, 349, 100, &[]);
+        assert_eq!(all, vec![range(100, 199), range(200, 299), range(300, 349)]);
+
+        // A partial last range from an earlier, shorter run is redone in full
+        let left = plan_ranges(100, 420, 100, &[range(100, 199), range(300, 349)]);
+        assert_eq!(left, vec![range(200, 299), range(300, 399), range(400, 420)]);
+    }
+
+    #[test]
+    fn test_find_gaps_between_merged_coverage() {
+        let covered = [range(150, 199), range(100, 120), range(121, 140), range(300, 310)];
+        assert_eq!(merge_ranges(&covered), vec![range(100, 140), range(150, 199), range(300, 310)]);
+        assert_eq!(find_gaps(100, 320, &covered), vec![range(141, 149), range(200, 299), range(311, 320)]);
+        assert_eq!(find_gaps(100, 140, &covered), Vec::<BlockRange>::new());
+        assert_eq!(find_gaps(90, 95, &covered), vec![range(90, 95)]);
+    }
+
+    #[test]
+    fn test_contiguous_end_stops_at_first_gap() {
+        assert_eq!(contiguous_end(100, &[]), None);
+        assert_eq!(contiguous_end(100, &[range(200, 299), range(100, 199), range(400, 499)]), Some(299));
+        assert_eq!(contiguous_end(100, &[range(200, 299)]), None);
+    }
+}
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, net::SocketAddr};
+
+use crate::{
+    error::{IndexerError, Result},
+    ChainIndexerConfig,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexerConfig {
+    pub database_url: String,
+    pub chains: Vec<ChainIndexerConfig>,
+    #[serde(default)]
+    pub backfill: BackfillConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub rollups: RollupConfig,
+    #[serde(default)]
+    pub bridge_sla: BridgeSlaConfig,
+    // Token metadata and USD pricing; amounts stay unpriced when unset
+    #[serde(default)]
+    pub enrichment: Option<EnrichmentConfig>,
+    // File of extra contracts to watch, applied as it changes
+    #[serde(default)]
+    pub watch_list: Option<WatchListConfig>,
+    // Pruning of old events to cold storage; nothing is pruned when unset
+    #[serde(default)]
+    pub retention: Option<RetentionConfig>,
+}
+
+// Historical backfill from each chain's `start_block`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackfillConfig {
+    // Blocks per checkpointed range
+    pub range_size: u64,
+    // Ranges fetched concurrently per chain
+    pub concurrency: usize,
+    // RPC requests per second per chain
+    pub requests_per_second: u32,
+}
+
+impl Default for BackfillConfig {
+    fn default() -> Self {
+        Self {
+            range_size: 2_000,
+            concurrency: 4,
+            requests_per_second: 20,
+        }
+    }
+}
+
+// Prometheus endpoint and lag alerting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    // Address to serve `/metrics` on; no endpoint when unset
+    pub listen_addr: Option<SocketAddr>,
+    // Blocks behind the confirmed head before a chain is alerted on
+    pub lag_alert_blocks: u64,
+    // Per-chain overrides of `lag_alert_blocks`
+    pub chain_lag_alert_blocks: HashMap<u64, u64>,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            listen_addr: None,
+            lag_alert_blocks: 100,
+            chain_lag_alert_blocks: HashMap::new(),
+        }
+    }
+}
+
+impl MetricsConfig {
+    pub fn lag_alert_threshold(&self, chain_id: u64) -> u64 {
+        self.chain_lag_alert_blocks.get(&chain_id).copied().unwrap_or(self.lag_alert_blocks)
+    }
+}
+
+// Hourly and daily analytics rollups
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RollupConfig {
+    pub interval_secs: u64,
+    // Fee rate used to estimate pool fees, which swap events do not carry
+    pub default_fee_bps: u32,
+    // Per-pool overrides, keyed by decimal pool id
+    pub pool_fee_bps: HashMap<String, u32>,
+}
+
+impl Default for RollupConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 300,
+            default_fee_bps: 30,
+            pool_fee_bps: HashMap::new(),
+        }
+    }
+}
+
+impl RollupConfig {
+    pub fn fee_bps(&self, pool_id: &str) -> u32 {
+        self.pool_fee_bps.get(pool_id).copied().unwrap_or(self.default_fee_bps)
+    }
+}
+
+// How long a bridge transfer may stay pending before it is stuck
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BridgeSlaConfig {
+    pub sla_secs: u64,
+    // Per-destination-chain overrides of `sla_secs`
+    pub chain_sla_secs: HashMap<u64, u64>,
+    pub interval_secs: u64,
+}
+
+impl Default for BridgeSlaConfig {
+    fn default() -> Self {
+        Self {
+            sla_secs: 1_800,
+            chain_sla_secs: HashMap::new(),
+            interval_secs: 60,
+        }
+    }
+}
+
+impl BridgeSlaConfig {
+    pub fn sla_secs(&self, destination_chain_id: u64) -> u64 {
+        self.chain_sla_secs.get(&destination_chain_id).copied().unwrap_or(self.sla_secs)
+    }
+
+    // The shortest SLA of any chain
+    pub fn min_sla_secs(&self) -> u64 {
+        self.chain_sla_secs.values().copied().fold(self.sla_secs, u64::min)
+    }
+}
+
+// A JSON array of watched contracts, polled for changes. Contracts added
+// to it are backfilled from their start block; removed ones stop indexing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchListConfig {
+    pub path: String,
+    #[serde(default = "default_watch_list_poll_secs")]
+    pub poll_secs: u64,
+}
+
+fn default_watch_list_poll_secs() -> u64 {
+    10
+}
+
+// Token metadata comes from the chain, USD prices from a
+// CoinGecko-compatible API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichmentConfig {
+    #[serde(default = "default_price_api_url")]
+    pub price_api_url: String,
+    #[serde(default)]
+    pub price_api_key: Option<String>,
+    // Price API platform per chain, e.g. 1 => "ethereum". Chains without
+    // one get token metadata but no USD values.
+    #[serde(default)]
+    pub platforms: HashMap<u64, String>,
+    #[serde(default = "default_enrichment_interval_secs")]
+    pub interval_secs: u64,
+    // Prices older than this at event time are not applied
+    #[serde(default = "default_max_price_age_secs")]
+    pub max_price_age_secs: u64,
+    // Rows enriched per pass
+    #[serde(default = "default_enrichment_batch_size")]
+    pub batch_size: u64,
+}
+
+fn default_price_api_url() -> String {
+    "https://api.coingecko.com/api/v3".to_string()
+}
+
+fn default_enrichment_interval_secs() -> u64 {
+    60
+}
+
+fn default_max_price_age_secs() -> u64 {
+    3_600
+}
+
+fn default_enrichment_batch_size() -> u64 {
+    1_000
+}
+
+// Events older than `hot_days` are exported to `archive_url` as Parquet and
+// deleted, leaving only their daily rollups in Postgres
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    // `s3://bucket/prefix` or `file:///path`
+    pub archive_url: String,
+    #[serde(default = "default_hot_days")]
+    pub hot_days: u32,
+    // Events exported per pass
+    #[serde(default = "default_prune_batch_size")]
+    pub batch_size: u64,
+    #[serde(default = "default_prune_interval_secs")]
+    pub interval_secs: u64,
+}
+
+fn default_hot_days() -> u32 {
+    90
+}
+
+fn default_prune_batch_size() -> u64 {
+    10_000
+}
+
+fn default_prune_interval_secs() -> u64 {
+    3_600
+}
+
+impl IndexerConfig {
+    pub fn from_json_file(path: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| IndexerError::Config(format!("Failed to read {}: {}", path, e)))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub fn chain(&self, chain_id: u64) -> Option<&ChainIndexerConfig> {
+        self.chains.iter().find(|chain| chain.chain_id == chain_id)
+    }
+}
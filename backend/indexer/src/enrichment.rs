@@ -0,0 +1,239 @@
+use chrono::Utc;
+use ethers::{
+    contract::abigen,
+    providers::{Http, Provider},
+    types::{Address, U256},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+use tokio::time::{interval, Duration};
+
+use crate::{
+    config::EnrichmentConfig,
+    error::{IndexerError, Result},
+    storage::IndexerStorage,
+    ChainIndexerConfig,
+};
+
+abigen!(
+    Erc20Metadata,
+    r#"[
+        function symbol() external view returns (string)
+        function decimals() external view returns (uint8)
+    ]"#
+);
+
+abigen!(
+    OrbitalPoolReader,
+    r#"[
+        function getPoolReserves(uint256 poolId) external view returns (address[], uint256[])
+    ]"#
+);
+
+// Addresses per price request, to stay within URL length limits
+const PRICE_REQUEST_CHUNK: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    pub chain_id: u64,
+    pub address: Address,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+// `amount` in whole tokens
+pub fn to_units(amount: U256, decimals: u8) -> f64 {
+    // Exact for amounts that fit in u128, which covers any realistic balance
+    let raw = if amount.bits() <= 128 {
+        amount.as_u128() as f64
+    } else {
+        amount.to_string().parse().unwrap_or(f64::MAX)
+    };
+    raw / 10f64.powi(i32::from(decimals))
+}
+
+pub fn usd_value(amount: U256, decimals: u8, price_usd: f64) -> f64 {
+    to_units(amount, decimals) * price_usd
+}
+
+// Reads `{"0xtoken": {"usd": 1.0}}` as returned by `/simple/token_price`
+pub fn parse_prices(body: &HashMap<String, HashMap<String, f64>>) -> Vec<(Address, f64)> {
+    let mut prices: Vec<(Address, f64)> = body
+        .iter()
+        .filter_map(|(token, quotes)| Some((Address::from_str(token).ok()?, *quotes.get("usd")?)))
+        .collect();
+    prices.sort_by_key(|(address, _)| *address);
+    prices
+}
+
+// Resolves token metadata on chain, records USD prices and fills in the
+// USD value of swaps and bridge transfers at the time they happened
+pub struct Enricher {
+    config: EnrichmentConfig,
+    storage: Arc<IndexerStorage>,
+    providers: HashMap<u64, Arc<Provider<Http>>>,
+    pool_contracts: HashMap<u64, Address>,
+    client: reqwest::Client,
+}
+
+impl Enricher {
+    pub fn new(config: EnrichmentConfig, chains: &[ChainIndexerConfig], storage: Arc<IndexerStorage>) -> Result<Self> {
+        let mut providers = HashMap::new();
+        let mut pool_contracts = HashMap::new();
+        for chain in chains.iter().filter(|chain| chain.enabled) {
+            let provider = Provider::<Http>::try_from(chain.rpc_url.as_str())
+                .map_err(|e| IndexerError::ProviderError(e.to_string()))?;
+            providers.insert(chain.chain_id, Arc::new(provider));
+            pool_contracts.insert(chain.chain_id, chain.contracts.orbital_amm_contract);
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| IndexerError::Config(format!("Failed to build price client: {}", e)))?;
+
+        Ok(Self { config, storage, providers, pool_contracts, client })
+    }
+
+    pub async fn run(self) {
+        let mut ticks = interval(Duration::from_secs(self.config.interval_secs.max(1)));
+        loop {
+            ticks.tick().await;
+            if let Err(e) = self.enrich_once().await {
+                tracing::warn!("Enrichment failed: {}", e);
+            }
+        }
+    }
+
+    pub async fn enrich_once(&self) -> Result<()> {
+        self.resolve_pools().await?;
+        self.resolve_tokens().await?;
+        self.refresh_prices().await?;
+
+        let limit = self.config.batch_size;
+        let max_age = self.config.max_price_age_secs;
+        let swaps = self.storage.enrich_swaps(max_age, limit).await?;
+        let transfers = self.storage.enrich_bridge_transfers(max_age, limit).await?;
+        if swaps + transfers > 0 {
+            tracing::debug!("Enriched {} swaps and {} bridge transfers", swaps, transfers);
+        }
+        Ok(())
+    }
+
+    // Swap events name tokens by their index in the pool
+    async fn resolve_pools(&self) -> Result<()> {
+        for (chain_id, pool_id) in self.storage.unresolved_pools(self.config.batch_size).await? {
+            let (Some(provider), Some(&contract)) = (self.providers.get(&chain_id), self.pool_contracts.get(&chain_id))
+            else {
+                continue;
+            };
+            let Ok(id) = U256::from_dec_str(&pool_id) else { continue };
+
+            match OrbitalPoolReader::new(contract, provider.clone()).get_pool_reserves(id).call().await {
+                Ok((tokens, _)) => self.storage.save_pool_tokens(chain_id, &pool_id, &tokens).await?,
+                Err(e) => tracing::debug!("Failed to read tokens of pool {} on chain {}: {}", pool_id, chain_id, e),
+            }
+        }
+        Ok(())
+    }
+
+    async fn resolve_tokens(&self) -> Result<()> {
+        for (chain_id, address) in self.storage.unresolved_tokens(self.config.batch_size).await? {
+            let Some(provider) = self.providers.get(&chain_id) else { continue };
+            let token = Erc20Metadata::new(address, provider.clone());
+
+            // Tokens without `decimals` cannot be normalized and are retried
+            // on the next pass
+            let decimals = match token.decimals().call().await {
+                Ok(decimals) => decimals,
+                Err(e) => {
+                    tracing::debug!("Failed to read decimals of {:#x} on chain {}: {}", address, chain_id, e);
+                    continue;
+                }
+            };
+            // Some older tokens return `bytes32` symbols
+            let symbol = token.symbol().call().await.unwrap_or_default();
+
+            self.storage
+                .save_token_metadata(&TokenMetadata { chain_id, address, symbol, decimals })
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn refresh_prices(&self) -> Result<()> {
+        let observed_at = Utc::now();
+        let mut by_chain: HashMap<u64, Vec<Address>> = HashMap::new();
+        for (chain_id, address) in self.storage.known_tokens().await? {
+            by_chain.entry(chain_id).or_default().push(address);
+        }
+
+        for (chain_id, tokens) in by_chain {
+            // Chains without a price platform get metadata but no USD values
+            let Some(platform) = self.config.platforms.get(&chain_id) else { continue };
+            for chunk in tokens.chunks(PRICE_REQUEST_CHUNK) {
+                match self.fetch_prices(platform, chunk).await {
+                    Ok(prices) => self.storage.record_token_prices(chain_id, observed_at, &prices).await?,
+                    Err(e) => tracing::warn!("Failed to fetch prices for chain {}: {}", chain_id, e),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn fetch_prices(&self, platform: &str, tokens: &[Address]) -> Result<Vec<(Address, f64)>> {
+        let addresses: Vec<String> = tokens.iter().map(|token| format!("{:#x}", token)).collect();
+        let url = format!("{}/simple/token_price/{}", self.config.price_api_url.trim_end_matches('/'), platform);
+
+        let mut request = self
+            .client
+            .get(&url)
+            .query(&[("contract_addresses", addresses.join(",")), ("vs_currencies", "usd".to_string())]);
+        if let Some(key) = &self.config.price_api_key {
+            request = request.header("x-cg-pro-api-key", key);
+        }
+
+        let body: HashMap<String, HashMap<String, f64>> = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| IndexerError::ProviderError(format!("Price request to {} failed: {}", url, e)))?
+            .json()
+            .await
+            .map_err(|e| IndexerError::Decoding(format!("Invalid price response from {}: {}", url, e)))?;
+
+        Ok(parse_prices(&body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amounts_are_normalized_by_decimals() {
+        let one_and_a_half = U256::from(1_500_000_000_000_000_000u128);
+        assert_eq!(to_units(one_and_a_half, 18), 1.5);
+        assert_eq!(to_units(U256::from(2_500_000u64), 6), 2.5);
+        assert_eq!(usd_value(U256::from(2_500_000u64), 6, 2.0), 5.0);
+        assert!(to_units(U256::MAX, 18) > 1e58);
+    }
+
+    #[test]
+    fn test_parse_prices_skips_bad_entries() {
+        let body: HashMap<String, HashMap<String, f64>> = serde_json::from_str(
+            r#"{
+                "0x0000000000000000000000000000000000000002": {"usd": 2.5},
+                "0x0000000000000000000000000000000000000001": {"usd": 1.0},
+                "0x0000000000000000000000000000000000000003": {"eur": 0.9},
+                "not-an-address": {"usd": 7.0}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            parse_prices(&body),
+            vec![(Address::from_low_u64_be(1), 1.0), (Address::from_low_u64_be(2), 2.5)]
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, IndexerError>;
+
+#[derive(Error, Debug)]
+pub enum IndexerError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Provider error: {0}")]
+    ProviderError(String),
+
+    #[error("Data not found: {0}")]
+    DataNotFound(String),
+
+    #[error("Event decoding error: {0}")]
+    Decoding(String),
+
+    #[error("Malformed log on chain {chain_id} in tx {transaction_hash:?} (log index {log_index:?}): {reason}")]
+    MalformedLog {
+        chain_id: u64,
+        transaction_hash: ethers::types::H256,
+        log_index: Option<u64>,
+        reason: String,
+    },
+
+    #[error("Reorg on chain {chain_id} deeper than {max_depth} blocks at block {block_number}")]
+    ReorgTooDeep {
+        chain_id: u64,
+        block_number: u64,
+        max_depth: u64,
+    },
+
+    #[error("Archive error: {0}")]
+    Archive(String),
+
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
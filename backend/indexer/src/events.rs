@@ -0,0 +1,240 @@
+use ethers::{
+    abi::RawLog,
+    contract::{abigen, EthEvent},
+    types::{Log, H256, U256},
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::{
+    error::{IndexerError, Result},
+    storage::IndexerStorage,
+    IndexedEvent,
+};
+
+// Decoders for the events the indexer stores. Event definitions follow what
+// the deployed contracts emit.
+abigen!(
+    IntentsContract,
+    r#"[
+        event IntentCreated(bytes32 indexed intentId, address indexed user, uint256 timestamp)
+        event IntentMatched(bytes32 indexed intentId, address indexed solver, uint256 timestamp)
+        event IntentExecuted(bytes32 indexed intentId, address indexed solver, bool success)
+        event SolverSlashed(address indexed solver, uint256 amount, bytes32 intentId)
+    ]"#,
+    derives(serde::Serialize, serde::Deserialize)
+);
+
+abigen!(
+    OrbitalAmmContract,
+    r#"[
+        event ToroidalSwap(uint256 indexed poolId, address indexed trader, uint256 tokenIn, uint256 tokenOut, uint256 amountIn, uint256 amountOut)
+        event LiquidityAdded(uint256 indexed poolId, address indexed provider, uint256 amount0, uint256 amount1)
+    ]"#,
+    derives(serde::Serialize, serde::Deserialize)
+);
+
+abigen!(
+    BridgeContract,
+    r#"[
+        event BridgeDeposit(bytes32 indexed messageHash, address indexed sender, address recipient, address token, uint256 amount, uint256 destChainId, uint256 nonce)
+        event BridgeWithdrawal(bytes32 indexed messageHash, address indexed recipient, address token, uint256 amount, uint256 sourceChainId, uint256 nonce)
+    ]"#,
+    derives(serde::Serialize, serde::Deserialize)
+);
+
+// A log decoded into one of the indexed event types
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event")]
+pub enum DecodedEvent {
+    IntentCreated(IntentCreatedFilter),
+    IntentMatched(IntentMatchedFilter),
+    IntentExecuted(IntentExecutedFilter),
+    SolverSlashed(SolverSlashedFilter),
+    ToroidalSwap(ToroidalSwapFilter),
+    LiquidityAdded(LiquidityAddedFilter),
+    BridgeDeposit(BridgeDepositFilter),
+    BridgeWithdrawal(BridgeWithdrawalFilter),
+}
+
+impl DecodedEvent {
+    // `None` for events the indexer does not track; an error when the topic
+    // is tracked but the log does not match its ABI
+    pub fn decode(log: &Log) -> std::result::Result<Option<Self>, String> {
+        let Some(&topic) = log.topics.first() else { return Ok(None) };
+        let raw = RawLog::from(log.clone());
+
+        let decoded = if topic == IntentCreatedFilter::signature() {
+            Self::IntentCreated(decode(&raw)?)
+        } else if topic == IntentMatchedFilter::signature() {
+            Self::IntentMatched(decode(&raw)?)
+        } else if topic == IntentExecutedFilter::signature() {
+            Self::IntentExecuted(decode(&raw)?)
+        } else if topic == SolverSlashedFilter::signature() {
+            Self::SolverSlashed(decode(&raw)?)
+        } else if topic == ToroidalSwapFilter::signature() {
+            Self::ToroidalSwap(decode(&raw)?)
+        } else if topic == LiquidityAddedFilter::signature() {
+            Self::LiquidityAdded(decode(&raw)?)
+        } else if topic == BridgeDepositFilter::signature() {
+            Self::BridgeDeposit(decode(&raw)?)
+        } else if topic == BridgeWithdrawalFilter::signature() {
+            Self::BridgeWithdrawal(decode(&raw)?)
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(decoded))
+    }
+
+    // Topic and name of every tracked event
+    pub fn tracked() -> [(H256, &'static str); 8] {
+        [
+            (IntentCreatedFilter::signature(), "IntentCreated"),
+            (IntentMatchedFilter::signature(), "IntentMatched"),
+            (IntentExecutedFilter::signature(), "IntentExecuted"),
+            (SolverSlashedFilter::signature(), "SolverSlashed"),
+            (ToroidalSwapFilter::signature(), "ToroidalSwap"),
+            (LiquidityAddedFilter::signature(), "LiquidityAdded"),
+            (BridgeDepositFilter::signature(), "BridgeDeposit"),
+            (BridgeWithdrawalFilter::signature(), "BridgeWithdrawal"),
+        ]
+    }
+
+    // Name of the tracked event with topic `signature`
+    pub fn name_of(signature: H256) -> Option<&'static str> {
+        Self::tracked()
+            .into_iter()
+            .find(|(topic, _)| *topic == signature)
+            .map(|(_, name)| name)
+    }
+
+    // Topic of the tracked event called `name`
+    pub fn signature_of(name: &str) -> Option<H256> {
+        Self::tracked()
+            .into_iter()
+            .find(|(_, tracked)| *tracked == name)
+            .map(|(topic, _)| topic)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::IntentCreated(_) => "IntentCreated",
+            Self::IntentMatched(_) => "IntentMatched",
+            Self::IntentExecuted(_) => "IntentExecuted",
+            Self::SolverSlashed(_) => "SolverSlashed",
+            Self::ToroidalSwap(_) => "ToroidalSwap",
+            Self::LiquidityAdded(_) => "LiquidityAdded",
+            Self::BridgeDeposit(_) => "BridgeDeposit",
+            Self::BridgeWithdrawal(_) => "BridgeWithdrawal",
+        }
+    }
+}
+
+fn decode<E: EthEvent>(raw: &RawLog) -> std::result::Result<E, String> {
+    E::decode_log(raw).map_err(|e| format!("{}: {}", E::name(), e))
+}
+
+// An indexed event with its typed payload
+#[derive(Debug, Clone)]
+pub struct ProcessedLog {
+    pub event: IndexedEvent,
+    pub decoded: DecodedEvent,
+}
+
+// Turns raw logs into indexed events
+pub struct EventProcessor {
+    storage: Arc<IndexerStorage>,
+}
+
+impl EventProcessor {
+    pub fn new(storage: Arc<IndexerStorage>) -> Self {
+        Self { storage }
+    }
+
+    pub fn storage(&self) -> &Arc<IndexerStorage> {
+        &self.storage
+    }
+
+    // `None` for logs of events the indexer does not track
+    pub async fn process_log(&self, chain_id: u64, log: &Log, block_timestamp: U256) -> Result<Option<ProcessedLog>> {
+        let malformed = |reason: String| IndexerError::MalformedLog {
+            chain_id,
+            transaction_hash: log.transaction_hash.unwrap_or_default(),
+            log_index: log.log_index.map(|index| index.as_u64()),
+            reason,
+        };
+        let field = |name: &str| malformed(format!("log is missing {}", name));
+
+        let Some(decoded) = DecodedEvent::decode(log).map_err(malformed)? else {
+            return Ok(None);
+        };
+        let block_number = log.block_number.ok_or_else(|| field("block_number"))?.as_u64();
+        let transaction_hash: H256 = log.transaction_hash.ok_or_else(|| field("transaction_hash"))?;
+        let transaction_index = log.transaction_index.ok_or_else(|| field("transaction_index"))?.as_u64();
+        let log_index = log.log_index.ok_or_else(|| field("log_index"))?.as_u64();
+
+        let timestamp = chrono::DateTime::from_timestamp(block_timestamp.low_u64() as i64, 0)
+            .unwrap_or_else(chrono::Utc::now);
+
+        let event = IndexedEvent {
+            id: uuid::Uuid::new_v4(),
+            chain_id,
+            block_number,
+            transaction_hash,
+            transaction_index,
+            log_index,
+            event_type: decoded.name().to_string(),
+            contract_address: log.address,
+            event_data: serde_json::to_value(&decoded)?,
+            timestamp,
+            processed: false,
+        };
+        Ok(Some(ProcessedLog { event, decoded }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        abi::{encode, Token},
+        types::{Address, Bytes},
+    };
+
+    fn swap_log(data: Vec<u8>) -> Log {
+        Log {
+            topics: vec![
+                ToroidalSwapFilter::signature(),
+                H256::from_low_u64_be(7),
+                H256::from(Address::repeat_byte(0xaa)),
+            ],
+            data: Bytes::from(data),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_decodes_typed_swap() {
+        let data = encode(&[
+            Token::Uint(0.into()),
+            Token::Uint(1.into()),
+            Token::Uint(1_000.into()),
+            Token::Uint(990.into()),
+        ]);
+        let Some(DecodedEvent::ToroidalSwap(swap)) = DecodedEvent::decode(&swap_log(data)).unwrap() else {
+            panic!("expected a swap");
+        };
+        assert_eq!(swap.pool_id, U256::from(7));
+        assert_eq!(swap.trader, Address::repeat_byte(0xaa));
+        assert_eq!((swap.amount_in, swap.amount_out), (U256::from(1_000), U256::from(990)));
+    }
+
+    #[test]
+    fn test_rejects_malformed_and_skips_unknown() {
+        assert!(DecodedEvent::decode(&swap_log(vec![0u8; 31])).is_err());
+
+        let mut unknown = swap_log(Vec::new());
+        unknown.topics[0] = H256::repeat_byte(1);
+        assert_eq!(DecodedEvent::decode(&unknown), Ok(None));
+    }
+}
@@ -0,0 +1,188 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::{
+    sync::{broadcast, mpsc},
+    task::JoinHandle,
+    time::{sleep, timeout, Duration},
+};
+
+use crate::{
+    error::{IndexerError, Result},
+    heads::Backoff,
+    storage::IndexerStorage,
+    IndexedEvent, StreamEvent,
+};
+
+const BATCH_SIZE: u64 = 500;
+// Stored events are re-checked this often even without a wake-up
+const IDLE_POLL: Duration = Duration::from_secs(5);
+
+// A downstream consumer of indexed events. Each handler reads from its own
+// stored offset, so a slow one falls behind without holding up indexing or
+// other handlers. The offset moves past an event only once `handle`
+// succeeds; failures are retried until they do.
+#[async_trait]
+pub trait EventHandler: Send + Sync {
+    // Stable name the handler's offset is stored under
+    fn name(&self) -> &str;
+
+    async fn handle(&self, event: &IndexedEvent) -> Result<()>;
+}
+
+// Forwards events to a channel, e.g. for a solver feed
+pub struct ChannelHandler {
+    name: String,
+    sender: mpsc::Sender<IndexedEvent>,
+}
+
+impl ChannelHandler {
+    pub fn new(name: impl Into<String>, sender: mpsc::Sender<IndexedEvent>) -> Self {
+        Self { name: name.into(), sender }
+    }
+}
+
+#[async_trait]
+impl EventHandler for ChannelHandler {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn handle(&self, event: &IndexedEvent) -> Result<()> {
+        self.sender.send(event.clone()).await
+            .map_err(|_| IndexerError::Internal(format!("Handler {} channel closed", self.name)))
+    }
+}
+
+// Registered handlers, each driven by its own task
+#[derive(Default)]
+pub struct HandlerPipeline {
+    handlers: Vec<Arc<dyn EventHandler>>,
+}
+
+impl HandlerPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, handler: Arc<dyn EventHandler>) -> Result<()> {
+        if self.handlers.iter().any(|existing| existing.name() == handler.name()) {
+            return Err(IndexerError::Config(format!("Event handler {} is already registered", handler.name())));
+        }
+        self.handlers.push(handler);
+        Ok(())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.handlers.iter().map(|handler| handler.name()).collect()
+    }
+
+    // One task per handler; new events on `events` wake them early
+    pub fn spawn(
+        &self,
+        storage: Arc<IndexerStorage>,
+        events: &broadcast::Sender<StreamEvent>,
+    ) -> Vec<JoinHandle<()>> {
+        self.handlers
+            .iter()
+            .map(|handler| {
+                let dispatcher = Dispatcher {
+                    handler: handler.clone(),
+                    storage: storage.clone(),
+                    wake: events.subscribe(),
+                };
+                tokio::spawn(dispatcher.run())
+            })
+            .collect()
+    }
+}
+
+struct Dispatcher {
+    handler: Arc<dyn EventHandler>,
+    storage: Arc<IndexerStorage>,
+    wake: broadcast::Receiver<StreamEvent>,
+}
+
+impl Dispatcher {
+    async fn run(mut self) {
+        let name = self.handler.name().to_string();
+        tracing::info!("Starting event handler {}", name);
+
+        loop {
+            match self.deliver_batch(&name).await {
+                Ok(0) => {
+                    // Caught up; lagging or a closed channel just means look again
+                    let _ = timeout(IDLE_POLL, self.wake.recv()).await;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!("Event handler {} failed to read events: {}", name, e);
+                    sleep(IDLE_POLL).await;
+                }
+            }
+        }
+    }
+
+    // Deliver the next batch past the stored offset; returns how many
+    async fn deliver_batch(&self, name: &str) -> Result<usize> {
+        let offset = self.storage.handler_offset(name).await?;
+        let batch = self.storage.events_after(offset, BATCH_SIZE).await?;
+
+        for (seq, event) in &batch {
+            let mut backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(30));
+            while let Err(e) = self.handler.handle(event).await {
+                let delay = backoff.next_delay();
+                tracing::warn!("Event handler {} failed on event {}, retrying in {:?}: {}", name, event.id, delay, e);
+                sleep(delay).await;
+            }
+            self.storage.save_handler_offset(name, *seq).await?;
+        }
+        Ok(batch.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Address, H256};
+
+    fn event() -> IndexedEvent {
+        IndexedEvent {
+            id: uuid::Uuid::new_v4(),
+            chain_id: 1,
+            block_number: 10,
+            transaction_hash: H256::zero(),
+            transaction_index: 0,
+            log_index: 0,
+            event_type: "IntentCreated".to_string(),
+            contract_address: Address::zero(),
+            event_data: serde_json::Value::Null,
+            timestamp: chrono::Utc::now(),
+            processed: false,
+        }
+    }
+
+    #[test]
+    fn test_register_rejects_duplicate_names() {
+        let (sender, _receiver) = mpsc::channel(1);
+        let mut pipeline = HandlerPipeline::new();
+        pipeline.register(Arc::new(ChannelHandler::new("solver-feed", sender.clone()))).unwrap();
+        pipeline.register(Arc::new(ChannelHandler::new("analytics", sender.clone()))).unwrap();
+
+        let duplicate = pipeline.register(Arc::new(ChannelHandler::new("solver-feed", sender)));
+        assert!(matches!(duplicate, Err(IndexerError::Config(_))));
+        assert_eq!(pipeline.names(), vec!["solver-feed", "analytics"]);
+    }
+
+    #[tokio::test]
+    async fn test_channel_handler_forwards_until_closed() {
+        let (sender, mut receiver) = mpsc::channel(1);
+        let handler = ChannelHandler::new("feed", sender);
+
+        let indexed = event();
+        handler.handle(&indexed).await.unwrap();
+        assert_eq!(receiver.recv().await.map(|e| e.id), Some(indexed.id));
+
+        drop(receiver);
+        assert!(handler.handle(&indexed).await.is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use ethers::providers::{Http, Middleware, Provider, StreamExt, Ws};
+use std::{ops::ControlFlow, sync::Arc};
+use tokio::{
+    sync::mpsc,
+    time::{interval, sleep, timeout, Duration, Instant, MissedTickBehavior},
+};
+
+use crate::metrics::IndexerMetrics;
+
+// A WebSocket subscription that announces nothing for this long is treated
+// as degraded and dropped in favour of polling
+const STALE_AFTER: Duration = Duration::from_secs(30);
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+// Larger jumps are left to historical sync rather than replayed here
+const MAX_CATCH_UP: u64 = 256;
+
+// Turns announced heads into the block numbers to index, in order, filling
+// in blocks the source skipped
+#[derive(Debug, Clone, Default)]
+pub struct HeadTracker {
+    last: Option<u64>,
+}
+
+impl HeadTracker {
+    pub fn new(last: Option<u64>) -> Self {
+        Self { last }
+    }
+
+    pub fn last(&self) -> Option<u64> {
+        self.last
+    }
+
+    // Blocks after the last announced head up to `head`. Heads at or below
+    // the last one yield nothing: a reorg there is caught when the next
+    // block's parent hash disagrees.
+    pub fn advance(&mut self, head: u64) -> std::ops::RangeInclusive<u64> {
+        let from = match self.last {
+            Some(last) if head <= last => return 1..=0,
+            Some(last) => (last + 1).max(head.saturating_sub(MAX_CATCH_UP - 1)),
+            None => head,
+        };
+        self.last = Some(head);
+        from..=head
+    }
+}
+
+// Doubling delay between WebSocket reconnects
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    next: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    pub fn new(min: Duration, max: Duration) -> Self {
+        Self { next: min, min, max }
+    }
+
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.next;
+        self.next = (self.next * 2).min(self.max);
+        delay
+    }
+
+    pub fn reset(&mut self) {
+        self.next = self.min;
+    }
+
+    pub fn max_delay(&self) -> Duration {
+        self.max
+    }
+}
+
+// Follows a chain's head over WebSocket, polling over HTTP whenever the
+// subscription is down, and sends every block number to index
+pub struct HeadFollower {
+    chain_id: u64,
+    ws_url: String,
+    http: Arc<Provider<Http>>,
+    metrics: Arc<IndexerMetrics>,
+}
+
+impl HeadFollower {
+    pub fn new(chain_id: u64, ws_url: String, http: Arc<Provider<Http>>, metrics: Arc<IndexerMetrics>) -> Self {
+        Self { chain_id, ws_url, http, metrics }
+    }
+
+    // Runs until `blocks` is closed
+    pub async fn run(self, blocks: mpsc::Sender<u64>) {
+        let mut tracker = HeadTracker::default();
+        let mut backoff = Backoff::new(MIN_RECONNECT_DELAY, MAX_RECONNECT_DELAY);
+
+        loop {
+            match Provider::<Ws>::connect(&self.ws_url).await {
+                Ok(ws) => {
+                    tracing::info!("Following heads over WebSocket for chain {}", self.chain_id);
+                    backoff.reset();
+                    if self.follow_ws(&ws, &mut tracker, &blocks).await.is_break() {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to connect to WebSocket for chain {}: {}", self.chain_id, e);
+                }
+            }
+
+            // Keep indexing over HTTP until the next reconnect attempt
+            self.metrics.record_head_fallback(self.chain_id);
+            let retry_in = backoff.next_delay();
+            tracing::warn!("Polling heads for chain {} for {:?} before reconnecting", self.chain_id, retry_in);
+            if self.poll(Instant::now() + retry_in, &mut tracker, &blocks).await.is_break() {
+                return;
+            }
+        }
+    }
+
+    async fn follow_ws(
+        &self,
+        ws: &Provider<Ws>,
+        tracker: &mut HeadTracker,
+        blocks: &mpsc::Sender<u64>,
+    ) -> ControlFlow<()> {
+        let mut stream = match ws.subscribe_blocks().await {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::warn!("Failed to subscribe to heads for chain {}: {}", self.chain_id, e);
+                return ControlFlow::Continue(());
+            }
+        };
+
+        loop {
+            match timeout(STALE_AFTER, stream.next()).await {
+                Ok(Some(block)) => {
+                    if let Some(number) = block.number {
+                        send_range(tracker, number.as_u64(), blocks).await?;
+                    }
+                }
+                Ok(None) => {
+                    tracing::warn!("WebSocket head stream ended for chain {}", self.chain_id);
+                    return ControlFlow::Continue(());
+                }
+                Err(_) => {
+                    tracing::warn!("No heads over WebSocket for chain {} in {:?}", self.chain_id, STALE_AFTER);
+                    return ControlFlow::Continue(());
+                }
+            }
+        }
+    }
+
+    async fn poll(&self, until: Instant, tracker: &mut HeadTracker, blocks: &mpsc::Sender<u64>) -> ControlFlow<()> {
+        let mut ticks = interval(POLL_INTERVAL);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        while Instant::now() < until {
+            ticks.tick().await;
+            match self.http.get_block_number().await {
+                Ok(number) => send_range(tracker, number.as_u64(), blocks).await?,
+                Err(e) => {
+                    tracing::warn!("Failed to poll head for chain {}: {}", self.chain_id, e);
+                    sleep(POLL_INTERVAL).await;
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+async fn send_range(tracker: &mut HeadTracker, head: u64, blocks: &mpsc::Sender<u64>) -> ControlFlow<()> {
+    for block_number in tracker.advance(head) {
+        if blocks.send(block_number).await.is_err() {
+            return ControlFlow::Break(());
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker_fills_gaps_and_ignores_old_heads() {
+        let mut tracker = HeadTracker::new(None);
+        assert_eq!(tracker.advance(100), 100..=100);
+        assert_eq!(tracker.advance(101), 101..=101);
+        assert_eq!(tracker.advance(104), 102..=104);
+        assert!(tracker.advance(104).is_empty());
+        assert!(tracker.advance(90).is_empty());
+
+        // Long outages only replay the most recent blocks
+        assert_eq!(tracker.advance(10_000), 10_000 - MAX_CATCH_UP + 1..=10_000);
+        assert_eq!(tracker.last(), Some(10_000));
+    }
+
+    #[test]
+    fn test_backoff_doubles_to_cap_and_resets() {
+        let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(5));
+        let delays: Vec<_> = (0..5).map(|_| backoff.next_delay().as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+    }
+}
@@ -1,26 +1,36 @@
 use ethers::{
-    providers::{Provider, Http, Ws, StreamExt, Middleware},
-    types::{Filter, Log, Block, Transaction, H256, U64, Address},
+    providers::{Provider, Http, Middleware},
+    types::{Log, Block, Transaction, H256, U64, Address},
     abi::AbiDecode,
 };
 use tokio::{
-    sync::{mpsc, broadcast, RwLock},
+    sync::{mpsc, broadcast, Mutex, RwLock},
     time::{interval, Duration, sleep},
-    task::JoinHandle,
+    task::{AbortHandle, JoinHandle},
 };
 use std::{
     collections::HashMap,
     sync::Arc,
     time::Instant,
 };
-use futures::future::join_all;
+use futures::{future::{join_all, select_all}, FutureExt};
 
 use crate::{
-    config::IndexerConfig,
+    archive::Archiver,
+    backfill::{Backfill, BackfillReport, BlockRange},
+    config::{BackfillConfig, IndexerConfig},
+    enrichment::Enricher,
     storage::IndexerStorage,
     events::EventProcessor,
+    handlers::{EventHandler, HandlerPipeline},
+    heads::{Backoff, HeadFollower},
     error::{Result, IndexerError},
-    metrics::IndexerMetrics,
+    metrics::{self, IndexerMetrics, LagAlert},
+    reorg::{BlockHashWindow, ReorgNotification},
+    rollups::RollupJob,
+    supervisor::{ChainStatus, ChainSupervisor},
+    transfers::BridgeMonitor,
+    watchlist::{WatchList, WatchListManager, WatchedContract},
     *,
 };
 
@@ -30,8 +40,10 @@ pub struct BlockchainIndexer {
     storage: Arc<IndexerStorage>,
     event_processor: Arc<EventProcessor>,
     metrics: Arc<IndexerMetrics>,
-    chain_handlers: Arc<RwLock<HashMap<u64, ChainIndexer>>>,
-    event_broadcaster: broadcast::Sender<IndexedEvent>,
+    supervisors: Arc<RwLock<HashMap<u64, ChainSupervisor>>>,
+    watch_list: Arc<WatchListManager>,
+    event_handlers: HandlerPipeline,
+    event_broadcaster: broadcast::Sender<StreamEvent>,
     shutdown_tx: mpsc::Sender<()>,
     shutdown_rx: mpsc::Receiver<()>,
 }
@@ -41,6 +53,12 @@ impl BlockchainIndexer {
         let storage = Arc::new(IndexerStorage::new(&config.database_url).await?);
         let event_processor = Arc::new(EventProcessor::new(storage.clone()));
         let metrics = Arc::new(IndexerMetrics::new());
+        let watch_list = Arc::new(WatchListManager::new(
+            config.chains.clone(),
+            config.backfill.clone(),
+            storage.clone(),
+            event_processor.clone(),
+        ));
         
         let (event_broadcaster, _) = broadcast::channel(10000);
         let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
@@ -50,7 +68,9 @@ impl BlockchainIndexer {
             storage,
             event_processor,
             metrics,
-            chain_handlers: Arc::new(RwLock::new(HashMap::new())),
+            supervisors: Arc::new(RwLock::new(HashMap::new())),
+            watch_list,
+            event_handlers: HandlerPipeline::new(),
             event_broadcaster,
             shutdown_tx,
             shutdown_rx,
@@ -63,35 +83,60 @@ impl BlockchainIndexer {
         // Initialize database
         self.storage.initialize().await?;
         
-        // Start chain indexers
-        let mut tasks = Vec::new();
+        // Pick up contracts watched in earlier runs before indexing starts
+        self.watch_list.restore().await?;
         
+        // Start one supervised indexer per chain
         for chain_config in &self.config.chains {
             if chain_config.enabled {
-                let chain_indexer = ChainIndexer::new(
-                    chain_config.clone(),
-                    self.storage.clone(),
-                    self.event_processor.clone(),
-                    self.metrics.clone(),
-                    self.event_broadcaster.clone(),
-                ).await?;
-                
-                // Start chain indexer
-                let task = tokio::spawn(async move {
-                    chain_indexer.start().await
-                });
-                
-                tasks.push(task);
-                
-                self.chain_handlers.write().await.insert(
-                    chain_config.chain_id,
-                    chain_indexer,
-                );
+                let supervisor = self.supervise_chain(chain_config.clone());
+                self.supervisors.write().await.insert(chain_config.chain_id, supervisor);
                 
                 tracing::info!("Started indexer for chain: {}", chain_config.name);
             }
         }
         
+        // Start event handlers, each from its own stored offset
+        let mut tasks = Vec::new();
+        tasks.extend(self.event_handlers.spawn(self.storage.clone(), &self.event_broadcaster));
+        
+        // Apply watch list file changes without a restart
+        if let Some(watch_file) = self.config.watch_list.clone() {
+            let watch_list = self.watch_list.clone();
+            let poll = Duration::from_secs(watch_file.poll_secs.max(1));
+            tasks.push(tokio::spawn(watch_list.follow_file(watch_file.path.into(), poll)));
+        }
+        
+        // Alert on bridge transfers stuck past their SLA
+        let bridge_monitor = BridgeMonitor::new(self.config.bridge_sla.clone(), self.storage.clone(), self.metrics.clone());
+        tasks.push(tokio::spawn(bridge_monitor.run()));
+        
+        // Keep the analytics rollups current
+        let rollups = RollupJob::new(self.config.rollups.clone(), self.storage.clone());
+        tasks.push(tokio::spawn(rollups.run()));
+        
+        // Price swaps and bridge transfers in USD
+        if let Some(enrichment) = self.config.enrichment.clone() {
+            let enricher = Enricher::new(enrichment, &self.config.chains, self.storage.clone())?;
+            tasks.push(tokio::spawn(enricher.run()));
+        }
+        
+        // Move events past the retention window to cold storage
+        if let Some(retention) = self.config.retention.clone() {
+            let archiver = Archiver::new(retention, self.storage.clone())?;
+            tasks.push(tokio::spawn(archiver.run()));
+        }
+        
+        // Serve Prometheus metrics
+        if let Some(addr) = self.config.metrics.listen_addr {
+            let metrics = self.metrics.clone();
+            tasks.push(tokio::spawn(async move {
+                if let Err(e) = metrics::serve(metrics, addr).await {
+                    tracing::error!("{}", e);
+                }
+            }));
+        }
+        
         // Start metrics collection
         let metrics_task = self.start_metrics_collection();
         tasks.push(metrics_task);
@@ -120,16 +165,108 @@ impl BlockchainIndexer {
         tracing::info!("Shutting down blockchain indexer");
         
         // Stop all chain indexers
-        let handlers = self.chain_handlers.read().await;
-        for (chain_id, handler) in handlers.iter() {
+        let supervisors = self.supervisors.read().await;
+        for (chain_id, supervisor) in supervisors.iter() {
             tracing::info!("Stopping indexer for chain: {}", chain_id);
-            handler.stop().await?;
+            supervisor.stop();
         }
         
         Ok(())
     }
     
-    pub fn subscribe_to_events(&self) -> broadcast::Receiver<IndexedEvent> {
+    // Stop indexing `chain_id` until `resume_chain`; other chains carry on
+    pub async fn pause_chain(&self, chain_id: u64) -> Result<()> {
+        self.with_supervisor(chain_id, ChainSupervisor::pause).await
+    }
+    
+    pub async fn resume_chain(&self, chain_id: u64) -> Result<()> {
+        self.with_supervisor(chain_id, ChainSupervisor::resume).await
+    }
+    
+    // Start indexing `contract`, backfilling it from its start block. Takes
+    // effect from each chain indexer's next block.
+    pub async fn watch_contract(&self, contract: WatchedContract) -> Result<()> {
+        self.watch_list.watch(contract).await
+    }
+    
+    // Stop indexing a contract; its indexed events are kept
+    pub async fn unwatch_contract(&self, chain_id: u64, address: Address) -> Result<bool> {
+        self.watch_list.unwatch(chain_id, address).await
+    }
+    
+    pub fn watched_contracts(&self, chain_id: u64) -> Vec<WatchedContract> {
+        self.watch_list.watch_list().contracts(chain_id)
+    }
+    
+    async fn with_supervisor(&self, chain_id: u64, action: fn(&ChainSupervisor)) -> Result<()> {
+        let supervisors = self.supervisors.read().await;
+        let supervisor = supervisors.get(&chain_id).ok_or_else(||
+            IndexerError::DataNotFound(format!("Chain {} is not being indexed", chain_id))
+        )?;
+        action(supervisor);
+        Ok(())
+    }
+    
+    // Restart the chain's indexer with its own backoff whenever it fails
+    fn supervise_chain(&self, config: ChainIndexerConfig) -> ChainSupervisor {
+        let chain_id = config.chain_id;
+        let storage = self.storage.clone();
+        let event_processor = self.event_processor.clone();
+        let metrics = self.metrics.clone();
+        let event_broadcaster = self.event_broadcaster.clone();
+        let backfill = self.config.backfill.clone();
+        let watch_list = self.watch_list.watch_list().clone();
+        let backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(300));
+        
+        ChainSupervisor::spawn(chain_id, backoff, move || {
+            let chain_indexer = ChainIndexer::new(
+                config.clone(),
+                storage.clone(),
+                event_processor.clone(),
+                metrics.clone(),
+                event_broadcaster.clone(),
+                backfill.clone(),
+                watch_list.clone(),
+            );
+            async move { chain_indexer.await?.start().await }.boxed()
+        })
+    }
+    
+    // Backfill every enabled chain from its start block to the confirmed head,
+    // resuming from the ranges an earlier run checkpointed
+    pub async fn backfill(&self) -> Result<Vec<BackfillReport>> {
+        self.storage.initialize().await?;
+        self.watch_list.load().await?;
+
+        let runs = self.config.chains.iter()
+            .filter(|chain| chain.enabled)
+            .map(|chain| async move {
+                let backfill = Backfill::new(
+                    chain.clone(),
+                    self.config.backfill.clone(),
+                    self.storage.clone(),
+                    self.event_processor.clone(),
+                    self.watch_list.watch_list().clone(),
+                )?;
+                let report = backfill.run().await?;
+                tracing::info!(
+                    "Backfill of chain {} done: {} ranges, {} events",
+                    chain.name,
+                    report.ranges_completed,
+                    report.events_indexed
+                );
+                Ok(report)
+            });
+
+        join_all(runs).await.into_iter().collect()
+    }
+
+    // Handlers must be registered before `start`
+    pub fn register_handler(&mut self, handler: Arc<dyn EventHandler>) -> Result<()> {
+        self.event_handlers.register(handler)
+    }
+    
+    pub fn subscribe_to_events(&self) -> broadcast::Receiver<StreamEvent> {
         self.event_broadcaster.subscribe()
     }
     
@@ -138,12 +275,19 @@ impl BlockchainIndexer {
     }
     
     pub async fn get_stats(&self) -> Result<IndexerStats> {
-        self.storage.get_stats().await
+        let mut stats = self.storage.get_stats().await?;
+        for (chain_id, supervisor) in self.supervisors.read().await.iter() {
+            if let Some(chain) = stats.chains.get_mut(chain_id) {
+                chain.status = Some(supervisor.status());
+            }
+        }
+        Ok(stats)
     }
     
     fn start_metrics_collection(&self) -> JoinHandle<()> {
         let metrics = self.metrics.clone();
         let storage = self.storage.clone();
+        let metrics_config = self.config.metrics.clone();
         
         tokio::spawn(async move {
             let mut interval = interval(Duration::from_secs(60)); // Collect metrics every minute
@@ -154,12 +298,28 @@ impl BlockchainIndexer {
                 if let Err(e) = Self::collect_metrics(&metrics, &storage).await {
                     tracing::warn!("Failed to collect metrics: {}", e);
                 }
+                
+                for alert in metrics.check_lag_alerts(&metrics_config) {
+                    match alert {
+                        LagAlert::Raised { chain_id, blocks_behind, threshold } => tracing::error!(
+                            "Chain {} is {} blocks behind, over the alert threshold of {}",
+                            chain_id,
+                            blocks_behind,
+                            threshold
+                        ),
+                        LagAlert::Cleared { chain_id, blocks_behind } => tracing::info!(
+                            "Chain {} caught up to {} blocks behind",
+                            chain_id,
+                            blocks_behind
+                        ),
+                    }
+                }
             }
         })
     }
     
     fn start_health_monitoring(&self) -> JoinHandle<()> {
-        let chain_handlers = self.chain_handlers.clone();
+        let supervisors = self.supervisors.clone();
         let storage = self.storage.clone();
         
         tokio::spawn(async move {
@@ -168,7 +328,7 @@ impl BlockchainIndexer {
             loop {
                 interval.tick().await;
                 
-                if let Err(e) = Self::monitor_health(&chain_handlers, &storage).await {
+                if let Err(e) = Self::monitor_health(&supervisors, &storage).await {
                     tracing::warn!("Health monitoring failed: {}", e);
                 }
             }
@@ -197,9 +357,15 @@ impl BlockchainIndexer {
     }
     
     async fn monitor_health(
-        _chain_handlers: &Arc<RwLock<HashMap<u64, ChainIndexer>>>,
+        supervisors: &Arc<RwLock<HashMap<u64, ChainSupervisor>>>,
         _storage: &IndexerStorage,
     ) -> Result<()> {
+        for (chain_id, supervisor) in supervisors.read().await.iter() {
+            if let ChainStatus::Restarting { attempt, last_error, .. } = supervisor.status() {
+                tracing::warn!("Chain {} is restarting (attempt {}): {}", chain_id, attempt, last_error);
+            }
+        }
+        
         // TODO: Implement health monitoring logic
         // - Check if chains are syncing
         // - Monitor error rates
@@ -210,27 +376,52 @@ impl BlockchainIndexer {
     }
 }
 
+// Aborts the tasks of a chain indexer run when it ends
+struct AbortOnDrop(Vec<AbortHandle>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        for task in &self.0 {
+            task.abort();
+        }
+    }
+}
+
 // Chain-specific indexer
 #[derive(Clone)]
 pub struct ChainIndexer {
     config: ChainIndexerConfig,
     provider: Arc<Provider<Http>>,
-    ws_provider: Option<Arc<Provider<Ws>>>,
     storage: Arc<IndexerStorage>,
     event_processor: Arc<EventProcessor>,
     metrics: Arc<IndexerMetrics>,
-    event_broadcaster: broadcast::Sender<IndexedEvent>,
+    event_broadcaster: broadcast::Sender<StreamEvent>,
+    block_hashes: Arc<Mutex<BlockHashWindow>>,
+    watch_list: WatchList,
+    // Fills gaps in block coverage
+    backfill: Arc<Backfill>,
     shutdown_tx: mpsc::Sender<()>,
     shutdown_rx: Option<mpsc::Receiver<()>>,
 }
 
+// What processing one block did
+#[derive(Debug)]
+enum BlockOutcome {
+    Indexed,
+    // The block did not extend the indexed chain; storage was rolled back
+    // to the fork block, which indexing resumes from
+    Reorged(ReorgNotification),
+}
+
 impl ChainIndexer {
     pub async fn new(
         config: ChainIndexerConfig,
         storage: Arc<IndexerStorage>,
         event_processor: Arc<EventProcessor>,
         metrics: Arc<IndexerMetrics>,
-        event_broadcaster: broadcast::Sender<IndexedEvent>,
+        event_broadcaster: broadcast::Sender<StreamEvent>,
+        backfill_settings: BackfillConfig,
+        watch_list: WatchList,
     ) -> Result<Self> {
         // Create HTTP provider
         let provider = Arc::new(
@@ -238,29 +429,26 @@ impl ChainIndexer {
                 .map_err(|e| IndexerError::ProviderError(e.to_string()))?
         );
         
-        // Create WebSocket provider if available
-        let ws_provider = if let Some(ws_url) = &config.ws_url {
-            match Provider::<Ws>::connect(ws_url).await {
-                Ok(ws) => Some(Arc::new(ws)),
-                Err(e) => {
-                    tracing::warn!("Failed to connect to WebSocket for chain {}: {}", config.chain_id, e);
-                    None
-                }
-            }
-        } else {
-            None
-        };
-        
         let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
+        let block_hashes = Arc::new(Mutex::new(BlockHashWindow::new(config.max_reorg_depth)));
+        let backfill = Arc::new(Backfill::new(
+            config.clone(),
+            backfill_settings,
+            storage.clone(),
+            event_processor.clone(),
+            watch_list.clone(),
+        )?);
         
         Ok(Self {
             config,
             provider,
-            ws_provider,
             storage,
             event_processor,
             metrics,
             event_broadcaster,
+            block_hashes,
+            watch_list,
+            backfill,
             shutdown_tx,
             shutdown_rx: Some(shutdown_rx),
         })
@@ -272,12 +460,13 @@ impl ChainIndexer {
         // Get current chain state
         let mut chain_state = self.get_or_create_chain_state().await?;
         
+        // Restore the reorg window from the last run
+        let recent = self.storage.recent_block_hashes(self.config.chain_id, self.config.max_reorg_depth).await?;
+        *self.block_hashes.lock().await = BlockHashWindow::from_recent(self.config.max_reorg_depth, recent);
+        
         // Start real-time event monitoring if WebSocket is available
-        let realtime_task = if let Some(ws_provider) = &self.ws_provider {
-            Some(self.start_realtime_monitoring(ws_provider.clone()))
-        } else {
-            None
-        };
+        let realtime_task = self.config.ws_url.clone()
+            .map(|ws_url| self.start_realtime_monitoring(ws_url));
         
         // Start historical sync
         let historical_task = self.start_historical_sync(chain_state.clone());
@@ -285,22 +474,33 @@ impl ChainIndexer {
         // Start block monitoring
         let block_monitoring_task = self.start_block_monitoring();
         
-        let mut tasks = vec![historical_task, block_monitoring_task];
+        // Start gap reconciliation
+        let reconciliation_task = self.start_reconciliation();
+        
+        let mut tasks = vec![historical_task, block_monitoring_task, reconciliation_task];
         if let Some(task) = realtime_task {
             tasks.push(task);
         }
         
-        // Wait for shutdown or task completion
+        // The tasks live only as long as this run, which the supervisor
+        // may cancel
+        let _tasks_guard = AbortOnDrop(tasks.iter().map(|task| task.abort_handle()).collect());
+        
+        // Wait for shutdown or the first task to end
         tokio::select! {
             _ = self.shutdown_rx.as_mut().unwrap().recv() => {
                 tracing::info!("Chain indexer shutdown for chain: {}", self.config.chain_id);
+                Ok(())
             }
-            result = join_all(tasks) => {
-                tracing::error!("Chain indexer task failed for chain {}: {:?}", self.config.chain_id, result);
+            (result, _, _) = select_all(tasks) => {
+                let reason = match result {
+                    Ok(Ok(())) => "task ended".to_string(),
+                    Ok(Err(e)) => e.to_string(),
+                    Err(e) => e.to_string(),
+                };
+                Err(IndexerError::Internal(format!("Chain indexer task failed for chain {}: {}", self.config.chain_id, reason)))
             }
         }
-        
-        Ok(())
     }
     
     pub async fn stop(&self) -> Result<()> {
@@ -336,18 +536,21 @@ impl ChainIndexer {
         let provider = self.provider.clone();
         let storage = self.storage.clone();
         let event_processor = self.event_processor.clone();
+        let metrics = self.metrics.clone();
         let config = self.config.clone();
         let event_broadcaster = self.event_broadcaster.clone();
+        let block_hashes = self.block_hashes.clone();
+        let watch_list = self.watch_list.clone();
         
         tokio::spawn(async move {
             tracing::info!("Starting historical sync for chain: {}", config.chain_id);
             
             loop {
                 // Get latest block
-                let latest_block = provider.get_block_number().await
-                    .map_err(|e| IndexerError::ProviderError(e.to_string()))?;
+                let latest_block = rpc(&metrics, config.chain_id, provider.get_block_number()).await?;
                 
                 let target_block = latest_block.as_u64().saturating_sub(config.confirmation_blocks);
+                metrics.set_chain_blocks_behind(config.chain_id, target_block.saturating_sub(chain_state.indexed_block));
                 
                 if chain_state.indexed_block >= target_block {
                     // We're caught up, wait a bit
@@ -369,27 +572,36 @@ impl ChainIndexer {
                 let start_time = Instant::now();
                 
                 for block_number in (chain_state.indexed_block + 1)..=end_block {
-                    if let Err(e) = Self::process_block(
+                    match Self::process_block(
                         &provider,
                         &storage,
                         &event_processor,
+                        &metrics,
                         &config,
                         &event_broadcaster,
+                        &block_hashes,
+                        &watch_list,
                         block_number,
                     ).await {
-                        tracing::error!(
-                            "Failed to process block {} for chain {}: {}",
-                            block_number,
-                            config.chain_id,
-                            e
-                        );
-                        
-                        // Wait before retrying
-                        sleep(Duration::from_secs(5)).await;
-                        continue;
+                        Ok(BlockOutcome::Indexed) => chain_state.indexed_block = block_number,
+                        Ok(BlockOutcome::Reorged(reorg)) => {
+                            // Re-index from the fork on the next pass
+                            chain_state.indexed_block = reorg.fork_block;
+                            break;
+                        }
+                        Err(e) => {
+                            tracing::error!(
+                                "Failed to process block {} for chain {}: {}",
+                                block_number,
+                                config.chain_id,
+                                e
+                            );
+                            
+                            // Wait before retrying
+                            sleep(Duration::from_secs(5)).await;
+                            continue;
+                        }
                     }
-                    
-                    chain_state.indexed_block = block_number;
                 }
                 
                 chain_state.latest_block = latest_block.as_u64();
@@ -414,45 +626,83 @@ impl ChainIndexer {
         })
     }
     
-    fn start_realtime_monitoring(&self, ws_provider: Arc<Provider<Ws>>) -> JoinHandle<Result<()>> {
+    fn start_realtime_monitoring(&self, ws_url: String) -> JoinHandle<Result<()>> {
+        let provider = self.provider.clone();
         let storage = self.storage.clone();
         let event_processor = self.event_processor.clone();
+        let metrics = self.metrics.clone();
         let config = self.config.clone();
         let event_broadcaster = self.event_broadcaster.clone();
+        let block_hashes = self.block_hashes.clone();
+        let watch_list = self.watch_list.clone();
         
         tokio::spawn(async move {
             tracing::info!("Starting real-time monitoring for chain: {}", config.chain_id);
             
-            // Subscribe to new blocks
-            let mut stream = ws_provider.subscribe_blocks().await
-                .map_err(|e| IndexerError::ProviderError(e.to_string()))?;
+            // Heads arrive over WebSocket, or over HTTP polling while the
+            // subscription is down, with skipped blocks filled in
+            let (blocks_tx, mut blocks) = mpsc::channel(256);
+            let follower = HeadFollower::new(config.chain_id, ws_url, provider.clone(), metrics.clone());
+            let follower_task = tokio::spawn(follower.run(blocks_tx));
             
-            while let Some(block) = stream.next().await {
-                if let Some(block_number) = block.number {
-                    // Process the new block
-                    if let Err(e) = Self::process_block(
-                        &ws_provider,
+            while let Some(block_number) = blocks.recv().await {
+                // Process the new block; after a reorg, re-index the new
+                // branch from the fork up to it
+                let mut next = block_number;
+                while next <= block_number {
+                    match Self::process_block(
+                        &provider,
                         &storage,
                         &event_processor,
+                        &metrics,
                         &config,
                         &event_broadcaster,
-                        block_number.as_u64(),
+                        &block_hashes,
+                        &watch_list,
+                        next,
                     ).await {
-                        tracing::error!(
-                            "Failed to process real-time block {} for chain {}: {}",
-                            block_number,
-                            config.chain_id,
-                            e
-                        );
+                        Ok(BlockOutcome::Indexed) => next += 1,
+                        Ok(BlockOutcome::Reorged(reorg)) => next = reorg.fork_block + 1,
+                        Err(e) => {
+                            tracing::error!(
+                                "Failed to process real-time block {} for chain {}: {}",
+                                next,
+                                config.chain_id,
+                                e
+                            );
+                            break;
+                        }
                     }
                 }
             }
             
-            tracing::warn!("Real-time monitoring stream ended for chain: {}", config.chain_id);
+            follower_task.abort();
+            tracing::warn!("Real-time monitoring stopped for chain: {}", config.chain_id);
             Ok(())
         })
     }
     
+    fn start_reconciliation(&self) -> JoinHandle<Result<()>> {
+        let backfill = self.backfill.clone();
+        let chain_id = self.config.chain_id;
+        
+        tokio::spawn(async move {
+            let mut interval = interval(Duration::from_secs(600)); // Look for gaps every 10 minutes
+            
+            loop {
+                interval.tick().await;
+                
+                match backfill.reconcile().await {
+                    Ok(gaps) if !gaps.is_empty() => {
+                        tracing::info!("Filled {} coverage gaps on chain {}", gaps.len(), chain_id);
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Gap reconciliation failed for chain {}: {}", chain_id, e),
+                }
+            }
+        })
+    }
+    
     fn start_block_monitoring(&self) -> JoinHandle<Result<()>> {
         let provider = self.provider.clone();
         let storage = self.storage.clone();
@@ -479,88 +729,148 @@ impl ChainIndexer {
         })
     }
     
+    #[allow(clippy::too_many_arguments)]
     async fn process_block<P: Middleware + 'static>(
         provider: &Arc<P>,
         storage: &Arc<IndexerStorage>,
         event_processor: &Arc<EventProcessor>,
+        metrics: &Arc<IndexerMetrics>,
         config: &ChainIndexerConfig,
-        event_broadcaster: &broadcast::Sender<IndexedEvent>,
+        event_broadcaster: &broadcast::Sender<StreamEvent>,
+        block_hashes: &Arc<Mutex<BlockHashWindow>>,
+        watch_list: &WatchList,
         block_number: u64,
-    ) -> Result<()> {
+    ) -> Result<BlockOutcome> {
         // Get block with transactions
-        let block = provider.get_block_with_txs(block_number).await
-            .map_err(|e| IndexerError::ProviderError(e.to_string()))?;
+        let block = rpc(metrics, config.chain_id, provider.get_block_with_txs(block_number)).await?;
         
         let block = block.ok_or_else(|| 
             IndexerError::DataNotFound(format!("Block {} not found", block_number))
         )?;
+        let block_hash = block.hash.ok_or_else(||
+            IndexerError::DataNotFound(format!("Block {} has no hash", block_number))
+        )?;
+        
+        // A block that does not extend what we indexed means a reorg
+        if block_hashes.lock().await.conflicts(block_number, block_hash, block.parent_hash) {
+            let reorg = Self::roll_back_reorg(provider, storage, metrics, config, block_hashes).await?;
+            if let Err(e) = event_broadcaster.send(StreamEvent::Reorg(reorg.clone())) {
+                tracing::warn!("Failed to broadcast reorg: {}", e);
+            }
+            return Ok(BlockOutcome::Reorged(reorg));
+        }
         
         // Create event filters for our contracts
-        let filters = Self::create_event_filters(config, block_number);
+        let filters = watch_list.filters(config.chain_id, BlockRange { from: block_number, to: block_number });
         
         // Get logs for this block
         for filter in filters {
-            let logs = provider.get_logs(&filter).await
-                .map_err(|e| IndexerError::ProviderError(e.to_string()))?;
+            let logs = rpc(metrics, config.chain_id, provider.get_logs(&filter)).await?;
             
             // Process each log
             for log in logs {
-                let indexed_event = event_processor.process_log(
+                let processed = match event_processor.process_log(
                     config.chain_id,
                     &log,
                     block.timestamp,
-                ).await?;
+                ).await {
+                    Ok(Some(processed)) => processed,
+                    Ok(None) => continue,
+                    Err(e @ IndexerError::MalformedLog { .. }) => {
+                        // Skip the log rather than stall the chain on it
+                        tracing::warn!("Rejected log: {}", e);
+                        metrics.record_malformed_log(config.chain_id);
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
                 
-                // Store the event
-                storage.store_event(&indexed_event).await?;
+                // Store the event with its typed row; a log stored before, e.g.
+                // before a restart, is neither stored nor broadcast again
+                let started = Instant::now();
+                let stored = storage.store_processed(&processed).await?;
+                metrics.observe_storage_latency(started.elapsed());
+                if !stored {
+                    continue;
+                }
                 
                 // Broadcast to subscribers
-                if let Err(e) = event_broadcaster.send(indexed_event) {
+                if let Err(e) = event_broadcaster.send(StreamEvent::Indexed(processed.event)) {
                     tracing::warn!("Failed to broadcast event: {}", e);
                 }
             }
         }
         
-        Ok(())
+        // Remember the block for reorg detection
+        let started = Instant::now();
+        storage.save_block_hash(config.chain_id, block_number, block_hash, block.parent_hash).await?;
+        storage.add_coverage(config.chain_id, BlockRange { from: block_number, to: block_number }).await?;
+        metrics.observe_storage_latency(started.elapsed());
+        let mut window = block_hashes.lock().await;
+        window.record(block_number, block_hash);
+        if let Some(oldest) = window.oldest() {
+            storage.prune_block_hashes(config.chain_id, oldest).await?;
+        }
+        
+        Ok(BlockOutcome::Indexed)
     }
     
-    fn create_event_filters(config: &ChainIndexerConfig, block_number: u64) -> Vec<Filter> {
-        let mut filters = Vec::new();
-        
-        // Intent contract events
-        filters.push(
-            Filter::new()
-                .address(config.contracts.intents_contract)
-                .from_block(block_number)
-                .to_block(block_number)
-        );
-        
-        // Orbital AMM contract events
-        filters.push(
-            Filter::new()
-                .address(config.contracts.orbital_amm_contract)
-                .from_block(block_number)
-                .to_block(block_number)
-        );
-        
-        // Bridge contract events
-        filters.push(
-            Filter::new()
-                .address(config.contracts.bridge_contract)
-                .from_block(block_number)
-                .to_block(block_number)
-        );
-        
-        // Solver registry events (if configured)
-        if let Some(solver_registry) = config.contracts.solver_registry {
-            filters.push(
-                Filter::new()
-                    .address(solver_registry)
-                    .from_block(block_number)
-                    .to_block(block_number)
-            );
+    // Walk back through the window to the newest block the provider still
+    // has, then roll storage back to it
+    async fn roll_back_reorg<P: Middleware + 'static>(
+        provider: &Arc<P>,
+        storage: &Arc<IndexerStorage>,
+        metrics: &Arc<IndexerMetrics>,
+        config: &ChainIndexerConfig,
+        block_hashes: &Arc<Mutex<BlockHashWindow>>,
+    ) -> Result<ReorgNotification> {
+        let indexed = block_hashes.lock().await.newest_first();
+        let previous_head = indexed.first().map(|(number, _)| *number).unwrap_or_default();
+        
+        let mut fork_block = None;
+        for (block_number, hash) in indexed {
+            let canonical = rpc(metrics, config.chain_id, provider.get_block(block_number)).await?
+                .and_then(|block| block.hash);
+            if canonical == Some(hash) {
+                fork_block = Some(block_number);
+                break;
+            }
         }
-        
-        filters
+        let fork_block = fork_block.ok_or(IndexerError::ReorgTooDeep {
+            chain_id: config.chain_id,
+            block_number: previous_head,
+            max_depth: config.max_reorg_depth,
+        })?;
+        
+        let events_rolled_back = storage.rollback_to(config.chain_id, fork_block).await?;
+        block_hashes.lock().await.rewind(fork_block);
+        metrics.record_reorg(config.chain_id, events_rolled_back);
+        
+        let reorg = ReorgNotification {
+            chain_id: config.chain_id,
+            fork_block,
+            previous_head,
+            events_rolled_back,
+            detected_at: chrono::Utc::now(),
+        };
+        tracing::warn!(
+            "Reorg of depth {} on chain {}: rolled back {} events above block {}",
+            reorg.depth(),
+            config.chain_id,
+            events_rolled_back,
+            fork_block
+        );
+        Ok(reorg)
     }
-}
\ No newline at end of file
+}
+
+// Await an RPC call, counting it and any failure in the chain's metrics
+async fn rpc<T, E: std::fmt::Display>(
+    metrics: &IndexerMetrics,
+    chain_id: u64,
+    call: impl std::future::Future<Output = std::result::Result<T, E>>,
+) -> Result<T> {
+    let result = call.await;
+    metrics.record_rpc(chain_id, result.is_ok());
+    result.map_err(|e| IndexerError::ProviderError(e.to_string()))
+}
@@ -0,0 +1,226 @@
+use chrono::{DateTime, Utc};
+use ethers::types::{Address, H256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Intent lifecycle across chains, assembled from intent events and the
+// bridge legs deposited in the same transactions as them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentJourney {
+    pub intent_id: H256,
+    pub user: Option<Address>,
+    pub solver: Option<Address>,
+    pub source_chain_id: Option<u64>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub matched_at: Option<DateTime<Utc>>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub success: Option<bool>,
+    pub bridge_legs: Vec<BridgeLeg>,
+    pub latency: PhaseLatency,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BridgeLeg {
+    pub message_hash: H256,
+    pub source_chain_id: u64,
+    pub destination_chain_id: u64,
+    pub deposited_at: Option<DateTime<Utc>>,
+    pub withdrawn_at: Option<DateTime<Utc>>,
+}
+
+// Milliseconds spent in each phase; `None` until both ends are known
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhaseLatency {
+    pub matching_ms: Option<i64>,
+    pub execution_ms: Option<i64>,
+    pub bridging_ms: Option<i64>,
+    pub total_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntentPhase {
+    Created,
+    Matched,
+    Executed,
+}
+
+impl IntentPhase {
+    // Matches the `kind` column of `intent_events`
+    pub fn from_kind(kind: &str) -> Option<Self> {
+        match kind {
+            "created" => Some(Self::Created),
+            "matched" => Some(Self::Matched),
+            "executed" => Some(Self::Executed),
+            _ => None,
+        }
+    }
+}
+
+// One intent event
+#[derive(Debug, Clone)]
+pub struct IntentMilestone {
+    pub phase: IntentPhase,
+    pub chain_id: u64,
+    pub account: Address,
+    pub success: Option<bool>,
+    pub timestamp: DateTime<Utc>,
+}
+
+// One bridge event of a leg
+#[derive(Debug, Clone)]
+pub struct BridgeMilestone {
+    pub message_hash: H256,
+    pub deposit: bool,
+    pub chain_id: u64,
+    pub counterpart_chain_id: u64,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl IntentJourney {
+    // `None` when no intent event is left, e.g. after a rollback
+    pub fn build(intent_id: H256, milestones: &[IntentMilestone], bridge: &[BridgeMilestone]) -> Option<Self> {
+        if milestones.is_empty() {
+            return None;
+        }
+        let first = |phase: IntentPhase| {
+            milestones
+                .iter()
+                .filter(|m| m.phase == phase)
+                .min_by_key(|m| m.timestamp)
+        };
+        let created = first(IntentPhase::Created);
+        let matched = first(IntentPhase::Matched);
+        let executed = first(IntentPhase::Executed);
+
+        let mut legs: BTreeMap<H256, BridgeLeg> = BTreeMap::new();
+        for event in bridge {
+            let (source_chain_id, destination_chain_id) = if event.deposit {
+                (event.chain_id, event.counterpart_chain_id)
+            } else {
+                (event.counterpart_chain_id, event.chain_id)
+            };
+            let leg = legs.entry(event.message_hash).or_insert(BridgeLeg {
+                message_hash: event.message_hash,
+                source_chain_id,
+                destination_chain_id,
+                deposited_at: None,
+                withdrawn_at: None,
+            });
+            let at = if event.deposit { &mut leg.deposited_at } else { &mut leg.withdrawn_at };
+            *at = Some(at.map_or(event.timestamp, |at| at.min(event.timestamp)));
+        }
+        let bridge_legs: Vec<BridgeLeg> = legs.into_values().collect();
+
+        let mut journey = Self {
+            intent_id,
+            user: created.map(|m| m.account),
+            solver: matched.or(executed).map(|m| m.account),
+            source_chain_id: created.map(|m| m.chain_id),
+            created_at: created.map(|m| m.timestamp),
+            matched_at: matched.map(|m| m.timestamp),
+            executed_at: executed.map(|m| m.timestamp),
+            success: executed.and_then(|m| m.success),
+            bridge_legs,
+            latency: PhaseLatency::default(),
+        };
+        journey.latency = journey.phase_latency();
+        Some(journey)
+    }
+
+    // When the intent finished: its last bridge leg landing, or execution
+    // when nothing was bridged
+    pub fn completed_at(&self) -> Option<DateTime<Utc>> {
+        if self.bridge_legs.is_empty() {
+            return self.executed_at;
+        }
+        self.bridge_legs
+            .iter()
+            .map(|leg| leg.withdrawn_at)
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .max()
+    }
+
+    fn phase_latency(&self) -> PhaseLatency {
+        let between = |from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>| {
+            Some((to? - from?).num_milliseconds())
+        };
+        let bridging = if self.bridge_legs.is_empty() {
+            None
+        } else {
+            let deposited = self.bridge_legs.iter().filter_map(|leg| leg.deposited_at).min();
+            between(deposited, self.completed_at())
+        };
+
+        PhaseLatency {
+            matching_ms: between(self.created_at, self.matched_at),
+            execution_ms: between(self.matched_at.or(self.created_at), self.executed_at),
+            bridging_ms: bridging,
+            total_ms: between(self.created_at, self.completed_at()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000 + seconds, 0).unwrap()
+    }
+
+    fn milestone(phase: IntentPhase, account: u8, seconds: i64) -> IntentMilestone {
+        IntentMilestone {
+            phase,
+            chain_id: 1,
+            account: Address::repeat_byte(account),
+            success: (phase == IntentPhase::Executed).then_some(true),
+            timestamp: at(seconds),
+        }
+    }
+
+    #[test]
+    fn test_journey_phases_and_bridge_latency() {
+        let milestones = [
+            milestone(IntentPhase::Executed, 2, 30),
+            milestone(IntentPhase::Created, 1, 0),
+            milestone(IntentPhase::Matched, 2, 12),
+        ];
+        let leg = |deposit, chain_id, counterpart_chain_id, seconds| BridgeMilestone {
+            message_hash: H256::repeat_byte(9),
+            deposit,
+            chain_id,
+            counterpart_chain_id,
+            timestamp: at(seconds),
+        };
+
+        let pending = IntentJourney::build(H256::zero(), &milestones, &[leg(true, 1, 10, 30)]).unwrap();
+        assert_eq!(pending.user, Some(Address::repeat_byte(1)));
+        assert_eq!(pending.solver, Some(Address::repeat_byte(2)));
+        assert_eq!(pending.success, Some(true));
+        assert_eq!(pending.latency.matching_ms, Some(12_000));
+        assert_eq!(pending.latency.execution_ms, Some(18_000));
+        assert_eq!(pending.latency.total_ms, None);
+
+        let landed = IntentJourney::build(
+            H256::zero(),
+            &milestones,
+            &[leg(true, 1, 10, 30), leg(false, 10, 1, 90)],
+        )
+        .unwrap();
+        assert_eq!(landed.bridge_legs.len(), 1);
+        assert_eq!((landed.bridge_legs[0].source_chain_id, landed.bridge_legs[0].destination_chain_id), (1, 10));
+        assert_eq!(landed.latency.bridging_ms, Some(60_000));
+        assert_eq!(landed.latency.total_ms, Some(90_000));
+    }
+
+    #[test]
+    fn test_journey_without_events_is_none() {
+        assert!(IntentJourney::build(H256::zero(), &[], &[]).is_none());
+
+        let created = IntentJourney::build(H256::zero(), &[milestone(IntentPhase::Created, 1, 0)], &[]).unwrap();
+        assert_eq!(created.latency, PhaseLatency::default());
+        assert_eq!(created.completed_at(), None);
+    }
+}
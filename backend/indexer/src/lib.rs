@@ -1,13 +1,30 @@
 pub mod indexer;
+pub mod archive;
+pub mod backfill;
+pub mod enrichment;
 pub mod events;
+pub mod handlers;
+pub mod heads;
+pub mod journeys;
+pub mod market;
 pub mod storage;
 pub mod config;
 pub mod error;
 pub mod metrics;
+pub mod reorg;
+pub mod rollups;
+pub mod solvers;
+pub mod supervisor;
+pub mod transfers;
+pub mod watchlist;
 
 pub use config::IndexerConfig;
 pub use error::{IndexerError, Result};
+pub use handlers::EventHandler;
 pub use indexer::BlockchainIndexer;
+pub use reorg::ReorgNotification;
+pub use supervisor::ChainStatus;
+pub use watchlist::WatchedContract;
 
 use ethers::types::{Address, U256, H256};
 use serde::{Deserialize, Serialize};
@@ -142,6 +159,9 @@ pub struct ChainStats {
     pub blocks_behind: u64,
     pub avg_block_time: f64,
     pub last_indexed_block: u64,
+    // Supervisor state, when reported by a running indexer
+    #[serde(default)]
+    pub status: Option<ChainStatus>,
 }
 
 // Event processing result
@@ -186,11 +206,20 @@ pub struct EventFilter {
     pub to_block: Option<u64>,
 }
 
+// Items on the real-time event stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamEvent {
+    Indexed(IndexedEvent),
+    // Events above the fork block were removed and will be re-indexed
+    Reorg(ReorgNotification),
+}
+
 // Real-time event stream
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct EventStream {
     pub chain_id: u64,
-    pub receiver: tokio::sync::broadcast::Receiver<IndexedEvent>,
+    pub receiver: tokio::sync::broadcast::Receiver<StreamEvent>,
 }
 
 // Public API for starting the indexer
@@ -199,6 +228,12 @@ pub async fn start_indexer(config: IndexerConfig) -> Result<()> {
     indexer.start().await
 }
 
+// Public API for backfilling every enabled chain from its start block
+pub async fn run_backfill(config: IndexerConfig) -> Result<Vec<backfill::BackfillReport>> {
+    let indexer = BlockchainIndexer::new(config).await?;
+    indexer.backfill().await
+}
+
 // Public API for getting indexer stats
 pub async fn get_indexer_stats(config: &IndexerConfig) -> Result<IndexerStats> {
     let storage = storage::IndexerStorage::new(&config.database_url).await?;
@@ -215,6 +250,53 @@ pub async fn query_events(
     storage.query_events(filter, limit).await
 }
 
+// Public API for an intent's lifecycle across chains
+pub async fn query_intent_journey(
+    config: &IndexerConfig,
+    intent_id: H256,
+) -> Result<Option<journeys::IntentJourney>> {
+    let storage = storage::IndexerStorage::new(&config.database_url).await?;
+    storage.query_intent_journey(intent_id).await
+}
+
+// Public API for the solver leaderboard, best reputation first
+pub async fn query_solver_leaderboard(
+    config: &IndexerConfig,
+    limit: u64,
+) -> Result<Vec<solvers::SolverStats>> {
+    let storage = storage::IndexerStorage::new(&config.database_url).await?;
+    storage.solver_leaderboard(limit).await
+}
+
+// Public API for one solver's indexed track record
+pub async fn query_solver_stats(
+    config: &IndexerConfig,
+    solver: Address,
+) -> Result<Option<solvers::SolverStats>> {
+    let storage = storage::IndexerStorage::new(&config.database_url).await?;
+    storage.solver_stats(solver).await
+}
+
+// Public API for "where is my transfer": by message hash, or by the
+// transaction that sent or received it
+pub async fn find_bridge_transfer(
+    config: &IndexerConfig,
+    hash: H256,
+) -> Result<Option<transfers::BridgeTransfer>> {
+    let storage = storage::IndexerStorage::new(&config.database_url).await?;
+    storage.find_bridge_transfer(hash).await
+}
+
+// Public API for an account's recent bridge transfers
+pub async fn query_bridge_transfers(
+    config: &IndexerConfig,
+    account: Address,
+    limit: u64,
+) -> Result<Vec<transfers::BridgeTransfer>> {
+    let storage = storage::IndexerStorage::new(&config.database_url).await?;
+    storage.bridge_transfers_for(account, limit).await
+}
+
 // Public API for getting chain state
 pub async fn get_chain_state(
     config: &IndexerConfig,
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use ethers::types::{Address, H256, U256};
+use serde::{Deserialize, Serialize};
+
+// A pool swap with its tokens resolved, as read for market data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwapTrade {
+    // Position in `indexed_events`
+    pub seq: i64,
+    pub chain_id: u64,
+    pub pool_id: String,
+    pub trader: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub amount_in: U256,
+    pub amount_out: U256,
+    // 18 when the token's metadata hasn't been resolved yet
+    pub decimals_in: u8,
+    pub decimals_out: u8,
+    pub timestamp: DateTime<Utc>,
+}
+
+// Liquidity added to or removed from a pool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidityChange {
+    pub seq: i64,
+    pub chain_id: u64,
+    pub pool_id: String,
+    pub provider: Address,
+    pub timestamp: DateTime<Utc>,
+}
+
+// A pool swap as exported, with where it happened on chain. Token addresses
+// and decimals are `None` until the pool's tokens are resolved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwapRecord {
+    pub chain_id: u64,
+    pub block_number: u64,
+    pub transaction_hash: H256,
+    pub log_index: u64,
+    pub pool_id: String,
+    pub trader: Address,
+    pub token_in: Option<Address>,
+    pub token_out: Option<Address>,
+    pub amount_in: U256,
+    pub amount_out: U256,
+    pub decimals_in: Option<u8>,
+    pub decimals_out: Option<u8>,
+    pub timestamp: DateTime<Utc>,
+}
@@ -0,0 +1,319 @@
+use axum::{extract::State, http::header, response::IntoResponse, routing::get, Router};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+
+use crate::{
+    config::MetricsConfig,
+    error::{IndexerError, Result},
+};
+
+// Upper bounds, in seconds, of the storage latency histogram buckets
+const STORAGE_LATENCY_BUCKETS: [f64; 10] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0];
+
+// Per-chain gauges
+#[derive(Debug, Clone, Default)]
+pub struct ChainMetrics {
+    pub blocks_indexed: u64,
+    pub events_indexed: u64,
+    pub sync_progress: f64,
+    pub blocks_behind: u64,
+    pub reorgs: u64,
+    pub events_rolled_back: u64,
+    pub malformed_logs: u64,
+    // Times head following dropped from WebSocket to HTTP polling
+    pub head_fallbacks: u64,
+    pub rpc_requests: u64,
+    pub rpc_errors: u64,
+    // Whether `blocks_behind` is over the chain's alert threshold
+    pub lag_alert: bool,
+    // Bridge transfers to this chain pending past their SLA
+    pub stuck_transfers: u64,
+}
+
+// Lag alert state change, for logging
+#[derive(Debug, Clone, PartialEq)]
+pub enum LagAlert {
+    Raised { chain_id: u64, blocks_behind: u64, threshold: u64 },
+    Cleared { chain_id: u64, blocks_behind: u64 },
+}
+
+#[derive(Debug, Default)]
+struct Histogram {
+    buckets: [u64; STORAGE_LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+// In-process indexer metrics
+#[derive(Debug, Default)]
+pub struct IndexerMetrics {
+    total_events_indexed: AtomicU64,
+    events_per_second: AtomicU64,
+    total_intents_processed: AtomicU64,
+    chains: RwLock<HashMap<u64, ChainMetrics>>,
+    storage_latency: RwLock<Histogram>,
+}
+
+impl IndexerMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_total_events_indexed(&self, value: u64) {
+        self.total_events_indexed.store(value, Ordering::Relaxed);
+    }
+
+    pub fn set_events_per_second(&self, value: f64) {
+        self.events_per_second.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn set_total_intents_processed(&self, value: u64) {
+        self.total_intents_processed.store(value, Ordering::Relaxed);
+    }
+
+    pub fn set_chain_blocks_indexed(&self, chain_id: u64, value: u64) {
+        self.update_chain(chain_id, |chain| chain.blocks_indexed = value);
+    }
+
+    pub fn set_chain_events_indexed(&self, chain_id: u64, value: u64) {
+        self.update_chain(chain_id, |chain| chain.events_indexed = value);
+    }
+
+    pub fn set_chain_sync_progress(&self, chain_id: u64, value: f64) {
+        self.update_chain(chain_id, |chain| chain.sync_progress = value);
+    }
+
+    pub fn set_chain_blocks_behind(&self, chain_id: u64, value: u64) {
+        self.update_chain(chain_id, |chain| chain.blocks_behind = value);
+    }
+
+    pub fn record_reorg(&self, chain_id: u64, events_rolled_back: u64) {
+        self.update_chain(chain_id, |chain| {
+            chain.reorgs += 1;
+            chain.events_rolled_back += events_rolled_back;
+        });
+    }
+
+    pub fn record_malformed_log(&self, chain_id: u64) {
+        self.update_chain(chain_id, |chain| chain.malformed_logs += 1);
+    }
+
+    pub fn record_head_fallback(&self, chain_id: u64) {
+        self.update_chain(chain_id, |chain| chain.head_fallbacks += 1);
+    }
+
+    // Stuck transfers per destination chain; chains left out have none
+    pub fn set_stuck_transfers(&self, counts: &HashMap<u64, u64>) {
+        let mut chains = self.chains.write().unwrap();
+        for (chain_id, chain) in chains.iter_mut() {
+            chain.stuck_transfers = counts.get(chain_id).copied().unwrap_or_default();
+        }
+        for (&chain_id, &count) in counts {
+            chains.entry(chain_id).or_default().stuck_transfers = count;
+        }
+    }
+
+    pub fn record_rpc(&self, chain_id: u64, ok: bool) {
+        self.update_chain(chain_id, |chain| {
+            chain.rpc_requests += 1;
+            if !ok {
+                chain.rpc_errors += 1;
+            }
+        });
+    }
+
+    pub fn observe_storage_latency(&self, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+        let mut histogram = self.storage_latency.write().unwrap();
+        histogram.count += 1;
+        histogram.sum += seconds;
+        for (bucket, bound) in histogram.buckets.iter_mut().zip(STORAGE_LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+    }
+
+    // Compare each chain's lag with its threshold and report the chains
+    // whose alert state changed
+    pub fn check_lag_alerts(&self, config: &MetricsConfig) -> Vec<LagAlert> {
+        let mut changes = Vec::new();
+        for (&chain_id, chain) in self.chains.write().unwrap().iter_mut() {
+            let threshold = config.lag_alert_threshold(chain_id);
+            let lagging = chain.blocks_behind > threshold;
+            if lagging == chain.lag_alert {
+                continue;
+            }
+            chain.lag_alert = lagging;
+            changes.push(if lagging {
+                LagAlert::Raised { chain_id, blocks_behind: chain.blocks_behind, threshold }
+            } else {
+                LagAlert::Cleared { chain_id, blocks_behind: chain.blocks_behind }
+            });
+        }
+        changes.sort_by_key(|change| match change {
+            LagAlert::Raised { chain_id, .. } | LagAlert::Cleared { chain_id, .. } => *chain_id,
+        });
+        changes
+    }
+
+    pub fn total_events_indexed(&self) -> u64 {
+        self.total_events_indexed.load(Ordering::Relaxed)
+    }
+
+    pub fn events_per_second(&self) -> f64 {
+        f64::from_bits(self.events_per_second.load(Ordering::Relaxed))
+    }
+
+    pub fn total_intents_processed(&self) -> u64 {
+        self.total_intents_processed.load(Ordering::Relaxed)
+    }
+
+    pub fn chain(&self, chain_id: u64) -> ChainMetrics {
+        self.chains.read().unwrap().get(&chain_id).cloned().unwrap_or_default()
+    }
+
+    // Prometheus text exposition format
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: Vec<(String, f64)>| {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            for (labels, value) in samples {
+                let _ = writeln!(out, "{}{} {}", name, labels, value);
+            }
+        };
+
+        metric("indexer_events_indexed_total", "counter", "Events stored across all chains",
+            vec![(String::new(), self.total_events_indexed() as f64)]);
+        metric("indexer_events_per_second", "gauge", "Recent indexing rate",
+            vec![(String::new(), self.events_per_second())]);
+        metric("indexer_intents_processed_total", "counter", "Intent events stored",
+            vec![(String::new(), self.total_intents_processed() as f64)]);
+
+        let chains: BTreeMap<u64, ChainMetrics> = self.chains.read().unwrap()
+            .iter()
+            .map(|(&chain_id, chain)| (chain_id, chain.clone()))
+            .collect();
+        let per_chain = |value: fn(&ChainMetrics) -> f64| {
+            chains.iter()
+                .map(|(chain_id, chain)| (format!("{{chain_id=\"{}\"}}", chain_id), value(chain)))
+                .collect::<Vec<_>>()
+        };
+        metric("indexer_chain_blocks_behind", "gauge", "Confirmed head minus indexed block",
+            per_chain(|c| c.blocks_behind as f64));
+        metric("indexer_chain_lag_alert", "gauge", "1 while blocks behind is over the alert threshold",
+            per_chain(|c| u8::from(c.lag_alert) as f64));
+        metric("indexer_chain_blocks_indexed", "gauge", "Last indexed block",
+            per_chain(|c| c.blocks_indexed as f64));
+        metric("indexer_chain_events_indexed", "gauge", "Events stored for the chain",
+            per_chain(|c| c.events_indexed as f64));
+        metric("indexer_chain_sync_progress", "gauge", "Fraction of the chain indexed",
+            per_chain(|c| c.sync_progress));
+        metric("indexer_chain_rpc_requests_total", "counter", "RPC requests made",
+            per_chain(|c| c.rpc_requests as f64));
+        metric("indexer_chain_rpc_errors_total", "counter", "RPC requests that failed",
+            per_chain(|c| c.rpc_errors as f64));
+        metric("indexer_chain_reorgs_total", "counter", "Reorgs rolled back",
+            per_chain(|c| c.reorgs as f64));
+        metric("indexer_chain_events_rolled_back_total", "counter", "Events removed by reorgs",
+            per_chain(|c| c.events_rolled_back as f64));
+        metric("indexer_chain_malformed_logs_total", "counter", "Logs rejected by the decoder",
+            per_chain(|c| c.malformed_logs as f64));
+        metric("indexer_chain_head_fallbacks_total", "counter", "Drops from WebSocket heads to polling",
+            per_chain(|c| c.head_fallbacks as f64));
+        metric("indexer_chain_bridge_transfers_stuck", "gauge", "Bridge transfers to the chain pending past their SLA",
+            per_chain(|c| c.stuck_transfers as f64));
+
+        let histogram = self.storage_latency.read().unwrap();
+        let mut samples: Vec<(String, f64)> = STORAGE_LATENCY_BUCKETS.iter()
+            .zip(histogram.buckets)
+            .map(|(bound, count)| (format!("_bucket{{le=\"{}\"}}", bound), count as f64))
+            .collect();
+        samples.push(("_bucket{le=\"+Inf\"}".to_string(), histogram.count as f64));
+        samples.push(("_sum".to_string(), histogram.sum));
+        samples.push(("_count".to_string(), histogram.count as f64));
+        metric("indexer_storage_latency_seconds", "histogram", "Time spent on storage writes", samples);
+
+        out
+    }
+
+    fn update_chain(&self, chain_id: u64, update: impl FnOnce(&mut ChainMetrics)) {
+        update(self.chains.write().unwrap().entry(chain_id).or_default());
+    }
+}
+
+// Serve `/metrics` on `addr` until the listener fails
+pub async fn serve(metrics: Arc<IndexerMetrics>, addr: SocketAddr) -> Result<()> {
+    let app = Router::new()
+        .route("/metrics", get(prometheus_metrics))
+        .with_state(metrics);
+
+    let listener = tokio::net::TcpListener::bind(addr).await
+        .map_err(|e| IndexerError::Internal(format!("Failed to bind metrics endpoint on {}: {}", addr, e)))?;
+    tracing::info!("Serving indexer metrics on {}", addr);
+
+    axum::serve(listener, app).await
+        .map_err(|e| IndexerError::Internal(format!("Metrics endpoint failed: {}", e)))
+}
+
+async fn prometheus_metrics(State(metrics): State<Arc<IndexerMetrics>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        metrics.render_prometheus(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lag_alerts_raise_and_clear_once() {
+        let metrics = IndexerMetrics::new();
+        let config = MetricsConfig {
+            lag_alert_blocks: 100,
+            chain_lag_alert_blocks: HashMap::from([(10, 5)]),
+            ..Default::default()
+        };
+
+        metrics.set_chain_blocks_behind(1, 50);
+        metrics.set_chain_blocks_behind(10, 50);
+        assert_eq!(
+            metrics.check_lag_alerts(&config),
+            vec![LagAlert::Raised { chain_id: 10, blocks_behind: 50, threshold: 5 }]
+        );
+        assert!(metrics.check_lag_alerts(&config).is_empty());
+
+        metrics.set_chain_blocks_behind(10, 2);
+        assert_eq!(
+            metrics.check_lag_alerts(&config),
+            vec![LagAlert::Cleared { chain_id: 10, blocks_behind: 2 }]
+        );
+    }
+
+    #[test]
+    fn test_render_prometheus() {
+        let metrics = IndexerMetrics::new();
+        metrics.record_rpc(1, true);
+        metrics.record_rpc(1, false);
+        metrics.record_reorg(1, 3);
+        metrics.observe_storage_latency(Duration::from_millis(20));
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains("# TYPE indexer_chain_rpc_errors_total counter"));
+        assert!(text.contains("indexer_chain_rpc_requests_total{chain_id=\"1\"} 2"));
+        assert!(text.contains("indexer_chain_events_rolled_back_total{chain_id=\"1\"} 3"));
+        assert!(text.contains("indexer_storage_latency_seconds_bucket{le=\"0.01\"} 0"));
+        assert!(text.contains("indexer_storage_latency_seconds_bucket{le=\"0.025\"} 1"));
+        assert!(text.contains("indexer_storage_latency_seconds_count 1"));
+    }
+}
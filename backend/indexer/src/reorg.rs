@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+use ethers::types::H256;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Hashes of the last `max_depth` indexed blocks of one chain. A block whose
+// parent hash, or whose own hash at an already indexed height, disagrees
+// with the window means the chain reorganised under the indexer.
+#[derive(Debug, Clone)]
+pub struct BlockHashWindow {
+    max_depth: u64,
+    hashes: BTreeMap<u64, H256>,
+}
+
+impl BlockHashWindow {
+    pub fn new(max_depth: u64) -> Self {
+        Self {
+            max_depth: max_depth.max(1),
+            hashes: BTreeMap::new(),
+        }
+    }
+
+    // Window restored from stored `(block_number, hash)` pairs
+    pub fn from_recent(max_depth: u64, recent: impl IntoIterator<Item = (u64, H256)>) -> Self {
+        let mut window = Self::new(max_depth);
+        for (block_number, hash) in recent {
+            window.record(block_number, hash);
+        }
+        window
+    }
+
+    pub fn hash(&self, block_number: u64) -> Option<H256> {
+        self.hashes.get(&block_number).copied()
+    }
+
+    pub fn head(&self) -> Option<u64> {
+        self.hashes.keys().next_back().copied()
+    }
+
+    // Whether a block with `hash` and `parent_hash` at `block_number`
+    // contradicts the indexed chain
+    pub fn conflicts(&self, block_number: u64, hash: H256, parent_hash: H256) -> bool {
+        let replaced = self.hash(block_number).map_or(false, |known| known != hash);
+        let orphaned = block_number
+            .checked_sub(1)
+            .and_then(|parent| self.hash(parent))
+            .map_or(false, |known| known != parent_hash);
+        replaced || orphaned
+    }
+
+    pub fn record(&mut self, block_number: u64, hash: H256) {
+        self.hashes.insert(block_number, hash);
+        if let Some(head) = self.head() {
+            let oldest = head.saturating_sub(self.max_depth - 1);
+            self.hashes = self.hashes.split_off(&oldest);
+        }
+    }
+
+    // Indexed blocks, newest first, for walking back to the fork point
+    pub fn newest_first(&self) -> Vec<(u64, H256)> {
+        self.hashes.iter().rev().map(|(&number, &hash)| (number, hash)).collect()
+    }
+
+    // Forget every block above `fork_block`
+    pub fn rewind(&mut self, fork_block: u64) {
+        self.hashes.split_off(&(fork_block + 1));
+    }
+
+    // Lowest block the window can still roll back to
+    pub fn oldest(&self) -> Option<u64> {
+        self.hashes.keys().next().copied()
+    }
+}
+
+// Sent on the event stream after a rollback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReorgNotification {
+    pub chain_id: u64,
+    // Last block both chains agree on; everything above it was rolled back
+    pub fork_block: u64,
+    // Highest block indexed before the rollback
+    pub previous_head: u64,
+    pub events_rolled_back: u64,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl ReorgNotification {
+    pub fn depth(&self) -> u64 {
+        self.previous_head.saturating_sub(self.fork_block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(n: u64, fork: u8) -> H256 {
+        let mut bytes = [fork; 32];
+        bytes[..8].copy_from_slice(&n.to_be_bytes());
+        H256::from(bytes)
+    }
+
+    #[test]
+    fn test_detects_orphaned_and_replaced_blocks() {
+        let window = BlockHashWindow::from_recent(64, (100..=105).map(|n| (n, hash(n, 0))));
+
+        assert!(!window.conflicts(106, hash(106, 0), hash(105, 0)));
+        assert!(window.conflicts(106, hash(106, 1), hash(105, 1)));
+        assert!(window.conflicts(104, hash(104, 1), hash(103, 0)));
+        // Nothing known below the window
+        assert!(!window.conflicts(100, hash(100, 0), hash(99, 1)));
+    }
+
+    #[test]
+    fn test_window_prunes_and_rewinds() {
+        let mut window = BlockHashWindow::new(4);
+        for n in 1..=10 {
+            window.record(n, hash(n, 0));
+        }
+        assert_eq!((window.oldest(), window.head()), (Some(7), Some(10)));
+
+        window.rewind(8);
+        assert_eq!(window.head(), Some(8));
+        assert_eq!(window.newest_first(), vec![(8, hash(8, 0)), (7, hash(7, 0))]);
+    }
+}
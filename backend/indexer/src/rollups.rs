@@ -0,0 +1,208 @@
+use chrono::{DateTime, Duration as ChronoDuration, DurationRound, Utc};
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+use std::{str::FromStr, sync::Arc};
+use tokio::time::{interval, Duration};
+
+use crate::{
+    config::RollupConfig,
+    error::{IndexerError, Result},
+    storage::IndexerStorage,
+};
+
+// Postgres channel told each time rollups are recomputed, so readers can
+// drop anything cached from the old ones. The payload is the run's time in
+// milliseconds.
+pub const ROLLUPS_UPDATED_CHANNEL: &str = "rollups_updated";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Granularity {
+    Hourly,
+    Daily,
+}
+
+impl Granularity {
+    pub const ALL: [Granularity; 2] = [Granularity::Hourly, Granularity::Daily];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hourly => "hourly",
+            Self::Daily => "daily",
+        }
+    }
+
+    // Unit for Postgres `date_trunc`
+    pub fn trunc_unit(&self) -> &'static str {
+        match self {
+            Self::Hourly => "hour",
+            Self::Daily => "day",
+        }
+    }
+
+    pub fn length(&self) -> ChronoDuration {
+        match self {
+            Self::Hourly => ChronoDuration::hours(1),
+            Self::Daily => ChronoDuration::days(1),
+        }
+    }
+
+    pub fn bucket_start(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        at.duration_trunc(self.length()).unwrap_or(at)
+    }
+
+    // Start of the previous bucket: recomputing from there picks up events
+    // indexed late, e.g. by backfill or after a reorg
+    pub fn recompute_from(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.bucket_start(now) - self.length()
+    }
+}
+
+impl FromStr for Granularity {
+    type Err = IndexerError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "hourly" => Ok(Self::Hourly),
+            "daily" => Ok(Self::Daily),
+            other => Err(IndexerError::Config(format!("Unknown rollup granularity: {}", other))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolVolumeRollup {
+    pub bucket_start: DateTime<Utc>,
+    pub chain_id: u64,
+    pub pool_id: String,
+    pub token_in: String,
+    pub swaps: u64,
+    pub volume_in: U256,
+    pub volume_out: U256,
+    // Estimated from the configured fee rate, as swap events carry no fee
+    pub fees: U256,
+}
+
+// Pool value at the end of a bucket, in pool token units
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolTvlRollup {
+    pub bucket_start: DateTime<Utc>,
+    pub chain_id: u64,
+    pub pool_id: String,
+    pub tvl: U256,
+    pub liquidity_added: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentRollup {
+    pub bucket_start: DateTime<Utc>,
+    pub chain_id: u64,
+    pub created: u64,
+    pub matched: u64,
+    pub executed: u64,
+    pub succeeded: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolverRollup {
+    pub bucket_start: DateTime<Utc>,
+    pub chain_id: u64,
+    pub solver: String,
+    pub matched: u64,
+    pub executed: u64,
+    pub succeeded: u64,
+}
+
+impl SolverRollup {
+    // Share of matched intents the solver executed successfully
+    pub fn fill_rate(&self) -> f64 {
+        if self.matched == 0 {
+            0.0
+        } else {
+            self.succeeded as f64 / self.matched as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BridgeLatencyRollup {
+    pub bucket_start: DateTime<Utc>,
+    pub source_chain_id: u64,
+    pub destination_chain_id: u64,
+    pub legs: u64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+}
+
+// Fee on `volume` at `fee_bps` basis points
+pub fn estimate_fees(volume: U256, fee_bps: u32) -> U256 {
+    volume.saturating_mul(U256::from(fee_bps)) / U256::from(10_000u32)
+}
+
+// Recomputes the recent rollup buckets and the solver leaderboard on a
+// schedule
+pub struct RollupJob {
+    config: RollupConfig,
+    storage: Arc<IndexerStorage>,
+}
+
+impl RollupJob {
+    pub fn new(config: RollupConfig, storage: Arc<IndexerStorage>) -> Self {
+        Self { config, storage }
+    }
+
+    pub async fn run(self) {
+        let mut ticks = interval(Duration::from_secs(self.config.interval_secs.max(1)));
+        loop {
+            ticks.tick().await;
+            if let Err(e) = self.compute(Utc::now()).await {
+                tracing::warn!("Failed to compute rollups: {}", e);
+            }
+        }
+    }
+
+    pub async fn compute(&self, now: DateTime<Utc>) -> Result<()> {
+        for granularity in Granularity::ALL {
+            let from = granularity.recompute_from(now);
+            self.storage.compute_rollups(granularity, from, &self.config).await?;
+            tracing::debug!("Recomputed {} rollups from {}", granularity.as_str(), from);
+        }
+        let solvers = self.storage.refresh_solver_stats().await?;
+        tracing::debug!("Refreshed stats for {} solvers", solvers);
+        self.storage.notify_rollups_updated(now).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_buckets_and_recompute_window() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 5, 14, 37, 12).unwrap();
+        assert_eq!(Granularity::Hourly.bucket_start(now), Utc.with_ymd_and_hms(2024, 3, 5, 14, 0, 0).unwrap());
+        assert_eq!(Granularity::Daily.bucket_start(now), Utc.with_ymd_and_hms(2024, 3, 5, 0, 0, 0).unwrap());
+        assert_eq!(Granularity::Hourly.recompute_from(now), Utc.with_ymd_and_hms(2024, 3, 5, 13, 0, 0).unwrap());
+        assert_eq!(Granularity::Daily.recompute_from(now), Utc.with_ymd_and_hms(2024, 3, 4, 0, 0, 0).unwrap());
+        assert_eq!("daily".parse::<Granularity>().unwrap(), Granularity::Daily);
+        assert!("weekly".parse::<Granularity>().is_err());
+    }
+
+    #[test]
+    fn test_fee_estimate_and_fill_rate() {
+        assert_eq!(estimate_fees(U256::from(1_000_000u64), 30), U256::from(3_000u64));
+        assert_eq!(estimate_fees(U256::MAX, 30), U256::MAX / U256::from(10_000u32));
+
+        let solver = SolverRollup {
+            bucket_start: Utc::now(),
+            chain_id: 1,
+            solver: "0xabc".to_string(),
+            matched: 4,
+            executed: 4,
+            succeeded: 3,
+        };
+        assert_eq!(solver.fill_rate(), 0.75);
+    }
+}
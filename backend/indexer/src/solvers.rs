@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+
+// Score points lost per slashing, on the matcher's 0-10000 reputation scale
+const SLASHING_PENALTY: u64 = 1_000;
+// Average slippage beyond this many basis points costs the whole score
+const MAX_SLIPPAGE_BPS: f64 = 500.0;
+
+// A solver's track record across every indexed chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolverStats {
+    pub solver: String,
+    pub intents_matched: u64,
+    pub intents_executed: u64,
+    pub intents_succeeded: u64,
+    // Matched to executed, over intents with both events indexed
+    pub avg_latency_ms: Option<f64>,
+    // Quoted against realized output, over executions with a recorded quote.
+    // Positive when the solver delivered less than it quoted.
+    pub avg_slippage_bps: Option<f64>,
+    pub slippage_samples: u64,
+    pub slashings: u64,
+    pub slashed_amount: U256,
+    pub last_active_at: Option<DateTime<Utc>>,
+}
+
+impl SolverStats {
+    // Share of matched intents the solver executed successfully
+    pub fn fill_rate(&self) -> f64 {
+        if self.intents_matched == 0 {
+            0.0
+        } else {
+            (self.intents_succeeded as f64 / self.intents_matched as f64).min(1.0)
+        }
+    }
+
+    // Fill rate scaled to 0-10000, less slippage and slashing penalties
+    pub fn reputation_score(&self) -> u64 {
+        let base = self.fill_rate() * 10_000.0;
+        let slippage = self.avg_slippage_bps.unwrap_or(0.0).clamp(0.0, MAX_SLIPPAGE_BPS);
+        let after_slippage = base * (1.0 - slippage / MAX_SLIPPAGE_BPS);
+        (after_slippage as u64).saturating_sub(self.slashings.saturating_mul(SLASHING_PENALTY))
+    }
+}
+
+// Shortfall of `realized` against `quoted` in basis points; negative when
+// the solver delivered more than quoted
+pub fn slippage_bps(quoted: U256, realized: U256) -> Option<f64> {
+    if quoted.is_zero() {
+        return None;
+    }
+    let quoted = crate::enrichment::to_units(quoted, 0);
+    let realized = crate::enrichment::to_units(realized, 0);
+    Some((quoted - realized) / quoted * 10_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(matched: u64, succeeded: u64, slippage: Option<f64>, slashings: u64) -> SolverStats {
+        SolverStats {
+            solver: "0x01".to_string(),
+            intents_matched: matched,
+            intents_executed: succeeded,
+            intents_succeeded: succeeded,
+            avg_latency_ms: None,
+            avg_slippage_bps: slippage,
+            slippage_samples: 0,
+            slashings,
+            slashed_amount: U256::zero(),
+            last_active_at: None,
+        }
+    }
+
+    #[test]
+    fn test_slippage_against_quote() {
+        assert_eq!(slippage_bps(U256::from(10_000u64), U256::from(9_950u64)), Some(50.0));
+        assert_eq!(slippage_bps(U256::from(10_000u64), U256::from(10_100u64)), Some(-100.0));
+        assert_eq!(slippage_bps(U256::zero(), U256::from(1u64)), None);
+    }
+
+    #[test]
+    fn test_reputation_score_penalizes_slippage_and_slashing() {
+        assert_eq!(stats(0, 0, None, 0).reputation_score(), 0);
+        assert_eq!(stats(10, 9, None, 0).reputation_score(), 9_000);
+        // Better-than-quoted fills are not rewarded beyond the fill rate
+        assert_eq!(stats(10, 10, Some(-20.0), 0).reputation_score(), 10_000);
+        assert_eq!(stats(10, 10, Some(250.0), 0).reputation_score(), 5_000);
+        assert_eq!(stats(10, 10, None, 2).reputation_score(), 8_000);
+        assert_eq!(stats(10, 1, None, 5).reputation_score(), 0);
+    }
+}
@@ -0,0 +1,2401 @@
+use chrono::{DateTime, Utc};
+use ethers::types::{Address, H256};
+use futures::stream::{BoxStream, StreamExt};
+use sqlx::{postgres::{PgArguments, PgPoolOptions, PgRow}, query::Query, PgConnection, PgExecutor, PgPool, Postgres, QueryBuilder, Row};
+use std::{collections::HashMap, str::FromStr};
+
+use crate::{
+    archive::DailyRollup,
+    config::RollupConfig,
+    enrichment::{usd_value, TokenMetadata},
+    error::{IndexerError, Result},
+    backfill::BlockRange,
+    events::{DecodedEvent, ProcessedLog},
+    journeys::{BridgeLeg, BridgeMilestone, IntentJourney, IntentMilestone, IntentPhase, PhaseLatency},
+    market::{LiquidityChange, SwapRecord, SwapTrade},
+    solvers::{slippage_bps, SolverStats},
+    transfers::{BridgeTransfer, TransferEnd, TransferStatus},
+    watchlist::WatchedContract,
+    rollups::{
+        estimate_fees, BridgeLatencyRollup, Granularity, IntentRollup, PoolTvlRollup, PoolVolumeRollup, SolverRollup,
+        ROLLUPS_UPDATED_CHANNEL,
+    },
+    ChainState, ChainStats, EventFilter, IndexedEvent, IndexerStats,
+};
+
+// Postgres-backed indexer storage
+pub struct IndexerStorage {
+    pool: PgPool,
+}
+
+impl IndexerStorage {
+    pub async fn new(database_url: &str) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(10)
+            .acquire_timeout(std::time::Duration::from_secs(30))
+            .connect(database_url)
+            .await?;
+
+        Ok(Self { pool })
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    pub async fn initialize(&self) -> Result<()> {
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS chain_states (
+                chain_id BIGINT PRIMARY KEY,
+                latest_block BIGINT NOT NULL,
+                latest_block_hash VARCHAR(66) NOT NULL,
+                indexed_block BIGINT NOT NULL,
+                confirmation_blocks BIGINT NOT NULL,
+                is_synced BOOLEAN NOT NULL DEFAULT false,
+                last_update TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS indexed_events (
+                id UUID PRIMARY KEY,
+                chain_id BIGINT NOT NULL,
+                block_number BIGINT NOT NULL,
+                transaction_hash VARCHAR(66) NOT NULL,
+                transaction_index BIGINT NOT NULL,
+                log_index BIGINT NOT NULL,
+                event_type VARCHAR(64) NOT NULL,
+                contract_address VARCHAR(42) NOT NULL,
+                event_data JSONB NOT NULL,
+                timestamp TIMESTAMPTZ NOT NULL,
+                processed BOOLEAN NOT NULL DEFAULT false,
+                seq BIGSERIAL NOT NULL
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Insertion order, which event handlers track their offsets by
+        sqlx::query("ALTER TABLE indexed_events ADD COLUMN IF NOT EXISTS seq BIGSERIAL NOT NULL")
+            .execute(&self.pool)
+            .await?;
+
+        // Each log is stored once; drop duplicates written before the key
+        // existed, keeping the first copy
+        sqlx::query(r#"
+            DELETE FROM indexed_events a USING indexed_events b
+            WHERE a.chain_id = b.chain_id
+              AND a.transaction_hash = b.transaction_hash
+              AND a.log_index = b.log_index
+              AND a.seq > b.seq
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_indexed_events_log ON indexed_events(chain_id, transaction_hash, log_index)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS handler_offsets (
+                handler VARCHAR(64) PRIMARY KEY,
+                last_seq BIGINT NOT NULL,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Hashes of recently indexed blocks, for reorg detection
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS block_hashes (
+                chain_id BIGINT NOT NULL,
+                block_number BIGINT NOT NULL,
+                block_hash VARCHAR(66) NOT NULL,
+                parent_hash VARCHAR(66) NOT NULL,
+                PRIMARY KEY (chain_id, block_number)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Typed rows of decoded events; each belongs to one indexed event
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS intent_events (
+                event_id UUID PRIMARY KEY REFERENCES indexed_events(id) ON DELETE CASCADE,
+                chain_id BIGINT NOT NULL,
+                block_number BIGINT NOT NULL,
+                intent_id VARCHAR(66) NOT NULL,
+                kind VARCHAR(16) NOT NULL,
+                account VARCHAR(42) NOT NULL,
+                success BOOLEAN,
+                event_timestamp TEXT
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS pool_swaps (
+                event_id UUID PRIMARY KEY REFERENCES indexed_events(id) ON DELETE CASCADE,
+                chain_id BIGINT NOT NULL,
+                block_number BIGINT NOT NULL,
+                pool_id TEXT NOT NULL,
+                trader VARCHAR(42) NOT NULL,
+                token_in TEXT NOT NULL,
+                token_out TEXT NOT NULL,
+                amount_in TEXT NOT NULL,
+                amount_out TEXT NOT NULL
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS pool_liquidity (
+                event_id UUID PRIMARY KEY REFERENCES indexed_events(id) ON DELETE CASCADE,
+                chain_id BIGINT NOT NULL,
+                block_number BIGINT NOT NULL,
+                pool_id TEXT NOT NULL,
+                provider VARCHAR(42) NOT NULL,
+                amount0 TEXT NOT NULL,
+                amount1 TEXT NOT NULL
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS bridge_events (
+                event_id UUID PRIMARY KEY REFERENCES indexed_events(id) ON DELETE CASCADE,
+                chain_id BIGINT NOT NULL,
+                block_number BIGINT NOT NULL,
+                message_hash VARCHAR(66) NOT NULL,
+                kind VARCHAR(16) NOT NULL,
+                sender VARCHAR(42),
+                recipient VARCHAR(42) NOT NULL,
+                token VARCHAR(42) NOT NULL,
+                amount TEXT NOT NULL,
+                counterpart_chain_id BIGINT NOT NULL,
+                nonce TEXT NOT NULL
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // One row per intent, rebuilt from its events whenever they change
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS intent_journeys (
+                intent_id VARCHAR(66) PRIMARY KEY,
+                user_address VARCHAR(42),
+                solver VARCHAR(42),
+                source_chain_id BIGINT,
+                created_at TIMESTAMPTZ,
+                matched_at TIMESTAMPTZ,
+                executed_at TIMESTAMPTZ,
+                success BOOLEAN,
+                bridge_legs JSONB NOT NULL DEFAULT '[]',
+                matching_latency_ms BIGINT,
+                execution_latency_ms BIGINT,
+                bridging_latency_ms BIGINT,
+                total_latency_ms BIGINT,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Block ranges indexed so far, by live indexing or backfill
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS block_coverage (
+                chain_id BIGINT NOT NULL,
+                from_block BIGINT NOT NULL,
+                to_block BIGINT NOT NULL,
+                PRIMARY KEY (chain_id, from_block)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Daily event counts, which outlive events pruned to cold storage
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS event_rollups_daily (
+                chain_id BIGINT NOT NULL,
+                day DATE NOT NULL,
+                event_type VARCHAR(64) NOT NULL,
+                archived_events BIGINT NOT NULL,
+                PRIMARY KEY (chain_id, day, event_type)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Analytics rollups, recomputed by `compute_rollups`
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS pool_volume_rollups (
+                granularity VARCHAR(8) NOT NULL,
+                bucket_start TIMESTAMPTZ NOT NULL,
+                chain_id BIGINT NOT NULL,
+                pool_id TEXT NOT NULL,
+                token_in TEXT NOT NULL,
+                swaps BIGINT NOT NULL,
+                volume_in TEXT NOT NULL,
+                volume_out TEXT NOT NULL,
+                fees TEXT NOT NULL,
+                PRIMARY KEY (granularity, bucket_start, chain_id, pool_id, token_in)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Pool value at the end of each bucket: liquidity added plus net swap
+        // inflow, in pool token units. Buckets without activity have no row.
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS pool_tvl_rollups (
+                granularity VARCHAR(8) NOT NULL,
+                bucket_start TIMESTAMPTZ NOT NULL,
+                chain_id BIGINT NOT NULL,
+                pool_id TEXT NOT NULL,
+                tvl TEXT NOT NULL,
+                liquidity_added TEXT NOT NULL,
+                PRIMARY KEY (granularity, bucket_start, chain_id, pool_id)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS intent_rollups (
+                granularity VARCHAR(8) NOT NULL,
+                bucket_start TIMESTAMPTZ NOT NULL,
+                chain_id BIGINT NOT NULL,
+                created BIGINT NOT NULL,
+                matched BIGINT NOT NULL,
+                executed BIGINT NOT NULL,
+                succeeded BIGINT NOT NULL,
+                PRIMARY KEY (granularity, bucket_start, chain_id)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS solver_rollups (
+                granularity VARCHAR(8) NOT NULL,
+                bucket_start TIMESTAMPTZ NOT NULL,
+                chain_id BIGINT NOT NULL,
+                solver VARCHAR(42) NOT NULL,
+                matched BIGINT NOT NULL,
+                executed BIGINT NOT NULL,
+                succeeded BIGINT NOT NULL,
+                PRIMARY KEY (granularity, bucket_start, chain_id, solver)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS bridge_latency_rollups (
+                granularity VARCHAR(8) NOT NULL,
+                bucket_start TIMESTAMPTZ NOT NULL,
+                source_chain_id BIGINT NOT NULL,
+                destination_chain_id BIGINT NOT NULL,
+                legs BIGINT NOT NULL,
+                p50_ms DOUBLE PRECISION NOT NULL,
+                p90_ms DOUBLE PRECISION NOT NULL,
+                p99_ms DOUBLE PRECISION NOT NULL,
+                PRIMARY KEY (granularity, bucket_start, source_chain_id, destination_chain_id)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS solver_slashings (
+                event_id UUID PRIMARY KEY REFERENCES indexed_events(id) ON DELETE CASCADE,
+                chain_id BIGINT NOT NULL,
+                block_number BIGINT NOT NULL,
+                solver VARCHAR(42) NOT NULL,
+                amount TEXT NOT NULL,
+                intent_id VARCHAR(66) NOT NULL
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Quotes solvers won intents with, reported off chain, to measure
+        // realized slippage against
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS intent_quotes (
+                intent_id VARCHAR(66) NOT NULL,
+                solver VARCHAR(42) NOT NULL,
+                quoted_output TEXT NOT NULL,
+                quoted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                PRIMARY KEY (intent_id, solver)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Bridge messages matched across chains, kept in step with
+        // bridge_events by `refresh_transfers`
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS bridge_transfers (
+                message_hash VARCHAR(66) PRIMARY KEY,
+                nonce TEXT NOT NULL,
+                source_chain_id BIGINT NOT NULL,
+                destination_chain_id BIGINT NOT NULL,
+                sender VARCHAR(42),
+                recipient VARCHAR(42) NOT NULL,
+                token VARCHAR(42) NOT NULL,
+                amount TEXT NOT NULL,
+                source_tx VARCHAR(66),
+                destination_tx VARCHAR(66),
+                sent_at TIMESTAMPTZ,
+                received_at TIMESTAMPTZ,
+                status VARCHAR(16) NOT NULL,
+                latency_ms BIGINT,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Solver leaderboard, rebuilt by `refresh_solver_stats`
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS solver_stats (
+                solver VARCHAR(42) PRIMARY KEY,
+                intents_matched BIGINT NOT NULL,
+                intents_executed BIGINT NOT NULL,
+                intents_succeeded BIGINT NOT NULL,
+                fill_rate DOUBLE PRECISION NOT NULL,
+                avg_latency_ms DOUBLE PRECISION,
+                avg_slippage_bps DOUBLE PRECISION,
+                slippage_samples BIGINT NOT NULL,
+                slashings BIGINT NOT NULL,
+                slashed_amount TEXT NOT NULL,
+                reputation_score BIGINT NOT NULL,
+                last_active_at TIMESTAMPTZ,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Token metadata and prices for USD enrichment
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS token_metadata (
+                chain_id BIGINT NOT NULL,
+                address VARCHAR(42) NOT NULL,
+                symbol TEXT NOT NULL,
+                decimals SMALLINT NOT NULL,
+                PRIMARY KEY (chain_id, address)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Token addresses behind the indices swap events carry
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS pool_tokens (
+                chain_id BIGINT NOT NULL,
+                pool_id TEXT NOT NULL,
+                token_index TEXT NOT NULL,
+                token VARCHAR(42) NOT NULL,
+                PRIMARY KEY (chain_id, pool_id, token_index)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS token_prices (
+                chain_id BIGINT NOT NULL,
+                token VARCHAR(42) NOT NULL,
+                observed_at TIMESTAMPTZ NOT NULL,
+                price_usd DOUBLE PRECISION NOT NULL,
+                PRIMARY KEY (chain_id, token, observed_at)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // USD values next to the raw amounts; `enriched_at` is set once a
+        // row has been priced, whether or not a price was known
+        for statement in [
+            "ALTER TABLE pool_swaps ADD COLUMN IF NOT EXISTS token_in_address VARCHAR(42)",
+            "ALTER TABLE pool_swaps ADD COLUMN IF NOT EXISTS token_out_address VARCHAR(42)",
+            "ALTER TABLE pool_swaps ADD COLUMN IF NOT EXISTS amount_in_usd DOUBLE PRECISION",
+            "ALTER TABLE pool_swaps ADD COLUMN IF NOT EXISTS amount_out_usd DOUBLE PRECISION",
+            "ALTER TABLE pool_swaps ADD COLUMN IF NOT EXISTS enriched_at TIMESTAMPTZ",
+            "ALTER TABLE bridge_events ADD COLUMN IF NOT EXISTS amount_usd DOUBLE PRECISION",
+            "ALTER TABLE bridge_events ADD COLUMN IF NOT EXISTS enriched_at TIMESTAMPTZ",
+        ] {
+            sqlx::query(statement).execute(&self.pool).await?;
+        }
+
+        // Contracts watched at runtime, on top of each chain's configured
+        // contracts, and how far their own backfill got
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS watched_contracts (
+                chain_id BIGINT NOT NULL,
+                address VARCHAR(42) NOT NULL,
+                start_block BIGINT NOT NULL,
+                events TEXT[] NOT NULL DEFAULT '{}',
+                backfilled_to BIGINT,
+                added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                PRIMARY KEY (chain_id, address)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        // Completed backfill ranges, for resuming an interrupted backfill
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS backfill_ranges (
+                chain_id BIGINT NOT NULL,
+                from_block BIGINT NOT NULL,
+                to_block BIGINT NOT NULL,
+                events BIGINT NOT NULL,
+                completed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                PRIMARY KEY (chain_id, from_block)
+            )
+        "#)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_indexed_events_chain_block ON indexed_events(chain_id, block_number)")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_indexed_events_event_type ON indexed_events(event_type)")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_indexed_events_seq ON indexed_events(seq)")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_indexed_events_timestamp ON indexed_events(timestamp)")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_indexed_events_chain_tx ON indexed_events(chain_id, transaction_hash)")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_intent_events_intent ON intent_events(intent_id)")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_bridge_events_message ON bridge_events(message_hash)")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_bridge_transfers_status ON bridge_transfers(status, sent_at)")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_bridge_transfers_source_tx ON bridge_transfers(source_tx)")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_bridge_transfers_destination_tx ON bridge_transfers(destination_tx)")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_bridge_transfers_sender ON bridge_transfers(sender)")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_bridge_transfers_recipient ON bridge_transfers(recipient)")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_pool_swaps_unenriched ON pool_swaps(event_id) WHERE enriched_at IS NULL")
+            .execute(&self.pool).await.ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_bridge_events_unenriched ON bridge_events(event_id) WHERE enriched_at IS NULL")
+            .execute(&self.pool).await.ok();
+
+        Ok(())
+    }
+
+    pub async fn get_chain_state(&self, chain_id: u64) -> Result<Option<ChainState>> {
+        let row = sqlx::query("SELECT * FROM chain_states WHERE chain_id = $1")
+            .bind(chain_id as i64)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| {
+            Ok(ChainState {
+                chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+                latest_block: row.try_get::<i64, _>("latest_block")? as u64,
+                latest_block_hash: parse_hash(row.try_get("latest_block_hash")?)?,
+                indexed_block: row.try_get::<i64, _>("indexed_block")? as u64,
+                confirmation_blocks: row.try_get::<i64, _>("confirmation_blocks")? as u64,
+                is_synced: row.try_get("is_synced")?,
+                last_update: row.try_get("last_update")?,
+            })
+        })
+        .transpose()
+    }
+
+    pub async fn save_chain_state(&self, state: &ChainState) -> Result<()> {
+        sqlx::query(r#"
+            INSERT INTO chain_states (
+                chain_id, latest_block, latest_block_hash, indexed_block,
+                confirmation_blocks, is_synced, last_update
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (chain_id) DO UPDATE SET
+                latest_block = EXCLUDED.latest_block,
+                latest_block_hash = EXCLUDED.latest_block_hash,
+                indexed_block = EXCLUDED.indexed_block,
+                confirmation_blocks = EXCLUDED.confirmation_blocks,
+                is_synced = EXCLUDED.is_synced,
+                last_update = EXCLUDED.last_update
+        "#)
+        .bind(state.chain_id as i64)
+        .bind(state.latest_block as i64)
+        .bind(format!("{:#x}", state.latest_block_hash))
+        .bind(state.indexed_block as i64)
+        .bind(state.confirmation_blocks as i64)
+        .bind(state.is_synced)
+        .bind(state.last_update)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // Returns false when the log was already stored
+    pub async fn store_event(&self, event: &IndexedEvent) -> Result<bool> {
+        insert_event(&self.pool, event).await
+    }
+
+    // Store an event and its typed row atomically. Returns false, writing
+    // nothing, when the log was already stored.
+    pub async fn store_processed(&self, processed: &ProcessedLog) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+        if !insert_event(&mut *tx, &processed.event).await? {
+            return Ok(false);
+        }
+        insert_typed_row(&mut *tx, &processed.event, &processed.decoded).await?;
+        let block = processed.event.block_number;
+        let intents = affected_intents(&mut tx, processed.event.chain_id, block, block).await?;
+        refresh_journeys(&mut tx, &intents).await?;
+        let messages = affected_messages(&mut tx, processed.event.chain_id, block, block).await?;
+        refresh_transfers(&mut tx, &messages).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    pub async fn query_events(&self, filter: EventFilter, limit: Option<u64>) -> Result<Vec<IndexedEvent>> {
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM indexed_events WHERE TRUE");
+        if let Some(address) = filter.contract_address {
+            query.push(" AND contract_address = ").push_bind(format!("{:#x}", address));
+        }
+        if let Some(signature) = filter.event_signature {
+            let name = DecodedEvent::name_of(signature).unwrap_or("Unknown");
+            query.push(" AND event_type = ").push_bind(name);
+        }
+        if let Some(from_block) = filter.from_block {
+            query.push(" AND block_number >= ").push_bind(from_block as i64);
+        }
+        if let Some(to_block) = filter.to_block {
+            query.push(" AND block_number <= ").push_bind(to_block as i64);
+        }
+        query.push(" ORDER BY block_number, log_index LIMIT ").push_bind(limit.unwrap_or(1000) as i64);
+
+        let rows = query.build().fetch_all(&self.pool).await?;
+        rows.iter().map(event_from_row).collect()
+    }
+
+    pub async fn get_stats(&self) -> Result<IndexerStats> {
+        let rows = sqlx::query(r#"
+            SELECT s.chain_id, s.latest_block, s.indexed_block,
+                   (SELECT COUNT(*) FROM indexed_events e WHERE e.chain_id = s.chain_id) AS events
+            FROM chain_states s
+        "#)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut chains = HashMap::new();
+        for row in rows {
+            let chain_id = row.try_get::<i64, _>("chain_id")? as u64;
+            let latest_block = row.try_get::<i64, _>("latest_block")? as u64;
+            let indexed_block = row.try_get::<i64, _>("indexed_block")? as u64;
+            chains.insert(chain_id, ChainStats {
+                chain_id,
+                blocks_indexed: indexed_block,
+                events_indexed: row.try_get::<i64, _>("events")? as u64,
+                sync_progress: if latest_block == 0 { 0.0 } else { indexed_block as f64 / latest_block as f64 },
+                blocks_behind: latest_block.saturating_sub(indexed_block),
+                avg_block_time: 0.0,
+                last_indexed_block: indexed_block,
+                status: None,
+            });
+        }
+
+        let intents: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM indexed_events WHERE event_type = 'IntentCreated'")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(IndexerStats {
+            total_events_indexed: chains.values().map(|chain| chain.events_indexed).sum(),
+            chains,
+            events_per_second: 0.0,
+            total_intents_processed: intents.0 as u64,
+            uptime: 0,
+            last_update: chrono::Utc::now(),
+        })
+    }
+
+    pub async fn save_block_hash(&self, chain_id: u64, block_number: u64, block_hash: H256, parent_hash: H256) -> Result<()> {
+        sqlx::query(r#"
+            INSERT INTO block_hashes (chain_id, block_number, block_hash, parent_hash)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (chain_id, block_number) DO UPDATE SET
+                block_hash = EXCLUDED.block_hash,
+                parent_hash = EXCLUDED.parent_hash
+        "#)
+        .bind(chain_id as i64)
+        .bind(block_number as i64)
+        .bind(format!("{:#x}", block_hash))
+        .bind(format!("{:#x}", parent_hash))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // Drop hashes older than the reorg window
+    pub async fn prune_block_hashes(&self, chain_id: u64, below_block: u64) -> Result<()> {
+        sqlx::query("DELETE FROM block_hashes WHERE chain_id = $1 AND block_number < $2")
+            .bind(chain_id as i64)
+            .bind(below_block as i64)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // Most recent `limit` block hashes, newest first
+    pub async fn recent_block_hashes(&self, chain_id: u64, limit: u64) -> Result<Vec<(u64, H256)>> {
+        let rows = sqlx::query(r#"
+            SELECT block_number, block_hash FROM block_hashes
+            WHERE chain_id = $1
+            ORDER BY block_number DESC
+            LIMIT $2
+        "#)
+        .bind(chain_id as i64)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get::<i64, _>("block_number")? as u64, parse_hash(row.try_get("block_hash")?)?)))
+            .collect()
+    }
+
+    pub async fn completed_backfill_ranges(&self, chain_id: u64) -> Result<Vec<BlockRange>> {
+        let rows = sqlx::query("SELECT from_block, to_block FROM backfill_ranges WHERE chain_id = $1 ORDER BY from_block")
+            .bind(chain_id as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| Ok(BlockRange {
+                from: row.try_get::<i64, _>("from_block")? as u64,
+                to: row.try_get::<i64, _>("to_block")? as u64,
+            }))
+            .collect()
+    }
+
+    // Replace whatever was indexed in `range` with `processed` and checkpoint
+    // the range, in one transaction
+    pub async fn complete_backfill_range(&self, chain_id: u64, range: BlockRange, processed: &[ProcessedLog]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        let mut intents = affected_intents(&mut tx, chain_id, range.from, range.to).await?;
+        let mut messages = affected_messages(&mut tx, chain_id, range.from, range.to).await?;
+
+        sqlx::query("DELETE FROM indexed_events WHERE chain_id = $1 AND block_number BETWEEN $2 AND $3")
+            .bind(chain_id as i64)
+            .bind(range.from as i64)
+            .bind(range.to as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        for log in processed {
+            if insert_event(&mut *tx, &log.event).await? {
+                insert_typed_row(&mut *tx, &log.event, &log.decoded).await?;
+            }
+        }
+        record_coverage(&mut tx, chain_id, range).await?;
+        intents.extend(affected_intents(&mut tx, chain_id, range.from, range.to).await?);
+        refresh_journeys(&mut tx, &intents).await?;
+        messages.extend(affected_messages(&mut tx, chain_id, range.from, range.to).await?);
+        refresh_transfers(&mut tx, &messages).await?;
+
+        // A longer range supersedes shorter ones it covers
+        sqlx::query("DELETE FROM backfill_ranges WHERE chain_id = $1 AND from_block BETWEEN $2 AND $3")
+            .bind(chain_id as i64)
+            .bind(range.from as i64)
+            .bind(range.to as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("INSERT INTO backfill_ranges (chain_id, from_block, to_block, events) VALUES ($1, $2, $3, $4)")
+            .bind(chain_id as i64)
+            .bind(range.from as i64)
+            .bind(range.to as i64)
+            .bind(processed.len() as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn load_watched_contracts(&self) -> Result<Vec<WatchedContract>> {
+        let rows = sqlx::query("SELECT chain_id, address, start_block, events FROM watched_contracts ORDER BY added_at")
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| Ok(WatchedContract {
+                chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+                address: parse_address(row.try_get("address")?)?,
+                start_block: row.try_get::<i64, _>("start_block")? as u64,
+                events: row.try_get("events")?,
+            }))
+            .collect()
+    }
+
+    // A changed start block or event set restarts the contract's backfill
+    pub async fn save_watched_contract(&self, contract: &WatchedContract) -> Result<()> {
+        sqlx::query(r#"
+            INSERT INTO watched_contracts (chain_id, address, start_block, events) VALUES ($1, $2, $3, $4)
+            ON CONFLICT (chain_id, address) DO UPDATE SET
+                backfilled_to = CASE
+                    WHEN watched_contracts.start_block = EXCLUDED.start_block
+                        AND watched_contracts.events = EXCLUDED.events
+                    THEN watched_contracts.backfilled_to
+                END,
+                start_block = EXCLUDED.start_block,
+                events = EXCLUDED.events
+        "#)
+        .bind(contract.chain_id as i64)
+        .bind(format!("{:#x}", contract.address))
+        .bind(contract.start_block as i64)
+        .bind(&contract.events)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove_watched_contract(&self, chain_id: u64, address: Address) -> Result<()> {
+        sqlx::query("DELETE FROM watched_contracts WHERE chain_id = $1 AND address = $2")
+            .bind(chain_id as i64)
+            .bind(format!("{:#x}", address))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn contract_backfilled_to(&self, chain_id: u64, address: Address) -> Result<Option<u64>> {
+        let backfilled_to: Option<Option<i64>> = sqlx::query_scalar(
+            "SELECT backfilled_to FROM watched_contracts WHERE chain_id = $1 AND address = $2"
+        )
+        .bind(chain_id as i64)
+        .bind(format!("{:#x}", address))
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(backfilled_to.flatten().map(|block| block as u64))
+    }
+
+    // Add one contract's events in `range` and record the contract's
+    // backfill progress. Other events in the range are left alone.
+    pub async fn store_contract_range(
+        &self,
+        contract: &WatchedContract,
+        range: BlockRange,
+        processed: &[ProcessedLog],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for log in processed {
+            if insert_event(&mut *tx, &log.event).await? {
+                insert_typed_row(&mut *tx, &log.event, &log.decoded).await?;
+            }
+        }
+        let intents = affected_intents(&mut tx, contract.chain_id, range.from, range.to).await?;
+        refresh_journeys(&mut tx, &intents).await?;
+        let messages = affected_messages(&mut tx, contract.chain_id, range.from, range.to).await?;
+        refresh_transfers(&mut tx, &messages).await?;
+
+        sqlx::query("UPDATE watched_contracts SET backfilled_to = $3 WHERE chain_id = $1 AND address = $2")
+            .bind(contract.chain_id as i64)
+            .bind(format!("{:#x}", contract.address))
+            .bind(range.to as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // Mark `range` as indexed
+    pub async fn add_coverage(&self, chain_id: u64, range: BlockRange) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        record_coverage(&mut conn, chain_id, range).await
+    }
+
+    pub async fn coverage(&self, chain_id: u64) -> Result<Vec<BlockRange>> {
+        let rows = sqlx::query("SELECT from_block, to_block FROM block_coverage WHERE chain_id = $1 ORDER BY from_block")
+            .bind(chain_id as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| Ok(BlockRange {
+                from: row.try_get::<i64, _>("from_block")? as u64,
+                to: row.try_get::<i64, _>("to_block")? as u64,
+            }))
+            .collect()
+    }
+
+    // Replace the chain's coverage rows with `ranges`, already merged
+    pub async fn replace_coverage(&self, chain_id: u64, ranges: &[BlockRange]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM block_coverage WHERE chain_id = $1")
+            .bind(chain_id as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        for range in ranges {
+            sqlx::query("INSERT INTO block_coverage (chain_id, from_block, to_block) VALUES ($1, $2, $3)")
+                .bind(chain_id as i64)
+                .bind(range.from as i64)
+                .bind(range.to as i64)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // Move the chain's indexed block forward to `block`, never back
+    pub async fn advance_indexed_block(&self, chain_id: u64, block: u64) -> Result<()> {
+        sqlx::query("UPDATE chain_states SET indexed_block = GREATEST(indexed_block, $2), last_update = NOW() WHERE chain_id = $1")
+            .bind(chain_id as i64)
+            .bind(block as i64)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // Last event sequence number `handler` has processed, 0 before its first
+    pub async fn handler_offset(&self, handler: &str) -> Result<i64> {
+        let offset = sqlx::query_scalar("SELECT last_seq FROM handler_offsets WHERE handler = $1")
+            .bind(handler)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(offset.unwrap_or(0))
+    }
+
+    pub async fn save_handler_offset(&self, handler: &str, seq: i64) -> Result<()> {
+        sqlx::query(r#"
+            INSERT INTO handler_offsets (handler, last_seq, updated_at) VALUES ($1, $2, NOW())
+            ON CONFLICT (handler) DO UPDATE SET
+                last_seq = EXCLUDED.last_seq,
+                updated_at = EXCLUDED.updated_at
+        "#)
+        .bind(handler)
+        .bind(seq)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // Events stored after sequence number `seq`, oldest first
+    pub async fn events_after(&self, seq: i64, limit: u64) -> Result<Vec<(i64, IndexedEvent)>> {
+        let rows = sqlx::query("SELECT * FROM indexed_events WHERE seq > $1 ORDER BY seq LIMIT $2")
+            .bind(seq)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get("seq")?, event_from_row(row)?)))
+            .collect()
+    }
+
+    // Swaps indexed after `seq` that happened at or after `since`, in
+    // indexing order. Swaps on pools whose tokens aren't resolved yet are
+    // left out.
+    pub async fn swaps_after(&self, seq: i64, since: DateTime<Utc>, limit: u64) -> Result<Vec<SwapTrade>> {
+        let rows = sqlx::query(r#"
+            SELECT e.seq, e.timestamp, s.chain_id, s.pool_id, s.trader, s.amount_in, s.amount_out,
+                ti.token AS token_in_address, tout.token AS token_out_address,
+                COALESCE(mi.decimals, 18::SMALLINT) AS decimals_in, COALESCE(mout.decimals, 18::SMALLINT) AS decimals_out
+            FROM pool_swaps s
+            JOIN indexed_events e ON e.id = s.event_id
+            JOIN pool_tokens ti ON ti.chain_id = s.chain_id AND ti.pool_id = s.pool_id AND ti.token_index = s.token_in
+            JOIN pool_tokens tout ON tout.chain_id = s.chain_id AND tout.pool_id = s.pool_id AND tout.token_index = s.token_out
+            LEFT JOIN token_metadata mi ON mi.chain_id = s.chain_id AND mi.address = ti.token
+            LEFT JOIN token_metadata mout ON mout.chain_id = s.chain_id AND mout.address = tout.token
+            WHERE e.seq > $1 AND e.timestamp >= $2
+            ORDER BY e.seq
+            LIMIT $3
+        "#)
+        .bind(seq)
+        .bind(since)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok(SwapTrade {
+                seq: row.try_get("seq")?,
+                chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+                pool_id: row.try_get("pool_id")?,
+                trader: parse_address(row.try_get("trader")?)?,
+                token_in: parse_address(row.try_get("token_in_address")?)?,
+                token_out: parse_address(row.try_get("token_out_address")?)?,
+                amount_in: parse_u256(row.try_get("amount_in")?)?,
+                amount_out: parse_u256(row.try_get("amount_out")?)?,
+                decimals_in: row.try_get::<i16, _>("decimals_in")? as u8,
+                decimals_out: row.try_get::<i16, _>("decimals_out")? as u8,
+                timestamp: row.try_get("timestamp")?,
+            }))
+            .collect()
+    }
+
+    // Swaps that happened in [from, to), oldest first. Rows are read off
+    // the connection as the stream is polled, so a slow consumer holds back
+    // the query instead of buffering the range.
+    pub fn swaps_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> BoxStream<'_, Result<SwapRecord>> {
+        sqlx::query(r#"
+            SELECT s.chain_id, s.block_number, e.transaction_hash, e.log_index, e.timestamp, s.pool_id, s.trader,
+                s.amount_in, s.amount_out, ti.token AS token_in_address, tout.token AS token_out_address,
+                mi.decimals AS decimals_in, mout.decimals AS decimals_out
+            FROM pool_swaps s
+            JOIN indexed_events e ON e.id = s.event_id
+            LEFT JOIN pool_tokens ti ON ti.chain_id = s.chain_id AND ti.pool_id = s.pool_id AND ti.token_index = s.token_in
+            LEFT JOIN pool_tokens tout ON tout.chain_id = s.chain_id AND tout.pool_id = s.pool_id AND tout.token_index = s.token_out
+            LEFT JOIN token_metadata mi ON mi.chain_id = s.chain_id AND mi.address = ti.token
+            LEFT JOIN token_metadata mout ON mout.chain_id = s.chain_id AND mout.address = tout.token
+            WHERE e.timestamp >= $1 AND e.timestamp < $2
+            ORDER BY e.timestamp, e.seq
+        "#)
+        .bind(from)
+        .bind(to)
+        .fetch(&self.pool)
+        .map(|row| {
+            let row = row?;
+            let address = |column: &str| -> Result<Option<Address>> {
+                row.try_get::<Option<&str>, _>(column)?.map(parse_address).transpose()
+            };
+            Ok(SwapRecord {
+                chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+                block_number: row.try_get::<i64, _>("block_number")? as u64,
+                transaction_hash: parse_hash(row.try_get("transaction_hash")?)?,
+                log_index: row.try_get::<i64, _>("log_index")? as u64,
+                pool_id: row.try_get("pool_id")?,
+                trader: parse_address(row.try_get("trader")?)?,
+                token_in: address("token_in_address")?,
+                token_out: address("token_out_address")?,
+                amount_in: parse_u256(row.try_get("amount_in")?)?,
+                amount_out: parse_u256(row.try_get("amount_out")?)?,
+                decimals_in: row.try_get::<Option<i16>, _>("decimals_in")?.map(|decimals| decimals as u8),
+                decimals_out: row.try_get::<Option<i16>, _>("decimals_out")?.map(|decimals| decimals as u8),
+                timestamp: row.try_get("timestamp")?,
+            })
+        })
+        .boxed()
+    }
+
+    // Liquidity changes indexed after `seq` that happened at or after
+    // `since`, in indexing order
+    pub async fn liquidity_after(&self, seq: i64, since: DateTime<Utc>, limit: u64) -> Result<Vec<LiquidityChange>> {
+        let rows = sqlx::query(r#"
+            SELECT e.seq, e.timestamp, l.chain_id, l.pool_id, l.provider
+            FROM pool_liquidity l
+            JOIN indexed_events e ON e.id = l.event_id
+            WHERE e.seq > $1 AND e.timestamp >= $2
+            ORDER BY e.seq
+            LIMIT $3
+        "#)
+        .bind(seq)
+        .bind(since)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok(LiquidityChange {
+                seq: row.try_get("seq")?,
+                chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+                pool_id: row.try_get("pool_id")?,
+                provider: parse_address(row.try_get("provider")?)?,
+                timestamp: row.try_get("timestamp")?,
+            }))
+            .collect()
+    }
+
+    // Oldest events from before `cutoff`, with their sequence numbers
+    pub async fn events_before(&self, cutoff: DateTime<Utc>, limit: u64) -> Result<Vec<(i64, IndexedEvent)>> {
+        let rows = sqlx::query("SELECT * FROM indexed_events WHERE timestamp < $1 ORDER BY seq LIMIT $2")
+            .bind(cutoff)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get("seq")?, event_from_row(row)?)))
+            .collect()
+    }
+
+    // Delete archived events, adding them to the daily rollups in the same
+    // transaction. Returns the number of events deleted.
+    pub async fn prune_archived(&self, seqs: &[i64], rollups: &[DailyRollup]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        for rollup in rollups {
+            sqlx::query(r#"
+                INSERT INTO event_rollups_daily (chain_id, day, event_type, archived_events)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (chain_id, day, event_type) DO UPDATE SET
+                    archived_events = event_rollups_daily.archived_events + EXCLUDED.archived_events
+            "#)
+            .bind(rollup.chain_id as i64)
+            .bind(rollup.day)
+            .bind(&rollup.event_type)
+            .bind(rollup.events as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let deleted = sqlx::query("DELETE FROM indexed_events WHERE seq = ANY($1)")
+            .bind(seqs)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        tx.commit().await?;
+        Ok(deleted)
+    }
+
+    // Rebuild every `granularity` rollup bucket from `from` on, in one
+    // transaction so readers never see a half-computed bucket
+    pub async fn compute_rollups(&self, granularity: Granularity, from: DateTime<Utc>, config: &RollupConfig) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        let name = granularity.as_str();
+        let unit = granularity.trunc_unit();
+
+        for table in ["pool_volume_rollups", "pool_tvl_rollups", "intent_rollups", "solver_rollups", "bridge_latency_rollups"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE granularity = $1 AND bucket_start >= $2", table))
+                .bind(name)
+                .bind(from)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        // Swap volume per pool and input token; fees need the U256 math
+        let swaps = sqlx::query(r#"
+            SELECT date_trunc($1, e.timestamp, 'UTC') AS bucket_start, s.chain_id, s.pool_id, s.token_in,
+                COUNT(*) AS swaps,
+                SUM(s.amount_in::NUMERIC)::TEXT AS volume_in,
+                SUM(s.amount_out::NUMERIC)::TEXT AS volume_out
+            FROM pool_swaps s JOIN indexed_events e ON e.id = s.event_id
+            WHERE e.timestamp >= $2
+            GROUP BY 1, 2, 3, 4
+        "#)
+        .bind(unit)
+        .bind(from)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for row in &swaps {
+            let pool_id: String = row.try_get("pool_id")?;
+            let volume_in = parse_u256(row.try_get("volume_in")?)?;
+            sqlx::query(r#"
+                INSERT INTO pool_volume_rollups (
+                    granularity, bucket_start, chain_id, pool_id, token_in, swaps, volume_in, volume_out, fees
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#)
+            .bind(name)
+            .bind(row.try_get::<DateTime<Utc>, _>("bucket_start")?)
+            .bind(row.try_get::<i64, _>("chain_id")?)
+            .bind(&pool_id)
+            .bind(row.try_get::<String, _>("token_in")?)
+            .bind(row.try_get::<i64, _>("swaps")?)
+            .bind(volume_in.to_string())
+            .bind(row.try_get::<String, _>("volume_out")?)
+            .bind(estimate_fees(volume_in, config.fee_bps(&pool_id)).to_string())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // Running totals continue from each pool's last bucket before `from`
+        sqlx::query(r#"
+            INSERT INTO pool_tvl_rollups (granularity, bucket_start, chain_id, pool_id, tvl, liquidity_added)
+            WITH flows AS (
+                SELECT date_trunc($1, e.timestamp, 'UTC') AS bucket_start, l.chain_id, l.pool_id,
+                    l.amount0::NUMERIC + l.amount1::NUMERIC AS delta,
+                    l.amount0::NUMERIC + l.amount1::NUMERIC AS added
+                FROM pool_liquidity l JOIN indexed_events e ON e.id = l.event_id
+                WHERE e.timestamp >= $2
+                UNION ALL
+                SELECT date_trunc($1, e.timestamp, 'UTC'), s.chain_id, s.pool_id,
+                    s.amount_in::NUMERIC - s.amount_out::NUMERIC, 0
+                FROM pool_swaps s JOIN indexed_events e ON e.id = s.event_id
+                WHERE e.timestamp >= $2
+            ),
+            buckets AS (
+                SELECT bucket_start, chain_id, pool_id, SUM(delta) AS delta, SUM(added) AS added
+                FROM flows GROUP BY 1, 2, 3
+            ),
+            base AS (
+                SELECT DISTINCT ON (chain_id, pool_id) chain_id, pool_id, tvl::NUMERIC AS tvl
+                FROM pool_tvl_rollups
+                WHERE granularity = $3 AND bucket_start < $2
+                ORDER BY chain_id, pool_id, bucket_start DESC
+            )
+            SELECT $3, b.bucket_start, b.chain_id, b.pool_id,
+                GREATEST(COALESCE(base.tvl, 0) + SUM(b.delta) OVER (
+                    PARTITION BY b.chain_id, b.pool_id ORDER BY b.bucket_start
+                ), 0)::TEXT,
+                b.added::TEXT
+            FROM buckets b
+            LEFT JOIN base ON base.chain_id = b.chain_id AND base.pool_id = b.pool_id
+        "#)
+        .bind(unit)
+        .bind(from)
+        .bind(name)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(r#"
+            INSERT INTO intent_rollups (granularity, bucket_start, chain_id, created, matched, executed, succeeded)
+            SELECT $3, date_trunc($1, e.timestamp, 'UTC'), i.chain_id,
+                COUNT(*) FILTER (WHERE i.kind = 'created'),
+                COUNT(*) FILTER (WHERE i.kind = 'matched'),
+                COUNT(*) FILTER (WHERE i.kind = 'executed'),
+                COUNT(*) FILTER (WHERE i.kind = 'executed' AND i.success)
+            FROM intent_events i JOIN indexed_events e ON e.id = i.event_id
+            WHERE e.timestamp >= $2
+            GROUP BY 2, 3
+        "#)
+        .bind(unit)
+        .bind(from)
+        .bind(name)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(r#"
+            INSERT INTO solver_rollups (granularity, bucket_start, chain_id, solver, matched, executed, succeeded)
+            SELECT $3, date_trunc($1, e.timestamp, 'UTC'), i.chain_id, i.account,
+                COUNT(*) FILTER (WHERE i.kind = 'matched'),
+                COUNT(*) FILTER (WHERE i.kind = 'executed'),
+                COUNT(*) FILTER (WHERE i.kind = 'executed' AND i.success)
+            FROM intent_events i JOIN indexed_events e ON e.id = i.event_id
+            WHERE e.timestamp >= $2 AND i.kind IN ('matched', 'executed')
+            GROUP BY 2, 3, 4
+        "#)
+        .bind(unit)
+        .bind(from)
+        .bind(name)
+        .execute(&mut *tx)
+        .await?;
+
+        // Legs are bucketed by deposit time and counted once they land
+        sqlx::query(r#"
+            INSERT INTO bridge_latency_rollups (
+                granularity, bucket_start, source_chain_id, destination_chain_id, legs, p50_ms, p90_ms, p99_ms
+            )
+            SELECT $3, date_trunc($1, deposited_at, 'UTC'), source_chain_id, destination_chain_id, COUNT(*),
+                percentile_cont(0.5) WITHIN GROUP (ORDER BY latency_ms),
+                percentile_cont(0.9) WITHIN GROUP (ORDER BY latency_ms),
+                percentile_cont(0.99) WITHIN GROUP (ORDER BY latency_ms)
+            FROM (
+                SELECT d.chain_id AS source_chain_id, d.counterpart_chain_id AS destination_chain_id,
+                    de.timestamp AS deposited_at,
+                    EXTRACT(EPOCH FROM (MIN(we.timestamp) - de.timestamp)) * 1000 AS latency_ms
+                FROM bridge_events d
+                JOIN indexed_events de ON de.id = d.event_id
+                JOIN bridge_events w ON w.message_hash = d.message_hash AND w.kind = 'withdrawal'
+                JOIN indexed_events we ON we.id = w.event_id
+                WHERE d.kind = 'deposit' AND de.timestamp >= $2
+                GROUP BY d.event_id, d.chain_id, d.counterpart_chain_id, de.timestamp
+            ) legs
+            GROUP BY 2, 3, 4
+        "#)
+        .bind(unit)
+        .bind(from)
+        .bind(name)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn pool_volume_rollups(
+        &self,
+        granularity: Granularity,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<PoolVolumeRollup>> {
+        let rows = rollup_rows(&self.pool, "pool_volume_rollups", granularity, from, to).await?;
+        rows.iter()
+            .map(|row| Ok(PoolVolumeRollup {
+                bucket_start: row.try_get("bucket_start")?,
+                chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+                pool_id: row.try_get("pool_id")?,
+                token_in: row.try_get("token_in")?,
+                swaps: row.try_get::<i64, _>("swaps")? as u64,
+                volume_in: parse_u256(row.try_get("volume_in")?)?,
+                volume_out: parse_u256(row.try_get("volume_out")?)?,
+                fees: parse_u256(row.try_get("fees")?)?,
+            }))
+            .collect()
+    }
+
+    pub async fn pool_tvl_rollups(
+        &self,
+        granularity: Granularity,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<PoolTvlRollup>> {
+        let rows = rollup_rows(&self.pool, "pool_tvl_rollups", granularity, from, to).await?;
+        rows.iter()
+            .map(tvl_from_row)
+            .collect()
+    }
+
+    // Each pool's last TVL bucket before `before`, to carry into a window
+    // that starts with no activity
+    pub async fn latest_pool_tvl(&self, granularity: Granularity, before: DateTime<Utc>) -> Result<Vec<PoolTvlRollup>> {
+        let rows = sqlx::query(r#"
+            SELECT DISTINCT ON (chain_id, pool_id) * FROM pool_tvl_rollups
+            WHERE granularity = $1 AND bucket_start < $2
+            ORDER BY chain_id, pool_id, bucket_start DESC
+        "#)
+        .bind(granularity.as_str())
+        .bind(before)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(tvl_from_row)
+            .collect()
+    }
+
+    pub async fn intent_rollups(
+        &self,
+        granularity: Granularity,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<IntentRollup>> {
+        let rows = rollup_rows(&self.pool, "intent_rollups", granularity, from, to).await?;
+        rows.iter()
+            .map(|row| Ok(IntentRollup {
+                bucket_start: row.try_get("bucket_start")?,
+                chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+                created: row.try_get::<i64, _>("created")? as u64,
+                matched: row.try_get::<i64, _>("matched")? as u64,
+                executed: row.try_get::<i64, _>("executed")? as u64,
+                succeeded: row.try_get::<i64, _>("succeeded")? as u64,
+            }))
+            .collect()
+    }
+
+    pub async fn solver_rollups(
+        &self,
+        granularity: Granularity,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<SolverRollup>> {
+        let rows = rollup_rows(&self.pool, "solver_rollups", granularity, from, to).await?;
+        rows.iter()
+            .map(|row| Ok(SolverRollup {
+                bucket_start: row.try_get("bucket_start")?,
+                chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+                solver: row.try_get("solver")?,
+                matched: row.try_get::<i64, _>("matched")? as u64,
+                executed: row.try_get::<i64, _>("executed")? as u64,
+                succeeded: row.try_get::<i64, _>("succeeded")? as u64,
+            }))
+            .collect()
+    }
+
+    pub async fn bridge_latency_rollups(
+        &self,
+        granularity: Granularity,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<BridgeLatencyRollup>> {
+        let rows = rollup_rows(&self.pool, "bridge_latency_rollups", granularity, from, to).await?;
+        rows.iter()
+            .map(|row| Ok(BridgeLatencyRollup {
+                bucket_start: row.try_get("bucket_start")?,
+                source_chain_id: row.try_get::<i64, _>("source_chain_id")? as u64,
+                destination_chain_id: row.try_get::<i64, _>("destination_chain_id")? as u64,
+                legs: row.try_get::<i64, _>("legs")? as u64,
+                p50_ms: row.try_get("p50_ms")?,
+                p90_ms: row.try_get("p90_ms")?,
+                p99_ms: row.try_get("p99_ms")?,
+            }))
+            .collect()
+    }
+
+    // Pools seen in swaps whose token list is not known yet
+    pub async fn unresolved_pools(&self, limit: u64) -> Result<Vec<(u64, String)>> {
+        let rows = sqlx::query(r#"
+            SELECT DISTINCT s.chain_id, s.pool_id FROM pool_swaps s
+            WHERE NOT EXISTS (
+                SELECT 1 FROM pool_tokens p WHERE p.chain_id = s.chain_id AND p.pool_id = s.pool_id
+            )
+            LIMIT $1
+        "#)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get::<i64, _>("chain_id")? as u64, row.try_get("pool_id")?)))
+            .collect()
+    }
+
+    pub async fn save_pool_tokens(&self, chain_id: u64, pool_id: &str, tokens: &[Address]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for (index, token) in tokens.iter().enumerate() {
+            sqlx::query(r#"
+                INSERT INTO pool_tokens (chain_id, pool_id, token_index, token) VALUES ($1, $2, $3, $4)
+                ON CONFLICT (chain_id, pool_id, token_index) DO UPDATE SET token = EXCLUDED.token
+            "#)
+            .bind(chain_id as i64)
+            .bind(pool_id)
+            .bind(index.to_string())
+            .bind(format!("{:#x}", token))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // Pool and bridged tokens without metadata
+    pub async fn unresolved_tokens(&self, limit: u64) -> Result<Vec<(u64, Address)>> {
+        let rows = sqlx::query(r#"
+            SELECT chain_id, token FROM (
+                SELECT chain_id, token FROM pool_tokens
+                UNION
+                SELECT chain_id, token FROM bridge_events
+            ) tokens
+            WHERE NOT EXISTS (
+                SELECT 1 FROM token_metadata m WHERE m.chain_id = tokens.chain_id AND m.address = tokens.token
+            )
+            LIMIT $1
+        "#)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get::<i64, _>("chain_id")? as u64, parse_address(row.try_get("token")?)?)))
+            .collect()
+    }
+
+    pub async fn save_token_metadata(&self, token: &TokenMetadata) -> Result<()> {
+        sqlx::query(r#"
+            INSERT INTO token_metadata (chain_id, address, symbol, decimals) VALUES ($1, $2, $3, $4)
+            ON CONFLICT (chain_id, address) DO UPDATE SET symbol = EXCLUDED.symbol, decimals = EXCLUDED.decimals
+        "#)
+        .bind(token.chain_id as i64)
+        .bind(format!("{:#x}", token.address))
+        .bind(&token.symbol)
+        .bind(i16::from(token.decimals))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn token_metadata(&self, chain_id: u64, address: Address) -> Result<Option<TokenMetadata>> {
+        let row = sqlx::query("SELECT symbol, decimals FROM token_metadata WHERE chain_id = $1 AND address = $2")
+            .bind(chain_id as i64)
+            .bind(format!("{:#x}", address))
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| Ok(TokenMetadata {
+            chain_id,
+            address,
+            symbol: row.try_get("symbol")?,
+            decimals: row.try_get::<i16, _>("decimals")? as u8,
+        }))
+        .transpose()
+    }
+
+    pub async fn known_tokens(&self) -> Result<Vec<(u64, Address)>> {
+        let rows = sqlx::query("SELECT chain_id, address FROM token_metadata ORDER BY chain_id, address")
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get::<i64, _>("chain_id")? as u64, parse_address(row.try_get("address")?)?)))
+            .collect()
+    }
+
+    pub async fn record_token_prices(&self, chain_id: u64, observed_at: DateTime<Utc>, prices: &[(Address, f64)]) -> Result<()> {
+        if prices.is_empty() {
+            return Ok(());
+        }
+
+        let mut builder = QueryBuilder::<Postgres>::new("INSERT INTO token_prices (chain_id, token, observed_at, price_usd) ");
+        builder.push_values(prices, |mut row, (token, price)| {
+            row.push_bind(chain_id as i64)
+                .push_bind(format!("{:#x}", token))
+                .push_bind(observed_at)
+                .push_bind(*price);
+        });
+        builder.push(" ON CONFLICT DO NOTHING");
+        builder.build().execute(&self.pool).await?;
+        Ok(())
+    }
+
+    // Price swaps whose tokens are resolved, using the latest price at or
+    // before the swap no older than `max_price_age_secs`
+    pub async fn enrich_swaps(&self, max_price_age_secs: u64, limit: u64) -> Result<u64> {
+        let rows = sqlx::query(r#"
+            SELECT s.event_id, s.amount_in, s.amount_out,
+                ti.token AS token_in_address, tout.token AS token_out_address,
+                mi.decimals AS decimals_in, mout.decimals AS decimals_out,
+                pi.price_usd AS price_in, pout.price_usd AS price_out
+            FROM pool_swaps s
+            JOIN indexed_events e ON e.id = s.event_id
+            JOIN pool_tokens ti ON ti.chain_id = s.chain_id AND ti.pool_id = s.pool_id AND ti.token_index = s.token_in
+            JOIN pool_tokens tout ON tout.chain_id = s.chain_id AND tout.pool_id = s.pool_id AND tout.token_index = s.token_out
+            JOIN token_metadata mi ON mi.chain_id = s.chain_id AND mi.address = ti.token
+            JOIN token_metadata mout ON mout.chain_id = s.chain_id AND mout.address = tout.token
+            LEFT JOIN LATERAL (
+                SELECT price_usd FROM token_prices p
+                WHERE p.chain_id = s.chain_id AND p.token = ti.token
+                    AND p.observed_at <= e.timestamp AND p.observed_at > e.timestamp - make_interval(secs => $1)
+                ORDER BY p.observed_at DESC LIMIT 1
+            ) pi ON TRUE
+            LEFT JOIN LATERAL (
+                SELECT price_usd FROM token_prices p
+                WHERE p.chain_id = s.chain_id AND p.token = tout.token
+                    AND p.observed_at <= e.timestamp AND p.observed_at > e.timestamp - make_interval(secs => $1)
+                ORDER BY p.observed_at DESC LIMIT 1
+            ) pout ON TRUE
+            WHERE s.enriched_at IS NULL
+            LIMIT $2
+        "#)
+        .bind(max_price_age_secs as f64)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut tx = self.pool.begin().await?;
+        for row in &rows {
+            let amount_in = parse_u256(row.try_get("amount_in")?)?;
+            let amount_out = parse_u256(row.try_get("amount_out")?)?;
+            let decimals_in = row.try_get::<i16, _>("decimals_in")? as u8;
+            let decimals_out = row.try_get::<i16, _>("decimals_out")? as u8;
+            let amount_in_usd = row
+                .try_get::<Option<f64>, _>("price_in")?
+                .map(|price| usd_value(amount_in, decimals_in, price));
+            let amount_out_usd = row
+                .try_get::<Option<f64>, _>("price_out")?
+                .map(|price| usd_value(amount_out, decimals_out, price));
+
+            sqlx::query(r#"
+                UPDATE pool_swaps SET token_in_address = $2, token_out_address = $3,
+                    amount_in_usd = $4, amount_out_usd = $5, enriched_at = NOW()
+                WHERE event_id = $1
+            "#)
+            .bind(row.try_get::<uuid::Uuid, _>("event_id")?)
+            .bind(row.try_get::<String, _>("token_in_address")?)
+            .bind(row.try_get::<String, _>("token_out_address")?)
+            .bind(amount_in_usd)
+            .bind(amount_out_usd)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(rows.len() as u64)
+    }
+
+    pub async fn enrich_bridge_transfers(&self, max_price_age_secs: u64, limit: u64) -> Result<u64> {
+        let rows = sqlx::query(r#"
+            SELECT b.event_id, b.amount, m.decimals, p.price_usd
+            FROM bridge_events b
+            JOIN indexed_events e ON e.id = b.event_id
+            JOIN token_metadata m ON m.chain_id = b.chain_id AND m.address = b.token
+            LEFT JOIN LATERAL (
+                SELECT price_usd FROM token_prices p
+                WHERE p.chain_id = b.chain_id AND p.token = b.token
+                    AND p.observed_at <= e.timestamp AND p.observed_at > e.timestamp - make_interval(secs => $1)
+                ORDER BY p.observed_at DESC LIMIT 1
+            ) p ON TRUE
+            WHERE b.enriched_at IS NULL
+            LIMIT $2
+        "#)
+        .bind(max_price_age_secs as f64)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut tx = self.pool.begin().await?;
+        for row in &rows {
+            let amount = parse_u256(row.try_get("amount")?)?;
+            let decimals = row.try_get::<i16, _>("decimals")? as u8;
+            let amount_usd = row
+                .try_get::<Option<f64>, _>("price_usd")?
+                .map(|price| usd_value(amount, decimals, price));
+
+            sqlx::query("UPDATE bridge_events SET amount_usd = $2, enriched_at = NOW() WHERE event_id = $1")
+                .bind(row.try_get::<uuid::Uuid, _>("event_id")?)
+                .bind(amount_usd)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(rows.len() as u64)
+    }
+
+    pub async fn record_quote(&self, intent_id: H256, solver: Address, quoted_output: ethers::types::U256) -> Result<()> {
+        sqlx::query(r#"
+            INSERT INTO intent_quotes (intent_id, solver, quoted_output) VALUES ($1, $2, $3)
+            ON CONFLICT (intent_id, solver) DO UPDATE SET quoted_output = EXCLUDED.quoted_output, quoted_at = NOW()
+        "#)
+        .bind(format!("{:#x}", intent_id))
+        .bind(format!("{:#x}", solver))
+        .bind(quoted_output.to_string())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    // Tell listeners on `ROLLUPS_UPDATED_CHANNEL` that rollups changed
+    pub async fn notify_rollups_updated(&self, at: DateTime<Utc>) -> Result<()> {
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(ROLLUPS_UPDATED_CHANNEL)
+            .bind(at.timestamp_millis().to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    // Rebuild the solver leaderboard from the indexed intent events
+    pub async fn refresh_solver_stats(&self) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        let rows = sqlx::query(r#"
+            WITH matched AS (
+                SELECT i.account AS solver, i.intent_id, MIN(e.timestamp) AS matched_at
+                FROM intent_events i JOIN indexed_events e ON e.id = i.event_id
+                WHERE i.kind = 'matched'
+                GROUP BY 1, 2
+            ), executed AS (
+                SELECT i.account AS solver, i.intent_id, bool_or(i.success) AS success, MIN(e.timestamp) AS executed_at
+                FROM intent_events i JOIN indexed_events e ON e.id = i.event_id
+                WHERE i.kind = 'executed'
+                GROUP BY 1, 2
+            ), matched_counts AS (
+                SELECT solver, COUNT(*) AS matched, MAX(matched_at) AS last_at FROM matched GROUP BY 1
+            ), executed_counts AS (
+                SELECT x.solver, COUNT(*) AS executed, COUNT(*) FILTER (WHERE x.success) AS succeeded,
+                    AVG(EXTRACT(EPOCH FROM (x.executed_at - m.matched_at)) * 1000) AS avg_latency_ms,
+                    MAX(x.executed_at) AS last_at
+                FROM executed x LEFT JOIN matched m ON m.solver = x.solver AND m.intent_id = x.intent_id
+                GROUP BY 1
+            ), slashed AS (
+                SELECT solver, COUNT(*) AS slashings, SUM(amount::NUMERIC)::TEXT AS amount, MAX(e.timestamp) AS last_at
+                FROM solver_slashings s JOIN indexed_events e ON e.id = s.event_id
+                GROUP BY 1
+            ), solvers AS (
+                SELECT solver FROM matched_counts
+                UNION SELECT solver FROM executed_counts
+                UNION SELECT solver FROM slashed
+            )
+            SELECT s.solver,
+                COALESCE(m.matched, 0) AS matched,
+                COALESCE(x.executed, 0) AS executed,
+                COALESCE(x.succeeded, 0) AS succeeded,
+                x.avg_latency_ms::DOUBLE PRECISION AS avg_latency_ms,
+                COALESCE(sl.slashings, 0) AS slashings,
+                COALESCE(sl.amount, '0') AS slashed_amount,
+                GREATEST(m.last_at, x.last_at, sl.last_at) AS last_active_at
+            FROM solvers s
+            LEFT JOIN matched_counts m ON m.solver = s.solver
+            LEFT JOIN executed_counts x ON x.solver = s.solver
+            LEFT JOIN slashed sl ON sl.solver = s.solver
+        "#)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut stats: HashMap<String, SolverStats> = HashMap::new();
+        for row in &rows {
+            let solver: String = row.try_get("solver")?;
+            stats.insert(solver.clone(), SolverStats {
+                solver,
+                intents_matched: row.try_get::<i64, _>("matched")? as u64,
+                intents_executed: row.try_get::<i64, _>("executed")? as u64,
+                intents_succeeded: row.try_get::<i64, _>("succeeded")? as u64,
+                avg_latency_ms: row.try_get("avg_latency_ms")?,
+                avg_slippage_bps: None,
+                slippage_samples: 0,
+                slashings: row.try_get::<i64, _>("slashings")? as u64,
+                slashed_amount: parse_u256(row.try_get("slashed_amount")?)?,
+                last_active_at: row.try_get("last_active_at")?,
+            });
+        }
+
+        // Realized output is the last pool swap in the execution transaction
+        let fills = sqlx::query(r#"
+            SELECT DISTINCT ON (i.event_id) i.account AS solver, q.quoted_output, s.amount_out
+            FROM intent_events i
+            JOIN indexed_events e ON e.id = i.event_id
+            JOIN intent_quotes q ON q.intent_id = i.intent_id AND q.solver = i.account
+            JOIN indexed_events se ON se.chain_id = e.chain_id AND se.transaction_hash = e.transaction_hash
+            JOIN pool_swaps s ON s.event_id = se.id
+            WHERE i.kind = 'executed' AND i.success
+            ORDER BY i.event_id, se.log_index DESC
+        "#)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut slippage: HashMap<String, Vec<f64>> = HashMap::new();
+        for row in &fills {
+            let quoted = parse_u256(row.try_get("quoted_output")?)?;
+            let realized = parse_u256(row.try_get("amount_out")?)?;
+            if let Some(bps) = slippage_bps(quoted, realized) {
+                slippage.entry(row.try_get("solver")?).or_default().push(bps);
+            }
+        }
+        for (solver, samples) in slippage {
+            if let Some(entry) = stats.get_mut(&solver) {
+                entry.avg_slippage_bps = Some(samples.iter().sum::<f64>() / samples.len() as f64);
+                entry.slippage_samples = samples.len() as u64;
+            }
+        }
+
+        sqlx::query("DELETE FROM solver_stats").execute(&mut *tx).await?;
+        for entry in stats.values() {
+            sqlx::query(r#"
+                INSERT INTO solver_stats (
+                    solver, intents_matched, intents_executed, intents_succeeded, fill_rate, avg_latency_ms,
+                    avg_slippage_bps, slippage_samples, slashings, slashed_amount, reputation_score, last_active_at
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            "#)
+            .bind(&entry.solver)
+            .bind(entry.intents_matched as i64)
+            .bind(entry.intents_executed as i64)
+            .bind(entry.intents_succeeded as i64)
+            .bind(entry.fill_rate())
+            .bind(entry.avg_latency_ms)
+            .bind(entry.avg_slippage_bps)
+            .bind(entry.slippage_samples as i64)
+            .bind(entry.slashings as i64)
+            .bind(entry.slashed_amount.to_string())
+            .bind(entry.reputation_score() as i64)
+            .bind(entry.last_active_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(stats.len() as u64)
+    }
+
+    // Solvers ranked by reputation score, then by successful executions
+    pub async fn solver_leaderboard(&self, limit: u64) -> Result<Vec<SolverStats>> {
+        let rows = sqlx::query(r#"
+            SELECT * FROM solver_stats
+            ORDER BY reputation_score DESC, intents_succeeded DESC, solver
+            LIMIT $1
+        "#)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(solver_stats_from_row).collect()
+    }
+
+    pub async fn solver_stats(&self, solver: Address) -> Result<Option<SolverStats>> {
+        let row = sqlx::query("SELECT * FROM solver_stats WHERE solver = $1")
+            .bind(format!("{:#x}", solver))
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.as_ref().map(solver_stats_from_row).transpose()
+    }
+
+    // Look a transfer up by message hash or by its source or destination
+    // transaction hash
+    pub async fn find_bridge_transfer(&self, hash: H256) -> Result<Option<BridgeTransfer>> {
+        let row = sqlx::query(r#"
+            SELECT * FROM bridge_transfers
+            WHERE message_hash = $1 OR source_tx = $1 OR destination_tx = $1
+            LIMIT 1
+        "#)
+        .bind(format!("{:#x}", hash))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(transfer_from_row).transpose()
+    }
+
+    // Transfers sent or received by `account`, newest first
+    pub async fn bridge_transfers_for(&self, account: Address, limit: u64) -> Result<Vec<BridgeTransfer>> {
+        let rows = sqlx::query(r#"
+            SELECT * FROM bridge_transfers
+            WHERE sender = $1 OR recipient = $1
+            ORDER BY COALESCE(sent_at, received_at) DESC
+            LIMIT $2
+        "#)
+        .bind(format!("{:#x}", account))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(transfer_from_row).collect()
+    }
+
+    // Pending transfers sent before `sent_before`
+    pub async fn pending_bridge_transfers(&self, sent_before: DateTime<Utc>) -> Result<Vec<BridgeTransfer>> {
+        let rows = sqlx::query("SELECT * FROM bridge_transfers WHERE status = 'pending' AND sent_at < $1 ORDER BY sent_at")
+            .bind(sent_before)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(transfer_from_row).collect()
+    }
+
+    pub async fn mark_transfers_stuck(&self, message_hashes: &[H256]) -> Result<()> {
+        let hashes: Vec<String> = message_hashes.iter().map(|hash| format!("{:#x}", hash)).collect();
+        sqlx::query(r#"
+            UPDATE bridge_transfers SET status = 'stuck', updated_at = NOW()
+            WHERE message_hash = ANY($1) AND status = 'pending'
+        "#)
+        .bind(&hashes)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    // Stuck transfers per destination chain
+    pub async fn stuck_transfer_counts(&self) -> Result<Vec<(u64, u64)>> {
+        let rows = sqlx::query(r#"
+            SELECT destination_chain_id, COUNT(*) AS stuck FROM bridge_transfers
+            WHERE status = 'stuck'
+            GROUP BY 1
+        "#)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok((
+                row.try_get::<i64, _>("destination_chain_id")? as u64,
+                row.try_get::<i64, _>("stuck")? as u64,
+            )))
+            .collect()
+    }
+
+    pub async fn query_intent_journey(&self, intent_id: H256) -> Result<Option<IntentJourney>> {
+        let row = sqlx::query("SELECT * FROM intent_journeys WHERE intent_id = $1")
+            .bind(format!("{:#x}", intent_id))
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| {
+            let address = |column: &str| -> Result<Option<Address>> {
+                row.try_get::<Option<String>, _>(column)?
+                    .map(|value| Address::from_str(&value).map_err(|e| IndexerError::Decoding(e.to_string())))
+                    .transpose()
+            };
+            let bridge_legs: Vec<BridgeLeg> = serde_json::from_value(row.try_get("bridge_legs")?)?;
+
+            Ok(IntentJourney {
+                intent_id,
+                user: address("user_address")?,
+                solver: address("solver")?,
+                source_chain_id: row.try_get::<Option<i64>, _>("source_chain_id")?.map(|id| id as u64),
+                created_at: row.try_get("created_at")?,
+                matched_at: row.try_get("matched_at")?,
+                executed_at: row.try_get("executed_at")?,
+                success: row.try_get("success")?,
+                bridge_legs,
+                latency: PhaseLatency {
+                    matching_ms: row.try_get("matching_latency_ms")?,
+                    execution_ms: row.try_get("execution_latency_ms")?,
+                    bridging_ms: row.try_get("bridging_latency_ms")?,
+                    total_ms: row.try_get("total_latency_ms")?,
+                },
+            })
+        })
+        .transpose()
+    }
+
+    // Remove everything indexed above `fork_block` in one transaction and
+    // rewind the chain state to it. Returns the number of events removed.
+    pub async fn rollback_to(&self, chain_id: u64, fork_block: u64) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let intents = affected_intents(&mut tx, chain_id, fork_block + 1, i64::MAX as u64).await?;
+        let messages = affected_messages(&mut tx, chain_id, fork_block + 1, i64::MAX as u64).await?;
+
+        let removed = sqlx::query("DELETE FROM indexed_events WHERE chain_id = $1 AND block_number > $2")
+            .bind(chain_id as i64)
+            .bind(fork_block as i64)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        refresh_journeys(&mut tx, &intents).await?;
+        refresh_transfers(&mut tx, &messages).await?;
+
+        sqlx::query("DELETE FROM block_hashes WHERE chain_id = $1 AND block_number > $2")
+            .bind(chain_id as i64)
+            .bind(fork_block as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM block_coverage WHERE chain_id = $1 AND from_block > $2")
+            .bind(chain_id as i64)
+            .bind(fork_block as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("UPDATE block_coverage SET to_block = $2 WHERE chain_id = $1 AND to_block > $2")
+            .bind(chain_id as i64)
+            .bind(fork_block as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(r#"
+            UPDATE chain_states SET
+                indexed_block = LEAST(indexed_block, $2),
+                latest_block_hash = COALESCE(
+                    (SELECT block_hash FROM block_hashes WHERE chain_id = $1 AND block_number = $2),
+                    latest_block_hash
+                ),
+                last_update = NOW()
+            WHERE chain_id = $1
+        "#)
+        .bind(chain_id as i64)
+        .bind(fork_block as i64)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(removed)
+    }
+}
+
+// Keyed on (chain_id, transaction_hash, log_index); returns false when the
+// log is already stored
+async fn insert_event<'e>(executor: impl PgExecutor<'e>, event: &IndexedEvent) -> Result<bool> {
+    let inserted = sqlx::query(r#"
+        INSERT INTO indexed_events (
+            id, chain_id, block_number, transaction_hash, transaction_index, log_index,
+            event_type, contract_address, event_data, timestamp, processed
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        ON CONFLICT (chain_id, transaction_hash, log_index) DO NOTHING
+    "#)
+    .bind(event.id)
+    .bind(event.chain_id as i64)
+    .bind(event.block_number as i64)
+    .bind(format!("{:#x}", event.transaction_hash))
+    .bind(event.transaction_index as i64)
+    .bind(event.log_index as i64)
+    .bind(&event.event_type)
+    .bind(format!("{:#x}", event.contract_address))
+    .bind(&event.event_data)
+    .bind(event.timestamp)
+    .bind(event.processed)
+    .execute(executor)
+    .await?
+    .rows_affected();
+
+    Ok(inserted == 1)
+}
+
+// Extend a coverage range that `range` touches, or start a new one;
+// `replace_coverage` merges the rest
+async fn record_coverage(conn: &mut PgConnection, chain_id: u64, range: BlockRange) -> Result<()> {
+    let extended = sqlx::query(r#"
+        UPDATE block_coverage SET to_block = GREATEST(to_block, $3)
+        WHERE chain_id = $1 AND from_block <= $2 AND to_block + 1 >= $2
+    "#)
+    .bind(chain_id as i64)
+    .bind(range.from as i64)
+    .bind(range.to as i64)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    if extended == 0 {
+        sqlx::query(r#"
+            INSERT INTO block_coverage (chain_id, from_block, to_block) VALUES ($1, $2, $3)
+            ON CONFLICT (chain_id, from_block) DO UPDATE SET to_block = GREATEST(block_coverage.to_block, EXCLUDED.to_block)
+        "#)
+        .bind(chain_id as i64)
+        .bind(range.from as i64)
+        .bind(range.to as i64)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn insert_typed_row<'e>(executor: impl PgExecutor<'e>, event: &IndexedEvent, decoded: &DecodedEvent) -> Result<()> {
+    let query = match decoded {
+        DecodedEvent::IntentCreated(e) => {
+            intent_row(event, e.intent_id, "created", e.user, None, Some(e.timestamp.to_string()))
+        }
+        DecodedEvent::IntentMatched(e) => {
+            intent_row(event, e.intent_id, "matched", e.solver, None, Some(e.timestamp.to_string()))
+        }
+        DecodedEvent::IntentExecuted(e) => intent_row(event, e.intent_id, "executed", e.solver, Some(e.success), None),
+        DecodedEvent::SolverSlashed(e) => sqlx::query(r#"
+            INSERT INTO solver_slashings (event_id, chain_id, block_number, solver, amount, intent_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+        "#)
+        .bind(event.id)
+        .bind(event.chain_id as i64)
+        .bind(event.block_number as i64)
+        .bind(format!("{:#x}", e.solver))
+        .bind(e.amount.to_string())
+        .bind(format!("{:#x}", H256::from(e.intent_id))),
+        DecodedEvent::ToroidalSwap(e) => sqlx::query(r#"
+            INSERT INTO pool_swaps (
+                event_id, chain_id, block_number, pool_id, trader, token_in, token_out, amount_in, amount_out
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#)
+        .bind(event.id)
+        .bind(event.chain_id as i64)
+        .bind(event.block_number as i64)
+        .bind(e.pool_id.to_string())
+        .bind(format!("{:#x}", e.trader))
+        .bind(e.token_in.to_string())
+        .bind(e.token_out.to_string())
+        .bind(e.amount_in.to_string())
+        .bind(e.amount_out.to_string()),
+        DecodedEvent::LiquidityAdded(e) => sqlx::query(r#"
+            INSERT INTO pool_liquidity (
+                event_id, chain_id, block_number, pool_id, provider, amount0, amount1
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#)
+        .bind(event.id)
+        .bind(event.chain_id as i64)
+        .bind(event.block_number as i64)
+        .bind(e.pool_id.to_string())
+        .bind(format!("{:#x}", e.provider))
+        .bind(e.amount0.to_string())
+        .bind(e.amount1.to_string()),
+        DecodedEvent::BridgeDeposit(e) => bridge_row(event, BridgeRow {
+            message_hash: e.message_hash.into(),
+            kind: "deposit",
+            sender: Some(e.sender),
+            recipient: e.recipient,
+            token: e.token,
+            amount: e.amount,
+            counterpart_chain_id: e.dest_chain_id.low_u64(),
+            nonce: e.nonce,
+        }),
+        DecodedEvent::BridgeWithdrawal(e) => bridge_row(event, BridgeRow {
+            message_hash: e.message_hash.into(),
+            kind: "withdrawal",
+            sender: None,
+            recipient: e.recipient,
+            token: e.token,
+            amount: e.amount,
+            counterpart_chain_id: e.source_chain_id.low_u64(),
+            nonce: e.nonce,
+        }),
+    };
+    query.execute(executor).await?;
+    Ok(())
+}
+
+// Intents whose journey depends on events of `chain_id` in the block range:
+// their own intent events, or bridge events of legs deposited alongside them
+async fn affected_intents(conn: &mut PgConnection, chain_id: u64, from_block: u64, to_block: u64) -> Result<Vec<String>> {
+    let rows = sqlx::query(r#"
+        SELECT i.intent_id FROM intent_events i
+        WHERE i.chain_id = $1 AND i.block_number BETWEEN $2 AND $3
+        UNION
+        SELECT i.intent_id FROM bridge_events b
+        JOIN bridge_events d ON d.message_hash = b.message_hash AND d.kind = 'deposit'
+        JOIN indexed_events de ON de.id = d.event_id
+        JOIN indexed_events ie ON ie.chain_id = de.chain_id AND ie.transaction_hash = de.transaction_hash
+        JOIN intent_events i ON i.event_id = ie.id
+        WHERE b.chain_id = $1 AND b.block_number BETWEEN $2 AND $3
+    "#)
+    .bind(chain_id as i64)
+    .bind(from_block as i64)
+    .bind(to_block as i64)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    rows.iter().map(|row| Ok(row.try_get("intent_id")?)).collect()
+}
+
+// Rebuild the journeys of `intent_ids` from the events now stored
+async fn refresh_journeys(conn: &mut PgConnection, intent_ids: &[String]) -> Result<()> {
+    let mut intent_ids = intent_ids.to_vec();
+    intent_ids.sort();
+    intent_ids.dedup();
+
+    for intent_id in intent_ids {
+        let milestones = sqlx::query(r#"
+            SELECT i.kind, i.chain_id, i.account, i.success, e.timestamp
+            FROM intent_events i JOIN indexed_events e ON e.id = i.event_id
+            WHERE i.intent_id = $1
+        "#)
+        .bind(&intent_id)
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .filter_map(|row| intent_milestone(row).transpose())
+        .collect::<Result<Vec<_>>>()?;
+
+        let bridge = sqlx::query(r#"
+            SELECT b.message_hash, b.kind, b.chain_id, b.counterpart_chain_id, e.timestamp
+            FROM bridge_events b JOIN indexed_events e ON e.id = b.event_id
+            WHERE b.message_hash IN (
+                SELECT d.message_hash FROM bridge_events d
+                JOIN indexed_events de ON de.id = d.event_id
+                JOIN indexed_events ie ON ie.chain_id = de.chain_id AND ie.transaction_hash = de.transaction_hash
+                JOIN intent_events i ON i.event_id = ie.id
+                WHERE d.kind = 'deposit' AND i.intent_id = $1
+            )
+        "#)
+        .bind(&intent_id)
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .map(bridge_milestone)
+        .collect::<Result<Vec<_>>>()?;
+
+        let Some(journey) = IntentJourney::build(parse_hash(&intent_id)?, &milestones, &bridge) else {
+            sqlx::query("DELETE FROM intent_journeys WHERE intent_id = $1")
+                .bind(&intent_id)
+                .execute(&mut *conn)
+                .await?;
+            continue;
+        };
+
+        sqlx::query(r#"
+            INSERT INTO intent_journeys (
+                intent_id, user_address, solver, source_chain_id, created_at, matched_at,
+                executed_at, success, bridge_legs, matching_latency_ms, execution_latency_ms,
+                bridging_latency_ms, total_latency_ms, updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, NOW())
+            ON CONFLICT (intent_id) DO UPDATE SET
+                user_address = EXCLUDED.user_address,
+                solver = EXCLUDED.solver,
+                source_chain_id = EXCLUDED.source_chain_id,
+                created_at = EXCLUDED.created_at,
+                matched_at = EXCLUDED.matched_at,
+                executed_at = EXCLUDED.executed_at,
+                success = EXCLUDED.success,
+                bridge_legs = EXCLUDED.bridge_legs,
+                matching_latency_ms = EXCLUDED.matching_latency_ms,
+                execution_latency_ms = EXCLUDED.execution_latency_ms,
+                bridging_latency_ms = EXCLUDED.bridging_latency_ms,
+                total_latency_ms = EXCLUDED.total_latency_ms,
+                updated_at = EXCLUDED.updated_at
+        "#)
+        .bind(&intent_id)
+        .bind(journey.user.map(|user| format!("{:#x}", user)))
+        .bind(journey.solver.map(|solver| format!("{:#x}", solver)))
+        .bind(journey.source_chain_id.map(|id| id as i64))
+        .bind(journey.created_at)
+        .bind(journey.matched_at)
+        .bind(journey.executed_at)
+        .bind(journey.success)
+        .bind(serde_json::to_value(&journey.bridge_legs)?)
+        .bind(journey.latency.matching_ms)
+        .bind(journey.latency.execution_ms)
+        .bind(journey.latency.bridging_ms)
+        .bind(journey.latency.total_ms)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn affected_messages(conn: &mut PgConnection, chain_id: u64, from_block: u64, to_block: u64) -> Result<Vec<String>> {
+    let rows = sqlx::query(r#"
+        SELECT DISTINCT message_hash FROM bridge_events
+        WHERE chain_id = $1 AND block_number BETWEEN $2 AND $3
+    "#)
+    .bind(chain_id as i64)
+    .bind(from_block as i64)
+    .bind(to_block as i64)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    rows.iter().map(|row| Ok(row.try_get("message_hash")?)).collect()
+}
+
+// Rebuild the transfers of `message_hashes` from the bridge events now
+// stored. A stuck transfer stays stuck until it is received.
+async fn refresh_transfers(conn: &mut PgConnection, message_hashes: &[String]) -> Result<()> {
+    let mut message_hashes = message_hashes.to_vec();
+    message_hashes.sort();
+    message_hashes.dedup();
+
+    for message_hash in message_hashes {
+        let rows = sqlx::query(r#"
+            SELECT b.kind, b.chain_id, b.counterpart_chain_id, b.block_number, b.sender, b.recipient, b.token,
+                b.amount, b.nonce, e.transaction_hash, e.timestamp
+            FROM bridge_events b JOIN indexed_events e ON e.id = b.event_id
+            WHERE b.message_hash = $1
+            ORDER BY e.timestamp
+        "#)
+        .bind(&message_hash)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let mut deposit = None;
+        let mut withdrawal = None;
+        for row in &rows {
+            let end = transfer_end(row)?;
+            match row.try_get::<&str, _>("kind")? {
+                "deposit" => deposit = deposit.or(Some(end)),
+                "withdrawal" => withdrawal = withdrawal.or(Some(end)),
+                _ => {}
+            }
+        }
+
+        let Some(transfer) = BridgeTransfer::build(parse_hash(&message_hash)?, deposit.as_ref(), withdrawal.as_ref()) else {
+            sqlx::query("DELETE FROM bridge_transfers WHERE message_hash = $1")
+                .bind(&message_hash)
+                .execute(&mut *conn)
+                .await?;
+            continue;
+        };
+
+        sqlx::query(r#"
+            INSERT INTO bridge_transfers (
+                message_hash, nonce, source_chain_id, destination_chain_id, sender, recipient, token, amount,
+                source_tx, destination_tx, sent_at, received_at, status, latency_ms, updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, NOW())
+            ON CONFLICT (message_hash) DO UPDATE SET
+                nonce = EXCLUDED.nonce,
+                source_chain_id = EXCLUDED.source_chain_id,
+                destination_chain_id = EXCLUDED.destination_chain_id,
+                sender = EXCLUDED.sender,
+                recipient = EXCLUDED.recipient,
+                token = EXCLUDED.token,
+                amount = EXCLUDED.amount,
+                source_tx = EXCLUDED.source_tx,
+                destination_tx = EXCLUDED.destination_tx,
+                sent_at = EXCLUDED.sent_at,
+                received_at = EXCLUDED.received_at,
+                status = CASE
+                    WHEN bridge_transfers.status = 'stuck' AND EXCLUDED.status = 'pending' THEN 'stuck'
+                    ELSE EXCLUDED.status
+                END,
+                latency_ms = EXCLUDED.latency_ms,
+                updated_at = EXCLUDED.updated_at
+        "#)
+        .bind(&message_hash)
+        .bind(transfer.nonce.to_string())
+        .bind(transfer.source_chain_id as i64)
+        .bind(transfer.destination_chain_id as i64)
+        .bind(transfer.sender.map(|sender| format!("{:#x}", sender)))
+        .bind(format!("{:#x}", transfer.recipient))
+        .bind(format!("{:#x}", transfer.token))
+        .bind(transfer.amount.to_string())
+        .bind(transfer.source_tx.map(|tx| format!("{:#x}", tx)))
+        .bind(transfer.destination_tx.map(|tx| format!("{:#x}", tx)))
+        .bind(transfer.sent_at)
+        .bind(transfer.received_at)
+        .bind(transfer.status.as_str())
+        .bind(transfer.latency_ms)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn transfer_end(row: &PgRow) -> Result<TransferEnd> {
+    Ok(TransferEnd {
+        chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+        counterpart_chain_id: row.try_get::<i64, _>("counterpart_chain_id")? as u64,
+        transaction_hash: parse_hash(row.try_get("transaction_hash")?)?,
+        block_number: row.try_get::<i64, _>("block_number")? as u64,
+        timestamp: row.try_get("timestamp")?,
+        sender: row.try_get::<Option<&str>, _>("sender")?.map(parse_address).transpose()?,
+        recipient: parse_address(row.try_get("recipient")?)?,
+        token: parse_address(row.try_get("token")?)?,
+        amount: parse_u256(row.try_get("amount")?)?,
+        nonce: parse_u256(row.try_get("nonce")?)?,
+    })
+}
+
+fn transfer_from_row(row: &PgRow) -> Result<BridgeTransfer> {
+    let optional_hash = |column: &str| -> Result<Option<H256>> {
+        row.try_get::<Option<&str>, _>(column)?.map(parse_hash).transpose()
+    };
+    Ok(BridgeTransfer {
+        message_hash: parse_hash(row.try_get("message_hash")?)?,
+        nonce: parse_u256(row.try_get("nonce")?)?,
+        source_chain_id: row.try_get::<i64, _>("source_chain_id")? as u64,
+        destination_chain_id: row.try_get::<i64, _>("destination_chain_id")? as u64,
+        sender: row.try_get::<Option<&str>, _>("sender")?.map(parse_address).transpose()?,
+        recipient: parse_address(row.try_get("recipient")?)?,
+        token: parse_address(row.try_get("token")?)?,
+        amount: parse_u256(row.try_get("amount")?)?,
+        source_tx: optional_hash("source_tx")?,
+        destination_tx: optional_hash("destination_tx")?,
+        sent_at: row.try_get("sent_at")?,
+        received_at: row.try_get("received_at")?,
+        status: row.try_get::<&str, _>("status")?.parse::<TransferStatus>()?,
+        latency_ms: row.try_get("latency_ms")?,
+    })
+}
+
+fn intent_milestone(row: &PgRow) -> Result<Option<IntentMilestone>> {
+    let Some(phase) = IntentPhase::from_kind(row.try_get("kind")?) else { return Ok(None) };
+    Ok(Some(IntentMilestone {
+        phase,
+        chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+        account: Address::from_str(row.try_get("account")?)
+            .map_err(|e| IndexerError::Decoding(e.to_string()))?,
+        success: row.try_get("success")?,
+        timestamp: row.try_get("timestamp")?,
+    }))
+}
+
+fn bridge_milestone(row: &PgRow) -> Result<BridgeMilestone> {
+    Ok(BridgeMilestone {
+        message_hash: parse_hash(row.try_get("message_hash")?)?,
+        deposit: row.try_get::<&str, _>("kind")? == "deposit",
+        chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+        counterpart_chain_id: row.try_get::<i64, _>("counterpart_chain_id")? as u64,
+        timestamp: row.try_get("timestamp")?,
+    })
+}
+
+type PgQuery = Query<'static, Postgres, PgArguments>;
+
+fn intent_row(
+    event: &IndexedEvent,
+    intent_id: [u8; 32],
+    kind: &'static str,
+    account: Address,
+    success: Option<bool>,
+    event_timestamp: Option<String>,
+) -> PgQuery {
+    sqlx::query(r#"
+        INSERT INTO intent_events (
+            event_id, chain_id, block_number, intent_id, kind, account, success, event_timestamp
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+    "#)
+    .bind(event.id)
+    .bind(event.chain_id as i64)
+    .bind(event.block_number as i64)
+    .bind(format!("{:#x}", H256::from(intent_id)))
+    .bind(kind)
+    .bind(format!("{:#x}", account))
+    .bind(success)
+    .bind(event_timestamp)
+}
+
+struct BridgeRow {
+    message_hash: H256,
+    kind: &'static str,
+    sender: Option<Address>,
+    recipient: Address,
+    token: Address,
+    amount: ethers::types::U256,
+    counterpart_chain_id: u64,
+    nonce: ethers::types::U256,
+}
+
+fn bridge_row(event: &IndexedEvent, row: BridgeRow) -> PgQuery {
+    sqlx::query(r#"
+        INSERT INTO bridge_events (
+            event_id, chain_id, block_number, message_hash, kind, sender, recipient,
+            token, amount, counterpart_chain_id, nonce
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+    "#)
+    .bind(event.id)
+    .bind(event.chain_id as i64)
+    .bind(event.block_number as i64)
+    .bind(format!("{:#x}", row.message_hash))
+    .bind(row.kind)
+    .bind(row.sender.map(|sender| format!("{:#x}", sender)))
+    .bind(format!("{:#x}", row.recipient))
+    .bind(format!("{:#x}", row.token))
+    .bind(row.amount.to_string())
+    .bind(row.counterpart_chain_id as i64)
+    .bind(row.nonce.to_string())
+}
+
+fn event_from_row(row: &PgRow) -> Result<IndexedEvent> {
+    Ok(IndexedEvent {
+        id: row.try_get("id")?,
+        chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+        block_number: row.try_get::<i64, _>("block_number")? as u64,
+        transaction_hash: parse_hash(row.try_get("transaction_hash")?)?,
+        transaction_index: row.try_get::<i64, _>("transaction_index")? as u64,
+        log_index: row.try_get::<i64, _>("log_index")? as u64,
+        event_type: row.try_get("event_type")?,
+        contract_address: Address::from_str(row.try_get("contract_address")?)
+            .map_err(|e| IndexerError::Decoding(e.to_string()))?,
+        event_data: row.try_get("event_data")?,
+        timestamp: row.try_get("timestamp")?,
+        processed: row.try_get("processed")?,
+    })
+}
+
+fn solver_stats_from_row(row: &PgRow) -> Result<SolverStats> {
+    Ok(SolverStats {
+        solver: row.try_get("solver")?,
+        intents_matched: row.try_get::<i64, _>("intents_matched")? as u64,
+        intents_executed: row.try_get::<i64, _>("intents_executed")? as u64,
+        intents_succeeded: row.try_get::<i64, _>("intents_succeeded")? as u64,
+        avg_latency_ms: row.try_get("avg_latency_ms")?,
+        avg_slippage_bps: row.try_get("avg_slippage_bps")?,
+        slippage_samples: row.try_get::<i64, _>("slippage_samples")? as u64,
+        slashings: row.try_get::<i64, _>("slashings")? as u64,
+        slashed_amount: parse_u256(row.try_get("slashed_amount")?)?,
+        last_active_at: row.try_get("last_active_at")?,
+    })
+}
+
+async fn rollup_rows(
+    pool: &PgPool,
+    table: &str,
+    granularity: Granularity,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<PgRow>> {
+    let query = format!(
+        "SELECT * FROM {} WHERE granularity = $1 AND bucket_start >= $2 AND bucket_start < $3 ORDER BY bucket_start",
+        table
+    );
+    Ok(sqlx::query(&query)
+        .bind(granularity.as_str())
+        .bind(from)
+        .bind(to)
+        .fetch_all(pool)
+        .await?)
+}
+
+fn tvl_from_row(row: &PgRow) -> Result<PoolTvlRollup> {
+    Ok(PoolTvlRollup {
+        bucket_start: row.try_get("bucket_start")?,
+        chain_id: row.try_get::<i64, _>("chain_id")? as u64,
+        pool_id: row.try_get("pool_id")?,
+        tvl: parse_u256(row.try_get("tvl")?)?,
+        liquidity_added: parse_u256(row.try_get("liquidity_added")?)?,
+    })
+}
+
+fn parse_u256(value: &str) -> Result<ethers::types::U256> {
+    ethers::types::U256::from_dec_str(value).map_err(|e| IndexerError::Decoding(e.to_string()))
+}
+
+fn parse_address(value: &str) -> Result<Address> {
+    Address::from_str(value).map_err(|e| IndexerError::Decoding(e.to_string()))
+}
+
+fn parse_hash(value: &str) -> Result<H256> {
+    H256::from_str(value).map_err(|e| IndexerError::Decoding(e.to_string()))
+}
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use tokio::{
+    sync::watch,
+    task::JoinHandle,
+    time::{sleep, Duration, Instant},
+};
+
+use crate::{error::Result, heads::Backoff};
+
+// What a chain's supervisor is doing
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ChainStatus {
+    #[default]
+    Starting,
+    Running { since: DateTime<Utc> },
+    Paused,
+    // The last run failed; the next starts at `retry_at`
+    Restarting { attempt: u32, retry_at: DateTime<Utc>, last_error: String },
+}
+
+// Restarts a chain's indexing whenever it fails, with its own backoff, so
+// one chain's dead RPC never holds up the others. Pausing cancels the
+// current run; resuming starts a fresh one from the stored chain state.
+pub struct ChainSupervisor {
+    chain_id: u64,
+    paused: watch::Sender<bool>,
+    status: Arc<RwLock<ChainStatus>>,
+    task: JoinHandle<()>,
+}
+
+impl ChainSupervisor {
+    // `run` starts one run of the chain's indexer, which only returns on
+    // failure
+    pub fn spawn<F>(chain_id: u64, backoff: Backoff, run: F) -> Self
+    where
+        F: Fn() -> BoxFuture<'static, Result<()>> + Send + Sync + 'static,
+    {
+        let (paused, paused_rx) = watch::channel(false);
+        let status = Arc::new(RwLock::new(ChainStatus::Starting));
+        let task = tokio::spawn(supervise(chain_id, backoff, run, paused_rx, status.clone()));
+
+        Self { chain_id, paused, status, task }
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    pub fn status(&self) -> ChainStatus {
+        self.status.read().unwrap().clone()
+    }
+
+    pub fn pause(&self) {
+        self.paused.send_replace(true);
+    }
+
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    pub fn stop(&self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for ChainSupervisor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn supervise<F>(
+    chain_id: u64,
+    mut backoff: Backoff,
+    run: F,
+    mut paused: watch::Receiver<bool>,
+    status: Arc<RwLock<ChainStatus>>,
+) where
+    F: Fn() -> BoxFuture<'static, Result<()>>,
+{
+    let set_status = |value: ChainStatus| *status.write().unwrap() = value;
+    let mut attempt = 0;
+
+    loop {
+        if *paused.borrow_and_update() {
+            set_status(ChainStatus::Paused);
+            tracing::info!("Indexing paused for chain {}", chain_id);
+            if paused.wait_for(|paused| !*paused).await.is_err() {
+                return;
+            }
+            tracing::info!("Indexing resumed for chain {}", chain_id);
+            backoff.reset();
+            attempt = 0;
+        }
+
+        set_status(ChainStatus::Running { since: Utc::now() });
+        let started = Instant::now();
+        let error = tokio::select! {
+            result = run() => match result {
+                Ok(()) => "indexer stopped".to_string(),
+                Err(e) => e.to_string(),
+            },
+            // Dropping the run cancels it
+            changed = paused.wait_for(|paused| *paused) => {
+                if changed.is_err() {
+                    return;
+                }
+                continue;
+            }
+        };
+
+        // A run that stayed up for a while starts the backoff over
+        if started.elapsed() >= backoff.max_delay() {
+            backoff.reset();
+            attempt = 0;
+        }
+        let delay = backoff.next_delay();
+        attempt += 1;
+
+        tracing::error!("Indexer for chain {} failed (attempt {}), restarting in {:?}: {}", chain_id, attempt, delay, error);
+        set_status(ChainStatus::Restarting {
+            attempt,
+            retry_at: Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default(),
+            last_error: error,
+        });
+
+        tokio::select! {
+            _ = sleep(delay) => {}
+            _ = paused.wait_for(|paused| *paused) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::IndexerError;
+    use futures::FutureExt;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_backoff() -> Backoff {
+        Backoff::new(Duration::from_millis(10), Duration::from_millis(40))
+    }
+
+    #[tokio::test]
+    async fn test_failing_chain_is_restarted_with_backoff() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = runs.clone();
+        let supervisor = ChainSupervisor::spawn(1, fast_backoff(), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { Err(IndexerError::ProviderError("connection refused".to_string())) }.boxed()
+        });
+
+        sleep(Duration::from_millis(150)).await;
+        assert!(runs.load(Ordering::SeqCst) >= 3);
+        assert!(matches!(
+            supervisor.status(),
+            ChainStatus::Restarting { ref last_error, .. } if last_error.contains("connection refused")
+        ));
+        supervisor.stop();
+    }
+
+    #[tokio::test]
+    async fn test_pause_cancels_run_and_resume_restarts() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = runs.clone();
+        let supervisor = ChainSupervisor::spawn(1, fast_backoff(), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            futures::future::pending().boxed()
+        });
+
+        sleep(Duration::from_millis(20)).await;
+        assert!(matches!(supervisor.status(), ChainStatus::Running { .. }));
+
+        supervisor.pause();
+        sleep(Duration::from_millis(20)).await;
+        assert_eq!(supervisor.status(), ChainStatus::Paused);
+
+        supervisor.resume();
+        sleep(Duration::from_millis(20)).await;
+        assert!(matches!(supervisor.status(), ChainStatus::Running { .. }));
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        supervisor.stop();
+    }
+}
@@ -0,0 +1,237 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use ethers::types::{Address, H256, U256};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+use tokio::time::{interval, Duration};
+
+use crate::{
+    config::BridgeSlaConfig,
+    error::{IndexerError, Result},
+    metrics::IndexerMetrics,
+    storage::IndexerStorage,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferStatus {
+    // Sent, not yet received
+    Pending,
+    Completed,
+    // Pending for longer than the destination chain's SLA
+    Stuck,
+    // Received, but the send was not indexed
+    Orphaned,
+}
+
+impl TransferStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Completed => "completed",
+            Self::Stuck => "stuck",
+            Self::Orphaned => "orphaned",
+        }
+    }
+}
+
+impl FromStr for TransferStatus {
+    type Err = IndexerError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "pending" => Ok(Self::Pending),
+            "completed" => Ok(Self::Completed),
+            "stuck" => Ok(Self::Stuck),
+            "orphaned" => Ok(Self::Orphaned),
+            other => Err(IndexerError::Decoding(format!("Unknown transfer status: {}", other))),
+        }
+    }
+}
+
+// One side of a bridge message as indexed on its chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferEnd {
+    pub chain_id: u64,
+    pub counterpart_chain_id: u64,
+    pub transaction_hash: H256,
+    pub block_number: u64,
+    pub timestamp: DateTime<Utc>,
+    pub sender: Option<Address>,
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: U256,
+    pub nonce: U256,
+}
+
+// A bridge message with its send and receive matched across chains
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BridgeTransfer {
+    pub message_hash: H256,
+    pub nonce: U256,
+    pub source_chain_id: u64,
+    pub destination_chain_id: u64,
+    pub sender: Option<Address>,
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: U256,
+    pub source_tx: Option<H256>,
+    pub destination_tx: Option<H256>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub received_at: Option<DateTime<Utc>>,
+    pub status: TransferStatus,
+    pub latency_ms: Option<i64>,
+}
+
+impl BridgeTransfer {
+    // `None` when neither side is indexed. Transfers come out pending or
+    // completed; only the SLA monitor marks them stuck.
+    pub fn build(message_hash: H256, deposit: Option<&TransferEnd>, withdrawal: Option<&TransferEnd>) -> Option<Self> {
+        let known = deposit.or(withdrawal)?;
+        let (source_chain_id, destination_chain_id) = match (deposit, withdrawal) {
+            (Some(deposit), _) => (deposit.chain_id, deposit.counterpart_chain_id),
+            (None, Some(withdrawal)) => (withdrawal.counterpart_chain_id, withdrawal.chain_id),
+            (None, None) => return None,
+        };
+        let status = match (deposit, withdrawal) {
+            (Some(_), Some(_)) => TransferStatus::Completed,
+            (Some(_), None) => TransferStatus::Pending,
+            _ => TransferStatus::Orphaned,
+        };
+        let sent_at = deposit.map(|end| end.timestamp);
+        let received_at = withdrawal.map(|end| end.timestamp);
+
+        Some(Self {
+            message_hash,
+            nonce: known.nonce,
+            source_chain_id,
+            destination_chain_id,
+            sender: deposit.and_then(|end| end.sender),
+            recipient: known.recipient,
+            token: known.token,
+            amount: known.amount,
+            source_tx: deposit.map(|end| end.transaction_hash),
+            destination_tx: withdrawal.map(|end| end.transaction_hash),
+            sent_at,
+            received_at,
+            status,
+            latency_ms: sent_at.zip(received_at).map(|(sent, received)| (received - sent).num_milliseconds()),
+        })
+    }
+
+    // Whether a pending transfer has waited longer than `sla`
+    pub fn is_overdue(&self, now: DateTime<Utc>, sla: ChronoDuration) -> bool {
+        self.status == TransferStatus::Pending && self.sent_at.is_some_and(|sent| now - sent > sla)
+    }
+}
+
+// Marks transfers stuck once they outlive their SLA and alerts on them
+pub struct BridgeMonitor {
+    config: BridgeSlaConfig,
+    storage: Arc<IndexerStorage>,
+    metrics: Arc<IndexerMetrics>,
+}
+
+impl BridgeMonitor {
+    pub fn new(config: BridgeSlaConfig, storage: Arc<IndexerStorage>, metrics: Arc<IndexerMetrics>) -> Self {
+        Self { config, storage, metrics }
+    }
+
+    pub async fn run(self) {
+        let mut ticks = interval(Duration::from_secs(self.config.interval_secs.max(1)));
+        loop {
+            ticks.tick().await;
+            if let Err(e) = self.check().await {
+                tracing::warn!("Bridge SLA check failed: {}", e);
+            }
+        }
+    }
+
+    pub async fn check(&self) -> Result<Vec<BridgeTransfer>> {
+        let now = Utc::now();
+        let oldest = now - ChronoDuration::seconds(self.config.min_sla_secs() as i64);
+
+        let overdue: Vec<BridgeTransfer> = self
+            .storage
+            .pending_bridge_transfers(oldest)
+            .await?
+            .into_iter()
+            .filter(|transfer| {
+                let sla = self.config.sla_secs(transfer.destination_chain_id);
+                transfer.is_overdue(now, ChronoDuration::seconds(sla as i64))
+            })
+            .collect();
+
+        for transfer in &overdue {
+            tracing::error!(
+                "Bridge message {:#x} from chain {} to {} is stuck: sent {} in tx {:#x}, not received within {}s",
+                transfer.message_hash,
+                transfer.source_chain_id,
+                transfer.destination_chain_id,
+                transfer.sent_at.map(|at| at.to_rfc3339()).unwrap_or_default(),
+                transfer.source_tx.unwrap_or_default(),
+                self.config.sla_secs(transfer.destination_chain_id)
+            );
+        }
+        let hashes: Vec<H256> = overdue.iter().map(|transfer| transfer.message_hash).collect();
+        self.storage.mark_transfers_stuck(&hashes).await?;
+
+        let stuck: HashMap<u64, u64> = self.storage.stuck_transfer_counts().await?.into_iter().collect();
+        self.metrics.set_stuck_transfers(&stuck);
+
+        Ok(overdue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn end(chain_id: u64, counterpart_chain_id: u64, second: u32) -> TransferEnd {
+        TransferEnd {
+            chain_id,
+            counterpart_chain_id,
+            transaction_hash: H256::from_low_u64_be(chain_id),
+            block_number: 10,
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, second).unwrap(),
+            sender: (chain_id == 1).then(|| Address::repeat_byte(1)),
+            recipient: Address::repeat_byte(2),
+            token: Address::repeat_byte(3),
+            amount: U256::from(100u64),
+            nonce: U256::from(7u64),
+        }
+    }
+
+    #[test]
+    fn test_matches_send_and_receive() {
+        let hash = H256::repeat_byte(9);
+        let deposit = end(1, 10, 0);
+        let withdrawal = end(10, 1, 45);
+
+        let completed = BridgeTransfer::build(hash, Some(&deposit), Some(&withdrawal)).unwrap();
+        assert_eq!(completed.status, TransferStatus::Completed);
+        assert_eq!((completed.source_chain_id, completed.destination_chain_id), (1, 10));
+        assert_eq!(completed.latency_ms, Some(45_000));
+        assert_eq!(completed.destination_tx, Some(withdrawal.transaction_hash));
+        assert_eq!(completed.sender, Some(Address::repeat_byte(1)));
+
+        let orphaned = BridgeTransfer::build(hash, None, Some(&withdrawal)).unwrap();
+        assert_eq!(orphaned.status, TransferStatus::Orphaned);
+        assert_eq!((orphaned.source_chain_id, orphaned.destination_chain_id), (1, 10));
+        assert!(BridgeTransfer::build(hash, None, None).is_none());
+    }
+
+    #[test]
+    fn test_pending_transfers_become_overdue_after_sla() {
+        let pending = BridgeTransfer::build(H256::zero(), Some(&end(1, 10, 0)), None).unwrap();
+        assert_eq!(pending.status, TransferStatus::Pending);
+
+        let sent = pending.sent_at.unwrap();
+        let sla = ChronoDuration::minutes(30);
+        assert!(!pending.is_overdue(sent + ChronoDuration::minutes(29), sla));
+        assert!(pending.is_overdue(sent + ChronoDuration::minutes(31), sla));
+
+        assert_eq!("stuck".parse::<TransferStatus>().unwrap(), TransferStatus::Stuck);
+        assert!("lost".parse::<TransferStatus>().is_err());
+    }
+}
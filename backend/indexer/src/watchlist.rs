@@ -0,0 +1,347 @@
+use ethers::types::{Address, Filter, H256};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
+use tokio::time::{interval, Duration};
+
+use crate::{
+    backfill::{Backfill, BlockRange},
+    config::BackfillConfig,
+    error::{IndexerError, Result},
+    events::{DecodedEvent, EventProcessor},
+    storage::IndexerStorage,
+    ChainIndexerConfig,
+};
+
+// A contract whose events are indexed from `start_block` on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchedContract {
+    pub chain_id: u64,
+    pub address: Address,
+    pub start_block: u64,
+    // Tracked event names to index, e.g. "ToroidalSwap"; all when empty
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+impl WatchedContract {
+    pub fn validate(&self) -> Result<()> {
+        match self.events.iter().find(|name| DecodedEvent::signature_of(name).is_none()) {
+            Some(unknown) => Err(IndexerError::Config(format!(
+                "Unknown event {} for contract {:#x} on chain {}",
+                unknown, self.address, self.chain_id
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn topics(&self) -> Vec<H256> {
+        let mut topics: Vec<H256> = self.events.iter().filter_map(|name| DecodedEvent::signature_of(name)).collect();
+        topics.sort();
+        topics.dedup();
+        topics
+    }
+}
+
+// Log filters for `contracts` over `range`, one per distinct start block and
+// event set, each clipped to the contracts' start block
+pub fn build_filters(contracts: &[WatchedContract], range: BlockRange) -> Vec<Filter> {
+    let mut groups: BTreeMap<(u64, Vec<H256>), Vec<Address>> = BTreeMap::new();
+    for contract in contracts.iter().filter(|contract| contract.start_block <= range.to) {
+        let from = range.from.max(contract.start_block);
+        groups.entry((from, contract.topics())).or_default().push(contract.address);
+    }
+
+    groups
+        .into_iter()
+        .map(|((from, topics), addresses)| {
+            let filter = Filter::new().address(addresses).from_block(from).to_block(range.to);
+            if topics.is_empty() {
+                filter
+            } else {
+                filter.topic0(topics)
+            }
+        })
+        .collect()
+}
+
+// Entries of `new` that are not in `old` or changed, and entries of `old`
+// no longer in `new`
+pub fn diff(old: &[WatchedContract], new: &[WatchedContract]) -> (Vec<WatchedContract>, Vec<WatchedContract>) {
+    let added = new.iter().filter(|contract| !old.contains(contract)).cloned().collect();
+    let removed = old
+        .iter()
+        .filter(|contract| {
+            !new.iter().any(|other| other.chain_id == contract.chain_id && other.address == contract.address)
+        })
+        .cloned()
+        .collect();
+    (added, removed)
+}
+
+// The contracts each chain indexes, shared with the chain indexers so
+// changes apply from their next block
+#[derive(Debug, Clone, Default)]
+pub struct WatchList {
+    contracts: Arc<RwLock<HashMap<u64, BTreeMap<Address, WatchedContract>>>>,
+}
+
+impl WatchList {
+    // The contracts each chain is configured with
+    pub fn from_chains(chains: &[ChainIndexerConfig]) -> Self {
+        let list = Self::default();
+        for chain in chains {
+            let contracts = &chain.contracts;
+            let addresses = [contracts.intents_contract, contracts.orbital_amm_contract, contracts.bridge_contract]
+                .into_iter()
+                .chain(contracts.solver_registry);
+            for address in addresses {
+                list.insert(WatchedContract {
+                    chain_id: chain.chain_id,
+                    address,
+                    start_block: chain.start_block,
+                    events: Vec::new(),
+                });
+            }
+        }
+        list
+    }
+
+    // Replaces any entry for the same contract
+    pub fn insert(&self, contract: WatchedContract) -> Option<WatchedContract> {
+        self.contracts
+            .write()
+            .unwrap()
+            .entry(contract.chain_id)
+            .or_default()
+            .insert(contract.address, contract)
+    }
+
+    pub fn remove(&self, chain_id: u64, address: Address) -> Option<WatchedContract> {
+        self.contracts.write().unwrap().get_mut(&chain_id)?.remove(&address)
+    }
+
+    pub fn contains(&self, chain_id: u64, address: Address) -> bool {
+        self.contracts
+            .read()
+            .unwrap()
+            .get(&chain_id)
+            .is_some_and(|contracts| contracts.contains_key(&address))
+    }
+
+    pub fn contracts(&self, chain_id: u64) -> Vec<WatchedContract> {
+        self.contracts
+            .read()
+            .unwrap()
+            .get(&chain_id)
+            .map(|contracts| contracts.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn filters(&self, chain_id: u64, range: BlockRange) -> Vec<Filter> {
+        build_filters(&self.contracts(chain_id), range)
+    }
+}
+
+// Adds and removes watched contracts at runtime, persisting them and
+// backfilling each new contract from its start block
+pub struct WatchListManager {
+    chains: Vec<ChainIndexerConfig>,
+    settings: BackfillConfig,
+    storage: Arc<IndexerStorage>,
+    event_processor: Arc<EventProcessor>,
+    watch_list: WatchList,
+}
+
+impl WatchListManager {
+    pub fn new(
+        chains: Vec<ChainIndexerConfig>,
+        settings: BackfillConfig,
+        storage: Arc<IndexerStorage>,
+        event_processor: Arc<EventProcessor>,
+    ) -> Self {
+        let watch_list = WatchList::from_chains(&chains);
+        Self { chains, settings, storage, event_processor, watch_list }
+    }
+
+    pub fn watch_list(&self) -> &WatchList {
+        &self.watch_list
+    }
+
+    // Add the contracts watched in earlier runs
+    pub async fn load(&self) -> Result<()> {
+        for contract in self.storage.load_watched_contracts().await? {
+            self.watch_list.insert(contract);
+        }
+        Ok(())
+    }
+
+    // `load`, then finish any backfill an earlier run left off
+    pub async fn restore(self: &Arc<Self>) -> Result<()> {
+        for contract in self.storage.load_watched_contracts().await? {
+            self.watch_list.insert(contract.clone());
+            self.spawn_backfill(contract);
+        }
+        Ok(())
+    }
+
+    pub async fn watch(self: &Arc<Self>, contract: WatchedContract) -> Result<()> {
+        contract.validate()?;
+        if self.chain(contract.chain_id).is_none() {
+            return Err(IndexerError::Config(format!("Chain {} is not configured", contract.chain_id)));
+        }
+
+        self.storage.save_watched_contract(&contract).await?;
+        self.watch_list.insert(contract.clone());
+        tracing::info!("Watching {:#x} on chain {} from block {}", contract.address, contract.chain_id, contract.start_block);
+        self.spawn_backfill(contract);
+        Ok(())
+    }
+
+    // Events already indexed for the contract are kept
+    pub async fn unwatch(&self, chain_id: u64, address: Address) -> Result<bool> {
+        self.storage.remove_watched_contract(chain_id, address).await?;
+        let removed = self.watch_list.remove(chain_id, address).is_some();
+        if removed {
+            tracing::info!("Stopped watching {:#x} on chain {}", address, chain_id);
+        }
+        Ok(removed)
+    }
+
+    // Apply changes to a JSON list of contracts as the file changes
+    pub async fn follow_file(self: Arc<Self>, path: PathBuf, poll_interval: Duration) {
+        let mut current: Vec<WatchedContract> = Vec::new();
+        let mut ticks = interval(poll_interval);
+        loop {
+            ticks.tick().await;
+
+            let listed = match read_watch_file(&path).await {
+                Ok(listed) => listed,
+                Err(e) => {
+                    tracing::warn!("Failed to read watch list {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+
+            let (added, removed) = diff(&current, &listed);
+            for contract in added {
+                if let Err(e) = self.watch(contract).await {
+                    tracing::warn!("Failed to watch contract from {}: {}", path.display(), e);
+                }
+            }
+            for contract in removed {
+                if let Err(e) = self.unwatch(contract.chain_id, contract.address).await {
+                    tracing::warn!("Failed to unwatch contract from {}: {}", path.display(), e);
+                }
+            }
+            current = listed;
+        }
+    }
+
+    fn chain(&self, chain_id: u64) -> Option<&ChainIndexerConfig> {
+        self.chains.iter().find(|chain| chain.chain_id == chain_id && chain.enabled)
+    }
+
+    fn spawn_backfill(self: &Arc<Self>, contract: WatchedContract) {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            match manager.backfill(&contract).await {
+                Ok(events) => tracing::info!(
+                    "Backfilled {} events for {:#x} on chain {}",
+                    events,
+                    contract.address,
+                    contract.chain_id
+                ),
+                Err(e) => tracing::error!(
+                    "Backfill of {:#x} on chain {} failed: {}",
+                    contract.address,
+                    contract.chain_id,
+                    e
+                ),
+            }
+        });
+    }
+
+    async fn backfill(&self, contract: &WatchedContract) -> Result<u64> {
+        let chain = self
+            .chain(contract.chain_id)
+            .ok_or_else(|| IndexerError::Config(format!("Chain {} is not configured", contract.chain_id)))?;
+        let backfill = Backfill::new(
+            chain.clone(),
+            self.settings.clone(),
+            self.storage.clone(),
+            self.event_processor.clone(),
+            self.watch_list.clone(),
+        )?;
+        backfill.backfill_contract(contract).await
+    }
+}
+
+async fn read_watch_file(path: &PathBuf) -> Result<Vec<WatchedContract>> {
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| IndexerError::Config(e.to_string()))?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{BlockNumber, FilterBlockOption};
+
+    fn contract(byte: u8, start_block: u64, events: &[&str]) -> WatchedContract {
+        WatchedContract {
+            chain_id: 1,
+            address: Address::repeat_byte(byte),
+            start_block,
+            events: events.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    fn from_block(filter: &Filter) -> Option<u64> {
+        match filter.block_option {
+            FilterBlockOption::Range { from_block: Some(BlockNumber::Number(from)), .. } => Some(from.as_u64()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_filters_group_contracts_and_clip_start_blocks() {
+        let contracts = [
+            contract(1, 0, &[]),
+            contract(2, 50, &[]),
+            contract(3, 150, &[]),
+            contract(4, 500, &[]),
+            contract(5, 0, &["ToroidalSwap"]),
+        ];
+        let filters = build_filters(&contracts, BlockRange { from: 100, to: 200 });
+
+        // 1 and 2 share a filter, 3 starts mid-range, 4 is not live yet and
+        // 5 only wants swaps
+        assert_eq!(filters.len(), 3);
+        assert_eq!(filters.iter().map(from_block).collect::<Vec<_>>(), vec![Some(100), Some(100), Some(150)]);
+        assert!(filters.iter().any(|filter| filter.topics[0].is_some()));
+
+        assert!(contract(6, 0, &["NotAnEvent"]).validate().is_err());
+        assert!(contract(6, 0, &["BridgeDeposit"]).validate().is_ok());
+    }
+
+    #[test]
+    fn test_diff_and_list_updates() {
+        let old = [contract(1, 0, &[]), contract(2, 10, &[])];
+        let new = [contract(2, 5, &[]), contract(3, 0, &[])];
+        let (added, removed) = diff(&old, &new);
+        assert_eq!(added, vec![contract(2, 5, &[]), contract(3, 0, &[])]);
+        assert_eq!(removed, vec![contract(1, 0, &[])]);
+
+        let list = WatchList::default();
+        list.insert(contract(1, 0, &[]));
+        assert!(list.insert(contract(1, 20, &[])).is_some());
+        assert_eq!(list.contracts(1), vec![contract(1, 20, &[])]);
+        assert!(list.remove(1, Address::repeat_byte(1)).is_some());
+        assert!(!list.contains(1, Address::repeat_byte(1)));
+    }
+}
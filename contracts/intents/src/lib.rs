@@ -1,10 +1,357 @@
-#![cfg_attr(not(feature = "export-abi"), no_std, no_main)]
+#![cfg_attr(not(any(test, feature = "export-abi")), no_std, no_main)]
 
 extern crate alloc;
+use alloc::vec::Vec;
 
-use stylus_sdk::{alloy_primitives::{U256, Address, B256}, prelude::*, ArbResult};
+use stylus_sdk::{alloy_primitives::{U256, Address, B256}, call::{self, Call}, contract, prelude::*, ArbResult};
 use alloy_sol_types::sol;
 
+sol_interface! {
+    interface IOrbitalAMM {
+        function toroidalSwap(uint256 pool_id, uint256 token_in, uint256 token_out, uint256 amount_in, uint256 min_amount_out) external returns (uint256);
+        function getOrbitalPool(uint256 pool_id) external view returns (address[] memory, uint256[] memory, uint256, uint256, bool);
+    }
+
+    interface IERC20 {
+        function transfer(address to, uint256 amount) external returns (bool);
+        function transferFrom(address from, address to, uint256 amount) external returns (bool);
+        function approve(address spender, uint256 amount) external returns (bool);
+    }
+}
+
+const MAX_INTENT_LEGS: usize = 8;
+const BPS_DENOMINATOR: u64 = 10_000;
+const SECONDS_PER_DAY: u64 = 86_400;
+const REPUTATION_REWARD: u64 = 10;
+const REPUTATION_PENALTY: u64 = 20;
+const MAX_SIZE_WEIGHT: u64 = 5;
+const MAX_BATCH_SIZE: usize = 64;
+const BATCH_CREATE: u64 = 0;
+const BATCH_MATCH: u64 = 1;
+const BATCH_EXECUTE: u64 = 2;
+const OPERATOR_RATE_WINDOW: u64 = 3_600;
+const MIN_DEFENSE_PERIOD: u64 = 50; // blocks
+const DEFAULT_CHALLENGE_BOND: u64 = 10_000_000_000_000_000; // 0.01 ETH
+const DEFAULT_STAKE_COOLDOWN: u64 = 7 * SECONDS_PER_DAY;
+const PRICE_PRECISION: u64 = 1_000_000_000_000_000_000; // stake prices are per 1e18 token units
+const ECRECOVER: Address = Address::with_last_byte(1);
+// (minimum reputation, exposure multiplier of stake), highest tier first
+const EXPOSURE_TIERS: [(u64, u64); 4] = [(1000, 10), (500, 5), (100, 2), (0, 1)];
+
+// Escrow and reputation arithmetic, kept free of storage so it can be tested
+mod settlement {
+    use super::*;
+
+    /// `bps` basis points of `amount`, rounded down
+    pub fn bps_of(amount: U256, bps: U256) -> U256 {
+        amount * bps / U256::from(BPS_DENOMINATOR)
+    }
+
+    /// Flat + proportional fee on `amount`, capped at the amount, and the
+    /// part of it rebated to the solver
+    pub fn fee_split(
+        amount: U256,
+        flat_fee: U256,
+        fee_bps: U256,
+        rebate_bps: U256,
+        rebate_eligible: bool,
+    ) -> (U256, U256) {
+        let fee = (flat_fee + bps_of(amount, fee_bps)).min(amount);
+        let rebate = if rebate_eligible { bps_of(fee, rebate_bps) } else { U256::ZERO };
+        (fee, rebate)
+    }
+
+    /// Reputation left after `idle_days` of linear decay
+    pub fn decay(score: U256, decay_bps: U256, idle_days: U256) -> U256 {
+        score.saturating_sub(bps_of(score * idle_days, decay_bps))
+    }
+
+    /// Reputation weight of an intent: one per `unit` of source amount, capped
+    pub fn size_weight(amount: U256, unit: U256) -> U256 {
+        if unit == U256::ZERO {
+            return U256::from(1);
+        }
+        (U256::from(1) + amount / unit).min(U256::from(MAX_SIZE_WEIGHT))
+    }
+
+    /// Stake multiplier of the highest exposure tier `score` qualifies for
+    pub fn exposure_multiplier(score: U256) -> U256 {
+        let multiplier = EXPOSURE_TIERS
+            .iter()
+            .find(|(min_score, _)| score >= U256::from(*min_score))
+            .map(|(_, multiplier)| *multiplier)
+            .unwrap_or(1);
+        U256::from(multiplier)
+    }
+
+    /// Operator window start and action count after one more action at
+    /// `now`, or `None` once the window's limit is used up
+    pub fn next_operator_window(
+        now: U256,
+        window_start: U256,
+        actions: U256,
+        max_actions: U256,
+    ) -> Option<(U256, U256)> {
+        let (window_start, actions) = if now >= window_start + U256::from(OPERATOR_RATE_WINDOW) {
+            (now, U256::ZERO)
+        } else {
+            (window_start, actions)
+        };
+        (actions < max_actions).then(|| (window_start, actions + U256::from(1)))
+    }
+
+    /// Stake wei backing `amount` of a token worth `price` wei per 1e18
+    /// units, so exposure is compared with the stake in one unit
+    pub fn stake_value(amount: U256, price: U256) -> U256 {
+        amount.saturating_mul(price) / U256::from(PRICE_PRECISION)
+    }
+
+    /// A stake can leave once its cooldown has run out and nothing it
+    /// backs (a match, an execution or its challenge) is still open
+    pub fn may_withdraw_stake(now: U256, unlocks_at: U256, open_intents: U256) -> bool {
+        unlocks_at != U256::ZERO && now >= unlocks_at && open_intents == U256::ZERO
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn u(value: u64) -> U256 {
+            U256::from(value)
+        }
+
+        #[test]
+        fn test_fee_split_and_escrow_shares() {
+            // 10 flat + 0.3% of 10_000, a fifth of it rebated
+            assert_eq!(fee_split(u(10_000), u(10), u(30), u(2_000), true), (u(40), u(8)));
+            assert_eq!(fee_split(u(10_000), u(10), u(30), u(2_000), false), (u(40), u(0)));
+            // The fee never exceeds the escrow, so the solver payout cannot underflow
+            assert_eq!(fee_split(u(5), u(10), u(30), u(0), false), (u(5), u(0)));
+
+            // Unfilled 70% of an expired multi-leg intent, and a 0.5% cancellation fee
+            assert_eq!(bps_of(u(1_000), u(7_000)), u(700));
+            assert_eq!(bps_of(u(1_000), u(50)), u(5));
+        }
+
+        #[test]
+        fn test_reputation_decay_and_exposure_tiers() {
+            assert_eq!(decay(u(1_000), u(100), u(3)), u(970));
+            assert_eq!(decay(u(1_000), u(5_000), u(3)), u(0));
+
+            assert_eq!(size_weight(u(2_500), u(1_000)), u(3));
+            assert_eq!(size_weight(u(1_000_000), u(1_000)), u(MAX_SIZE_WEIGHT));
+            assert_eq!(size_weight(u(1_000_000), u(0)), u(1));
+
+            assert_eq!(exposure_multiplier(u(1_500)), u(10));
+            assert_eq!(exposure_multiplier(u(500)), u(5));
+            assert_eq!(exposure_multiplier(u(99)), u(1));
+        }
+
+        #[test]
+        fn test_stake_pricing_and_withdrawal() {
+            // 2_000 USDC (6 decimals) at 0.0005 ETH each is 1 ETH of exposure
+            let usdc_price = u(500_000_000_000_000) * u(1_000_000_000_000);
+            assert_eq!(stake_value(u(2_000_000_000), usdc_price), u(1_000_000_000_000_000_000));
+            assert_eq!(stake_value(u(1_000), u(0)), u(0));
+
+            assert!(may_withdraw_stake(u(100), u(100), u(0)));
+            // Not unregistered, still cooling down, or backing an open intent
+            assert!(!may_withdraw_stake(u(100), u(0), u(0)));
+            assert!(!may_withdraw_stake(u(99), u(100), u(0)));
+            assert!(!may_withdraw_stake(u(100), u(100), u(1)));
+        }
+
+        #[test]
+        fn test_operator_rate_window() {
+            assert_eq!(next_operator_window(u(100), u(0), u(1), u(2)), Some((u(0), u(2))));
+            assert_eq!(next_operator_window(u(100), u(0), u(2), u(2)), None);
+            // A fresh window resets the count
+            let now = u(OPERATOR_RATE_WINDOW);
+            assert_eq!(next_operator_window(now, u(0), u(2), u(2)), Some((now, u(1))));
+        }
+    }
+}
+
+// Attester-signed delivery receipts, the evidence for challenges and defenses
+mod attestation {
+    use super::*;
+
+    /// Packed receipt: delivered amount, observation time, 65-byte signature
+    pub const RECEIPT_LEN: usize = 32 + 32 + 65;
+
+    /// A trusted attester's report of what an execution delivered on the
+    /// destination chain, as observed at `observed_at`
+    pub struct DeliveryReceipt {
+        pub delivered_amount: U256,
+        pub observed_at: U256,
+        pub signature: [u8; 65],
+    }
+
+    impl DeliveryReceipt {
+        pub fn decode(bytes: &[u8]) -> Option<Self> {
+            if bytes.len() != RECEIPT_LEN {
+                return None;
+            }
+            let mut signature = [0u8; 65];
+            signature.copy_from_slice(&bytes[64..]);
+            Some(Self {
+                delivered_amount: U256::from_be_slice(&bytes[..32]),
+                observed_at: U256::from_be_slice(&bytes[32..64]),
+                signature,
+            })
+        }
+    }
+
+    /// EIP-191 hash an attester signs. It binds the receipt to this contract,
+    /// chain, intent and the proof the solver committed to.
+    pub fn receipt_digest(
+        contract: Address,
+        chain_id: U256,
+        intent_id: B256,
+        proof_hash: B256,
+        delivered_amount: U256,
+        observed_at: U256,
+    ) -> B256 {
+        let receipt_hash = keccak256((contract, chain_id, intent_id, proof_hash, delivered_amount, observed_at).abi_encode());
+        let mut message = Vec::with_capacity(60);
+        message.extend_from_slice(b"\x19Ethereum Signed Message:\n32");
+        message.extend_from_slice(receipt_hash.as_slice());
+        keccak256(message)
+    }
+
+    /// Input for the ecrecover precompile, rejecting malleable signatures
+    pub fn ecrecover_input(digest: B256, signature: &[u8; 65]) -> Option<[u8; 128]> {
+        // secp256k1n / 2
+        let half_order = U256::from_be_slice(&[
+            0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+        ]);
+        let v = match signature[64] {
+            0 | 27 => 27,
+            1 | 28 => 28,
+            _ => return None,
+        };
+        if U256::from_be_slice(&signature[32..64]) > half_order {
+            return None;
+        }
+
+        let mut input = [0u8; 128];
+        input[..32].copy_from_slice(digest.as_slice());
+        input[63] = v;
+        input[64..].copy_from_slice(&signature[..64]);
+        Some(input)
+    }
+
+    /// Signer returned by the precompile; empty output means no valid signer
+    pub fn recovered_signer(output: &[u8]) -> Option<Address> {
+        if output.len() != 32 {
+            return None;
+        }
+        let signer = Address::from_slice(&output[12..]);
+        (signer != Address::ZERO).then_some(signer)
+    }
+
+    /// Only the intent's user or an unrelated registered solver may
+    /// challenge; `challenger_identity` is the solver an operator acts for
+    pub fn may_challenge(
+        challenger: Address,
+        challenger_identity: Address,
+        user: Address,
+        solver: Address,
+        challenger_registered: bool,
+    ) -> bool {
+        if challenger == solver || challenger_identity == solver {
+            return false;
+        }
+        challenger == user || challenger_registered
+    }
+
+    /// Evidence must show less delivered than the solver reported, observed
+    /// after anything already used to settle an earlier challenge
+    pub fn shows_shortfall(delivered: U256, reported: U256, observed_at: U256, last_observed_at: U256) -> bool {
+        delivered < reported && observed_at > last_observed_at
+    }
+
+    /// A defense must show the reported amount delivered, observed after the
+    /// challenge's evidence
+    pub fn answers_challenge(delivered: U256, reported: U256, observed_at: U256, evidence_observed_at: U256) -> bool {
+        delivered >= reported && observed_at > evidence_observed_at
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn u(value: u64) -> U256 {
+            U256::from(value)
+        }
+
+        #[test]
+        fn test_receipt_decoding_and_digest() {
+            let mut bytes = [0u8; RECEIPT_LEN];
+            bytes[31] = 99;
+            bytes[63] = 7;
+            bytes[128] = 28;
+            let receipt = DeliveryReceipt::decode(&bytes).unwrap();
+            assert_eq!((receipt.delivered_amount, receipt.observed_at), (u(99), u(7)));
+            assert!(DeliveryReceipt::decode(&bytes[1..]).is_none());
+
+            let contract = Address::repeat_byte(1);
+            let (intent_id, proof_hash) = (B256::repeat_byte(2), B256::repeat_byte(3));
+            let digest = receipt_digest(contract, u(42161), intent_id, proof_hash, u(99), u(7));
+            // Replaying a receipt for another chain, intent or proof fails
+            assert_ne!(digest, receipt_digest(contract, u(1), intent_id, proof_hash, u(99), u(7)));
+            assert_ne!(digest, receipt_digest(contract, u(42161), B256::repeat_byte(4), proof_hash, u(99), u(7)));
+            assert_ne!(digest, receipt_digest(contract, u(42161), intent_id, B256::ZERO, u(99), u(7)));
+
+            let input = ecrecover_input(digest, &receipt.signature).unwrap();
+            assert_eq!((&input[..32], input[63]), (digest.as_slice(), 28));
+            // High-s and unknown recovery ids are rejected
+            let mut high_s = receipt.signature;
+            high_s[32] = 0xff;
+            assert!(ecrecover_input(digest, &high_s).is_none());
+            let mut bad_v = receipt.signature;
+            bad_v[64] = 5;
+            assert!(ecrecover_input(digest, &bad_v).is_none());
+
+            let mut output = [0u8; 32];
+            assert_eq!(recovered_signer(&output), None);
+            output[12..].copy_from_slice(contract.as_slice());
+            assert_eq!(recovered_signer(&output), Some(contract));
+            assert_eq!(recovered_signer(&[]), None);
+        }
+
+        #[test]
+        fn test_self_challenge_is_rejected() {
+            let (user, solver, watcher) = (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3));
+            assert!(may_challenge(user, user, user, solver, false));
+            assert!(may_challenge(watcher, watcher, user, solver, true));
+            assert!(!may_challenge(watcher, watcher, user, solver, false));
+            // An operator is judged by the registration of the solver it acts for
+            let operator = Address::repeat_byte(4);
+            assert!(may_challenge(operator, watcher, user, solver, true));
+            // The solver, one of its operators, or a solver filling its own intent
+            assert!(!may_challenge(solver, solver, user, solver, true));
+            assert!(!may_challenge(watcher, solver, user, solver, false));
+            assert!(!may_challenge(solver, solver, solver, solver, true));
+        }
+
+        #[test]
+        fn test_frivolous_challenge_is_rejected_or_defended() {
+            // Evidence of full delivery is no evidence at all
+            assert!(!shows_shortfall(u(1_000), u(1_000), u(10), u(0)));
+            assert!(shows_shortfall(u(900), u(1_000), u(10), u(0)));
+            // Nor is a stale receipt older than one that already settled a challenge
+            assert!(!shows_shortfall(u(900), u(1_000), u(10), u(12)));
+
+            // A later receipt of full delivery defends, forfeiting the bond
+            assert!(answers_challenge(u(1_000), u(1_000), u(11), u(10)));
+            assert!(!answers_challenge(u(1_000), u(1_000), u(10), u(10)));
+            assert!(!answers_challenge(u(999), u(1_000), u(11), u(10)));
+        }
+    }
+}
+
 sol! {
     event IntentCreated(bytes32 indexed intentId, address indexed user, uint256 timestamp);
     event IntentMatched(bytes32 indexed intentId, address indexed solver, uint256 timestamp);
@@ -12,6 +359,30 @@ sol! {
     event IntentCancelled(bytes32 indexed intentId, address indexed user);
     event SolverRegistered(address indexed solver, uint256 stake);
     event SolverSlashed(address indexed solver, uint256 amount, bytes32 intentId);
+    event IntentChallenged(bytes32 indexed intentId, address indexed challenger, bytes32 evidenceHash);
+    event ExecutionDefended(bytes32 indexed intentId, address indexed solver, bytes32 defenseHash);
+    event ChallengeResolved(bytes32 indexed intentId, address indexed solver, bool slashed);
+    event IntentFinalized(bytes32 indexed intentId, address indexed solver, uint256 timestamp);
+    event IntentRefunded(bytes32 indexed intentId, address indexed user, uint256 amount);
+    event MultiLegIntentCreated(bytes32 indexed intentId, address indexed user, uint256 legCount);
+    event IntentLegExecuted(bytes32 indexed intentId, uint256 legIndex, uint256 destAmount);
+    event IntentExpirySwept(bytes32 indexed intentId, address indexed caller, address solver);
+    event FeeCharged(bytes32 indexed intentId, address indexed token, uint256 fee, uint256 rebate);
+    event FeesClaimed(address indexed recipient, address indexed token, uint256 amount);
+    event IntentReplaced(bytes32 indexed oldIntentId, bytes32 indexed newIntentId, address indexed user, uint256 nonce, uint256 minDestAmount);
+    event CancellationCompensated(bytes32 indexed intentId, address indexed solver, uint256 amount);
+    event OperatorAdded(address indexed solver, address indexed operator, uint256 maxActionsPerWindow);
+    event OperatorRemoved(address indexed solver, address indexed operator);
+    event IntentBatchProcessed(address indexed caller, uint256 operation, uint256 succeeded, uint256 failed);
+    event IntentSettledOnAMM(bytes32 indexed intentId, uint256 indexed poolId, uint256 amountIn, uint256 amountOut);
+    event RefundWithdrawn(address indexed user, address indexed token, uint256 amount);
+    event AttesterUpdated(address indexed attester, bool trusted);
+    event ChallengeBondSettled(bytes32 indexed intentId, address indexed recipient, uint256 amount);
+    event SolverUnregistered(address indexed solver, uint256 unlocksAt);
+    event StakeWithdrawn(address indexed solver, uint256 amount);
+    event SlashAwarded(bytes32 indexed intentId, address indexed recipient, uint256 amount);
+    event SlashPayoutClaimed(address indexed recipient, uint256 amount);
+    event TokenStakePriceUpdated(address indexed token, uint256 price);
 }
 
 #[derive(SolidityError)]
@@ -24,6 +395,19 @@ pub enum IntentsError {
     IntentNotMatched(IntentNotMatched),
     ExecutionFailed(ExecutionFailed),
     InvalidIntent(InvalidIntent),
+    IntentNotExecuted(IntentNotExecuted),
+    ChallengeWindowOpen(ChallengeWindowOpen),
+    ChallengeWindowClosed(ChallengeWindowClosed),
+    IntentNotExpired(IntentNotExpired),
+    ExposureLimitExceeded(ExposureLimitExceeded),
+    OperatorRateLimited(OperatorRateLimited),
+    DefenseWindowOpen(DefenseWindowOpen),
+    DefenseWindowClosed(DefenseWindowClosed),
+    InvalidDefense(InvalidDefense),
+    TransferFailed(TransferFailed),
+    InsufficientBond(InsufficientBond),
+    InvalidEvidence(InvalidEvidence),
+    StakeLocked(StakeLocked),
 }
 
 sol! {
@@ -35,6 +419,19 @@ sol! {
     error IntentNotMatched();
     error ExecutionFailed();
     error InvalidIntent();
+    error IntentNotExecuted();
+    error ChallengeWindowOpen();
+    error ChallengeWindowClosed();
+    error IntentNotExpired();
+    error ExposureLimitExceeded();
+    error OperatorRateLimited();
+    error DefenseWindowOpen();
+    error DefenseWindowClosed();
+    error InvalidDefense();
+    error TransferFailed();
+    error InsufficientBond();
+    error InvalidEvidence();
+    error StakeLocked();
 }
 
 sol_storage! {
@@ -42,14 +439,37 @@ sol_storage! {
     pub struct IntentsEngine {
         mapping(bytes32 => Intent) intents;
         mapping(address => Solver) solvers;
+        mapping(address => Operator) operators; // hot key => delegation
         mapping(address => uint256) user_nonces;
+        mapping(address => mapping(uint256 => bytes32)) nonce_intents; // user => nonce => live intent_id
         mapping(bytes32 => IntentExecution) executions;
+        mapping(bytes32 => mapping(uint256 => IntentLeg)) legs; // intent_id => leg_index => leg
+        mapping(address => mapping(address => uint256)) refunds; // user => token => amount
+        mapping(address => uint256) accrued_fees; // token => protocol fees
+        mapping(address => mapping(address => uint256)) solver_rebates; // solver => token => amount
+        mapping(address => mapping(address => uint256)) solver_compensation; // solver => token => amount
         
         uint256 min_solver_stake;
-        uint256 intent_fee;
+        uint256 intent_fee; // flat fee per intent, in source token units
+        uint256 fee_bps; // proportional fee on source_amount
+        uint256 fee_rebate_bps; // share of the fee returned to high-reputation solvers
+        uint256 fee_rebate_min_reputation;
         uint256 slash_percentage;
+        uint256 challenge_period; // blocks
+        uint256 defense_period; // blocks a challenged solver has to answer, at least MIN_DEFENSE_PERIOD
+        uint256 expiry_penalty_bps; // stake penalty for matched-but-unexecuted intents
+        uint256 reputation_decay_bps; // reputation lost per idle day
+        uint256 reputation_size_unit; // source amount worth one extra reputation weight
+        uint256 cancellation_fee_bps; // paid to the matched solver on late cancellation
+        uint256 cancellation_grace_period; // seconds after matching during which cancellation is free
+        address orbital_amm; // same-chain settlement venue
         address fee_recipient;
         address owner;
+        uint256 challenge_bond; // wei posted with a challenge, forfeited to the solver if defended
+        mapping(address => bool) attesters; // signers trusted for delivery receipts
+        uint256 stake_cooldown; // seconds between unregister_solver and withdraw_stake
+        mapping(address => uint256) token_stake_prices; // source token => stake wei per 1e18 units
+        mapping(address => uint256) slash_payouts; // recipient => slashed stake owed, in wei
     }
 
     pub struct Intent {
@@ -64,6 +484,21 @@ sol_storage! {
         uint256 nonce;
         bytes32 data_hash;
         IntentStatus status;
+        uint256 leg_count; // 0 for single-output intents
+        uint256 legs_executed;
+        uint256 refunded_amount; // unfilled share returned when a partially filled intent expired
+    }
+
+    pub struct IntentLeg {
+        uint256 dest_chain_id;
+        address dest_token;
+        uint256 share_bps; // share of source_amount routed to this leg
+        uint256 min_dest_amount;
+        uint256 deadline;
+        uint256 dest_amount;
+        uint256 executed_at;
+        bytes32 proof_hash;
+        bool executed;
     }
 
     pub struct IntentExecution {
@@ -72,7 +507,16 @@ sol_storage! {
         uint256 executed_at;
         uint256 dest_amount;
         bytes32 proof_hash;
-        bool verified;
+        bool verified; // set once the challenge window closes without a dispute
+        uint256 challenge_deadline;
+        address challenger;
+        bytes32 evidence_hash;
+        uint256 fee_paid;
+        uint256 defense_deadline; // set when challenged, if a defense period is configured
+        bytes32 defense_hash;
+        bool defended;
+        uint256 challenge_bond; // held until the challenge is resolved
+        uint256 evidence_observed_at; // latest receipt used by a challenge or defense
     }
 
     pub struct Solver {
@@ -82,6 +526,17 @@ sol_storage! {
         uint256 failed_intents;
         uint256 last_active;
         bool is_registered;
+        uint256 reputation_updated_at;
+        uint256 open_intents; // matched or executed and not yet settled, failed or cancelled
+        uint256 unlocks_at; // when an unregistered solver may withdraw its stake, 0 if registered
+    }
+
+    pub struct Operator {
+        address solver; // cold identity holding stake and reputation
+        uint256 max_actions_per_window;
+        uint256 window_start;
+        uint256 actions_in_window;
+        bool active;
     }
 
     pub enum IntentStatus {
@@ -89,7 +544,8 @@ sol_storage! {
         Matched,
         Executed,
         Cancelled,
-        Failed
+        Failed,
+        Replaced
     }
 }
 
@@ -108,6 +564,149 @@ impl IntentsEngine {
         self.min_solver_stake.set(min_stake);
         self.intent_fee.set(intent_fee);
         self.slash_percentage.set(slash_percentage);
+        self.challenge_period.set(U256::from(100)); // ~100 blocks default
+        self.defense_period.set(U256::from(MIN_DEFENSE_PERIOD));
+        self.challenge_bond.set(U256::from(DEFAULT_CHALLENGE_BOND));
+        self.expiry_penalty_bps.set(U256::from(100)); // 1% default
+        self.reputation_decay_bps.set(U256::from(100)); // 1% per day default
+        self.reputation_size_unit.set(U256::from(10).pow(U256::from(21))); // 1000 tokens at 18 decimals
+        self.cancellation_fee_bps.set(U256::from(50)); // 0.5% default
+        self.cancellation_grace_period.set(U256::from(60)); // 1 minute default
+        self.stake_cooldown.set(U256::from(DEFAULT_STAKE_COOLDOWN));
+        Ok(())
+    }
+
+    pub fn configure_cancellation(
+        &mut self,
+        cancellation_fee_bps: U256,
+        cancellation_grace_period: U256,
+    ) -> Result<(), IntentsError> {
+        if msg::sender() != self.owner.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+        if cancellation_fee_bps > U256::from(BPS_DENOMINATOR) {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+        self.cancellation_fee_bps.set(cancellation_fee_bps);
+        self.cancellation_grace_period.set(cancellation_grace_period);
+        Ok(())
+    }
+
+    pub fn set_orbital_amm(&mut self, orbital_amm: Address) -> Result<(), IntentsError> {
+        if msg::sender() != self.owner.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+        self.orbital_amm.set(orbital_amm);
+        Ok(())
+    }
+
+    pub fn configure_fees(
+        &mut self,
+        intent_fee: U256,
+        fee_bps: U256,
+        fee_rebate_bps: U256,
+        fee_rebate_min_reputation: U256,
+    ) -> Result<(), IntentsError> {
+        if msg::sender() != self.owner.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+        if fee_bps > U256::from(BPS_DENOMINATOR) || fee_rebate_bps > U256::from(BPS_DENOMINATOR) {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+        self.intent_fee.set(intent_fee);
+        self.fee_bps.set(fee_bps);
+        self.fee_rebate_bps.set(fee_rebate_bps);
+        self.fee_rebate_min_reputation.set(fee_rebate_min_reputation);
+        Ok(())
+    }
+
+    pub fn configure_reputation(
+        &mut self,
+        reputation_decay_bps: U256,
+        reputation_size_unit: U256,
+    ) -> Result<(), IntentsError> {
+        if msg::sender() != self.owner.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+        if reputation_decay_bps > U256::from(BPS_DENOMINATOR) {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+        self.reputation_decay_bps.set(reputation_decay_bps);
+        self.reputation_size_unit.set(reputation_size_unit);
+        Ok(())
+    }
+
+    pub fn configure_expiry_penalty(&mut self, expiry_penalty_bps: U256) -> Result<(), IntentsError> {
+        if msg::sender() != self.owner.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+        if expiry_penalty_bps > U256::from(BPS_DENOMINATOR) {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+        self.expiry_penalty_bps.set(expiry_penalty_bps);
+        Ok(())
+    }
+
+    pub fn configure_challenge_period(&mut self, challenge_period: U256) -> Result<(), IntentsError> {
+        if msg::sender() != self.owner.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+        self.challenge_period.set(challenge_period);
+        Ok(())
+    }
+
+    pub fn configure_defense_period(&mut self, defense_period: U256) -> Result<(), IntentsError> {
+        if msg::sender() != self.owner.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+        if defense_period < U256::from(MIN_DEFENSE_PERIOD) {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+        self.defense_period.set(defense_period);
+        Ok(())
+    }
+
+    pub fn configure_challenge_bond(&mut self, challenge_bond: U256) -> Result<(), IntentsError> {
+        if msg::sender() != self.owner.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+        if challenge_bond == U256::ZERO {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+        self.challenge_bond.set(challenge_bond);
+        Ok(())
+    }
+
+    pub fn configure_stake_cooldown(&mut self, stake_cooldown: U256) -> Result<(), IntentsError> {
+        if msg::sender() != self.owner.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+        self.stake_cooldown.set(stake_cooldown);
+        Ok(())
+    }
+
+    /// Price `token` in stake wei per 1e18 units, so intents selling it can
+    /// be held against a solver's stake. Unpriced tokens cannot be matched.
+    pub fn set_token_stake_price(&mut self, token: Address, price: U256) -> Result<(), IntentsError> {
+        if msg::sender() != self.owner.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+        self.token_stake_prices.setter(token).set(price);
+
+        evm::log(TokenStakePriceUpdated { token, price });
+
+        Ok(())
+    }
+
+    /// Trust or distrust a signer of delivery receipts
+    pub fn set_attester(&mut self, attester: Address, trusted: bool) -> Result<(), IntentsError> {
+        if msg::sender() != self.owner.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+        self.attesters.setter(attester).set(trusted);
+
+        evm::log(AttesterUpdated { attester, trusted });
+
         Ok(())
     }
 
@@ -131,10 +730,12 @@ impl IntentsEngine {
         }
 
         let user = msg::sender();
+        self.pull_tokens(source_token, user, source_amount)?;
+
         let nonce = self.user_nonces.get(user);
         self.user_nonces.setter(user).set(nonce + U256::from(1));
 
-        let intent_id = self.compute_intent_id(
+        self.store_intent(
             user,
             source_chain_id,
             dest_chain_id,
@@ -144,73 +745,186 @@ impl IntentsEngine {
             min_dest_amount,
             deadline,
             nonce,
-        );
-
-        let mut intent = self.intents.setter(intent_id);
-        intent.user.set(user);
-        intent.source_chain_id.set(source_chain_id);
-        intent.dest_chain_id.set(dest_chain_id);
-        intent.source_token.set(source_token);
-        intent.dest_token.set(dest_token);
-        intent.source_amount.set(source_amount);
-        intent.min_dest_amount.set(min_dest_amount);
-        intent.deadline.set(deadline);
-        intent.nonce.set(nonce);
-        intent.data_hash.set(keccak256(data));
-        intent.status.set(IntentStatus::Created);
-
-        evm::log(IntentCreated {
-            intentId: intent_id,
-            user,
-            timestamp: U256::from(block::timestamp()),
-        });
-
-        Ok(intent_id)
+            data,
+        )
     }
 
-    pub fn match_intent(&mut self, intent_id: B256) -> Result<(), IntentsError> {
-        let solver = msg::sender();
-        let solver_info = self.solvers.get(solver);
-        
-        if !solver_info.is_registered.get() || solver_info.stake.get() < self.min_solver_stake.get() {
-            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+    /// Replace-by-nonce: supersede the caller's unmatched intent at `nonce`
+    /// with a new one that demands a strictly higher `min_dest_amount`. The old
+    /// intent is marked `Replaced` so solvers can drop it from their books.
+    pub fn replace_intent(
+        &mut self,
+        nonce: U256,
+        source_chain_id: U256,
+        dest_chain_id: U256,
+        source_token: Address,
+        dest_token: Address,
+        source_amount: U256,
+        min_dest_amount: U256,
+        deadline: U256,
+        data: Vec<u8>,
+    ) -> Result<B256, IntentsError> {
+        if deadline <= U256::from(block::timestamp()) {
+            return Err(IntentsError::IntentExpired(IntentExpired {}));
         }
 
-        let intent = self.intents.get(intent_id);
-        if matches!(intent.status.get(), IntentStatus::Created) {
-            return Err(IntentsError::IntentNotFound(IntentNotFound {}));
+        if source_amount == U256::ZERO {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
         }
 
-        if intent.deadline.get() <= U256::from(block::timestamp()) {
-            return Err(IntentsError::IntentExpired(IntentExpired {}));
+        let user = msg::sender();
+        let old_intent_id = self.nonce_intents.get(user).get(nonce);
+        if old_intent_id == B256::ZERO {
+            return Err(IntentsError::IntentNotFound(IntentNotFound {}));
         }
 
-        if matches!(intent.status.get(), IntentStatus::Matched) {
+        let old_intent = self.intents.get(old_intent_id);
+        if !matches!(old_intent.status.get(), IntentStatus::Created) {
             return Err(IntentsError::IntentAlreadyMatched(IntentAlreadyMatched {}));
         }
 
-        self.intents.setter(intent_id).status.set(IntentStatus::Matched);
-        
-        let mut execution = self.executions.setter(intent_id);
-        execution.solver.set(solver);
-        execution.matched_at.set(U256::from(block::timestamp()));
-
-        evm::log(IntentMatched {
-            intentId: intent_id,
-            solver,
-            timestamp: U256::from(block::timestamp()),
-        });
+        if old_intent.leg_count.get() != U256::ZERO || min_dest_amount <= old_intent.min_dest_amount.get() {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
 
-        Ok(())
-    }
+        // The old escrow goes back to the user and the new one is pulled
+        let old_source_amount = old_intent.source_amount.get();
+        self.intents.setter(old_intent_id).status.set(IntentStatus::Replaced);
+        self.refund_intent(old_intent_id, old_source_amount);
+        self.pull_tokens(source_token, user, source_amount)?;
 
-    pub fn execute_intent(
-        &mut self,
-        intent_id: B256,
-        dest_amount: U256,
-        proof: Vec<u8>,
-    ) -> Result<(), IntentsError> {
-        let solver = msg::sender();
+        let intent_id = self.store_intent(
+            user,
+            source_chain_id,
+            dest_chain_id,
+            source_token,
+            dest_token,
+            source_amount,
+            min_dest_amount,
+            deadline,
+            nonce,
+            data,
+        )?;
+
+        evm::log(IntentReplaced {
+            oldIntentId: old_intent_id,
+            newIntentId: intent_id,
+            user,
+            nonce,
+            minDestAmount: min_dest_amount,
+        });
+
+        Ok(intent_id)
+    }
+
+    pub fn get_intent_by_nonce(&self, user: Address, nonce: U256) -> B256 {
+        self.nonce_intents.get(user).get(nonce)
+    }
+
+    fn store_intent(
+        &mut self,
+        user: Address,
+        source_chain_id: U256,
+        dest_chain_id: U256,
+        source_token: Address,
+        dest_token: Address,
+        source_amount: U256,
+        min_dest_amount: U256,
+        deadline: U256,
+        nonce: U256,
+        data: Vec<u8>,
+    ) -> Result<B256, IntentsError> {
+        let intent_id = self.compute_intent_id(
+            user,
+            source_chain_id,
+            dest_chain_id,
+            source_token,
+            dest_token,
+            source_amount,
+            min_dest_amount,
+            deadline,
+            nonce,
+        );
+
+        // An identical intent under the same nonce must never be re-created
+        if self.intents.get(intent_id).user.get() != Address::ZERO {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+
+        self.nonce_intents.setter(user).setter(nonce).set(intent_id);
+
+        let mut intent = self.intents.setter(intent_id);
+        intent.user.set(user);
+        intent.source_chain_id.set(source_chain_id);
+        intent.dest_chain_id.set(dest_chain_id);
+        intent.source_token.set(source_token);
+        intent.dest_token.set(dest_token);
+        intent.source_amount.set(source_amount);
+        intent.min_dest_amount.set(min_dest_amount);
+        intent.deadline.set(deadline);
+        intent.nonce.set(nonce);
+        intent.data_hash.set(keccak256(data));
+        intent.status.set(IntentStatus::Created);
+
+        evm::log(IntentCreated {
+            intentId: intent_id,
+            user,
+            timestamp: U256::from(block::timestamp()),
+        });
+
+        Ok(intent_id)
+    }
+
+    pub fn match_intent(&mut self, intent_id: B256) -> Result<(), IntentsError> {
+        let solver = self.acting_solver()?;
+        let solver_info = self.solvers.get(solver);
+        
+        if !solver_info.is_registered.get() || solver_info.stake.get() < self.min_solver_stake.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+
+        let intent = self.intents.get(intent_id);
+        if matches!(intent.status.get(), IntentStatus::Matched) {
+            return Err(IntentsError::IntentAlreadyMatched(IntentAlreadyMatched {}));
+        }
+
+        if !matches!(intent.status.get(), IntentStatus::Created) {
+            return Err(IntentsError::IntentNotFound(IntentNotFound {}));
+        }
+
+        if intent.deadline.get() <= U256::from(block::timestamp()) {
+            return Err(IntentsError::IntentExpired(IntentExpired {}));
+        }
+
+        let price = self.token_stake_prices.get(intent.source_token.get());
+        if price == U256::ZERO || settlement::stake_value(intent.source_amount.get(), price) > self.max_exposure(solver) {
+            return Err(IntentsError::ExposureLimitExceeded(ExposureLimitExceeded {}));
+        }
+
+        self.intents.setter(intent_id).status.set(IntentStatus::Matched);
+        let mut solver_mut = self.solvers.setter(solver);
+        solver_mut.open_intents.set(solver_mut.open_intents.get() + U256::from(1));
+        
+        let mut execution = self.executions.setter(intent_id);
+        execution.solver.set(solver);
+        execution.matched_at.set(U256::from(block::timestamp()));
+
+        evm::log(IntentMatched {
+            intentId: intent_id,
+            solver,
+            timestamp: U256::from(block::timestamp()),
+        });
+
+        Ok(())
+    }
+
+    pub fn execute_intent(
+        &mut self,
+        intent_id: B256,
+        dest_amount: U256,
+        proof: Vec<u8>,
+    ) -> Result<(), IntentsError> {
+        let solver = self.acting_solver()?;
         let execution = self.executions.get(intent_id);
         
         if execution.solver.get() != solver {
@@ -218,70 +932,841 @@ impl IntentsEngine {
         }
 
         let intent = self.intents.get(intent_id);
-        if !matches!(intent.status.get(), IntentStatus::Matched) {
-            return Err(IntentsError::IntentNotMatched(IntentNotMatched {}));
+        if !matches!(intent.status.get(), IntentStatus::Matched) {
+            return Err(IntentsError::IntentNotMatched(IntentNotMatched {}));
+        }
+
+        if intent.leg_count.get() != U256::ZERO {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+
+        if dest_amount < intent.min_dest_amount.get() {
+            return Err(IntentsError::ExecutionFailed(ExecutionFailed {}));
+        }
+
+        let challenge_period = self.challenge_period.get();
+        self.complete_execution(intent_id, solver, dest_amount, keccak256(proof), challenge_period);
+
+        Ok(())
+    }
+
+    /// Settle a same-chain intent by swapping through the Orbital AMM. `path`
+    /// lists token indices within `pool_id`; each consecutive pair is one hop,
+    /// starting at the source token and ending at the destination token. The
+    /// escrow less fees is swapped and the output sent to the user. It is
+    /// observed on-chain rather than reported by the solver, so the intent
+    /// settles at once without a challenge window.
+    pub fn execute_intent_via_amm(
+        &mut self,
+        intent_id: B256,
+        pool_id: U256,
+        path: Vec<U256>,
+    ) -> Result<U256, IntentsError> {
+        let solver = self.acting_solver()?;
+        if self.executions.get(intent_id).solver.get() != solver {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+
+        let intent = self.intents.get(intent_id);
+        if !matches!(intent.status.get(), IntentStatus::Matched) {
+            return Err(IntentsError::IntentNotMatched(IntentNotMatched {}));
+        }
+
+        let chain_id = U256::from(block::chainid());
+        if intent.leg_count.get() != U256::ZERO
+            || intent.source_chain_id.get() != chain_id
+            || intent.dest_chain_id.get() != chain_id
+            || path.len() < 2
+        {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+
+        let amm_address = self.orbital_amm.get();
+        if amm_address == Address::ZERO {
+            return Err(IntentsError::ExecutionFailed(ExecutionFailed {}));
+        }
+
+        let user = intent.user.get();
+        let source_amount = intent.source_amount.get();
+        let min_dest_amount = intent.min_dest_amount.get();
+        let (source_token, dest_token) = (intent.source_token.get(), intent.dest_token.get());
+
+        // Resolve the path to token addresses so each hop's input can be approved
+        let amm = IOrbitalAMM::new(amm_address);
+        let (pool_tokens, _, _, _, active) = amm
+            .get_orbital_pool(Call::new_in(self), pool_id)
+            .map_err(|_| IntentsError::ExecutionFailed(ExecutionFailed {}))?;
+        let mut hop_tokens = Vec::with_capacity(path.len());
+        for index in &path {
+            let token = usize::try_from(*index)
+                .ok()
+                .and_then(|index| pool_tokens.get(index).copied())
+                .ok_or(IntentsError::InvalidIntent(InvalidIntent {}))?;
+            hop_tokens.push(token);
+        }
+        if !active || hop_tokens[0] != source_token || hop_tokens[hop_tokens.len() - 1] != dest_token {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+
+        // Settle before calling out, so the intent cannot be executed,
+        // cancelled, challenged or expired again from within the swap
+        let route_hash = keccak256((pool_id, path.clone()).abi_encode());
+        self.complete_execution(intent_id, solver, min_dest_amount, route_hash, U256::ZERO);
+        let fee = self.settle_execution(intent_id, solver);
+        self.close_intent(solver);
+
+        let amount_in = source_amount - fee;
+        let mut amount = amount_in;
+        for (hop, pair) in path.windows(2).enumerate() {
+            // Only the final hop is bound by the user's minimum
+            let min_out = if hop == path.len() - 2 { min_dest_amount } else { U256::ZERO };
+            self.approve_tokens(hop_tokens[hop], amm_address, amount)?;
+            amount = amm
+                .toroidal_swap(Call::new_in(self), pool_id, pair[0], pair[1], amount, min_out)
+                .map_err(|_| IntentsError::ExecutionFailed(ExecutionFailed {}))?;
+        }
+
+        if amount < min_dest_amount {
+            return Err(IntentsError::ExecutionFailed(ExecutionFailed {}));
+        }
+
+        // Record the observed output; the intent is already settled above
+        self.executions.setter(intent_id).dest_amount.set(amount);
+
+        evm::log(IntentSettledOnAMM {
+            intentId: intent_id,
+            poolId: pool_id,
+            amountIn: amount_in,
+            amountOut: amount,
+        });
+
+        self.push_tokens(dest_token, user, amount)?;
+        Ok(amount)
+    }
+
+    /// Create a portfolio-style intent whose source amount is split across
+    /// several destination legs. `share_bps` must sum to 10_000 and every leg
+    /// carries its own minimum output and deadline.
+    pub fn create_multi_leg_intent(
+        &mut self,
+        source_chain_id: U256,
+        source_token: Address,
+        source_amount: U256,
+        dest_chain_ids: Vec<U256>,
+        dest_tokens: Vec<Address>,
+        share_bps: Vec<U256>,
+        min_dest_amounts: Vec<U256>,
+        deadlines: Vec<U256>,
+        data: Vec<u8>,
+    ) -> Result<B256, IntentsError> {
+        let leg_count = dest_chain_ids.len();
+        if leg_count == 0
+            || leg_count > MAX_INTENT_LEGS
+            || dest_tokens.len() != leg_count
+            || share_bps.len() != leg_count
+            || min_dest_amounts.len() != leg_count
+            || deadlines.len() != leg_count
+        {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+
+        if source_amount == U256::ZERO {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+
+        let now = U256::from(block::timestamp());
+        let mut total_share = U256::ZERO;
+        let mut total_min_dest = U256::ZERO;
+        let mut latest_deadline = U256::ZERO;
+        for i in 0..leg_count {
+            if deadlines[i] <= now {
+                return Err(IntentsError::IntentExpired(IntentExpired {}));
+            }
+            if share_bps[i] == U256::ZERO || min_dest_amounts[i] == U256::ZERO {
+                return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+            }
+            total_share += share_bps[i];
+            total_min_dest += min_dest_amounts[i];
+            if deadlines[i] > latest_deadline {
+                latest_deadline = deadlines[i];
+            }
+        }
+
+        if total_share != U256::from(BPS_DENOMINATOR) {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+
+        let user = msg::sender();
+        self.pull_tokens(source_token, user, source_amount)?;
+
+        let nonce = self.user_nonces.get(user);
+        self.user_nonces.setter(user).set(nonce + U256::from(1));
+
+        let legs_hash = keccak256((
+            dest_chain_ids.clone(),
+            dest_tokens.clone(),
+            share_bps.clone(),
+            min_dest_amounts.clone(),
+            deadlines.clone(),
+        ).abi_encode());
+
+        let intent_id = keccak256((
+            user,
+            source_chain_id,
+            source_token,
+            source_amount,
+            legs_hash,
+            nonce,
+        ).abi_encode());
+
+        let mut intent = self.intents.setter(intent_id);
+        intent.user.set(user);
+        intent.source_chain_id.set(source_chain_id);
+        intent.dest_chain_id.set(dest_chain_ids[0]);
+        intent.source_token.set(source_token);
+        intent.dest_token.set(dest_tokens[0]);
+        intent.source_amount.set(source_amount);
+        intent.min_dest_amount.set(total_min_dest);
+        intent.deadline.set(latest_deadline);
+        intent.nonce.set(nonce);
+        intent.data_hash.set(keccak256(data));
+        intent.status.set(IntentStatus::Created);
+        intent.leg_count.set(U256::from(leg_count));
+        self.nonce_intents.setter(user).setter(nonce).set(intent_id);
+
+        for i in 0..leg_count {
+            let mut leg = self.legs.setter(intent_id).setter(U256::from(i));
+            leg.dest_chain_id.set(dest_chain_ids[i]);
+            leg.dest_token.set(dest_tokens[i]);
+            leg.share_bps.set(share_bps[i]);
+            leg.min_dest_amount.set(min_dest_amounts[i]);
+            leg.deadline.set(deadlines[i]);
+        }
+
+        evm::log(MultiLegIntentCreated {
+            intentId: intent_id,
+            user,
+            legCount: U256::from(leg_count),
+        });
+
+        Ok(intent_id)
+    }
+
+    /// Execute a single leg of a multi-leg intent. The intent moves to
+    /// `Executed` (and its challenge window opens) once every leg is filled.
+    pub fn execute_intent_leg(
+        &mut self,
+        intent_id: B256,
+        leg_index: U256,
+        dest_amount: U256,
+        proof: Vec<u8>,
+    ) -> Result<(), IntentsError> {
+        let solver = self.acting_solver()?;
+        if self.executions.get(intent_id).solver.get() != solver {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+
+        let intent = self.intents.get(intent_id);
+        if !matches!(intent.status.get(), IntentStatus::Matched) {
+            return Err(IntentsError::IntentNotMatched(IntentNotMatched {}));
+        }
+
+        let leg_count = intent.leg_count.get();
+        if leg_index >= leg_count {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+
+        let leg = self.legs.get(intent_id).get(leg_index);
+        if leg.executed.get() {
+            return Err(IntentsError::ExecutionFailed(ExecutionFailed {}));
+        }
+
+        if leg.deadline.get() <= U256::from(block::timestamp()) {
+            return Err(IntentsError::IntentExpired(IntentExpired {}));
+        }
+
+        if dest_amount < leg.min_dest_amount.get() {
+            return Err(IntentsError::ExecutionFailed(ExecutionFailed {}));
+        }
+
+        let mut leg_mut = self.legs.setter(intent_id).setter(leg_index);
+        leg_mut.executed.set(true);
+        leg_mut.dest_amount.set(dest_amount);
+        leg_mut.executed_at.set(U256::from(block::timestamp()));
+        leg_mut.proof_hash.set(keccak256(proof));
+
+        let legs_executed = intent.legs_executed.get() + U256::from(1);
+        self.intents.setter(intent_id).legs_executed.set(legs_executed);
+
+        evm::log(IntentLegExecuted {
+            intentId: intent_id,
+            legIndex: leg_index,
+            destAmount: dest_amount,
+        });
+
+        if legs_executed == leg_count {
+            let mut total_dest = U256::ZERO;
+            let mut proof_hashes = Vec::new();
+            for i in 0..leg_count.to::<usize>() {
+                let leg = self.legs.get(intent_id).get(U256::from(i));
+                total_dest += leg.dest_amount.get();
+                proof_hashes.push(leg.proof_hash.get());
+            }
+            let challenge_period = self.challenge_period.get();
+            self.complete_execution(intent_id, solver, total_dest, keccak256(proof_hashes.abi_encode()), challenge_period);
+        }
+
+        Ok(())
+    }
+
+    /// Dispute an execution while its challenge window is open. The challenger
+    /// must be the intent's user or an unrelated registered solver, post the
+    /// challenge bond, and bring a trusted attester's receipt showing less
+    /// delivered than the solver reported. The solver may answer with
+    /// `defend_execution` until the defense period ends; otherwise it is
+    /// slashed, the user refunded and the bond returned.
+    #[payable]
+    pub fn challenge_execution(
+        &mut self,
+        intent_id: B256,
+        evidence: Vec<u8>,
+    ) -> Result<(), IntentsError> {
+        let challenger = msg::sender();
+        // An operator challenges for, and is rate limited as, its solver
+        let challenger_identity = self.acting_solver()?;
+        let intent = self.intents.get(intent_id);
+        if !matches!(intent.status.get(), IntentStatus::Executed) {
+            return Err(IntentsError::IntentNotExecuted(IntentNotExecuted {}));
+        }
+
+        let execution = self.executions.get(intent_id);
+        if execution.verified.get() || U256::from(block::number()) > execution.challenge_deadline.get() {
+            return Err(IntentsError::ChallengeWindowClosed(ChallengeWindowClosed {}));
+        }
+        if execution.challenger.get() != Address::ZERO && !execution.defended.get() {
+            return Err(IntentsError::DefenseWindowOpen(DefenseWindowOpen {}));
+        }
+
+        let solver = execution.solver.get();
+        if !attestation::may_challenge(
+            challenger,
+            challenger_identity,
+            intent.user.get(),
+            solver,
+            self.solvers.get(challenger_identity).is_registered.get(),
+        ) {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+
+        let bond = msg::value();
+        if bond < self.challenge_bond.get() {
+            return Err(IntentsError::InsufficientBond(InsufficientBond {}));
+        }
+
+        let receipt = attestation::DeliveryReceipt::decode(&evidence)
+            .ok_or(IntentsError::InvalidEvidence(InvalidEvidence {}))?;
+        let (proof_hash, reported) = (execution.proof_hash.get(), execution.dest_amount.get());
+        let last_observed_at = execution.evidence_observed_at.get();
+        if !attestation::shows_shortfall(receipt.delivered_amount, reported, receipt.observed_at, last_observed_at)
+            || !self.is_attested(intent_id, proof_hash, &receipt)
+        {
+            return Err(IntentsError::InvalidEvidence(InvalidEvidence {}));
+        }
+
+        let evidence_hash = keccak256(evidence);
+        let defense_deadline = U256::from(block::number()) + self.effective_defense_period();
+        let mut execution_mut = self.executions.setter(intent_id);
+        execution_mut.challenger.set(challenger);
+        execution_mut.evidence_hash.set(evidence_hash);
+        execution_mut.defended.set(false);
+        execution_mut.defense_deadline.set(defense_deadline);
+        execution_mut.challenge_bond.set(bond);
+        execution_mut.evidence_observed_at.set(receipt.observed_at);
+
+        evm::log(IntentChallenged {
+            intentId: intent_id,
+            challenger,
+            evidenceHash: evidence_hash,
+        });
+
+        Ok(())
+    }
+
+    /// Answer a pending challenge with the execution proof committed to at
+    /// execution (whose hash must match) and a trusted attester's receipt,
+    /// newer than the challenge's evidence, showing the reported amount
+    /// delivered. A valid defense dismisses the challenge and earns the
+    /// challenger's bond; the execution finalizes as usual.
+    pub fn defend_execution(
+        &mut self,
+        intent_id: B256,
+        proof: Vec<u8>,
+        attestation: Vec<u8>,
+    ) -> Result<(), IntentsError> {
+        let solver = self.acting_solver()?;
+        let execution = self.executions.get(intent_id);
+        if execution.solver.get() != solver {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+        if !matches!(self.intents.get(intent_id).status.get(), IntentStatus::Executed) {
+            return Err(IntentsError::IntentNotExecuted(IntentNotExecuted {}));
+        }
+        if execution.challenger.get() == Address::ZERO || execution.defended.get() {
+            return Err(IntentsError::InvalidDefense(InvalidDefense {}));
+        }
+        if U256::from(block::number()) > execution.defense_deadline.get() {
+            return Err(IntentsError::DefenseWindowClosed(DefenseWindowClosed {}));
+        }
+        let proof_hash = execution.proof_hash.get();
+        if keccak256(&proof) != proof_hash {
+            return Err(IntentsError::InvalidDefense(InvalidDefense {}));
+        }
+
+        let receipt = attestation::DeliveryReceipt::decode(&attestation)
+            .ok_or(IntentsError::InvalidDefense(InvalidDefense {}))?;
+        let (reported, evidence_observed_at) = (execution.dest_amount.get(), execution.evidence_observed_at.get());
+        if !attestation::answers_challenge(receipt.delivered_amount, reported, receipt.observed_at, evidence_observed_at)
+            || !self.is_attested(intent_id, proof_hash, &receipt)
+        {
+            return Err(IntentsError::InvalidDefense(InvalidDefense {}));
+        }
+
+        let defense_hash = keccak256((keccak256(&proof), keccak256(&attestation)).abi_encode());
+        let mut execution_mut = self.executions.setter(intent_id);
+        execution_mut.defended.set(true);
+        execution_mut.defense_hash.set(defense_hash);
+        execution_mut.evidence_observed_at.set(receipt.observed_at);
+
+        evm::log(ExecutionDefended {
+            intentId: intent_id,
+            solver,
+            defenseHash: defense_hash,
+        });
+
+        // A failed challenge forfeits its bond to the solver
+        self.release_challenge_bond(intent_id, solver)
+    }
+
+    /// Slash the solver of an undefended challenge once its defense window
+    /// has passed. Callable by anyone.
+    pub fn resolve_challenge(&mut self, intent_id: B256) -> Result<(), IntentsError> {
+        let intent = self.intents.get(intent_id);
+        if !matches!(intent.status.get(), IntentStatus::Executed) {
+            return Err(IntentsError::IntentNotExecuted(IntentNotExecuted {}));
+        }
+
+        let execution = self.executions.get(intent_id);
+        if execution.challenger.get() == Address::ZERO || execution.defended.get() {
+            return Err(IntentsError::InvalidDefense(InvalidDefense {}));
+        }
+        if U256::from(block::number()) <= execution.defense_deadline.get() {
+            return Err(IntentsError::DefenseWindowOpen(DefenseWindowOpen {}));
+        }
+
+        self.uphold_challenge(intent_id, execution.solver.get(), self.escrowed_amount(intent_id))
+    }
+
+    /// Finalize an execution once its challenge window has closed, paying the
+    /// escrow less fees to the solver. Callable by anyone.
+    pub fn finalize_intent(&mut self, intent_id: B256) -> Result<(), IntentsError> {
+        let intent = self.intents.get(intent_id);
+        if !matches!(intent.status.get(), IntentStatus::Executed) {
+            return Err(IntentsError::IntentNotExecuted(IntentNotExecuted {}));
+        }
+
+        let execution = self.executions.get(intent_id);
+        if execution.verified.get() {
+            return Err(IntentsError::IntentNotExecuted(IntentNotExecuted {}));
+        }
+
+        if U256::from(block::number()) <= execution.challenge_deadline.get() {
+            return Err(IntentsError::ChallengeWindowOpen(ChallengeWindowOpen {}));
+        }
+        if execution.challenger.get() != Address::ZERO && !execution.defended.get() {
+            return Err(IntentsError::DefenseWindowOpen(DefenseWindowOpen {}));
+        }
+
+        let solver = execution.solver.get();
+        let (token, amount) = (intent.source_token.get(), self.escrowed_amount(intent_id));
+        let fee = self.settle_execution(intent_id, solver);
+        self.close_intent(solver);
+
+        evm::log(IntentFinalized {
+            intentId: intent_id,
+            solver,
+            timestamp: U256::from(block::timestamp()),
+        });
+
+        // The escrow, less the fee, pays the solver for the fill
+        self.push_tokens(token, solver, amount - fee)?;
+
+        Ok(())
+    }
+
+    /// Create several intents in one call. Items that fail validation are
+    /// skipped and reported as a zero id; the rest are created as usual.
+    pub fn create_intents(
+        &mut self,
+        source_chain_ids: Vec<U256>,
+        dest_chain_ids: Vec<U256>,
+        source_tokens: Vec<Address>,
+        dest_tokens: Vec<Address>,
+        source_amounts: Vec<U256>,
+        min_dest_amounts: Vec<U256>,
+        deadlines: Vec<U256>,
+        data: Vec<Vec<u8>>,
+    ) -> Result<Vec<B256>, IntentsError> {
+        let count = source_chain_ids.len();
+        if count == 0
+            || count > MAX_BATCH_SIZE
+            || dest_chain_ids.len() != count
+            || source_tokens.len() != count
+            || dest_tokens.len() != count
+            || source_amounts.len() != count
+            || min_dest_amounts.len() != count
+            || deadlines.len() != count
+            || data.len() != count
+        {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+
+        let mut intent_ids = Vec::with_capacity(count);
+        let mut succeeded = 0u64;
+        for (i, item_data) in data.into_iter().enumerate() {
+            let result = self.create_intent(
+                source_chain_ids[i],
+                dest_chain_ids[i],
+                source_tokens[i],
+                dest_tokens[i],
+                source_amounts[i],
+                min_dest_amounts[i],
+                deadlines[i],
+                item_data,
+            );
+            match result {
+                Ok(intent_id) => {
+                    succeeded += 1;
+                    intent_ids.push(intent_id);
+                }
+                Err(_) => intent_ids.push(B256::ZERO),
+            }
+        }
+
+        self.log_batch(BATCH_CREATE, succeeded, count);
+        Ok(intent_ids)
+    }
+
+    /// Match several intents in one call, returning a success flag per item.
+    /// Failed items don't count against an operator's rate limit.
+    pub fn match_intents(&mut self, intent_ids: Vec<B256>) -> Result<Vec<bool>, IntentsError> {
+        if intent_ids.is_empty() || intent_ids.len() > MAX_BATCH_SIZE {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+
+        let mut results = Vec::with_capacity(intent_ids.len());
+        for intent_id in &intent_ids {
+            let window = self.operator_window();
+            let matched = self.match_intent(*intent_id).is_ok();
+            if !matched {
+                self.restore_operator_window(window);
+            }
+            results.push(matched);
+        }
+
+        let succeeded = results.iter().filter(|ok| **ok).count() as u64;
+        self.log_batch(BATCH_MATCH, succeeded, intent_ids.len());
+        Ok(results)
+    }
+
+    /// Execute several matched intents in one call, returning a success flag
+    /// per item. Failed items don't count against an operator's rate limit.
+    pub fn execute_intents(
+        &mut self,
+        intent_ids: Vec<B256>,
+        dest_amounts: Vec<U256>,
+        proofs: Vec<Vec<u8>>,
+    ) -> Result<Vec<bool>, IntentsError> {
+        let count = intent_ids.len();
+        if count == 0 || count > MAX_BATCH_SIZE || dest_amounts.len() != count || proofs.len() != count {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+
+        let mut results = Vec::with_capacity(count);
+        for (i, proof) in proofs.into_iter().enumerate() {
+            let window = self.operator_window();
+            let executed = self.execute_intent(intent_ids[i], dest_amounts[i], proof).is_ok();
+            if !executed {
+                self.restore_operator_window(window);
+            }
+            results.push(executed);
+        }
+
+        let succeeded = results.iter().filter(|ok| **ok).count() as u64;
+        self.log_batch(BATCH_EXECUTE, succeeded, count);
+        Ok(results)
+    }
+
+    /// Cancel an intent before execution. Matched intents can be cancelled
+    /// too: free within the grace period after matching, afterwards the user
+    /// pays `cancellation_fee_bps` of the source amount to the matched solver.
+    pub fn cancel_intent(&mut self, intent_id: B256) -> Result<(), IntentsError> {
+        let intent = self.intents.get(intent_id);
+        
+        if intent.user.get() != msg::sender() {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
+
+        let source_amount = intent.source_amount.get();
+        let was_matched = matches!(intent.status.get(), IntentStatus::Matched);
+        let compensation = match intent.status.get() {
+            IntentStatus::Created => U256::ZERO,
+            IntentStatus::Matched if intent.legs_executed.get() == U256::ZERO => {
+                self.cancellation_fee(intent_id, source_amount)
+            }
+            _ => return Err(IntentsError::IntentAlreadyMatched(IntentAlreadyMatched {})),
+        };
+
+        self.intents.setter(intent_id).status.set(IntentStatus::Cancelled);
+
+        if was_matched {
+            let solver = self.executions.get(intent_id).solver.get();
+            self.close_intent(solver);
+        }
+
+        if compensation > U256::ZERO {
+            let solver = self.executions.get(intent_id).solver.get();
+            let mut owed = self.solver_compensation.setter(solver).setter(intent.source_token.get());
+            owed.set(owed.get() + compensation);
+
+            evm::log(CancellationCompensated {
+                intentId: intent_id,
+                solver,
+                amount: compensation,
+            });
+        }
+
+        if source_amount > compensation {
+            self.refund_intent(intent_id, source_amount - compensation);
+        }
+
+        evm::log(IntentCancelled {
+            intentId: intent_id,
+            user: msg::sender(),
+        });
+
+        Ok(())
+    }
+
+    /// Sweep an intent whose deadline has passed without execution. Callable by
+    /// anyone: the unfilled part of the escrow is refunded and a solver that
+    /// matched but never executed is penalized. If legs of a multi-leg intent
+    /// were filled, the intent moves to `Executed` for those legs and their
+    /// share stays escrowed until it passes the challenge window and
+    /// `finalize_intent`; otherwise it is marked `Failed`.
+    pub fn expire_intent(&mut self, intent_id: B256) -> Result<(), IntentsError> {
+        let intent = self.intents.get(intent_id);
+        let (status, deadline) = (intent.status.get(), intent.deadline.get());
+        let (leg_count, source_amount) = (intent.leg_count.get(), intent.source_amount.get());
+        if !matches!(status, IntentStatus::Created | IntentStatus::Matched) {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
         }
 
-        if dest_amount < intent.min_dest_amount.get() {
-            return Err(IntentsError::ExecutionFailed(ExecutionFailed {}));
+        if deadline > U256::from(block::timestamp()) {
+            return Err(IntentsError::IntentNotExpired(IntentNotExpired {}));
         }
 
-        self.intents.setter(intent_id).status.set(IntentStatus::Executed);
-        
-        let mut execution_mut = self.executions.setter(intent_id);
-        execution_mut.executed_at.set(U256::from(block::timestamp()));
-        execution_mut.dest_amount.set(dest_amount);
-        execution_mut.proof_hash.set(keccak256(proof));
-        execution_mut.verified.set(true);
+        let mut unfilled_bps = U256::from(BPS_DENOMINATOR);
+        let mut filled_dest = U256::ZERO;
+        let mut proof_hashes = Vec::new();
+        for i in 0..leg_count.to::<usize>() {
+            let leg = self.legs.get(intent_id).get(U256::from(i));
+            if leg.executed.get() {
+                unfilled_bps -= leg.share_bps.get();
+                filled_dest += leg.dest_amount.get();
+                proof_hashes.push(leg.proof_hash.get());
+            }
+        }
+        let refund_amount = settlement::bps_of(source_amount, unfilled_bps);
+        if refund_amount > U256::ZERO {
+            self.intents.setter(intent_id).refunded_amount.set(refund_amount);
+            self.refund_intent(intent_id, refund_amount);
+        }
 
-        let mut solver_info = self.solvers.setter(solver);
-        solver_info.successful_intents.set(solver_info.successful_intents.get() + U256::from(1));
-        solver_info.reputation_score.set(
-            solver_info.reputation_score.get() + U256::from(10)
-        );
-        solver_info.last_active.set(U256::from(block::timestamp()));
+        let mut solver = Address::ZERO;
+        if matches!(status, IntentStatus::Matched) {
+            solver = self.executions.get(intent_id).solver.get();
+            let penalty = settlement::bps_of(self.solvers.get(solver).stake.get(), self.expiry_penalty_bps.get());
+            // The user waited out the deadline on this solver's match
+            let user = self.intents.get(intent_id).user.get();
+            self.penalize_solver(solver, intent_id, penalty, user);
+        }
 
-        evm::log(IntentExecuted {
+        if proof_hashes.is_empty() {
+            self.intents.setter(intent_id).status.set(IntentStatus::Failed);
+            if solver != Address::ZERO {
+                self.close_intent(solver);
+            }
+        } else {
+            // The reported fills are only trusted once they survive a challenge
+            let challenge_period = self.challenge_period.get();
+            self.complete_execution(intent_id, solver, filled_dest, keccak256(proof_hashes.abi_encode()), challenge_period);
+        }
+
+        evm::log(IntentExpirySwept {
             intentId: intent_id,
+            caller: msg::sender(),
             solver,
-            success: true,
         });
 
         Ok(())
     }
 
-    pub fn cancel_intent(&mut self, intent_id: B256) -> Result<(), IntentsError> {
-        let intent = self.intents.get(intent_id);
-        
-        if intent.user.get() != msg::sender() {
-            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+    /// Claim protocol fees accrued in `token`. Only callable by the fee recipient.
+    pub fn claim_fees(&mut self, token: Address) -> Result<U256, IntentsError> {
+        let recipient = msg::sender();
+        if recipient != self.fee_recipient.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
         }
 
-        if !matches!(intent.status.get(), IntentStatus::Created) {
-            return Err(IntentsError::IntentAlreadyMatched(IntentAlreadyMatched {}));
+        let amount = self.accrued_fees.get(token);
+        self.accrued_fees.setter(token).set(U256::ZERO);
+
+        evm::log(FeesClaimed {
+            recipient,
+            token,
+            amount,
+        });
+
+        self.push_tokens(token, recipient, amount)?;
+        Ok(amount)
+    }
+
+    /// Claim fee rebates earned by the calling solver in `token`.
+    pub fn claim_rebates(&mut self, token: Address) -> Result<U256, IntentsError> {
+        let solver = msg::sender();
+        let amount = self.solver_rebates.get(solver).get(token);
+        self.solver_rebates.setter(solver).setter(token).set(U256::ZERO);
+
+        evm::log(FeesClaimed {
+            recipient: solver,
+            token,
+            amount,
+        });
+
+        self.push_tokens(token, solver, amount)?;
+        Ok(amount)
+    }
+
+    /// Authorize a hot key to match and execute on the caller's behalf. Stake
+    /// and reputation stay with the calling solver; the operator is limited to
+    /// `max_actions_per_window` calls per hour.
+    pub fn add_operator(
+        &mut self,
+        operator: Address,
+        max_actions_per_window: U256,
+    ) -> Result<(), IntentsError> {
+        let solver = msg::sender();
+        if !self.solvers.get(solver).is_registered.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
         }
 
-        self.intents.setter(intent_id).status.set(IntentStatus::Cancelled);
+        let existing = self.operators.get(operator);
+        if operator == Address::ZERO
+            || operator == solver
+            || self.solvers.get(operator).is_registered.get()
+            || (existing.active.get() && existing.solver.get() != solver)
+            || max_actions_per_window == U256::ZERO
+        {
+            return Err(IntentsError::InvalidIntent(InvalidIntent {}));
+        }
 
-        evm::log(IntentCancelled {
-            intentId: intent_id,
-            user: msg::sender(),
+        let mut operator_info = self.operators.setter(operator);
+        operator_info.solver.set(solver);
+        operator_info.max_actions_per_window.set(max_actions_per_window);
+        operator_info.window_start.set(U256::from(block::timestamp()));
+        operator_info.actions_in_window.set(U256::ZERO);
+        operator_info.active.set(true);
+
+        evm::log(OperatorAdded {
+            solver,
+            operator,
+            maxActionsPerWindow: max_actions_per_window,
         });
 
         Ok(())
     }
 
+    pub fn remove_operator(&mut self, operator: Address) -> Result<(), IntentsError> {
+        let solver = msg::sender();
+        let operator_info = self.operators.get(operator);
+        if !operator_info.active.get() || operator_info.solver.get() != solver {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+
+        self.operators.setter(operator).active.set(false);
+
+        evm::log(OperatorRemoved { solver, operator });
+
+        Ok(())
+    }
+
+    /// Claim cancellation compensation owed to the calling solver in `token`.
+    pub fn claim_compensation(&mut self, token: Address) -> Result<U256, IntentsError> {
+        let solver = msg::sender();
+        let amount = self.solver_compensation.get(solver).get(token);
+        self.solver_compensation.setter(solver).setter(token).set(U256::ZERO);
+
+        evm::log(FeesClaimed {
+            recipient: solver,
+            token,
+            amount,
+        });
+
+        self.push_tokens(token, solver, amount)?;
+        Ok(amount)
+    }
+
+    /// Withdraw everything refunded to the caller in `token`, whether by a
+    /// challenge, cancellation, expiry or replacement.
+    pub fn withdraw_refund(&mut self, token: Address) -> Result<U256, IntentsError> {
+        let user = msg::sender();
+        let amount = self.refunds.get(user).get(token);
+        self.refunds.setter(user).setter(token).set(U256::ZERO);
+
+        evm::log(RefundWithdrawn {
+            user,
+            token,
+            amount,
+        });
+
+        self.push_tokens(token, user, amount)?;
+        Ok(amount)
+    }
+
+    /// Register the caller as a solver, or top up its stake. The stake is
+    /// posted in wei with the call and held by the contract, so exposure
+    /// limits and slashing are backed by funds actually escrowed.
+    /// Registering again cancels a pending `unregister_solver`.
+    #[payable]
     pub fn register_solver(&mut self, stake_amount: U256) -> Result<(), IntentsError> {
-        if stake_amount < self.min_solver_stake.get() {
+        if msg::value() != stake_amount || stake_amount < self.min_solver_stake.get() {
             return Err(IntentsError::InsufficientStake(InsufficientStake {}));
         }
 
         let solver = msg::sender();
+        if self.operators.get(solver).active.get() {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+
         let mut solver_info = self.solvers.setter(solver);
         
         solver_info.stake.set(solver_info.stake.get() + stake_amount);
         solver_info.is_registered.set(true);
+        solver_info.unlocks_at.set(U256::ZERO);
         solver_info.last_active.set(U256::from(block::timestamp()));
+        if solver_info.reputation_updated_at.get() == U256::ZERO {
+            solver_info.reputation_updated_at.set(U256::from(block::timestamp()));
+        }
 
         evm::log(SolverRegistered {
             solver,
@@ -296,26 +1781,411 @@ impl IntentsEngine {
             return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
         }
 
+        let treasury = self.fee_recipient.get();
+        self.apply_slash(solver, intent_id, treasury);
+        Ok(())
+    }
+
+    /// Stop matching and start the stake cooldown. The stake stays
+    /// slashable until `withdraw_stake` succeeds.
+    pub fn unregister_solver(&mut self) -> Result<(), IntentsError> {
+        let solver = msg::sender();
+        let solver_info = self.solvers.get(solver);
+        if solver_info.stake.get() == U256::ZERO || solver_info.unlocks_at.get() != U256::ZERO {
+            return Err(IntentsError::UnauthorizedSolver(UnauthorizedSolver {}));
+        }
+
+        let unlocks_at = U256::from(block::timestamp()) + self.stake_cooldown.get();
+        let mut solver_mut = self.solvers.setter(solver);
+        solver_mut.is_registered.set(false);
+        solver_mut.unlocks_at.set(unlocks_at);
+
+        evm::log(SolverUnregistered {
+            solver,
+            unlocksAt: unlocks_at,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw the caller's remaining stake once the cooldown started by
+    /// `unregister_solver` has passed. Fails while any intent the solver
+    /// matched is still open, executed or under challenge.
+    pub fn withdraw_stake(&mut self) -> Result<U256, IntentsError> {
+        let solver = msg::sender();
+        let solver_info = self.solvers.get(solver);
+        let stake = solver_info.stake.get();
+        if !settlement::may_withdraw_stake(
+            U256::from(block::timestamp()),
+            solver_info.unlocks_at.get(),
+            solver_info.open_intents.get(),
+        ) {
+            return Err(IntentsError::StakeLocked(StakeLocked {}));
+        }
+
+        let mut solver_mut = self.solvers.setter(solver);
+        solver_mut.stake.set(U256::ZERO);
+        solver_mut.unlocks_at.set(U256::ZERO);
+
+        evm::log(StakeWithdrawn {
+            solver,
+            amount: stake,
+        });
+
+        if stake > U256::ZERO {
+            call::transfer_eth(solver, stake).map_err(|_| IntentsError::TransferFailed(TransferFailed {}))?;
+        }
+        Ok(stake)
+    }
+
+    /// Claim slashed stake awarded to the caller, in wei
+    pub fn claim_slash_payout(&mut self) -> Result<U256, IntentsError> {
+        let recipient = msg::sender();
+        let amount = self.slash_payouts.get(recipient);
+        self.slash_payouts.setter(recipient).set(U256::ZERO);
+
+        evm::log(SlashPayoutClaimed { recipient, amount });
+
+        if amount > U256::ZERO {
+            call::transfer_eth(recipient, amount).map_err(|_| IntentsError::TransferFailed(TransferFailed {}))?;
+        }
+        Ok(amount)
+    }
+
+    /// Resolve the solver identity behind the caller: an active operator acts
+    /// for its delegating solver (consuming one unit of its rate limit), any
+    /// other caller acts for itself.
+    fn acting_solver(&mut self) -> Result<Address, IntentsError> {
+        let caller = msg::sender();
+        let operator_info = self.operators.get(caller);
+        if !operator_info.active.get() {
+            return Ok(caller);
+        }
+
+        let (window_start, actions) = settlement::next_operator_window(
+            U256::from(block::timestamp()),
+            operator_info.window_start.get(),
+            operator_info.actions_in_window.get(),
+            operator_info.max_actions_per_window.get(),
+        )
+        .ok_or(IntentsError::OperatorRateLimited(OperatorRateLimited {}))?;
+
+        let solver = operator_info.solver.get();
+        let mut operator_mut = self.operators.setter(caller);
+        operator_mut.window_start.set(window_start);
+        operator_mut.actions_in_window.set(actions);
+
+        Ok(solver)
+    }
+
+    /// The caller's operator rate-limit window, if it is an active operator
+    fn operator_window(&self) -> Option<(U256, U256)> {
+        let operator_info = self.operators.get(msg::sender());
+        operator_info
+            .active
+            .get()
+            .then(|| (operator_info.window_start.get(), operator_info.actions_in_window.get()))
+    }
+
+    /// Give back the rate-limit unit a failed batch item consumed: item
+    /// errors don't revert the batch, so `acting_solver`'s charge would stick
+    fn restore_operator_window(&mut self, window: Option<(U256, U256)>) {
+        if let Some((window_start, actions)) = window {
+            let mut operator_mut = self.operators.setter(msg::sender());
+            operator_mut.window_start.set(window_start);
+            operator_mut.actions_in_window.set(actions);
+        }
+    }
+
+    fn uphold_challenge(&mut self, intent_id: B256, solver: Address, source_amount: U256) -> Result<(), IntentsError> {
+        self.intents.setter(intent_id).status.set(IntentStatus::Failed);
+        // The slash compensates the user the execution shortchanged
+        let user = self.intents.get(intent_id).user.get();
+        self.apply_slash(solver, intent_id, user);
+        self.close_intent(solver);
+        self.refund_intent(intent_id, source_amount);
+
+        evm::log(ChallengeResolved {
+            intentId: intent_id,
+            solver,
+            slashed: true,
+        });
+
+        let challenger = self.executions.get(intent_id).challenger.get();
+        self.release_challenge_bond(intent_id, challenger)
+    }
+
+    /// Pay a challenge's bond to `recipient`: back to the challenger when it
+    /// is upheld, to the solver when it is defended
+    fn release_challenge_bond(&mut self, intent_id: B256, recipient: Address) -> Result<(), IntentsError> {
+        let amount = self.executions.get(intent_id).challenge_bond.get();
+        self.executions.setter(intent_id).challenge_bond.set(U256::ZERO);
+        if amount == U256::ZERO {
+            return Ok(());
+        }
+
+        evm::log(ChallengeBondSettled {
+            intentId: intent_id,
+            recipient,
+            amount,
+        });
+
+        call::transfer_eth(recipient, amount).map_err(|_| IntentsError::TransferFailed(TransferFailed {}))
+    }
+
+    fn effective_defense_period(&self) -> U256 {
+        self.defense_period.get().max(U256::from(MIN_DEFENSE_PERIOD))
+    }
+
+    /// Whether `receipt` is signed by a trusted attester for this execution
+    fn is_attested(&mut self, intent_id: B256, proof_hash: B256, receipt: &attestation::DeliveryReceipt) -> bool {
+        let digest = attestation::receipt_digest(
+            contract::address(),
+            U256::from(block::chainid()),
+            intent_id,
+            proof_hash,
+            receipt.delivered_amount,
+            receipt.observed_at,
+        );
+        let Some(input) = attestation::ecrecover_input(digest, &receipt.signature) else {
+            return false;
+        };
+        call::static_call(Call::new_in(self), ECRECOVER, &input)
+            .ok()
+            .and_then(|output| attestation::recovered_signer(&output))
+            .is_some_and(|signer| self.attesters.get(signer))
+    }
+
+    /// Mark an execution verified, reward the solver's reputation and charge
+    /// the fee. Returns the fee taken out of the escrow.
+    fn settle_execution(&mut self, intent_id: B256, solver: Address) -> U256 {
+        self.executions.setter(intent_id).verified.set(true);
+
+        let score = self.refresh_reputation(solver);
+        let reward = U256::from(REPUTATION_REWARD) * self.size_weight(self.escrowed_amount(intent_id));
         let mut solver_info = self.solvers.setter(solver);
-        let slash_amount = solver_info.stake.get() * self.slash_percentage.get() / U256::from(100);
-        
+        solver_info.successful_intents.set(solver_info.successful_intents.get() + U256::from(1));
+        solver_info.reputation_score.set(score + reward);
+
+        self.charge_fee(intent_id, solver, score)
+    }
+
+    fn apply_slash(&mut self, solver: Address, intent_id: B256, recipient: Address) -> U256 {
+        let slash_amount = self.solvers.get(solver).stake.get() * self.slash_percentage.get() / U256::from(100);
+        self.penalize_solver(solver, intent_id, slash_amount, recipient)
+    }
+
+    /// Take `slash_amount` out of the solver's stake and owe it to
+    /// `recipient`, who claims it with `claim_slash_payout`
+    fn penalize_solver(&mut self, solver: Address, intent_id: B256, slash_amount: U256, recipient: Address) -> U256 {
+        let min_stake = self.min_solver_stake.get();
+        let score = self.refresh_reputation(solver);
+        let penalty = U256::from(REPUTATION_PENALTY)
+            * self.size_weight(self.intents.get(intent_id).source_amount.get());
+        let mut solver_info = self.solvers.setter(solver);
+
         solver_info.stake.set(solver_info.stake.get() - slash_amount);
         solver_info.failed_intents.set(solver_info.failed_intents.get() + U256::from(1));
-        solver_info.reputation_score.set(
-            solver_info.reputation_score.get().saturating_sub(U256::from(20))
-        );
+        solver_info.reputation_score.set(score.saturating_sub(penalty));
 
-        if solver_info.stake.get() < self.min_solver_stake.get() {
+        if solver_info.stake.get() < min_stake {
             solver_info.is_registered.set(false);
         }
 
+        let mut payout = self.slash_payouts.setter(recipient);
+        payout.set(payout.get() + slash_amount);
+
         evm::log(SolverSlashed {
             solver,
             amount: slash_amount,
             intentId: intent_id,
         });
+        evm::log(SlashAwarded {
+            intentId: intent_id,
+            recipient,
+            amount: slash_amount,
+        });
 
-        Ok(())
+        slash_amount
+    }
+
+    /// Charge the flat + proportional fee on a finalized intent, accruing it
+    /// per source token and rebating part of it to high-reputation solvers.
+    /// Returns the fee taken out of the escrow.
+    fn charge_fee(&mut self, intent_id: B256, solver: Address, solver_reputation: U256) -> U256 {
+        let token = self.intents.get(intent_id).source_token.get();
+        let amount = self.escrowed_amount(intent_id);
+
+        let (fee, rebate) = settlement::fee_split(
+            amount,
+            self.intent_fee.get(),
+            self.fee_bps.get(),
+            self.fee_rebate_bps.get(),
+            solver_reputation >= self.fee_rebate_min_reputation.get(),
+        );
+        if fee == U256::ZERO {
+            return fee;
+        }
+
+        let mut accrued = self.accrued_fees.setter(token);
+        accrued.set(accrued.get() + fee - rebate);
+        if rebate > U256::ZERO {
+            let mut solver_rebate = self.solver_rebates.setter(solver).setter(token);
+            solver_rebate.set(solver_rebate.get() + rebate);
+        }
+        self.executions.setter(intent_id).fee_paid.set(fee);
+
+        evm::log(FeeCharged {
+            intentId: intent_id,
+            token,
+            fee,
+            rebate,
+        });
+
+        fee
+    }
+
+    /// Reputation after applying linear decay for every whole idle day since
+    /// the score was last updated.
+    fn decayed_reputation(&self, solver: Address) -> U256 {
+        let solver_info = self.solvers.get(solver);
+        let score = solver_info.reputation_score.get();
+        let updated_at = solver_info.reputation_updated_at.get();
+        let now = U256::from(block::timestamp());
+        if updated_at == U256::ZERO || now <= updated_at {
+            return score;
+        }
+
+        let idle_days = (now - updated_at) / U256::from(SECONDS_PER_DAY);
+        settlement::decay(score, self.reputation_decay_bps.get(), idle_days)
+    }
+
+    fn refresh_reputation(&mut self, solver: Address) -> U256 {
+        let score = self.decayed_reputation(solver);
+        let now = U256::from(block::timestamp());
+        let mut solver_info = self.solvers.setter(solver);
+        let updated_at = solver_info.reputation_updated_at.get();
+        solver_info.reputation_score.set(score);
+        if updated_at == U256::ZERO || now <= updated_at {
+            solver_info.reputation_updated_at.set(now);
+        } else {
+            // Only consume whole days so partial idle time keeps accruing
+            let idle_days = (now - updated_at) / U256::from(SECONDS_PER_DAY);
+            solver_info.reputation_updated_at.set(updated_at + idle_days * U256::from(SECONDS_PER_DAY));
+        }
+        score
+    }
+
+    fn size_weight(&self, amount: U256) -> U256 {
+        settlement::size_weight(amount, self.reputation_size_unit.get())
+    }
+
+    /// Largest intent a solver may match, in stake wei (see
+    /// `set_token_stake_price`): its stake times the multiplier of the
+    /// highest tier its decayed reputation qualifies for.
+    fn max_exposure(&self, solver: Address) -> U256 {
+        let score = self.decayed_reputation(solver);
+        self.solvers.get(solver).stake.get() * settlement::exposure_multiplier(score)
+    }
+
+    /// One intent the solver matched no longer needs its stake behind it
+    fn close_intent(&mut self, solver: Address) {
+        let mut solver_mut = self.solvers.setter(solver);
+        solver_mut.open_intents.set(solver_mut.open_intents.get().saturating_sub(U256::from(1)));
+    }
+
+    fn complete_execution(
+        &mut self,
+        intent_id: B256,
+        solver: Address,
+        dest_amount: U256,
+        proof_hash: B256,
+        challenge_period: U256,
+    ) {
+        self.intents.setter(intent_id).status.set(IntentStatus::Executed);
+
+        let mut execution_mut = self.executions.setter(intent_id);
+        execution_mut.executed_at.set(U256::from(block::timestamp()));
+        execution_mut.dest_amount.set(dest_amount);
+        execution_mut.proof_hash.set(proof_hash);
+        execution_mut.verified.set(false);
+        execution_mut.challenge_deadline.set(U256::from(block::number()) + challenge_period);
+
+        self.solvers.setter(solver).last_active.set(U256::from(block::timestamp()));
+
+        evm::log(IntentExecuted {
+            intentId: intent_id,
+            solver,
+            success: true,
+        });
+    }
+
+    /// Escrow still held for an intent: its source amount less any unfilled
+    /// share refunded at expiry
+    fn escrowed_amount(&self, intent_id: B256) -> U256 {
+        let intent = self.intents.get(intent_id);
+        intent.source_amount.get() - intent.refunded_amount.get()
+    }
+
+    fn cancellation_fee(&self, intent_id: B256, source_amount: U256) -> U256 {
+        let matched_at = self.executions.get(intent_id).matched_at.get();
+        let now = U256::from(block::timestamp());
+        if now <= matched_at + self.cancellation_grace_period.get() {
+            return U256::ZERO;
+        }
+        settlement::bps_of(source_amount, self.cancellation_fee_bps.get())
+    }
+
+    fn log_batch(&self, operation: u64, succeeded: u64, total: usize) {
+        evm::log(IntentBatchProcessed {
+            caller: msg::sender(),
+            operation: U256::from(operation),
+            succeeded: U256::from(succeeded),
+            failed: U256::from(total as u64 - succeeded),
+        });
+    }
+
+    fn refund_intent(&mut self, intent_id: B256, amount: U256) {
+        let intent = self.intents.get(intent_id);
+        let user = intent.user.get();
+
+        let mut refund = self.refunds.setter(user).setter(intent.source_token.get());
+        refund.set(refund.get() + amount);
+
+        evm::log(IntentRefunded {
+            intentId: intent_id,
+            user,
+            amount,
+        });
+    }
+
+    /// Escrow `amount` of `token` from `from`, which must have approved it
+    fn pull_tokens(&mut self, token: Address, from: Address, amount: U256) -> Result<(), IntentsError> {
+        let to = contract::address();
+        match IERC20::new(token).transfer_from(Call::new_in(self), from, to, amount) {
+            Ok(true) => Ok(()),
+            _ => Err(IntentsError::TransferFailed(TransferFailed {})),
+        }
+    }
+
+    /// Let `spender` pull `amount` of escrowed `token`
+    fn approve_tokens(&mut self, token: Address, spender: Address, amount: U256) -> Result<(), IntentsError> {
+        match IERC20::new(token).approve(Call::new_in(self), spender, amount) {
+            Ok(true) => Ok(()),
+            _ => Err(IntentsError::TransferFailed(TransferFailed {})),
+        }
+    }
+
+    /// Pay `amount` of escrowed `token` out to `to`. Callers update their
+    /// balances first.
+    fn push_tokens(&mut self, token: Address, to: Address, amount: U256) -> Result<(), IntentsError> {
+        if amount == U256::ZERO {
+            return Ok(());
+        }
+        match IERC20::new(token).transfer(Call::new_in(self), to, amount) {
+            Ok(true) => Ok(()),
+            _ => Err(IntentsError::TransferFailed(TransferFailed {})),
+        }
     }
 
     fn compute_intent_id(
@@ -351,7 +2221,51 @@ impl IntentsEngine {
         self.executions.get(intent_id)
     }
 
+    pub fn get_intent_leg(&self, intent_id: B256, leg_index: U256) -> IntentLeg {
+        self.legs.get(intent_id).get(leg_index)
+    }
+
     pub fn get_solver(&self, solver: Address) -> Solver {
         self.solvers.get(solver)
     }
+
+    pub fn get_operator(&self, operator: Address) -> Operator {
+        self.operators.get(operator)
+    }
+
+    pub fn get_effective_reputation(&self, solver: Address) -> U256 {
+        self.decayed_reputation(solver)
+    }
+
+    pub fn get_max_exposure(&self, solver: Address) -> U256 {
+        self.max_exposure(solver)
+    }
+
+    pub fn get_token_stake_price(&self, token: Address) -> U256 {
+        self.token_stake_prices.get(token)
+    }
+
+    pub fn get_slash_payout(&self, recipient: Address) -> U256 {
+        self.slash_payouts.get(recipient)
+    }
+
+    pub fn get_accrued_fees(&self, token: Address) -> U256 {
+        self.accrued_fees.get(token)
+    }
+
+    pub fn get_solver_rebate(&self, solver: Address, token: Address) -> U256 {
+        self.solver_rebates.get(solver).get(token)
+    }
+
+    pub fn get_solver_compensation(&self, solver: Address, token: Address) -> U256 {
+        self.solver_compensation.get(solver).get(token)
+    }
+
+    pub fn get_refund(&self, user: Address, token: Address) -> U256 {
+        self.refunds.get(user).get(token)
+    }
+
+    pub fn is_attester(&self, attester: Address) -> bool {
+        self.attesters.get(attester)
+    }
 }
\ No newline at end of file
@@ -1,14 +1,26 @@
-#![cfg_attr(not(feature = "export-abi"), no_std, no_main)]
+#![cfg_attr(not(any(test, feature = "export-abi")), no_std, no_main)]
 
 extern crate alloc;
 use alloc::vec::Vec;
 
-use stylus_sdk::{alloy_primitives::{U256, Address, FixedBytes}, prelude::*, ArbResult, storage::{StorageVec, StorageMap}};
+use stylus_sdk::{alloy_primitives::{U256, Address, FixedBytes}, call::Call, contract, prelude::*, ArbResult, storage::{StorageVec, StorageMap}};
 use alloy_sol_types::sol;
 
+sol_interface! {
+    interface IERC20 {
+        function transfer(address to, uint256 amount) external returns (bool);
+        function transferFrom(address from, address to, uint256 amount) external returns (bool);
+    }
+}
+
 // Import orbital math functionality
 mod orbital_math {
     use super::*;
+
+    /// Allowed deviation of Σr² from R², in basis points. Mirrors
+    /// `orbital_math::sphere::TolerancePolicy::CONTRACT`, which off-chain
+    /// checks and drift repair use so they agree with the pool.
+    pub const SPHERE_TOLERANCE_BP: u32 = 100;
     
     pub fn verify_sphere_constraint(reserves: &[U256], radius_squared: U256, tolerance_bp: u32) -> bool {
         let sum_of_squares: U256 = reserves.iter()
@@ -52,57 +64,146 @@ mod orbital_math {
         }
         
         let under_sqrt = radius_squared.checked_sub(sum_other_squares)?;
-        let new_reserve_out = sqrt_approximation(under_sqrt);
+        // Round the remaining reserve up so rounding never favours the trader
+        let new_reserve_out = sqrt_ceil(under_sqrt);
         
         reserves[token_out].checked_sub(new_reserve_out)
     }
     
-    fn sqrt_approximation(value: U256) -> U256 {
-        if value.is_zero() {
-            return U256::ZERO;
+    /// Floor integer square root, exact for every U256
+    fn sqrt(value: U256) -> U256 {
+        if value < U256::from(2) {
+            return value;
         }
-        
-        let mut x = value;
-        let mut y = (value + U256::from(1)) / U256::from(2);
-        
-        // Newton's method iterations
-        for _ in 0..10 {
-            if y >= x {
-                break;
+
+        // Start above the root so Babylonian steps decrease monotonically
+        let mut x = U256::from(1) << ((value.bit_len() + 1) / 2);
+        loop {
+            let next = (x + value / x) >> 1;
+            if next >= x {
+                return x;
             }
-            x = y;
-            y = (x + value / x) / U256::from(2);
+            x = next;
         }
-        
-        x
+    }
+
+    /// Ceiling integer square root; keeps the pool on or outside the sphere
+    fn sqrt_ceil(value: U256) -> U256 {
+        let root = sqrt(value);
+        if root * root == value { root } else { root + U256::from(1) }
     }
     
+    /// Swap on the torus (α - k_b)² + (‖w‖ - s_b)² = R_int², mirroring
+    /// `orbital_math::torus`: `radius_squared` is R_int², `boundary_plane`
+    /// and `boundary_radius` sum the plane constants and circle radii of
+    /// ticks pinned to their boundary. With none pinned it is the sphere.
     pub fn calculate_toroidal_swap(
         reserves: &[U256],
         token_in: usize,
         token_out: usize,
         amount_in: U256,
         radius_squared: U256,
-        concentrated_liquidity: U256,
+        boundary_plane: U256,
+        boundary_radius: U256,
     ) -> Option<U256> {
-        // First attempt spherical swap
-        if let Some(amount_out) = calculate_amount_out_sphere(
-            reserves, token_in, token_out, amount_in, radius_squared
-        ) {
-            return Some(amount_out);
+        if boundary_plane.is_zero() && boundary_radius.is_zero() {
+            return calculate_amount_out_sphere(reserves, token_in, token_out, amount_in, radius_squared);
         }
-        
-        // If spherical swap fails, use concentrated liquidity
-        let price_ratio = if reserves[token_out].is_zero() {
-            U256::from(1)
-        } else {
-            reserves[token_in] / reserves[token_out]
+        if token_in >= reserves.len() || token_out >= reserves.len() || token_in == token_out {
+            return None;
+        }
+
+        let mut point = reserves.to_vec();
+        point[token_in] = point[token_in].checked_add(amount_in)?;
+        let mut outside = |amount_out: U256| -> Option<bool> {
+            point[token_out] = reserves[token_out] - amount_out;
+            Some(torus_value(&point, boundary_plane, boundary_radius)? >= radius_squared)
         };
-        
-        let amount_out = (amount_in * U256::from(995)) / U256::from(1000); // 0.5% fee
-        let adjusted_amount = (amount_out * concentrated_liquidity) / U256::from(1_000_000); // Concentration factor
-        
-        Some(adjusted_amount)
+        if !outside(U256::ZERO)? {
+            return None;
+        }
+
+        // Bracket the first crossing of the torus, then bisect; the pool
+        // keeps the rounding
+        let (mut low, mut high) = (U256::ZERO, U256::from(1).min(reserves[token_out]));
+        while outside(high)? {
+            if high >= reserves[token_out] {
+                return None;
+            }
+            low = high;
+            high = high.saturating_mul(U256::from(2)).min(reserves[token_out]);
+        }
+        while high - low > U256::from(1) {
+            let mid = low + ((high - low) >> 1);
+            if outside(mid)? {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        Some(low)
+    }
+
+    /// (α - k_b)² + (‖w‖ - s_b)² with α = Σr/√N and ‖w‖ = √(Σr² - α²)
+    fn torus_value(reserves: &[U256], boundary_plane: U256, boundary_radius: U256) -> Option<U256> {
+        let wad = U256::from(1_000_000_000_000_000_000u128);
+        let n = U256::from(reserves.len());
+        let mut sum = U256::ZERO;
+        let mut sum_of_squares = U256::ZERO;
+        for &r in reserves {
+            sum = sum.checked_add(r)?;
+            sum_of_squares = sum_of_squares.checked_add(r.checked_mul(r)?)?;
+        }
+
+        let alpha = sum.checked_mul(wad)? / sqrt(n * wad * wad);
+        let perpendicular = sqrt(sum_of_squares.checked_mul(n)?.checked_sub(sum.checked_mul(sum)?)? / n);
+        let along = if alpha > boundary_plane { alpha - boundary_plane } else { boundary_plane - alpha };
+        let across = if perpendicular > boundary_radius { perpendicular - boundary_radius } else { boundary_radius - perpendicular };
+        along.checked_mul(along)?.checked_add(across.checked_mul(across)?)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_toroidal_swap_without_boundary_is_sphere_swap() {
+            let reserves = [U256::from(1_000_000u64), U256::from(1_000_000u64), U256::from(1_000_000u64)];
+            let radius_squared = U256::from(3_000_000_000_000u64);
+            let amount_in = U256::from(10_000u64);
+
+            let sphere = calculate_amount_out_sphere(&reserves, 0, 1, amount_in, radius_squared).unwrap();
+            let torus = calculate_toroidal_swap(&reserves, 0, 1, amount_in, radius_squared, U256::ZERO, U256::ZERO);
+            assert_eq!(torus, Some(sphere));
+            assert!(sphere > U256::ZERO);
+        }
+
+        #[test]
+        fn test_toroidal_swap_lands_on_torus() {
+            let reserves = [U256::from(1_000_000u64), U256::from(1_000_000u64), U256::from(1_000_000u64)];
+            let (plane, circle) = (U256::from(500_000u64), U256::from(100u64));
+            let radius_squared = torus_value(&reserves, plane, circle).unwrap();
+            let amount_in = U256::from(10_000u64);
+
+            let amount_out = calculate_toroidal_swap(&reserves, 0, 1, amount_in, radius_squared, plane, circle).unwrap();
+            assert!(amount_out > U256::ZERO);
+
+            // The pool stays on or outside the torus, one more unit out would cross it
+            let mut point = reserves.to_vec();
+            point[0] += amount_in;
+            point[1] -= amount_out;
+            assert!(torus_value(&point, plane, circle).unwrap() >= radius_squared);
+            point[1] -= U256::from(1);
+            assert!(torus_value(&point, plane, circle).unwrap() < radius_squared);
+
+            // Same token, unknown token, or a draining trade are refused
+            assert_eq!(calculate_toroidal_swap(&reserves, 1, 1, amount_in, radius_squared, plane, circle), None);
+            assert_eq!(calculate_toroidal_swap(&reserves, 0, 3, amount_in, radius_squared, plane, circle), None);
+            assert_eq!(
+                calculate_toroidal_swap(&reserves, 0, 1, U256::ZERO, radius_squared + U256::from(1), plane, circle),
+                None
+            );
+        }
     }
 }
 
@@ -132,6 +233,7 @@ pub enum OrbitalAMMError {
     TickOutOfRange(TickOutOfRange),
     ConcentratedLiquidityInsufficient(ConcentratedLiquidityInsufficient),
     ToroidalSwapFailed(ToroidalSwapFailed),
+    TransferFailed(TransferFailed),
     MEVProtectionActive(MEVProtectionActive),
     SuperellipseParameterInvalid(SuperellipseParameterInvalid),
 }
@@ -149,6 +251,7 @@ sol! {
     error TickOutOfRange();
     error ConcentratedLiquidityInsufficient();
     error ToroidalSwapFailed();
+    error TransferFailed();
     error MEVProtectionActive();
     error SuperellipseParameterInvalid();
 }
@@ -175,6 +278,8 @@ sol_storage! {
         uint256 radius_squared; // Sphere constraint: sum(r_i^2) = R^2
         uint256 total_liquidity_shares;
         uint256 concentrated_liquidity; // Total concentrated liquidity
+        uint256 boundary_plane; // Summed plane constant of ticks pinned to their boundary
+        uint256 boundary_radius; // Summed circle radius of ticks pinned to their boundary
         bool active;
         uint256 creation_block;
         uint8 token_count; // Number of tokens in pool (3-1000)
@@ -290,7 +395,7 @@ impl OrbitalAMM {
         
         // Verify sphere constraint
         let reserves_array: Vec<U256> = initial_reserves.clone();
-        if !orbital_math::verify_sphere_constraint(&reserves_array, radius_squared, 100) {
+        if !orbital_math::verify_sphere_constraint(&reserves_array, radius_squared, orbital_math::SPHERE_TOLERANCE_BP) {
             return Err(OrbitalAMMError::SphereConstraintViolated(SphereConstraintViolated {}));
         }
         
@@ -314,7 +419,200 @@ impl OrbitalAMM {
         pool.superellipse_u.set(superellipse_u);
         pool.token_count.set(tokens.len() as u8);
         pool.active.set(true);
-        pool.creation_block.set(U256::from(self.block_number()));\n        \n        evm::log(OrbitalPoolCreated {\n            poolId: pool_id,\n            tokens: tokens.clone(),\n            radius: radius_squared,\n        });\n        \n        Ok(pool_id)\n    }\n    \n    /// Execute a toroidal swap in N-dimensional space\n    /// - pool_id: Pool identifier\n    /// - token_in: Index of input token\n    /// - token_out: Index of output token\n    /// - amount_in: Amount of input token\n    /// - min_amount_out: Minimum acceptable output\n    pub fn toroidal_swap(\n        &mut self,\n        pool_id: U256,\n        token_in: U256,\n        token_out: U256,\n        amount_in: U256,\n        min_amount_out: U256,\n    ) -> Result<U256, OrbitalAMMError> {\n        let pool = self.pools.get(pool_id);\n        if !pool.active.get() {\n            return Err(OrbitalAMMError::PoolNotFound(PoolNotFound {}));\n        }\n        \n        let token_in_idx = token_in.as_usize();\n        let token_out_idx = token_out.as_usize();\n        \n        if token_in_idx >= pool.token_count.get() as usize || token_out_idx >= pool.token_count.get() as usize {\n            return Err(OrbitalAMMError::InvalidAmount(InvalidAmount {}));\n        }\n        \n        // Get current reserves\n        let mut reserves = Vec::new();\n        for i in 0..pool.token_count.get() as usize {\n            reserves.push(pool.reserves.get(i));\n        }\n        \n        // Calculate toroidal swap\n        let amount_out = orbital_math::calculate_toroidal_swap(\n            &reserves,\n            token_in_idx,\n            token_out_idx,\n            amount_in,\n            pool.radius_squared.get(),\n            pool.concentrated_liquidity.get(),\n        ).ok_or(OrbitalAMMError::ToroidalSwapFailed(ToroidalSwapFailed {}))?;\n        \n        if amount_out < min_amount_out {\n            return Err(OrbitalAMMError::SlippageExceeded(SlippageExceeded {}));\n        }\n        \n        // Update reserves\n        let mut pool_mut = self.pools.setter(pool_id);\n        let new_reserve_in = reserves[token_in_idx] + amount_in;\n        let new_reserve_out = reserves[token_out_idx] - amount_out;\n        \n        pool_mut.reserves.set(token_in_idx, new_reserve_in);\n        pool_mut.reserves.set(token_out_idx, new_reserve_out);\n        \n        // Verify sphere constraint after swap\n        reserves[token_in_idx] = new_reserve_in;\n        reserves[token_out_idx] = new_reserve_out;\n        \n        let constraint_valid = orbital_math::verify_sphere_constraint(\n            &reserves,\n            pool.radius_squared.get(),\n            100,\n        );\n        \n        evm::log(ToroidalSwap {\n            poolId: pool_id,\n            trader: msg::sender(),\n            tokenIn: token_in,\n            tokenOut: token_out,\n            amountIn: amount_in,\n            amountOut: amount_out,\n        });\n        \n        evm::log(SphereConstraintValidated {\n            poolId: pool_id,\n            sumSquares: reserves.iter().map(|&r| r * r).fold(U256::ZERO, |acc, sq| acc + sq),\n            radiusSquared: pool.radius_squared.get(),\n            valid: constraint_valid,\n        });\n        \n        Ok(amount_out)\n    }\n    \n    /// Add concentrated liquidity to a specific tick range\n    /// - pool_id: Pool identifier\n    /// - tick_lower: Lower tick boundary\n    /// - tick_upper: Upper tick boundary\n    /// - amounts: Amounts for each token in the pool\n    pub fn add_concentrated_liquidity(\n        &mut self,\n        pool_id: U256,\n        tick_lower: U256,\n        tick_upper: U256,\n        amounts: Vec<U256>,\n    ) -> Result<U256, OrbitalAMMError> {\n        let pool = self.pools.get(pool_id);\n        if !pool.active.get() {\n            return Err(OrbitalAMMError::PoolNotFound(PoolNotFound {}));\n        }\n        \n        if amounts.len() != pool.token_count.get() as usize {\n            return Err(OrbitalAMMError::InvalidAmount(InvalidAmount {}));\n        }\n        \n        if tick_lower >= tick_upper {\n            return Err(OrbitalAMMError::TickOutOfRange(TickOutOfRange {}));\n        }\n        \n        // Calculate liquidity amount based on amounts\n        let liquidity = amounts.iter().fold(U256::ZERO, |acc, &amount| acc + amount);\n        \n        // Update pool concentrated liquidity\n        let mut pool_mut = self.pools.setter(pool_id);\n        let new_cl = pool.concentrated_liquidity.get() + liquidity;\n        pool_mut.concentrated_liquidity.set(new_cl);\n        \n        // Update total liquidity shares\n        let new_shares = pool.total_liquidity_shares.get() + liquidity;\n        pool_mut.total_liquidity_shares.set(new_shares);\n        \n        evm::log(ConcentratedLiquidityAdded {\n            poolId: pool_id,\n            provider: msg::sender(),\n            amounts: amounts.clone(),\n            tickLower: tick_lower,\n            tickUpper: tick_upper,\n        });\n        \n        Ok(liquidity)\n    }\n    \n    /// Configure MEV protection parameters\n    /// - commit_reveal_delay: Blocks to wait between commit and reveal
+        pool.creation_block.set(U256::from(self.block_number()));
+        
+        evm::log(OrbitalPoolCreated {
+            poolId: pool_id,
+            tokens: tokens.clone(),
+            radius: radius_squared,
+        });
+        
+        Ok(pool_id)
+    }
+    
+    /// Execute a toroidal swap in N-dimensional space
+    /// - pool_id: Pool identifier
+    /// - token_in: Index of input token
+    /// - token_out: Index of output token
+    /// - amount_in: Amount of input token
+    /// - min_amount_out: Minimum acceptable output
+    ///
+    /// The input is pulled from the caller, which must have approved it, and
+    /// the output sent back once reserves are updated.
+    pub fn toroidal_swap(
+        &mut self,
+        pool_id: U256,
+        token_in: U256,
+        token_out: U256,
+        amount_in: U256,
+        min_amount_out: U256,
+    ) -> Result<U256, OrbitalAMMError> {
+        let pool = self.pools.get(pool_id);
+        if !pool.active.get() {
+            return Err(OrbitalAMMError::PoolNotFound(PoolNotFound {}));
+        }
+        
+        let token_in_idx = token_in.as_usize();
+        let token_out_idx = token_out.as_usize();
+        
+        if token_in_idx >= pool.token_count.get() as usize || token_out_idx >= pool.token_count.get() as usize {
+            return Err(OrbitalAMMError::InvalidAmount(InvalidAmount {}));
+        }
+        let token_in_address = pool.tokens.get(token_in_idx).unwrap_or_default();
+        let token_out_address = pool.tokens.get(token_out_idx).unwrap_or_default();
+        
+        // Get current reserves
+        let mut reserves = Vec::new();
+        for i in 0..pool.token_count.get() as usize {
+            reserves.push(pool.reserves.get(i).unwrap_or_default());
+        }
+        let radius_squared = pool.radius_squared.get();
+        
+        // Calculate toroidal swap
+        let amount_out = orbital_math::calculate_toroidal_swap(
+            &reserves,
+            token_in_idx,
+            token_out_idx,
+            amount_in,
+            radius_squared,
+            pool.boundary_plane.get(),
+            pool.boundary_radius.get(),
+        ).ok_or(OrbitalAMMError::ToroidalSwapFailed(ToroidalSwapFailed {}))?;
+        
+        if amount_out < min_amount_out {
+            return Err(OrbitalAMMError::SlippageExceeded(SlippageExceeded {}));
+        }
+        
+        // Update reserves
+        let mut pool_mut = self.pools.setter(pool_id);
+        let new_reserve_in = reserves[token_in_idx] + amount_in;
+        let new_reserve_out = reserves[token_out_idx] - amount_out;
+        
+        if let Some(mut reserve) = pool_mut.reserves.setter(token_in_idx) {
+            reserve.set(new_reserve_in);
+        }
+        if let Some(mut reserve) = pool_mut.reserves.setter(token_out_idx) {
+            reserve.set(new_reserve_out);
+        }
+        
+        // Verify sphere constraint after swap
+        reserves[token_in_idx] = new_reserve_in;
+        reserves[token_out_idx] = new_reserve_out;
+        
+        let constraint_valid = orbital_math::verify_sphere_constraint(
+            &reserves,
+            radius_squared,
+            orbital_math::SPHERE_TOLERANCE_BP,
+        );
+        
+        evm::log(ToroidalSwap {
+            poolId: pool_id,
+            trader: msg::sender(),
+            tokenIn: token_in,
+            tokenOut: token_out,
+            amountIn: amount_in,
+            amountOut: amount_out,
+        });
+        
+        evm::log(SphereConstraintValidated {
+            poolId: pool_id,
+            sumSquares: reserves.iter().map(|&r| r * r).fold(U256::ZERO, |acc, sq| acc + sq),
+            radiusSquared: radius_squared,
+            valid: constraint_valid,
+        });
+        
+        let trader = msg::sender();
+        let this = contract::address();
+        if !IERC20::new(token_in_address)
+            .transfer_from(Call::new_in(self), trader, this, amount_in)
+            .unwrap_or(false)
+        {
+            return Err(OrbitalAMMError::TransferFailed(TransferFailed {}));
+        }
+        if !IERC20::new(token_out_address)
+            .transfer(Call::new_in(self), trader, amount_out)
+            .unwrap_or(false)
+        {
+            return Err(OrbitalAMMError::TransferFailed(TransferFailed {}));
+        }
+        
+        Ok(amount_out)
+    }
+    
+    /// Set the boundary half of a pool's torus, consolidated off-chain from
+    /// the ticks pinned to their boundary (see `orbital_math::torus`)
+    /// - pool_id: Pool identifier
+    /// - boundary_plane: Summed plane constant of pinned ticks
+    /// - boundary_radius: Summed circle radius of pinned ticks
+    pub fn set_toroidal_boundary(
+        &mut self,
+        pool_id: U256,
+        boundary_plane: U256,
+        boundary_radius: U256,
+    ) -> Result<(), OrbitalAMMError> {
+        if msg::sender() != self.owner.get() {
+            return Err(OrbitalAMMError::Unauthorized(Unauthorized {}));
+        }
+        if !self.pools.get(pool_id).active.get() {
+            return Err(OrbitalAMMError::PoolNotFound(PoolNotFound {}));
+        }
+        
+        let mut pool_mut = self.pools.setter(pool_id);
+        pool_mut.boundary_plane.set(boundary_plane);
+        pool_mut.boundary_radius.set(boundary_radius);
+        Ok(())
+    }
+    
+    /// Add concentrated liquidity to a specific tick range
+    /// - pool_id: Pool identifier
+    /// - tick_lower: Lower tick boundary
+    /// - tick_upper: Upper tick boundary
+    /// - amounts: Amounts for each token in the pool
+    pub fn add_concentrated_liquidity(
+        &mut self,
+        pool_id: U256,
+        tick_lower: U256,
+        tick_upper: U256,
+        amounts: Vec<U256>,
+    ) -> Result<U256, OrbitalAMMError> {
+        let pool = self.pools.get(pool_id);
+        if !pool.active.get() {
+            return Err(OrbitalAMMError::PoolNotFound(PoolNotFound {}));
+        }
+        
+        if amounts.len() != pool.token_count.get() as usize {
+            return Err(OrbitalAMMError::InvalidAmount(InvalidAmount {}));
+        }
+        
+        if tick_lower >= tick_upper {
+            return Err(OrbitalAMMError::TickOutOfRange(TickOutOfRange {}));
+        }
+        
+        // Calculate liquidity amount based on amounts
+        let liquidity = amounts.iter().fold(U256::ZERO, |acc, &amount| acc + amount);
+        
+        // Update pool concentrated liquidity
+        let mut pool_mut = self.pools.setter(pool_id);
+        let new_cl = pool.concentrated_liquidity.get() + liquidity;
+        pool_mut.concentrated_liquidity.set(new_cl);
+        
+        // Update total liquidity shares
+        let new_shares = pool.total_liquidity_shares.get() + liquidity;
+        pool_mut.total_liquidity_shares.set(new_shares);
+        
+        evm::log(ConcentratedLiquidityAdded {
+            poolId: pool_id,
+            provider: msg::sender(),
+            amounts: amounts.clone(),
+            tickLower: tick_lower,
+            tickUpper: tick_upper,
+        });
+        
+        Ok(liquidity)
+    }
+    
+    /// Configure MEV protection parameters
+    /// - commit_reveal_delay: Blocks to wait between commit and reveal
     /// - twap_window: Time window for TWAP calculation in seconds
     pub fn configure_mev_protection(
         &mut self,
@@ -1110,6 +1408,43 @@ impl OrbitalAMM {
         (reserve0, reserve1, virtual0, virtual1, k, volume)
     }
 
+    /// Get N-dimensional orbital pool state: tokens, reserves, radius squared,
+    /// concentrated liquidity and whether the pool is active
+    pub fn get_orbital_pool(&self, pool_id: U256) -> (Vec<Address>, Vec<U256>, U256, U256, bool) {
+        let pool = self.pools.get(pool_id);
+        let count = pool.token_count.get() as usize;
+
+        let mut tokens = Vec::with_capacity(count);
+        let mut reserves = Vec::with_capacity(count);
+        for i in 0..count {
+            tokens.push(pool.tokens.get(i).unwrap_or_default());
+            reserves.push(pool.reserves.get(i).unwrap_or_default());
+        }
+
+        (
+            tokens,
+            reserves,
+            pool.radius_squared.get(),
+            pool.concentrated_liquidity.get(),
+            pool.active.get(),
+        )
+    }
+
+    /// Get concentrated liquidity state: current tick, active liquidity and
+    /// whether concentrated liquidity is enabled
+    pub fn get_cl_state(&self, pool_id: U256) -> (U256, U256, bool) {
+        let cl = self.cl_states.get(pool_id);
+        (cl.current_tick.get(), cl.active_liquidity.get(), cl.cl_enabled.get())
+    }
+
+    /// Get tick info: gross liquidity, fee growth outside and whether the
+    /// tick is initialized
+    pub fn get_tick(&self, pool_id: U256, tick: U256) -> (U256, U256, bool) {
+        let cl = self.cl_states.get(pool_id);
+        let info = cl.ticks.get(tick);
+        (info.liquidity_gross.get(), info.fee_growth_outside.get(), info.initialized.get())
+    }
+
     /// Get dynamic fee state for a pool
     pub fn get_fee_state(&self, pool_id: U256) -> (U256, U256, U256, U256) {
         let fee_state = self.dynamic_fees.get(pool_id);
@@ -119,26 +119,22 @@ mod orbital_math {
         }
     }
     
-    // Newton's method square root approximation
+    // Floor square root. Babylonian steps from 2^ceil(bits/2), which is never
+    // below the root, decrease monotonically to it, unlike a fixed number of
+    // steps from value / 2.
     fn sqrt_approximation(value: U256) -> U256 {
-        if value.is_zero() {
-            return U256::ZERO;
-        }
-        
-        if value <= U256::from(1) {
+        if value < U256::from(2) {
             return value;
         }
-        
-        let mut x = value / U256::from(2);
-        for _ in 0..10 { // Limited iterations for gas efficiency
-            let x_new = (x + value / x) / U256::from(2);
+
+        let mut x = U256::from(1) << ((value.bit_len() + 1) / 2);
+        loop {
+            let x_new = (x + value / x) >> 1;
             if x_new >= x {
-                break;
+                return x;
             }
             x = x_new;
         }
-        
-        x
     }
 }
 
@@ -0,0 +1,351 @@
+//! Append-only audit log of engine decisions.
+//!
+//! Every decision the engine makes about an intent — rejection, queueing,
+//! matching, simulation, each execution attempt, bridge dispatch and every
+//! lifecycle transition — is appended as an [`AuditEvent`] with a structured
+//! payload. Events are never updated or deleted, so [`AuditLog::replay`] can
+//! reconstruct exactly what happened to an intent and why, for debugging and
+//! dispute resolution.
+
+use crate::{
+    intent::IntentStatus,
+    lifecycle::Causer,
+    validator::rules::RejectionReason,
+    Result,
+};
+use async_trait::async_trait;
+use ethers::types::{H256, U256};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::RwLock;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditEventKind {
+    /// Rejected by a validation rule before being accepted
+    ValidationFailed { reason: RejectionReason },
+    Queued { expected_profit: U256, user_tier: u8 },
+    /// Picked up by the executor with destination inventory reserved
+    Matched { source_chain_id: u64, dest_chain_id: u64, reserved: U256 },
+    /// Dry-run before broadcasting; no predicted amount if a step reverted
+    Simulated { success: bool, predicted_dest_amount: Option<U256>, revert_reason: Option<String> },
+    /// One try at an execution step; `error` is set if it failed
+    ExecutionAttempt { step: String, attempt: u32, error: Option<String> },
+    AssetsLocked { chain_id: u64, tx_hash: H256, lock_id: H256 },
+    BridgeDispatched { chain_id: u64, dest_chain_id: u64, tx_hash: H256, message_id: H256 },
+    StatusChanged { from: IntentStatus, to: IntentStatus, causer: Causer, reason: Option<String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    /// Position in the global log, assigned by the store on append
+    pub sequence: u64,
+    pub intent_id: H256,
+    pub timestamp: u64,
+    pub kind: AuditEventKind,
+}
+
+/// Durable, append-only backend for audit events
+#[async_trait]
+pub trait AuditStore: Send + Sync {
+    /// Persist `event` and return the sequence number it was assigned
+    async fn append(&self, event: &AuditEvent) -> Result<u64>;
+
+    /// Every event for `intent_id`, oldest first
+    async fn load_intent(&self, intent_id: H256) -> Result<Vec<AuditEvent>>;
+
+    /// Up to `limit` events with a sequence greater than `after`
+    async fn load_after(&self, after: u64, limit: usize) -> Result<Vec<AuditEvent>>;
+}
+
+/// Non-durable audit store used when no backend is configured
+#[derive(Default)]
+pub struct MemoryAuditStore {
+    events: RwLock<Vec<AuditEvent>>,
+}
+
+impl MemoryAuditStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AuditStore for MemoryAuditStore {
+    async fn append(&self, event: &AuditEvent) -> Result<u64> {
+        let mut events = self.events.write().await;
+        let sequence = events.len() as u64 + 1;
+        events.push(AuditEvent { sequence, ..event.clone() });
+        Ok(sequence)
+    }
+
+    async fn load_intent(&self, intent_id: H256) -> Result<Vec<AuditEvent>> {
+        Ok(self.events.read().await
+            .iter()
+            .filter(|event| event.intent_id == intent_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn load_after(&self, after: u64, limit: usize) -> Result<Vec<AuditEvent>> {
+        Ok(self.events.read().await
+            .iter()
+            .skip(after as usize)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub use self::postgres_audit::PostgresAuditStore;
+
+#[cfg(feature = "postgres")]
+mod postgres_audit {
+    use super::*;
+    use crate::EngineError;
+    use sqlx::{postgres::PgPoolOptions, PgPool, Row};
+
+    /// Audit log shared by every engine instance writing to the database
+    pub struct PostgresAuditStore {
+        pool: PgPool,
+    }
+
+    impl PostgresAuditStore {
+        pub async fn connect(database_url: &str) -> Result<Self> {
+            let pool = PgPoolOptions::new()
+                .max_connections(5)
+                .acquire_timeout(std::time::Duration::from_secs(30))
+                .connect(database_url)
+                .await
+                .map_err(|e| EngineError::StorageError(format!("Failed to connect to database: {}", e)))?;
+
+            let store = Self { pool };
+            store.create_tables().await?;
+            Ok(store)
+        }
+
+        async fn create_tables(&self) -> Result<()> {
+            sqlx::query(r#"
+                CREATE TABLE IF NOT EXISTS engine_audit_log (
+                    sequence BIGSERIAL PRIMARY KEY,
+                    intent_id VARCHAR(66) NOT NULL,
+                    kind VARCHAR(32) NOT NULL,
+                    payload JSONB NOT NULL,
+                    timestamp BIGINT NOT NULL
+                )
+            "#)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EngineError::StorageError(format!("Failed to create engine_audit_log table: {}", e)))?;
+
+            sqlx::query("CREATE INDEX IF NOT EXISTS idx_engine_audit_log_intent ON engine_audit_log(intent_id, sequence)")
+                .execute(&self.pool).await.ok();
+
+            Ok(())
+        }
+
+        fn decode(row: &sqlx::postgres::PgRow) -> Result<AuditEvent> {
+            let sequence: i64 = row.try_get("sequence")
+                .map_err(|e| EngineError::StorageError(e.to_string()))?;
+            let payload: serde_json::Value = row.try_get("payload")
+                .map_err(|e| EngineError::StorageError(e.to_string()))?;
+            let event: AuditEvent = serde_json::from_value(payload)
+                .map_err(|e| EngineError::StorageError(e.to_string()))?;
+            Ok(AuditEvent { sequence: sequence as u64, ..event })
+        }
+    }
+
+    #[async_trait]
+    impl AuditStore for PostgresAuditStore {
+        async fn append(&self, event: &AuditEvent) -> Result<u64> {
+            let payload = serde_json::to_value(event)
+                .map_err(|e| EngineError::StorageError(e.to_string()))?;
+            let kind = payload["kind"]["type"].as_str().unwrap_or_default().to_string();
+
+            let row = sqlx::query(r#"
+                INSERT INTO engine_audit_log (intent_id, kind, payload, timestamp)
+                VALUES ($1, $2, $3, $4)
+                RETURNING sequence
+            "#)
+            .bind(format!("{:?}", event.intent_id))
+            .bind(kind)
+            .bind(payload)
+            .bind(event.timestamp as i64)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| EngineError::StorageError(format!("Failed to append audit event: {}", e)))?;
+
+            let sequence: i64 = row.try_get("sequence")
+                .map_err(|e| EngineError::StorageError(e.to_string()))?;
+            Ok(sequence as u64)
+        }
+
+        async fn load_intent(&self, intent_id: H256) -> Result<Vec<AuditEvent>> {
+            let rows = sqlx::query("SELECT sequence, payload FROM engine_audit_log WHERE intent_id = $1 ORDER BY sequence")
+                .bind(format!("{:?}", intent_id))
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| EngineError::StorageError(format!("Failed to load audit events: {}", e)))?;
+
+            rows.iter().map(Self::decode).collect()
+        }
+
+        async fn load_after(&self, after: u64, limit: usize) -> Result<Vec<AuditEvent>> {
+            let rows = sqlx::query("SELECT sequence, payload FROM engine_audit_log WHERE sequence > $1 ORDER BY sequence LIMIT $2")
+                .bind(after as i64)
+                .bind(limit as i64)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| EngineError::StorageError(format!("Failed to load audit events: {}", e)))?;
+
+            rows.iter().map(Self::decode).collect()
+        }
+    }
+}
+
+/// An intent's history rebuilt from its audit events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentReplay {
+    pub intent_id: H256,
+    /// Status after the last recorded transition; `None` if the intent was
+    /// rejected before being accepted
+    pub status: Option<IntentStatus>,
+    pub rejection: Option<RejectionReason>,
+    pub execution_attempts: u32,
+    pub failed_attempts: u32,
+    /// Source-chain transactions in the order they were confirmed
+    pub transactions: Vec<H256>,
+    pub events: Vec<AuditEvent>,
+}
+
+impl IntentReplay {
+    pub fn from_events(intent_id: H256, events: Vec<AuditEvent>) -> Self {
+        let mut replay = Self {
+            intent_id,
+            status: None,
+            rejection: None,
+            execution_attempts: 0,
+            failed_attempts: 0,
+            transactions: Vec::new(),
+            events: Vec::new(),
+        };
+
+        for event in &events {
+            match &event.kind {
+                AuditEventKind::ValidationFailed { reason } => replay.rejection = Some(reason.clone()),
+                AuditEventKind::StatusChanged { to, .. } => replay.status = Some(*to),
+                AuditEventKind::ExecutionAttempt { error, .. } => {
+                    replay.execution_attempts += 1;
+                    if error.is_some() {
+                        replay.failed_attempts += 1;
+                    }
+                }
+                AuditEventKind::AssetsLocked { tx_hash, .. } | AuditEventKind::BridgeDispatched { tx_hash, .. } => {
+                    replay.transactions.push(*tx_hash)
+                }
+                AuditEventKind::Queued { .. } | AuditEventKind::Matched { .. } | AuditEventKind::Simulated { .. } => {}
+            }
+        }
+
+        replay.events = events;
+        replay
+    }
+}
+
+pub struct AuditLog {
+    store: Arc<dyn AuditStore>,
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new(Arc::new(MemoryAuditStore::new()))
+    }
+}
+
+impl AuditLog {
+    pub fn new(store: Arc<dyn AuditStore>) -> Self {
+        Self { store }
+    }
+
+    /// Append an event. A failed write is logged rather than returned so
+    /// auditing never blocks execution.
+    pub async fn record(&self, intent_id: H256, kind: AuditEventKind) {
+        let event = AuditEvent {
+            sequence: 0,
+            intent_id,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            kind,
+        };
+
+        if let Err(e) = self.store.append(&event).await {
+            tracing::error!("Failed to record audit event for {:?}: {}", intent_id, e);
+        }
+    }
+
+    pub async fn replay(&self, intent_id: H256) -> Result<IntentReplay> {
+        let events = self.store.load_intent(intent_id).await?;
+        Ok(IntentReplay::from_events(intent_id, events))
+    }
+
+    /// Page through the global log, e.g. to export it
+    pub async fn events_after(&self, after: u64, limit: usize) -> Result<Vec<AuditEvent>> {
+        self.store.load_after(after, limit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::rules::RejectionCode;
+
+    #[tokio::test]
+    async fn test_replay_reconstructs_history() {
+        let log = AuditLog::default();
+        let intent_id = H256::random();
+        let lock_tx = H256::random();
+
+        log.record(intent_id, AuditEventKind::Queued { expected_profit: U256::zero(), user_tier: 0 }).await;
+        log.record(intent_id, AuditEventKind::ExecutionAttempt { step: "lock".into(), attempt: 1, error: Some("timeout".into()) }).await;
+        log.record(intent_id, AuditEventKind::ExecutionAttempt { step: "lock".into(), attempt: 2, error: None }).await;
+        log.record(intent_id, AuditEventKind::AssetsLocked { chain_id: 1, tx_hash: lock_tx, lock_id: H256::zero() }).await;
+        log.record(H256::random(), AuditEventKind::Queued { expected_profit: U256::zero(), user_tier: 0 }).await;
+        log.record(
+            intent_id,
+            AuditEventKind::StatusChanged {
+                from: IntentStatus::Executing,
+                to: IntentStatus::Settled,
+                causer: Causer::Executor,
+                reason: None,
+            },
+        ).await;
+
+        let replay = log.replay(intent_id).await.unwrap();
+        assert_eq!(replay.events.len(), 5);
+        assert_eq!(replay.status, Some(IntentStatus::Settled));
+        assert_eq!((replay.execution_attempts, replay.failed_attempts), (2, 1));
+        assert_eq!(replay.transactions, vec![lock_tx]);
+        assert!(replay.events.windows(2).all(|pair| pair[0].sequence < pair[1].sequence));
+
+        assert_eq!(log.events_after(4, 10).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_replay_of_rejected_intent() {
+        let log = AuditLog::default();
+        let intent_id = H256::random();
+        let reason = RejectionReason::new("deadline", RejectionCode::Expired, "expired");
+
+        log.record(intent_id, AuditEventKind::ValidationFailed { reason: reason.clone() }).await;
+
+        let replay = log.replay(intent_id).await.unwrap();
+        assert_eq!(replay.status, None);
+        assert_eq!(replay.rejection, Some(reason));
+    }
+}
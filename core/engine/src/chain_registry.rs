@@ -0,0 +1,342 @@
+//! Registry of chains the executor can reach.
+//!
+//! Each chain may have several RPC endpoints. A periodic health check probes
+//! every endpoint, records latency and head block, and marks endpoints that
+//! error or lag behind as unhealthy; callers always get the fastest healthy
+//! one. Chains can be added, removed, disabled and re-enabled at runtime and
+//! the executor picks the change up on its next execution.
+
+use crate::{signer::Signer, ChainConfig, EngineError, Result};
+use ethers::providers::{Http, Middleware, Provider};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{sync::RwLock, task::JoinHandle};
+
+/// Consecutive failed probes before an endpoint is taken out of rotation
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+/// Blocks an endpoint may trail the best head before it counts as stale
+const MAX_BLOCK_LAG: u64 = 5;
+
+/// A chain resolved to its currently preferred endpoint
+#[derive(Clone)]
+pub struct ChainState {
+    pub config: ChainConfig,
+    pub provider: Arc<Provider<Http>>,
+    pub signer: Option<Arc<dyn Signer>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointHealth {
+    pub url: String,
+    pub healthy: bool,
+    pub latency_ms: Option<u64>,
+    pub head_block: Option<u64>,
+    pub consecutive_failures: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainHealth {
+    pub chain_id: u64,
+    pub enabled: bool,
+    pub endpoints: Vec<EndpointHealth>,
+}
+
+struct Endpoint {
+    url: String,
+    provider: Arc<Provider<Http>>,
+    healthy: bool,
+    latency: Option<Duration>,
+    head_block: Option<u64>,
+    consecutive_failures: u32,
+}
+
+impl Endpoint {
+    fn record_failure(&mut self) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= MAX_CONSECUTIVE_FAILURES && self.healthy {
+            tracing::warn!("RPC endpoint {} marked unhealthy", self.url);
+            self.healthy = false;
+        }
+    }
+}
+
+struct ChainEntry {
+    config: ChainConfig,
+    endpoints: Vec<Endpoint>,
+    signer: Option<Arc<dyn Signer>>,
+    enabled: bool,
+}
+
+impl ChainEntry {
+    /// Healthy endpoint with the lowest latency; unprobed endpoints rank
+    /// after measured ones, in configuration order
+    fn best_endpoint(&self) -> Option<&Endpoint> {
+        self.endpoints
+            .iter()
+            .filter(|endpoint| endpoint.healthy)
+            .min_by_key(|endpoint| endpoint.latency.unwrap_or(Duration::MAX))
+    }
+}
+
+#[derive(Default)]
+pub struct ChainRegistry {
+    chains: RwLock<HashMap<u64, ChainEntry>>,
+}
+
+impl ChainRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a chain, or replace it if already present
+    pub async fn add_chain(&self, config: ChainConfig) -> Result<()> {
+        let mut endpoints = Vec::new();
+        for url in std::iter::once(&config.rpc_url).chain(config.fallback_rpc_urls.iter()) {
+            let provider = Provider::<Http>::try_from(url.as_str())
+                .map_err(|e| EngineError::BridgeError(format!("Invalid RPC URL {}: {}", url, e)))?;
+            endpoints.push(Endpoint {
+                url: url.clone(),
+                provider: Arc::new(provider),
+                healthy: true,
+                latency: None,
+                head_block: None,
+                consecutive_failures: 0,
+            });
+        }
+
+        let signer = match &config.signer {
+            Some(signer_config) => Some(signer_config.build(config.chain_id).await?),
+            None => None,
+        };
+
+        self.chains.write().await.insert(config.chain_id, ChainEntry {
+            config,
+            endpoints,
+            signer,
+            enabled: true,
+        });
+        Ok(())
+    }
+
+    pub async fn remove_chain(&self, chain_id: u64) -> Option<ChainConfig> {
+        self.chains.write().await.remove(&chain_id).map(|entry| entry.config)
+    }
+
+    pub async fn set_enabled(&self, chain_id: u64, enabled: bool) -> Result<()> {
+        let mut chains = self.chains.write().await;
+        let entry = chains.get_mut(&chain_id).ok_or(EngineError::ChainNotSupported(chain_id))?;
+        entry.enabled = enabled;
+        tracing::info!("Chain {} {}", chain_id, if enabled { "enabled" } else { "disabled" });
+        Ok(())
+    }
+
+    pub async fn is_enabled(&self, chain_id: u64) -> bool {
+        self.chains.read().await.get(&chain_id).map_or(false, |entry| entry.enabled)
+    }
+
+    pub async fn configs(&self) -> Vec<ChainConfig> {
+        self.chains.read().await.values().map(|entry| entry.config.clone()).collect()
+    }
+
+    /// Resolve an enabled chain to its best endpoint
+    pub async fn chain_state(&self, chain_id: u64) -> Result<ChainState> {
+        let chains = self.chains.read().await;
+        let entry = chains
+            .get(&chain_id)
+            .filter(|entry| entry.enabled)
+            .ok_or(EngineError::ChainNotSupported(chain_id))?;
+        let endpoint = entry
+            .best_endpoint()
+            .ok_or_else(|| EngineError::BridgeError(format!("No healthy RPC endpoint for chain {}", chain_id)))?;
+
+        Ok(ChainState {
+            config: entry.config.clone(),
+            provider: endpoint.provider.clone(),
+            signer: entry.signer.clone(),
+        })
+    }
+
+    pub async fn enabled_chains(&self) -> Vec<ChainState> {
+        let ids: Vec<u64> = self
+            .chains
+            .read()
+            .await
+            .iter()
+            .filter(|(_, entry)| entry.enabled)
+            .map(|(chain_id, _)| *chain_id)
+            .collect();
+
+        let mut states = Vec::new();
+        for chain_id in ids {
+            if let Ok(state) = self.chain_state(chain_id).await {
+                states.push(state);
+            }
+        }
+        states
+    }
+
+    /// Count a failed request against an endpoint so repeated errors fail
+    /// over to the next one without waiting for the health check
+    pub async fn report_failure(&self, chain_id: u64, provider: &Arc<Provider<Http>>) {
+        let mut chains = self.chains.write().await;
+        if let Some(entry) = chains.get_mut(&chain_id) {
+            if let Some(endpoint) = entry.endpoints.iter_mut().find(|endpoint| Arc::ptr_eq(&endpoint.provider, provider)) {
+                endpoint.record_failure();
+            }
+        }
+    }
+
+    /// Probe every endpoint of every chain once
+    pub async fn check_health(&self) {
+        let targets: Vec<(u64, String, Arc<Provider<Http>>)> = self
+            .chains
+            .read()
+            .await
+            .iter()
+            .flat_map(|(chain_id, entry)| {
+                entry
+                    .endpoints
+                    .iter()
+                    .map(move |endpoint| (*chain_id, endpoint.url.clone(), endpoint.provider.clone()))
+            })
+            .collect();
+
+        // Probe without holding the lock; RPCs can be slow
+        let mut results = Vec::with_capacity(targets.len());
+        for (chain_id, url, provider) in targets {
+            let started = Instant::now();
+            let probe = tokio::time::timeout(Duration::from_secs(5), provider.get_block_number()).await;
+            let result = match probe {
+                Ok(Ok(block)) => Some((started.elapsed(), block.as_u64())),
+                _ => None,
+            };
+            results.push((chain_id, url, result));
+        }
+
+        let mut chains = self.chains.write().await;
+        for (chain_id, url, result) in results {
+            let Some(entry) = chains.get_mut(&chain_id) else { continue };
+            let Some(endpoint) = entry.endpoints.iter_mut().find(|endpoint| endpoint.url == url) else { continue };
+
+            match result {
+                Some((latency, head)) => {
+                    endpoint.latency = Some(latency);
+                    endpoint.head_block = Some(head);
+                    endpoint.consecutive_failures = 0;
+                    if !endpoint.healthy {
+                        tracing::info!("RPC endpoint {} recovered", endpoint.url);
+                    }
+                    endpoint.healthy = true;
+                }
+                None => endpoint.record_failure(),
+            }
+        }
+
+        // Endpoints far behind the best head serve stale state
+        for entry in chains.values_mut() {
+            let best_head = entry.endpoints.iter().filter_map(|endpoint| endpoint.head_block).max().unwrap_or(0);
+            for endpoint in &mut entry.endpoints {
+                if let Some(head) = endpoint.head_block {
+                    if best_head.saturating_sub(head) > MAX_BLOCK_LAG && endpoint.healthy {
+                        tracing::warn!("RPC endpoint {} is {} blocks behind", endpoint.url, best_head - head);
+                        endpoint.healthy = false;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn spawn_health_monitor(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let registry = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                registry.check_health().await;
+            }
+        })
+    }
+
+    pub async fn health(&self) -> Vec<ChainHealth> {
+        self.chains
+            .read()
+            .await
+            .iter()
+            .map(|(chain_id, entry)| ChainHealth {
+                chain_id: *chain_id,
+                enabled: entry.enabled,
+                endpoints: entry
+                    .endpoints
+                    .iter()
+                    .map(|endpoint| EndpointHealth {
+                        url: endpoint.url.clone(),
+                        healthy: endpoint.healthy,
+                        latency_ms: endpoint.latency.map(|latency| latency.as_millis() as u64),
+                        head_block: endpoint.head_block,
+                        consecutive_failures: endpoint.consecutive_failures,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::Address;
+
+    fn config(chain_id: u64) -> ChainConfig {
+        ChainConfig {
+            chain_id,
+            rpc_url: "http://primary.invalid".to_string(),
+            fallback_rpc_urls: vec!["http://fallback.invalid".to_string()],
+            intents_contract: Address::zero(),
+            orbital_amm_contract: Address::zero(),
+            bridge_contract: Address::zero(),
+            confirmation_blocks: 1,
+            executor_account: None,
+            signer: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disable_and_remove_chain() {
+        let registry = ChainRegistry::new();
+        registry.add_chain(config(1)).await.unwrap();
+        assert!(registry.chain_state(1).await.is_ok());
+
+        registry.set_enabled(1, false).await.unwrap();
+        assert!(matches!(registry.chain_state(1).await, Err(EngineError::ChainNotSupported(1))));
+        assert!(registry.enabled_chains().await.is_empty());
+
+        registry.set_enabled(1, true).await.unwrap();
+        assert!(registry.chain_state(1).await.is_ok());
+
+        assert!(registry.remove_chain(1).await.is_some());
+        assert!(registry.chain_state(1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_after_repeated_failures() {
+        let registry = ChainRegistry::new();
+        registry.add_chain(config(1)).await.unwrap();
+
+        let primary = registry.chain_state(1).await.unwrap().provider;
+        for _ in 0..MAX_CONSECUTIVE_FAILURES {
+            registry.report_failure(1, &primary).await;
+        }
+
+        let fallback = registry.chain_state(1).await.unwrap().provider;
+        assert!(!Arc::ptr_eq(&primary, &fallback));
+
+        let health = registry.health().await;
+        assert!(!health[0].endpoints[0].healthy);
+        assert!(health[0].endpoints[1].healthy);
+    }
+}
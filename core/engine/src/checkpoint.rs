@@ -0,0 +1,108 @@
+//! Executor checkpoints written on shutdown and read back on resume.
+
+use crate::{
+    intent::{Intent, IntentStatus},
+    retry::DeadLetter,
+    EngineError, Result,
+};
+use ethers::types::{transaction::eip2718::TypedTransaction, Address, H256, U256};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointedIntent {
+    pub intent_id: H256,
+    pub intent: Intent,
+    /// Status when checkpointed, used if the state store has lost the intent
+    #[serde(default)]
+    pub status: Option<IntentStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingTxCheckpoint {
+    pub chain_id: u64,
+    pub from: Address,
+    pub nonce: U256,
+    pub tx_hash: H256,
+    pub request: TypedTransaction,
+    pub bumps: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EngineCheckpoint {
+    pub created_at: u64,
+    /// Intents that were waiting in the scheduler
+    pub queued: Vec<CheckpointedIntent>,
+    /// Intents still executing when the drain timeout expired
+    pub in_flight: Vec<CheckpointedIntent>,
+    /// Broadcast transactions not yet confirmed
+    pub pending_txs: Vec<PendingTxCheckpoint>,
+    /// Dead-letter queue awaiting manual intervention
+    #[serde(default)]
+    pub dead_letters: Vec<DeadLetter>,
+}
+
+impl EngineCheckpoint {
+    pub fn is_empty(&self) -> bool {
+        self.queued.is_empty() && self.in_flight.is_empty() && self.pending_txs.is_empty() && self.dead_letters.is_empty()
+    }
+
+    /// Write atomically via a temp file so a crash mid-write never leaves a
+    /// truncated checkpoint behind
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_vec_pretty(self).map_err(|e| EngineError::StorageError(e.to_string()))?;
+        let tmp = path.with_extension("tmp");
+
+        tokio::fs::write(&tmp, json)
+            .await
+            .map_err(|e| EngineError::StorageError(format!("Failed to write checkpoint: {}", e)))?;
+        tokio::fs::rename(&tmp, path)
+            .await
+            .map_err(|e| EngineError::StorageError(format!("Failed to write checkpoint: {}", e)))
+    }
+
+    /// Load a checkpoint, or `None` if there is none at `path`
+    pub async fn load(path: &Path) -> Result<Option<Self>> {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| EngineError::StorageError(format!("Corrupt checkpoint: {}", e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(EngineError::StorageError(format!("Failed to read checkpoint: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::TransactionRequest;
+
+    #[tokio::test]
+    async fn test_checkpoint_round_trip() {
+        let path = std::env::temp_dir().join(format!("engine-checkpoint-{}.json", std::process::id()));
+        assert!(EngineCheckpoint::load(&path).await.unwrap().is_none());
+
+        let checkpoint = EngineCheckpoint {
+            created_at: 1,
+            queued: vec![CheckpointedIntent { intent_id: H256::random(), intent: Intent::default(), status: None }],
+            in_flight: Vec::new(),
+            pending_txs: vec![PendingTxCheckpoint {
+                chain_id: 1,
+                from: Address::random(),
+                nonce: U256::from(7),
+                tx_hash: H256::random(),
+                request: TransactionRequest::new().into(),
+                bumps: 2,
+            }],
+            dead_letters: Vec::new(),
+        };
+        checkpoint.save(&path).await.unwrap();
+
+        let loaded = EngineCheckpoint::load(&path).await.unwrap().unwrap();
+        assert_eq!(loaded.queued[0].intent_id, checkpoint.queued[0].intent_id);
+        assert_eq!(loaded.pending_txs[0].nonce, U256::from(7));
+
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+}
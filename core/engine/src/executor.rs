@@ -1,68 +1,152 @@
-use crate::{intent::*, state::EngineState, ChainConfig, Result, EngineError};
+use crate::{
+    audit::{AuditEventKind, AuditLog},
+    chain_registry::{ChainHealth, ChainRegistry, ChainState},
+    checkpoint::{CheckpointedIntent, EngineCheckpoint},
+    gas_oracle::{FeeStrategy, GasOracle},
+    intent::*,
+    inventory::{InventoryManager, RebalanceSuggestion},
+    lifecycle::{self, Causer},
+    retry::{DeadLetter, DeadLetterQueue, RetryPolicy},
+    scheduler::{IntentScheduler, ScheduleHints, SchedulerMetrics},
+    signer::DynSigner,
+    simulation::{self, ApprovalOverride, SimulationResult, TokenSlots},
+    state::EngineState,
+    tx_manager::TxManager,
+    ChainConfig, Result, EngineError,
+};
 use ethers::{
     prelude::*,
     providers::{Provider, Http},
+    types::transaction::eip2718::TypedTransaction,
 };
 use std::{collections::HashMap, sync::Arc, time::Duration};
 use tokio::{
-    sync::{mpsc, RwLock},
+    sync::{RwLock, Semaphore},
     task::JoinHandle,
     time::timeout,
 };
 
+/// Upper bound on intents executing at once; the rest wait in the scheduler
+const MAX_CONCURRENT_EXECUTIONS: usize = 16;
+
+/// How often executor balances are re-read from chain
+const INVENTORY_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often RPC endpoints are probed
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
 pub struct IntentExecutor {
-    chains: Arc<RwLock<HashMap<u64, ChainState>>>,
+    registry: Arc<ChainRegistry>,
     state: Arc<EngineState>,
-    intent_queue: mpsc::UnboundedSender<(H256, Intent)>,
+    tx_manager: Arc<TxManager>,
+    scheduler: Arc<IntentScheduler>,
+    in_flight: Arc<RwLock<HashMap<H256, Intent>>>,
+    inventory: Arc<InventoryManager>,
+    retry_policy: Arc<RwLock<RetryPolicy>>,
+    dead_letters: Arc<DeadLetterQueue>,
     executor_handle: RwLock<Option<JoinHandle<()>>>,
-}
-
-struct ChainState {
-    config: ChainConfig,
-    provider: Arc<Provider<Http>>,
+    inventory_handle: RwLock<Option<JoinHandle<()>>>,
+    health_handle: RwLock<Option<JoinHandle<()>>>,
 }
 
 impl IntentExecutor {
     pub async fn new(chains: Vec<ChainConfig>, state: Arc<EngineState>) -> Result<Self> {
-        let mut chain_map = HashMap::new();
+        let registry = Arc::new(ChainRegistry::new());
         
         for config in chains {
-            let provider = Provider::<Http>::try_from(&config.rpc_url)
-                .map_err(|e| EngineError::BridgeError(e.to_string()))?;
-            
-            chain_map.insert(config.chain_id, ChainState {
-                config: config.clone(),
-                provider: Arc::new(provider),
-            });
+            registry.add_chain(config).await?;
         }
         
-        let (tx, rx) = mpsc::unbounded_channel();
-        
         Ok(Self {
-            chains: Arc::new(RwLock::new(chain_map)),
+            registry,
             state,
-            intent_queue: tx,
+            tx_manager: Arc::new(TxManager::default()),
+            scheduler: Arc::new(IntentScheduler::default()),
+            in_flight: Arc::new(RwLock::new(HashMap::new())),
+            inventory: Arc::new(InventoryManager::new()),
+            retry_policy: Arc::new(RwLock::new(RetryPolicy::default())),
+            dead_letters: Arc::new(DeadLetterQueue::new()),
             executor_handle: RwLock::new(None),
+            inventory_handle: RwLock::new(None),
+            health_handle: RwLock::new(None),
         })
     }
     
     pub async fn queue_intent(&self, intent_id: H256, intent: Intent) -> Result<()> {
-        self.intent_queue.send((intent_id, intent))
-            .map_err(|_| EngineError::ExecutionFailed("Failed to queue intent".to_string()))?;
+        self.queue_intent_with_hints(intent_id, intent, ScheduleHints::default()).await
+    }
+    
+    pub async fn queue_intent_with_hints(&self, intent_id: H256, intent: Intent, hints: ScheduleHints) -> Result<()> {
+        self.state.audit().record(intent_id, AuditEventKind::Queued {
+            expected_profit: hints.expected_profit,
+            user_tier: hints.user_tier,
+        }).await;
+        self.scheduler.push(intent_id, intent, hints).await;
         Ok(())
     }
     
+    pub fn scheduler(&self) -> Arc<IntentScheduler> {
+        self.scheduler.clone()
+    }
+    
+    pub async fn queue_metrics(&self) -> SchedulerMetrics {
+        self.scheduler.metrics().await
+    }
+    
+    pub fn inventory(&self) -> Arc<InventoryManager> {
+        self.inventory.clone()
+    }
+    
+    /// Policy applied to executions started from now on
+    pub async fn set_retry_policy(&self, policy: RetryPolicy) {
+        *self.retry_policy.write().await = policy;
+    }
+    
+    pub async fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy.read().await.clone()
+    }
+    
+    pub fn dead_letters(&self) -> Arc<DeadLetterQueue> {
+        self.dead_letters.clone()
+    }
+    
+    pub async fn in_flight_count(&self) -> usize {
+        self.in_flight.read().await.len()
+    }
+    
+    pub async fn is_in_flight(&self, intent_id: H256) -> bool {
+        self.in_flight.read().await.contains_key(&intent_id)
+    }
+    
+    /// Re-read executor balances on every chain and log rebalancing needs
+    pub async fn refresh_inventory(&self) -> Vec<RebalanceSuggestion> {
+        refresh_inventory(&self.registry, &self.inventory).await
+    }
+    
+    /// Fee estimator used to price executor transactions
+    pub fn gas_oracle(&self) -> Arc<GasOracle> {
+        self.tx_manager.gas_oracle()
+    }
+    
+    pub fn registry(&self) -> Arc<ChainRegistry> {
+        self.registry.clone()
+    }
+    
+    pub async fn chain_health(&self) -> Vec<ChainHealth> {
+        self.registry.health().await
+    }
+    
     pub async fn add_chain(&self, config: ChainConfig) -> Result<()> {
-        let provider = Provider::<Http>::try_from(&config.rpc_url)
-            .map_err(|e| EngineError::BridgeError(e.to_string()))?;
-        
-        let mut chains = self.chains.write().await;
-        chains.insert(config.chain_id, ChainState {
-            config: config.clone(),
-            provider: Arc::new(provider),
-        });
-        
-        Ok(())
+        self.registry.add_chain(config).await
+    }
+    
+    pub async fn remove_chain(&self, chain_id: u64) -> Option<ChainConfig> {
+        self.registry.remove_chain(chain_id).await
+    }
+    
+    /// Disabled chains are skipped by new executions until re-enabled
+    pub async fn set_chain_enabled(&self, chain_id: u64, enabled: bool) -> Result<()> {
+        self.registry.set_enabled(chain_id, enabled).await
     }
     
     pub async fn start(&self) -> Result<()> {
@@ -72,23 +156,56 @@ impl IntentExecutor {
             return Ok(());
         }
         
-        let chains = self.chains.clone();
+        let registry = self.registry.clone();
         let state = self.state.clone();
-        let mut rx = {
-            let (tx, rx) = mpsc::unbounded_channel();
-            let _ = std::mem::replace(&mut self.intent_queue, tx);
-            rx
-        };
+        let tx_manager = self.tx_manager.clone();
+        let scheduler = self.scheduler.clone();
+        let in_flight = self.in_flight.clone();
+        let inventory = self.inventory.clone();
+        let retry_policy = self.retry_policy.clone();
+        let dead_letters = self.dead_letters.clone();
+        let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_EXECUTIONS));
+        
+        *self.health_handle.write().await = Some(self.registry.spawn_health_monitor(HEALTH_CHECK_INTERVAL));
+        
+        let refresh_registry = self.registry.clone();
+        let refresh_inventory_manager = self.inventory.clone();
+        *self.inventory_handle.write().await = Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(INVENTORY_REFRESH_INTERVAL);
+            loop {
+                interval.tick().await;
+                refresh_inventory(&refresh_registry, &refresh_inventory_manager).await;
+            }
+        }));
         
         let executor_task = tokio::spawn(async move {
-            while let Some((intent_id, intent)) = rx.recv().await {
-                let chains = chains.clone();
+            loop {
+                // Only dequeue once a slot is free so the scheduler, not the
+                // runtime, decides what runs next
+                let permit = match permits.clone().acquire_owned().await {
+                    Ok(permit) => permit,
+                    Err(_) => break,
+                };
+                let queued = scheduler.pop().await;
+                in_flight.write().await.insert(queued.intent_id, queued.intent.clone());
+                
+                let registry = registry.clone();
                 let state = state.clone();
+                let tx_manager = tx_manager.clone();
+                let in_flight = in_flight.clone();
+                let inventory = inventory.clone();
+                let dead_letters = dead_letters.clone();
+                let policy = retry_policy.read().await.clone();
                 
                 tokio::spawn(async move {
-                    if let Err(e) = execute_intent(intent_id, intent, chains, state).await {
+                    let _permit = permit;
+                    let intent_id = queued.intent_id;
+                    let intent = queued.intent;
+                    if let Err(e) = execute_intent(intent_id, intent.clone(), registry, state, tx_manager, inventory, policy).await {
                         tracing::error!("Failed to execute intent {}: {:?}", intent_id, e);
+                        dead_letters.push(DeadLetter::new(intent_id, intent, &e)).await;
                     }
+                    in_flight.write().await.remove(&intent_id);
                 });
             }
         });
@@ -104,30 +221,221 @@ impl IntentExecutor {
             task.abort();
         }
         
+        if let Some(task) = self.inventory_handle.write().await.take() {
+            task.abort();
+        }
+        
+        if let Some(task) = self.health_handle.write().await.take() {
+            task.abort();
+        }
+        
+        Ok(())
+    }
+    
+    /// Stop dispatching, give in-flight executions up to `drain_timeout` to
+    /// finish, and checkpoint whatever is left
+    pub async fn shutdown(&self, drain_timeout: Duration) -> Result<EngineCheckpoint> {
+        self.stop().await?;
+        
+        let queued = self.scheduler.drain().await;
+        
+        let drained = timeout(drain_timeout, async {
+            while !self.in_flight.read().await.is_empty() {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }).await;
+        if drained.is_err() {
+            tracing::warn!(
+                "{} intents still executing after {:?}; checkpointing them",
+                self.in_flight.read().await.len(),
+                drain_timeout
+            );
+        }
+        
+        let in_flight: Vec<(H256, Intent)> = self.in_flight.read().await
+            .iter()
+            .map(|(intent_id, intent)| (*intent_id, intent.clone()))
+            .collect();
+        
+        let mut checkpointed_queue = Vec::new();
+        for entry in queued {
+            let status = self.state.get_intent_status(entry.intent_id).await.ok();
+            checkpointed_queue.push(CheckpointedIntent { intent_id: entry.intent_id, intent: entry.intent, status });
+        }
+        let mut checkpointed_in_flight = Vec::new();
+        for (intent_id, intent) in in_flight {
+            let status = self.state.get_intent_status(intent_id).await.ok();
+            checkpointed_in_flight.push(CheckpointedIntent { intent_id, intent, status });
+        }
+        
+        Ok(EngineCheckpoint {
+            created_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            queued: checkpointed_queue,
+            in_flight: checkpointed_in_flight,
+            pending_txs: self.tx_manager.snapshot().await,
+            dead_letters: self.dead_letters.list().await,
+        })
+    }
+    
+    /// Resume from a checkpoint: re-track unconfirmed transactions and
+    /// re-queue intents that had not started executing on-chain. Intents the
+    /// state store has lost are re-inserted first; nothing else is applied
+    /// unless that succeeds for all of them.
+    pub async fn restore(&self, checkpoint: EngineCheckpoint) -> Result<()> {
+        let in_flight = checkpoint.in_flight.into_iter().map(|entry| (entry, true));
+        let mut requeue = Vec::new();
+        for (entry, was_in_flight) in checkpoint.queued.into_iter().map(|entry| (entry, false)).chain(in_flight) {
+            // An in-flight intent may already have sent transactions, so
+            // without a recorded status it is not run again
+            let fallback = entry.status.unwrap_or(if was_in_flight {
+                IntentStatus::Executing
+            } else {
+                IntentStatus::Validated
+            });
+            match self.state.restore_intent(entry.intent_id, entry.intent.clone(), fallback).await? {
+                IntentStatus::Created | IntentStatus::Validated | IntentStatus::Matched => requeue.push(entry),
+                status => tracing::warn!(
+                    "Checkpointed intent {:?} is {:?}; not re-queuing",
+                    entry.intent_id,
+                    status
+                ),
+            }
+        }
+        
+        self.tx_manager.restore(checkpoint.pending_txs).await;
+        
+        for entry in checkpoint.dead_letters {
+            self.dead_letters.push(entry).await;
+        }
+        
+        for entry in requeue {
+            // Recovery from the state store may have queued it already
+            if self.scheduler.contains(entry.intent_id).await || self.is_in_flight(entry.intent_id).await {
+                continue;
+            }
+            self.queue_intent(entry.intent_id, entry.intent).await?;
+        }
+        
         Ok(())
     }
 }
 
+async fn refresh_inventory(
+    registry: &ChainRegistry,
+    inventory: &InventoryManager,
+) -> Vec<RebalanceSuggestion> {
+    for chain in registry.enabled_chains().await {
+        let refreshed = match executor_account(&chain).await {
+            Ok(account) => inventory.refresh(chain.config.chain_id, chain.provider.as_ref(), account).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = refreshed {
+            tracing::warn!("Failed to refresh inventory on chain {}: {:?}", chain.config.chain_id, e);
+        }
+    }
+    
+    let suggestions = inventory.rebalancing_suggestions().await;
+    for suggestion in &suggestions {
+        tracing::warn!(
+            "Inventory low: move {} {} from chain {} to chain {}",
+            suggestion.amount,
+            suggestion.asset,
+            suggestion.from_chain,
+            suggestion.to_chain
+        );
+    }
+    suggestions
+}
+
 async fn execute_intent(
     intent_id: H256,
     intent: Intent,
-    chains: Arc<RwLock<HashMap<u64, ChainState>>>,
+    registry: Arc<ChainRegistry>,
+    state: Arc<EngineState>,
+    tx_manager: Arc<TxManager>,
+    inventory: Arc<InventoryManager>,
+    policy: RetryPolicy,
+) -> Result<()> {
+    // Refuse to match intents the destination inventory can't cover
+    if let Err(e) = inventory.reserve(intent_id, intent.dest_chain_id, intent.dest_token, intent.min_dest_amount).await {
+        state.fail_intent(intent_id).await?;
+        return Err(e);
+    }
+    state.audit().record(intent_id, AuditEventKind::Matched {
+        source_chain_id: intent.source_chain_id,
+        dest_chain_id: intent.dest_chain_id,
+        reserved: intent.min_dest_amount,
+    }).await;
+    
+    let result = run_intent(intent_id, intent, registry, state, tx_manager, policy).await;
+    match result {
+        Ok(()) => inventory.consume(intent_id).await,
+        Err(_) => inventory.release(intent_id).await,
+    }
+    result
+}
+
+async fn run_intent(
+    intent_id: H256,
+    intent: Intent,
+    registry: Arc<ChainRegistry>,
     state: Arc<EngineState>,
+    tx_manager: Arc<TxManager>,
+    policy: RetryPolicy,
 ) -> Result<()> {
-    state.update_intent_status(intent_id, IntentStatus::Executing).await?;
+    let audit = state.audit();
     
-    let chains_read = chains.read().await;
+    // Intents resumed from a checkpoint or recovery may already be matched
+    if state.get_intent_status(intent_id).await? != IntentStatus::Matched {
+        state.advance::<lifecycle::Validated, lifecycle::Matched>(intent_id, Causer::Executor, None).await?;
+    }
+    state.advance::<lifecycle::Matched, lifecycle::Executing>(intent_id, Causer::Executor, None).await?;
+    
+    // Resolved per execution so disabled chains and failed-over endpoints
+    // take effect immediately
+    let (source_chain, dest_chain) = match (
+        registry.chain_state(intent.source_chain_id).await,
+        registry.chain_state(intent.dest_chain_id).await,
+    ) {
+        (Ok(source), Ok(dest)) => (source, dest),
+        (Err(e), _) | (_, Err(e)) => {
+            state.fail_intent(intent_id).await?;
+            return Err(e);
+        }
+    };
+    let (source_chain, dest_chain) = (&source_chain, &dest_chain);
     
-    let source_chain = chains_read.get(&intent.source_chain_id)
-        .ok_or_else(|| EngineError::ChainNotSupported(intent.source_chain_id))?;
+    // Dry-run before anything is broadcast; a predicted revert or short
+    // output fails the intent without spending gas
+    let simulated = retry_step("simulate", &intent, &policy, &audit, || {
+        simulate_execution(&intent, source_chain, dest_chain)
+    }).await;
+    let simulation = match simulated {
+        Ok(simulation) => simulation,
+        Err(e) => {
+            state.fail_intent(intent_id).await?;
+            return Err(e);
+        }
+    };
+    state.record_simulation(intent_id, simulation.clone()).await?;
     
-    let dest_chain = chains_read.get(&intent.dest_chain_id)
-        .ok_or_else(|| EngineError::ChainNotSupported(intent.dest_chain_id))?;
+    if !simulation.success {
+        state.fail_intent(intent_id).await?;
+        return Err(EngineError::SimulationFailed(
+            simulation.revert_reason.unwrap_or_else(|| "predicted output below minimum".to_string())
+        ));
+    }
     
     match execute_cross_chain_swap(
         &intent,
         source_chain,
         dest_chain,
+        &tx_manager,
+        &policy,
+        &audit,
     ).await {
         Ok(dest_amount) => {
             state.complete_intent(intent_id, dest_amount).await?;
@@ -144,6 +452,9 @@ async fn execute_cross_chain_swap(
     intent: &Intent,
     source_chain: &ChainState,
     dest_chain: &ChainState,
+    tx_manager: &TxManager,
+    policy: &RetryPolicy,
+    audit: &AuditLog,
 ) -> Result<U256> {
     tracing::info!(
         "Starting cross-chain swap execution for intent: {:?}",
@@ -154,8 +465,13 @@ async fn execute_cross_chain_swap(
     let _ = execute_hook("pre-task", &format!("execute_cross_chain_swap_{}", intent.compute_id())).await;
 
     // Step 1: Lock source assets on source chain
-    let lock_result = lock_source_assets(intent, source_chain).await?;
+    let lock_result = lock_source_assets(intent, source_chain, tx_manager, policy, audit).await?;
     tracing::info!("Assets locked on source chain: tx_hash={:?}", lock_result.tx_hash);
+    audit.record(intent.compute_id(), AuditEventKind::AssetsLocked {
+        chain_id: intent.source_chain_id,
+        tx_hash: lock_result.tx_hash,
+        lock_id: lock_result.lock_id,
+    }).await;
 
     // Hook: notify asset lock
     let _ = execute_hook(
@@ -174,8 +490,14 @@ async fn execute_cross_chain_swap(
     ).await;
 
     // Step 3: Execute swap on destination chain via bridge
-    let bridge_tx = execute_via_bridge(intent, &route, source_chain, dest_chain).await?;
+    let bridge_tx = execute_via_bridge(intent, &route, source_chain, dest_chain, tx_manager, policy, audit).await?;
     tracing::info!("Bridge transaction initiated: tx_hash={:?}", bridge_tx.tx_hash);
+    audit.record(intent.compute_id(), AuditEventKind::BridgeDispatched {
+        chain_id: intent.source_chain_id,
+        dest_chain_id: intent.dest_chain_id,
+        tx_hash: bridge_tx.tx_hash,
+        message_id: bridge_tx.message_id,
+    }).await;
 
     // Step 4: Wait for execution confirmation with timeout
     let execution_result = timeout(
@@ -203,35 +525,137 @@ async fn execute_cross_chain_swap(
     Ok(execution_result.dest_amount)
 }
 
-/// Lock source assets on the source chain
-async fn lock_source_assets(
+/// Simulate the lock, swap and bridge calls with `eth_call`, overriding the
+/// token approvals each call relies on
+async fn simulate_execution(
     intent: &Intent,
     source_chain: &ChainState,
-) -> Result<LockResult> {
-    const MAX_RETRIES: u32 = 3;
-    let mut retry_count = 0;
+    dest_chain: &ChainState,
+) -> Result<SimulationResult> {
+    let executor = executor_account(source_chain).await?;
+    let block_number = source_chain.provider
+        .get_block_number()
+        .await
+        .map_err(|e| EngineError::SimulationFailed(format!("Failed to get block number: {}", e)))?
+        .as_u64();
+    let reverted = |reason: String| SimulationResult {
+        success: false,
+        predicted_dest_amount: None,
+        gas_estimate: None,
+        revert_reason: Some(reason),
+        block_number,
+    };
 
-    loop {
-        match try_lock_assets(intent, source_chain).await {
-            Ok(result) => return Ok(result),
-            Err(e) if retry_count < MAX_RETRIES => {
-                retry_count += 1;
-                tracing::warn!(
-                    "Failed to lock assets (attempt {}/{}): {:?}. Retrying...",
-                    retry_count,
-                    MAX_RETRIES,
-                    e
-                );
-                tokio::time::sleep(Duration::from_secs(2u64.pow(retry_count))).await;
-            }
-            Err(e) => return Err(e),
+    // Lock: the intents contract pulls source tokens from the user
+    let intents_contract = source_chain.config.intents_contract;
+    let lock_tx = build_lock_transaction(intent, intents_contract)?;
+    let lock = simulation::simulate_call(
+        &source_chain.provider,
+        executor,
+        intents_contract,
+        lock_tx.data.unwrap_or_default(),
+        U256::zero(),
+        &[ApprovalOverride {
+            token: intent.source_token,
+            owner: intent.user,
+            spender: intents_contract,
+            amount: intent.source_amount,
+            slots: TokenSlots::default(),
+        }],
+    ).await?;
+    if let Some(reason) = lock.revert_reason {
+        return Ok(reverted(format!("lock: {}", reason)));
+    }
+
+    // Swap: every hop is dry-run against its pool and must clear min_dest_amount
+    let route = query_orbital_amm_route(intent, source_chain, dest_chain).await?;
+    let mut amount = intent.source_amount;
+    for hop in &route.hops {
+        let chain = if hop.chain_id == dest_chain.config.chain_id { dest_chain } else { source_chain };
+        let calldata = build_swap_calldata(hop, amount, &chain.provider).await?;
+        let swap = simulation::simulate_call(
+            &chain.provider,
+            executor,
+            hop.pool,
+            calldata,
+            U256::zero(),
+            &[ApprovalOverride {
+                token: hop.token_in,
+                owner: executor,
+                spender: hop.pool,
+                amount,
+                slots: TokenSlots::default(),
+            }],
+        ).await?;
+        if let Some(reason) = swap.revert_reason {
+            return Ok(reverted(format!("swap: {}", reason)));
         }
+        amount = simulation::decode_uint(&swap.output)
+            .ok_or_else(|| EngineError::SimulationFailed("Swap returned no amount".to_string()))?;
+    }
+
+    // Bridge dispatch with the simulated output
+    let simulated_route = ExecutionRoute { estimated_output: amount, ..route.clone() };
+    let payload = build_bridge_payload(intent, &simulated_route)?;
+    let bridge_calldata = encode_bridge_call(intent.dest_chain_id, dest_chain.config.intents_contract, payload)?;
+    let bridge = simulation::simulate_call(
+        &source_chain.provider,
+        executor,
+        source_chain.config.bridge_contract,
+        bridge_calldata.into(),
+        route.estimated_gas,
+        &[],
+    ).await?;
+    if let Some(reason) = bridge.revert_reason {
+        return Ok(reverted(format!("bridge: {}", reason)));
     }
+
+    Ok(SimulationResult {
+        success: amount >= intent.min_dest_amount,
+        predicted_dest_amount: Some(amount),
+        gas_estimate: Some(route.estimated_gas),
+        revert_reason: None,
+        block_number,
+    })
+}
+
+/// Encode `swap(uint256,bool,uint256,uint256)` for a hop, resolving the
+/// pool id from the token pair
+async fn build_swap_calldata(hop: &RouteHop, amount_in: U256, provider: &Provider<Http>) -> Result<Bytes> {
+    let selector = ethers::utils::keccak256(b"getPoolByTokens(address,address)");
+    let mut lookup = selector[..4].to_vec();
+    lookup.extend(ethers::abi::encode(&[
+        ethers::abi::Token::Address(hop.token_in),
+        ethers::abi::Token::Address(hop.token_out),
+    ]));
+
+    let call: TypedTransaction = TransactionRequest::new().to(hop.pool).data(lookup).into();
+    let output = provider
+        .call(&call, None)
+        .await
+        .map_err(|e| EngineError::SimulationFailed(format!("Failed to resolve pool: {}", e)))?;
+    let pool_id = simulation::decode_uint(&output)
+        .ok_or_else(|| EngineError::SimulationFailed("Pool not found".to_string()))?;
+
+    let selector = ethers::utils::keccak256(b"swap(uint256,bool,uint256,uint256)");
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend(ethers::abi::encode(&[
+        ethers::abi::Token::Uint(pool_id),
+        ethers::abi::Token::Bool(hop.token_in < hop.token_out),
+        ethers::abi::Token::Uint(amount_in),
+        ethers::abi::Token::Uint(U256::zero()),
+    ]));
+
+    Ok(calldata.into())
 }
 
-async fn try_lock_assets(
+/// Lock source assets on the source chain
+async fn lock_source_assets(
     intent: &Intent,
     source_chain: &ChainState,
+    tx_manager: &TxManager,
+    policy: &RetryPolicy,
+    audit: &AuditLog,
 ) -> Result<LockResult> {
     // Get the intents contract
     let intents_contract = source_chain.config.intents_contract;
@@ -239,18 +663,11 @@ async fn try_lock_assets(
     // Build the lock transaction
     let lock_tx = build_lock_transaction(intent, intents_contract)?;
 
-    // Send transaction
-    let pending_tx = source_chain.provider
-        .send_transaction(lock_tx, None)
-        .await
-        .map_err(|e| EngineError::ExecutionFailed(format!("Failed to send lock tx: {}", e)))?;
-
-    // Wait for confirmation
-    let receipt = pending_tx
-        .confirmations(source_chain.config.confirmation_blocks as usize)
-        .await
-        .map_err(|e| EngineError::ExecutionFailed(format!("Failed to confirm lock tx: {}", e)))?
-        .ok_or_else(|| EngineError::ExecutionFailed("Lock transaction failed".to_string()))?;
+    // Send transaction through the nonce manager and wait for confirmation
+    let receipt = submit_and_confirm("lock", intent, source_chain, tx_manager, lock_tx, policy, audit).await?;
+    if receipt.status != Some(1.into()) {
+        return Err(EngineError::ExecutionFailed("Lock transaction failed".to_string()));
+    }
 
     Ok(LockResult {
         tx_hash: receipt.transaction_hash,
@@ -259,6 +676,131 @@ async fn try_lock_assets(
     })
 }
 
+/// Run one execution step under `policy`, auditing every attempt. Gives up
+/// with `RetriesExhausted` once the policy stops retrying. Only for work
+/// that is safe to repeat: simulations and receipt lookups, never sends.
+async fn retry_step<T, F, Fut>(
+    step: &str,
+    intent: &Intent,
+    policy: &RetryPolicy,
+    audit: &AuditLog,
+    mut operation: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+
+    loop {
+        let result = operation().await;
+        audit.record(intent.compute_id(), AuditEventKind::ExecutionAttempt {
+            step: step.to_string(),
+            attempt,
+            error: result.as_ref().err().map(|e| e.to_string()),
+        }).await;
+
+        match result {
+            Ok(value) => return Ok(value),
+            Err(e) if policy.should_retry(attempt, &e) => {
+                let delay = policy.backoff(attempt);
+                tracing::warn!(
+                    "{} failed (attempt {}/{}): {:?}. Retrying in {:?}...",
+                    step,
+                    attempt,
+                    policy.max_attempts,
+                    e,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                return Err(EngineError::RetriesExhausted {
+                    step: step.to_string(),
+                    attempts: attempt,
+                    source: Box::new(e),
+                })
+            }
+        }
+    }
+}
+
+/// Account the executor sends from on this chain: the signer's, the
+/// configured one, or the node's first unlocked account
+async fn executor_account(chain: &ChainState) -> Result<Address> {
+    if let Some(signer) = &chain.signer {
+        return Ok(signer.address());
+    }
+    
+    if let Some(account) = chain.config.executor_account {
+        return Ok(account);
+    }
+
+    chain.provider
+        .get_accounts()
+        .await
+        .map_err(|e| EngineError::ExecutionFailed(format!("Failed to list accounts: {}", e)))?
+        .first()
+        .copied()
+        .ok_or_else(|| EngineError::ExecutionFailed(format!("No executor account on chain {}", chain.config.chain_id)))
+}
+
+/// Fee urgency for an intent's transactions, tighter as its deadline nears
+fn fee_strategy(intent: &Intent) -> FeeStrategy {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    FeeStrategy::for_deadline(intent.deadline.saturating_sub(now))
+}
+
+/// Broadcast a step's transaction once and wait for its receipt. Sends are
+/// not idempotent and a timed-out one may still be mined, so only the wait
+/// is retried under `policy`: every try looks the nonce up through the
+/// `TxManager`, which knows the original hash and any gas-bumped
+/// replacement, and nothing is sent a second time.
+async fn submit_and_confirm(
+    step: &str,
+    intent: &Intent,
+    chain: &ChainState,
+    tx_manager: &TxManager,
+    tx: TransactionRequest,
+    policy: &RetryPolicy,
+    audit: &AuditLog,
+) -> Result<TransactionReceipt> {
+    let from = executor_account(chain).await?;
+    let chain_id = chain.config.chain_id;
+    let strategy = fee_strategy(intent);
+
+    let submitted = match &chain.signer {
+        Some(signer) => {
+            let client = SignerMiddleware::new(chain.provider.as_ref().clone(), DynSigner(signer.clone()));
+            tx_manager.submit(chain_id, &client, from, tx, strategy).await
+        }
+        None => tx_manager.submit(chain_id, chain.provider.as_ref(), from, tx, strategy).await,
+    };
+    let pending = match submitted {
+        Ok(pending) => pending,
+        Err(e) => {
+            audit.record(intent.compute_id(), AuditEventKind::ExecutionAttempt {
+                step: step.to_string(),
+                attempt: 1,
+                error: Some(e.to_string()),
+            }).await;
+            return Err(EngineError::RetriesExhausted {
+                step: step.to_string(),
+                attempts: 1,
+                source: Box::new(e),
+            });
+        }
+    };
+
+    retry_step(step, intent, policy, audit, || {
+        tx_manager.wait_for_receipt(chain_id, chain.provider.as_ref(), from, pending.nonce, chain.config.confirmation_blocks)
+    }).await
+}
+
 fn build_lock_transaction(
     intent: &Intent,
     intents_contract: Address,
@@ -341,33 +883,9 @@ async fn execute_via_bridge(
     route: &ExecutionRoute,
     source_chain: &ChainState,
     dest_chain: &ChainState,
-) -> Result<BridgeTransaction> {
-    const MAX_RETRIES: u32 = 3;
-    let mut retry_count = 0;
-
-    loop {
-        match try_execute_bridge(intent, route, source_chain, dest_chain).await {
-            Ok(result) => return Ok(result),
-            Err(e) if retry_count < MAX_RETRIES => {
-                retry_count += 1;
-                tracing::warn!(
-                    "Failed to execute bridge (attempt {}/{}): {:?}. Retrying...",
-                    retry_count,
-                    MAX_RETRIES,
-                    e
-                );
-                tokio::time::sleep(Duration::from_secs(3u64.pow(retry_count))).await;
-            }
-            Err(e) => return Err(e),
-        }
-    }
-}
-
-async fn try_execute_bridge(
-    intent: &Intent,
-    route: &ExecutionRoute,
-    source_chain: &ChainState,
-    dest_chain: &ChainState,
+    tx_manager: &TxManager,
+    policy: &RetryPolicy,
+    audit: &AuditLog,
 ) -> Result<BridgeTransaction> {
     let bridge_contract = source_chain.config.bridge_contract;
 
@@ -387,16 +905,10 @@ async fn try_execute_bridge(
         .data(calldata)
         .value(route.estimated_gas); // Bridge fee
 
-    let pending_tx = source_chain.provider
-        .send_transaction(tx, None)
-        .await
-        .map_err(|e| EngineError::BridgeError(format!("Failed to send bridge tx: {}", e)))?;
-
-    let receipt = pending_tx
-        .confirmations(source_chain.config.confirmation_blocks as usize)
-        .await
-        .map_err(|e| EngineError::BridgeError(format!("Failed to confirm bridge tx: {}", e)))?
-        .ok_or_else(|| EngineError::BridgeError("Bridge transaction failed".to_string()))?;
+    let receipt = submit_and_confirm("bridge", intent, source_chain, tx_manager, tx, policy, audit).await?;
+    if receipt.status != Some(1.into()) {
+        return Err(EngineError::BridgeError("Bridge transaction failed".to_string()));
+    }
 
     Ok(BridgeTransaction {
         tx_hash: receipt.transaction_hash,
@@ -412,7 +924,7 @@ async fn try_execute_bridge(
 fn build_bridge_payload(intent: &Intent, route: &ExecutionRoute) -> Result<Vec<u8>> {
     // Encode the swap execution parameters
     let encoded = ethers::abi::encode(&[
-        ethers::abi::Token::Bytes32(intent.compute_id().into()),
+        ethers::abi::Token::FixedBytes(intent.compute_id().as_bytes().to_vec()),
         ethers::abi::Token::Address(intent.user),
         ethers::abi::Token::Address(intent.dest_token),
         ethers::abi::Token::Uint(route.estimated_output),
@@ -491,16 +1003,15 @@ async fn check_execution_status(
     // Build call to getExecutionStatus(bytes32 messageId)
     let function_selector = ethers::utils::keccak256(b"getExecutionStatus(bytes32)");
     let mut calldata = function_selector[..4].to_vec();
-    calldata.extend(ethers::abi::encode(&[ethers::abi::Token::Bytes32(message_id.into())]));
+    calldata.extend(ethers::abi::encode(&[ethers::abi::Token::FixedBytes(message_id.as_bytes().to_vec())]));
 
-    let call = CallRequest {
-        to: Some(intents_contract),
-        data: Some(calldata.into()),
-        ..Default::default()
-    };
+    let call: TypedTransaction = TransactionRequest::new()
+        .to(intents_contract)
+        .data(calldata)
+        .into();
 
     let result = dest_chain.provider
-        .call(&call.into(), None)
+        .call(&call, None)
         .await
         .map_err(|e| EngineError::BridgeError(format!("Failed to call getExecutionStatus: {}", e)))?;
 
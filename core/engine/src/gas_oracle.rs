@@ -0,0 +1,320 @@
+//! EIP-1559 fee estimation from recent blocks.
+//!
+//! The oracle samples `eth_feeHistory` per chain and derives
+//! `maxFeePerGas`/`maxPriorityFeePerGas` for a [`FeeStrategy`]: cheaper
+//! strategies tip at a low reward percentile and leave little base-fee
+//! headroom, urgent ones tip high and survive several full blocks. Chains
+//! without EIP-1559 fall back to `eth_gasPrice`. Samples are cached briefly
+//! so a burst of executions costs one RPC call.
+
+use crate::{EngineError, Result};
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{
+        transaction::eip2718::TypedTransaction, BlockNumber, Eip1559TransactionRequest, FeeHistory,
+        TransactionRequest, U256,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::RwLock;
+
+/// Reward percentiles requested from `eth_feeHistory`, one per strategy
+const REWARD_PERCENTILES: [f64; 3] = [10.0, 50.0, 90.0];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeStrategy {
+    /// Cheapest fee likely to be included within a few blocks
+    Economic,
+    Standard,
+    /// Next-block inclusion even if base fee keeps rising
+    Urgent,
+}
+
+impl FeeStrategy {
+    /// Pick a strategy from the time left before an intent's deadline
+    pub fn for_deadline(seconds_remaining: u64) -> Self {
+        match seconds_remaining {
+            0..=120 => FeeStrategy::Urgent,
+            121..=900 => FeeStrategy::Standard,
+            _ => FeeStrategy::Economic,
+        }
+    }
+
+    fn percentile_index(self) -> usize {
+        match self {
+            FeeStrategy::Economic => 0,
+            FeeStrategy::Standard => 1,
+            FeeStrategy::Urgent => 2,
+        }
+    }
+
+    /// Base fee headroom as a percentage. Base fee rises at most 12.5% per
+    /// full block, so 113% covers one block, 127% two and 200% about six.
+    fn base_fee_percent(self) -> u64 {
+        match self {
+            FeeStrategy::Economic => 113,
+            FeeStrategy::Standard => 127,
+            FeeStrategy::Urgent => 200,
+        }
+    }
+
+    /// Markup over `eth_gasPrice` on chains without EIP-1559
+    fn legacy_percent(self) -> u64 {
+        match self {
+            FeeStrategy::Economic => 100,
+            FeeStrategy::Standard => 110,
+            FeeStrategy::Urgent => 125,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    /// Projected base fee of the next block; zero on legacy chains
+    pub base_fee: U256,
+    pub eip1559: bool,
+}
+
+impl FeeEstimate {
+    /// Price per gas the transaction is expected to actually pay
+    pub fn expected_gas_price(&self) -> U256 {
+        if self.eip1559 {
+            (self.base_fee + self.max_priority_fee_per_gas).min(self.max_fee_per_gas)
+        } else {
+            self.max_fee_per_gas
+        }
+    }
+
+    /// Attach these fees to a transaction, as a type-2 transaction where
+    /// the chain supports it
+    pub fn apply(&self, tx: TransactionRequest) -> TypedTransaction {
+        if !self.eip1559 {
+            return tx.gas_price(self.max_fee_per_gas).into();
+        }
+
+        let mut request = Eip1559TransactionRequest::new()
+            .max_fee_per_gas(self.max_fee_per_gas)
+            .max_priority_fee_per_gas(self.max_priority_fee_per_gas);
+        request.from = tx.from;
+        request.to = tx.to;
+        request.gas = tx.gas;
+        request.value = tx.value;
+        request.data = tx.data;
+        request.nonce = tx.nonce;
+        request.chain_id = tx.chain_id;
+        request.into()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GasOracleConfig {
+    /// Blocks sampled per `eth_feeHistory` call
+    pub block_count: u64,
+    pub cache_ttl: Duration,
+    /// Floor for priority fees, for chains where recent blocks tipped nothing
+    pub min_priority_fee: U256,
+}
+
+impl Default for GasOracleConfig {
+    fn default() -> Self {
+        Self {
+            block_count: 20,
+            cache_ttl: Duration::from_secs(12),
+            min_priority_fee: U256::zero(),
+        }
+    }
+}
+
+/// Fee market summary derived from one `eth_feeHistory` response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeeSample {
+    Eip1559 { next_base_fee: U256, rewards: [U256; 3] },
+    Legacy { gas_price: U256 },
+}
+
+impl FeeSample {
+    /// `None` when the chain returned no base fees, i.e. has no EIP-1559
+    fn from_history(history: &FeeHistory) -> Option<Self> {
+        // The response carries one base fee per block plus the next block's
+        let next_base_fee = *history.base_fee_per_gas.last()?;
+        if next_base_fee.is_zero() {
+            return None;
+        }
+
+        let mut rewards = [U256::zero(); 3];
+        for (index, reward) in rewards.iter_mut().enumerate() {
+            // Empty blocks report zero rewards and would drag the median down
+            let mut column: Vec<U256> = history
+                .reward
+                .iter()
+                .filter_map(|block| block.get(index).copied())
+                .filter(|reward| !reward.is_zero())
+                .collect();
+            column.sort();
+            *reward = column.get(column.len() / 2).copied().unwrap_or_default();
+        }
+
+        Some(FeeSample::Eip1559 { next_base_fee, rewards })
+    }
+
+    fn estimate(&self, strategy: FeeStrategy, min_priority_fee: U256) -> FeeEstimate {
+        match *self {
+            FeeSample::Eip1559 { next_base_fee, rewards } => {
+                let priority = rewards[strategy.percentile_index()].max(min_priority_fee);
+                let base_headroom = next_base_fee * U256::from(strategy.base_fee_percent()) / U256::from(100);
+                FeeEstimate {
+                    max_fee_per_gas: base_headroom + priority,
+                    max_priority_fee_per_gas: priority,
+                    base_fee: next_base_fee,
+                    eip1559: true,
+                }
+            }
+            FeeSample::Legacy { gas_price } => {
+                let price = gas_price * U256::from(strategy.legacy_percent()) / U256::from(100);
+                FeeEstimate {
+                    max_fee_per_gas: price,
+                    max_priority_fee_per_gas: price,
+                    base_fee: U256::zero(),
+                    eip1559: false,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct GasOracle {
+    config: GasOracleConfig,
+    samples: RwLock<HashMap<u64, (FeeSample, Instant)>>,
+    providers: RwLock<HashMap<u64, Arc<Provider<Http>>>>,
+}
+
+impl GasOracle {
+    pub fn new(config: GasOracleConfig) -> Self {
+        Self {
+            config,
+            samples: RwLock::new(HashMap::new()),
+            providers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Register the provider used by [`GasOracle::estimate_for_chain`]
+    pub async fn register_chain(&self, chain_id: u64, provider: Arc<Provider<Http>>) {
+        self.providers.write().await.insert(chain_id, provider);
+    }
+
+    /// Estimate fees on a chain registered with [`GasOracle::register_chain`]
+    pub async fn estimate_for_chain(&self, chain_id: u64, strategy: FeeStrategy) -> Result<FeeEstimate> {
+        let provider = self
+            .providers
+            .read()
+            .await
+            .get(&chain_id)
+            .cloned()
+            .ok_or(EngineError::ChainNotSupported(chain_id))?;
+        self.estimate(chain_id, provider.as_ref(), strategy).await
+    }
+
+    /// Estimate fees on `chain_id` through `provider`, reusing a recent
+    /// sample when one is cached
+    pub async fn estimate<M: Middleware>(&self, chain_id: u64, provider: &M, strategy: FeeStrategy) -> Result<FeeEstimate> {
+        let cached = self
+            .samples
+            .read()
+            .await
+            .get(&chain_id)
+            .filter(|(_, sampled_at)| sampled_at.elapsed() < self.config.cache_ttl)
+            .map(|(sample, _)| *sample);
+
+        let sample = match cached {
+            Some(sample) => sample,
+            None => {
+                let sample = self.sample(provider).await?;
+                self.samples.write().await.insert(chain_id, (sample, Instant::now()));
+                sample
+            }
+        };
+
+        Ok(sample.estimate(strategy, self.config.min_priority_fee))
+    }
+
+    async fn sample<M: Middleware>(&self, provider: &M) -> Result<FeeSample> {
+        let history = provider
+            .fee_history(self.config.block_count, BlockNumber::Latest, &REWARD_PERCENTILES)
+            .await;
+
+        if let Some(sample) = history.ok().as_ref().and_then(FeeSample::from_history) {
+            return Ok(sample);
+        }
+
+        let gas_price = provider
+            .get_gas_price()
+            .await
+            .map_err(|e| EngineError::ExecutionFailed(format!("Failed to fetch gas price: {}", e)))?;
+        Ok(FeeSample::Legacy { gas_price })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gwei(amount: u64) -> U256 {
+        U256::from(amount) * U256::exp10(9)
+    }
+
+    #[test]
+    fn test_estimates_from_fee_history() {
+        let history = FeeHistory {
+            base_fee_per_gas: vec![gwei(9), gwei(10)],
+            gas_used_ratio: vec![0.5, 0.5, 0.0],
+            oldest_block: U256::zero(),
+            reward: vec![
+                vec![gwei(1), gwei(2), gwei(5)],
+                vec![U256::zero(), U256::zero(), U256::zero()],
+                vec![gwei(1), gwei(3), gwei(7)],
+            ],
+        };
+        let sample = FeeSample::from_history(&history).unwrap();
+
+        let economic = sample.estimate(FeeStrategy::Economic, U256::zero());
+        assert_eq!(economic.max_priority_fee_per_gas, gwei(1));
+        assert_eq!(economic.max_fee_per_gas, gwei(11) + gwei(3) / 10 + gwei(1));
+
+        let urgent = sample.estimate(FeeStrategy::Urgent, U256::zero());
+        assert_eq!(urgent.max_priority_fee_per_gas, gwei(7));
+        assert_eq!(urgent.max_fee_per_gas, gwei(27));
+        assert_eq!(urgent.expected_gas_price(), gwei(17));
+
+        let floored = sample.estimate(FeeStrategy::Economic, gwei(2));
+        assert_eq!(floored.max_priority_fee_per_gas, gwei(2));
+    }
+
+    #[test]
+    fn test_strategy_for_deadline_and_legacy_fallback() {
+        assert_eq!(FeeStrategy::for_deadline(30), FeeStrategy::Urgent);
+        assert_eq!(FeeStrategy::for_deadline(600), FeeStrategy::Standard);
+        assert_eq!(FeeStrategy::for_deadline(3600), FeeStrategy::Economic);
+
+        let history = FeeHistory {
+            base_fee_per_gas: Vec::new(),
+            gas_used_ratio: Vec::new(),
+            oldest_block: U256::zero(),
+            reward: Vec::new(),
+        };
+        assert!(FeeSample::from_history(&history).is_none());
+
+        let legacy = FeeSample::Legacy { gas_price: gwei(20) }.estimate(FeeStrategy::Urgent, U256::zero());
+        assert!(!legacy.eip1559);
+        assert_eq!(legacy.max_fee_per_gas, gwei(25));
+        assert!(matches!(legacy.apply(TransactionRequest::new()), TypedTransaction::Legacy(_)));
+    }
+}
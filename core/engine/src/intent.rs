@@ -1,5 +1,4 @@
 use ethers::types::{Address, U256, H256, Bytes, Signature};
-use ethers::core::utils::hash_message;
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -40,15 +39,6 @@ impl Default for Intent {
 }
 
 impl Intent {
-    /// Check if the intent has expired
-    pub fn is_expired(&self) -> bool {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        now > self.deadline
-    }
-
     /// Generate intent ID from hash of intent data
     pub fn id(&self) -> H256 {
         use ethers::core::utils::keccak256;
@@ -59,28 +49,22 @@ impl Intent {
         data.extend_from_slice(&self.dest_chain_id.to_le_bytes());
         data.extend_from_slice(self.source_token.as_bytes());
         data.extend_from_slice(self.dest_token.as_bytes());
-        data.extend_from_slice(&self.source_amount.to_le_bytes());
-        data.extend_from_slice(&self.min_dest_amount.to_le_bytes());
+        data.extend_from_slice(&<[u8; 32]>::from(self.source_amount));
+        data.extend_from_slice(&<[u8; 32]>::from(self.min_dest_amount));
         data.extend_from_slice(&self.deadline.to_le_bytes());
-        data.extend_from_slice(&self.nonce.to_le_bytes());
+        data.extend_from_slice(&<[u8; 32]>::from(self.nonce));
         
         H256::from(keccak256(data))
     }
-
-    /// Verify the intent signature
-    pub fn verify_signature(&self) -> bool {
-        // TODO: Implement EIP-712 signature verification
-        // For now, return true if signature is present
-        !self.signature.is_empty()
-    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IntentStatus {
-    Pending,
+    Created,
+    Validated,
     Matched,
     Executing,
-    Executed,
+    Settled,
     Failed,
     Cancelled,
     Expired,
@@ -96,6 +80,12 @@ pub struct IntentExecution {
     pub execution_time: u64,
     pub source_tx_hash: H256,
     pub dest_tx_hash: H256,
+    /// Bridge fee paid in the source chain's native token, zero if same-chain
+    #[serde(default)]
+    pub bridge_fee: U256,
+    /// Dry-run of the execution calldata taken before broadcasting
+    #[serde(default)]
+    pub simulation: Option<crate::simulation::SimulationResult>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -123,6 +113,7 @@ impl Intent {
         ethers::utils::keccak256(encoded).into()
     }
     
+    /// Check if the intent has expired
     pub fn is_expired(&self) -> bool {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -132,6 +123,7 @@ impl Intent {
         self.deadline < now
     }
     
+    /// Verify the EIP-712 signature was produced by `user`
     pub fn verify_signature(&self) -> bool {
         // EIP-712 Domain Separator
         let domain_separator = self.compute_domain_separator();
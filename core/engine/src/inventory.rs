@@ -0,0 +1,291 @@
+//! Token inventory of the executor's accounts.
+//!
+//! Balances are refreshed from chain per `(chain, token)`. Executions reserve
+//! the destination amount they will deliver before going on-chain, so two
+//! intents can never both count on the same funds. Assets are grouped across
+//! chains (e.g. USDC on every chain) to produce rebalancing suggestions when
+//! one chain runs low.
+
+use crate::{EngineError, Result};
+use ethers::{
+    providers::Middleware,
+    types::{transaction::eip2718::TypedTransaction, Address, TransactionRequest, H256, U256},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+/// `Address::zero()` stands for the chain's native token
+pub const NATIVE_TOKEN: Address = Address::zero();
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TokenInventory {
+    pub balance: U256,
+    pub reserved: U256,
+    pub updated_at: u64,
+}
+
+impl TokenInventory {
+    pub fn available(&self) -> U256 {
+        self.balance.saturating_sub(self.reserved)
+    }
+}
+
+/// One asset's token address and low-water mark on a chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetDeployment {
+    pub chain_id: u64,
+    pub token: Address,
+    pub min_balance: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RebalanceSuggestion {
+    pub asset: String,
+    pub from_chain: u64,
+    pub to_chain: u64,
+    pub amount: U256,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Reservation {
+    chain_id: u64,
+    token: Address,
+    amount: U256,
+}
+
+#[derive(Default)]
+pub struct InventoryManager {
+    inventory: RwLock<HashMap<(u64, Address), TokenInventory>>,
+    reservations: RwLock<HashMap<H256, Reservation>>,
+    assets: RwLock<HashMap<String, Vec<AssetDeployment>>>,
+}
+
+impl InventoryManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn register_asset(&self, asset: &str, deployment: AssetDeployment) {
+        let mut assets = self.assets.write().await;
+        let deployments = assets.entry(asset.to_string()).or_default();
+        deployments.retain(|existing| existing.chain_id != deployment.chain_id);
+        deployments.push(deployment);
+    }
+
+    /// Tokens tracked on `chain_id`
+    pub async fn tokens_on(&self, chain_id: u64) -> Vec<Address> {
+        self.assets
+            .read()
+            .await
+            .values()
+            .flatten()
+            .filter(|deployment| deployment.chain_id == chain_id)
+            .map(|deployment| deployment.token)
+            .collect()
+    }
+
+    pub async fn set_balance(&self, chain_id: u64, token: Address, balance: U256) {
+        let mut inventory = self.inventory.write().await;
+        let entry = inventory.entry((chain_id, token)).or_default();
+        entry.balance = balance;
+        entry.updated_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+    }
+
+    /// Re-read `account`'s balance of every tracked token on `chain_id`
+    pub async fn refresh<M: Middleware>(&self, chain_id: u64, provider: &M, account: Address) -> Result<()> {
+        for token in self.tokens_on(chain_id).await {
+            let balance = fetch_balance(provider, token, account).await?;
+            self.set_balance(chain_id, token, balance).await;
+        }
+        Ok(())
+    }
+
+    pub async fn get(&self, chain_id: u64, token: Address) -> TokenInventory {
+        self.inventory.read().await.get(&(chain_id, token)).copied().unwrap_or_default()
+    }
+
+    pub async fn snapshot(&self) -> HashMap<(u64, Address), TokenInventory> {
+        self.inventory.read().await.clone()
+    }
+
+    /// Earmark `amount` of `token` on `chain_id` for an intent. Fails with
+    /// `InsufficientBalance` if it exceeds unreserved inventory. Tokens with
+    /// no known balance are not tracked and always pass.
+    pub async fn reserve(&self, intent_id: H256, chain_id: u64, token: Address, amount: U256) -> Result<()> {
+        let mut inventory = self.inventory.write().await;
+        let mut reservations = self.reservations.write().await;
+
+        if reservations.contains_key(&intent_id) {
+            return Ok(());
+        }
+
+        let Some(entry) = inventory.get_mut(&(chain_id, token)) else {
+            return Ok(());
+        };
+        if entry.available() < amount {
+            tracing::warn!(
+                "Inventory for {:?} on chain {} too low for intent {:?}: {} available, {} needed",
+                token,
+                chain_id,
+                intent_id,
+                entry.available(),
+                amount
+            );
+            return Err(EngineError::InsufficientBalance);
+        }
+
+        entry.reserved += amount;
+        reservations.insert(intent_id, Reservation { chain_id, token, amount });
+        Ok(())
+    }
+
+    /// Return an intent's reservation to available inventory
+    pub async fn release(&self, intent_id: H256) {
+        let mut inventory = self.inventory.write().await;
+        if let Some(reservation) = self.reservations.write().await.remove(&intent_id) {
+            if let Some(entry) = inventory.get_mut(&(reservation.chain_id, reservation.token)) {
+                entry.reserved = entry.reserved.saturating_sub(reservation.amount);
+            }
+        }
+    }
+
+    /// Settle an intent's reservation against the balance once the funds
+    /// have actually left the account
+    pub async fn consume(&self, intent_id: H256) {
+        let mut inventory = self.inventory.write().await;
+        if let Some(reservation) = self.reservations.write().await.remove(&intent_id) {
+            if let Some(entry) = inventory.get_mut(&(reservation.chain_id, reservation.token)) {
+                entry.reserved = entry.reserved.saturating_sub(reservation.amount);
+                entry.balance = entry.balance.saturating_sub(reservation.amount);
+            }
+        }
+    }
+
+    /// Suggest transfers from chains with surplus to chains whose available
+    /// inventory has dropped below their low-water mark
+    pub async fn rebalancing_suggestions(&self) -> Vec<RebalanceSuggestion> {
+        let assets = self.assets.read().await;
+        let inventory = self.inventory.read().await;
+        let mut suggestions = Vec::new();
+
+        for (asset, deployments) in assets.iter() {
+            let available = |deployment: &AssetDeployment| {
+                inventory
+                    .get(&(deployment.chain_id, deployment.token))
+                    .map(|entry| entry.available())
+                    .unwrap_or_default()
+            };
+
+            let mut surpluses: Vec<(u64, U256)> = deployments
+                .iter()
+                .filter_map(|deployment| {
+                    let available = available(deployment);
+                    (available > deployment.min_balance).then(|| (deployment.chain_id, available - deployment.min_balance))
+                })
+                .collect();
+            surpluses.sort_by(|a, b| b.1.cmp(&a.1));
+
+            for deployment in deployments {
+                let mut deficit = deployment.min_balance.saturating_sub(available(deployment));
+
+                for (from_chain, surplus) in surpluses.iter_mut() {
+                    if deficit.is_zero() {
+                        break;
+                    }
+                    if surplus.is_zero() {
+                        continue;
+                    }
+
+                    let amount = deficit.min(*surplus);
+                    *surplus -= amount;
+                    deficit -= amount;
+                    suggestions.push(RebalanceSuggestion {
+                        asset: asset.clone(),
+                        from_chain: *from_chain,
+                        to_chain: deployment.chain_id,
+                        amount,
+                    });
+                }
+            }
+        }
+
+        suggestions
+    }
+}
+
+async fn fetch_balance<M: Middleware>(provider: &M, token: Address, account: Address) -> Result<U256> {
+    if token == NATIVE_TOKEN {
+        return provider
+            .get_balance(account, None)
+            .await
+            .map_err(|e| EngineError::ExecutionFailed(format!("Failed to fetch balance: {}", e)));
+    }
+
+    let selector = ethers::utils::keccak256(b"balanceOf(address)");
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend(ethers::abi::encode(&[ethers::abi::Token::Address(account)]));
+
+    let call: TypedTransaction = TransactionRequest::new().to(token).data(calldata).into();
+    let output = provider
+        .call(&call, None)
+        .await
+        .map_err(|e| EngineError::ExecutionFailed(format!("Failed to fetch balance of {:?}: {}", token, e)))?;
+
+    if output.len() < 32 {
+        return Err(EngineError::ExecutionFailed(format!("Invalid balanceOf response from {:?}", token)));
+    }
+    Ok(U256::from_big_endian(&output[..32]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reservations_cap_available_inventory() {
+        let manager = InventoryManager::new();
+        let token = Address::random();
+        manager.set_balance(10, token, U256::from(1000)).await;
+
+        let first = H256::random();
+        manager.reserve(first, 10, token, U256::from(700)).await.unwrap();
+        assert!(matches!(
+            manager.reserve(H256::random(), 10, token, U256::from(400)).await,
+            Err(EngineError::InsufficientBalance)
+        ));
+
+        manager.release(first).await;
+        manager.reserve(H256::random(), 10, token, U256::from(400)).await.unwrap();
+
+        let second = H256::random();
+        manager.reserve(second, 10, token, U256::from(600)).await.unwrap();
+        manager.consume(second).await;
+        let inventory = manager.get(10, token).await;
+        assert_eq!(inventory.balance, U256::from(400));
+        assert_eq!(inventory.available(), U256::zero());
+    }
+
+    #[tokio::test]
+    async fn test_rebalancing_moves_surplus_to_low_chain() {
+        let manager = InventoryManager::new();
+        let (usdc_eth, usdc_arb) = (Address::random(), Address::random());
+        manager
+            .register_asset("USDC", AssetDeployment { chain_id: 1, token: usdc_eth, min_balance: U256::from(100) })
+            .await;
+        manager
+            .register_asset("USDC", AssetDeployment { chain_id: 42161, token: usdc_arb, min_balance: U256::from(100) })
+            .await;
+
+        manager.set_balance(1, usdc_eth, U256::from(500)).await;
+        manager.set_balance(42161, usdc_arb, U256::from(20)).await;
+
+        assert_eq!(
+            manager.rebalancing_suggestions().await,
+            vec![RebalanceSuggestion { asset: "USDC".to_string(), from_chain: 1, to_chain: 42161, amount: U256::from(80) }]
+        );
+    }
+}
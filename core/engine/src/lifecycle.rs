@@ -0,0 +1,132 @@
+//! Intent lifecycle state machine.
+//!
+//! Created → Validated → Matched → Executing → Settled, with Failed, Expired
+//! and Cancelled as the other terminal states. Transitions whose source stage
+//! is known go through [`EngineState::advance`](crate::state::EngineState::advance)
+//! and are checked at compile time through [`TransitionTo`]; the rest are
+//! checked at runtime through [`IntentStatus::can_transition_to`]. Both are
+//! generated from the same table.
+
+use crate::intent::IntentStatus;
+use ethers::types::{Address, H256};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Who triggered a lifecycle transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Causer {
+    Engine,
+    Validator,
+    Executor,
+    Solver(Address),
+    User(Address),
+    /// An operator acting through the admin API
+    Operator,
+}
+
+/// Audit entry for a single transition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionRecord {
+    pub intent_id: H256,
+    pub from: IntentStatus,
+    pub to: IntentStatus,
+    pub causer: Causer,
+    pub reason: Option<String>,
+    pub timestamp: u64,
+}
+
+impl TransitionRecord {
+    pub fn new(intent_id: H256, from: IntentStatus, to: IntentStatus, causer: Causer, reason: Option<String>) -> Self {
+        Self {
+            intent_id,
+            from,
+            to,
+            causer,
+            reason,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        }
+    }
+}
+
+/// Called after every successful transition
+pub trait TransitionHook: Send + Sync {
+    fn on_transition(&self, record: &TransitionRecord);
+}
+
+impl IntentStatus {
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            IntentStatus::Settled | IntentStatus::Failed | IntentStatus::Expired | IntentStatus::Cancelled
+        )
+    }
+}
+
+/// Typestate marker for a lifecycle stage
+pub trait LifecycleState {
+    const STATUS: IntentStatus;
+}
+
+/// Implemented for every `(from, to)` pair the state machine allows
+pub trait TransitionTo<T: LifecycleState>: LifecycleState {}
+
+macro_rules! lifecycle_states {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[derive(Debug, Clone, Copy)]
+            pub struct $name;
+
+            impl LifecycleState for $name {
+                const STATUS: IntentStatus = IntentStatus::$name;
+            }
+        )*
+    };
+}
+
+lifecycle_states!(Created, Validated, Matched, Executing, Settled, Failed, Expired, Cancelled);
+
+macro_rules! allow_transitions {
+    ($($from:ident => [$($to:ident),*]);* $(;)?) => {
+        $($(impl TransitionTo<$to> for $from {})*)*
+
+        impl IntentStatus {
+            /// Runtime mirror of the typed transitions, for callers that
+            /// only learn the current stage from state
+            pub fn can_transition_to(self, next: IntentStatus) -> bool {
+                matches!((self, next), $($((IntentStatus::$from, IntentStatus::$to))|*)|*)
+            }
+        }
+    };
+}
+
+allow_transitions! {
+    Created => [Validated, Failed, Expired, Cancelled];
+    Validated => [Matched, Failed, Expired, Cancelled];
+    Matched => [Executing, Failed, Expired, Cancelled];
+    Executing => [Settled, Failed];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runtime_table_matches_terminal_states() {
+        use IntentStatus::*;
+        let all = [Created, Validated, Matched, Executing, Settled, Failed, Expired, Cancelled];
+
+        for from in all {
+            if from.is_terminal() {
+                assert!(all.iter().all(|to| !from.can_transition_to(*to)));
+            }
+        }
+
+        assert!(Created.can_transition_to(Validated));
+        assert!(!Created.can_transition_to(Executing));
+        assert!(!Executing.can_transition_to(Cancelled));
+        assert!(Executing.can_transition_to(Failed));
+    }
+}
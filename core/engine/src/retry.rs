@@ -0,0 +1,233 @@
+//! Retry policies for execution steps and the dead-letter queue.
+//!
+//! Each on-chain step is retried according to a [`RetryPolicy`]: errors are
+//! sorted into [`ErrorClass`]es, and only the classes the policy lists are
+//! retried, with exponential backoff up to `max_attempts`. Intents that still
+//! fail land in the [`DeadLetterQueue`] for an operator to inspect.
+
+use crate::{intent::Intent, EngineError};
+use ethers::types::H256;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::RwLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    /// Nonce too low, already known, replacement underpriced
+    Nonce,
+    /// Fee too low or out of gas
+    Gas,
+    /// RPC or network failure
+    Network,
+    Timeout,
+    /// The transaction or call reverted
+    Revert,
+    /// Anything else, e.g. insufficient balance or an unsupported chain
+    Permanent,
+}
+
+impl ErrorClass {
+    pub fn of(error: &EngineError) -> Self {
+        match error {
+            EngineError::RetriesExhausted { source, .. } => return ErrorClass::of(source),
+            EngineError::InsufficientBalance
+            | EngineError::ChainNotSupported(_)
+            | EngineError::IntentExpired
+            | EngineError::InvalidIntent(_)
+            | EngineError::Rejected(_)
+            | EngineError::SignerError(_)
+            | EngineError::InvalidTransition { .. } => return ErrorClass::Permanent,
+            _ => {}
+        }
+
+        let message = error.to_string().to_lowercase();
+        if message.contains("nonce") || message.contains("already known") || message.contains("replacement") {
+            ErrorClass::Nonce
+        } else if message.contains("underpriced")
+            || message.contains("fee too low")
+            || message.contains("max fee")
+            || message.contains("out of gas")
+            || message.contains("intrinsic gas")
+        {
+            ErrorClass::Gas
+        } else if message.contains("timeout") || message.contains("timed out") {
+            ErrorClass::Timeout
+        } else if message.contains("revert") || message.contains("transaction failed") {
+            ErrorClass::Revert
+        } else if message.contains("connection")
+            || message.contains("rpc")
+            || message.contains("failed to send")
+            || message.contains("failed to get")
+            || message.contains("failed to fetch")
+            || message.contains("no healthy")
+        {
+            ErrorClass::Network
+        } else {
+            ErrorClass::Permanent
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total tries including the first
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: u32,
+    pub retry_on: HashSet<ErrorClass>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(60),
+            multiplier: 2,
+            retry_on: [ErrorClass::Nonce, ErrorClass::Gas, ErrorClass::Network, ErrorClass::Timeout]
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Whether a step that failed with `error` on try `attempt` (1-based)
+    /// should be tried again
+    pub fn should_retry(&self, attempt: u32, error: &EngineError) -> bool {
+        attempt < self.max_attempts && self.retry_on.contains(&ErrorClass::of(error))
+    }
+
+    /// Delay before try `attempt + 1`
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// An intent whose execution failed for good
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub intent_id: H256,
+    pub intent: Intent,
+    /// Step that failed last, e.g. `lock` or `bridge`
+    pub step: String,
+    pub attempts: u32,
+    pub error: String,
+    pub error_class: ErrorClass,
+    pub failed_at: u64,
+}
+
+impl DeadLetter {
+    pub fn new(intent_id: H256, intent: Intent, error: &EngineError) -> Self {
+        let (step, attempts) = match error {
+            EngineError::RetriesExhausted { step, attempts, .. } => (step.clone(), *attempts),
+            _ => ("execute".to_string(), 1),
+        };
+
+        Self {
+            intent_id,
+            intent,
+            step,
+            attempts,
+            error: error.to_string(),
+            error_class: ErrorClass::of(error),
+            failed_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DeadLetterQueue {
+    entries: RwLock<HashMap<H256, DeadLetter>>,
+}
+
+impl DeadLetterQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn push(&self, entry: DeadLetter) {
+        tracing::error!(
+            "Intent {:?} dead-lettered after {} attempts at {}: {}",
+            entry.intent_id,
+            entry.attempts,
+            entry.step,
+            entry.error
+        );
+        self.entries.write().await.insert(entry.intent_id, entry);
+    }
+
+    /// Entries oldest first
+    pub async fn list(&self) -> Vec<DeadLetter> {
+        let mut entries: Vec<DeadLetter> = self.entries.read().await.values().cloned().collect();
+        entries.sort_by_key(|entry| entry.failed_at);
+        entries
+    }
+
+    pub async fn get(&self, intent_id: H256) -> Option<DeadLetter> {
+        self.entries.read().await.get(&intent_id).cloned()
+    }
+
+    /// Remove an entry once it has been dealt with
+    pub async fn remove(&self, intent_id: H256) -> Option<DeadLetter> {
+        self.entries.write().await.remove(&intent_id)
+    }
+
+    pub async fn len(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.entries.read().await.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classifies_errors() {
+        let class = |message: &str| ErrorClass::of(&EngineError::ExecutionFailed(message.to_string()));
+        assert_eq!(class("Failed to send tx: nonce too low"), ErrorClass::Nonce);
+        assert_eq!(class("Failed to send tx: transaction underpriced"), ErrorClass::Gas);
+        assert_eq!(class("Execution timeout exceeded"), ErrorClass::Timeout);
+        assert_eq!(class("Lock transaction failed"), ErrorClass::Revert);
+        assert_eq!(class("Failed to get receipt: connection refused"), ErrorClass::Network);
+        assert_eq!(ErrorClass::of(&EngineError::InsufficientBalance), ErrorClass::Permanent);
+
+        let exhausted = EngineError::RetriesExhausted {
+            step: "lock".to_string(),
+            attempts: 4,
+            source: Box::new(EngineError::ExecutionFailed("nonce too low".to_string())),
+        };
+        assert_eq!(ErrorClass::of(&exhausted), ErrorClass::Nonce);
+
+        let letter = DeadLetter::new(H256::zero(), Intent::default(), &exhausted);
+        assert_eq!((letter.step.as_str(), letter.attempts), ("lock", 4));
+    }
+
+    #[test]
+    fn test_policy_limits_attempts_and_backs_off() {
+        let policy = RetryPolicy::default();
+        let nonce = EngineError::ExecutionFailed("nonce too low".to_string());
+        let revert = EngineError::ExecutionFailed("execution reverted".to_string());
+
+        assert!(policy.should_retry(1, &nonce));
+        assert!(!policy.should_retry(policy.max_attempts, &nonce));
+        assert!(!policy.should_retry(1, &revert));
+
+        assert_eq!(policy.backoff(1), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(8));
+        assert_eq!(policy.backoff(20), policy.max_backoff);
+    }
+}
@@ -0,0 +1,323 @@
+//! Scheduling of queued intents.
+//!
+//! Intents wait in an [`IntentScheduler`] until an execution slot frees up;
+//! the next one out is whichever scores highest under the active
+//! [`SchedulingPolicy`]. Scores are computed at dequeue time so deadline
+//! pressure and waiting time are always current.
+
+use crate::intent::Intent;
+use ethers::types::{Address, H256, U256};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::{Notify, RwLock};
+
+/// Caller-supplied scheduling inputs for an intent
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScheduleHints {
+    /// Expected solver profit in wei of the source token
+    pub expected_profit: U256,
+    /// User tier; higher tiers are served first. Falls back to the tier
+    /// registered with [`IntentScheduler::set_user_tier`] when zero.
+    pub user_tier: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct QueuedIntent {
+    pub intent_id: H256,
+    pub intent: Intent,
+    pub hints: ScheduleHints,
+    pub enqueued_at: Instant,
+}
+
+impl QueuedIntent {
+    pub fn waited(&self) -> Duration {
+        self.enqueued_at.elapsed()
+    }
+
+    pub fn seconds_to_deadline(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        self.intent.deadline.saturating_sub(now)
+    }
+}
+
+/// Orders queued intents; the highest score is dispatched first
+pub trait SchedulingPolicy: Send + Sync {
+    fn score(&self, entry: &QueuedIntent) -> f64;
+}
+
+/// Weighted blend of deadline urgency, profit and user tier, plus an aging
+/// term so low-priority intents cannot starve
+#[derive(Debug, Clone)]
+pub struct WeightedPolicy {
+    pub deadline_weight: f64,
+    pub profit_weight: f64,
+    pub tier_weight: f64,
+    /// Score gained per second spent waiting
+    pub aging_per_sec: f64,
+}
+
+impl Default for WeightedPolicy {
+    fn default() -> Self {
+        Self {
+            deadline_weight: 100.0,
+            profit_weight: 10.0,
+            tier_weight: 5.0,
+            aging_per_sec: 1.0,
+        }
+    }
+}
+
+impl SchedulingPolicy for WeightedPolicy {
+    fn score(&self, entry: &QueuedIntent) -> f64 {
+        // 1.0 at the deadline, decaying towards 0 over the following hour
+        let urgency = 3600.0 / (3600.0 + entry.seconds_to_deadline() as f64);
+        // Log scale so a single whale intent can't dominate the queue
+        let profit = (entry.hints.expected_profit.min(U256::from(u128::MAX)).as_u128() as f64 + 1.0).log10();
+
+        self.deadline_weight * urgency
+            + self.profit_weight * profit
+            + self.tier_weight * entry.hints.user_tier as f64
+            + self.aging_per_sec * entry.waited().as_secs_f64()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    /// Intents waiting longer than this are dispatched ahead of any score
+    pub max_wait: Duration,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            max_wait: Duration::from_secs(120),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchedulerMetrics {
+    pub queue_depth: usize,
+    pub dispatched: u64,
+    pub starvation_promotions: u64,
+    pub avg_wait_ms: u64,
+    pub max_wait_ms: u64,
+    pub oldest_queued_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct WaitStats {
+    dispatched: u64,
+    starvation_promotions: u64,
+    total_wait_ms: u64,
+    max_wait_ms: u64,
+}
+
+pub struct IntentScheduler {
+    config: SchedulerConfig,
+    policy: RwLock<Arc<dyn SchedulingPolicy>>,
+    queue: RwLock<Vec<QueuedIntent>>,
+    user_tiers: RwLock<HashMap<Address, u8>>,
+    stats: RwLock<WaitStats>,
+    notify: Notify,
+}
+
+impl Default for IntentScheduler {
+    fn default() -> Self {
+        Self::new(SchedulerConfig::default(), Arc::new(WeightedPolicy::default()))
+    }
+}
+
+impl IntentScheduler {
+    pub fn new(config: SchedulerConfig, policy: Arc<dyn SchedulingPolicy>) -> Self {
+        Self {
+            config,
+            policy: RwLock::new(policy),
+            queue: RwLock::new(Vec::new()),
+            user_tiers: RwLock::new(HashMap::new()),
+            stats: RwLock::new(WaitStats::default()),
+            notify: Notify::new(),
+        }
+    }
+
+    pub async fn set_policy(&self, policy: Arc<dyn SchedulingPolicy>) {
+        *self.policy.write().await = policy;
+    }
+
+    pub async fn set_user_tier(&self, user: Address, tier: u8) {
+        self.user_tiers.write().await.insert(user, tier);
+    }
+
+    pub async fn push(&self, intent_id: H256, intent: Intent, mut hints: ScheduleHints) {
+        if hints.user_tier == 0 {
+            hints.user_tier = self.user_tiers.read().await.get(&intent.user).copied().unwrap_or(0);
+        }
+
+        self.queue.write().await.push(QueuedIntent {
+            intent_id,
+            intent,
+            hints,
+            enqueued_at: Instant::now(),
+        });
+        self.notify.notify_one();
+    }
+
+    /// Remove and return the next intent to execute, if any
+    pub async fn try_pop(&self) -> Option<QueuedIntent> {
+        let mut queue = self.queue.write().await;
+        if queue.is_empty() {
+            return None;
+        }
+        // Taken up front so nothing awaits between removing the entry and
+        // returning it; a cancelled pop must not lose an intent
+        let mut stats = self.stats.write().await;
+        let policy = self.policy.read().await.clone();
+
+        // Starvation guard: the longest-waiting intent past max_wait goes first
+        let starved = queue
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.waited() >= self.config.max_wait)
+            .max_by_key(|(_, entry)| entry.waited())
+            .map(|(index, _)| index);
+
+        let index = match starved {
+            Some(index) => {
+                stats.starvation_promotions += 1;
+                index
+            }
+            None => {
+                queue
+                    .iter()
+                    .enumerate()
+                    .map(|(index, entry)| (index, policy.score(entry)))
+                    .max_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(index, _)| index)?
+            }
+        };
+
+        let entry = queue.swap_remove(index);
+        let waited_ms = entry.waited().as_millis() as u64;
+
+        stats.dispatched += 1;
+        stats.total_wait_ms += waited_ms;
+        stats.max_wait_ms = stats.max_wait_ms.max(waited_ms);
+
+        Some(entry)
+    }
+
+    /// Wait until an intent is available and return it
+    pub async fn pop(&self) -> QueuedIntent {
+        loop {
+            let notified = self.notify.notified();
+            if let Some(entry) = self.try_pop().await {
+                return entry;
+            }
+            notified.await;
+        }
+    }
+
+    pub async fn contains(&self, intent_id: H256) -> bool {
+        self.queue.read().await.iter().any(|entry| entry.intent_id == intent_id)
+    }
+
+    /// Take one intent out of the queue without dispatching it
+    pub async fn remove(&self, intent_id: H256) -> Option<QueuedIntent> {
+        let mut queue = self.queue.write().await;
+        let index = queue.iter().position(|entry| entry.intent_id == intent_id)?;
+        Some(queue.swap_remove(index))
+    }
+
+    /// Remove everything still queued, e.g. on shutdown
+    pub async fn drain(&self) -> Vec<QueuedIntent> {
+        std::mem::take(&mut *self.queue.write().await)
+    }
+
+    pub async fn metrics(&self) -> SchedulerMetrics {
+        let queue = self.queue.read().await;
+        let stats = self.stats.read().await;
+
+        SchedulerMetrics {
+            queue_depth: queue.len(),
+            dispatched: stats.dispatched,
+            starvation_promotions: stats.starvation_promotions,
+            avg_wait_ms: stats.total_wait_ms.checked_div(stats.dispatched).unwrap_or(0),
+            max_wait_ms: stats.max_wait_ms,
+            oldest_queued_ms: queue.iter().map(|entry| entry.waited().as_millis() as u64).max().unwrap_or(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent_due_in(secs: u64) -> Intent {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        Intent {
+            deadline: now + secs,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_orders_by_deadline_profit_and_tier() {
+        let scheduler = IntentScheduler::default();
+        let vip = Address::random();
+        scheduler.set_user_tier(vip, 10).await;
+
+        scheduler.push(H256::from_low_u64_be(1), intent_due_in(7200), ScheduleHints::default()).await;
+        scheduler.push(H256::from_low_u64_be(2), intent_due_in(30), ScheduleHints::default()).await;
+        scheduler
+            .push(
+                H256::from_low_u64_be(3),
+                Intent { user: vip, ..intent_due_in(7200) },
+                ScheduleHints::default(),
+            )
+            .await;
+
+        assert_eq!(scheduler.pop().await.intent_id, H256::from_low_u64_be(2));
+        assert_eq!(scheduler.pop().await.intent_id, H256::from_low_u64_be(3));
+        assert_eq!(scheduler.pop().await.intent_id, H256::from_low_u64_be(1));
+
+        let metrics = scheduler.metrics().await;
+        assert_eq!(metrics.queue_depth, 0);
+        assert_eq!(metrics.dispatched, 3);
+    }
+
+    #[tokio::test]
+    async fn test_remove_queued_intent() {
+        let scheduler = IntentScheduler::default();
+        scheduler.push(H256::from_low_u64_be(1), intent_due_in(60), ScheduleHints::default()).await;
+        scheduler.push(H256::from_low_u64_be(2), intent_due_in(60), ScheduleHints::default()).await;
+
+        assert_eq!(scheduler.remove(H256::from_low_u64_be(1)).await.unwrap().intent_id, H256::from_low_u64_be(1));
+        assert!(scheduler.remove(H256::from_low_u64_be(1)).await.is_none());
+        assert_eq!(scheduler.metrics().await.queue_depth, 1);
+    }
+
+    #[tokio::test]
+    async fn test_starved_intent_is_promoted() {
+        let scheduler = IntentScheduler::new(
+            SchedulerConfig { max_wait: Duration::from_millis(10) },
+            Arc::new(WeightedPolicy { aging_per_sec: 0.0, ..Default::default() }),
+        );
+
+        scheduler.push(H256::from_low_u64_be(1), intent_due_in(7200), ScheduleHints::default()).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        scheduler.push(H256::from_low_u64_be(2), intent_due_in(10), ScheduleHints::default()).await;
+
+        assert_eq!(scheduler.pop().await.intent_id, H256::from_low_u64_be(1));
+        assert_eq!(scheduler.metrics().await.starvation_promotions, 1);
+    }
+}
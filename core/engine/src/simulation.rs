@@ -0,0 +1,196 @@
+//! Pre-execution simulation.
+//!
+//! Calldata is dry-run with `eth_call` against the pending block before it is
+//! broadcast. ERC20 balances and allowances the transaction depends on are
+//! injected through state overrides, so a simulation does not require the
+//! user's approval to have landed yet.
+
+use crate::{EngineError, Result};
+use ethers::{
+    abi::{self, ParamType, Token},
+    providers::{call_raw::RawCall, spoof, Http, Provider, RpcError},
+    types::{transaction::eip2718::TypedTransaction, Address, Bytes, TransactionRequest, H256, U256},
+    utils::keccak256,
+};
+use serde::{Deserialize, Serialize};
+
+/// `Error(string)` selector used by Solidity `require`/`revert`
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulationResult {
+    pub success: bool,
+    pub predicted_dest_amount: Option<U256>,
+    pub gas_estimate: Option<U256>,
+    pub revert_reason: Option<String>,
+    pub block_number: u64,
+}
+
+/// Storage layout of the token being overridden. Slots default to the
+/// OpenZeppelin ERC20 layout (`_balances` at 0, `_allowances` at 1).
+#[derive(Debug, Clone, Copy)]
+pub struct TokenSlots {
+    pub balance_slot: u64,
+    pub allowance_slot: u64,
+}
+
+impl Default for TokenSlots {
+    fn default() -> Self {
+        Self { balance_slot: 0, allowance_slot: 1 }
+    }
+}
+
+/// A balance and approval the simulated call expects to exist
+#[derive(Debug, Clone, Copy)]
+pub struct ApprovalOverride {
+    pub token: Address,
+    pub owner: Address,
+    pub spender: Address,
+    pub amount: U256,
+    pub slots: TokenSlots,
+}
+
+/// Outcome of a single simulated call
+#[derive(Debug, Clone)]
+pub struct CallOutcome {
+    pub output: Bytes,
+    pub revert_reason: Option<String>,
+}
+
+impl CallOutcome {
+    pub fn reverted(&self) -> bool {
+        self.revert_reason.is_some()
+    }
+}
+
+pub fn override_state(approvals: &[ApprovalOverride]) -> spoof::State {
+    let mut state = spoof::state();
+
+    for approval in approvals {
+        let amount = u256_to_h256(approval.amount);
+        state
+            .account(approval.token)
+            .store(mapping_slot(approval.owner, H256::from_low_u64_be(approval.slots.balance_slot)), amount)
+            .store(
+                mapping_slot(
+                    approval.spender,
+                    mapping_slot(approval.owner, H256::from_low_u64_be(approval.slots.allowance_slot)),
+                ),
+                amount,
+            );
+    }
+
+    state
+}
+
+/// Big-endian storage word holding `value`
+fn u256_to_h256(value: U256) -> H256 {
+    H256::from(<[u8; 32]>::from(value))
+}
+
+/// Storage slot of `mapping(address => _)[key]` declared at `slot`
+fn mapping_slot(key: Address, slot: H256) -> H256 {
+    let encoded = abi::encode(&[Token::Address(key), Token::FixedBytes(slot.as_bytes().to_vec())]);
+    H256::from(keccak256(encoded))
+}
+
+/// `eth_call` the transaction with the given overrides. A revert is returned
+/// as a decoded reason rather than an error; only transport failures error.
+pub async fn simulate_call(
+    provider: &Provider<Http>,
+    from: Address,
+    to: Address,
+    calldata: Bytes,
+    value: U256,
+    approvals: &[ApprovalOverride],
+) -> Result<CallOutcome> {
+    let tx: TypedTransaction = TransactionRequest::new()
+        .from(from)
+        .to(to)
+        .data(calldata)
+        .value(value)
+        .into();
+    let state = override_state(approvals);
+
+    match provider.call_raw(&tx).state(&state).await {
+        Ok(output) => Ok(CallOutcome { output, revert_reason: None }),
+        Err(e) => match e.as_error_response().and_then(|response| response.as_revert_data()) {
+            Some(data) => Ok(CallOutcome {
+                output: data.clone(),
+                revert_reason: Some(decode_revert_reason(&data)),
+            }),
+            None if e.to_string().contains("revert") => Ok(CallOutcome {
+                output: Bytes::default(),
+                revert_reason: Some(e.to_string()),
+            }),
+            None => Err(EngineError::SimulationFailed(format!("eth_call failed: {}", e))),
+        },
+    }
+}
+
+pub fn decode_revert_reason(data: &[u8]) -> String {
+    if data.len() >= 4 && data[..4] == ERROR_STRING_SELECTOR {
+        if let Ok(tokens) = abi::decode(&[ParamType::String], &data[4..]) {
+            if let Some(Token::String(reason)) = tokens.into_iter().next() {
+                return reason;
+            }
+        }
+    }
+
+    if data.is_empty() {
+        "execution reverted".to_string()
+    } else {
+        format!("execution reverted: 0x{}", hex::encode(data))
+    }
+}
+
+/// Decode a single `uint256` return value
+pub fn decode_uint(output: &[u8]) -> Option<U256> {
+    (output.len() >= 32).then(|| U256::from_big_endian(&output[..32]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_revert_reason() {
+        let mut data = ERROR_STRING_SELECTOR.to_vec();
+        data.extend(abi::encode(&[Token::String("Slippage exceeded".to_string())]));
+        assert_eq!(decode_revert_reason(&data), "Slippage exceeded");
+
+        assert_eq!(decode_revert_reason(&[]), "execution reverted");
+        assert_eq!(decode_revert_reason(&[0xde, 0xad, 0xbe, 0xef]), "execution reverted: 0xdeadbeef");
+    }
+
+    #[test]
+    fn test_mapping_slot_matches_solidity_layout() {
+        // keccak256(abi.encode(address(0), uint256(0)))
+        let slot = mapping_slot(Address::zero(), H256::zero());
+        assert_eq!(
+            hex::encode(slot.as_bytes()),
+            "ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5"
+        );
+    }
+
+    #[test]
+    fn test_override_state_stores_big_endian_amounts() {
+        assert_eq!(u256_to_h256(U256::from(100)), H256::from_low_u64_be(100));
+
+        let approval = ApprovalOverride {
+            token: Address::repeat_byte(0x11),
+            owner: Address::repeat_byte(0x22),
+            spender: Address::repeat_byte(0x33),
+            amount: U256::from(100),
+            slots: TokenSlots::default(),
+        };
+        let state = serde_json::to_value(override_state(&[approval])).unwrap();
+        let storage = &state[format!("{:?}", approval.token)]["stateDiff"];
+
+        let balance_slot = mapping_slot(approval.owner, H256::zero());
+        let allowance_slot = mapping_slot(approval.spender, mapping_slot(approval.owner, H256::from_low_u64_be(1)));
+        let expected = serde_json::to_value(H256::from_low_u64_be(100)).unwrap();
+        assert_eq!(storage[format!("{:?}", balance_slot)], expected);
+        assert_eq!(storage[format!("{:?}", allowance_slot)], expected);
+    }
+}
@@ -1,26 +1,92 @@
-use crate::{intent::*, Result, EngineError};
+use crate::{
+    audit::{AuditEventKind, AuditLog},
+    intent::*,
+    lifecycle::{self, Causer, LifecycleState, TransitionHook, TransitionRecord, TransitionTo},
+    simulation::SimulationResult,
+    store::{IntentRecord, MemoryStore, StateStore},
+    Result, EngineError,
+};
 use ethers::types::{H256, U256};
-use std::collections::HashMap;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+};
 use tokio::sync::RwLock;
 
+/// In-memory cache of engine state, written through to a `StateStore`
 pub struct EngineState {
-    intents: RwLock<HashMap<H256, IntentState>>,
+    intents: RwLock<HashMap<H256, IntentRecord>>,
     executions: RwLock<HashMap<H256, IntentExecution>>,
-}
-
-struct IntentState {
-    intent: Intent,
-    status: IntentStatus,
-    created_at: u64,
-    updated_at: u64,
+    transitions: RwLock<HashMap<H256, Vec<TransitionRecord>>>,
+    simulations: RwLock<HashMap<H256, SimulationResult>>,
+    hooks: RwLock<Vec<Arc<dyn TransitionHook>>>,
+    store: Arc<dyn StateStore>,
+    audit: Arc<AuditLog>,
 }
 
 impl EngineState {
     pub fn new() -> Self {
+        Self::with_store(Arc::new(MemoryStore::new()))
+    }
+
+    pub fn with_store(store: Arc<dyn StateStore>) -> Self {
         Self {
             intents: RwLock::new(HashMap::new()),
             executions: RwLock::new(HashMap::new()),
+            transitions: RwLock::new(HashMap::new()),
+            simulations: RwLock::new(HashMap::new()),
+            hooks: RwLock::new(Vec::new()),
+            store,
+            audit: Arc::new(AuditLog::default()),
+        }
+    }
+
+    /// Record decisions to `audit` instead of the in-memory default
+    pub fn with_audit_log(mut self, audit: Arc<AuditLog>) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    pub fn audit(&self) -> Arc<AuditLog> {
+        self.audit.clone()
+    }
+
+    /// Reload intents that were in flight when the engine last stopped.
+    /// Returns the recovered records so the caller can re-queue them.
+    pub async fn recover(&self) -> Result<Vec<IntentRecord>> {
+        let records = self.store.load_in_flight().await?;
+
+        let mut intents = self.intents.write().await;
+        for record in &records {
+            intents.insert(record.intent_id, record.clone());
         }
+
+        tracing::info!("Recovered {} in-flight intents from state store", records.len());
+        Ok(records)
+    }
+    
+    /// Status of a checkpointed intent, re-inserting it with `status` if the
+    /// store does not know it (e.g. the engine runs on a fresh `MemoryStore`)
+    pub async fn restore_intent(&self, intent_id: H256, intent: Intent, status: IntentStatus) -> Result<IntentStatus> {
+        if let Some(record) = self.intents.read().await.get(&intent_id) {
+            return Ok(record.status);
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let record = IntentRecord {
+            intent_id,
+            intent,
+            status,
+            created_at: now,
+            updated_at: now,
+        };
+        self.store.save_intent(&record).await?;
+
+        // A concurrent restore of the same checkpoint may have won the race
+        Ok(self.intents.write().await.entry(intent_id).or_insert(record).status)
     }
     
     pub async fn add_intent(&self, intent: Intent) -> Result<H256> {
@@ -30,20 +96,26 @@ impl EngineState {
             .unwrap()
             .as_secs();
         
-        let mut intents = self.intents.write().await;
-        
-        if intents.contains_key(&intent_id) {
+        if self.intents.read().await.contains_key(&intent_id) {
             return Err(EngineError::InvalidIntent("Intent already exists".to_string()));
         }
         
-        intents.insert(intent_id, IntentState {
+        let record = IntentRecord {
+            intent_id,
             intent,
-            status: IntentStatus::Pending,
+            status: IntentStatus::Created,
             created_at: now,
             updated_at: now,
-        });
+        };
+        self.store.save_intent(&record).await?;
         
-        Ok(intent_id)
+        match self.intents.write().await.entry(intent_id) {
+            Entry::Occupied(_) => Err(EngineError::InvalidIntent("Intent already exists".to_string())),
+            Entry::Vacant(entry) => {
+                entry.insert(record);
+                Ok(intent_id)
+            }
+        }
     }
     
     pub async fn get_intent_status(&self, intent_id: H256) -> Result<IntentStatus> {
@@ -55,60 +127,173 @@ impl EngineState {
     }
     
     pub async fn update_intent_status(&self, intent_id: H256, status: IntentStatus) -> Result<()> {
-        let mut intents = self.intents.write().await;
+        self.transition(intent_id, status, Causer::Engine, None).await.map(|_| ())
+    }
+    
+    /// Move an intent from stage `S` to stage `T`. The pair is checked
+    /// against the lifecycle at compile time; the intent must currently be
+    /// in `S`, otherwise the transition is rejected like any other.
+    pub async fn advance<S, T>(&self, intent_id: H256, causer: Causer, reason: Option<String>) -> Result<TransitionRecord>
+    where
+        S: TransitionTo<T>,
+        T: LifecycleState,
+    {
+        self.apply_transition(intent_id, Some(S::STATUS), T::STATUS, causer, reason).await
+    }
+    
+    /// Move an intent to `to` from whatever stage it is in, rejecting
+    /// transitions the lifecycle does not allow. Prefer [`Self::advance`]
+    /// when the current stage is known.
+    pub async fn transition(
+        &self,
+        intent_id: H256,
+        to: IntentStatus,
+        causer: Causer,
+        reason: Option<String>,
+    ) -> Result<TransitionRecord> {
+        self.apply_transition(intent_id, None, to, causer, reason).await
+    }
+    
+    /// The transition is persisted, appended to the intent's transition
+    /// history and audit log, and passed to every registered hook.
+    async fn apply_transition(
+        &self,
+        intent_id: H256,
+        expected: Option<IntentStatus>,
+        to: IntentStatus,
+        causer: Causer,
+        reason: Option<String>,
+    ) -> Result<TransitionRecord> {
+        let record = {
+            let intents = self.intents.read().await;
+            
+            let state = intents.get(&intent_id)
+                .ok_or_else(|| EngineError::InvalidIntent("Intent not found".to_string()))?;
+            
+            let in_expected_stage = expected.map_or(true, |from| from == state.status);
+            if !in_expected_stage || !state.status.can_transition_to(to) {
+                return Err(EngineError::InvalidTransition { from: state.status, to });
+            }
+            
+            TransitionRecord::new(intent_id, state.status, to, causer, reason)
+        };
         
-        let state = intents.get_mut(&intent_id)
-            .ok_or_else(|| EngineError::InvalidIntent("Intent not found".to_string()))?;
+        // The store is written without holding the intents lock, so another
+        // transition may have moved the intent on in the meantime
+        self.store.update_status(intent_id, to, record.timestamp).await?;
+        let raced = {
+            let mut intents = self.intents.write().await;
+            let state = intents.get_mut(&intent_id)
+                .ok_or_else(|| EngineError::InvalidIntent("Intent not found".to_string()))?;
+            
+            if state.status == record.from {
+                state.status = to;
+                state.updated_at = record.timestamp;
+                None
+            } else {
+                Some((state.status, state.updated_at))
+            }
+        };
+        if let Some((current, updated_at)) = raced {
+            self.store.update_status(intent_id, current, updated_at).await?;
+            return Err(EngineError::InvalidTransition { from: current, to });
+        }
         
-        state.status = status;
-        state.updated_at = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        self.transitions.write().await
+            .entry(intent_id)
+            .or_default()
+            .push(record.clone());
         
-        Ok(())
+        self.audit.record(intent_id, AuditEventKind::StatusChanged {
+            from: record.from,
+            to: record.to,
+            causer: record.causer,
+            reason: record.reason.clone(),
+        }).await;
+        
+        for hook in self.hooks.read().await.iter() {
+            hook.on_transition(&record);
+        }
+        
+        Ok(record)
+    }
+    
+    pub async fn add_transition_hook(&self, hook: Arc<dyn TransitionHook>) {
+        self.hooks.write().await.push(hook);
     }
     
-    pub async fn add_execution(&self, execution: IntentExecution) -> Result<()> {
+    pub async fn get_transition_history(&self, intent_id: H256) -> Vec<TransitionRecord> {
+        self.transitions.read().await
+            .get(&intent_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+    
+    pub async fn add_execution(&self, mut execution: IntentExecution) -> Result<()> {
+        if execution.simulation.is_none() {
+            execution.simulation = self.simulations.read().await.get(&execution.intent_id).cloned();
+        }
+        
+        self.store.save_execution(&execution).await?;
         let mut executions = self.executions.write().await;
         executions.insert(execution.intent_id, execution);
         Ok(())
     }
     
-    pub async fn complete_intent(&self, intent_id: H256, dest_amount: U256) -> Result<()> {
-        self.update_intent_status(intent_id, IntentStatus::Executed).await?;
+    /// Record the pre-execution simulation for an intent. It is attached to
+    /// the intent's execution record now or once one is added.
+    pub async fn record_simulation(&self, intent_id: H256, simulation: SimulationResult) -> Result<()> {
+        self.audit.record(intent_id, AuditEventKind::Simulated {
+            success: simulation.success,
+            predicted_dest_amount: simulation.predicted_dest_amount,
+            revert_reason: simulation.revert_reason.clone(),
+        }).await;
         
-        let mut executions = self.executions.write().await;
-        if let Some(execution) = executions.get_mut(&intent_id) {
-            execution.status = ExecutionStatus::Completed;
-            execution.dest_amount = Some(dest_amount);
-            execution.executed_at = Some(
-                std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs()
-            );
+        self.simulations.write().await.insert(intent_id, simulation.clone());
+        
+        let execution = self.executions.write().await
+            .get_mut(&intent_id)
+            .map(|execution| {
+                execution.simulation = Some(simulation);
+                execution.clone()
+            });
+        if let Some(execution) = execution {
+            self.store.save_execution(&execution).await?;
         }
         
         Ok(())
     }
     
-    pub async fn fail_intent(&self, intent_id: H256) -> Result<()> {
-        self.update_intent_status(intent_id, IntentStatus::Failed).await?;
+    pub async fn get_simulation(&self, intent_id: H256) -> Option<SimulationResult> {
+        self.simulations.read().await.get(&intent_id).cloned()
+    }
+    
+    pub async fn complete_intent(&self, intent_id: H256, dest_amount: U256) -> Result<()> {
+        self.advance::<lifecycle::Executing, lifecycle::Settled>(intent_id, Causer::Executor, None).await?;
         
-        let mut executions = self.executions.write().await;
-        if let Some(execution) = executions.get_mut(&intent_id) {
-            execution.status = ExecutionStatus::Failed;
+        let execution = self.executions.write().await
+            .get_mut(&intent_id)
+            .map(|execution| {
+                execution.dest_amount = dest_amount;
+                execution.clone()
+            });
+        if let Some(execution) = execution {
+            self.store.save_execution(&execution).await?;
         }
         
         Ok(())
     }
     
+    pub async fn fail_intent(&self, intent_id: H256) -> Result<()> {
+        self.transition(intent_id, IntentStatus::Failed, Causer::Executor, None).await?;
+        Ok(())
+    }
+    
     pub async fn get_pending_intents(&self) -> Vec<(H256, Intent)> {
         let intents = self.intents.read().await;
         
         intents.iter()
-            .filter(|(_, state)| state.status == IntentStatus::Pending)
+            .filter(|(_, state)| state.status == IntentStatus::Validated)
             .map(|(id, state)| (*id, state.intent.clone()))
             .collect()
     }
@@ -0,0 +1,361 @@
+use crate::{intent::*, EngineError, Result};
+use async_trait::async_trait;
+use ethers::types::H256;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+/// Persisted view of an intent tracked by the engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentRecord {
+    pub intent_id: H256,
+    pub intent: Intent,
+    pub status: IntentStatus,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+impl IntentRecord {
+    /// Intents that were accepted but had not reached a terminal state
+    pub fn is_in_flight(&self) -> bool {
+        matches!(
+            self.status,
+            IntentStatus::Created | IntentStatus::Validated | IntentStatus::Matched | IntentStatus::Executing
+        )
+    }
+}
+
+/// Durable backend for engine state. `EngineState` writes through to the
+/// store on every mutation and reloads in-flight intents from it on startup.
+#[async_trait]
+pub trait StateStore: Send + Sync {
+    async fn save_intent(&self, record: &IntentRecord) -> Result<()>;
+
+    async fn update_status(&self, intent_id: H256, status: IntentStatus, updated_at: u64) -> Result<()>;
+
+    async fn save_execution(&self, execution: &IntentExecution) -> Result<()>;
+
+    async fn load_intent(&self, intent_id: H256) -> Result<Option<IntentRecord>>;
+
+    async fn load_in_flight(&self) -> Result<Vec<IntentRecord>>;
+}
+
+/// Non-durable store used when no backend is configured
+#[derive(Default)]
+pub struct MemoryStore {
+    intents: RwLock<HashMap<H256, IntentRecord>>,
+    executions: RwLock<HashMap<H256, IntentExecution>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl StateStore for MemoryStore {
+    async fn save_intent(&self, record: &IntentRecord) -> Result<()> {
+        self.intents.write().await.insert(record.intent_id, record.clone());
+        Ok(())
+    }
+
+    async fn update_status(&self, intent_id: H256, status: IntentStatus, updated_at: u64) -> Result<()> {
+        let mut intents = self.intents.write().await;
+        let record = intents.get_mut(&intent_id)
+            .ok_or_else(|| EngineError::StorageError(format!("Intent {:?} not persisted", intent_id)))?;
+        record.status = status;
+        record.updated_at = updated_at;
+        Ok(())
+    }
+
+    async fn save_execution(&self, execution: &IntentExecution) -> Result<()> {
+        self.executions.write().await.insert(execution.intent_id, execution.clone());
+        Ok(())
+    }
+
+    async fn load_intent(&self, intent_id: H256) -> Result<Option<IntentRecord>> {
+        Ok(self.intents.read().await.get(&intent_id).cloned())
+    }
+
+    async fn load_in_flight(&self) -> Result<Vec<IntentRecord>> {
+        Ok(self.intents.read().await
+            .values()
+            .filter(|record| record.is_in_flight())
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(feature = "sled-store")]
+pub use self::sled_store::SledStore;
+
+#[cfg(feature = "sled-store")]
+mod sled_store {
+    use super::*;
+
+    /// Embedded store for single-node deployments
+    pub struct SledStore {
+        intents: sled::Tree,
+        executions: sled::Tree,
+    }
+
+    impl SledStore {
+        pub fn open(path: &str) -> Result<Self> {
+            let db = sled::open(path)
+                .map_err(|e| EngineError::StorageError(format!("Failed to open sled db: {}", e)))?;
+            let intents = db.open_tree("intents")
+                .map_err(|e| EngineError::StorageError(e.to_string()))?;
+            let executions = db.open_tree("executions")
+                .map_err(|e| EngineError::StorageError(e.to_string()))?;
+
+            Ok(Self { intents, executions })
+        }
+
+        /// Write and flush on the blocking pool so disk I/O doesn't stall
+        /// the runtime's worker threads
+        async fn put<T: Serialize>(tree: &sled::Tree, key: H256, value: &T) -> Result<()> {
+            let bytes = serde_json::to_vec(value)
+                .map_err(|e| EngineError::StorageError(e.to_string()))?;
+            let tree = tree.clone();
+            tokio::task::spawn_blocking(move || {
+                tree.insert(key.as_bytes(), bytes)?;
+                tree.flush()?;
+                Ok::<_, sled::Error>(())
+            })
+            .await
+            .map_err(|e| EngineError::StorageError(format!("Sled write task failed: {}", e)))?
+            .map_err(|e| EngineError::StorageError(e.to_string()))
+        }
+
+        fn decode(bytes: &[u8]) -> Result<IntentRecord> {
+            serde_json::from_slice(bytes).map_err(|e| EngineError::StorageError(e.to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl StateStore for SledStore {
+        async fn save_intent(&self, record: &IntentRecord) -> Result<()> {
+            Self::put(&self.intents, record.intent_id, record).await
+        }
+
+        async fn update_status(&self, intent_id: H256, status: IntentStatus, updated_at: u64) -> Result<()> {
+            let mut record = self.load_intent(intent_id).await?
+                .ok_or_else(|| EngineError::StorageError(format!("Intent {:?} not persisted", intent_id)))?;
+            record.status = status;
+            record.updated_at = updated_at;
+            Self::put(&self.intents, intent_id, &record).await
+        }
+
+        async fn save_execution(&self, execution: &IntentExecution) -> Result<()> {
+            Self::put(&self.executions, execution.intent_id, execution).await
+        }
+
+        async fn load_intent(&self, intent_id: H256) -> Result<Option<IntentRecord>> {
+            self.intents.get(intent_id.as_bytes())
+                .map_err(|e| EngineError::StorageError(e.to_string()))?
+                .map(|bytes| Self::decode(&bytes))
+                .transpose()
+        }
+
+        async fn load_in_flight(&self) -> Result<Vec<IntentRecord>> {
+            let mut records = Vec::new();
+            for entry in self.intents.iter() {
+                let (_, bytes) = entry.map_err(|e| EngineError::StorageError(e.to_string()))?;
+                let record = Self::decode(&bytes)?;
+                if record.is_in_flight() {
+                    records.push(record);
+                }
+            }
+            Ok(records)
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub use self::postgres_store::PostgresStore;
+
+#[cfg(feature = "postgres")]
+mod postgres_store {
+    use super::*;
+    use sqlx::{postgres::PgPoolOptions, PgPool, Row};
+
+    /// Shared store for multi-instance deployments
+    pub struct PostgresStore {
+        pool: PgPool,
+    }
+
+    impl PostgresStore {
+        pub async fn connect(database_url: &str) -> Result<Self> {
+            let pool = PgPoolOptions::new()
+                .max_connections(10)
+                .acquire_timeout(std::time::Duration::from_secs(30))
+                .connect(database_url)
+                .await
+                .map_err(|e| EngineError::StorageError(format!("Failed to connect to database: {}", e)))?;
+
+            let store = Self { pool };
+            store.create_tables().await?;
+            Ok(store)
+        }
+
+        async fn create_tables(&self) -> Result<()> {
+            sqlx::query(r#"
+                CREATE TABLE IF NOT EXISTS engine_intents (
+                    intent_id VARCHAR(66) PRIMARY KEY,
+                    status VARCHAR(20) NOT NULL,
+                    payload JSONB NOT NULL,
+                    created_at BIGINT NOT NULL,
+                    updated_at BIGINT NOT NULL
+                )
+            "#)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EngineError::StorageError(format!("Failed to create engine_intents table: {}", e)))?;
+
+            sqlx::query(r#"
+                CREATE TABLE IF NOT EXISTS engine_executions (
+                    intent_id VARCHAR(66) PRIMARY KEY,
+                    payload JSONB NOT NULL
+                )
+            "#)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EngineError::StorageError(format!("Failed to create engine_executions table: {}", e)))?;
+
+            sqlx::query("CREATE INDEX IF NOT EXISTS idx_engine_intents_status ON engine_intents(status)")
+                .execute(&self.pool).await.ok();
+
+            Ok(())
+        }
+
+        fn decode(row: &sqlx::postgres::PgRow) -> Result<IntentRecord> {
+            let payload: serde_json::Value = row.try_get("payload")
+                .map_err(|e| EngineError::StorageError(e.to_string()))?;
+            serde_json::from_value(payload).map_err(|e| EngineError::StorageError(e.to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl StateStore for PostgresStore {
+        async fn save_intent(&self, record: &IntentRecord) -> Result<()> {
+            let payload = serde_json::to_value(record)
+                .map_err(|e| EngineError::StorageError(e.to_string()))?;
+
+            sqlx::query(r#"
+                INSERT INTO engine_intents (intent_id, status, payload, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (intent_id) DO UPDATE
+                SET status = EXCLUDED.status, payload = EXCLUDED.payload, updated_at = EXCLUDED.updated_at
+            "#)
+            .bind(format!("{:?}", record.intent_id))
+            .bind(format!("{:?}", record.status))
+            .bind(payload)
+            .bind(record.created_at as i64)
+            .bind(record.updated_at as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EngineError::StorageError(format!("Failed to save intent: {}", e)))?;
+
+            Ok(())
+        }
+
+        async fn update_status(&self, intent_id: H256, status: IntentStatus, updated_at: u64) -> Result<()> {
+            let mut record = self.load_intent(intent_id).await?
+                .ok_or_else(|| EngineError::StorageError(format!("Intent {:?} not persisted", intent_id)))?;
+            record.status = status;
+            record.updated_at = updated_at;
+            self.save_intent(&record).await
+        }
+
+        async fn save_execution(&self, execution: &IntentExecution) -> Result<()> {
+            let payload = serde_json::to_value(execution)
+                .map_err(|e| EngineError::StorageError(e.to_string()))?;
+
+            sqlx::query(r#"
+                INSERT INTO engine_executions (intent_id, payload)
+                VALUES ($1, $2)
+                ON CONFLICT (intent_id) DO UPDATE SET payload = EXCLUDED.payload
+            "#)
+            .bind(format!("{:?}", execution.intent_id))
+            .bind(payload)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EngineError::StorageError(format!("Failed to save execution: {}", e)))?;
+
+            Ok(())
+        }
+
+        async fn load_intent(&self, intent_id: H256) -> Result<Option<IntentRecord>> {
+            sqlx::query("SELECT payload FROM engine_intents WHERE intent_id = $1")
+                .bind(format!("{:?}", intent_id))
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| EngineError::StorageError(format!("Failed to load intent: {}", e)))?
+                .map(|row| Self::decode(&row))
+                .transpose()
+        }
+
+        async fn load_in_flight(&self) -> Result<Vec<IntentRecord>> {
+            let rows = sqlx::query(
+                "SELECT payload FROM engine_intents WHERE status IN ('Created', 'Validated', 'Matched', 'Executing') ORDER BY created_at"
+            )
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| EngineError::StorageError(format!("Failed to load in-flight intents: {}", e)))?;
+
+            rows.iter().map(Self::decode).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(status: IntentStatus) -> IntentRecord {
+        let intent = Intent::default();
+        IntentRecord {
+            intent_id: H256::random(),
+            intent,
+            status,
+            created_at: 1,
+            updated_at: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_recovers_only_in_flight() {
+        let store = MemoryStore::new();
+        let pending = record(IntentStatus::Created);
+        let executing = record(IntentStatus::Executing);
+        let executed = record(IntentStatus::Settled);
+
+        for r in [&pending, &executing, &executed] {
+            store.save_intent(r).await.unwrap();
+        }
+
+        let mut recovered: Vec<H256> = store.load_in_flight().await.unwrap()
+            .into_iter()
+            .map(|r| r.intent_id)
+            .collect();
+        recovered.sort();
+        let mut expected = vec![pending.intent_id, executing.intent_id];
+        expected.sort();
+        assert_eq!(recovered, expected);
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_status_update() {
+        let store = MemoryStore::new();
+        let pending = record(IntentStatus::Created);
+        store.save_intent(&pending).await.unwrap();
+
+        store.update_status(pending.intent_id, IntentStatus::Failed, 2).await.unwrap();
+
+        let loaded = store.load_intent(pending.intent_id).await.unwrap().unwrap();
+        assert_eq!(loaded.status, IntentStatus::Failed);
+        assert_eq!(loaded.updated_at, 2);
+        assert!(store.update_status(H256::random(), IntentStatus::Failed, 3).await.is_err());
+    }
+}
@@ -0,0 +1,426 @@
+//! Nonce and transaction management for executor accounts.
+//!
+//! Submissions are serialized per `(chain, signer)` so concurrent intent
+//! executions never race on nonces. Transactions that sit unmined past
+//! `stuck_after` are re-broadcast with the same nonce and bumped gas. Fees
+//! for new transactions come from the [`GasOracle`].
+
+use crate::{
+    checkpoint::PendingTxCheckpoint,
+    gas_oracle::{FeeStrategy, GasOracle},
+    EngineError, Result,
+};
+use ethers::{
+    providers::Middleware,
+    types::{
+        transaction::eip2718::TypedTransaction, Address, BlockNumber, TransactionReceipt, TransactionRequest, H256,
+        U256,
+    },
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{Mutex, RwLock};
+
+#[derive(Debug, Clone)]
+pub struct TxManagerConfig {
+    /// How long a transaction may stay pending before it is replaced
+    pub stuck_after: Duration,
+    /// Gas price increase per replacement, in percent. Most nodes require at
+    /// least 10% to accept a replacement.
+    pub bump_percent: u64,
+    pub max_bumps: u32,
+    pub poll_interval: Duration,
+    /// How long `wait_for_receipt` waits before giving up. The transaction
+    /// stays tracked, so a later wait picks it up again.
+    pub receipt_timeout: Duration,
+}
+
+impl Default for TxManagerConfig {
+    fn default() -> Self {
+        Self {
+            stuck_after: Duration::from_secs(60),
+            bump_percent: 15,
+            max_bumps: 5,
+            poll_interval: Duration::from_secs(3),
+            receipt_timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingTx {
+    pub nonce: U256,
+    pub tx_hash: H256,
+    pub request: TypedTransaction,
+    pub submitted_at: Instant,
+    pub bumps: u32,
+}
+
+#[derive(Debug, Default)]
+struct AccountState {
+    next_nonce: Option<U256>,
+    pending: BTreeMap<U256, PendingTx>,
+}
+
+type AccountKey = (u64, Address);
+
+#[derive(Default)]
+pub struct TxManager {
+    config: TxManagerConfig,
+    gas_oracle: Arc<GasOracle>,
+    accounts: RwLock<HashMap<AccountKey, Arc<Mutex<AccountState>>>>,
+}
+
+impl TxManager {
+    pub fn new(config: TxManagerConfig, gas_oracle: Arc<GasOracle>) -> Self {
+        Self {
+            config,
+            gas_oracle,
+            accounts: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn gas_oracle(&self) -> Arc<GasOracle> {
+        self.gas_oracle.clone()
+    }
+
+    async fn account(&self, chain_id: u64, from: Address) -> Arc<Mutex<AccountState>> {
+        if let Some(account) = self.accounts.read().await.get(&(chain_id, from)) {
+            return account.clone();
+        }
+
+        self.accounts
+            .write()
+            .await
+            .entry((chain_id, from))
+            .or_default()
+            .clone()
+    }
+
+    /// Price `tx` with `strategy`, assign the next nonce for `from` on
+    /// `chain_id` and broadcast. The account lock is held until the node
+    /// accepts the transaction, so nonces are handed out in submission order.
+    pub async fn submit<M: Middleware>(
+        &self,
+        chain_id: u64,
+        provider: &M,
+        from: Address,
+        tx: TransactionRequest,
+        strategy: FeeStrategy,
+    ) -> Result<PendingTx> {
+        let fees = self.gas_oracle.estimate(chain_id, provider, strategy).await?;
+
+        let account = self.account(chain_id, from).await;
+        let mut account = account.lock().await;
+
+        let nonce = match account.next_nonce {
+            Some(nonce) => nonce,
+            None => fetch_pending_nonce(provider, from).await?,
+        };
+
+        let mut request = fees.apply(tx);
+        request.set_from(from).set_nonce(nonce);
+        let tx_hash = match send(provider, &request).await {
+            Ok(hash) => hash,
+            Err(e) if is_nonce_error(&e) => {
+                // Our view of the nonce drifted (e.g. the key was used
+                // elsewhere); resync once and retry
+                tracing::warn!("Nonce {} rejected for {:?} on chain {}, resyncing", nonce, from, chain_id);
+                let nonce = fetch_pending_nonce(provider, from).await?;
+                request.set_nonce(nonce);
+                let tx_hash = send(provider, &request).await?;
+                let pending = PendingTx {
+                    nonce,
+                    tx_hash,
+                    request,
+                    submitted_at: Instant::now(),
+                    bumps: 0,
+                };
+                account.next_nonce = Some(nonce + 1);
+                account.pending.insert(nonce, pending.clone());
+                return Ok(pending);
+            }
+            Err(e) => {
+                // Nothing was broadcast, so the nonce is still free
+                account.next_nonce = Some(nonce);
+                return Err(e);
+            }
+        };
+
+        let pending = PendingTx {
+            nonce,
+            tx_hash,
+            request,
+            submitted_at: Instant::now(),
+            bumps: 0,
+        };
+        account.next_nonce = Some(nonce + 1);
+        account.pending.insert(nonce, pending.clone());
+
+        Ok(pending)
+    }
+
+    /// Wait until the transaction occupying `nonce` has `confirmations`
+    /// blocks on top of it, replacing it with bumped gas if it gets stuck.
+    /// The receipt may belong to a replacement rather than the original hash.
+    /// Fails with a timeout once `receipt_timeout` passes, e.g. when the
+    /// transaction was dropped or replaced by one we did not send.
+    pub async fn wait_for_receipt<M: Middleware>(
+        &self,
+        chain_id: u64,
+        provider: &M,
+        from: Address,
+        nonce: U256,
+        confirmations: u64,
+    ) -> Result<TransactionReceipt> {
+        // Every hash broadcast for this nonce; any one of them may be mined
+        let mut known: Vec<H256> = Vec::new();
+        let deadline = Instant::now() + self.config.receipt_timeout;
+
+        loop {
+            {
+                let account = self.account(chain_id, from).await;
+                let account = account.lock().await;
+                if let Some(pending) = account.pending.get(&nonce) {
+                    if !known.contains(&pending.tx_hash) {
+                        known.push(pending.tx_hash);
+                    }
+                }
+            }
+
+            for hash in known.iter().copied() {
+                let receipt = provider
+                    .get_transaction_receipt(hash)
+                    .await
+                    .map_err(|e| EngineError::ExecutionFailed(format!("Failed to get receipt: {}", e)))?;
+
+                if let Some(receipt) = receipt {
+                    let mined_at = receipt.block_number.unwrap_or_default().as_u64();
+                    let head = provider
+                        .get_block_number()
+                        .await
+                        .map_err(|e| EngineError::ExecutionFailed(format!("Failed to get block number: {}", e)))?
+                        .as_u64();
+
+                    if head.saturating_sub(mined_at) + 1 >= confirmations {
+                        self.confirm(chain_id, from, nonce).await;
+                        return Ok(receipt);
+                    }
+                }
+            }
+
+            if Instant::now() >= deadline {
+                // Worded without "nonce" so `ErrorClass::of` sees a timeout
+                return Err(EngineError::ExecutionFailed(format!(
+                    "Timed out after {:?} waiting for receipt of tx #{} from {:?} on chain {}",
+                    self.config.receipt_timeout, nonce, from, chain_id
+                )));
+            }
+
+            self.bump_stuck(chain_id, provider, from).await?;
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+
+    /// Drop pending entries the chain has already mined past
+    pub async fn reconcile<M: Middleware>(&self, chain_id: u64, provider: &M, from: Address) -> Result<()> {
+        let account = self.account(chain_id, from).await;
+        let mut account = account.lock().await;
+
+        let mined = provider
+            .get_transaction_count(from, Some(BlockNumber::Latest.into()))
+            .await
+            .map_err(|e| EngineError::ExecutionFailed(format!("Failed to fetch nonce: {}", e)))?;
+
+        account.pending.retain(|nonce, _| *nonce >= mined);
+        if account.next_nonce.map_or(true, |next| next < mined) {
+            account.next_nonce = Some(mined);
+        }
+
+        Ok(())
+    }
+
+    /// Re-broadcast stuck transactions with bumped gas. Returns the new
+    /// hashes keyed by nonce.
+    pub async fn bump_stuck<M: Middleware>(
+        &self,
+        chain_id: u64,
+        provider: &M,
+        from: Address,
+    ) -> Result<Vec<(U256, H256)>> {
+        self.reconcile(chain_id, provider, from).await?;
+
+        let account = self.account(chain_id, from).await;
+        let mut account = account.lock().await;
+        let mut replaced = Vec::new();
+
+        for pending in account.pending.values_mut() {
+            if pending.submitted_at.elapsed() < self.config.stuck_after || pending.bumps >= self.config.max_bumps {
+                continue;
+            }
+
+            let mut request = pending.request.clone();
+            let bumped = match request.as_eip1559_mut() {
+                // Replacements must raise both the fee cap and the tip
+                Some(eip1559) => {
+                    let max_fee = bump_gas_price(eip1559.max_fee_per_gas.unwrap_or_default(), self.config.bump_percent);
+                    let tip = bump_gas_price(eip1559.max_priority_fee_per_gas.unwrap_or_default(), self.config.bump_percent);
+                    eip1559.max_fee_per_gas = Some(max_fee);
+                    eip1559.max_priority_fee_per_gas = Some(tip);
+                    max_fee
+                }
+                None => {
+                    let current = match request.gas_price() {
+                        Some(price) => price,
+                        None => provider
+                            .get_gas_price()
+                            .await
+                            .map_err(|e| EngineError::ExecutionFailed(format!("Failed to fetch gas price: {}", e)))?,
+                    };
+                    let bumped = bump_gas_price(current, self.config.bump_percent);
+                    request.set_gas_price(bumped);
+                    bumped
+                }
+            };
+
+            match send(provider, &request).await {
+                Ok(hash) => {
+                    tracing::info!(
+                        "Replaced stuck tx {:?} (nonce {}) with {:?} at {} wei",
+                        pending.tx_hash,
+                        pending.nonce,
+                        hash,
+                        bumped
+                    );
+                    pending.tx_hash = hash;
+                    pending.request = request;
+                    pending.submitted_at = Instant::now();
+                    pending.bumps += 1;
+                    replaced.push((pending.nonce, hash));
+                }
+                Err(e) => tracing::warn!("Failed to replace tx with nonce {}: {:?}", pending.nonce, e),
+            }
+        }
+
+        Ok(replaced)
+    }
+
+    pub async fn confirm(&self, chain_id: u64, from: Address, nonce: U256) {
+        let account = self.account(chain_id, from).await;
+        account.lock().await.pending.remove(&nonce);
+    }
+
+    pub async fn pending(&self, chain_id: u64, from: Address) -> Vec<PendingTx> {
+        let account = self.account(chain_id, from).await;
+        let account = account.lock().await;
+        account.pending.values().cloned().collect()
+    }
+
+    /// Every unconfirmed transaction across all accounts
+    pub async fn snapshot(&self) -> Vec<PendingTxCheckpoint> {
+        let accounts: Vec<_> = self
+            .accounts
+            .read()
+            .await
+            .iter()
+            .map(|(key, account)| (*key, account.clone()))
+            .collect();
+
+        let mut snapshot = Vec::new();
+        for ((chain_id, from), account) in accounts {
+            for pending in account.lock().await.pending.values() {
+                snapshot.push(PendingTxCheckpoint {
+                    chain_id,
+                    from,
+                    nonce: pending.nonce,
+                    tx_hash: pending.tx_hash,
+                    request: pending.request.clone(),
+                    bumps: pending.bumps,
+                });
+            }
+        }
+
+        snapshot
+    }
+
+    /// Re-track transactions from a checkpoint so they keep being watched
+    /// and bumped. Nonce assignment resumes after the highest restored nonce.
+    pub async fn restore(&self, snapshot: Vec<PendingTxCheckpoint>) {
+        for entry in snapshot {
+            let account = self.account(entry.chain_id, entry.from).await;
+            let mut account = account.lock().await;
+
+            if account.next_nonce.map_or(true, |next| next <= entry.nonce) {
+                account.next_nonce = Some(entry.nonce + 1);
+            }
+            account.pending.insert(entry.nonce, PendingTx {
+                nonce: entry.nonce,
+                tx_hash: entry.tx_hash,
+                request: entry.request,
+                submitted_at: Instant::now(),
+                bumps: entry.bumps,
+            });
+        }
+    }
+}
+
+async fn fetch_pending_nonce<M: Middleware>(provider: &M, from: Address) -> Result<U256> {
+    provider
+        .get_transaction_count(from, Some(BlockNumber::Pending.into()))
+        .await
+        .map_err(|e| EngineError::ExecutionFailed(format!("Failed to fetch nonce: {}", e)))
+}
+
+async fn send<M: Middleware>(provider: &M, request: &TypedTransaction) -> Result<H256> {
+    provider
+        .send_transaction(request.clone(), None)
+        .await
+        .map(|pending| pending.tx_hash())
+        .map_err(|e| EngineError::ExecutionFailed(format!("Failed to send tx: {}", e)))
+}
+
+fn is_nonce_error(error: &EngineError) -> bool {
+    let message = error.to_string().to_lowercase();
+    message.contains("nonce too low") || message.contains("already known") || message.contains("nonce has already been used")
+}
+
+fn bump_gas_price(price: U256, bump_percent: u64) -> U256 {
+    let bumped = price * U256::from(100 + bump_percent) / U256::from(100);
+    // Always move by at least 1 wei so the replacement is strictly higher
+    bumped.max(price + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump_gas_price() {
+        assert_eq!(bump_gas_price(U256::from(100), 15), U256::from(115));
+        assert_eq!(bump_gas_price(U256::from(1), 10), U256::from(2));
+    }
+
+    #[test]
+    fn test_nonce_error_detection() {
+        assert!(is_nonce_error(&EngineError::ExecutionFailed("Failed to send tx: nonce too low".into())));
+        assert!(!is_nonce_error(&EngineError::ExecutionFailed("insufficient funds".into())));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_receipt_times_out() {
+        let manager = TxManager::new(
+            TxManagerConfig { receipt_timeout: Duration::ZERO, ..Default::default() },
+            Arc::new(GasOracle::default()),
+        );
+        let (provider, _mock) = ethers::providers::Provider::mocked();
+
+        let err = manager
+            .wait_for_receipt(1, &provider, Address::zero(), U256::zero(), 1)
+            .await
+            .unwrap_err();
+        assert_eq!(crate::retry::ErrorClass::of(&err), crate::retry::ErrorClass::Timeout);
+    }
+}
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use thiserror::Error;
 
+pub mod rules;
+
 /// Validation-specific error types
 #[derive(Error, Debug)]
 pub enum ValidatorError {
@@ -400,10 +402,13 @@ mod tests {
         let validator = Validator::new(U256::from(1000));
 
         let intent = create_test_intent();
-        let actual_amount = intent.min_dest_amount + U256::from(100);
-
+        // Within the 2% deviation band either side of min_dest_amount
+        let actual_amount = intent.min_dest_amount + U256::from(10);
         assert!(validator.validate_slippage(&intent, actual_amount).await.is_ok());
 
+        let windfall = intent.min_dest_amount + U256::from(100);
+        assert!(validator.validate_slippage(&intent, windfall).await.is_err());
+
         let low_amount = intent.min_dest_amount - U256::from(100);
         assert!(validator.validate_slippage(&intent, low_amount).await.is_err());
     }
@@ -0,0 +1,564 @@
+//! Composable intent validation pipeline.
+//!
+//! Each [`ValidationRule`] inspects an intent and either accepts it or returns
+//! a structured [`RejectionReason`]. Rules can be registered and removed at
+//! runtime through [`ValidationPipeline`].
+
+use crate::{intent::Intent, EngineError};
+use ethers::types::{Address, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RejectionCode {
+    InvalidAmount,
+    Expired,
+    DeadlineTooClose,
+    InvalidSignature,
+    TokenNotAllowed,
+    ChainNotSupported,
+    RateLimited,
+    SanctionedAddress,
+    SlippageTooLoose,
+}
+
+/// Why a rule rejected an intent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RejectionReason {
+    pub rule: String,
+    pub code: RejectionCode,
+    pub message: String,
+}
+
+impl RejectionReason {
+    pub fn new(rule: &str, code: RejectionCode, message: impl Into<String>) -> Self {
+        Self {
+            rule: rule.to_string(),
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {:?}: {}", self.rule, self.code, self.message)
+    }
+}
+
+impl From<RejectionReason> for EngineError {
+    fn from(reason: RejectionReason) -> Self {
+        // Expiry keeps the error callers matched on before the pipeline
+        match reason.code {
+            RejectionCode::Expired => EngineError::IntentExpired,
+            _ => EngineError::Rejected(reason),
+        }
+    }
+}
+
+pub type RuleResult = std::result::Result<(), RejectionReason>;
+
+pub trait ValidationRule: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn check(&self, intent: &Intent) -> RuleResult;
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Ordered set of rules evaluated on every submitted intent. Evaluation stops
+/// at the first rejection.
+#[derive(Default)]
+pub struct ValidationPipeline {
+    rules: RwLock<Vec<Arc<dyn ValidationRule>>>,
+}
+
+impl std::fmt::Debug for ValidationPipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidationPipeline").field("rules", &self.rule_names()).finish()
+    }
+}
+
+impl ValidationPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pipeline equivalent to the legacy `validate_intent` checks
+    pub fn with_default_rules() -> Self {
+        let pipeline = Self::new();
+        pipeline.register(Arc::new(MinAmountRule::new(U256::one())));
+        pipeline.register(Arc::new(DeadlineRule::new(0)));
+        pipeline.register(Arc::new(SignatureRule));
+        pipeline.register(Arc::new(SlippageFloorRule::new(5_000)));
+        pipeline
+    }
+
+    /// Append a rule, replacing any existing rule with the same name
+    pub fn register(&self, rule: Arc<dyn ValidationRule>) {
+        let mut rules = self.rules.write().unwrap();
+        rules.retain(|existing| existing.name() != rule.name());
+        rules.push(rule);
+    }
+
+    pub fn remove(&self, name: &str) -> bool {
+        let mut rules = self.rules.write().unwrap();
+        let before = rules.len();
+        rules.retain(|rule| rule.name() != name);
+        rules.len() != before
+    }
+
+    pub fn rule_names(&self) -> Vec<String> {
+        self.rules.read().unwrap().iter().map(|rule| rule.name().to_string()).collect()
+    }
+
+    pub fn validate(&self, intent: &Intent) -> RuleResult {
+        let rules = self.rules.read().unwrap().clone();
+        for rule in rules {
+            rule.check(intent)?;
+        }
+        Ok(())
+    }
+}
+
+/// Rejects zero amounts and source amounts below a floor
+pub struct MinAmountRule {
+    min_source_amount: U256,
+}
+
+impl MinAmountRule {
+    pub fn new(min_source_amount: U256) -> Self {
+        Self { min_source_amount }
+    }
+}
+
+impl ValidationRule for MinAmountRule {
+    fn name(&self) -> &str {
+        "min_amount"
+    }
+
+    fn check(&self, intent: &Intent) -> RuleResult {
+        if intent.source_amount.is_zero() || intent.source_amount < self.min_source_amount {
+            return Err(RejectionReason::new(
+                self.name(),
+                RejectionCode::InvalidAmount,
+                format!("Source amount {} below minimum {}", intent.source_amount, self.min_source_amount),
+            ));
+        }
+
+        if intent.min_dest_amount.is_zero() {
+            return Err(RejectionReason::new(
+                self.name(),
+                RejectionCode::InvalidAmount,
+                "Minimum destination amount cannot be zero",
+            ));
+        }
+
+        if intent.source_chain_id == intent.dest_chain_id && intent.source_token == intent.dest_token {
+            return Err(RejectionReason::new(
+                self.name(),
+                RejectionCode::InvalidAmount,
+                "Same token swap on same chain not allowed",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Rejects expired intents and those too close to their deadline to execute
+pub struct DeadlineRule {
+    min_time_remaining: u64,
+}
+
+impl DeadlineRule {
+    pub fn new(min_time_remaining: u64) -> Self {
+        Self { min_time_remaining }
+    }
+}
+
+impl ValidationRule for DeadlineRule {
+    fn name(&self) -> &str {
+        "deadline"
+    }
+
+    fn check(&self, intent: &Intent) -> RuleResult {
+        if intent.is_expired() {
+            return Err(RejectionReason::new(self.name(), RejectionCode::Expired, "Intent expired"));
+        }
+
+        let remaining = intent.deadline.saturating_sub(now_secs());
+        if remaining < self.min_time_remaining {
+            return Err(RejectionReason::new(
+                self.name(),
+                RejectionCode::DeadlineTooClose,
+                format!("Only {}s until deadline, need {}s", remaining, self.min_time_remaining),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+pub struct SignatureRule;
+
+impl ValidationRule for SignatureRule {
+    fn name(&self) -> &str {
+        "signature"
+    }
+
+    fn check(&self, intent: &Intent) -> RuleResult {
+        if !intent.verify_signature() {
+            return Err(RejectionReason::new(self.name(), RejectionCode::InvalidSignature, "Invalid signature"));
+        }
+        Ok(())
+    }
+}
+
+/// Rejects intents whose min_dest_amount is below a fraction (in basis
+/// points) of the source amount, which invites front-running
+pub struct SlippageFloorRule {
+    min_ratio_bps: u64,
+}
+
+impl SlippageFloorRule {
+    pub fn new(min_ratio_bps: u64) -> Self {
+        Self { min_ratio_bps }
+    }
+}
+
+impl ValidationRule for SlippageFloorRule {
+    fn name(&self) -> &str {
+        "slippage_floor"
+    }
+
+    fn check(&self, intent: &Intent) -> RuleResult {
+        let floor = intent.source_amount * U256::from(self.min_ratio_bps) / U256::from(10_000);
+        if intent.min_dest_amount < floor {
+            return Err(RejectionReason::new(
+                self.name(),
+                RejectionCode::SlippageTooLoose,
+                "Minimum destination amount too low - potential front-running risk",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Only accepts intents whose source and destination tokens are allowlisted
+pub struct TokenAllowlistRule {
+    tokens: RwLock<HashSet<Address>>,
+}
+
+impl TokenAllowlistRule {
+    pub fn new(tokens: impl IntoIterator<Item = Address>) -> Self {
+        Self { tokens: RwLock::new(tokens.into_iter().collect()) }
+    }
+
+    pub fn allow(&self, token: Address) {
+        self.tokens.write().unwrap().insert(token);
+    }
+
+    pub fn disallow(&self, token: Address) {
+        self.tokens.write().unwrap().remove(&token);
+    }
+}
+
+impl ValidationRule for TokenAllowlistRule {
+    fn name(&self) -> &str {
+        "token_allowlist"
+    }
+
+    fn check(&self, intent: &Intent) -> RuleResult {
+        let tokens = self.tokens.read().unwrap();
+        for token in [intent.source_token, intent.dest_token] {
+            if !tokens.contains(&token) {
+                return Err(RejectionReason::new(
+                    self.name(),
+                    RejectionCode::TokenNotAllowed,
+                    format!("Token {:?} is not allowlisted", token),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct ChainSupportRule {
+    chains: RwLock<HashSet<u64>>,
+}
+
+impl ChainSupportRule {
+    pub fn new(chains: impl IntoIterator<Item = u64>) -> Self {
+        Self { chains: RwLock::new(chains.into_iter().collect()) }
+    }
+
+    pub fn set_supported(&self, chain_id: u64, supported: bool) {
+        let mut chains = self.chains.write().unwrap();
+        if supported {
+            chains.insert(chain_id);
+        } else {
+            chains.remove(&chain_id);
+        }
+    }
+}
+
+impl ValidationRule for ChainSupportRule {
+    fn name(&self) -> &str {
+        "chain_support"
+    }
+
+    fn check(&self, intent: &Intent) -> RuleResult {
+        let chains = self.chains.read().unwrap();
+        for chain_id in [intent.source_chain_id, intent.dest_chain_id] {
+            if !chains.contains(&chain_id) {
+                return Err(RejectionReason::new(
+                    self.name(),
+                    RejectionCode::ChainNotSupported,
+                    format!("Chain {} is not supported", chain_id),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sliding-window limit on intents accepted per user
+pub struct UserRateLimitRule {
+    max_intents: usize,
+    window_secs: u64,
+    history: Mutex<HashMap<Address, VecDeque<u64>>>,
+}
+
+impl UserRateLimitRule {
+    pub fn new(max_intents: usize, window_secs: u64) -> Self {
+        Self {
+            max_intents,
+            window_secs,
+            history: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl ValidationRule for UserRateLimitRule {
+    fn name(&self) -> &str {
+        "user_rate_limit"
+    }
+
+    fn check(&self, intent: &Intent) -> RuleResult {
+        let now = now_secs();
+        let mut history = self.history.lock().unwrap();
+        let submissions = history.entry(intent.user).or_default();
+
+        while submissions.front().map_or(false, |t| now.saturating_sub(*t) >= self.window_secs) {
+            submissions.pop_front();
+        }
+
+        if submissions.len() >= self.max_intents {
+            return Err(RejectionReason::new(
+                self.name(),
+                RejectionCode::RateLimited,
+                format!("More than {} intents in {}s", self.max_intents, self.window_secs),
+            ));
+        }
+
+        submissions.push_back(now);
+        Ok(())
+    }
+}
+
+/// Sliding-window limit on intents accepted per source chain. Chains
+/// without a limit are unrestricted; limits can be changed at runtime.
+#[derive(Debug, Default)]
+pub struct ChainRateLimitRule {
+    limits: RwLock<HashMap<u64, ChainRateLimit>>,
+    history: Mutex<HashMap<u64, VecDeque<u64>>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainRateLimit {
+    pub max_intents: usize,
+    pub window_secs: u64,
+}
+
+impl ChainRateLimitRule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set or, with `None`, lift the limit on `chain_id`
+    pub fn set_limit(&self, chain_id: u64, limit: Option<ChainRateLimit>) {
+        let mut limits = self.limits.write().unwrap();
+        match limit {
+            Some(limit) => limits.insert(chain_id, limit),
+            None => limits.remove(&chain_id),
+        };
+    }
+
+    pub fn limits(&self) -> HashMap<u64, ChainRateLimit> {
+        self.limits.read().unwrap().clone()
+    }
+}
+
+impl ValidationRule for ChainRateLimitRule {
+    fn name(&self) -> &str {
+        "chain_rate_limit"
+    }
+
+    fn check(&self, intent: &Intent) -> RuleResult {
+        let Some(limit) = self.limits.read().unwrap().get(&intent.source_chain_id).copied() else {
+            return Ok(());
+        };
+
+        let now = now_secs();
+        let mut history = self.history.lock().unwrap();
+        let submissions = history.entry(intent.source_chain_id).or_default();
+
+        while submissions.front().map_or(false, |t| now.saturating_sub(*t) >= limit.window_secs) {
+            submissions.pop_front();
+        }
+
+        if submissions.len() >= limit.max_intents {
+            return Err(RejectionReason::new(
+                self.name(),
+                RejectionCode::RateLimited,
+                format!(
+                    "Chain {} is limited to {} intents per {}s",
+                    intent.source_chain_id, limit.max_intents, limit.window_secs
+                ),
+            ));
+        }
+
+        submissions.push_back(now);
+        Ok(())
+    }
+}
+
+/// Screens the intent's user against a sanctioned-address list
+pub struct SanctionsRule {
+    blocked: RwLock<HashSet<Address>>,
+}
+
+impl SanctionsRule {
+    pub fn new(blocked: impl IntoIterator<Item = Address>) -> Self {
+        Self { blocked: RwLock::new(blocked.into_iter().collect()) }
+    }
+
+    pub fn block(&self, address: Address) {
+        self.blocked.write().unwrap().insert(address);
+    }
+
+    pub fn unblock(&self, address: Address) {
+        self.blocked.write().unwrap().remove(&address);
+    }
+}
+
+impl ValidationRule for SanctionsRule {
+    fn name(&self) -> &str {
+        "sanctions"
+    }
+
+    fn check(&self, intent: &Intent) -> RuleResult {
+        if self.blocked.read().unwrap().contains(&intent.user) {
+            return Err(RejectionReason::new(
+                self.name(),
+                RejectionCode::SanctionedAddress,
+                format!("Address {:?} is sanctioned", intent.user),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_intent() -> Intent {
+        Intent {
+            user: Address::random(),
+            source_chain_id: 1,
+            dest_chain_id: 10,
+            source_token: Address::random(),
+            dest_token: Address::random(),
+            source_amount: U256::from(1000),
+            min_dest_amount: U256::from(950),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rules_registered_at_runtime() {
+        let pipeline = ValidationPipeline::new();
+        let intent = test_intent();
+        assert!(pipeline.validate(&intent).is_ok());
+
+        pipeline.register(Arc::new(ChainSupportRule::new([1])));
+        let rejection = pipeline.validate(&intent).unwrap_err();
+        assert_eq!(rejection.code, RejectionCode::ChainNotSupported);
+        assert_eq!(rejection.rule, "chain_support");
+
+        assert!(pipeline.remove("chain_support"));
+        assert!(pipeline.validate(&intent).is_ok());
+    }
+
+    #[test]
+    fn test_expired_intents_keep_their_error() {
+        let expired = Intent { deadline: now_secs() - 1, ..test_intent() };
+        let rejection = DeadlineRule::new(0).check(&expired).unwrap_err();
+        assert!(matches!(EngineError::from(rejection), EngineError::IntentExpired));
+
+        let rejection = RejectionReason::new("deadline", RejectionCode::DeadlineTooClose, "too close");
+        assert!(matches!(EngineError::from(rejection), EngineError::Rejected(_)));
+    }
+
+    #[test]
+    fn test_rate_limit_and_sanctions() {
+        let intent = test_intent();
+
+        let rate_limit = UserRateLimitRule::new(2, 60);
+        assert!(rate_limit.check(&intent).is_ok());
+        assert!(rate_limit.check(&intent).is_ok());
+        assert_eq!(rate_limit.check(&intent).unwrap_err().code, RejectionCode::RateLimited);
+
+        let sanctions = SanctionsRule::new([]);
+        assert!(sanctions.check(&intent).is_ok());
+        sanctions.block(intent.user);
+        assert_eq!(sanctions.check(&intent).unwrap_err().code, RejectionCode::SanctionedAddress);
+    }
+
+    #[test]
+    fn test_chain_rate_limit() {
+        let intent = test_intent();
+        let rule = ChainRateLimitRule::new();
+        for _ in 0..3 {
+            assert!(rule.check(&intent).is_ok());
+        }
+
+        rule.set_limit(intent.source_chain_id, Some(ChainRateLimit { max_intents: 1, window_secs: 60 }));
+        assert!(rule.check(&intent).is_ok());
+        assert_eq!(rule.check(&intent).unwrap_err().code, RejectionCode::RateLimited);
+        // Other chains are unaffected
+        assert!(rule.check(&Intent { source_chain_id: 10, ..intent.clone() }).is_ok());
+
+        rule.set_limit(intent.source_chain_id, None);
+        assert!(rule.check(&intent).is_ok());
+    }
+
+    #[test]
+    fn test_token_allowlist() {
+        let intent = test_intent();
+        let allowlist = TokenAllowlistRule::new([intent.source_token]);
+        assert_eq!(allowlist.check(&intent).unwrap_err().code, RejectionCode::TokenNotAllowed);
+
+        allowlist.allow(intent.dest_token);
+        assert!(allowlist.check(&intent).is_ok());
+    }
+}
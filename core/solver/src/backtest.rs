@@ -0,0 +1,501 @@
+//! Replay of historical intents against a solver strategy.
+//!
+//! A [`HistorySource`] yields intents, their historical fills and orbital
+//! pool snapshots in the order they were indexed. The [`Backtester`] feeds
+//! each intent to a [`ReplayStrategy`] together with the pool state current
+//! at that point, treats the strategy as having filled the intent if its
+//! quote beats both the user's minimum and whatever the winning solver paid
+//! out, and aggregates the outcome into a [`BacktestReport`] with P&L,
+//! fill rate and latency figures.
+
+use crate::{pool_state::PoolSnapshot, Result, SolverQuote};
+use async_trait::async_trait;
+use ethers::types::{Address, H256, U256};
+use intents_engine::intent::Intent;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// One record from the indexer's history, ordered by `timestamp`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HistoricalRecord {
+    IntentCreated { intent_id: H256, timestamp: u64, intent: Intent },
+    /// Fill by whichever solver won the intent at the time
+    IntentExecuted { intent_id: H256, timestamp: u64, solver: Address, dest_amount: U256 },
+    PoolState { chain_id: u64, timestamp: u64, snapshot: PoolSnapshot },
+}
+
+impl HistoricalRecord {
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            HistoricalRecord::IntentCreated { timestamp, .. }
+            | HistoricalRecord::IntentExecuted { timestamp, .. }
+            | HistoricalRecord::PoolState { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+/// Where historical records are replayed from
+#[async_trait]
+pub trait HistorySource: Send + Sync {
+    /// Every record with `from <= timestamp < to`, oldest first
+    async fn load(&self, from: u64, to: u64) -> Result<Vec<HistoricalRecord>>;
+}
+
+/// History held in memory, e.g. fixtures or a previously exported range
+#[derive(Debug, Clone, Default)]
+pub struct MemoryHistory {
+    records: Vec<HistoricalRecord>,
+}
+
+impl MemoryHistory {
+    pub fn new(mut records: Vec<HistoricalRecord>) -> Self {
+        records.sort_by_key(HistoricalRecord::timestamp);
+        Self { records }
+    }
+}
+
+#[async_trait]
+impl HistorySource for MemoryHistory {
+    async fn load(&self, from: u64, to: u64) -> Result<Vec<HistoricalRecord>> {
+        Ok(self.records
+            .iter()
+            .filter(|record| (from..to).contains(&record.timestamp()))
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub use self::postgres_history::IndexerHistory;
+
+#[cfg(feature = "postgres")]
+mod postgres_history {
+    use super::*;
+    use crate::SolverError;
+    use sqlx::{postgres::PgPoolOptions, PgPool, Row};
+
+    /// Reads the indexer's `indexed_events` table. Intent events carry the
+    /// indexer's JSON payloads; pool states are `PoolState` events whose
+    /// payload is a [`PoolSnapshot`].
+    pub struct IndexerHistory {
+        pool: PgPool,
+    }
+
+    #[derive(Deserialize)]
+    struct IntentCreatedPayload {
+        intent_id: H256,
+        user: Address,
+        source_chain_id: u64,
+        dest_chain_id: u64,
+        source_token: Address,
+        dest_token: Address,
+        source_amount: U256,
+        min_dest_amount: U256,
+        deadline: u64,
+        nonce: U256,
+    }
+
+    #[derive(Deserialize)]
+    struct IntentExecutedPayload {
+        intent_id: H256,
+        solver: Address,
+        dest_amount: U256,
+    }
+
+    impl IndexerHistory {
+        pub async fn connect(database_url: &str) -> Result<Self> {
+            let pool = PgPoolOptions::new()
+                .max_connections(2)
+                .acquire_timeout(std::time::Duration::from_secs(30))
+                .connect(database_url)
+                .await
+                .map_err(|e| SolverError::StorageError(format!("Failed to connect to indexer database: {}", e)))?;
+            Ok(Self { pool })
+        }
+
+        fn decode(row: &sqlx::postgres::PgRow) -> Result<Option<HistoricalRecord>> {
+            let storage_err = |e: &dyn std::fmt::Display| SolverError::StorageError(e.to_string());
+            let event_type: String = row.try_get("event_type").map_err(|e| storage_err(&e))?;
+            let chain_id: i64 = row.try_get("chain_id").map_err(|e| storage_err(&e))?;
+            let timestamp: i64 = row.try_get("ts").map_err(|e| storage_err(&e))?;
+            let data: serde_json::Value = row.try_get("event_data").map_err(|e| storage_err(&e))?;
+            let timestamp = timestamp as u64;
+
+            let record = match event_type.as_str() {
+                "IntentCreated" => {
+                    let event: IntentCreatedPayload = serde_json::from_value(data).map_err(|e| storage_err(&e))?;
+                    HistoricalRecord::IntentCreated {
+                        intent_id: event.intent_id,
+                        timestamp,
+                        intent: Intent {
+                            user: event.user,
+                            source_chain_id: event.source_chain_id,
+                            dest_chain_id: event.dest_chain_id,
+                            source_token: event.source_token,
+                            dest_token: event.dest_token,
+                            source_amount: event.source_amount,
+                            min_dest_amount: event.min_dest_amount,
+                            deadline: event.deadline,
+                            nonce: event.nonce,
+                            ..Default::default()
+                        },
+                    }
+                }
+                "IntentExecuted" => {
+                    let event: IntentExecutedPayload = serde_json::from_value(data).map_err(|e| storage_err(&e))?;
+                    HistoricalRecord::IntentExecuted {
+                        intent_id: event.intent_id,
+                        timestamp,
+                        solver: event.solver,
+                        dest_amount: event.dest_amount,
+                    }
+                }
+                "PoolState" => HistoricalRecord::PoolState {
+                    chain_id: chain_id as u64,
+                    timestamp,
+                    snapshot: serde_json::from_value(data).map_err(|e| storage_err(&e))?,
+                },
+                _ => return Ok(None),
+            };
+            Ok(Some(record))
+        }
+    }
+
+    #[async_trait]
+    impl HistorySource for IndexerHistory {
+        async fn load(&self, from: u64, to: u64) -> Result<Vec<HistoricalRecord>> {
+            let rows = sqlx::query(r#"
+                SELECT event_type, chain_id, event_data, EXTRACT(EPOCH FROM timestamp)::BIGINT AS ts
+                FROM indexed_events
+                WHERE event_type IN ('IntentCreated', 'IntentExecuted', 'PoolState')
+                  AND timestamp >= to_timestamp($1) AND timestamp < to_timestamp($2)
+                ORDER BY timestamp, block_number, log_index
+            "#)
+            .bind(from as i64)
+            .bind(to as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| SolverError::StorageError(format!("Failed to load indexed events: {}", e)))?;
+
+            let mut records = Vec::with_capacity(rows.len());
+            for row in &rows {
+                if let Some(record) = Self::decode(row)? {
+                    records.push(record);
+                }
+            }
+            Ok(records)
+        }
+    }
+}
+
+/// Market state visible to the strategy when an intent arrives
+#[derive(Debug, Clone, Default)]
+pub struct MarketView {
+    pub timestamp: u64,
+    /// Latest orbital pool snapshot per chain
+    pub pools: HashMap<u64, PoolSnapshot>,
+}
+
+/// Strategy under test
+#[async_trait]
+pub trait ReplayStrategy: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Quote for `intent`, or `None` to pass on it
+    async fn evaluate(&self, intent: &Intent, market: &MarketView) -> Result<Option<SolverQuote>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayOutcome {
+    Filled,
+    /// Quoted, but below the user's minimum or the historical winner
+    Outbid,
+    Passed,
+    Errored,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayedIntent {
+    pub intent_id: H256,
+    pub timestamp: u64,
+    pub outcome: ReplayOutcome,
+    pub quoted_dest_amount: Option<U256>,
+    pub historical_dest_amount: Option<U256>,
+    pub profit: U256,
+    /// Wall-clock time the strategy took to decide
+    pub decision_latency: Duration,
+    /// Strategy's own estimate of time to settle
+    pub execution_time_estimate: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BacktestReport {
+    pub strategy: String,
+    pub from: u64,
+    pub to: u64,
+    pub intents: u64,
+    pub quoted: u64,
+    pub filled: u64,
+    pub outbid: u64,
+    pub errors: u64,
+    pub fill_rate: f64,
+    pub total_profit: U256,
+    pub profit_by_chain: HashMap<u64, U256>,
+    pub decision_latency_p50: Duration,
+    pub decision_latency_p95: Duration,
+    pub average_execution_time: u64,
+    pub intents_replayed: Vec<ReplayedIntent>,
+}
+
+pub struct Backtester<S: HistorySource> {
+    source: S,
+}
+
+impl<S: HistorySource> Backtester<S> {
+    pub fn new(source: S) -> Self {
+        Self { source }
+    }
+
+    /// Replay every intent created in `[from, to)` against `strategy`
+    pub async fn run(&self, strategy: &dyn ReplayStrategy, from: u64, to: u64) -> Result<BacktestReport> {
+        let records = self.source.load(from, to).await?;
+
+        // Historical fills are known up front so each quote can be compared
+        // with what the winning solver actually paid out
+        let historical_fills: HashMap<H256, U256> = records
+            .iter()
+            .filter_map(|record| match record {
+                HistoricalRecord::IntentExecuted { intent_id, dest_amount, .. } => Some((*intent_id, *dest_amount)),
+                _ => None,
+            })
+            .collect();
+
+        let mut market = MarketView::default();
+        let mut replayed = Vec::new();
+        let mut profit_by_chain: HashMap<u64, U256> = HashMap::new();
+
+        for record in records {
+            match record {
+                HistoricalRecord::PoolState { chain_id, timestamp, snapshot } => {
+                    market.timestamp = timestamp;
+                    market.pools.insert(chain_id, snapshot);
+                }
+                HistoricalRecord::IntentCreated { intent_id, timestamp, intent } => {
+                    market.timestamp = timestamp;
+                    let historical = historical_fills.get(&intent_id).copied();
+
+                    let started = Instant::now();
+                    let quote = strategy.evaluate(&intent, &market).await;
+                    let decision_latency = started.elapsed();
+
+                    let entry = replay_intent(intent_id, timestamp, &intent, quote, historical, decision_latency);
+                    if entry.outcome == ReplayOutcome::Filled {
+                        *profit_by_chain.entry(intent.dest_chain_id).or_default() += entry.profit;
+                    }
+                    replayed.push(entry);
+                }
+                HistoricalRecord::IntentExecuted { .. } => {}
+            }
+        }
+
+        Ok(summarize(strategy.name(), from, to, replayed, profit_by_chain))
+    }
+}
+
+fn replay_intent(
+    intent_id: H256,
+    timestamp: u64,
+    intent: &Intent,
+    quote: Result<Option<SolverQuote>>,
+    historical_dest_amount: Option<U256>,
+    decision_latency: Duration,
+) -> ReplayedIntent {
+    let mut entry = ReplayedIntent {
+        intent_id,
+        timestamp,
+        outcome: ReplayOutcome::Passed,
+        quoted_dest_amount: None,
+        historical_dest_amount,
+        profit: U256::zero(),
+        decision_latency,
+        execution_time_estimate: 0,
+    };
+
+    match quote {
+        Err(_) => entry.outcome = ReplayOutcome::Errored,
+        Ok(None) => {}
+        Ok(Some(quote)) => {
+            entry.quoted_dest_amount = Some(quote.dest_amount);
+            entry.execution_time_estimate = quote.execution_time_estimate;
+            let beats_winner = historical_dest_amount.map_or(true, |paid| quote.dest_amount >= paid);
+            if quote.dest_amount >= intent.min_dest_amount && beats_winner {
+                entry.outcome = ReplayOutcome::Filled;
+                entry.profit = quote.profit;
+            } else {
+                entry.outcome = ReplayOutcome::Outbid;
+            }
+        }
+    }
+    entry
+}
+
+fn summarize(
+    strategy: &str,
+    from: u64,
+    to: u64,
+    replayed: Vec<ReplayedIntent>,
+    profit_by_chain: HashMap<u64, U256>,
+) -> BacktestReport {
+    let count = |outcome| replayed.iter().filter(|entry| entry.outcome == outcome).count() as u64;
+    let filled = count(ReplayOutcome::Filled);
+    let outbid = count(ReplayOutcome::Outbid);
+    let intents = replayed.len() as u64;
+
+    let mut latencies: Vec<Duration> = replayed.iter().map(|entry| entry.decision_latency).collect();
+    latencies.sort();
+    let percentile = |p: usize| {
+        if latencies.is_empty() {
+            Duration::ZERO
+        } else {
+            latencies[(latencies.len() - 1) * p / 100]
+        }
+    };
+
+    let filled_times: Vec<u64> = replayed
+        .iter()
+        .filter(|entry| entry.outcome == ReplayOutcome::Filled)
+        .map(|entry| entry.execution_time_estimate)
+        .collect();
+
+    BacktestReport {
+        strategy: strategy.to_string(),
+        from,
+        to,
+        intents,
+        quoted: filled + outbid,
+        filled,
+        outbid,
+        errors: count(ReplayOutcome::Errored),
+        fill_rate: if intents == 0 { 0.0 } else { filled as f64 / intents as f64 },
+        total_profit: profit_by_chain.values().fold(U256::zero(), |acc, &p| acc + p),
+        profit_by_chain,
+        decision_latency_p50: percentile(50),
+        decision_latency_p95: percentile(95),
+        average_execution_time: if filled_times.is_empty() {
+            0
+        } else {
+            filled_times.iter().sum::<u64>() / filled_times.len() as u64
+        },
+        intents_replayed: replayed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Quotes a fixed 1% below the source amount, but only when a pool
+    /// snapshot for the source chain has been seen
+    struct FixedSpread;
+
+    #[async_trait]
+    impl ReplayStrategy for FixedSpread {
+        fn name(&self) -> &str {
+            "fixed-spread"
+        }
+
+        async fn evaluate(&self, intent: &Intent, market: &MarketView) -> Result<Option<SolverQuote>> {
+            if !market.pools.contains_key(&intent.source_chain_id) {
+                return Ok(None);
+            }
+            Ok(Some(SolverQuote {
+                solver: Address::zero(),
+                dest_amount: intent.source_amount * U256::from(99) / U256::from(100),
+                profit: intent.source_amount / U256::from(200),
+                execution_time_estimate: 45,
+                confidence: 0.9,
+            }))
+        }
+    }
+
+    fn snapshot() -> PoolSnapshot {
+        PoolSnapshot {
+            block_number: 1,
+            tokens: vec![Address::repeat_byte(1), Address::repeat_byte(2)],
+            reserves: vec![U256::from(1_000_000), U256::from(1_000_000)],
+            radius_squared: U256::zero(),
+            concentrated_liquidity: U256::zero(),
+            active: true,
+            fee_bps: U256::from(30),
+            current_tick: U256::zero(),
+            active_liquidity: U256::zero(),
+            ticks: Vec::new(),
+        }
+    }
+
+    fn intent_created(id: u8, timestamp: u64, min_dest_amount: u64) -> HistoricalRecord {
+        HistoricalRecord::IntentCreated {
+            intent_id: H256::repeat_byte(id),
+            timestamp,
+            intent: Intent {
+                source_chain_id: 1,
+                dest_chain_id: 137,
+                source_amount: U256::from(10_000),
+                min_dest_amount: U256::from(min_dest_amount),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_classifies_outcomes() {
+        let history = MemoryHistory::new(vec![
+            // Before any pool state: the strategy passes
+            intent_created(1, 100, 9_000),
+            HistoricalRecord::PoolState { chain_id: 1, timestamp: 110, snapshot: snapshot() },
+            // Quote of 9_900 beats the historical fill of 9_800
+            intent_created(2, 120, 9_000),
+            HistoricalRecord::IntentExecuted {
+                intent_id: H256::repeat_byte(2),
+                timestamp: 130,
+                solver: Address::repeat_byte(9),
+                dest_amount: U256::from(9_800),
+            },
+            // Historical winner paid out more than the strategy would
+            intent_created(3, 140, 9_000),
+            HistoricalRecord::IntentExecuted {
+                intent_id: H256::repeat_byte(3),
+                timestamp: 150,
+                solver: Address::repeat_byte(9),
+                dest_amount: U256::from(9_950),
+            },
+            // Below the user's minimum
+            intent_created(4, 160, 9_950),
+        ]);
+
+        let report = Backtester::new(history).run(&FixedSpread, 0, 1_000).await.unwrap();
+        let outcomes: Vec<_> = report.intents_replayed.iter().map(|entry| entry.outcome).collect();
+        assert_eq!(
+            outcomes,
+            vec![ReplayOutcome::Passed, ReplayOutcome::Filled, ReplayOutcome::Outbid, ReplayOutcome::Outbid]
+        );
+        assert_eq!(report.filled, 1);
+        assert_eq!(report.quoted, 3);
+        assert_eq!(report.fill_rate, 0.25);
+        assert_eq!(report.total_profit, U256::from(50));
+        assert_eq!(report.profit_by_chain.get(&137), Some(&U256::from(50)));
+        assert_eq!(report.average_execution_time, 45);
+    }
+
+    #[tokio::test]
+    async fn test_history_window_is_half_open() {
+        let history = MemoryHistory::new(vec![intent_created(1, 100, 0), intent_created(2, 200, 0)]);
+        assert_eq!(history.load(100, 200).await.unwrap().len(), 1);
+        assert_eq!(history.load(0, 201).await.unwrap().len(), 2);
+    }
+}
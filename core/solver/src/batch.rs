@@ -0,0 +1,301 @@
+//! Batch settlement of same-chain intents.
+//!
+//! Same-chain intents swapping the same token pair can settle in a single
+//! transaction through a solver-owned settler contract, which executes a
+//! list of calls as itself with `execute((address,uint256,bytes)[])`. The
+//! batch pulls the combined input from the solver once, approves each swap
+//! venue once for everything routed through it, runs every swap and pays
+//! every user. Gas of the batch is split evenly across its intents, so each
+//! intent's P&L carries its share. Won intents are collected for a short
+//! window per token pair before the batch is sent.
+
+use ethers::{
+    abi::{self, Token},
+    types::{Address, Bytes, TransactionRequest, H256, U256},
+    utils::keccak256,
+};
+use intents_engine::intent::Intent;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, time::Duration};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchConfig {
+    /// Settler contract per chain; intents on other chains are never batched
+    pub settlers: HashMap<u64, Address>,
+    pub max_batch_size: usize,
+    /// How long a batch collects intents before it is sent
+    pub collect_window_ms: u64,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            settlers: HashMap::new(),
+            max_batch_size: 8,
+            collect_window_ms: 2000,
+        }
+    }
+}
+
+/// Intents sharing a key can settle together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BatchKey {
+    pub chain_id: u64,
+    pub source_token: Address,
+    pub dest_token: Address,
+}
+
+/// `None` for cross-chain intents, which settle across two transactions
+pub fn batch_key(intent: &Intent) -> Option<BatchKey> {
+    (intent.source_chain_id == intent.dest_chain_id).then_some(BatchKey {
+        chain_id: intent.source_chain_id,
+        source_token: intent.source_token,
+        dest_token: intent.dest_token,
+    })
+}
+
+/// Split `intents` into batches of at most `max_batch_size` sharing a key,
+/// in order of first appearance. Unbatchable intents come back alone.
+pub fn group_intents(intents: Vec<(H256, Intent)>, max_batch_size: usize) -> Vec<Vec<(H256, Intent)>> {
+    let max_batch_size = max_batch_size.max(1);
+    let mut groups: Vec<Vec<(H256, Intent)>> = Vec::new();
+    let mut open: HashMap<BatchKey, usize> = HashMap::new();
+
+    for (intent_id, intent) in intents {
+        let Some(key) = batch_key(&intent) else {
+            groups.push(vec![(intent_id, intent)]);
+            continue;
+        };
+        match open.get(&key) {
+            Some(&index) if groups[index].len() < max_batch_size => groups[index].push((intent_id, intent)),
+            _ => {
+                open.insert(key, groups.len());
+                groups.push(vec![(intent_id, intent)]);
+            }
+        }
+    }
+    groups
+}
+
+/// One call the settler makes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+    pub target: Address,
+    pub value: U256,
+    pub data: Bytes,
+}
+
+/// Calls settling `intents` from `solver` through `settler`, where
+/// `swaps[i]` is the swap transaction for `intents[i]`
+pub fn settlement_calls(solver: Address, settler: Address, intents: &[Intent], swaps: &[TransactionRequest]) -> Vec<Call> {
+    let mut calls = Vec::new();
+    let Some(first) = intents.first() else { return calls };
+    let total_in = intents.iter().fold(U256::zero(), |acc, intent| acc + intent.source_amount);
+
+    // Pull the combined input once and approve each venue once
+    if first.source_token != Address::zero() {
+        calls.push(Call {
+            target: first.source_token,
+            value: U256::zero(),
+            data: erc20_call("transferFrom(address,address,uint256)", &[
+                Token::Address(solver),
+                Token::Address(settler),
+                Token::Uint(total_in),
+            ]),
+        });
+
+        let mut approvals: Vec<(Address, U256)> = Vec::new();
+        for (intent, swap) in intents.iter().zip(swaps) {
+            let spender = swap_target(swap);
+            match approvals.iter_mut().find(|(existing, _)| *existing == spender) {
+                Some((_, amount)) => *amount += intent.source_amount,
+                None => approvals.push((spender, intent.source_amount)),
+            }
+        }
+        calls.extend(approvals.into_iter().map(|(spender, amount)| Call {
+            target: first.source_token,
+            value: U256::zero(),
+            data: erc20_call("approve(address,uint256)", &[Token::Address(spender), Token::Uint(amount)]),
+        }));
+    }
+
+    calls.extend(swaps.iter().map(|swap| Call {
+        target: swap_target(swap),
+        value: swap.value.unwrap_or_default(),
+        data: swap.data.clone().unwrap_or_default(),
+    }));
+
+    calls.extend(intents.iter().map(|intent| {
+        if intent.dest_token == Address::zero() {
+            Call { target: intent.user, value: intent.min_dest_amount, data: Bytes::default() }
+        } else {
+            Call {
+                target: intent.dest_token,
+                value: U256::zero(),
+                data: erc20_call("transfer(address,uint256)", &[
+                    Token::Address(intent.user),
+                    Token::Uint(intent.min_dest_amount),
+                ]),
+            }
+        }
+    }));
+    calls
+}
+
+/// Calldata of `execute((address,uint256,bytes)[])` on the settler
+pub fn encode_execute(calls: &[Call]) -> Bytes {
+    let selector = keccak256(b"execute((address,uint256,bytes)[])");
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend(abi::encode(&[Token::Array(
+        calls
+            .iter()
+            .map(|call| {
+                Token::Tuple(vec![
+                    Token::Address(call.target),
+                    Token::Uint(call.value),
+                    Token::Bytes(call.data.to_vec()),
+                ])
+            })
+            .collect(),
+    )]));
+    calldata.into()
+}
+
+/// Native value the settler needs for `calls`
+pub fn total_value(calls: &[Call]) -> U256 {
+    calls.iter().fold(U256::zero(), |acc, call| acc + call.value)
+}
+
+/// Even share of `gas_used` per intent; the remainder goes to the first ones
+pub fn split_gas(gas_used: U256, intents: usize) -> Vec<U256> {
+    if intents == 0 {
+        return Vec::new();
+    }
+    let count = U256::from(intents);
+    let (share, remainder) = (gas_used / count, (gas_used % count).as_usize());
+    (0..intents).map(|i| if i < remainder { share + 1 } else { share }).collect()
+}
+
+/// Where a batch is added
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Enqueued {
+    /// Started a new batch; its collect window begins now
+    Opened,
+    Joined,
+    /// Filled the batch; it should be sent right away
+    Full,
+}
+
+/// Won intents waiting to be settled, per batch key
+pub struct BatchQueue {
+    max_batch_size: usize,
+    window: Duration,
+    pending: Mutex<HashMap<BatchKey, Vec<H256>>>,
+}
+
+impl BatchQueue {
+    pub fn new(config: &BatchConfig) -> Self {
+        Self {
+            max_batch_size: config.max_batch_size.max(1),
+            window: Duration::from_millis(config.collect_window_ms),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    pub async fn push(&self, key: BatchKey, intent_id: H256) -> Enqueued {
+        let mut pending = self.pending.lock().await;
+        let batch = pending.entry(key).or_default();
+        batch.push(intent_id);
+        match batch.len() {
+            len if len >= self.max_batch_size => Enqueued::Full,
+            1 => Enqueued::Opened,
+            _ => Enqueued::Joined,
+        }
+    }
+
+    /// Remove and return the intents collected under `key`
+    pub async fn take(&self, key: BatchKey) -> Vec<H256> {
+        self.pending.lock().await.remove(&key).unwrap_or_default()
+    }
+}
+
+fn erc20_call(signature: &str, args: &[Token]) -> Bytes {
+    let selector = keccak256(signature.as_bytes());
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend(abi::encode(args));
+    calldata.into()
+}
+
+fn swap_target(swap: &TransactionRequest) -> Address {
+    swap.to.as_ref().and_then(|to| to.as_address().copied()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent(dest_chain_id: u64, dest_token: Address) -> Intent {
+        Intent {
+            user: Address::repeat_byte(9),
+            source_chain_id: 1,
+            dest_chain_id,
+            source_token: Address::repeat_byte(1),
+            dest_token,
+            source_amount: U256::from(100),
+            min_dest_amount: U256::from(95),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_group_intents_by_chain_and_direction() {
+        let usdc = Address::repeat_byte(2);
+        let dai = Address::repeat_byte(3);
+        let intents = vec![
+            (H256::repeat_byte(1), intent(1, usdc)),
+            (H256::repeat_byte(2), intent(1, dai)),
+            (H256::repeat_byte(3), intent(137, usdc)),
+            (H256::repeat_byte(4), intent(1, usdc)),
+            (H256::repeat_byte(5), intent(1, usdc)),
+        ];
+
+        let groups: Vec<Vec<H256>> = group_intents(intents, 2)
+            .into_iter()
+            .map(|group| group.into_iter().map(|(id, _)| id).collect())
+            .collect();
+        assert_eq!(groups, vec![
+            vec![H256::repeat_byte(1), H256::repeat_byte(4)],
+            vec![H256::repeat_byte(2)],
+            vec![H256::repeat_byte(3)],
+            vec![H256::repeat_byte(5)],
+        ]);
+    }
+
+    #[test]
+    fn test_settlement_shares_approvals_and_gas() {
+        let router = Address::repeat_byte(7);
+        let settler = Address::repeat_byte(8);
+        let intents = vec![intent(1, Address::repeat_byte(2)); 3];
+        let swaps = vec![TransactionRequest::new().to(router).data(vec![1u8]); 3];
+
+        let calls = settlement_calls(Address::repeat_byte(6), settler, &intents, &swaps);
+        // One pull, one approval, three swaps, three payouts
+        assert_eq!(calls.len(), 8);
+        assert_eq!(calls[1].data, erc20_call("approve(address,uint256)", &[
+            Token::Address(router),
+            Token::Uint(U256::from(300)),
+        ]));
+        assert_eq!(total_value(&calls), U256::zero());
+        assert_eq!(&encode_execute(&calls)[..4], &keccak256(b"execute((address,uint256,bytes)[])")[..4]);
+
+        let shares = split_gas(U256::from(100_001), 3);
+        assert_eq!(shares, vec![U256::from(33_334), U256::from(33_334), U256::from(33_333)]);
+        assert_eq!(shares.iter().fold(U256::zero(), |acc, share| acc + share), U256::from(100_001));
+    }
+}
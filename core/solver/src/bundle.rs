@@ -0,0 +1,352 @@
+//! Private bundle submission for solver executions.
+//!
+//! Execution transactions (approve, swap, settle) are signed together and
+//! sent to a private relay so they land atomically and never sit in the
+//! public mempool. Flashbots-style relays receive `eth_sendBundle`, MEV-Share
+//! relays `mev_sendBundle`, and Protect-style RPCs one
+//! `eth_sendPrivateTransaction` per transaction. A bundle is resubmitted for
+//! each new block until it lands or `public_fallback_after` passes, after
+//! which the transactions are broadcast publicly.
+
+use crate::{Result, SolverError};
+use ethers::{
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{transaction::eip2718::TypedTransaction, BlockNumber, Bytes, TransactionRequest, H256, U256, U64},
+    utils::keccak256,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+
+/// Gas limit for bundle transactions that can't be estimated on their own,
+/// e.g. a swap that depends on an approve earlier in the bundle
+const DEFAULT_BUNDLE_TX_GAS: u64 = 500_000;
+
+/// Blocks a private transaction stays valid for
+const PRIVATE_TX_BLOCK_WINDOW: u64 = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelayKind {
+    /// `eth_sendBundle`, e.g. the Flashbots relay
+    Flashbots,
+    /// `mev_sendBundle` on MEV-Share
+    MevShare,
+    /// `eth_sendPrivateTransaction`, e.g. Flashbots Protect
+    Protect,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayConfig {
+    pub chain_id: u64,
+    pub kind: RelayKind,
+    pub url: String,
+    /// Key signing relay requests; a random one is used if unset
+    #[serde(default)]
+    pub auth_key: Option<String>,
+    /// Give up on the relay and broadcast publicly after this long
+    #[serde(with = "secs", default = "default_public_fallback")]
+    pub public_fallback_after: Duration,
+}
+
+fn default_public_fallback() -> Duration {
+    Duration::from_secs(60)
+}
+
+mod secs {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_secs())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_secs)
+    }
+}
+
+/// How a bundle ended up on chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Submission {
+    Private,
+    Public,
+}
+
+#[derive(Debug, Clone)]
+pub struct BundleOutcome {
+    pub tx_hashes: Vec<H256>,
+    pub submission: Submission,
+}
+
+struct Relay {
+    config: RelayConfig,
+    auth: LocalWallet,
+}
+
+pub struct BundleSubmitter {
+    client: reqwest::Client,
+    relays: HashMap<u64, Relay>,
+}
+
+impl BundleSubmitter {
+    pub fn new(relays: &[RelayConfig]) -> Result<Self> {
+        let relays = relays
+            .iter()
+            .map(|config| {
+                let auth = match &config.auth_key {
+                    Some(key) => key
+                        .parse::<LocalWallet>()
+                        .map_err(|e| SolverError::ExecutionFailed(format!("Invalid relay auth key: {}", e)))?,
+                    None => LocalWallet::new(&mut rand::thread_rng()),
+                };
+                Ok((config.chain_id, Relay { config: config.clone(), auth }))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            relays,
+        })
+    }
+
+    pub fn has_relay(&self, chain_id: u64) -> bool {
+        self.relays.contains_key(&chain_id)
+    }
+
+    /// Sign `txs` with consecutive nonces and get them included in order,
+    /// privately if a relay serves `chain_id`
+    pub async fn submit(
+        &self,
+        chain_id: u64,
+        client: &SignerMiddleware<Arc<Provider<Http>>, LocalWallet>,
+        txs: Vec<TransactionRequest>,
+    ) -> Result<BundleOutcome> {
+        let signed = sign_bundle(client, txs).await?;
+        let tx_hashes: Vec<H256> = signed.iter().map(|raw| H256::from(keccak256(raw))).collect();
+
+        if let Some(relay) = self.relays.get(&chain_id) {
+            if self.submit_private(relay, client.inner(), &signed, &tx_hashes).await? {
+                return Ok(BundleOutcome { tx_hashes, submission: Submission::Private });
+            }
+            warn!(
+                "Bundle not included via {:?} relay within {:?}, broadcasting publicly",
+                relay.config.kind, relay.config.public_fallback_after
+            );
+        }
+
+        for raw in &signed {
+            client
+                .inner()
+                .send_raw_transaction(raw.clone())
+                .await
+                .map_err(|e| SolverError::ExecutionFailed(format!("Failed to broadcast transaction: {}", e)))?;
+        }
+        Ok(BundleOutcome { tx_hashes, submission: Submission::Public })
+    }
+
+    /// Resubmit for each block until the last transaction is mined or the
+    /// fallback deadline passes. Returns whether the bundle landed.
+    async fn submit_private(
+        &self,
+        relay: &Relay,
+        provider: &Provider<Http>,
+        signed: &[Bytes],
+        tx_hashes: &[H256],
+    ) -> Result<bool> {
+        let deadline = Instant::now() + relay.config.public_fallback_after;
+        let last_hash = *tx_hashes.last().ok_or_else(|| SolverError::ExecutionFailed("Empty bundle".to_string()))?;
+        let mut submitted_for = 0;
+
+        while Instant::now() < deadline {
+            if provider
+                .get_transaction_receipt(last_hash)
+                .await
+                .map_err(|e| SolverError::ExecutionFailed(format!("Failed to get receipt: {}", e)))?
+                .is_some()
+            {
+                info!("Bundle {:?} included via {:?} relay", last_hash, relay.config.kind);
+                return Ok(true);
+            }
+
+            let current = provider
+                .get_block_number()
+                .await
+                .map_err(|e| SolverError::ExecutionFailed(format!("Failed to get block number: {}", e)))?
+                .as_u64();
+            let target = current + 1;
+
+            // Protect-style relays keep retrying on their own; bundles
+            // target a single block and must be resent for the next one
+            let resend = match relay.config.kind {
+                RelayKind::Protect => submitted_for == 0,
+                RelayKind::Flashbots | RelayKind::MevShare => submitted_for < target,
+            };
+            if resend {
+                for request in relay_requests(relay.config.kind, signed, target) {
+                    if let Err(e) = self.send(relay, &request).await {
+                        warn!("{:?} relay rejected bundle for block {}: {}", relay.config.kind, target, e);
+                    }
+                }
+                submitted_for = target;
+                debug!("Submitted bundle {:?} for block {}", last_hash, target);
+            }
+
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        }
+
+        Ok(false)
+    }
+
+    async fn send(&self, relay: &Relay, request: &Value) -> Result<()> {
+        let body = request.to_string();
+        let signature = flashbots_signature(&relay.auth, body.as_bytes()).await?;
+
+        let response: Value = self
+            .client
+            .post(&relay.config.url)
+            .header("Content-Type", "application/json")
+            .header("X-Flashbots-Signature", signature)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Relay request failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Invalid relay response: {}", e)))?;
+
+        match response.get("error") {
+            Some(error) => Err(SolverError::ExecutionFailed(format!("Relay error: {}", error))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Sign `txs` with consecutive pending nonces
+async fn sign_bundle(
+    client: &SignerMiddleware<Arc<Provider<Http>>, LocalWallet>,
+    txs: Vec<TransactionRequest>,
+) -> Result<Vec<Bytes>> {
+    let from = client.address();
+    let nonce = client
+        .get_transaction_count(from, Some(BlockNumber::Pending.into()))
+        .await
+        .map_err(|e| SolverError::ExecutionFailed(format!("Failed to get nonce: {}", e)))?;
+
+    let mut signed = Vec::with_capacity(txs.len());
+    for (i, tx) in txs.into_iter().enumerate() {
+        let mut tx: TypedTransaction = tx.from(from).nonce(nonce + U256::from(i)).into();
+        if tx.gas().is_none() {
+            let gas = client.estimate_gas(&tx, None).await.unwrap_or_else(|_| U256::from(DEFAULT_BUNDLE_TX_GAS));
+            tx.set_gas(gas);
+        }
+        client
+            .fill_transaction(&mut tx, None)
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Failed to fill transaction: {}", e)))?;
+
+        let signature = client
+            .signer()
+            .sign_transaction(&tx)
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Failed to sign transaction: {}", e)))?;
+        signed.push(tx.rlp_signed(&signature));
+    }
+    Ok(signed)
+}
+
+/// JSON-RPC requests submitting `signed` to a relay of `kind` for `block`
+fn relay_requests(kind: RelayKind, signed: &[Bytes], block: u64) -> Vec<Value> {
+    let txs: Vec<String> = signed.iter().map(|raw| format!("{}", raw)).collect();
+    let block_hex = format!("{:#x}", U64::from(block));
+
+    match kind {
+        RelayKind::Flashbots => vec![json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_sendBundle",
+            "params": [{ "txs": txs, "blockNumber": block_hex }],
+        })],
+        RelayKind::MevShare => vec![json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "mev_sendBundle",
+            "params": [{
+                "version": "v0.1",
+                "inclusion": { "block": block_hex },
+                "body": txs.iter().map(|tx| json!({ "tx": tx, "canRevert": false })).collect::<Vec<_>>(),
+            }],
+        })],
+        RelayKind::Protect => txs
+            .iter()
+            .map(|tx| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "eth_sendPrivateTransaction",
+                    "params": [{
+                        "tx": tx,
+                        "maxBlockNumber": format!("{:#x}", U64::from(block + PRIVATE_TX_BLOCK_WINDOW)),
+                    }],
+                })
+            })
+            .collect(),
+    }
+}
+
+/// `X-Flashbots-Signature` header: the auth address and its signature over
+/// the hex-encoded keccak of the body
+async fn flashbots_signature(auth: &LocalWallet, body: &[u8]) -> Result<String> {
+    let digest = format!("0x{}", hex::encode(keccak256(body)));
+    let signature = auth
+        .sign_message(digest)
+        .await
+        .map_err(|e| SolverError::ExecutionFailed(format!("Failed to sign relay request: {}", e)))?;
+    Ok(format!("{:?}:0x{}", auth.address(), signature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relay_requests() {
+        let signed = vec![Bytes::from(vec![0x01]), Bytes::from(vec![0x02])];
+
+        let bundle = &relay_requests(RelayKind::Flashbots, &signed, 16)[0];
+        assert_eq!(bundle["method"], "eth_sendBundle");
+        assert_eq!(bundle["params"][0]["txs"], json!(["0x01", "0x02"]));
+        assert_eq!(bundle["params"][0]["blockNumber"], "0x10");
+
+        let share = &relay_requests(RelayKind::MevShare, &signed, 16)[0];
+        assert_eq!(share["params"][0]["body"][1]["tx"], "0x02");
+
+        let private = relay_requests(RelayKind::Protect, &signed, 16);
+        assert_eq!(private.len(), 2);
+        assert_eq!(private[0]["params"][0]["maxBlockNumber"], format!("{:#x}", 16 + PRIVATE_TX_BLOCK_WINDOW));
+    }
+
+    #[tokio::test]
+    async fn test_flashbots_signature_recovers_to_auth_address() {
+        let auth = LocalWallet::new(&mut rand::thread_rng());
+        let body = br#"{"method":"eth_sendBundle"}"#;
+
+        let header = flashbots_signature(&auth, body).await.unwrap();
+        let (address, signature) = header.split_once(':').unwrap();
+        assert_eq!(address, format!("{:?}", auth.address()));
+
+        let signature: ethers::types::Signature = signature.parse().unwrap();
+        let digest = format!("0x{}", hex::encode(keccak256(body)));
+        assert_eq!(signature.recover(digest).unwrap(), auth.address());
+    }
+}
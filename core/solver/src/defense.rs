@@ -0,0 +1,503 @@
+//! Slashing defense.
+//!
+//! An executed intent can be challenged on the intents contract until its
+//! challenge window closes; a challenged solver is slashed unless it answers
+//! with `defendExecution` before the defense window ends. The
+//! [`SlashingDefense`] archives everything needed for that answer when an
+//! execution completes — the execution proof committed on-chain, the
+//! transaction receipts of every leg and, for cross-chain intents, the
+//! bridge attestation — and watches each intents contract for
+//! `IntentChallenged` against this solver, submitting the defense from the
+//! archive as soon as a challenge appears. The contract only accepts a
+//! defense carrying a delivery receipt signed by one of its trusted
+//! attesters, so a fresh receipt is requested from the configured attester
+//! for every defense.
+
+use crate::{executor::SolverExecutor, Result, SolverError};
+use async_trait::async_trait;
+use ethers::{
+    abi::{self, Token},
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::Signer,
+    types::{Address, Filter, TransactionReceipt, TransactionRequest, H256, U256},
+};
+use intents_bridge::CrossChainProof;
+use intents_engine::intent::{Intent, IntentExecution};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// Blocks scanned per `eth_getLogs` request
+const MAX_LOG_RANGE: u64 = 2000;
+
+/// Packed delivery receipt the intents contract verifies: delivered amount,
+/// observation time and a 65-byte attester signature
+const DELIVERY_RECEIPT_LEN: usize = 32 + 32 + 65;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DefenseConfig {
+    /// Directory execution records are archived to, one file per intent
+    pub archive_dir: PathBuf,
+    /// Intents contract watched for challenges per chain
+    pub intents_contracts: HashMap<u64, Address>,
+    pub poll_interval_secs: u64,
+    /// Attester that signs delivery receipts trusted by the intents contracts
+    pub attester_url: Option<String>,
+}
+
+impl Default for DefenseConfig {
+    fn default() -> Self {
+        Self {
+            archive_dir: PathBuf::from("./execution-archive"),
+            intents_contracts: HashMap::new(),
+            poll_interval_secs: 12,
+            attester_url: None,
+        }
+    }
+}
+
+/// Everything archived about one execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionRecord {
+    pub intent_id: H256,
+    /// Chain whose intents contract holds the intent
+    pub chain_id: u64,
+    pub solver: Address,
+    pub dest_amount: U256,
+    /// Proof whose hash was committed with the execution
+    pub proof: Vec<u8>,
+    pub receipts: Vec<TransactionReceipt>,
+    /// Delivery of the bridged funds on the destination chain
+    pub attestation: Option<CrossChainProof>,
+    pub archived_at: u64,
+}
+
+/// Body of a delivery receipt request to the attester
+#[derive(Debug, Serialize)]
+struct ReceiptRequest {
+    chain_id: u64,
+    contract: Address,
+    intent_id: H256,
+    proof_hash: H256,
+    dest_amount: U256,
+    /// Destination delivery the attester should observe, when bridged
+    attestation: Option<CrossChainProof>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReceiptResponse {
+    /// Hex-encoded packed receipt
+    receipt: String,
+}
+
+#[async_trait]
+pub trait ExecutionArchive: Send + Sync {
+    async fn put(&self, record: &ExecutionRecord) -> Result<()>;
+    async fn get(&self, intent_id: H256) -> Result<Option<ExecutionRecord>>;
+}
+
+#[derive(Default)]
+pub struct MemoryExecutionArchive {
+    records: RwLock<HashMap<H256, ExecutionRecord>>,
+}
+
+impl MemoryExecutionArchive {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ExecutionArchive for MemoryExecutionArchive {
+    async fn put(&self, record: &ExecutionRecord) -> Result<()> {
+        self.records.write().await.insert(record.intent_id, record.clone());
+        Ok(())
+    }
+
+    async fn get(&self, intent_id: H256) -> Result<Option<ExecutionRecord>> {
+        Ok(self.records.read().await.get(&intent_id).cloned())
+    }
+}
+
+/// JSON file per intent. Records are written to a temporary file and
+/// renamed into place, so a crash never leaves a partial record behind.
+pub struct FileExecutionArchive {
+    dir: PathBuf,
+}
+
+impl FileExecutionArchive {
+    pub async fn open(dir: PathBuf) -> Result<Self> {
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|e| SolverError::StorageError(format!("Failed to create archive directory: {}", e)))?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, intent_id: H256) -> PathBuf {
+        self.dir.join(format!("{:x}.json", intent_id))
+    }
+}
+
+#[async_trait]
+impl ExecutionArchive for FileExecutionArchive {
+    async fn put(&self, record: &ExecutionRecord) -> Result<()> {
+        let contents = serde_json::to_vec_pretty(record)
+            .map_err(|e| SolverError::StorageError(e.to_string()))?;
+        let path = self.path(record.intent_id);
+        let staging = path.with_extension("json.tmp");
+
+        tokio::fs::write(&staging, contents)
+            .await
+            .map_err(|e| SolverError::StorageError(format!("Failed to write execution record: {}", e)))?;
+        tokio::fs::rename(&staging, &path)
+            .await
+            .map_err(|e| SolverError::StorageError(format!("Failed to store execution record: {}", e)))
+    }
+
+    async fn get(&self, intent_id: H256) -> Result<Option<ExecutionRecord>> {
+        let contents = match tokio::fs::read(self.path(intent_id)).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(SolverError::StorageError(format!("Failed to read execution record: {}", e))),
+        };
+        serde_json::from_slice(&contents)
+            .map(Some)
+            .map_err(|e| SolverError::StorageError(format!("Corrupt execution record: {}", e)))
+    }
+}
+
+/// A challenge seen on-chain and what was done about it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChallengeDefense {
+    pub intent_id: H256,
+    pub chain_id: u64,
+    pub challenger: Address,
+    /// Defense transaction, if one was submitted
+    pub tx_hash: Option<H256>,
+    pub error: Option<String>,
+    pub handled_at: u64,
+}
+
+pub struct SlashingDefense {
+    config: DefenseConfig,
+    client: reqwest::Client,
+    archive: Arc<dyn ExecutionArchive>,
+    executor: Arc<SolverExecutor>,
+    handled: RwLock<HashSet<H256>>,
+    defenses: RwLock<Vec<ChallengeDefense>>,
+}
+
+impl SlashingDefense {
+    pub fn new(config: DefenseConfig, archive: Arc<dyn ExecutionArchive>, executor: Arc<SolverExecutor>) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+            archive,
+            executor,
+            handled: RwLock::new(HashSet::new()),
+            defenses: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Challenges handled so far
+    pub async fn defenses(&self) -> Vec<ChallengeDefense> {
+        self.defenses.read().await.clone()
+    }
+
+    /// Archive a completed execution of `intent`
+    pub async fn archive(&self, intent: &Intent, execution: &IntentExecution) -> Result<()> {
+        let mut receipts = Vec::new();
+        for (chain_id, tx_hash) in [
+            (intent.source_chain_id, execution.source_tx_hash),
+            (intent.dest_chain_id, execution.dest_tx_hash),
+        ] {
+            if tx_hash.is_zero() || receipts.iter().any(|r: &TransactionReceipt| r.transaction_hash == tx_hash) {
+                continue;
+            }
+            if let Some(receipt) = self.receipt(chain_id, tx_hash).await? {
+                receipts.push(receipt);
+            }
+        }
+
+        // The destination receipt attests the bridged funds arrived
+        let attestation = if intent.source_chain_id != intent.dest_chain_id {
+            receipts
+                .iter()
+                .find(|receipt| receipt.transaction_hash == execution.dest_tx_hash)
+                .map(|receipt| CrossChainProof {
+                    block_height: receipt.block_number.unwrap_or_default().as_u64(),
+                    block_hash: receipt.block_hash.unwrap_or_default().0,
+                    tx_hash: receipt.transaction_hash.0,
+                    merkle_proof: Vec::new(),
+                    proof_data: HashMap::new(),
+                })
+        } else {
+            None
+        };
+
+        self.archive
+            .put(&ExecutionRecord {
+                intent_id: execution.intent_id,
+                chain_id: intent.source_chain_id,
+                solver: execution.solver,
+                dest_amount: execution.dest_amount,
+                proof: execution.execution_proof.clone(),
+                receipts,
+                attestation,
+                archived_at: current_timestamp(),
+            })
+            .await
+    }
+
+    /// Watch every configured intents contract for challenges until dropped
+    pub async fn run(self: Arc<Self>) {
+        for (&chain_id, &contract) in &self.config.intents_contracts {
+            match self.executor.providers().get(&chain_id) {
+                Some(provider) => {
+                    tokio::spawn(self.clone().watch_chain(chain_id, provider.clone(), contract));
+                }
+                None => warn!("No provider for chain {}; not watching for challenges", chain_id),
+            }
+        }
+    }
+
+    async fn watch_chain(self: Arc<Self>, chain_id: u64, provider: Arc<Provider<Http>>, contract: Address) {
+        let topic = H256::from(ethers::utils::keccak256(b"IntentChallenged(bytes32,address,bytes32)"));
+        let mut next_block = None;
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.poll_interval_secs));
+
+        loop {
+            interval.tick().await;
+
+            let latest = match provider.get_block_number().await {
+                Ok(block) => block.as_u64(),
+                Err(e) => {
+                    warn!("Failed to get block number on chain {}: {}", chain_id, e);
+                    continue;
+                }
+            };
+            let from = *next_block.get_or_insert(latest);
+            if from > latest {
+                continue;
+            }
+            let to = latest.min(from + MAX_LOG_RANGE - 1);
+
+            let filter = Filter::new().address(contract).topic0(topic).from_block(from).to_block(to);
+            let logs = match provider.get_logs(&filter).await {
+                Ok(logs) => logs,
+                Err(e) => {
+                    warn!("Failed to get IntentChallenged logs on chain {}: {}", chain_id, e);
+                    continue;
+                }
+            };
+
+            for log in logs {
+                let (Some(&intent_id), Some(&challenger)) = (log.topics.get(1), log.topics.get(2)) else {
+                    continue;
+                };
+                self.handle_challenge(chain_id, contract, intent_id, Address::from(challenger)).await;
+            }
+            next_block = Some(to + 1);
+        }
+    }
+
+    /// Defend `intent_id` if this solver executed it and it was not handled yet
+    async fn handle_challenge(&self, chain_id: u64, contract: Address, intent_id: H256, challenger: Address) {
+        let record = match self.archive.get(intent_id).await {
+            Ok(Some(record)) if record.solver == self.executor.address() => record,
+            Ok(_) => return,
+            Err(e) => {
+                warn!("Failed to load execution record for challenged intent {:?}: {}", intent_id, e);
+                return;
+            }
+        };
+        if !self.handled.write().await.insert(intent_id) {
+            return;
+        }
+
+        warn!("Execution of intent {:?} challenged by {:?}; submitting defense", intent_id, challenger);
+        let result = self.submit_defense(chain_id, contract, &record).await;
+        match &result {
+            Ok(tx_hash) => info!("Defended intent {:?} in {:?}", intent_id, tx_hash),
+            Err(e) => {
+                warn!("Defense of intent {:?} failed: {}", intent_id, e);
+                // Let the next challenge log for this intent retry
+                self.handled.write().await.remove(&intent_id);
+            }
+        }
+
+        self.defenses.write().await.push(ChallengeDefense {
+            intent_id,
+            chain_id,
+            challenger,
+            tx_hash: result.as_ref().ok().copied(),
+            error: result.err().map(|e| e.to_string()),
+            handled_at: current_timestamp(),
+        });
+    }
+
+    async fn submit_defense(&self, chain_id: u64, contract: Address, record: &ExecutionRecord) -> Result<H256> {
+        let provider = self.executor
+            .providers()
+            .get(&chain_id)
+            .cloned()
+            .ok_or(SolverError::ChainNotSupported(chain_id))?;
+        let receipt = self.delivery_receipt(chain_id, contract, record).await?;
+        let client = SignerMiddleware::new(provider, self.executor.signer()?.with_chain_id(chain_id));
+
+        let tx = TransactionRequest::new()
+            .to(contract)
+            .data(defense_calldata(record, &receipt));
+        let pending = client
+            .send_transaction(tx, None)
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Failed to send defense: {}", e)))?;
+        let tx_hash = pending.tx_hash();
+
+        match pending.await {
+            Ok(Some(receipt)) if receipt.status == Some(1u64.into()) => Ok(tx_hash),
+            Ok(_) => Err(SolverError::ExecutionFailed(format!("Defense {:?} reverted", tx_hash))),
+            Err(e) => Err(SolverError::ExecutionFailed(format!("Defense {:?} not confirmed: {}", tx_hash, e))),
+        }
+    }
+
+    /// Ask the attester for a receipt of the delivery, observed now so it
+    /// postdates the challenge's evidence
+    async fn delivery_receipt(&self, chain_id: u64, contract: Address, record: &ExecutionRecord) -> Result<Vec<u8>> {
+        let url = self.config.attester_url.as_ref().ok_or_else(|| {
+            SolverError::ExecutionFailed("No attester configured to sign a delivery receipt".to_string())
+        })?;
+        let request = ReceiptRequest {
+            chain_id,
+            contract,
+            intent_id: record.intent_id,
+            proof_hash: H256::from(ethers::utils::keccak256(&record.proof)),
+            dest_amount: record.dest_amount,
+            attestation: record.attestation.clone(),
+        };
+
+        let response: ReceiptResponse = self.client
+            .post(format!("{}/v1/receipts", url.trim_end_matches('/')))
+            .json(&request)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| SolverError::ExecutionFailed(format!("Attester request failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Invalid attester response: {}", e)))?;
+        decode_receipt(&response.receipt)
+    }
+
+    async fn receipt(&self, chain_id: u64, tx_hash: H256) -> Result<Option<TransactionReceipt>> {
+        let provider = self.executor
+            .providers()
+            .get(&chain_id)
+            .ok_or(SolverError::ChainNotSupported(chain_id))?;
+        provider
+            .get_transaction_receipt(tx_hash)
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Failed to get receipt {:?}: {}", tx_hash, e)))
+    }
+}
+
+fn decode_receipt(receipt: &str) -> Result<Vec<u8>> {
+    let bytes = hex::decode(receipt.trim_start_matches("0x"))
+        .map_err(|e| SolverError::ExecutionFailed(format!("Invalid delivery receipt: {}", e)))?;
+    if bytes.len() != DELIVERY_RECEIPT_LEN {
+        return Err(SolverError::ExecutionFailed(format!(
+            "Delivery receipt is {} bytes, expected {}",
+            bytes.len(),
+            DELIVERY_RECEIPT_LEN
+        )));
+    }
+    Ok(bytes)
+}
+
+/// `defendExecution(bytes32,bytes,bytes)` calldata for an archived execution
+/// and a signed delivery receipt
+fn defense_calldata(record: &ExecutionRecord, receipt: &[u8]) -> Vec<u8> {
+    let selector = ethers::utils::keccak256(b"defendExecution(bytes32,bytes,bytes)");
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend(abi::encode(&[
+        Token::FixedBytes(record.intent_id.as_bytes().to_vec()),
+        Token::Bytes(record.proof.clone()),
+        Token::Bytes(receipt.to_vec()),
+    ]));
+    calldata
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::ParamType;
+
+    fn record(intent_id: H256) -> ExecutionRecord {
+        ExecutionRecord {
+            intent_id,
+            chain_id: 42161,
+            solver: Address::repeat_byte(0x5a),
+            dest_amount: U256::from(990),
+            proof: vec![7u8; 32],
+            receipts: Vec::new(),
+            attestation: Some(CrossChainProof {
+                block_height: 100,
+                block_hash: [2u8; 32],
+                tx_hash: [3u8; 32],
+                merkle_proof: Vec::new(),
+                proof_data: HashMap::new(),
+            }),
+            archived_at: 1_700_000_000,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_archive_round_trip() {
+        let dir = std::env::temp_dir().join(format!("execution-archive-{}", std::process::id()));
+        let archive = FileExecutionArchive::open(dir.clone()).await.unwrap();
+        let intent_id = H256::repeat_byte(1);
+
+        assert!(archive.get(intent_id).await.unwrap().is_none());
+        archive.put(&record(intent_id)).await.unwrap();
+
+        // A fresh handle on the same directory sees the record
+        let reopened = FileExecutionArchive::open(dir.clone()).await.unwrap();
+        let stored = reopened.get(intent_id).await.unwrap().unwrap();
+        assert_eq!(stored.proof, vec![7u8; 32]);
+        assert_eq!(stored.attestation.unwrap().block_height, 100);
+
+        tokio::fs::remove_dir_all(dir).await.ok();
+    }
+
+    #[test]
+    fn test_defense_calldata() {
+        let record = record(H256::repeat_byte(1));
+        let receipt = decode_receipt(&format!("0x{}", "ab".repeat(DELIVERY_RECEIPT_LEN))).unwrap();
+        assert!(decode_receipt(&"ab".repeat(DELIVERY_RECEIPT_LEN - 1)).is_err());
+        let calldata = defense_calldata(&record, &receipt);
+        assert_eq!(calldata[..4], ethers::utils::keccak256(b"defendExecution(bytes32,bytes,bytes)")[..4]);
+
+        let tokens = abi::decode(
+            &[ParamType::FixedBytes(32), ParamType::Bytes, ParamType::Bytes],
+            &calldata[4..],
+        )
+        .unwrap();
+        assert_eq!(tokens[1].clone().into_bytes().unwrap(), record.proof);
+        assert_eq!(tokens[2].clone().into_bytes().unwrap(), receipt);
+    }
+}
@@ -3,7 +3,12 @@
 //! This module implements the core execution logic for cross-chain intent fulfillment,
 //! including transaction execution, bridge operations, error recovery, and MEV protection.
 
-use crate::{Result, SolverError, SolverConfig};
+use crate::{
+    batch,
+    bundle::{BundleSubmitter, Submission},
+    pipeline::{self, Admission, ChainPools, ExecutionRegistry, ExecutionTrace, PipelineConfig, TraceKind},
+    Result, SolverError, SolverConfig,
+};
 use async_trait::async_trait;
 use ethers::{
     middleware::SignerMiddleware,
@@ -12,7 +17,10 @@ use ethers::{
     signers::{LocalWallet, Signer},
     types::{Address, TransactionRequest, U256, H256},
 };
-use intents_engine::intent::{Intent, IntentExecution};
+use intents_engine::{
+    intent::{Intent, IntentExecution},
+    simulation::{self, SimulationResult},
+};
 use intents_bridge::{Bridge, BridgeManager, BridgeProtocol, CrossChainMessage, CrossChainProof};
 use serde::{Deserialize, Serialize};
 use std::{
@@ -21,20 +29,11 @@ use std::{
     time::{Duration, Instant},
 };
 use tokio::{
-    sync::{RwLock, Semaphore},
+    sync::RwLock,
     time::{timeout, sleep},
 };
 use tracing::{error, info, warn, debug, instrument};
 
-/// Maximum number of concurrent executions
-const MAX_CONCURRENT_EXECUTIONS: usize = 10;
-
-/// Maximum retry attempts for failed operations
-const MAX_RETRY_ATTEMPTS: usize = 3;
-
-/// Base delay for exponential backoff (milliseconds)
-const RETRY_BASE_DELAY_MS: u64 = 1000;
-
 /// Maximum execution timeout (5 minutes)
 const EXECUTION_TIMEOUT: Duration = Duration::from_secs(300);
 
@@ -43,7 +42,7 @@ const MEV_PROTECTION_MIN_DELAY: u64 = 2;
 const MEV_PROTECTION_MAX_DELAY: u64 = 8;
 
 /// Execution step for detailed tracking
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ExecutionStep {
     ValidatingIntent,
     LockingSourceAssets,
@@ -71,6 +70,10 @@ pub struct ExecutionContext {
     pub bridge_tx_hash: Option<H256>,
     pub dest_tx_hash: Option<H256>,
     pub locked_assets: HashMap<Address, U256>,
+    /// Source leg result, kept so a retry resumes after it
+    pub source_result: Option<ExecutionResult>,
+    /// Pre-flight simulation of the source transaction
+    pub simulation: Option<SimulationResult>,
 }
 
 /// Asset lock information for rollback capability
@@ -90,8 +93,10 @@ pub struct SolverExecutor {
     bridge_manager: Arc<BridgeManager>,
     active_executions: Arc<RwLock<HashMap<H256, ExecutionContext>>>,
     asset_locks: Arc<RwLock<HashMap<H256, Vec<AssetLock>>>>,
-    execution_semaphore: Arc<Semaphore>,
+    pools: ChainPools,
+    registry: Arc<RwLock<ExecutionRegistry>>,
     mev_protection_enabled: bool,
+    bundle_submitter: Option<Arc<BundleSubmitter>>,
     performance_metrics: Arc<RwLock<ExecutionMetrics>>,
 }
 
@@ -106,6 +111,8 @@ pub struct ExecutionMetrics {
     pub average_execution_time: Duration,
     pub mev_protection_triggers: u64,
     pub rollback_operations: u64,
+    pub private_submissions: u64,
+    pub public_fallbacks: u64,
 }
 
 impl SolverExecutor {
@@ -129,6 +136,15 @@ impl SolverExecutor {
         let mut bridge_manager = BridgeManager::new(BridgeProtocol::LayerZero);
         Self::setup_bridge_protocols(&mut bridge_manager).await?;
 
+        let bundle_submitter = if config.private_relays.is_empty() {
+            None
+        } else {
+            Some(Arc::new(BundleSubmitter::new(&config.private_relays)?))
+        };
+
+        let pools = ChainPools::new(&config.supported_chains, &config.pipeline);
+        let registry = ExecutionRegistry::new(config.pipeline.retained_executions);
+
         Ok(Self {
             config,
             providers,
@@ -136,23 +152,98 @@ impl SolverExecutor {
             bridge_manager: Arc::new(bridge_manager),
             active_executions: Arc::new(RwLock::new(HashMap::new())),
             asset_locks: Arc::new(RwLock::new(HashMap::new())),
-            execution_semaphore: Arc::new(Semaphore::new(MAX_CONCURRENT_EXECUTIONS)),
+            pools,
+            registry: Arc::new(RwLock::new(registry)),
             mev_protection_enabled: true,
+            bundle_submitter,
             performance_metrics: Arc::new(RwLock::new(ExecutionMetrics::default())),
         })
     }
 
-    /// Execute an intent with full error handling and recovery
+    /// Execute an intent with full error handling and recovery. Executing
+    /// an intent that already settled returns the earlier result; executing
+    /// one that is still running fails without touching it.
     #[instrument(skip(self), fields(intent_id = %intent_id))]
     pub async fn execute(&self, intent_id: H256) -> Result<IntentExecution> {
-        // Acquire execution permit to limit concurrency
-        let _permit = self.execution_semaphore.acquire().await
-            .map_err(|e| SolverError::ExecutionFailed(format!("Failed to acquire execution permit: {}", e)))?;
+        if let Some(execution) = self.admit(intent_id).await? {
+            return Ok(execution);
+        }
+        self.execute_admitted(intent_id).await
+    }
 
-        // Execute with timeout protection
-        let result = timeout(EXECUTION_TIMEOUT, self.execute_internal(intent_id)).await;
+    /// Settle intents together where possible. Same-chain intents sharing a
+    /// token pair go out as one transaction through the chain's settler;
+    /// the others, and every intent of a batch whose simulation reverts,
+    /// are executed on their own.
+    pub async fn execute_batch(&self, intent_ids: &[H256]) -> Vec<(H256, Result<IntentExecution>)> {
+        let mut results = Vec::with_capacity(intent_ids.len());
+        let mut admitted = Vec::new();
+        for &intent_id in intent_ids {
+            match self.admit(intent_id).await {
+                Ok(None) => match self.get_matched_intent(intent_id).await {
+                    Ok(intent) => admitted.push((intent_id, intent)),
+                    Err(e) => {
+                        self.registry.write().await.finish(intent_id, Err(&e));
+                        results.push((intent_id, Err(e)));
+                    }
+                },
+                Ok(Some(execution)) => results.push((intent_id, Ok(execution))),
+                Err(e) => results.push((intent_id, Err(e))),
+            }
+        }
 
-        match result {
+        let mut singles = Vec::new();
+        for group in batch::group_intents(admitted, self.config.batching.max_batch_size) {
+            let settler = self.config.batching.settlers.get(&group[0].1.source_chain_id).copied();
+            let Some(settler) = settler.filter(|_| group.len() > 1) else {
+                singles.extend(group.into_iter().map(|(intent_id, _)| intent_id));
+                continue;
+            };
+
+            match self.settle_batch(settler, &group).await {
+                Ok(Some(executions)) => {
+                    for execution in executions {
+                        let intent_id = execution.intent_id;
+                        let result = Ok(execution);
+                        self.update_metrics_on_completion(&result).await;
+                        self.registry.write().await.finish(intent_id, result.as_ref());
+                        results.push((intent_id, result));
+                    }
+                }
+                Ok(None) => singles.extend(group.into_iter().map(|(intent_id, _)| intent_id)),
+                Err(e) => {
+                    error!("Batch of {} intents failed: {}", group.len(), e);
+                    for (intent_id, _) in group {
+                        let result = Err(SolverError::ExecutionFailed(format!("Batch settlement failed: {}", e)));
+                        self.update_metrics_on_completion(&result).await;
+                        self.registry.write().await.finish(intent_id, result.as_ref());
+                        results.push((intent_id, result));
+                    }
+                }
+            }
+        }
+
+        let singles = futures::future::join_all(
+            singles.into_iter().map(|intent_id| async move { (intent_id, self.execute_admitted(intent_id).await) }),
+        )
+        .await;
+        results.extend(singles);
+        results
+    }
+
+    /// Claim `intent_id` for execution; returns the earlier result if it
+    /// already settled
+    async fn admit(&self, intent_id: H256) -> Result<Option<IntentExecution>> {
+        match self.registry.write().await.admit(intent_id) {
+            Admission::Start => Ok(None),
+            Admission::Running => Err(SolverError::ExecutionFailed("Intent is already executing".to_string())),
+            Admission::Settled(execution) => Ok(Some(execution)),
+        }
+    }
+
+    async fn execute_admitted(&self, intent_id: H256) -> Result<IntentExecution> {
+        // Execute with timeout protection
+        let result = match timeout(EXECUTION_TIMEOUT, self.execute_internal(intent_id)).await {
             Ok(execution_result) => {
                 self.update_metrics_on_completion(&execution_result).await;
                 execution_result
@@ -162,10 +253,30 @@ impl SolverExecutor {
                 self.handle_execution_timeout(intent_id).await;
                 Err(SolverError::ExecutionFailed("Execution timeout".to_string()))
             }
-        }
+        };
+
+        self.registry.write().await.finish(intent_id, result.as_ref());
+        result
+    }
+
+    /// Structured trace of an intent's execution, while it runs and for a
+    /// while after it finished
+    pub async fn get_trace(&self, intent_id: H256) -> Option<ExecutionTrace> {
+        self.registry.read().await.trace(intent_id)
+    }
+
+    /// Executions currently holding a worker on `chain_id`
+    pub fn busy_workers(&self, chain_id: u64) -> usize {
+        self.pools.busy(chain_id)
     }
 
-    /// Internal execution logic with comprehensive error handling
+    async fn trace(&self, intent_id: H256, kind: TraceKind) {
+        self.registry.write().await.record(intent_id, kind);
+    }
+
+    /// Internal execution logic with comprehensive error handling. Failed
+    /// attempts are retried with backoff; a retry resumes after the last
+    /// phase that completed, so no transaction is sent twice.
     async fn execute_internal(&self, intent_id: H256) -> Result<IntentExecution> {
         info!("Starting execution for intent {}", intent_id);
 
@@ -186,6 +297,8 @@ impl SolverExecutor {
             bridge_tx_hash: None,
             dest_tx_hash: None,
             locked_assets: HashMap::new(),
+            source_result: None,
+            simulation: None,
         };
 
         // Store execution context
@@ -195,7 +308,25 @@ impl SolverExecutor {
         }
 
         // Execute the intent through all phases
-        let result = self.execute_phases(&mut context).await;
+        let pipeline = &self.config.pipeline;
+        let mut attempt = 1;
+        let result = loop {
+            self.trace(intent_id, TraceKind::AttemptStarted { attempt }).await;
+            match self.execute_phases(&mut context).await {
+                Err(e) if attempt < pipeline.max_attempts && pipeline::is_retryable(&e) => {
+                    let delay = pipeline.retry_delay(attempt);
+                    warn!("Attempt {} for intent {} failed, retrying in {:?}: {}", attempt, intent_id, delay, e);
+                    self.trace(intent_id, TraceKind::Retrying {
+                        attempt,
+                        error: e.to_string(),
+                        delay_ms: delay.as_millis() as u64,
+                    }).await;
+                    sleep(delay).await;
+                    attempt += 1;
+                }
+                result => break result,
+            }
+        };
 
         // Clean up execution context
         {
@@ -217,38 +348,65 @@ impl SolverExecutor {
         }
     }
 
-    /// Execute all phases of intent fulfillment
+    /// Execute all phases of intent fulfillment. Phases already completed by
+    /// an earlier attempt on `context` are skipped. Source-chain phases hold a
+    /// source-chain worker and destination phases a destination-chain worker;
+    /// no worker is held while waiting on the bridge.
     async fn execute_phases(&self, context: &mut ExecutionContext) -> Result<IntentExecution> {
+        let source_chain = context.intent.source_chain_id;
+        let dest_chain = context.intent.dest_chain_id;
+
         // Phase 1: Validate intent and prepare for execution
         self.update_step(context, ExecutionStep::ValidatingIntent).await;
         self.validate_execution_prerequisites(context).await?;
 
-        // Phase 2: Apply MEV protection if enabled
-        if self.mev_protection_enabled {
-            self.apply_mev_protection(context).await?;
-        }
-
-        // Phase 3: Lock source assets
-        self.update_step(context, ExecutionStep::LockingSourceAssets).await;
-        self.lock_source_assets(context).await?;
-
-        // Phase 4: Execute source chain operations
-        self.update_step(context, ExecutionStep::ExecutingSourceSwap).await;
-        let source_result = self.execute_source_operations(context).await?;
+        let source_result = match context.source_result.clone() {
+            Some(source_result) => source_result,
+            None => {
+                // Phase 2: Apply MEV protection if enabled. Chains with a private
+                // relay are protected by bundle submission instead.
+                if self.mev_protection_enabled && self.private_relay(source_chain).is_none() {
+                    self.apply_mev_protection(context).await?;
+                }
+
+                let _worker = self.acquire_worker(context.intent_id, source_chain).await?;
+
+                // Phase 3: Lock source assets
+                if context.locked_assets.is_empty() {
+                    self.update_step(context, ExecutionStep::LockingSourceAssets).await;
+                    self.lock_source_assets(context).await?;
+                }
+
+                // Phase 4: Execute source chain operations
+                self.update_step(context, ExecutionStep::ExecutingSourceSwap).await;
+                let source_result = self.execute_source_operations(context).await?;
+                context.source_result = Some(source_result.clone());
+                source_result
+            }
+        };
 
         // Phase 5: Initiate cross-chain bridge if needed
-        if context.intent.source_chain_id != context.intent.dest_chain_id {
-            self.update_step(context, ExecutionStep::InitiatingBridge).await;
-            self.initiate_bridge_transfer(context, &source_result).await?;
+        if source_chain != dest_chain {
+            if context.bridge_tx_hash.is_none() {
+                let _worker = self.acquire_worker(context.intent_id, source_chain).await?;
+                self.update_step(context, ExecutionStep::InitiatingBridge).await;
+                self.initiate_bridge_transfer(context, &source_result).await?;
+            }
 
             // Phase 6: Wait for bridge confirmation
             self.update_step(context, ExecutionStep::WaitingForBridgeConfirmation).await;
             self.wait_for_bridge_confirmation(context).await?;
         }
 
-        // Phase 7: Execute destination chain operations
-        self.update_step(context, ExecutionStep::ExecutingDestinationSwap).await;
-        let dest_result = self.execute_destination_operations(context).await?;
+        // Phase 7: Execute destination chain operations, unless the source
+        // bundle already settled to the user
+        let dest_result = if context.dest_tx_hash.is_some() {
+            source_result
+        } else {
+            let _worker = self.acquire_worker(context.intent_id, dest_chain).await?;
+            self.update_step(context, ExecutionStep::ExecutingDestinationSwap).await;
+            self.execute_destination_operations(context).await?
+        };
 
         // Phase 8: Final validation and proof generation
         self.update_step(context, ExecutionStep::FinalValidation).await;
@@ -267,9 +425,182 @@ impl SolverExecutor {
             execution_time: context.started_at.elapsed().as_secs(),
             source_tx_hash: context.source_tx_hash.unwrap_or_default(),
             dest_tx_hash: context.dest_tx_hash.unwrap_or_default(),
+            bridge_fee: context.bridge_fee,
+            simulation: context.simulation.clone(),
         })
     }
 
+    /// Settle `group` in one transaction through `settler`. Returns `None`
+    /// without sending anything if the batch does not simulate cleanly.
+    async fn settle_batch(&self, settler: Address, group: &[(H256, Intent)]) -> Result<Option<Vec<IntentExecution>>> {
+        let (first_id, first) = &group[0];
+        let chain_id = first.source_chain_id;
+        let intents: Vec<Intent> = group.iter().map(|(_, intent)| intent.clone()).collect();
+
+        let mut swaps = Vec::with_capacity(intents.len());
+        for intent in &intents {
+            let route = self.get_optimal_route(intent).await?;
+            swaps.push(self.build_swap_tx(intent, &route).await?);
+        }
+        let calls = batch::settlement_calls(self.config.address, settler, &intents, &swaps);
+        let tx = TransactionRequest::new()
+            .to(settler)
+            .data(batch::encode_execute(&calls))
+            .value(batch::total_value(&calls));
+
+        let _worker = self.acquire_worker(*first_id, chain_id).await?;
+        let provider = self.get_provider(chain_id)?;
+        if self.config.pipeline.simulate {
+            let outcome = simulation::simulate_call(
+                &provider,
+                self.config.address,
+                settler,
+                tx.data.clone().unwrap_or_default(),
+                tx.value.unwrap_or_default(),
+                &[],
+            )
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Batch simulation failed: {}", e)))?;
+            if let Some(reason) = outcome.revert_reason {
+                warn!("Batch of {} intents on chain {} reverts in simulation: {}", group.len(), chain_id, reason);
+                for (intent_id, _) in group {
+                    self.trace(*intent_id, TraceKind::BatchFallback { reason: reason.clone() }).await;
+                }
+                return Ok(None);
+            }
+        }
+
+        let mut contexts: Vec<ExecutionContext> = group
+            .iter()
+            .map(|(intent_id, intent)| ExecutionContext {
+                intent_id: *intent_id,
+                intent: intent.clone(),
+                solver: self.config.address,
+                started_at: Instant::now(),
+                current_step: ExecutionStep::ExecutingSourceSwap,
+                gas_used: U256::zero(),
+                bridge_fee: U256::zero(),
+                execution_proof: None,
+                source_tx_hash: None,
+                bridge_tx_hash: None,
+                dest_tx_hash: None,
+                locked_assets: HashMap::new(),
+                source_result: None,
+                simulation: None,
+            })
+            .collect();
+        {
+            let mut executions = self.active_executions.write().await;
+            for context in &contexts {
+                executions.insert(context.intent_id, context.clone());
+            }
+        }
+
+        let client = SignerMiddleware::new(provider, self.get_wallet(chain_id)?);
+        let receipt: Result<TransactionReceipt> = async {
+            let tx_hash = self.send_transaction_with_retry(&client, tx).await?;
+            for context in &contexts {
+                self.trace(context.intent_id, TraceKind::TransactionSent { chain_id, tx_hash }).await;
+                self.trace(context.intent_id, TraceKind::Batched { batch_size: group.len(), tx_hash }).await;
+            }
+            self.wait_for_confirmation(&client, tx_hash).await
+        }
+        .await;
+        {
+            let mut executions = self.active_executions.write().await;
+            for context in &contexts {
+                executions.remove(&context.intent_id);
+            }
+        }
+        let receipt = receipt?;
+
+        let gas_shares = batch::split_gas(receipt.gas_used.unwrap_or_default(), contexts.len());
+        let block_number = receipt.block_number.unwrap_or_default().as_u64();
+        let mut executions = Vec::with_capacity(contexts.len());
+        for (context, gas_used) in contexts.iter_mut().zip(gas_shares) {
+            context.gas_used = gas_used;
+            context.source_tx_hash = Some(receipt.transaction_hash);
+            context.dest_tx_hash = Some(receipt.transaction_hash);
+            let result = ExecutionResult {
+                tx_hash: receipt.transaction_hash,
+                amount_out: context.intent.min_dest_amount,
+                gas_used,
+                block_number,
+            };
+            executions.push(IntentExecution {
+                intent_id: context.intent_id,
+                solver: context.solver,
+                dest_amount: result.amount_out,
+                execution_proof: self.generate_execution_proof(context, &result).await?,
+                gas_used,
+                execution_time: context.started_at.elapsed().as_secs(),
+                source_tx_hash: receipt.transaction_hash,
+                dest_tx_hash: receipt.transaction_hash,
+                bridge_fee: U256::zero(),
+                simulation: None,
+            });
+        }
+        info!("Settled {} intents on chain {} in {:?}", executions.len(), chain_id, receipt.transaction_hash);
+        Ok(Some(executions))
+    }
+
+    /// Wait for a worker on `chain_id`, recording the wait in the trace
+    async fn acquire_worker(&self, intent_id: H256, chain_id: u64) -> Result<tokio::sync::OwnedSemaphorePermit> {
+        let (worker, waited) = self.pools.acquire(chain_id).await?;
+        self.trace(intent_id, TraceKind::WorkerAcquired {
+            chain_id,
+            waited_ms: waited.as_millis() as u64,
+        }).await;
+        Ok(worker)
+    }
+
+    /// Dry-run `tx` from the solver account before broadcasting it. A revert
+    /// fails the attempt without spending gas.
+    async fn preflight(&self, context: &mut ExecutionContext, chain_id: u64, tx: &TransactionRequest) -> Result<()> {
+        if !self.config.pipeline.simulate {
+            return Ok(());
+        }
+        let Some(to) = tx.to.as_ref().and_then(|to| to.as_address().copied()) else {
+            return Ok(());
+        };
+        let provider = self.get_provider(chain_id)?;
+
+        let outcome = simulation::simulate_call(
+            &provider,
+            self.config.address,
+            to,
+            tx.data.clone().unwrap_or_default(),
+            tx.value.unwrap_or_default(),
+            &[],
+        )
+        .await
+        .map_err(|e| SolverError::ExecutionFailed(format!("Simulation failed: {}", e)))?;
+        let gas_estimate = if outcome.reverted() {
+            None
+        } else {
+            provider.estimate_gas(&tx.clone().from(self.config.address).into(), None).await.ok()
+        };
+
+        self.trace(context.intent_id, TraceKind::Simulated {
+            chain_id,
+            success: !outcome.reverted(),
+            gas_estimate,
+            revert_reason: outcome.revert_reason.clone(),
+        }).await;
+        context.simulation = Some(SimulationResult {
+            success: !outcome.reverted(),
+            predicted_dest_amount: None,
+            gas_estimate,
+            revert_reason: outcome.revert_reason.clone(),
+            block_number: provider.get_block_number().await.map(|block| block.as_u64()).unwrap_or_default(),
+        });
+
+        match outcome.revert_reason {
+            Some(reason) => Err(SolverError::ExecutionFailed(format!("Simulation reverted: {}", reason))),
+            None => Ok(()),
+        }
+    }
+
     /// Validate prerequisites for execution
     async fn validate_execution_prerequisites(&self, context: &ExecutionContext) -> Result<()> {
         let intent = &context.intent;
@@ -300,8 +631,8 @@ impl SolverExecutor {
     async fn apply_mev_protection(&self, context: &ExecutionContext) -> Result<()> {
         use rand::Rng;
         
-        let mut rng = rand::thread_rng();
-        let delay_secs = rng.gen_range(MEV_PROTECTION_MIN_DELAY..=MEV_PROTECTION_MAX_DELAY);
+        // ThreadRng is not Send, so it must not live across the sleep
+        let delay_secs = rand::thread_rng().gen_range(MEV_PROTECTION_MIN_DELAY..=MEV_PROTECTION_MAX_DELAY);
         
         debug!("Applying MEV protection delay of {} seconds for intent {}", 
                delay_secs, context.intent_id);
@@ -406,6 +737,8 @@ impl SolverExecutor {
         };
 
         let tx_hash = self.send_transaction_with_retry(client, tx).await?;
+        let chain_id = client.signer().chain_id();
+        self.trace(context.intent_id, TraceKind::TransactionSent { chain_id, tx_hash }).await;
         let receipt = self.wait_for_confirmation(client, tx_hash).await?;
 
         Ok(ExecutionResult {
@@ -428,14 +761,16 @@ impl SolverExecutor {
         let route = self.get_optimal_route(intent).await?;
 
         // Build swap transaction based on protocol
-        let swap_tx = match route.protocol.as_str() {
-            "orbital_amm" => self.build_orbital_amm_swap(intent, &route).await?,
-            "uniswap_v3" => self.build_uniswap_v3_swap(intent, &route).await?,
-            "sushiswap" => self.build_sushiswap_swap(intent, &route).await?,
-            _ => return Err(SolverError::ExecutionFailed("Unsupported protocol".to_string())),
-        };
+        let swap_tx = self.build_swap_tx(intent, &route).await?;
 
-        let tx_hash = self.send_transaction_with_retry(client, swap_tx).await?;
+        let chain_id = client.signer().chain_id();
+        self.preflight(context, chain_id, &swap_tx).await?;
+        let tx_hash = match self.private_relay(chain_id) {
+            Some(submitter) => self.submit_swap_bundle(submitter, client, context, swap_tx, chain_id).await?,
+            None => self.send_transaction_with_retry(client, swap_tx).await?,
+        };
+        self.trace(context.intent_id, TraceKind::TransactionSent { chain_id, tx_hash }).await;
+        let intent = &context.intent;
         let receipt = self.wait_for_confirmation(client, tx_hash).await?;
 
         // Extract amount out from receipt logs
@@ -449,6 +784,56 @@ impl SolverExecutor {
         })
     }
 
+    /// Swap transaction for `intent` along `route`
+    async fn build_swap_tx(&self, intent: &Intent, route: &RouteInfo) -> Result<TransactionRequest> {
+        match route.protocol.as_str() {
+            "orbital_amm" => self.build_orbital_amm_swap(intent, route).await,
+            "uniswap_v3" => self.build_uniswap_v3_swap(intent, route).await,
+            "sushiswap" => self.build_sushiswap_swap(intent, route).await,
+            _ => Err(SolverError::ExecutionFailed("Unsupported protocol".to_string())),
+        }
+    }
+
+    /// Submit approve + swap (+ settle on same-chain intents) as one private
+    /// bundle, returning the swap transaction hash
+    async fn submit_swap_bundle(
+        &self,
+        submitter: &BundleSubmitter,
+        client: &SignerMiddleware<Arc<Provider<Http>>, LocalWallet>,
+        context: &mut ExecutionContext,
+        swap_tx: TransactionRequest,
+        chain_id: u64,
+    ) -> Result<H256> {
+        let intent = context.intent.clone();
+        let mut txs = Vec::with_capacity(3);
+
+        if intent.source_token != Address::zero() {
+            let spender = swap_tx.to.as_ref().and_then(|to| to.as_address().copied()).unwrap_or_default();
+            txs.push(self.build_erc20_approve_call(intent.source_token, spender, intent.source_amount).await?);
+        }
+        let swap_index = txs.len();
+        txs.push(swap_tx);
+
+        let settles = intent.source_chain_id == intent.dest_chain_id;
+        if settles {
+            txs.push(self.build_erc20_transfer_call(intent.dest_token, intent.user, intent.min_dest_amount).await?);
+        }
+
+        let outcome = submitter.submit(chain_id, client, txs).await?;
+        {
+            let mut metrics = self.performance_metrics.write().await;
+            match outcome.submission {
+                Submission::Private => metrics.private_submissions += 1,
+                Submission::Public => metrics.public_fallbacks += 1,
+            }
+        }
+
+        if settles {
+            context.dest_tx_hash = outcome.tx_hashes.last().copied();
+        }
+        Ok(outcome.tx_hashes[swap_index])
+    }
+
     /// Initiate cross-chain bridge transfer
     async fn initiate_bridge_transfer(
         &self,
@@ -634,6 +1019,7 @@ impl SolverExecutor {
     /// Update execution step and track progress
     async fn update_step(&self, context: &mut ExecutionContext, step: ExecutionStep) {
         debug!("Intent {} moving to step: {:?}", context.intent_id, step);
+        self.trace(context.intent_id, TraceKind::StepEntered { step: step.clone() }).await;
         context.current_step = step;
 
         // Update active execution context
@@ -656,6 +1042,15 @@ impl SolverExecutor {
         executions.keys().copied().collect()
     }
 
+    /// Current step of every active execution and how long it has been running
+    pub async fn active_execution_ages(&self) -> Vec<(H256, ExecutionStep, Duration)> {
+        let executions = self.active_executions.read().await;
+        executions
+            .values()
+            .map(|ctx| (ctx.intent_id, ctx.current_step.clone(), ctx.started_at.elapsed()))
+            .collect()
+    }
+
     /// Get execution status for a specific intent
     pub async fn get_execution_status(&self, intent_id: H256) -> Option<ExecutionStep> {
         let executions = self.active_executions.read().await;
@@ -681,12 +1076,42 @@ impl SolverExecutor {
         }
     }
 
+    /// RPC providers for every supported chain
+    pub fn providers(&self) -> &HashMap<u64, Arc<Provider<Http>>> {
+        &self.providers
+    }
+
+    /// Solver account holding inventory on every chain
+    pub fn address(&self) -> Address {
+        self.config.address
+    }
+
+    pub fn bridge_manager(&self) -> Arc<BridgeManager> {
+        self.bridge_manager.clone()
+    }
+
+    /// Solver key, for signing off-chain messages such as quotes
+    pub fn signer(&self) -> Result<LocalWallet> {
+        let chain_id = self.config.supported_chains.first()
+            .copied()
+            .ok_or_else(|| SolverError::ExecutionFailed("No supported chains".to_string()))?;
+        self.get_wallet(chain_id)
+    }
+
     fn get_provider(&self, chain_id: u64) -> Result<Arc<Provider<Http>>> {
         self.providers.get(&chain_id)
             .cloned()
             .ok_or(SolverError::ChainNotSupported(chain_id))
     }
 
+    /// Bundle submitter, if MEV protection is on and a private relay serves
+    /// `chain_id`
+    fn private_relay(&self, chain_id: u64) -> Option<&BundleSubmitter> {
+        self.bundle_submitter
+            .as_deref()
+            .filter(|submitter| self.mev_protection_enabled && submitter.has_relay(chain_id))
+    }
+
     fn get_wallet(&self, chain_id: u64) -> Result<LocalWallet> {
         self.wallets.get(&chain_id)
             .cloned()
@@ -767,6 +1192,16 @@ impl SolverExecutor {
         Ok(TransactionRequest::new())
     }
 
+    async fn build_erc20_approve_call(&self, token: Address, spender: Address, amount: U256) -> Result<TransactionRequest> {
+        let selector = ethers::utils::keccak256(b"approve(address,uint256)");
+        let mut calldata = selector[..4].to_vec();
+        calldata.extend(ethers::abi::encode(&[
+            ethers::abi::Token::Address(spender),
+            ethers::abi::Token::Uint(amount),
+        ]));
+        Ok(TransactionRequest::new().to(token).data(calldata))
+    }
+
     async fn extract_swap_amount_from_receipt(&self, _receipt: &TransactionReceipt, _intent: &Intent) -> Result<U256> {
         Ok(U256::zero())
     }
@@ -834,6 +1269,8 @@ impl Default for ExecutionMetrics {
             average_execution_time: Duration::from_secs(0),
             mev_protection_triggers: 0,
             rollback_operations: 0,
+            private_submissions: 0,
+            public_fallbacks: 0,
         }
     }
 }
@@ -849,6 +1286,8 @@ impl Clone for ExecutionMetrics {
             average_execution_time: self.average_execution_time,
             mev_protection_triggers: self.mev_protection_triggers,
             rollback_operations: self.rollback_operations,
+            private_submissions: self.private_submissions,
+            public_fallbacks: self.public_fallbacks,
         }
     }
 }
@@ -874,6 +1313,8 @@ mod tests {
             bridge_tx_hash: None,
             dest_tx_hash: None,
             locked_assets: HashMap::new(),
+            source_result: None,
+            simulation: None,
         };
 
         assert_eq!(context.intent_id, H256::zero());
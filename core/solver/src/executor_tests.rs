@@ -90,6 +90,8 @@ mod executor_tests {
             bridge_tx_hash: None,
             dest_tx_hash: None,
             locked_assets: std::collections::HashMap::new(),
+            source_result: None,
+            simulation: None,
         };
 
         assert_eq!(context.intent_id, H256::from_low_u64_be(1));
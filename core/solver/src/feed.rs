@@ -0,0 +1,352 @@
+//! Live feed of newly created intents.
+//!
+//! Intents reach the solver two ways: the backend API announces each
+//! submission on its `new_intents` WebSocket channel, and the intents
+//! contract emits `IntentCreated` on every chain. The [`IntentFeed`] listens
+//! to both, fetches the full intent for on-chain events with `getIntent`,
+//! and forwards each intent once, whichever source saw it first.
+
+use crate::{Result, SolverError};
+use ethers::{
+    abi::{self, ParamType, Token},
+    providers::{Http, Middleware, Provider},
+    types::{transaction::eip2718::TypedTransaction, Address, Filter, TransactionRequest, H256, U256},
+};
+use futures::{SinkExt, StreamExt};
+use intents_engine::intent::Intent;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::{mpsc, Mutex};
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, info, warn};
+
+/// Blocks scanned per `eth_getLogs` request
+const MAX_LOG_RANGE: u64 = 2000;
+
+/// Delay before reconnecting to the backend WebSocket
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntentFeedConfig {
+    /// Backend API WebSocket endpoint, e.g. `wss://api.example.com/ws`
+    pub websocket_url: Option<String>,
+    /// Intents contract watched for `IntentCreated` per chain
+    pub intents_contracts: HashMap<u64, Address>,
+    pub poll_interval_secs: u64,
+    /// Intent ids remembered for deduplication
+    pub dedupe_capacity: usize,
+    /// How long the local auction for each intent stays open
+    pub auction_duration_secs: u64,
+}
+
+impl Default for IntentFeedConfig {
+    fn default() -> Self {
+        Self {
+            websocket_url: None,
+            intents_contracts: HashMap::new(),
+            poll_interval_secs: 4,
+            dedupe_capacity: 10_000,
+            auction_duration_secs: 10,
+        }
+    }
+}
+
+/// Bounded set of recently seen intent ids; the oldest are forgotten first
+struct SeenIntents {
+    ids: HashSet<H256>,
+    order: VecDeque<H256>,
+    capacity: usize,
+}
+
+impl SeenIntents {
+    fn new(capacity: usize) -> Self {
+        Self {
+            ids: HashSet::new(),
+            order: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// `true` if `id` had not been seen yet
+    fn insert(&mut self, id: H256) -> bool {
+        if !self.ids.insert(id) {
+            return false;
+        }
+        self.order.push_back(id);
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+#[derive(Deserialize)]
+struct WsMessage {
+    message_type: String,
+    data: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct NewIntent {
+    intent_id: H256,
+    intent: Intent,
+}
+
+pub struct IntentFeed {
+    config: IntentFeedConfig,
+    providers: HashMap<u64, Arc<Provider<Http>>>,
+    seen: Mutex<SeenIntents>,
+}
+
+impl IntentFeed {
+    pub fn new(config: IntentFeedConfig, providers: HashMap<u64, Arc<Provider<Http>>>) -> Self {
+        let seen = Mutex::new(SeenIntents::new(config.dedupe_capacity));
+        Self { config, providers, seen }
+    }
+
+    pub fn auction_duration(&self) -> u64 {
+        self.config.auction_duration_secs
+    }
+
+    /// Start every configured source and return the deduplicated stream
+    pub fn subscribe(self: Arc<Self>) -> mpsc::Receiver<(H256, Intent)> {
+        let (tx, rx) = mpsc::channel(1024);
+
+        if let Some(url) = self.config.websocket_url.clone() {
+            tokio::spawn(self.clone().watch_backend(url, tx.clone()));
+        }
+        for (&chain_id, &contract) in &self.config.intents_contracts {
+            match self.providers.get(&chain_id) {
+                Some(provider) => {
+                    tokio::spawn(self.clone().watch_chain(chain_id, provider.clone(), contract, tx.clone()));
+                }
+                None => warn!("No provider for chain {}; not watching its intents contract", chain_id),
+            }
+        }
+        rx
+    }
+
+    async fn forward(&self, intent_id: H256, intent: Intent, tx: &mpsc::Sender<(H256, Intent)>) -> bool {
+        if !self.seen.lock().await.insert(intent_id) {
+            return true;
+        }
+        tx.send((intent_id, intent)).await.is_ok()
+    }
+
+    async fn watch_backend(self: Arc<Self>, url: String, tx: mpsc::Sender<(H256, Intent)>) {
+        let endpoint = format!("{}?subscribe=new_intents", url);
+        loop {
+            match tokio_tungstenite::connect_async(endpoint.as_str()).await {
+                Ok((mut socket, _)) => {
+                    info!("Subscribed to new intents at {}", url);
+                    while let Some(message) = socket.next().await {
+                        let text = match message {
+                            Ok(Message::Text(text)) => text,
+                            Ok(Message::Ping(payload)) => {
+                                socket.send(Message::Pong(payload)).await.ok();
+                                continue;
+                            }
+                            Ok(Message::Close(_)) => break,
+                            Ok(_) => continue,
+                            Err(e) => {
+                                warn!("Intent WebSocket error: {}", e);
+                                break;
+                            }
+                        };
+
+                        let Ok(message) = serde_json::from_str::<WsMessage>(&text) else {
+                            continue;
+                        };
+                        if message.message_type != "new_intent" {
+                            continue;
+                        }
+                        match serde_json::from_value::<NewIntent>(message.data) {
+                            Ok(new) => {
+                                if !self.forward(new.intent_id, new.intent, &tx).await {
+                                    return;
+                                }
+                            }
+                            Err(e) => debug!("Ignoring malformed new_intent message: {}", e),
+                        }
+                    }
+                }
+                Err(e) => warn!("Failed to connect to intent WebSocket {}: {}", url, e),
+            }
+
+            if tx.is_closed() {
+                return;
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    async fn watch_chain(
+        self: Arc<Self>,
+        chain_id: u64,
+        provider: Arc<Provider<Http>>,
+        contract: Address,
+        tx: mpsc::Sender<(H256, Intent)>,
+    ) {
+        let topic = H256::from(ethers::utils::keccak256(b"IntentCreated(bytes32,address,uint256)"));
+        let mut next_block = None;
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.poll_interval_secs));
+
+        loop {
+            interval.tick().await;
+            if tx.is_closed() {
+                return;
+            }
+
+            let latest = match provider.get_block_number().await {
+                Ok(block) => block.as_u64(),
+                Err(e) => {
+                    warn!("Failed to get block number on chain {}: {}", chain_id, e);
+                    continue;
+                }
+            };
+            // Start at the current block; intents created before startup are not replayed
+            let from = *next_block.get_or_insert(latest);
+            if from > latest {
+                continue;
+            }
+            let to = latest.min(from + MAX_LOG_RANGE - 1);
+
+            let filter = Filter::new().address(contract).topic0(topic).from_block(from).to_block(to);
+            let logs = match provider.get_logs(&filter).await {
+                Ok(logs) => logs,
+                Err(e) => {
+                    warn!("Failed to get IntentCreated logs on chain {}: {}", chain_id, e);
+                    continue;
+                }
+            };
+
+            for log in logs {
+                let Some(&intent_id) = log.topics.get(1) else {
+                    continue;
+                };
+                if self.seen.lock().await.ids.contains(&intent_id) {
+                    continue;
+                }
+                match fetch_intent(&provider, contract, intent_id).await {
+                    Ok(intent) => {
+                        if !self.forward(intent_id, intent, &tx).await {
+                            return;
+                        }
+                    }
+                    Err(e) => warn!("Failed to read intent {:?} on chain {}: {}", intent_id, chain_id, e),
+                }
+            }
+            next_block = Some(to + 1);
+        }
+    }
+}
+
+async fn fetch_intent(provider: &Provider<Http>, contract: Address, intent_id: H256) -> Result<Intent> {
+    let selector = ethers::utils::keccak256(b"getIntent(bytes32)");
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend(abi::encode(&[Token::FixedBytes(intent_id.as_bytes().to_vec())]));
+    let call: TypedTransaction = TransactionRequest::new().to(contract).data(calldata).into();
+    let output = provider
+        .call(&call, None)
+        .await
+        .map_err(|e| SolverError::ExecutionFailed(format!("getIntent failed: {}", e)))?;
+    decode_intent(&output)
+}
+
+/// Decode the contract's `Intent` struct as returned by `getIntent`
+fn decode_intent(output: &[u8]) -> Result<Intent> {
+    let uint = ParamType::Uint(256);
+    let tokens = abi::decode(
+        &[
+            ParamType::Address,   // user
+            uint.clone(),         // source_chain_id
+            uint.clone(),         // dest_chain_id
+            ParamType::Address,   // source_token
+            ParamType::Address,   // dest_token
+            uint.clone(),         // source_amount
+            uint.clone(),         // min_dest_amount
+            uint.clone(),         // deadline
+            uint.clone(),         // nonce
+            ParamType::FixedBytes(32), // data_hash
+            ParamType::Uint(8),   // status
+            uint.clone(),         // leg_count
+            uint,                 // legs_executed
+        ],
+        output,
+    )
+    .map_err(|e| SolverError::ExecutionFailed(format!("Malformed intent: {}", e)))?;
+
+    let address = |i: usize| tokens[i].clone().into_address().unwrap_or_default();
+    let number = |i: usize| tokens[i].clone().into_uint().unwrap_or_default();
+    let user = address(0);
+    if user.is_zero() {
+        return Err(SolverError::ExecutionFailed("Intent not found".to_string()));
+    }
+
+    Ok(Intent {
+        user,
+        source_chain_id: number(1).low_u64(),
+        dest_chain_id: number(2).low_u64(),
+        source_token: address(3),
+        dest_token: address(4),
+        source_amount: number(5),
+        min_dest_amount: number(6),
+        deadline: number(7).low_u64(),
+        nonce: number(8),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seen_intents_forgets_oldest() {
+        let mut seen = SeenIntents::new(2);
+        assert!(seen.insert(H256::repeat_byte(1)));
+        assert!(!seen.insert(H256::repeat_byte(1)));
+        assert!(seen.insert(H256::repeat_byte(2)));
+        assert!(seen.insert(H256::repeat_byte(3)));
+        // 1 was evicted to make room for 3
+        assert!(seen.insert(H256::repeat_byte(1)));
+        assert!(!seen.insert(H256::repeat_byte(3)));
+    }
+
+    #[test]
+    fn test_decode_intent() {
+        let user = Address::repeat_byte(0xaa);
+        let word = |v: u64| Token::Uint(U256::from(v));
+        let output = abi::encode(&[
+            Token::Address(user),
+            word(1),
+            word(137),
+            Token::Address(Address::repeat_byte(1)),
+            Token::Address(Address::repeat_byte(2)),
+            word(1_000),
+            word(990),
+            word(1_700_000_000),
+            word(7),
+            Token::FixedBytes(vec![0u8; 32]),
+            Token::Uint(U256::zero()),
+            word(0),
+            word(0),
+        ]);
+
+        let intent = decode_intent(&output).unwrap();
+        assert_eq!(intent.user, user);
+        assert_eq!(intent.dest_chain_id, 137);
+        assert_eq!(intent.min_dest_amount, U256::from(990));
+        assert_eq!(intent.nonce, U256::from(7));
+
+        let missing = abi::encode(&vec![Token::Uint(U256::zero()); 13]);
+        assert!(decode_intent(&missing).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+//! Operator-defined intent filters.
+//!
+//! Operators narrow the intents the node considers with a small TOML file,
+//! checked before any quoting so filtered intents cost no oracle, pool or
+//! route lookups:
+//!
+//! ```toml
+//! # Both tokens must be listed; omit to allow any token
+//! allowed_tokens = ["0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", "0x0000000000000000000000000000000000000000"]
+//! denied_tokens = []
+//! # Source amount bounds in token base units, decimal or 0x-hex
+//! min_source_amount = "1_000_000"
+//! max_source_amount = "50_000_000_000"
+//! # "source -> dest", `*` matches any chain; omit to allow every pair
+//! chain_pairs = ["1 -> 137", "42161 -> *"]
+//! # Skip intents expiring sooner than this, or later than the maximum
+//! min_time_to_deadline_secs = 60
+//! max_time_to_deadline_secs = 86400
+//! ```
+//!
+//! Every rule is optional; an empty filter accepts every intent.
+
+use crate::{Result, SolverError};
+use ethers::types::{Address, U256};
+use intents_engine::intent::Intent;
+use serde::{Deserialize, Serialize};
+use std::{fmt, path::Path, str::FromStr};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IntentFilter {
+    /// Tokens an intent may spend and receive; any token if empty
+    pub allowed_tokens: Vec<Address>,
+    /// Tokens that disqualify an intent on either side
+    pub denied_tokens: Vec<Address>,
+    #[serde(with = "amount", skip_serializing_if = "Option::is_none")]
+    pub min_source_amount: Option<U256>,
+    #[serde(with = "amount", skip_serializing_if = "Option::is_none")]
+    pub max_source_amount: Option<U256>,
+    /// Routes the node serves; any supported route if empty
+    pub chain_pairs: Vec<ChainPair>,
+    /// Intents closer to their deadline than this are too urgent to fill safely
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_time_to_deadline_secs: Option<u64>,
+    /// Intents further from their deadline than this are not worth holding
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_time_to_deadline_secs: Option<u64>,
+}
+
+/// A `source -> dest` route; `None` matches any chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ChainPair {
+    pub source: Option<u64>,
+    pub dest: Option<u64>,
+}
+
+impl ChainPair {
+    pub fn matches(&self, source_chain: u64, dest_chain: u64) -> bool {
+        self.source.map_or(true, |chain| chain == source_chain) && self.dest.map_or(true, |chain| chain == dest_chain)
+    }
+}
+
+impl FromStr for ChainPair {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let (source, dest) = value
+            .split_once("->")
+            .ok_or_else(|| format!("chain pair {:?} is not of the form \"source -> dest\"", value))?;
+        let chain = |side: &str| match side.trim() {
+            "*" => Ok(None),
+            id => id.parse::<u64>().map(Some).map_err(|_| format!("invalid chain id {:?} in {:?}", id, value)),
+        };
+        Ok(Self { source: chain(source)?, dest: chain(dest)? })
+    }
+}
+
+impl TryFrom<String> for ChainPair {
+    type Error = String;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ChainPair> for String {
+    fn from(pair: ChainPair) -> Self {
+        pair.to_string()
+    }
+}
+
+impl fmt::Display for ChainPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let side = |chain: Option<u64>| chain.map_or("*".to_string(), |id| id.to_string());
+        write!(f, "{} -> {}", side(self.source), side(self.dest))
+    }
+}
+
+/// Why an intent was filtered out
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Error)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FilterRejection {
+    #[error("token {token:?} is not allowed")]
+    TokenNotAllowed { token: Address },
+    #[error("token {token:?} is denied")]
+    TokenDenied { token: Address },
+    #[error("source amount {amount} is below {min}")]
+    TooSmall { amount: U256, min: U256 },
+    #[error("source amount {amount} is above {max}")]
+    TooLarge { amount: U256, max: U256 },
+    #[error("route {source_chain} -> {dest_chain} is not served")]
+    ChainPairNotAllowed { source_chain: u64, dest_chain: u64 },
+    #[error("deadline in {remaining_secs}s is sooner than {min_secs}s")]
+    TooUrgent { remaining_secs: u64, min_secs: u64 },
+    #[error("deadline in {remaining_secs}s is later than {max_secs}s")]
+    TooDistant { remaining_secs: u64, max_secs: u64 },
+}
+
+impl IntentFilter {
+    pub fn from_toml(text: &str) -> Result<Self> {
+        toml::from_str(text).map_err(|e| SolverError::ExecutionFailed(format!("Invalid intent filter: {}", e)))
+    }
+
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Failed to read intent filter {}: {}", path.display(), e)))?;
+        Self::from_toml(&text)
+    }
+
+    /// Check `intent` against every rule as of unix time `now`, cheapest first
+    pub fn check(&self, intent: &Intent, now: u64) -> std::result::Result<(), FilterRejection> {
+        for token in [intent.source_token, intent.dest_token] {
+            if self.denied_tokens.contains(&token) {
+                return Err(FilterRejection::TokenDenied { token });
+            }
+            if !self.allowed_tokens.is_empty() && !self.allowed_tokens.contains(&token) {
+                return Err(FilterRejection::TokenNotAllowed { token });
+            }
+        }
+
+        let amount = intent.source_amount;
+        if let Some(min) = self.min_source_amount.filter(|min| amount < *min) {
+            return Err(FilterRejection::TooSmall { amount, min });
+        }
+        if let Some(max) = self.max_source_amount.filter(|max| amount > *max) {
+            return Err(FilterRejection::TooLarge { amount, max });
+        }
+
+        if !self.chain_pairs.is_empty()
+            && !self.chain_pairs.iter().any(|pair| pair.matches(intent.source_chain_id, intent.dest_chain_id))
+        {
+            return Err(FilterRejection::ChainPairNotAllowed {
+                source_chain: intent.source_chain_id,
+                dest_chain: intent.dest_chain_id,
+            });
+        }
+
+        let remaining_secs = intent.deadline.saturating_sub(now);
+        if let Some(min_secs) = self.min_time_to_deadline_secs.filter(|min| remaining_secs < *min) {
+            return Err(FilterRejection::TooUrgent { remaining_secs, min_secs });
+        }
+        if let Some(max_secs) = self.max_time_to_deadline_secs.filter(|max| remaining_secs > *max) {
+            return Err(FilterRejection::TooDistant { remaining_secs, max_secs });
+        }
+        Ok(())
+    }
+}
+
+/// Optional token amounts written as decimal or `0x` hex strings, with `_`
+/// separators allowed, or as plain integers
+mod amount {
+    use ethers::types::U256;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Integer(u64),
+        Text(String),
+    }
+
+    pub fn serialize<S: Serializer>(value: &Option<U256>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(amount) => serializer.serialize_str(&amount.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<U256>, D::Error> {
+        let Some(raw) = Option::<Raw>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+        match raw {
+            Raw::Integer(value) => Ok(Some(U256::from(value))),
+            Raw::Text(text) => {
+                let digits = text.replace('_', "");
+                let parsed = match digits.strip_prefix("0x") {
+                    Some(hex) => U256::from_str_radix(hex, 16).ok(),
+                    None => U256::from_dec_str(&digits).ok(),
+                };
+                parsed.map(Some).ok_or_else(|| D::Error::custom(format!("invalid amount {:?}", text)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDC: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+
+    fn intent(source_chain_id: u64, dest_chain_id: u64, source_amount: u64, deadline: u64) -> Intent {
+        let usdc = Address::from_str(USDC).unwrap();
+        Intent {
+            source_chain_id,
+            dest_chain_id,
+            source_token: usdc,
+            dest_token: usdc,
+            source_amount: U256::from(source_amount),
+            deadline,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_filter_from_toml() {
+        let filter = IntentFilter::from_toml(&format!(
+            r#"
+            allowed_tokens = ["{}"]
+            min_source_amount = "1_000_000"
+            max_source_amount = "0x3b9aca00"
+            chain_pairs = ["1 -> 137", "42161 -> *"]
+            min_time_to_deadline_secs = 60
+            "#,
+            USDC
+        ))
+        .unwrap();
+
+        assert_eq!(filter.min_source_amount, Some(U256::from(1_000_000)));
+        assert_eq!(filter.max_source_amount, Some(U256::from(1_000_000_000)));
+        assert_eq!(filter.chain_pairs[1], ChainPair { source: Some(42161), dest: None });
+        assert_eq!(filter.chain_pairs[0].to_string(), "1 -> 137");
+
+        assert!(IntentFilter::from_toml("chain_pairs = [\"1 => 137\"]").is_err());
+        assert!(IntentFilter::from_toml("min_size = 5").is_err());
+        assert_eq!(IntentFilter::from_toml("").unwrap(), IntentFilter::default());
+    }
+
+    #[test]
+    fn test_filter_rejections() {
+        let filter = IntentFilter {
+            allowed_tokens: vec![Address::from_str(USDC).unwrap()],
+            min_source_amount: Some(U256::from(1_000)),
+            max_source_amount: Some(U256::from(1_000_000)),
+            chain_pairs: vec!["1 -> 137".parse().unwrap(), "42161 -> *".parse().unwrap()],
+            min_time_to_deadline_secs: Some(60),
+            max_time_to_deadline_secs: Some(3600),
+            ..Default::default()
+        };
+        let now = 1_000;
+
+        assert_eq!(filter.check(&intent(1, 137, 5_000, now + 600), now), Ok(()));
+        assert_eq!(filter.check(&intent(42161, 10, 5_000, now + 600), now), Ok(()));
+        assert!(matches!(
+            filter.check(&intent(1, 137, 500, now + 600), now),
+            Err(FilterRejection::TooSmall { .. })
+        ));
+        assert!(matches!(
+            filter.check(&intent(137, 1, 5_000, now + 600), now),
+            Err(FilterRejection::ChainPairNotAllowed { source_chain: 137, dest_chain: 1 })
+        ));
+        assert_eq!(
+            filter.check(&intent(1, 137, 5_000, now + 30), now),
+            Err(FilterRejection::TooUrgent { remaining_secs: 30, min_secs: 60 })
+        );
+
+        let mut other_token = intent(1, 137, 5_000, now + 600);
+        other_token.dest_token = Address::repeat_byte(1);
+        assert!(matches!(filter.check(&other_token, now), Err(FilterRejection::TokenNotAllowed { .. })));
+    }
+}
@@ -0,0 +1,482 @@
+//! Hedging of price risk on open cross-chain intents.
+//!
+//! Between paying out on the destination chain and being repaid on the
+//! source chain, the solver is long the source token. The [`Hedger`] shorts
+//! a perpetual on that token for the intent's notional (scaled by
+//! `hedge_ratio_bps`) once the intent is matched and closes the position
+//! once the source leg has settled. Realized hedge P&L and fees are tracked
+//! so they can be accounted for alongside the execution itself.
+//!
+//! Venues implement [`PerpVenue`]; [`BinanceFuturesVenue`] trades USDⓈ-M
+//! futures and [`MockPerpVenue`] fills at a settable mark price.
+
+use crate::{Result, SolverError};
+use async_trait::async_trait;
+use ethers::types::{Address, H256, U256};
+use hmac::{Hmac, Mac};
+use intents_engine::intent::Intent;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Long,
+    Short,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PerpPosition {
+    pub venue: String,
+    pub market: String,
+    pub side: Side,
+    /// Contracts in the market's base asset, 18 decimals
+    pub size: U256,
+    /// Entry price in USD, 18 decimals
+    pub entry_price: U256,
+    pub opened_at: u64,
+}
+
+impl PerpPosition {
+    /// USD notional at entry, 18 decimals
+    pub fn notional(&self) -> U256 {
+        self.size * self.entry_price / U256::exp10(18)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HedgeFill {
+    pub position: PerpPosition,
+    pub exit_price: U256,
+    /// Price P&L before fees, USD with 18 decimals
+    pub pnl: i128,
+    /// Trading fees for opening and closing, USD with 18 decimals
+    pub fees: U256,
+}
+
+impl HedgeFill {
+    pub fn net_pnl(&self) -> i128 {
+        self.pnl - to_i128(self.fees)
+    }
+}
+
+/// A perpetual futures venue the hedger can trade on
+#[async_trait]
+pub trait PerpVenue: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Open a position worth `notional` USD (18 decimals)
+    async fn open(&self, market: &str, side: Side, notional: U256) -> Result<PerpPosition>;
+
+    /// Close `position` entirely
+    async fn close(&self, position: &PerpPosition) -> Result<HedgeFill>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "venue", rename_all = "snake_case")]
+pub enum PerpVenueConfig {
+    BinanceFutures {
+        api_key: String,
+        api_secret: String,
+        #[serde(default = "default_binance_url")]
+        base_url: String,
+        /// Taker fee charged per fill
+        #[serde(default = "default_taker_fee_bps")]
+        taker_fee_bps: u16,
+    },
+    /// Fills at fixed prices without trading, for dry runs
+    Mock {
+        #[serde(default)]
+        prices: HashMap<String, U256>,
+    },
+}
+
+fn default_binance_url() -> String {
+    "https://fapi.binance.com".to_string()
+}
+
+fn default_taker_fee_bps() -> u16 {
+    5
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HedgeConfig {
+    pub venue: PerpVenueConfig,
+    /// Perp market per source token, e.g. WETH -> `ETHUSDT`. Intents whose
+    /// source token has no market are not hedged.
+    pub markets: HashMap<Address, String>,
+    /// Share of the exposure hedged
+    #[serde(default = "default_hedge_ratio")]
+    pub hedge_ratio_bps: u16,
+    /// Exposure below this USD notional (18 decimals) is not worth hedging
+    #[serde(default)]
+    pub min_notional: U256,
+}
+
+fn default_hedge_ratio() -> u16 {
+    10000
+}
+
+pub fn venue_from_config(config: &PerpVenueConfig) -> Arc<dyn PerpVenue> {
+    match config {
+        PerpVenueConfig::BinanceFutures { api_key, api_secret, base_url, taker_fee_bps } => Arc::new(
+            BinanceFuturesVenue::new(api_key.clone(), api_secret.clone(), base_url.clone(), *taker_fee_bps),
+        ),
+        PerpVenueConfig::Mock { prices } => Arc::new(MockPerpVenue::with_prices(prices.clone())),
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HedgeStats {
+    pub opened: u64,
+    pub closed: u64,
+    /// Sum of net P&L over closed hedges, USD with 18 decimals
+    pub realized_pnl: i128,
+    pub fees: U256,
+}
+
+pub struct Hedger {
+    config: HedgeConfig,
+    venue: Arc<dyn PerpVenue>,
+    positions: RwLock<HashMap<H256, PerpPosition>>,
+    stats: RwLock<HedgeStats>,
+}
+
+impl Hedger {
+    pub fn new(config: HedgeConfig, venue: Arc<dyn PerpVenue>) -> Self {
+        Self {
+            config,
+            venue,
+            positions: RwLock::new(HashMap::new()),
+            stats: RwLock::new(HedgeStats::default()),
+        }
+    }
+
+    /// Short the source token for an intent the solver has taken on.
+    /// `notional` is the USD value of the source amount.
+    pub async fn hedge(&self, intent_id: H256, intent: &Intent, notional: U256) -> Result<Option<PerpPosition>> {
+        let Some(market) = self.config.markets.get(&intent.source_token) else {
+            return Ok(None);
+        };
+        let size = notional * U256::from(self.config.hedge_ratio_bps) / U256::from(10000);
+        if size.is_zero() || size < self.config.min_notional {
+            return Ok(None);
+        }
+
+        let position = self.venue.open(market, Side::Short, size).await?;
+        info!(
+            "Hedged intent {:?}: short {} on {} ({} USD notional)",
+            intent_id, market, self.venue.name(), position.notional()
+        );
+        self.positions.write().await.insert(intent_id, position.clone());
+        self.stats.write().await.opened += 1;
+        Ok(Some(position))
+    }
+
+    /// Close the hedge for `intent_id`, if one is open
+    pub async fn unwind(&self, intent_id: H256) -> Result<Option<HedgeFill>> {
+        let Some(position) = self.positions.write().await.remove(&intent_id) else {
+            return Ok(None);
+        };
+
+        match self.venue.close(&position).await {
+            Ok(fill) => {
+                let mut stats = self.stats.write().await;
+                stats.closed += 1;
+                stats.realized_pnl = stats.realized_pnl.saturating_add(fill.net_pnl());
+                stats.fees = stats.fees.saturating_add(fill.fees);
+                Ok(Some(fill))
+            }
+            Err(e) => {
+                // Keep tracking the position so the unwind can be retried
+                warn!("Failed to unwind hedge for intent {:?}: {}", intent_id, e);
+                self.positions.write().await.insert(intent_id, position);
+                Err(e)
+            }
+        }
+    }
+
+    /// Total USD notional currently hedged
+    pub async fn open_notional(&self) -> U256 {
+        self.positions.read().await
+            .values()
+            .fold(U256::zero(), |acc, position| acc + position.notional())
+    }
+
+    pub async fn stats(&self) -> HedgeStats {
+        self.stats.read().await.clone()
+    }
+}
+
+/// Signed P&L of closing `position` at `exit_price`
+fn position_pnl(position: &PerpPosition, exit_price: U256) -> i128 {
+    let scale = U256::exp10(18);
+    let entry = to_i128(position.size * position.entry_price / scale);
+    let exit = to_i128(position.size * exit_price / scale);
+    match position.side {
+        Side::Long => exit - entry,
+        Side::Short => entry - exit,
+    }
+}
+
+fn fee(notional: U256, fee_bps: u16) -> U256 {
+    notional * U256::from(fee_bps) / U256::from(10000)
+}
+
+fn to_i128(value: U256) -> i128 {
+    value.min(U256::from(i128::MAX as u128)).as_u128() as i128
+}
+
+/// Fills every order at the market's current mark price, without fees
+#[derive(Default)]
+pub struct MockPerpVenue {
+    prices: RwLock<HashMap<String, U256>>,
+}
+
+impl MockPerpVenue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_prices(prices: HashMap<String, U256>) -> Self {
+        Self { prices: RwLock::new(prices) }
+    }
+
+    pub async fn set_price(&self, market: &str, price: U256) {
+        self.prices.write().await.insert(market.to_string(), price);
+    }
+
+    async fn price(&self, market: &str) -> Result<U256> {
+        self.prices.read().await
+            .get(market)
+            .copied()
+            .ok_or_else(|| SolverError::PriceUnavailable(market.to_string()))
+    }
+}
+
+#[async_trait]
+impl PerpVenue for MockPerpVenue {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    async fn open(&self, market: &str, side: Side, notional: U256) -> Result<PerpPosition> {
+        let price = self.price(market).await?;
+        Ok(PerpPosition {
+            venue: self.name().to_string(),
+            market: market.to_string(),
+            side,
+            size: notional * U256::exp10(18) / price,
+            entry_price: price,
+            opened_at: current_timestamp(),
+        })
+    }
+
+    async fn close(&self, position: &PerpPosition) -> Result<HedgeFill> {
+        let exit_price = self.price(&position.market).await?;
+        Ok(HedgeFill {
+            position: position.clone(),
+            exit_price,
+            pnl: position_pnl(position, exit_price),
+            fees: U256::zero(),
+        })
+    }
+}
+
+/// Market orders on Binance USDⓈ-M futures
+pub struct BinanceFuturesVenue {
+    client: reqwest::Client,
+    api_key: String,
+    api_secret: String,
+    base_url: String,
+    taker_fee_bps: u16,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinanceOrder {
+    avg_price: String,
+    executed_qty: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinancePremiumIndex {
+    mark_price: String,
+}
+
+impl BinanceFuturesVenue {
+    pub fn new(api_key: String, api_secret: String, base_url: String, taker_fee_bps: u16) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key,
+            api_secret,
+            base_url,
+            taker_fee_bps,
+        }
+    }
+
+    async fn mark_price(&self, market: &str) -> Result<f64> {
+        let index: BinancePremiumIndex = self.client
+            .get(format!("{}/fapi/v1/premiumIndex", self.base_url))
+            .query(&[("symbol", market)])
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| SolverError::PriceUnavailable(format!("{}: {}", market, e)))?
+            .json()
+            .await
+            .map_err(|e| SolverError::PriceUnavailable(format!("{}: {}", market, e)))?;
+        index.mark_price.parse().map_err(|_| SolverError::PriceUnavailable(market.to_string()))
+    }
+
+    async fn market_order(&self, market: &str, side: Side, quantity: f64, reduce_only: bool) -> Result<(U256, U256)> {
+        let query = format!(
+            "symbol={}&side={}&type=MARKET&quantity={:.3}&reduceOnly={}&newOrderRespType=RESULT&timestamp={}",
+            market,
+            match side {
+                Side::Long => "BUY",
+                Side::Short => "SELL",
+            },
+            quantity,
+            reduce_only,
+            current_timestamp() * 1000,
+        );
+        let signature = sign_query(&self.api_secret, &query);
+
+        let order: BinanceOrder = self.client
+            .post(format!("{}/fapi/v1/order?{}&signature={}", self.base_url, query, signature))
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| SolverError::ExecutionFailed(format!("Binance order failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Malformed Binance order: {}", e)))?;
+
+        let parse = |value: &str| value.parse::<f64>().map(to_wad).unwrap_or_default();
+        Ok((parse(&order.executed_qty), parse(&order.avg_price)))
+    }
+}
+
+#[async_trait]
+impl PerpVenue for BinanceFuturesVenue {
+    fn name(&self) -> &str {
+        "binance-futures"
+    }
+
+    async fn open(&self, market: &str, side: Side, notional: U256) -> Result<PerpPosition> {
+        let price = self.mark_price(market).await?;
+        let quantity = from_wad(notional) / price;
+        let (size, entry_price) = self.market_order(market, side, quantity, false).await?;
+
+        Ok(PerpPosition {
+            venue: self.name().to_string(),
+            market: market.to_string(),
+            side,
+            size,
+            entry_price,
+            opened_at: current_timestamp(),
+        })
+    }
+
+    async fn close(&self, position: &PerpPosition) -> Result<HedgeFill> {
+        let opposite = match position.side {
+            Side::Long => Side::Short,
+            Side::Short => Side::Long,
+        };
+        let (_, exit_price) = self.market_order(&position.market, opposite, from_wad(position.size), true).await?;
+
+        let exit_notional = position.size * exit_price / U256::exp10(18);
+        Ok(HedgeFill {
+            position: position.clone(),
+            exit_price,
+            pnl: position_pnl(position, exit_price),
+            fees: fee(position.notional(), self.taker_fee_bps) + fee(exit_notional, self.taker_fee_bps),
+        })
+    }
+}
+
+/// HMAC-SHA256 signature Binance expects on signed endpoints
+fn sign_query(secret: &str, query: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(query.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn to_wad(value: f64) -> U256 {
+    U256::from((value.max(0.0) * 1e18) as u128)
+}
+
+fn from_wad(value: U256) -> f64 {
+    value.min(U256::from(u128::MAX)).as_u128() as f64 / 1e18
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usd(amount: u64) -> U256 {
+        U256::from(amount) * U256::exp10(18)
+    }
+
+    #[tokio::test]
+    async fn test_short_hedge_offsets_price_drop() {
+        let weth = Address::repeat_byte(1);
+        let venue = Arc::new(MockPerpVenue::new());
+        venue.set_price("ETHUSDT", usd(2000)).await;
+
+        let hedger = Hedger::new(
+            HedgeConfig {
+                venue: PerpVenueConfig::Mock { prices: HashMap::new() },
+                markets: HashMap::from([(weth, "ETHUSDT".to_string())]),
+                hedge_ratio_bps: 5000,
+                min_notional: usd(100),
+            },
+            venue.clone(),
+        );
+
+        let intent = Intent { source_token: weth, ..Default::default() };
+        let position = hedger.hedge(H256::repeat_byte(1), &intent, usd(4000)).await.unwrap().unwrap();
+        assert_eq!(position.side, Side::Short);
+        assert_eq!(position.size, U256::exp10(18)); // 1 ETH = half of 4000 USD
+        assert_eq!(hedger.open_notional().await, usd(2000));
+
+        // ETH falls 10%: the short gains what the receivable lost on the hedged half
+        venue.set_price("ETHUSDT", usd(1800)).await;
+        let fill = hedger.unwind(H256::repeat_byte(1)).await.unwrap().unwrap();
+        assert_eq!(fill.pnl, to_i128(usd(200)));
+        assert_eq!(hedger.stats().await.realized_pnl, to_i128(usd(200)));
+        assert!(hedger.open_notional().await.is_zero());
+
+        // Unhedgeable: no market, or below the minimum
+        let other = Intent { source_token: Address::repeat_byte(2), ..Default::default() };
+        assert!(hedger.hedge(H256::repeat_byte(2), &other, usd(4000)).await.unwrap().is_none());
+        assert!(hedger.hedge(H256::repeat_byte(3), &intent, usd(100)).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_binance_signature() {
+        // Example from the Binance API documentation
+        let query = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000&timestamp=1499827319559";
+        assert_eq!(
+            sign_query("NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j", query),
+            "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+    }
+}
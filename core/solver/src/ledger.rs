@@ -0,0 +1,366 @@
+//! Double-entry profit and loss ledger.
+//!
+//! Every executed intent is booked as balanced postings between the
+//! solver's inventory and its income and expense accounts: the quoted
+//! spread, slippage against the quote, gas, bridge fees and the result of
+//! any hedge. Failed executions book the gas they burned. Amounts are USD
+//! with 18 decimals. The ledger answers per-intent and daily P&L and exports
+//! its postings as CSV or JSON.
+
+use ethers::types::{H256, U256};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, collections::HashMap, sync::RwLock};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Account {
+    /// Tokens the solver holds across chains
+    Inventory,
+    /// Spread between what the solver receives and what it quoted to pay
+    TradingIncome,
+    /// Paying out more (or less) than quoted
+    SlippageExpense,
+    GasExpense,
+    BridgeFeeExpense,
+    HedgingIncome,
+}
+
+impl Account {
+    /// Income accounts carry credit balances; the rest carry debit balances
+    fn is_income(&self) -> bool {
+        matches!(self, Account::TradingIncome | Account::HedgingIncome)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Posting {
+    pub sequence: u64,
+    pub intent_id: H256,
+    pub timestamp: u64,
+    pub debit: Account,
+    pub credit: Account,
+    pub amount: U256,
+}
+
+/// Everything known about one execution's economics, in USD (18 decimals)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionPnl {
+    pub intent_id: H256,
+    pub timestamp: u64,
+    /// Value of the source amount the solver is repaid
+    pub received: U256,
+    /// Value of the destination amount the solver quoted
+    pub quoted_paid: U256,
+    /// Value of the destination amount actually paid out
+    pub paid: U256,
+    pub gas_cost: U256,
+    pub bridge_fee: U256,
+    /// Net result of closing the intent's hedge, fees included
+    pub hedging_pnl: i128,
+    pub execution_time: u64,
+}
+
+/// Per-account P&L of one intent, or of a period. Expenses are positive
+/// numbers; `net` is income minus expenses.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PnlBreakdown {
+    pub trading: i128,
+    pub slippage: i128,
+    pub gas: i128,
+    pub bridge_fees: i128,
+    pub hedging: i128,
+    pub net: i128,
+}
+
+impl PnlBreakdown {
+    fn apply(&mut self, posting: &Posting) {
+        let amount = to_i128(posting.amount);
+        for (account, sign) in [(posting.debit, 1), (posting.credit, -1)] {
+            // Debits increase expenses and decrease income
+            let signed = amount * sign;
+            match account {
+                Account::TradingIncome => self.trading -= signed,
+                Account::HedgingIncome => self.hedging -= signed,
+                Account::SlippageExpense => self.slippage += signed,
+                Account::GasExpense => self.gas += signed,
+                Account::BridgeFeeExpense => self.bridge_fees += signed,
+                Account::Inventory => {}
+            }
+        }
+        self.net = self.trading + self.hedging - self.slippage - self.gas - self.bridge_fees;
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyRollup {
+    /// Unix timestamp of 00:00 UTC
+    pub day: u64,
+    pub executed: u64,
+    pub failed: u64,
+    pub pnl: PnlBreakdown,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerTotals {
+    pub executed: u64,
+    pub failed: u64,
+    pub total_execution_time: u64,
+    pub pnl: PnlBreakdown,
+}
+
+#[derive(Default)]
+struct LedgerState {
+    postings: Vec<Posting>,
+    /// (day, executed) per recorded execution attempt
+    outcomes: Vec<(u64, bool)>,
+    total_execution_time: u64,
+}
+
+/// Uses a synchronous lock so metrics can be read from non-async code
+#[derive(Default)]
+pub struct Ledger {
+    state: RwLock<LedgerState>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Book a completed execution
+    pub fn record_execution(&self, pnl: &ExecutionPnl) {
+        self.book(pnl, true);
+    }
+
+    /// Book a failed execution and the gas (and hedge result) it cost
+    pub fn record_failure(&self, intent_id: H256, timestamp: u64, gas_cost: U256, hedging_pnl: i128) {
+        let pnl = ExecutionPnl {
+            intent_id,
+            timestamp,
+            gas_cost,
+            hedging_pnl,
+            ..Default::default()
+        };
+        self.book(&pnl, false);
+    }
+
+    fn book(&self, pnl: &ExecutionPnl, executed: bool) {
+        let mut state = self.state.write().unwrap();
+        let post = |state: &mut LedgerState, debit, credit, amount: U256| {
+            if !amount.is_zero() {
+                let sequence = state.postings.len() as u64 + 1;
+                state.postings.push(Posting {
+                    sequence,
+                    intent_id: pnl.intent_id,
+                    timestamp: pnl.timestamp,
+                    debit,
+                    credit,
+                    amount,
+                });
+            }
+        };
+
+        // Quoted spread, then the difference between quoted and actual payout
+        if pnl.received >= pnl.quoted_paid {
+            post(&mut state, Account::Inventory, Account::TradingIncome, pnl.received - pnl.quoted_paid);
+        } else {
+            post(&mut state, Account::TradingIncome, Account::Inventory, pnl.quoted_paid - pnl.received);
+        }
+        if pnl.paid >= pnl.quoted_paid {
+            post(&mut state, Account::SlippageExpense, Account::Inventory, pnl.paid - pnl.quoted_paid);
+        } else {
+            post(&mut state, Account::Inventory, Account::SlippageExpense, pnl.quoted_paid - pnl.paid);
+        }
+
+        post(&mut state, Account::GasExpense, Account::Inventory, pnl.gas_cost);
+        post(&mut state, Account::BridgeFeeExpense, Account::Inventory, pnl.bridge_fee);
+
+        let hedging = U256::from(pnl.hedging_pnl.unsigned_abs());
+        if pnl.hedging_pnl >= 0 {
+            post(&mut state, Account::Inventory, Account::HedgingIncome, hedging);
+        } else {
+            post(&mut state, Account::HedgingIncome, Account::Inventory, hedging);
+        }
+
+        state.outcomes.push((day_of(pnl.timestamp), executed));
+        state.total_execution_time += pnl.execution_time;
+    }
+
+    /// Postings with `from <= timestamp < to`
+    pub fn postings(&self, from: u64, to: u64) -> Vec<Posting> {
+        self.state.read().unwrap()
+            .postings
+            .iter()
+            .filter(|posting| (from..to).contains(&posting.timestamp))
+            .cloned()
+            .collect()
+    }
+
+    /// Balance of every account; debit balances positive for expenses and
+    /// inventory, credit balances positive for income
+    pub fn balances(&self) -> HashMap<Account, i128> {
+        let mut balances = HashMap::new();
+        for posting in &self.state.read().unwrap().postings {
+            let amount = to_i128(posting.amount);
+            for (account, debit) in [(posting.debit, true), (posting.credit, false)] {
+                let signed = if debit != account.is_income() { amount } else { -amount };
+                *balances.entry(account).or_insert(0) += signed;
+            }
+        }
+        balances
+    }
+
+    pub fn intent_pnl(&self, intent_id: H256) -> Option<PnlBreakdown> {
+        let state = self.state.read().unwrap();
+        let mut pnl = PnlBreakdown::default();
+        let mut found = false;
+        for posting in state.postings.iter().filter(|posting| posting.intent_id == intent_id) {
+            pnl.apply(posting);
+            found = true;
+        }
+        found.then_some(pnl)
+    }
+
+    /// P&L per UTC day, oldest first
+    pub fn daily_rollups(&self) -> Vec<DailyRollup> {
+        let state = self.state.read().unwrap();
+        let mut days: BTreeMap<u64, DailyRollup> = BTreeMap::new();
+
+        for posting in &state.postings {
+            let day = day_of(posting.timestamp);
+            days.entry(day).or_insert_with(|| DailyRollup { day, ..Default::default() }).pnl.apply(posting);
+        }
+        for &(day, executed) in &state.outcomes {
+            let rollup = days.entry(day).or_insert_with(|| DailyRollup { day, ..Default::default() });
+            if executed {
+                rollup.executed += 1;
+            } else {
+                rollup.failed += 1;
+            }
+        }
+        days.into_values().collect()
+    }
+
+    pub fn totals(&self) -> LedgerTotals {
+        let state = self.state.read().unwrap();
+        let mut pnl = PnlBreakdown::default();
+        for posting in &state.postings {
+            pnl.apply(posting);
+        }
+        let executed = state.outcomes.iter().filter(|(_, executed)| *executed).count() as u64;
+        LedgerTotals {
+            executed,
+            failed: state.outcomes.len() as u64 - executed,
+            total_execution_time: state.total_execution_time,
+            pnl,
+        }
+    }
+
+    pub fn export_json(&self) -> serde_json::Value {
+        let postings = self.state.read().unwrap().postings.clone();
+        serde_json::json!({
+            "postings": postings,
+            "daily": self.daily_rollups(),
+        })
+    }
+
+    /// One posting per line: sequence, timestamp, intent, debit, credit, amount
+    pub fn export_csv(&self) -> String {
+        let mut csv = String::from("sequence,timestamp,intent_id,debit,credit,amount\n");
+        for posting in &self.state.read().unwrap().postings {
+            csv.push_str(&format!(
+                "{},{},{:#x},{},{},{}\n",
+                posting.sequence,
+                posting.timestamp,
+                posting.intent_id,
+                account_name(posting.debit),
+                account_name(posting.credit),
+                posting.amount,
+            ));
+        }
+        csv
+    }
+}
+
+fn account_name(account: Account) -> String {
+    serde_json::to_value(account)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn day_of(timestamp: u64) -> u64 {
+    timestamp - timestamp % SECONDS_PER_DAY
+}
+
+fn to_i128(value: U256) -> i128 {
+    value.min(U256::from(i128::MAX as u128)).as_u128() as i128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execution_postings_balance() {
+        let ledger = Ledger::new();
+        let intent_id = H256::repeat_byte(1);
+        ledger.record_execution(&ExecutionPnl {
+            intent_id,
+            timestamp: 1_000,
+            received: U256::from(1_000),
+            quoted_paid: U256::from(980),
+            paid: U256::from(985),
+            gas_cost: U256::from(4),
+            bridge_fee: U256::from(2),
+            hedging_pnl: -3,
+            execution_time: 40,
+        });
+
+        let pnl = ledger.intent_pnl(intent_id).unwrap();
+        assert_eq!(
+            pnl,
+            PnlBreakdown { trading: 20, slippage: 5, gas: 4, bridge_fees: 2, hedging: -3, net: 6 }
+        );
+
+        // Inventory moves by exactly the net result
+        let balances = ledger.balances();
+        assert_eq!(balances[&Account::Inventory], 6);
+        assert_eq!(balances[&Account::TradingIncome], 20);
+        assert!(ledger.export_csv().contains(",slippage_expense,inventory,5\n"));
+    }
+
+    #[test]
+    fn test_daily_rollups() {
+        let ledger = Ledger::new();
+        let day_two = SECONDS_PER_DAY + 10;
+        ledger.record_execution(&ExecutionPnl {
+            intent_id: H256::repeat_byte(1),
+            timestamp: 10,
+            received: U256::from(100),
+            quoted_paid: U256::from(90),
+            paid: U256::from(90),
+            ..Default::default()
+        });
+        ledger.record_failure(H256::repeat_byte(2), day_two, U256::from(7), 0);
+        ledger.record_execution(&ExecutionPnl {
+            intent_id: H256::repeat_byte(3),
+            timestamp: day_two,
+            received: U256::from(50),
+            quoted_paid: U256::from(45),
+            paid: U256::from(45),
+            ..Default::default()
+        });
+
+        let rollups = ledger.daily_rollups();
+        assert_eq!(rollups.len(), 2);
+        assert_eq!((rollups[0].day, rollups[0].executed, rollups[0].pnl.net), (0, 1, 10));
+        assert_eq!((rollups[1].day, rollups[1].executed, rollups[1].failed), (SECONDS_PER_DAY, 1, 1));
+        assert_eq!(rollups[1].pnl.net, -2);
+
+        let totals = ledger.totals();
+        assert_eq!((totals.executed, totals.failed, totals.pnl.net), (2, 1, 8));
+    }
+}
@@ -2,7 +2,25 @@ pub mod matcher;
 pub mod optimizer;
 pub mod executor;
 pub mod reputation;
+pub mod reputation_sync;
 pub mod monitoring;
+pub mod oracle;
+pub mod pool_state;
+pub mod venues;
+pub mod bundle;
+pub mod rebalancer;
+pub mod risk;
+pub mod backtest;
+pub mod strategy;
+pub mod feed;
+pub mod rpc;
+pub mod hedge;
+pub mod ledger;
+pub mod pipeline;
+pub mod defense;
+pub mod filter;
+pub mod shared_auction;
+pub mod batch;
 
 #[cfg(test)]
 mod executor_tests;
@@ -12,9 +30,15 @@ mod tests;
 
 use async_trait::async_trait;
 use ethers::types::{Address, U256, H256};
-use intents_engine::intent::{Intent, IntentExecution};
+use intents_engine::{
+    gas_oracle::GasOracle,
+    intent::{Intent, IntentExecution},
+};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -25,14 +49,23 @@ pub enum SolverError {
     #[error("Unprofitable intent")]
     Unprofitable,
     
-    #[error("Risk limit exceeded")]
-    RiskLimitExceeded,
+    #[error("Risk limit exceeded: {0}")]
+    RiskLimitExceeded(risk::RiskViolation),
     
     #[error("Chain not supported: {0}")]
     ChainNotSupported(u64),
     
     #[error("Execution failed: {0}")]
     ExecutionFailed(String),
+
+    #[error("Price unavailable: {0}")]
+    PriceUnavailable(String),
+
+    #[error("Storage error: {0}")]
+    StorageError(String),
+
+    #[error("Intent filtered out: {0}")]
+    Filtered(filter::FilterRejection),
 }
 
 pub type Result<T> = std::result::Result<T, SolverError>;
@@ -45,6 +78,55 @@ pub struct SolverConfig {
     pub max_slippage_bps: u16,
     pub supported_chains: Vec<u64>,
     pub oracle_addresses: std::collections::HashMap<String, String>,
+    /// Orbital pool quoted against on each chain
+    #[serde(default)]
+    pub orbital_pools: std::collections::HashMap<u64, pool_state::OrbitalPoolConfig>,
+    /// DEX venues the route optimizer sources liquidity from
+    #[serde(default)]
+    pub venues: Vec<venues::VenueConfig>,
+    /// Private relays executions are submitted through, per chain
+    #[serde(default)]
+    pub private_relays: Vec<bundle::RelayConfig>,
+    /// Cross-chain inventory rebalancing; disabled if unset
+    #[serde(default)]
+    pub rebalancing: Option<rebalancer::RebalanceConfig>,
+    /// Reputation persistence and on-chain reconciliation; in-memory only if unset
+    #[serde(default)]
+    pub reputation_sync: Option<reputation_sync::ReputationSyncConfig>,
+    /// Exposure limits checked before matching; slippage uses `max_slippage_bps`
+    #[serde(default)]
+    pub risk_limits: risk::RiskLimits,
+    /// Sources of new intents; the node only acts on direct calls if unset
+    #[serde(default)]
+    pub intent_feed: Option<feed::IntentFeedConfig>,
+    /// JSON-RPC server answering quote requests; not started if unset
+    #[serde(default)]
+    pub quote_rpc: Option<rpc::QuoteRpcConfig>,
+    /// Perp hedging of open intent exposure; unhedged if unset
+    #[serde(default)]
+    pub hedging: Option<hedge::HedgeConfig>,
+    /// Quote budgets, re-quoting and stale-bid cancellation for auctions
+    #[serde(default)]
+    pub bidding: matcher::BiddingConfig,
+    /// Per-chain worker pools, retries and pre-flight simulation for executions
+    #[serde(default)]
+    pub pipeline: pipeline::PipelineConfig,
+    /// Execution archival and automatic answers to on-chain challenges; off if unset
+    #[serde(default)]
+    pub slashing_defense: Option<defense::DefenseConfig>,
+    /// Health checks, alert sinks and the `/health` endpoint; unmonitored if unset
+    #[serde(default)]
+    pub monitoring: Option<monitoring::MonitorConfig>,
+    /// TOML file of operator rules intents must pass before they are quoted;
+    /// every intent is considered if unset
+    #[serde(default)]
+    pub intent_filter_path: Option<std::path::PathBuf>,
+    /// External sealed-bid auction coordinator to bid through; not joined if unset
+    #[serde(default)]
+    pub shared_auction: Option<shared_auction::SharedAuctionConfig>,
+    /// Settler contracts and collect window for batching same-chain intents
+    #[serde(default)]
+    pub batching: batch::BatchConfig,
 }
 
 #[derive(Debug, Clone)]
@@ -79,14 +161,102 @@ pub struct SolverNode {
     optimizer: Arc<optimizer::RouteOptimizer>,
     executor: Arc<executor::SolverExecutor>,
     reputation: Arc<reputation::ReputationManager>,
+    rebalancer: Option<Arc<rebalancer::Rebalancer>>,
+    reputation_sync: Option<Arc<reputation_sync::ReputationSync>>,
+    risk: Arc<risk::RiskManager>,
+    strategies: strategy::StrategySet,
+    feed: Option<Arc<feed::IntentFeed>>,
+    hedger: Option<Arc<hedge::Hedger>>,
+    defense: Option<Arc<defense::SlashingDefense>>,
+    monitor: Option<Arc<monitoring::SolverMonitor>>,
+    intent_filter: filter::IntentFilter,
+    batch_queue: batch::BatchQueue,
+    ledger: Arc<ledger::Ledger>,
+    intents_matched: AtomicU64,
 }
 
 impl SolverNode {
     pub async fn new(config: SolverConfig) -> Result<Self> {
-        let reputation = Arc::new(reputation::ReputationManager::new());
-        let matcher = Arc::new(matcher::IntentMatcher::new(reputation.clone()));
-        let optimizer = Arc::new(optimizer::RouteOptimizer::new(&config).await?);
+        let reputation = Arc::new(Self::reputation_manager(&config).await?);
         let executor = Arc::new(executor::SolverExecutor::new(config.clone()).await?);
+        let optimizer = Arc::new(
+            optimizer::RouteOptimizer::new(&config)
+                .await?
+                .with_venues(venues::from_config(&config.venues, executor.providers())),
+        );
+
+        let gas_oracle = Arc::new(GasOracle::default());
+        for (chain_id, provider) in executor.providers() {
+            gas_oracle.register_chain(*chain_id, provider.clone()).await;
+        }
+        let risk = Arc::new(risk::RiskManager::new(config.risk_limits.clone(), config.max_slippage_bps));
+        let mut matcher = matcher::IntentMatcher::new(reputation.clone())
+            .with_gas_oracle(gas_oracle)
+            .with_risk_manager(risk.clone())
+            .with_bidding(config.bidding.clone());
+
+        let price_oracle = oracle::PriceAggregator::from_config(
+            &config.oracle_addresses,
+            executor.providers().clone(),
+            oracle::OracleConfig::default(),
+        )?;
+        if !price_oracle.is_empty() {
+            matcher = matcher.with_price_oracle(Arc::new(price_oracle));
+        } else {
+            tracing::warn!("No price oracles configured, the solver cannot price intents");
+        }
+        if !config.orbital_pools.is_empty() {
+            let pool_state = pool_state::PoolStateProvider::new(executor.providers().clone(), config.orbital_pools.clone());
+            matcher = matcher.with_pool_state(Arc::new(pool_state));
+        } else {
+            tracing::warn!("No orbital pools configured, the solver has no liquidity to quote");
+        }
+        let matcher = Arc::new(matcher);
+
+        let rebalancer = config
+            .rebalancing
+            .clone()
+            .map(|rebalancing| Arc::new(rebalancer::Rebalancer::new(rebalancing, executor.clone())));
+
+        let reputation_sync = config
+            .reputation_sync
+            .clone()
+            .filter(|sync| !sync.intents_contracts.is_empty())
+            .map(|sync| {
+                Arc::new(reputation_sync::ReputationSync::new(sync, reputation.clone(), executor.providers().clone()))
+            });
+
+        let feed = config
+            .intent_feed
+            .clone()
+            .map(|feed| Arc::new(feed::IntentFeed::new(feed, executor.providers().clone())));
+
+        let hedger = config.hedging.clone().map(|hedging| {
+            let venue = hedge::venue_from_config(&hedging.venue);
+            Arc::new(hedge::Hedger::new(hedging, venue))
+        });
+
+        let defense = match config.slashing_defense.clone() {
+            Some(defense) => {
+                let archive = Arc::new(defense::FileExecutionArchive::open(defense.archive_dir.clone()).await?);
+                Some(Arc::new(defense::SlashingDefense::new(defense, archive, executor.clone())))
+            }
+            None => None,
+        };
+
+        let monitor = config.monitoring.clone().map(|monitoring| {
+            Arc::new(monitoring::SolverMonitor::new(monitoring, executor.clone(), reputation.clone()))
+        });
+
+        let intent_filter = match &config.intent_filter_path {
+            Some(path) => filter::IntentFilter::load(path).await?,
+            None => filter::IntentFilter::default(),
+        };
+
+        let batch_queue = batch::BatchQueue::new(&config.batching);
+
+        let strategies = strategy::StrategySet::new()
+            .with(Arc::new(strategy::RouteStrategy::new(config.clone(), optimizer.clone())), 1);
 
         Ok(Self {
             config,
@@ -94,15 +264,309 @@ impl SolverNode {
             optimizer,
             executor,
             reputation,
+            rebalancer,
+            reputation_sync,
+            risk,
+            strategies,
+            feed,
+            hedger,
+            defense,
+            monitor,
+            intent_filter,
+            batch_queue,
+            ledger: Arc::new(ledger::Ledger::new()),
+            intents_matched: AtomicU64::new(0),
+        })
+    }
+
+    /// Replace the built-in route strategy with `strategies`. Include
+    /// [`SolverNode::route_strategy`] to keep it in the mix.
+    pub fn with_strategies(mut self, strategies: strategy::StrategySet) -> Self {
+        self.strategies = strategies;
+        self
+    }
+
+    /// Replace the operator filter loaded from `intent_filter_path`
+    pub fn with_intent_filter(mut self, intent_filter: filter::IntentFilter) -> Self {
+        self.intent_filter = intent_filter;
+        self
+    }
+
+    /// The built-in strategy quoting through the route optimizer
+    pub fn route_strategy(&self) -> Arc<dyn strategy::Strategy> {
+        Arc::new(strategy::RouteStrategy::new(self.config.clone(), self.optimizer.clone()))
+    }
+
+    /// Reputation manager backed by the configured store, with persisted
+    /// scores loaded
+    async fn reputation_manager(config: &SolverConfig) -> Result<reputation::ReputationManager> {
+        let database_url = config.reputation_sync.as_ref().and_then(|sync| sync.database_url.as_deref());
+        let manager = match database_url {
+            #[cfg(feature = "postgres")]
+            Some(url) => reputation::ReputationManager::new()
+                .with_store(Arc::new(reputation::PostgresReputationStore::connect(url).await?)),
+            #[cfg(not(feature = "postgres"))]
+            Some(_) => {
+                return Err(SolverError::StorageError(
+                    "database_url requires the postgres feature".to_string(),
+                ))
+            }
+            None => reputation::ReputationManager::new(),
+        };
+        manager.load().await?;
+        Ok(manager)
+    }
+
+    /// P&L ledger of every execution this node attempted
+    pub fn ledger(&self) -> Arc<ledger::Ledger> {
+        self.ledger.clone()
+    }
+
+    /// Value the legs of a completed execution in USD against the winning quote
+    async fn execution_pnl(
+        &self,
+        intent_id: H256,
+        intent: &Intent,
+        quote: Option<SolverQuote>,
+        execution: &IntentExecution,
+        hedging_pnl: i128,
+    ) -> Result<ledger::ExecutionPnl> {
+        let received = self.matcher
+            .notional_usd(intent.source_token, intent.source_chain_id, intent.source_amount)
+            .await?;
+        let paid = self.matcher
+            .notional_usd(intent.dest_token, intent.dest_chain_id, execution.dest_amount)
+            .await?;
+        let quoted_paid = match quote {
+            Some(quote) => self.matcher
+                .notional_usd(intent.dest_token, intent.dest_chain_id, quote.dest_amount)
+                .await?,
+            None => paid,
+        };
+
+        Ok(ledger::ExecutionPnl {
+            intent_id,
+            timestamp: current_timestamp(),
+            received,
+            quoted_paid,
+            paid,
+            gas_cost: self.matcher.gas_cost_usd(intent, execution.gas_used).await?,
+            bridge_fee: self.matcher
+                .notional_usd(Address::zero(), intent.source_chain_id, execution.bridge_fee)
+                .await?,
+            hedging_pnl,
+            execution_time: execution.execution_time,
         })
     }
+
+    /// Health of the node as last checked; `None` if monitoring is off
+    pub async fn health(&self) -> Option<monitoring::HealthSummary> {
+        match &self.monitor {
+            Some(monitor) => Some(monitor.health().await),
+            None => None,
+        }
+    }
+
+    /// Divergences between off-chain and on-chain reputation seen so far
+    pub async fn reputation_discrepancies(&self) -> Vec<reputation_sync::ReputationDiscrepancy> {
+        match &self.reputation_sync {
+            Some(sync) => sync.discrepancies().await,
+            None => Vec::new(),
+        }
+    }
     
-    pub async fn start(&self) -> Result<()> {
-        // Start monitoring for new intents
-        // Start execution loop
-        // Start reputation updates
+    pub async fn start(self: Arc<Self>) -> Result<()> {
+        if let Some(sync) = &self.reputation_sync {
+            tokio::spawn(sync.clone().run());
+        }
+        if let Some(rebalancer) = &self.rebalancer {
+            tokio::spawn(rebalancer.clone().run());
+        }
+        if let Some(defense) = &self.defense {
+            tokio::spawn(defense.clone().run());
+        }
+        if let Some(monitor) = &self.monitor {
+            tokio::spawn(monitor.clone().run());
+            let monitor = monitor.clone();
+            tokio::spawn(async move {
+                if let Err(e) = monitor.serve_health().await {
+                    tracing::error!("Health endpoint stopped: {}", e);
+                }
+            });
+        }
+
+        if let Some(quote_rpc) = self.config.quote_rpc.clone() {
+            let service = Arc::new(rpc::QuoteService::new(quote_rpc, self.clone(), self.executor.signer()?));
+            tokio::spawn(async move {
+                if let Err(e) = service.serve().await {
+                    tracing::error!("Quote RPC stopped: {}", e);
+                }
+            });
+        }
+
+        if let Some(shared_auction) = self.config.shared_auction.clone() {
+            let client = shared_auction::SharedAuctionClient::new(
+                shared_auction,
+                self.clone(),
+                self.clone(),
+                self.executor.signer()?,
+            );
+            tokio::spawn(Arc::new(client).run());
+        }
+
+        // Quote every new intent from the feed and run it through the auction
+        if let Some(feed) = &self.feed {
+            tokio::spawn(self.matcher.clone().run_bidding(self.clone()));
+
+            let auction_duration = feed.auction_duration();
+            let mut intents = feed.clone().subscribe();
+            let node = self.clone();
+            tokio::spawn(async move {
+                while let Some((intent_id, intent)) = intents.recv().await {
+                    tokio::spawn(node.clone().handle_new_intent(intent_id, intent, auction_duration));
+                }
+            });
+        }
         Ok(())
     }
+
+    /// Execute a won intent, or queue it with other same-chain intents on the
+    /// same token pair when the chain has a batch settler. Whoever opens or
+    /// fills a batch sends it.
+    async fn execute_or_batch(&self, intent_id: H256, intent: &Intent) {
+        let key = batch::batch_key(intent).filter(|key| self.config.batching.settlers.contains_key(&key.chain_id));
+        let intent_ids = match key {
+            Some(key) => match self.batch_queue.push(key, intent_id).await {
+                batch::Enqueued::Joined => return,
+                batch::Enqueued::Full => self.batch_queue.take(key).await,
+                batch::Enqueued::Opened => {
+                    tokio::time::sleep(self.batch_queue.window()).await;
+                    self.batch_queue.take(key).await
+                }
+            },
+            None => vec![intent_id],
+        };
+        if intent_ids.is_empty() {
+            return;
+        }
+
+        for (intent_id, result) in self.execute_intents(&intent_ids).await {
+            if let Err(e) = result {
+                tracing::warn!("Failed to execute intent {:?}: {}", intent_id, e);
+            }
+        }
+    }
+
+    /// Execute matched intents, batching those that can settle together
+    pub async fn execute_intents(&self, intent_ids: &[H256]) -> Vec<(H256, Result<IntentExecution>)> {
+        let mut results = Vec::with_capacity(intent_ids.len());
+        for (intent_id, result) in self.executor.execute_batch(intent_ids).await {
+            results.push((intent_id, self.settle_execution(intent_id, result).await));
+        }
+        results
+    }
+
+    /// Release, unwind and book everything tied to an execution's outcome
+    async fn settle_execution(&self, intent_id: H256, result: Result<IntentExecution>) -> Result<IntentExecution> {
+        let intent = self.matcher.get_matched_intent(intent_id).await;
+        let quote = self.matcher.get_winning_quote(intent_id).await;
+        self.risk.release(intent_id).await;
+
+        if let Some(strategy) = intent.as_ref().and_then(|intent| self.strategies.assign(intent.id())) {
+            if let Err(e) = strategy.on_settled(intent_id, result.as_ref().ok()).await {
+                tracing::warn!("Strategy hook failed on settlement of intent {:?}: {}", intent_id, e);
+            }
+        }
+
+        // The source leg has settled (or failed), so the price risk is gone
+        let mut hedging_pnl = 0;
+        if let Some(hedger) = &self.hedger {
+            match hedger.unwind(intent_id).await {
+                Ok(Some(fill)) => {
+                    hedging_pnl = fill.net_pnl();
+                    self.risk.record_pnl(hedging_pnl).await;
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Hedge for intent {:?} left open: {}", intent_id, e),
+            }
+        }
+
+        // Book the execution and feed realized P&L to the drawdown breaker
+        match (&result, intent) {
+            (Ok(execution), Some(intent)) => {
+                // Keep what is needed to answer a challenge of this execution
+                if let Some(defense) = &self.defense {
+                    if let Err(e) = defense.archive(&intent, execution).await {
+                        tracing::error!("Failed to archive execution of intent {:?}: {}", intent_id, e);
+                    }
+                }
+                let pnl = match self.execution_pnl(intent_id, &intent, quote, execution, hedging_pnl).await {
+                    Ok(pnl) => pnl,
+                    Err(e) => {
+                        // The execution still counts; only its value is unknown
+                        tracing::warn!("Failed to value execution of intent {:?}, booking zero P&L: {}", intent_id, e);
+                        ledger::ExecutionPnl {
+                            intent_id,
+                            timestamp: current_timestamp(),
+                            received: U256::zero(),
+                            quoted_paid: U256::zero(),
+                            paid: U256::zero(),
+                            gas_cost: U256::zero(),
+                            bridge_fee: U256::zero(),
+                            hedging_pnl,
+                            execution_time: execution.execution_time,
+                        }
+                    }
+                };
+                let to_i128 = |v: U256| v.min(U256::from(i128::MAX as u128)).as_u128() as i128;
+                self.risk.record_pnl(to_i128(pnl.received) - to_i128(pnl.paid)).await;
+                self.ledger.record_execution(&pnl);
+            }
+            _ => self.ledger.record_failure(intent_id, current_timestamp(), U256::zero(), hedging_pnl),
+        }
+        result
+    }
+
+    /// Quote `intent`, bid it into its auction and, if the auction is won,
+    /// match and execute it
+    async fn handle_new_intent(self: Arc<Self>, intent_id: H256, intent: Intent, auction_duration: u64) {
+        if intent.is_expired() {
+            return;
+        }
+        if let Err(rejection) = self.intent_filter.check(&intent, current_timestamp()) {
+            tracing::debug!("Ignoring intent {:?}: {}", intent_id, rejection);
+            return;
+        }
+        let started = match self.matcher.start_auction(intent_id, intent.clone(), auction_duration).await {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::debug!("Auction for intent {:?} not started: {}", intent_id, e);
+                false
+            }
+        };
+        // The matcher re-quotes or cancels the bid while the auction runs
+        if let Err(e) = self.matcher.place_bid(intent_id, &*self).await {
+            tracing::debug!("Not bidding on intent {:?}: {}", intent_id, e);
+            if started {
+                self.matcher.cancel_auction(intent_id).await;
+            }
+            return;
+        }
+
+        // Finalize once the deadline has strictly passed
+        tokio::time::sleep(std::time::Duration::from_secs(auction_duration + 1)).await;
+        match self.matcher.finalize_auction(intent_id).await {
+            Ok(winner) if winner == self.config.address => {
+                if let Err(e) = self.match_intent(intent_id, &intent).await {
+                    tracing::warn!("Failed to match won intent {:?}: {}", intent_id, e);
+                    return;
+                }
+                self.execute_or_batch(intent_id, &intent).await;
+            }
+            Ok(_) => {}
+            Err(e) => tracing::debug!("Auction for intent {:?} closed without us: {}", intent_id, e),
+        }
+    }
 }
 
 #[async_trait]
@@ -113,64 +577,77 @@ impl Solver for SolverNode {
            !self.config.supported_chains.contains(&intent.dest_chain_id) {
             return Err(SolverError::ChainNotSupported(intent.source_chain_id));
         }
+        self.intent_filter
+            .check(intent, current_timestamp())
+            .map_err(SolverError::Filtered)?;
         
-        // Find optimal route
-        let route = self.optimizer.find_best_route(intent).await?;
-        
-        // Calculate expected output and profit
-        let (dest_amount, profit) = self.optimizer.calculate_profit(&route, intent).await?;
-        
-        // Check profitability
-        let profit_bps = profit * U256::from(10000) / intent.source_amount;
-        if profit_bps < U256::from(self.config.min_profit_bps) {
-            return Err(SolverError::Unprofitable);
-        }
-        
-        // Estimate execution time
-        let execution_time = self.estimate_execution_time(&route);
-        
-        Ok(SolverQuote {
-            solver: self.config.address,
-            dest_amount,
-            profit,
-            execution_time_estimate: execution_time,
-            confidence: 0.95, // TODO: Calculate based on historical performance
-        })
+        // The strategy assigned to this intent prices it, or passes
+        self.strategies.quote(intent).await?.ok_or(SolverError::Unprofitable)
     }
     
     async fn match_intent(&self, intent_id: H256, intent: &Intent) -> Result<()> {
-        self.matcher.match_intent(intent_id, intent, &self.config).await
+        self.matcher.match_intent(intent_id, intent, &self.config).await?;
+        self.intents_matched.fetch_add(1, Ordering::Relaxed);
+        if let Some(rebalancer) = &self.rebalancer {
+            rebalancer.record_intent(intent).await;
+        }
+        // The match is committed, so a failing hook must not stop execution
+        if let Some(strategy) = self.strategies.assign(intent.id()) {
+            if let Err(e) = strategy.on_matched(intent_id, intent).await {
+                tracing::warn!("Strategy hook failed on match of intent {:?}: {}", intent_id, e);
+            }
+        }
+
+        // A failed hedge leaves the intent matched but unhedged
+        if let Some(hedger) = &self.hedger {
+            let notional = self.matcher
+                .notional_usd(intent.source_token, intent.source_chain_id, intent.source_amount)
+                .await;
+            let hedged = match notional {
+                Ok(notional) => hedger.hedge(intent_id, intent, notional).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = hedged {
+                tracing::warn!("Failed to hedge intent {:?}: {}", intent_id, e);
+            }
+        }
+        Ok(())
     }
     
     async fn execute_intent(&self, intent_id: H256) -> Result<IntentExecution> {
-        self.executor.execute(intent_id).await
+        let result = self.executor.execute(intent_id).await;
+        self.settle_execution(intent_id, result).await
     }
     
     fn get_metrics(&self) -> SolverMetrics {
-        // TODO: Aggregate metrics from components
+        let totals = self.ledger.totals();
+        let attempts = totals.executed + totals.failed;
         SolverMetrics {
-            total_intents_matched: 0,
-            total_intents_executed: 0,
-            total_profit: U256::zero(),
-            average_execution_time: 0,
-            success_rate: 0.0,
+            total_intents_matched: self.intents_matched.load(Ordering::Relaxed),
+            total_intents_executed: totals.executed,
+            total_profit: U256::from(totals.pnl.net.max(0) as u128),
+            average_execution_time: totals.total_execution_time.checked_div(totals.executed).unwrap_or(0),
+            success_rate: if attempts == 0 { 0.0 } else { totals.executed as f64 / attempts as f64 },
         }
     }
 }
 
-impl SolverNode {
-    fn estimate_execution_time(&self, route: &optimizer::Route) -> u64 {
-        // Base time for transaction confirmation
-        let mut time = 30; // seconds
-        
-        // Add time for each hop
-        time += route.hops.len() as u64 * 15;
-        
-        // Add buffer for cross-chain messages
-        if route.cross_chain {
-            time += 60;
-        }
-        
-        time
+#[async_trait]
+impl shared_auction::AuctionCallbacks for SolverNode {
+    async fn on_won(
+        &self,
+        announcement: &shared_auction::AuctionAnnouncement,
+        _bid: &shared_auction::SealedBid,
+    ) -> Result<()> {
+        let intent_id = announcement.intent.id();
+        self.match_intent(intent_id, &announcement.intent).await?;
+        self.execute_intent(intent_id).await.map(|_| ())
     }
-}
\ No newline at end of file
+}
+
+fn current_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
@@ -1,12 +1,19 @@
-use crate::{Result, SolverError, SolverConfig, SolverQuote};
+use crate::{Result, Solver, SolverError, SolverConfig, SolverQuote};
+use crate::oracle::PriceAggregator;
+use crate::pool_state::{PoolSnapshot, PoolStateProvider};
 use crate::reputation::ReputationManager;
+use crate::risk::{Exposure, RiskManager};
 use ethers::{
     prelude::*,
     types::{H256, U256, Address},
 };
-use intents_engine::intent::Intent;
+use intents_engine::{
+    gas_oracle::{FeeStrategy, GasOracle},
+    intent::Intent,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use serde::{Deserialize, Serialize};
 
@@ -33,10 +40,63 @@ pub enum MevPotential {
     Sandwich(U256),  // Cost of MEV protection
 }
 
+/// How the solver bids into auctions it is tracking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BiddingConfig {
+    /// Longest a quote may take to compute before the bid is abandoned
+    pub quote_budget_ms: u64,
+    /// Bids are re-quoted once, this close to the auction deadline
+    pub requote_window_secs: u64,
+    /// Pool price move since a bid was quoted that cancels the bid
+    pub stale_tolerance_bps: u64,
+    /// Auctions bid into at once; further bids are refused
+    pub max_concurrent_bids: usize,
+}
+
+impl Default for BiddingConfig {
+    fn default() -> Self {
+        Self {
+            quote_budget_ms: 500,
+            requote_window_secs: 2,
+            stale_tolerance_bps: 50,
+            max_concurrent_bids: 256,
+        }
+    }
+}
+
+/// Changes made by one [`IntentMatcher::refresh_bids`] pass
+#[derive(Debug, Clone, Default)]
+pub struct BidRefresh {
+    pub requoted: Vec<H256>,
+    pub cancelled: Vec<H256>,
+}
+
+/// A bid this solver has standing in an auction
+#[derive(Debug, Clone)]
+struct OwnBid {
+    quote: SolverQuote,
+    /// Pool exchange rate the quote was computed against
+    reference_rate: Option<U256>,
+    requoted: bool,
+}
+
+enum BidAction {
+    Kept,
+    Requoted,
+    Cancelled,
+}
+
 pub struct IntentMatcher {
     matched_intents: RwLock<HashMap<H256, MatchedIntent>>,
     pending_auctions: RwLock<HashMap<H256, IntentAuction>>,
+    own_bids: RwLock<HashMap<H256, OwnBid>>,
+    bidding: BiddingConfig,
     reputation_manager: Arc<ReputationManager>,
+    gas_oracle: Option<Arc<GasOracle>>,
+    price_oracle: Option<Arc<PriceAggregator>>,
+    pool_state: Option<Arc<PoolStateProvider>>,
+    risk_manager: Option<Arc<RiskManager>>,
 }
 
 #[derive(Clone)]
@@ -64,10 +124,46 @@ impl IntentMatcher {
         Self {
             matched_intents: RwLock::new(HashMap::new()),
             pending_auctions: RwLock::new(HashMap::new()),
+            own_bids: RwLock::new(HashMap::new()),
+            bidding: BiddingConfig::default(),
             reputation_manager,
+            gas_oracle: None,
+            price_oracle: None,
+            pool_state: None,
+            risk_manager: None,
         }
     }
 
+    /// Price gas from live fee markets instead of static per-chain defaults
+    pub fn with_gas_oracle(mut self, gas_oracle: Arc<GasOracle>) -> Self {
+        self.gas_oracle = Some(gas_oracle);
+        self
+    }
+
+    /// Price tokens from Chainlink/Pyth feeds
+    pub fn with_price_oracle(mut self, price_oracle: Arc<PriceAggregator>) -> Self {
+        self.price_oracle = Some(price_oracle);
+        self
+    }
+
+    /// Quote against live orbital pool state
+    pub fn with_pool_state(mut self, pool_state: Arc<PoolStateProvider>) -> Self {
+        self.pool_state = Some(pool_state);
+        self
+    }
+
+    /// Veto matches that would breach the solver's exposure limits
+    pub fn with_risk_manager(mut self, risk_manager: Arc<RiskManager>) -> Self {
+        self.risk_manager = Some(risk_manager);
+        self
+    }
+
+    /// Bid with `bidding` instead of the default budgets and tolerances
+    pub fn with_bidding(mut self, bidding: BiddingConfig) -> Self {
+        self.bidding = bidding;
+        self
+    }
+
     /// Start competitive auction for intent
     pub async fn start_auction(
         &self,
@@ -146,6 +242,8 @@ impl IntentMatcher {
 
         let auction = auctions.remove(&intent_id)
             .ok_or(SolverError::ExecutionFailed("Auction not found".to_string()))?;
+        drop(auctions);
+        self.own_bids.write().await.remove(&intent_id);
 
         // Check if auction deadline passed
         if current_timestamp() < auction.deadline {
@@ -271,6 +369,172 @@ impl IntentMatcher {
         let auctions = self.pending_auctions.read().await;
         auctions.keys().copied().collect()
     }
+
+    /// Drop an auction without selecting a winner
+    pub async fn cancel_auction(&self, intent_id: H256) {
+        self.pending_auctions.write().await.remove(&intent_id);
+        self.own_bids.write().await.remove(&intent_id);
+    }
+
+    /// Auctions this solver currently has a bid in
+    pub async fn active_bids(&self) -> Vec<H256> {
+        self.own_bids.read().await.keys().copied().collect()
+    }
+
+    /// Quote the intent of a pending auction with `solver`, within the quote
+    /// budget, and bid the quote into the auction
+    pub async fn place_bid(&self, intent_id: H256, solver: &dyn Solver) -> Result<SolverQuote> {
+        if self.own_bids.read().await.len() >= self.bidding.max_concurrent_bids {
+            return Err(SolverError::ExecutionFailed("Too many concurrent bids".to_string()));
+        }
+        let intent = self.auction_intent(intent_id).await?;
+
+        let quote = self.timed_quote(&intent, solver).await?;
+        let reference_rate = self.calculate_orbital_exchange_rate(&intent).await.ok();
+        self.submit_quote(intent_id, quote.clone()).await?;
+
+        self.own_bids.write().await.insert(intent_id, OwnBid {
+            quote: quote.clone(),
+            reference_rate,
+            requoted: false,
+        });
+        Ok(quote)
+    }
+
+    /// Withdraw this solver's bid from an auction. Returns `false` if there
+    /// was no bid.
+    pub async fn cancel_bid(&self, intent_id: H256) -> bool {
+        let Some(bid) = self.own_bids.write().await.remove(&intent_id) else {
+            return false;
+        };
+        if let Some(auction) = self.pending_auctions.write().await.get_mut(&intent_id) {
+            auction.quotes.retain(|quote| quote.solver != bid.quote.solver);
+        }
+        true
+    }
+
+    /// Check every standing bid: cancel bids whose pool has moved beyond the
+    /// stale tolerance and re-quote bids entering the last seconds of their
+    /// auction, so a late competitor cannot snipe a quote made on old state.
+    /// Bids are refreshed concurrently.
+    pub async fn refresh_bids(&self, solver: &dyn Solver) -> BidRefresh {
+        let bids: Vec<(H256, OwnBid)> = self.own_bids
+            .read()
+            .await
+            .iter()
+            .map(|(intent_id, bid)| (*intent_id, bid.clone()))
+            .collect();
+
+        let now = current_timestamp();
+        let actions = futures::future::join_all(
+            bids.into_iter().map(|(intent_id, bid)| async move {
+                (intent_id, self.refresh_bid(intent_id, bid, solver, now).await)
+            }),
+        )
+        .await;
+
+        let mut refresh = BidRefresh::default();
+        for (intent_id, action) in actions {
+            match action {
+                BidAction::Requoted => refresh.requoted.push(intent_id),
+                BidAction::Cancelled => refresh.cancelled.push(intent_id),
+                BidAction::Kept => {}
+            }
+        }
+        refresh
+    }
+
+    /// Refresh standing bids every second until the task is dropped
+    pub async fn run_bidding(self: Arc<Self>, solver: Arc<dyn Solver>) {
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            let refresh = self.refresh_bids(solver.as_ref()).await;
+            if !refresh.cancelled.is_empty() {
+                tracing::info!("Cancelled {} stale bids", refresh.cancelled.len());
+            }
+        }
+    }
+
+    async fn refresh_bid(&self, intent_id: H256, bid: OwnBid, solver: &dyn Solver, now: u64) -> BidAction {
+        let Some((intent, deadline)) = self.pending_auctions
+            .read()
+            .await
+            .get(&intent_id)
+            .map(|auction| (auction.intent.clone(), auction.deadline))
+        else {
+            self.own_bids.write().await.remove(&intent_id);
+            return BidAction::Cancelled;
+        };
+        if now > deadline {
+            return BidAction::Kept;
+        }
+
+        let current_rate = self.calculate_orbital_exchange_rate(&intent).await.ok();
+        if let (Some(reference), Some(current)) = (bid.reference_rate, current_rate) {
+            if pool_moved(reference, current, self.bidding.stale_tolerance_bps) {
+                self.cancel_bid(intent_id).await;
+                return BidAction::Cancelled;
+            }
+        }
+
+        if bid.requoted || !in_requote_window(deadline, now, self.bidding.requote_window_secs) {
+            return BidAction::Kept;
+        }
+
+        // A failed re-quote leaves the original bid standing
+        let (quote, action) = match self.timed_quote(&intent, solver).await {
+            Ok(quote) if self.replace_quote(intent_id, quote.clone()).await => (quote, BidAction::Requoted),
+            Ok(_) => (bid.quote, BidAction::Kept),
+            Err(e) => {
+                tracing::debug!("Re-quote for intent {:?} failed: {}", intent_id, e);
+                (bid.quote, BidAction::Kept)
+            }
+        };
+        if let Some(own) = self.own_bids.write().await.get_mut(&intent_id) {
+            own.quote = quote;
+            own.reference_rate = current_rate.or(own.reference_rate);
+            own.requoted = true;
+        }
+        action
+    }
+
+    async fn auction_intent(&self, intent_id: H256) -> Result<Intent> {
+        self.pending_auctions
+            .read()
+            .await
+            .get(&intent_id)
+            .map(|auction| auction.intent.clone())
+            .ok_or(SolverError::ExecutionFailed("Auction not found".to_string()))
+    }
+
+    async fn timed_quote(&self, intent: &Intent, solver: &dyn Solver) -> Result<SolverQuote> {
+        let budget = Duration::from_millis(self.bidding.quote_budget_ms);
+        tokio::time::timeout(budget, solver.evaluate_intent(intent))
+            .await
+            .map_err(|_| SolverError::ExecutionFailed(format!(
+                "Quote not ready within {}ms", self.bidding.quote_budget_ms
+            )))?
+    }
+
+    /// Swap the solver's quote in an open auction. Returns `false` if the
+    /// auction closed or the solver has no quote in it.
+    async fn replace_quote(&self, intent_id: H256, quote: SolverQuote) -> bool {
+        let mut auctions = self.pending_auctions.write().await;
+        let Some(auction) = auctions.get_mut(&intent_id) else {
+            return false;
+        };
+        if current_timestamp() > auction.deadline {
+            return false;
+        }
+        match auction.quotes.iter_mut().find(|q| q.solver == quote.solver) {
+            Some(existing) => {
+                *existing = quote;
+                true
+            }
+            None => false,
+        }
+    }
     
     /// Enhanced intent matching with orbital path optimization
     pub async fn match_intent(
@@ -292,6 +556,24 @@ impl IntentMatcher {
         // Calculate enhanced output amount using orbital mathematics
         let enhanced_dest_amount = self.calculate_orbital_output_amount(intent, &optimal_path).await?
             .unwrap_or(intent.min_dest_amount);
+
+        // Reserve exposure; the risk manager vetoes the match if a limit would be breached
+        if let Some(risk_manager) = &self.risk_manager {
+            let slippage_cost = self.calculate_orbital_slippage_impact(intent).await?;
+            let slippage_bps = if intent.min_dest_amount.is_zero() {
+                0
+            } else {
+                (slippage_cost * U256::from(10000) / intent.min_dest_amount).min(U256::from(u64::MAX)).as_u64()
+            };
+            let exposure = Exposure {
+                token: intent.dest_token,
+                chain_id: intent.dest_chain_id,
+                counterparty: intent.user,
+                notional: self.notional_usd(intent.dest_token, intent.dest_chain_id, enhanced_dest_amount).await?,
+                slippage_bps,
+            };
+            risk_manager.reserve(intent_id, exposure).await?;
+        }
         
         // Store matched intent with orbital enhancements
         let mut matched = self.matched_intents.write().await;
@@ -467,7 +749,7 @@ impl IntentMatcher {
         let total_gas = base_swap_gas + cross_chain_gas + routing_gas + mev_protection_gas;
         
         // Convert to cost using current gas price
-        let gas_price = self.get_current_gas_price(intent.source_chain_id).await?;
+        let gas_price = self.get_current_gas_price(intent).await?;
         let gas_cost_wei = total_gas * gas_price;
         
         // Convert to USD equivalent
@@ -729,7 +1011,7 @@ impl IntentMatcher {
         let total_gas = base_orbital_gas + constraint_verification_gas + routing_gas + tick_crossing_gas + cross_chain_gas;
         
         // Convert to cost using current gas price
-        let gas_price = self.get_current_gas_price(intent.source_chain_id).await?;
+        let gas_price = self.get_current_gas_price(intent).await?;
         let gas_cost_wei = total_gas * gas_price;
         
         // Convert to USD equivalent
@@ -885,27 +1167,35 @@ impl IntentMatcher {
     
     /// Get orbital pool reserves for token pair
     async fn get_orbital_pool_reserves(&self, source_token: Address, dest_token: Address, chain_id: u64) -> Result<Vec<U256>> {
-        // Mock implementation - in production, query actual orbital pool
-        let base_reserve = U256::from(1_000_000) * U256::from(10).pow(18.into());
-        
-        // Create reserves based on token addresses to ensure consistent behavior
-        let mut reserves = Vec::new();
-        reserves.push(base_reserve); // Source token reserve
-        reserves.push(base_reserve * U256::from(95) / U256::from(100)); // Dest token reserve (slightly different)
-        
-        Ok(reserves)
+        let snapshot = self.pool_snapshot(chain_id).await?;
+        let source_idx = self.get_token_index(source_token, &snapshot.tokens)?;
+        let dest_idx = self.get_token_index(dest_token, &snapshot.tokens)?;
+        Ok(vec![snapshot.reserves[source_idx], snapshot.reserves[dest_idx]])
     }
     
     /// Get available tokens in orbital pool
     async fn get_orbital_pool_tokens(&self, chain_id: u64) -> Result<Vec<Address>> {
-        // Mock implementation - in production, query actual pool tokens
-        Ok(vec![
-            Address::zero(), // ETH
-            "0xA0b86a33E6E4f6c5F1A6C8D5e4B3F4C4E8C8F4D4".parse().unwrap(), // USDC
-            "0xdAC17F958D2ee523a2206206994597C13D831ec7".parse().unwrap(), // USDT
-        ])
+        Ok(self.pool_snapshot(chain_id).await?.tokens.clone())
     }
     
+    /// Pool state provider, if it has a pool on `chain_id`
+    fn live_pool(&self, chain_id: u64) -> Option<&PoolStateProvider> {
+        self.pool_state.as_deref().filter(|pool_state| pool_state.has_pool(chain_id))
+    }
+
+    /// Latest state of the orbital pool on `chain_id`
+    ///
+    /// A chain without a configured pool has no liquidity to quote against.
+    async fn pool_snapshot(&self, chain_id: u64) -> Result<Arc<PoolSnapshot>> {
+        match self.live_pool(chain_id) {
+            Some(pool_state) => pool_state.snapshot(chain_id).await,
+            #[cfg(test)]
+            None => Ok(Arc::new(tests::mock_pool_snapshot())),
+            #[cfg(not(test))]
+            None => Err(SolverError::InsufficientLiquidity),
+        }
+    }
+
     /// Get token index in orbital pool
     fn get_token_index(&self, token: Address, available_tokens: &[Address]) -> Result<usize> {
         available_tokens.iter().position(|&t| t == token)
@@ -913,29 +1203,37 @@ impl IntentMatcher {
     }
     
     /// Get orbital pool state
+    ///
+    /// The AMM keys its concentrated liquidity ticks by plane constant, so each
+    /// initialized tick becomes a cap bounded at its index on the pool's sphere.
     async fn get_orbital_pool_state(&self, chain_id: u64) -> Result<orbital_math::types::PoolState> {
-        use orbital_math::types::{PoolState, CurveType, ReservePoint};
-        
-        let reserves = vec![
-            U256::from(1_000_000) * U256::from(10).pow(18.into()),
-            U256::from(950_000) * U256::from(10).pow(18.into()),
-            U256::from(1_050_000) * U256::from(10).pow(18.into()),
-        ];
-        
-        // Calculate radius squared for spherical constraint: Σ(r_i²) = R²
-        let radius_squared = reserves.iter()
-            .try_fold(U256::zero(), |acc, &r| {
-                r.checked_mul(r)?.checked_add(acc)
-            })
-            .ok_or_else(|| SolverError::ExecutionFailed("Failed to calculate radius".to_string()))?;
-        
+        use orbital_math::types::{PoolState, CurveType, Tick};
+
+        let snapshot = self.pool_snapshot(chain_id).await?;
+        if !snapshot.active {
+            return Err(SolverError::InsufficientLiquidity);
+        }
+
+        let radius = snapshot.radius_squared.integer_sqrt();
+        let ticks = snapshot
+            .ticks
+            .iter()
+            .map(|tick| Tick::new(tick.index, tick.index, tick.liquidity_gross, radius, 0))
+            .collect();
+
         Ok(PoolState::new(
-            reserves,
+            snapshot.reserves.clone(),
             CurveType::sphere(),
-            radius_squared,
-            vec![], // No ticks for now
+            snapshot.radius_squared,
+            ticks,
         ))
     }
+
+    /// `amount_in` less the pool's current dynamic fee
+    async fn amount_after_pool_fee(&self, chain_id: u64, amount_in: U256) -> Result<U256> {
+        let fee_bps = self.pool_snapshot(chain_id).await?.fee_bps.min(U256::from(10000));
+        Ok(amount_in * (U256::from(10000) - fee_bps) / U256::from(10000))
+    }
     
     /// Calculate market exchange rate from external sources
     async fn get_market_exchange_rate(&self, intent: &Intent) -> Result<U256> {
@@ -951,30 +1249,33 @@ impl IntentMatcher {
     
     /// Calculate direct orbital output
     async fn calculate_direct_orbital_output(&self, intent: &Intent, pool_state: &orbital_math::types::PoolState) -> Result<U256> {
-        use orbital_math::sphere::calculate_amount_out_sphere;
-        
-        let source_idx = self.get_token_index(intent.source_token, &self.get_orbital_pool_tokens(intent.source_chain_id).await?)?;
-        let dest_idx = self.get_token_index(intent.dest_token, &self.get_orbital_pool_tokens(intent.source_chain_id).await?)?;
-        
-        calculate_amount_out_sphere(
-            &pool_state.reserves.reserves,
-            source_idx,
-            dest_idx,
-            intent.source_amount,
-            pool_state.invariant,
-        ).map_err(|e| SolverError::ExecutionFailed(format!("Direct output calculation failed: {:?}", e)))
+        let available_tokens = self.get_orbital_pool_tokens(intent.source_chain_id).await?;
+        let source_idx = self.get_token_index(intent.source_token, &available_tokens)?;
+        let dest_idx = self.get_token_index(intent.dest_token, &available_tokens)?;
+
+        self.calculate_path_output(intent, &[source_idx, dest_idx], pool_state).await
     }
     
     /// Calculate output for a specific path
+    ///
+    /// Each hop pays the pool's current fee on its input and is split at the
+    /// tick boundaries it crosses.
     async fn calculate_path_output(&self, intent: &Intent, path: &[usize], pool_state: &orbital_math::types::PoolState) -> Result<U256> {
-        use orbital_math::trades::execute_multi_hop_swap;
-        
-        let mut pool_copy = pool_state.clone();
-        
-        match execute_multi_hop_swap(&mut pool_copy, path, intent.source_amount, U256::zero()) {
-            Ok(trade_info) => Ok(trade_info.amount_out),
-            Err(e) => Err(SolverError::ExecutionFailed(format!("Path output calculation failed: {:?}", e)))
+        use orbital_math::trades::solve_segmented_trade;
+        
+        let mut reserves = pool_state.reserves.reserves.clone();
+        let mut amount = intent.source_amount;
+        for hop in path.windows(2) {
+            let amount_in = self.amount_after_pool_fee(intent.source_chain_id, amount).await?;
+            let trade = solve_segmented_trade(&reserves, pool_state.invariant, &pool_state.ticks, hop[0], hop[1], amount_in)
+                .map_err(|e| SolverError::ExecutionFailed(format!("Path output calculation failed: {:?}", e)))?;
+
+            reserves[hop[0]] += amount_in;
+            reserves[hop[1]] -= trade.total_out;
+            amount = trade.total_out;
         }
+        
+        Ok(amount)
     }
     
     /// Calculate concentration liquidity bonus
@@ -1017,8 +1318,14 @@ impl IntentMatcher {
     
     /// Estimate tick crossing gas
     async fn estimate_tick_crossing_gas(&self, intent: &Intent) -> Result<U256> {
-        // Mock implementation - in production, analyze actual tick boundaries
-        let potential_crossings = self.estimate_tick_crossings(intent).await?;
+        let mut potential_crossings = self.estimate_tick_crossings(intent).await?;
+
+        // A trade can't cross more initialized ticks than the pool has near
+        // the current price
+        if let Some(live) = self.live_pool(intent.source_chain_id) {
+            let initialized = live.snapshot(intent.source_chain_id).await?.ticks.len();
+            potential_crossings = potential_crossings.min(initialized);
+        }
         let gas_per_crossing = U256::from(25_000);
         
         Ok(U256::from(potential_crossings as u64) * gas_per_crossing)
@@ -1258,25 +1565,9 @@ impl IntentMatcher {
             return Ok(None);
         }
         
-        // Calculate output using orbital mathematics
-        if path.len() == 2 {
-            // Direct swap
-            use orbital_math::sphere::calculate_amount_out_sphere;
-            
-            let amount_out = calculate_amount_out_sphere(
-                &pool_state.reserves.reserves,
-                path[0],
-                path[1],
-                intent.source_amount,
-                pool_state.invariant,
-            ).map_err(|e| SolverError::ExecutionFailed(format!("Orbital output calculation failed: {:?}", e)))?;
-            
-            Ok(Some(amount_out))
-        } else {
-            // Multi-hop swap
-            let output = self.calculate_path_output(intent, path, &pool_state).await?;
-            Ok(Some(output))
-        }
+        // Direct and multi-hop swaps both pay the pool fee per hop
+        let output = self.calculate_path_output(intent, path, &pool_state).await?;
+        Ok(Some(output))
     }
     
     /// Estimate execution time for orbital path
@@ -1332,30 +1623,28 @@ impl IntentMatcher {
         confidence.max(0.1).min(0.99) // Clamp between 10% and 99%
     }
     
+    /// USD value (18 decimals) of `amount` of `token`
+    pub async fn notional_usd(&self, token: Address, chain_id: u64, amount: U256) -> Result<U256> {
+        let price = self.get_token_price(token, chain_id).await?;
+        Ok(amount.saturating_mul(price) / U256::exp10(18))
+    }
+
+    /// USD value of `gas_used` on the intent's source chain at the current gas price
+    pub async fn gas_cost_usd(&self, intent: &Intent, gas_used: U256) -> Result<U256> {
+        let gas_price = self.get_current_gas_price(intent).await?;
+        self.notional_usd(Address::zero(), intent.source_chain_id, gas_used.saturating_mul(gas_price))
+            .await
+    }
+
     /// Get token price from external price oracles
     async fn get_token_price(&self, token: Address, chain_id: u64) -> Result<U256> {
-        // In production, this would integrate with price oracles like Chainlink
-        // For now, return mock prices based on token address
-        
-        let price = if token == Address::zero() {
-            // ETH price (mock)
-            U256::from(2000) * U256::from(10).pow(18.into()) // $2000 in 18 decimals
-        } else {
-            // Mock price calculation based on token address
-            let addr_bytes = token.as_bytes();
-            let price_factor = (addr_bytes[0] as u64 % 100) + 1;
-            U256::from(price_factor) * U256::from(10).pow(16.into()) // $0.01 to $1.00
-        };
-        
-        // Add some network-based adjustment
-        let network_multiplier = match chain_id {
-            1 => 100,     // Ethereum mainnet - higher prices
-            137 => 95,    // Polygon - slightly lower
-            42161 => 98,  // Arbitrum - close to mainnet
-            _ => 90,      // Other chains - lower prices
-        };
-        
-        Ok((price * U256::from(network_multiplier)) / U256::from(100))
+        match &self.price_oracle {
+            Some(oracle) => oracle.price(token, chain_id).await,
+            #[cfg(test)]
+            None => Ok(tests::mock_token_price(token, chain_id)),
+            #[cfg(not(test))]
+            None => Err(SolverError::PriceUnavailable("no price oracle configured".to_string())),
+        }
     }
     
     /// Get token volatility metrics
@@ -1413,24 +1702,32 @@ impl IntentMatcher {
         Ok(base_liquidity * U256::from(multiplier))
     }
     
-    /// Get current gas price for chain
-    async fn get_current_gas_price(&self, chain_id: u64) -> Result<U256> {
-        // Mock gas prices - in production would query gas price oracles
-        let base_gas_price = match chain_id {
+    /// Expected gas price for executing `intent` on its source chain, at the
+    /// fee strategy its deadline calls for
+    async fn get_current_gas_price(&self, intent: &Intent) -> Result<U256> {
+        let chain_id = intent.source_chain_id;
+
+        if let Some(oracle) = &self.gas_oracle {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            let strategy = FeeStrategy::for_deadline(intent.deadline.saturating_sub(now));
+
+            match oracle.estimate_for_chain(chain_id, strategy).await {
+                Ok(estimate) => return Ok(estimate.expected_gas_price()),
+                Err(e) => tracing::warn!("Gas oracle unavailable for chain {}: {}", chain_id, e),
+            }
+        }
+
+        // Conservative defaults when no fee data is available
+        let fallback_gas_price = match chain_id {
             1 => 20_000_000_000u64,      // 20 gwei for Ethereum
             137 => 30_000_000_000u64,    // 30 gwei for Polygon
             42161 => 1_000_000_000u64,   // 1 gwei for Arbitrum
             _ => 10_000_000_000u64,      // 10 gwei default
         };
-        
-        // Add some randomness to simulate gas price fluctuation
-        let fluctuation = (std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() % 50) as u64; // 0-50% fluctuation
-        
-        let adjusted_price = base_gas_price + (base_gas_price * fluctuation / 100);
-        Ok(U256::from(adjusted_price))
+        Ok(U256::from(fallback_gas_price))
     }
     
     /// Get bridge fee for cross-chain operations
@@ -1480,6 +1777,17 @@ impl IntentMatcher {
         // Check if this creates arbitrage opportunities
         let source_price = self.get_token_price(intent.source_token, intent.source_chain_id).await?;
         let dest_price = self.get_token_price(intent.dest_token, intent.dest_chain_id).await?;
+
+        // Oracles disagreeing about either token means the arbitrage estimate
+        // can't be trusted; price in protection instead
+        if let Some(oracle) = &self.price_oracle {
+            let window = std::time::Duration::from_secs(300);
+            if oracle.has_recent_alarm(intent.source_token, intent.source_chain_id, window).await
+                || oracle.has_recent_alarm(intent.dest_token, intent.dest_chain_id, window).await
+            {
+                return Ok(MevPotential::Sandwich(intent.source_amount / U256::from(1000)));
+            }
+        }
         
         // Calculate price difference
         let price_ratio = if dest_price > U256::zero() {
@@ -1517,6 +1825,7 @@ impl IntentMatcher {
         // Clean up expired auctions
         let mut auctions = self.pending_auctions.write().await;
         auctions.retain(|_, a| a.deadline > now && a.intent.deadline > now);
+        self.own_bids.write().await.retain(|intent_id, _| auctions.contains_key(intent_id));
     }
 
     /// Get winning quote for matched intent
@@ -1531,4 +1840,153 @@ fn current_timestamp() -> u64 {
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .as_secs()
-}
\ No newline at end of file
+}
+
+/// `true` if `current` differs from `reference` by more than `tolerance_bps`
+fn pool_moved(reference: U256, current: U256, tolerance_bps: u64) -> bool {
+    if reference.is_zero() {
+        return !current.is_zero();
+    }
+    let delta = if current > reference { current - reference } else { reference - current };
+    delta.saturating_mul(U256::from(10000)) > reference.saturating_mul(U256::from(tolerance_bps))
+}
+
+fn in_requote_window(deadline: u64, now: u64, window_secs: u64) -> bool {
+    now <= deadline && deadline - now <= window_secs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use intents_engine::intent::IntentExecution;
+
+    /// Address-derived USD price standing in for an oracle in tests
+    pub(super) fn mock_token_price(token: Address, chain_id: u64) -> U256 {
+        let price = if token == Address::zero() {
+            U256::from(2000) * U256::exp10(18) // $2000 in 18 decimals
+        } else {
+            let price_factor = (token.as_bytes()[0] as u64 % 100) + 1;
+            U256::from(price_factor) * U256::exp10(16) // $0.01 to $1.00
+        };
+
+        let network_multiplier = match chain_id {
+            1 => 100,
+            137 => 95,
+            42161 => 98,
+            _ => 90,
+        };
+
+        price * U256::from(network_multiplier) / U256::from(100)
+    }
+
+    /// Three-token pool standing in for live state when no pool is configured
+    pub(super) fn mock_pool_snapshot() -> PoolSnapshot {
+        let reserves = vec![
+            U256::from(1_000_000) * U256::exp10(18),
+            U256::from(950_000) * U256::exp10(18),
+            U256::from(1_050_000) * U256::exp10(18),
+        ];
+        let radius_squared = reserves.iter().fold(U256::zero(), |acc, &r| acc + r * r);
+
+        PoolSnapshot {
+            block_number: 0,
+            tokens: vec![
+                Address::zero(), // ETH
+                "0xA0b86a33E6E4f6c5F1A6C8D5e4B3F4C4E8C8F4D4".parse().unwrap(), // USDC
+                "0xdAC17F958D2ee523a2206206994597C13D831ec7".parse().unwrap(), // USDT
+            ],
+            reserves,
+            radius_squared,
+            concentrated_liquidity: U256::zero(),
+            active: true,
+            fee_bps: U256::zero(),
+            current_tick: U256::zero(),
+            active_liquidity: U256::zero(),
+            ticks: Vec::new(),
+        }
+    }
+
+    /// Quotes the intent's minimum output after `delay`
+    struct DelayedSolver {
+        address: Address,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl Solver for DelayedSolver {
+        async fn evaluate_intent(&self, intent: &Intent) -> Result<SolverQuote> {
+            tokio::time::sleep(self.delay).await;
+            Ok(SolverQuote {
+                solver: self.address,
+                dest_amount: intent.min_dest_amount,
+                profit: U256::zero(),
+                execution_time_estimate: 30,
+                confidence: 1.0,
+            })
+        }
+
+        async fn match_intent(&self, _intent_id: H256, _intent: &Intent) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute_intent(&self, _intent_id: H256) -> Result<IntentExecution> {
+            Err(SolverError::ExecutionFailed("not implemented".to_string()))
+        }
+
+        fn get_metrics(&self) -> crate::SolverMetrics {
+            crate::SolverMetrics {
+                total_intents_matched: 0,
+                total_intents_executed: 0,
+                total_profit: U256::zero(),
+                average_execution_time: 0,
+                success_rate: 0.0,
+            }
+        }
+    }
+
+    #[test]
+    fn test_stale_and_requote_thresholds() {
+        let reference = U256::from(10_000);
+        assert!(!pool_moved(reference, U256::from(10_050), 50));
+        assert!(pool_moved(reference, U256::from(10_051), 50));
+        assert!(pool_moved(reference, U256::from(9_900), 50));
+
+        assert!(!in_requote_window(100, 97, 2));
+        assert!(in_requote_window(100, 98, 2));
+        assert!(!in_requote_window(100, 101, 2));
+    }
+
+    #[tokio::test]
+    async fn test_bids_respect_quote_budget() {
+        let solver = Address::repeat_byte(0x42);
+        let reputation = Arc::new(ReputationManager::new());
+        reputation.register_solver(solver, U256::exp10(20)).await.unwrap();
+        let matcher = IntentMatcher::new(reputation).with_bidding(BiddingConfig {
+            quote_budget_ms: 50,
+            ..Default::default()
+        });
+
+        let intent = Intent {
+            source_amount: U256::exp10(15),
+            min_dest_amount: U256::from(1_000),
+            ..Default::default()
+        };
+        let slow_id = H256::repeat_byte(1);
+        let fast_id = H256::repeat_byte(2);
+        matcher.start_auction(slow_id, intent.clone(), 60).await.unwrap();
+        matcher.start_auction(fast_id, intent, 60).await.unwrap();
+
+        let slow = DelayedSolver { address: solver, delay: Duration::from_millis(200) };
+        assert!(matcher.place_bid(slow_id, &slow).await.is_err());
+
+        let fast = DelayedSolver { address: solver, delay: Duration::ZERO };
+        matcher.place_bid(fast_id, &fast).await.unwrap();
+        assert_eq!(matcher.active_bids().await, vec![fast_id]);
+
+        assert!(matcher.cancel_bid(fast_id).await);
+        assert!(matcher.active_bids().await.is_empty());
+        let auctions = matcher.pending_auctions.read().await;
+        assert!(auctions[&fast_id].quotes.is_empty());
+    }
+}
@@ -6,17 +6,35 @@
 //! - Success/failure rate monitoring
 //! - MEV protection effectiveness
 //! - Bridge operation metrics
-
-use crate::executor::{ExecutionMetrics, ExecutionStep};
-use ethers::types::{U256, H256};
+//!
+//! [`SolverMonitor`] watches a running node: chain RPC heartbeats, executions
+//! stuck in one step, inventory below configured floors and reputation
+//! losses. Alerts go to pluggable [`AlertSink`]s (log, webhook, PagerDuty)
+//! and the current state is served on `/health` for orchestrators.
+
+use crate::{
+    executor::{ExecutionMetrics, ExecutionStep, SolverExecutor},
+    rebalancer::balance_of,
+    reputation::ReputationManager,
+    Result, SolverError,
+};
+use async_trait::async_trait;
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use ethers::{
+    providers::Middleware,
+    types::{Address, U256, H256},
+};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::{
     collections::HashMap,
     sync::Arc,
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::RwLock;
-use tracing::{info, warn, debug};
+use tracing::{info, warn, debug, error};
+
+const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
 
 /// Detailed performance monitoring system
 #[derive(Debug)]
@@ -463,7 +481,7 @@ impl PerformanceMonitor {
     }
 
     /// Export metrics to JSON
-    pub async fn export_metrics(&self) -> Result<String, serde_json::Error> {
+    pub async fn export_metrics(&self) -> std::result::Result<String, serde_json::Error> {
         let dashboard = self.get_dashboard().await;
         serde_json::to_string_pretty(&dashboard)
     }
@@ -518,6 +536,593 @@ impl Default for DetailedMetrics {
     }
 }
 
+/// Health checks and alert routing for a running solver node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonitorConfig {
+    /// Address `/health` is served on; not served if unset
+    pub health_listen_address: Option<String>,
+    /// How often checks run
+    pub check_interval_secs: u64,
+    /// A component without a heartbeat for longer is considered down
+    pub heartbeat_timeout_secs: u64,
+    /// An execution running for longer is reported as stuck
+    pub stuck_execution_secs: u64,
+    /// Balances the solver must keep per chain and token
+    pub liquidity_floors: Vec<LiquidityFloor>,
+    /// Score points lost between two checks that trigger an alert
+    pub reputation_drop_threshold: u64,
+    /// Alert while our score is below this
+    pub min_reputation: u64,
+    /// An alert still firing is not repeated to sinks within this window
+    pub alert_cooldown_secs: u64,
+    pub sinks: Vec<SinkConfig>,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            health_listen_address: Some("0.0.0.0:8548".to_string()),
+            check_interval_secs: 15,
+            heartbeat_timeout_secs: 120,
+            stuck_execution_secs: 600,
+            liquidity_floors: Vec::new(),
+            reputation_drop_threshold: 200,
+            min_reputation: 3500,
+            alert_cooldown_secs: 300,
+            sinks: vec![SinkConfig::Log],
+        }
+    }
+}
+
+/// Minimum balance of `token` on `chain_id`; the zero address is the native asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidityFloor {
+    pub chain_id: u64,
+    pub token: Address,
+    pub minimum: U256,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkConfig {
+    Log,
+    Webhook { url: String },
+    PagerDuty {
+        routing_key: String,
+        /// Alerts below this severity are not paged
+        #[serde(default = "default_page_severity")]
+        min_severity: AlertSeverity,
+    },
+}
+
+fn default_page_severity() -> AlertSeverity {
+    AlertSeverity::Critical
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AlertKind {
+    MissedHeartbeat { component: String, silent_secs: u64 },
+    StuckExecution { intent_id: H256, step: ExecutionStep, running_secs: u64 },
+    LiquidityShortfall { chain_id: u64, token: Address, balance: U256, minimum: U256 },
+    ReputationDrop { previous: u64, current: u64 },
+    LowReputation { score: u64, minimum: u64 },
+}
+
+impl AlertKind {
+    pub fn severity(&self) -> AlertSeverity {
+        match self {
+            AlertKind::MissedHeartbeat { .. } | AlertKind::LowReputation { .. } => AlertSeverity::Critical,
+            AlertKind::StuckExecution { .. }
+            | AlertKind::LiquidityShortfall { .. }
+            | AlertKind::ReputationDrop { .. } => AlertSeverity::Warning,
+        }
+    }
+
+    /// Identifies the condition, so repeats of it can be throttled and deduplicated
+    pub fn key(&self) -> String {
+        match self {
+            AlertKind::MissedHeartbeat { component, .. } => format!("heartbeat:{}", component),
+            AlertKind::StuckExecution { intent_id, .. } => format!("stuck:{:?}", intent_id),
+            AlertKind::LiquidityShortfall { chain_id, token, .. } => format!("liquidity:{}:{:?}", chain_id, token),
+            AlertKind::ReputationDrop { .. } => "reputation_drop".to_string(),
+            AlertKind::LowReputation { .. } => "low_reputation".to_string(),
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        match self {
+            AlertKind::MissedHeartbeat { component, silent_secs } => {
+                format!("{} has not reported for {}s", component, silent_secs)
+            }
+            AlertKind::StuckExecution { intent_id, step, running_secs } => {
+                format!("Execution of {:?} stuck in {:?} for {}s", intent_id, step, running_secs)
+            }
+            AlertKind::LiquidityShortfall { chain_id, token, balance, minimum } => {
+                format!("Balance of {:?} on chain {} is {}, below {}", token, chain_id, balance, minimum)
+            }
+            AlertKind::ReputationDrop { previous, current } => {
+                format!("Reputation dropped from {} to {}", previous, current)
+            }
+            AlertKind::LowReputation { score, minimum } => {
+                format!("Reputation {} is below {}", score, minimum)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    pub severity: AlertSeverity,
+    #[serde(flatten)]
+    pub kind: AlertKind,
+    pub message: String,
+    pub raised_at: u64,
+}
+
+impl From<AlertKind> for Alert {
+    fn from(kind: AlertKind) -> Self {
+        Self {
+            severity: kind.severity(),
+            message: kind.summary(),
+            kind,
+            raised_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        }
+    }
+}
+
+/// Destination for alerts
+#[async_trait]
+pub trait AlertSink: Send + Sync {
+    fn name(&self) -> &str;
+    async fn send(&self, alert: &Alert) -> Result<()>;
+}
+
+/// Writes alerts to the tracing log
+pub struct LogSink;
+
+#[async_trait]
+impl AlertSink for LogSink {
+    fn name(&self) -> &str {
+        "log"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<()> {
+        match alert.severity {
+            AlertSeverity::Critical => error!("[alert] {}", alert.message),
+            AlertSeverity::Warning => warn!("[alert] {}", alert.message),
+            AlertSeverity::Info => info!("[alert] {}", alert.message),
+        }
+        Ok(())
+    }
+}
+
+/// POSTs every alert as JSON to a URL
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: String) -> Self {
+        Self { client: reqwest::Client::new(), url }
+    }
+}
+
+#[async_trait]
+impl AlertSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<()> {
+        self.client
+            .post(&self.url)
+            .json(alert)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| SolverError::ExecutionFailed(format!("Webhook alert failed: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Triggers PagerDuty incidents through the Events API v2, deduplicated by
+/// alert condition
+pub struct PagerDutySink {
+    client: reqwest::Client,
+    routing_key: String,
+    min_severity: AlertSeverity,
+}
+
+impl PagerDutySink {
+    pub fn new(routing_key: String, min_severity: AlertSeverity) -> Self {
+        Self { client: reqwest::Client::new(), routing_key, min_severity }
+    }
+}
+
+#[async_trait]
+impl AlertSink for PagerDutySink {
+    fn name(&self) -> &str {
+        "pagerduty"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<()> {
+        if alert.severity < self.min_severity {
+            return Ok(());
+        }
+        let event = json!({
+            "routing_key": self.routing_key,
+            "event_action": "trigger",
+            "dedup_key": alert.kind.key(),
+            "payload": {
+                "summary": alert.message,
+                "source": "orbital-solver",
+                "severity": match alert.severity {
+                    AlertSeverity::Critical => "critical",
+                    AlertSeverity::Warning => "warning",
+                    AlertSeverity::Info => "info",
+                },
+                "custom_details": alert,
+            },
+        });
+        self.client
+            .post(PAGERDUTY_EVENTS_URL)
+            .json(&event)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| SolverError::ExecutionFailed(format!("PagerDuty alert failed: {}", e)))?;
+        Ok(())
+    }
+}
+
+pub fn sink_from_config(config: &SinkConfig) -> Arc<dyn AlertSink> {
+    match config {
+        SinkConfig::Log => Arc::new(LogSink),
+        SinkConfig::Webhook { url } => Arc::new(WebhookSink::new(url.clone())),
+        SinkConfig::PagerDuty { routing_key, min_severity } => {
+            Arc::new(PagerDutySink::new(routing_key.clone(), *min_severity))
+        }
+    }
+}
+
+/// Last sign of life per component
+#[derive(Debug, Default)]
+pub struct Heartbeats {
+    last_seen: HashMap<String, Instant>,
+}
+
+impl Heartbeats {
+    pub fn beat(&mut self, component: &str) {
+        self.last_seen.insert(component.to_string(), Instant::now());
+    }
+
+    pub fn components(&self, timeout: Duration) -> Vec<ComponentHealth> {
+        let mut components: Vec<ComponentHealth> = self
+            .last_seen
+            .iter()
+            .map(|(name, seen)| ComponentHealth {
+                name: name.clone(),
+                last_seen_secs: seen.elapsed().as_secs(),
+                alive: seen.elapsed() <= timeout,
+            })
+            .collect();
+        components.sort_by(|a, b| a.name.cmp(&b.name));
+        components
+    }
+}
+
+/// Suppresses repeats of an alert within the cooldown
+#[derive(Debug)]
+pub struct AlertThrottle {
+    cooldown: Duration,
+    last_sent: HashMap<String, Instant>,
+}
+
+impl AlertThrottle {
+    pub fn new(cooldown: Duration) -> Self {
+        Self { cooldown, last_sent: HashMap::new() }
+    }
+
+    /// `true` if `key` has not been sent within the cooldown; records it as sent
+    pub fn admit(&mut self, key: &str, now: Instant) -> bool {
+        match self.last_sent.get(key) {
+            Some(sent) if now.duration_since(*sent) < self.cooldown => false,
+            _ => {
+                self.last_sent.insert(key.to_string(), now);
+                true
+            }
+        }
+    }
+
+    /// Forget conditions that are no longer firing, so a recurrence alerts at once
+    pub fn retain(&mut self, firing: &[Alert]) {
+        self.last_sent.retain(|key, _| firing.iter().any(|alert| alert.kind.key() == *key));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentHealth {
+    pub name: String,
+    pub last_seen_secs: u64,
+    pub alive: bool,
+}
+
+/// What `/health` returns
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthSummary {
+    pub status: HealthStatus,
+    pub uptime_secs: u64,
+    pub components: Vec<ComponentHealth>,
+    pub active_executions: usize,
+    /// Conditions firing as of the last check
+    pub alerts: Vec<Alert>,
+}
+
+impl HealthSummary {
+    /// Unhealthy if a component is down or a critical alert fires, degraded
+    /// on any other alert
+    pub fn status_of(components: &[ComponentHealth], alerts: &[Alert]) -> HealthStatus {
+        if components.iter().any(|c| !c.alive) || alerts.iter().any(|a| a.severity == AlertSeverity::Critical) {
+            HealthStatus::Unhealthy
+        } else if !alerts.is_empty() {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+}
+
+/// Alerts for a reputation score moving from `previous` to `current`
+pub fn reputation_alerts(previous: Option<u64>, current: u64, drop_threshold: u64, minimum: u64) -> Vec<AlertKind> {
+    let mut alerts = Vec::new();
+    if let Some(previous) = previous {
+        if previous.saturating_sub(current) >= drop_threshold.max(1) {
+            alerts.push(AlertKind::ReputationDrop { previous, current });
+        }
+    }
+    if current < minimum {
+        alerts.push(AlertKind::LowReputation { score: current, minimum });
+    }
+    alerts
+}
+
+/// Watches a solver node: chain RPC heartbeats, stuck executions, inventory
+/// below configured floors and reputation losses, routing alerts to sinks
+pub struct SolverMonitor {
+    config: MonitorConfig,
+    executor: Arc<SolverExecutor>,
+    reputation: Arc<ReputationManager>,
+    sinks: Vec<Arc<dyn AlertSink>>,
+    heartbeats: RwLock<Heartbeats>,
+    last_blocks: RwLock<HashMap<u64, u64>>,
+    last_score: RwLock<Option<u64>>,
+    firing: RwLock<Vec<Alert>>,
+    throttle: RwLock<AlertThrottle>,
+    started_at: Instant,
+}
+
+impl SolverMonitor {
+    pub fn new(config: MonitorConfig, executor: Arc<SolverExecutor>, reputation: Arc<ReputationManager>) -> Self {
+        let sinks = config.sinks.iter().map(sink_from_config).collect();
+        let throttle = AlertThrottle::new(Duration::from_secs(config.alert_cooldown_secs));
+
+        // Chains count as alive from startup until they miss a heartbeat
+        let mut heartbeats = Heartbeats::default();
+        for chain_id in executor.providers().keys() {
+            heartbeats.beat(&chain_component(*chain_id));
+        }
+
+        Self {
+            config,
+            executor,
+            reputation,
+            sinks,
+            heartbeats: RwLock::new(heartbeats),
+            last_blocks: RwLock::new(HashMap::new()),
+            last_score: RwLock::new(None),
+            firing: RwLock::new(Vec::new()),
+            throttle: RwLock::new(throttle),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Also deliver alerts to `sink`
+    pub fn with_sink(mut self, sink: Arc<dyn AlertSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Record that `component` is alive
+    pub async fn heartbeat(&self, component: &str) {
+        self.heartbeats.write().await.beat(component);
+    }
+
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.check_interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            self.heartbeat("monitor").await;
+            self.check().await;
+        }
+    }
+
+    /// Run every check, deliver new alerts and return everything firing
+    pub async fn check(&self) -> Vec<Alert> {
+        self.poll_chains().await;
+
+        let mut kinds = self.stale_components().await;
+        kinds.extend(self.stuck_executions().await);
+        kinds.extend(self.liquidity_shortfalls().await);
+        kinds.extend(self.reputation_changes().await);
+        let firing: Vec<Alert> = kinds.into_iter().map(Alert::from).collect();
+
+        let now = Instant::now();
+        let due: Vec<&Alert> = {
+            let mut throttle = self.throttle.write().await;
+            throttle.retain(&firing);
+            firing.iter().filter(|alert| throttle.admit(&alert.kind.key(), now)).collect()
+        };
+        for alert in due {
+            self.dispatch(alert).await;
+        }
+
+        *self.firing.write().await = firing.clone();
+        firing
+    }
+
+    pub async fn health(&self) -> HealthSummary {
+        let components = self
+            .heartbeats
+            .read()
+            .await
+            .components(Duration::from_secs(self.config.heartbeat_timeout_secs));
+        let alerts = self.firing.read().await.clone();
+
+        HealthSummary {
+            status: HealthSummary::status_of(&components, &alerts),
+            uptime_secs: self.started_at.elapsed().as_secs(),
+            components,
+            active_executions: self.executor.get_active_executions().await.len(),
+            alerts,
+        }
+    }
+
+    /// Serve `/health`: 200 while healthy or degraded, 503 when unhealthy
+    pub async fn serve_health(self: Arc<Self>) -> Result<()> {
+        let Some(address) = self.config.health_listen_address.clone() else {
+            return Ok(());
+        };
+        let listener = tokio::net::TcpListener::bind(&address)
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Failed to bind health endpoint: {}", e)))?;
+        info!("Health endpoint listening on {}", address);
+
+        let app = Router::new().route("/health", get(handle_health)).with_state(self);
+        axum::serve(listener, app)
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Health server error: {}", e)))
+    }
+
+    async fn dispatch(&self, alert: &Alert) {
+        for sink in &self.sinks {
+            if let Err(e) = sink.send(alert).await {
+                warn!("Alert sink {} failed: {}", sink.name(), e);
+            }
+        }
+    }
+
+    /// A chain is alive while its RPC answers with advancing blocks
+    async fn poll_chains(&self) {
+        for (&chain_id, provider) in self.executor.providers() {
+            let block = match provider.get_block_number().await {
+                Ok(block) => block.as_u64(),
+                Err(e) => {
+                    debug!("Chain {} RPC unreachable: {}", chain_id, e);
+                    continue;
+                }
+            };
+            let advanced = self.last_blocks.write().await.insert(chain_id, block).map_or(true, |last| block > last);
+            if advanced {
+                self.heartbeat(&chain_component(chain_id)).await;
+            }
+        }
+    }
+
+    async fn stale_components(&self) -> Vec<AlertKind> {
+        let timeout = Duration::from_secs(self.config.heartbeat_timeout_secs);
+        self.heartbeats
+            .read()
+            .await
+            .components(timeout)
+            .into_iter()
+            .filter(|component| !component.alive)
+            .map(|component| AlertKind::MissedHeartbeat {
+                component: component.name,
+                silent_secs: component.last_seen_secs,
+            })
+            .collect()
+    }
+
+    async fn stuck_executions(&self) -> Vec<AlertKind> {
+        let threshold = Duration::from_secs(self.config.stuck_execution_secs);
+        self.executor
+            .active_execution_ages()
+            .await
+            .into_iter()
+            .filter(|(_, _, age)| *age > threshold)
+            .map(|(intent_id, step, age)| AlertKind::StuckExecution {
+                intent_id,
+                step,
+                running_secs: age.as_secs(),
+            })
+            .collect()
+    }
+
+    async fn liquidity_shortfalls(&self) -> Vec<AlertKind> {
+        let account = self.executor.address();
+        let mut alerts = Vec::new();
+        for floor in &self.config.liquidity_floors {
+            let Some(provider) = self.executor.providers().get(&floor.chain_id) else { continue };
+            match balance_of(provider, floor.token, account).await {
+                Ok(balance) if balance < floor.minimum => alerts.push(AlertKind::LiquidityShortfall {
+                    chain_id: floor.chain_id,
+                    token: floor.token,
+                    balance,
+                    minimum: floor.minimum,
+                }),
+                Ok(_) => {}
+                Err(e) => debug!("Skipping liquidity check on chain {}: {}", floor.chain_id, e),
+            }
+        }
+        alerts
+    }
+
+    async fn reputation_changes(&self) -> Vec<AlertKind> {
+        let Some(reputation) = self.reputation.get_reputation(self.executor.address()).await else {
+            return Vec::new();
+        };
+        let previous = self.last_score.write().await.replace(reputation.score);
+        reputation_alerts(
+            previous,
+            reputation.score,
+            self.config.reputation_drop_threshold,
+            self.config.min_reputation,
+        )
+    }
+}
+
+async fn handle_health(State(monitor): State<Arc<SolverMonitor>>) -> (StatusCode, Json<HealthSummary>) {
+    let summary = monitor.health().await;
+    let status = match summary.status {
+        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+        HealthStatus::Healthy | HealthStatus::Degraded => StatusCode::OK,
+    };
+    (status, Json(summary))
+}
+
+fn chain_component(chain_id: u64) -> String {
+    format!("chain:{}", chain_id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -592,4 +1197,43 @@ mod tests {
             _ => panic!("Wrong alert type"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_alert_throttle_and_health_status() {
+        let stuck = Alert::from(AlertKind::StuckExecution {
+            intent_id: H256::from_low_u64_be(7),
+            step: ExecutionStep::WaitingForBridgeConfirmation,
+            running_secs: 900,
+        });
+        let mut throttle = AlertThrottle::new(Duration::from_secs(300));
+        let now = Instant::now();
+
+        assert!(throttle.admit(&stuck.kind.key(), now));
+        assert!(!throttle.admit(&stuck.kind.key(), now + Duration::from_secs(60)));
+        assert!(throttle.admit(&stuck.kind.key(), now + Duration::from_secs(301)));
+
+        // A resolved condition alerts again as soon as it recurs
+        throttle.retain(&[]);
+        assert!(throttle.admit(&stuck.kind.key(), now + Duration::from_secs(302)));
+
+        let alive = ComponentHealth { name: "chain:1".to_string(), last_seen_secs: 5, alive: true };
+        let dead = ComponentHealth { name: "chain:137".to_string(), last_seen_secs: 600, alive: false };
+        assert_eq!(HealthSummary::status_of(&[alive.clone()], &[]), HealthStatus::Healthy);
+        assert_eq!(HealthSummary::status_of(&[alive.clone()], &[stuck]), HealthStatus::Degraded);
+        assert_eq!(HealthSummary::status_of(&[alive, dead], &[]), HealthStatus::Unhealthy);
+    }
+
+    #[test]
+    fn test_reputation_alerts() {
+        assert!(reputation_alerts(None, 5000, 200, 3500).is_empty());
+        assert!(reputation_alerts(Some(5000), 4900, 200, 3500).is_empty());
+        assert_eq!(
+            reputation_alerts(Some(5000), 4800, 200, 3500),
+            vec![AlertKind::ReputationDrop { previous: 5000, current: 4800 }]
+        );
+
+        let alerts = reputation_alerts(Some(3600), 3300, 200, 3500);
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[1].severity(), AlertSeverity::Critical);
+    }
+}
@@ -1,15 +1,37 @@
-use crate::{Result, SolverError, SolverConfig};
+use crate::{
+    venues::{SwapCall, Venue, VenuePool},
+    Result, SolverError, SolverConfig,
+};
 use ethers::types::{Address, U256};
 use intents_engine::intent::Intent;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
+
+/// Longest swap path considered on one chain
+const MAX_ROUTE_HOPS: usize = 3;
+/// Paths an order may be split across
+const MAX_SPLIT_PATHS: usize = 3;
+/// Split granularity: orders are divided into this many equal chunks
+const SPLIT_CHUNKS: usize = 4;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Route {
+    /// Every hop of every leg, in execution order
     pub hops: Vec<Hop>,
     pub estimated_gas: U256,
     pub estimated_output: U256,
     pub cross_chain: bool,
+    /// Parallel paths a swap is split across; empty for single-path routes
+    #[serde(default)]
+    pub legs: Vec<RouteLeg>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteLeg {
+    pub share_bps: u16,
+    pub amount_in: U256,
+    pub amount_out: U256,
+    pub hops: Vec<Hop>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,9 +43,12 @@ pub struct Hop {
     pub token_out: Address,
     pub amount_in: U256,
     pub amount_out: U256,
+    /// Call executing this hop, for venue-sourced hops
+    #[serde(default)]
+    pub call: Option<SwapCall>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Protocol {
     OrbitalAMM,
     UniswapV3,
@@ -35,6 +60,92 @@ pub enum Protocol {
 pub struct RouteOptimizer {
     pools: HashMap<u64, Vec<PoolInfo>>,
     bridges: Vec<BridgeInfo>,
+    venues: Vec<Arc<dyn Venue>>,
+    /// Receives swap output in venue calldata
+    recipient: Address,
+}
+
+/// One hop of a candidate path through the routing graph
+#[derive(Debug, Clone)]
+struct PathStep {
+    venue: usize,
+    pool: VenuePool,
+    token_in: Address,
+    token_out: Address,
+}
+
+/// Tokens connected by venue pools on one chain
+struct RoutingGraph {
+    edges: HashMap<Address, Vec<(usize, VenuePool)>>,
+}
+
+impl RoutingGraph {
+    fn new(pools: impl IntoIterator<Item = (usize, VenuePool)>) -> Self {
+        let mut edges: HashMap<Address, Vec<(usize, VenuePool)>> = HashMap::new();
+        for (venue, pool) in pools {
+            edges.entry(pool.token0).or_default().push((venue, pool.clone()));
+            edges.entry(pool.token1).or_default().push((venue, pool));
+        }
+        Self { edges }
+    }
+
+    /// Simple paths from `from` to `to` of at most `max_hops` hops
+    fn paths(&self, from: Address, to: Address, max_hops: usize) -> Vec<Vec<PathStep>> {
+        let mut paths = Vec::new();
+        let mut current = Vec::new();
+        self.walk(from, to, max_hops, &mut vec![from], &mut current, &mut paths);
+        paths
+    }
+
+    fn walk(
+        &self,
+        at: Address,
+        to: Address,
+        hops_left: usize,
+        visited: &mut Vec<Address>,
+        current: &mut Vec<PathStep>,
+        paths: &mut Vec<Vec<PathStep>>,
+    ) {
+        if hops_left == 0 {
+            return;
+        }
+        for (venue, pool) in self.edges.get(&at).into_iter().flatten() {
+            let Some(next) = pool.other(at) else { continue };
+            if visited.contains(&next) {
+                continue;
+            }
+
+            current.push(PathStep { venue: *venue, pool: pool.clone(), token_in: at, token_out: next });
+            if next == to {
+                paths.push(current.clone());
+            } else {
+                visited.push(next);
+                self.walk(next, to, hops_left - 1, visited, current, paths);
+                visited.pop();
+            }
+            current.pop();
+        }
+    }
+}
+
+/// Greedily assign `chunks` equal chunks of an order to paths.
+/// `outputs[p][k]` is the output of path `p` for `k` chunks, with
+/// `outputs[p][0] == 0`. Returns the chunk count per path.
+fn allocate_splits(outputs: &[Vec<U256>], chunks: usize) -> Vec<usize> {
+    let mut allocation = vec![0; outputs.len()];
+    for _ in 0..chunks {
+        let best = outputs
+            .iter()
+            .enumerate()
+            .filter(|(p, quotes)| allocation[*p] + 1 < quotes.len())
+            .max_by_key(|(p, quotes)| quotes[allocation[*p] + 1].saturating_sub(quotes[allocation[*p]]))
+            .map(|(p, _)| p);
+        match best {
+            Some(p) => allocation[p] += 1,
+            None => break,
+        }
+    }
+    allocation
 }
 
 #[derive(Clone)]
@@ -62,13 +173,39 @@ impl RouteOptimizer {
         let mut optimizer = Self {
             pools: HashMap::new(),
             bridges: Vec::new(),
+            venues: Vec::new(),
+            recipient: config.address,
         };
         
         // Load pool and bridge information from on-chain
-        optimizer.load_pools_and_bridges(config).await?;
+        optimizer.load_pools_and_bridges(&config.supported_chains).await?;
         
         Ok(optimizer)
     }
+
+    /// Optimizer for pricing routes only, e.g. API simulations. Venue calls
+    /// it builds have no recipient.
+    pub async fn for_chains(supported_chains: &[u64]) -> Result<Self> {
+        let mut optimizer = Self {
+            pools: HashMap::new(),
+            bridges: Vec::new(),
+            venues: Vec::new(),
+            recipient: Address::zero(),
+        };
+        optimizer.load_pools_and_bridges(supported_chains).await?;
+        Ok(optimizer)
+    }
+
+    /// Source liquidity from live DEX venues. Chains with venues are routed
+    /// through them only; the rest keep the built-in pool set.
+    pub fn with_venues(mut self, venues: Vec<Arc<dyn Venue>>) -> Self {
+        self.venues = venues;
+        self
+    }
+
+    fn has_venues(&self, chain_id: u64) -> bool {
+        self.venues.iter().any(|venue| venue.chain_id() == chain_id)
+    }
     
     pub async fn find_best_route(&self, intent: &Intent) -> Result<Route> {
         if intent.source_chain_id == intent.dest_chain_id {
@@ -79,6 +216,23 @@ impl RouteOptimizer {
     }
     
     async fn find_single_chain_route(&self, intent: &Intent) -> Result<Route> {
+        if self.has_venues(intent.source_chain_id) {
+            let (amount_out, legs) = self
+                .route_via_venues(
+                    intent.source_chain_id,
+                    intent.source_token,
+                    intent.dest_token,
+                    intent.source_amount,
+                    intent.min_dest_amount,
+                )
+                .await?
+                .ok_or(SolverError::InsufficientLiquidity)?;
+            if amount_out < intent.min_dest_amount {
+                return Err(SolverError::InsufficientLiquidity);
+            }
+            return Ok(Self::composite_route(legs, amount_out, false));
+        }
+
         let pools = self.pools.get(&intent.source_chain_id)
             .ok_or(SolverError::ChainNotSupported(intent.source_chain_id))?;
         
@@ -104,10 +258,12 @@ impl RouteOptimizer {
                         token_out: intent.dest_token,
                         amount_in: intent.source_amount,
                         amount_out,
+                        call: None,
                     }],
                     estimated_gas: U256::from(150000),
                     estimated_output: amount_out,
                     cross_chain: false,
+                    legs: Vec::new(),
                 });
             }
         }
@@ -141,10 +297,12 @@ impl RouteOptimizer {
                         token_out: intent.dest_token,
                         amount_in: intent.source_amount,
                         amount_out: amount_after_bridge,
+                        call: None,
                     }],
                     estimated_gas: U256::from(300000),
                     estimated_output: amount_after_bridge,
                     cross_chain: true,
+                    legs: Vec::new(),
                 });
             }
         }
@@ -191,6 +349,7 @@ impl RouteOptimizer {
                                     token_out: base_token,
                                     amount_in: intent.source_amount,
                                     amount_out: mid_amount,
+                                    call: None,
                                 },
                                 Hop {
                                     protocol: pool2.protocol,
@@ -200,11 +359,13 @@ impl RouteOptimizer {
                                     token_out: intent.dest_token,
                                     amount_in: mid_amount,
                                     amount_out: final_amount,
+                                    call: None,
                                 },
                             ],
                             estimated_gas: U256::from(250000),
                             estimated_output: final_amount,
                             cross_chain: false,
+                            legs: Vec::new(),
                         });
                     }
                 }
@@ -282,15 +443,15 @@ impl RouteOptimizer {
     }
     
     /// Load pools and bridges information from on-chain sources
-    async fn load_pools_and_bridges(&mut self, config: &SolverConfig) -> Result<()> {
+    async fn load_pools_and_bridges(&mut self, supported_chains: &[u64]) -> Result<()> {
         // Load pools for each supported chain
-        for chain_id in &config.supported_chains {
+        for chain_id in supported_chains {
             let pools = self.load_chain_pools(*chain_id).await?;
             self.pools.insert(*chain_id, pools);
         }
         
         // Load bridge information
-        self.bridges = self.load_bridge_info(&config.supported_chains).await?;
+        self.bridges = self.load_bridge_info(supported_chains).await?;
         
         Ok(())
     }
@@ -385,12 +546,6 @@ impl RouteOptimizer {
     async fn find_complex_cross_chain_route(&self, intent: &Intent) -> Result<Route> {
         // Strategy: source_token -> bridge_token (source chain) -> bridge_token (dest chain) -> dest_token
         
-        let source_pools = self.pools.get(&intent.source_chain_id)
-            .ok_or(SolverError::ChainNotSupported(intent.source_chain_id))?;
-        
-        let dest_pools = self.pools.get(&intent.dest_chain_id)
-            .ok_or(SolverError::ChainNotSupported(intent.dest_chain_id))?;
-        
         // Find available bridges
         let available_bridges: Vec<&BridgeInfo> = self.bridges
             .iter()
@@ -405,22 +560,13 @@ impl RouteOptimizer {
                 // Route: source_token -> source_bridge_token -> dest_bridge_token -> dest_token
                 
                 // Step 1: Swap on source chain (if needed)
-                let after_source_swap = if intent.source_token == *source_bridge_token {
-                    intent.source_amount
-                } else {
-                    if let Some(source_pool) = self.find_direct_pool(
-                        source_pools,
-                        intent.source_token,
-                        *source_bridge_token,
-                    ) {
-                        self.calculate_swap_output(
-                            source_pool,
-                            intent.source_token,
-                            intent.source_amount,
-                        )
-                    } else {
-                        continue; // No route available
-                    }
+                let Some((after_source_swap, source_hops)) = self.swap_on_chain(
+                    intent.source_chain_id,
+                    intent.source_token,
+                    *source_bridge_token,
+                    intent.source_amount,
+                ).await? else {
+                    continue; // No route available
                 };
                 
                 // Step 2: Bridge
@@ -428,44 +574,20 @@ impl RouteOptimizer {
                 let after_bridge = after_source_swap.saturating_sub(bridge_fee);
                 
                 // Step 3: Swap on destination chain (if needed)
-                let final_amount = if *dest_bridge_token == intent.dest_token {
-                    after_bridge
-                } else {
-                    if let Some(dest_pool) = self.find_direct_pool(
-                        dest_pools,
-                        *dest_bridge_token,
-                        intent.dest_token,
-                    ) {
-                        self.calculate_swap_output(dest_pool, *dest_bridge_token, after_bridge)
-                    } else {
-                        continue; // No route available
-                    }
+                let Some((final_amount, dest_hops)) = self.swap_on_chain(
+                    intent.dest_chain_id,
+                    *dest_bridge_token,
+                    intent.dest_token,
+                    after_bridge,
+                ).await? else {
+                    continue; // No route available
                 };
                 
                 if final_amount > best_output && final_amount >= intent.min_dest_amount {
                     best_output = final_amount;
                     
                     // Build route with all hops
-                    let mut hops = Vec::new();
-                    
-                    // Source swap hop (if needed)
-                    if intent.source_token != *source_bridge_token {
-                        if let Some(source_pool) = self.find_direct_pool(
-                            source_pools,
-                            intent.source_token,
-                            *source_bridge_token,
-                        ) {
-                            hops.push(Hop {
-                                protocol: source_pool.protocol,
-                                chain_id: intent.source_chain_id,
-                                pool_address: source_pool.address,
-                                token_in: intent.source_token,
-                                token_out: *source_bridge_token,
-                                amount_in: intent.source_amount,
-                                amount_out: after_source_swap,
-                            });
-                        }
-                    }
+                    let mut hops = source_hops;
                     
                     // Bridge hop
                     hops.push(Hop {
@@ -476,32 +598,17 @@ impl RouteOptimizer {
                         token_out: *dest_bridge_token,
                         amount_in: after_source_swap,
                         amount_out: after_bridge,
+                        call: None,
                     });
                     
-                    // Destination swap hop (if needed)
-                    if *dest_bridge_token != intent.dest_token {
-                        if let Some(dest_pool) = self.find_direct_pool(
-                            dest_pools,
-                            *dest_bridge_token,
-                            intent.dest_token,
-                        ) {
-                            hops.push(Hop {
-                                protocol: dest_pool.protocol,
-                                chain_id: intent.dest_chain_id,
-                                pool_address: dest_pool.address,
-                                token_in: *dest_bridge_token,
-                                token_out: intent.dest_token,
-                                amount_in: after_bridge,
-                                amount_out: final_amount,
-                            });
-                        }
-                    }
+                    hops.extend(dest_hops);
                     
                     best_route = Some(Route {
                         hops,
                         estimated_gas: U256::from(500_000), // Higher gas for complex route
                         estimated_output: final_amount,
                         cross_chain: true,
+                        legs: Vec::new(),
                     });
                 }
             }
@@ -509,6 +616,166 @@ impl RouteOptimizer {
         
         best_route.ok_or(SolverError::InsufficientLiquidity)
     }
+
+    /// Swap `amount_in` of `token_in` into `token_out` on one chain, returning
+    /// the output and hops, or `None` without liquidity. Same-token swaps are
+    /// a no-op.
+    async fn swap_on_chain(
+        &self,
+        chain_id: u64,
+        token_in: Address,
+        token_out: Address,
+        amount_in: U256,
+    ) -> Result<Option<(U256, Vec<Hop>)>> {
+        if token_in == token_out {
+            return Ok(Some((amount_in, Vec::new())));
+        }
+
+        if self.has_venues(chain_id) {
+            return Ok(self
+                .route_via_venues(chain_id, token_in, token_out, amount_in, U256::zero())
+                .await?
+                .map(|(amount_out, legs)| (amount_out, legs.into_iter().flat_map(|leg| leg.hops).collect())));
+        }
+
+        let pools = self.pools.get(&chain_id).ok_or(SolverError::ChainNotSupported(chain_id))?;
+        Ok(self.find_direct_pool(pools, token_in, token_out).map(|pool| {
+            let amount_out = self.calculate_swap_output(pool, token_in, amount_in);
+            let hop = Hop {
+                protocol: pool.protocol,
+                chain_id,
+                pool_address: pool.address,
+                token_in,
+                token_out,
+                amount_in,
+                amount_out,
+                call: None,
+            };
+            (amount_out, vec![hop])
+        }))
+    }
+
+    /// Best way to swap through the venues on `chain_id`, split across up to
+    /// `MAX_SPLIT_PATHS` paths. Returns the total output and one leg per
+    /// path used, or `None` if no path connects the tokens.
+    async fn route_via_venues(
+        &self,
+        chain_id: u64,
+        token_in: Address,
+        token_out: Address,
+        amount_in: U256,
+        min_amount_out: U256,
+    ) -> Result<Option<(U256, Vec<RouteLeg>)>> {
+        let graph = RoutingGraph::new(
+            self.venues
+                .iter()
+                .enumerate()
+                .filter(|(_, venue)| venue.chain_id() == chain_id)
+                .flat_map(|(i, venue)| venue.pools().iter().cloned().map(move |pool| (i, pool))),
+        );
+
+        // Rank paths by their quote for the full amount
+        let mut candidates = Vec::new();
+        for path in graph.paths(token_in, token_out, MAX_ROUTE_HOPS) {
+            match self.quote_path(&path, amount_in).await {
+                Ok(output) if !output.is_zero() => candidates.push((output, path)),
+                Ok(_) => {}
+                Err(e) => tracing::debug!("Skipping path on chain {}: {}", chain_id, e),
+            }
+        }
+        candidates.sort_by(|a, b| b.0.cmp(&a.0));
+        candidates.truncate(MAX_SPLIT_PATHS);
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+
+        // Quote each path at every chunk size, then split greedily
+        let mut outputs = Vec::with_capacity(candidates.len());
+        for (full_output, path) in &candidates {
+            let mut quotes = vec![U256::zero()];
+            for k in 1..SPLIT_CHUNKS {
+                let chunk_amount = amount_in * U256::from(k) / U256::from(SPLIT_CHUNKS);
+                quotes.push(self.quote_path(path, chunk_amount).await.unwrap_or_default());
+            }
+            quotes.push(*full_output);
+            outputs.push(quotes);
+        }
+        let allocation = allocate_splits(&outputs, SPLIT_CHUNKS);
+        let expected_total: U256 = allocation.iter().zip(&outputs).map(|(&k, quotes)| quotes[k]).fold(U256::zero(), |a, b| a + b);
+        if expected_total.is_zero() {
+            return Ok(None);
+        }
+
+        let mut legs = Vec::new();
+        let mut remaining = amount_in;
+        let used: Vec<usize> = (0..candidates.len()).filter(|&p| allocation[p] > 0).collect();
+        for (n, &p) in used.iter().enumerate() {
+            let leg_amount = if n + 1 == used.len() {
+                remaining
+            } else {
+                amount_in * U256::from(allocation[p]) / U256::from(SPLIT_CHUNKS)
+            };
+            remaining -= leg_amount;
+
+            // Each leg must return its share of the minimum output
+            let leg_min = min_amount_out * outputs[p][allocation[p]] / expected_total;
+            let leg = self.build_leg(&candidates[p].1, leg_amount, leg_min).await?;
+            legs.push(RouteLeg {
+                share_bps: (allocation[p] * 10000 / SPLIT_CHUNKS) as u16,
+                ..leg
+            });
+        }
+
+        let total = legs.iter().fold(U256::zero(), |acc, leg| acc + leg.amount_out);
+        Ok(Some((total, legs)))
+    }
+
+    async fn quote_path(&self, path: &[PathStep], amount_in: U256) -> Result<U256> {
+        let mut amount = amount_in;
+        for step in path {
+            amount = self.venues[step.venue].quote(&step.pool, step.token_in, amount).await?;
+        }
+        Ok(amount)
+    }
+
+    /// Quote `path` hop by hop and encode each hop's swap call. Intermediate
+    /// hops accept any output; the last enforces `min_amount_out`.
+    async fn build_leg(&self, path: &[PathStep], amount_in: U256, min_amount_out: U256) -> Result<RouteLeg> {
+        let mut hops = Vec::with_capacity(path.len());
+        let mut amount = amount_in;
+
+        for (i, step) in path.iter().enumerate() {
+            let venue = &self.venues[step.venue];
+            let amount_out = venue.quote(&step.pool, step.token_in, amount).await?;
+            let min_out = if i + 1 == path.len() { min_amount_out } else { U256::zero() };
+
+            hops.push(Hop {
+                protocol: venue.protocol(),
+                chain_id: step.pool.chain_id,
+                pool_address: step.pool.address,
+                token_in: step.token_in,
+                token_out: step.token_out,
+                amount_in: amount,
+                amount_out,
+                call: Some(venue.swap_call(&step.pool, step.token_in, amount, min_out, self.recipient)?),
+            });
+            amount = amount_out;
+        }
+
+        Ok(RouteLeg { share_bps: 10000, amount_in, amount_out: amount, hops })
+    }
+
+    /// Flatten legs into a single-chain route
+    fn composite_route(legs: Vec<RouteLeg>, amount_out: U256, cross_chain: bool) -> Route {
+        let hops: Vec<Hop> = legs.iter().flat_map(|leg| leg.hops.clone()).collect();
+        Route {
+            estimated_gas: U256::from(50_000 + 100_000 * hops.len() as u64),
+            estimated_output: amount_out,
+            cross_chain,
+            legs: if legs.len() > 1 { legs } else { Vec::new() },
+            hops,
+        }
+    }
     
     /// Get common tokens for a chain (mock implementation)
     fn get_common_tokens(&self, chain_id: u64) -> Vec<Address> {
@@ -566,7 +833,7 @@ impl RouteOptimizer {
     }
     
     /// Get bridge time in seconds
-    fn get_bridge_time(&self, source_chain: u64, dest_chain: u64) -> u64 {
+    pub fn get_bridge_time(&self, source_chain: u64, dest_chain: u64) -> u64 {
         match (source_chain, dest_chain) {
             (1, 137) | (137, 1) => 300,   // 5 minutes for Ethereum <-> Polygon
             (1, 42161) | (42161, 1) => 600, // 10 minutes for Ethereum <-> Arbitrum
@@ -574,4 +841,53 @@ impl RouteOptimizer {
             _ => 900, // 15 minutes for other bridges
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(address: u64, token0: Address, token1: Address) -> (usize, VenuePool) {
+        (0, VenuePool {
+            protocol: Protocol::UniswapV3,
+            chain_id: 1,
+            address: Address::from_low_u64_be(address),
+            token0,
+            token1,
+        })
+    }
+
+    #[test]
+    fn test_routing_graph_finds_simple_paths() {
+        let (a, b, c, d) = (
+            Address::from_low_u64_be(1),
+            Address::from_low_u64_be(2),
+            Address::from_low_u64_be(3),
+            Address::from_low_u64_be(4),
+        );
+        let graph = RoutingGraph::new(vec![
+            pool(10, a, b),
+            pool(11, a, b),
+            pool(12, a, c),
+            pool(13, c, b),
+            pool(14, b, d),
+        ]);
+
+        let paths = graph.paths(a, b, MAX_ROUTE_HOPS);
+        // Two direct pools plus a -> c -> b
+        assert_eq!(paths.len(), 3);
+        assert!(paths.iter().any(|path| path.len() == 2 && path[0].token_out == c));
+        assert!(graph.paths(a, d, 1).is_empty());
+        assert_eq!(graph.paths(a, d, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_allocate_splits_follows_marginal_output() {
+        let quotes = |values: &[u64]| values.iter().map(|&v| U256::from(v)).collect::<Vec<_>>();
+        // Path 0 is deep, path 1 is shallow and saturates after one chunk
+        let outputs = vec![quotes(&[0, 100, 190, 270, 340]), quotes(&[0, 95, 120, 130, 135])];
+
+        assert_eq!(allocate_splits(&outputs, SPLIT_CHUNKS), vec![3, 1]);
+        assert_eq!(allocate_splits(&outputs[..1], SPLIT_CHUNKS), vec![4]);
+    }
+}
@@ -0,0 +1,440 @@
+//! USD price feeds for profit estimation and MEV analysis.
+//!
+//! Prices come from one or more [`OracleProvider`]s — Chainlink aggregators
+//! read on-chain and Pyth prices from a Hermes endpoint. The
+//! [`PriceAggregator`] drops stale quotes, takes the median of the rest and
+//! raises a [`DeviationAlarm`] when sources disagree by more than the
+//! configured tolerance. All prices are USD with 18 decimals.
+
+use crate::{Result, SolverError};
+use async_trait::async_trait;
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{transaction::eip2718::TypedTransaction, Address, TransactionRequest, I256, U256},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::RwLock;
+
+/// Default public Hermes endpoint
+pub const PYTH_HERMES_URL: &str = "https://hermes.pyth.network";
+
+/// Alarms kept for inspection
+const MAX_ALARMS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceQuote {
+    pub source: String,
+    /// USD price with 18 decimals
+    pub price: U256,
+    pub published_at: u64,
+}
+
+#[async_trait]
+pub trait OracleProvider: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Latest price of `token` on `chain_id`, or `None` if this oracle has
+    /// no feed for it
+    async fn price(&self, token: Address, chain_id: u64) -> Result<Option<PriceQuote>>;
+}
+
+/// Reads Chainlink `AggregatorV3Interface` feeds
+pub struct ChainlinkOracle {
+    providers: HashMap<u64, Arc<Provider<Http>>>,
+    feeds: HashMap<(u64, Address), Address>,
+}
+
+impl ChainlinkOracle {
+    pub fn new(providers: HashMap<u64, Arc<Provider<Http>>>) -> Self {
+        Self { providers, feeds: HashMap::new() }
+    }
+
+    /// Use the USD aggregator at `aggregator` for `token` on `chain_id`
+    pub fn with_feed(mut self, chain_id: u64, token: Address, aggregator: Address) -> Self {
+        self.feeds.insert((chain_id, token), aggregator);
+        self
+    }
+
+    async fn call(&self, provider: &Provider<Http>, aggregator: Address, signature: &[u8]) -> Result<Vec<u8>> {
+        let selector = ethers::utils::keccak256(signature);
+        let call: TypedTransaction = TransactionRequest::new().to(aggregator).data(selector[..4].to_vec()).into();
+        provider
+            .call(&call, None)
+            .await
+            .map(|output| output.to_vec())
+            .map_err(|e| SolverError::PriceUnavailable(format!("Chainlink call to {:?} failed: {}", aggregator, e)))
+    }
+}
+
+#[async_trait]
+impl OracleProvider for ChainlinkOracle {
+    fn name(&self) -> &str {
+        "chainlink"
+    }
+
+    async fn price(&self, token: Address, chain_id: u64) -> Result<Option<PriceQuote>> {
+        let Some(aggregator) = self.feeds.get(&(chain_id, token)).copied() else {
+            return Ok(None);
+        };
+        let provider = self.providers.get(&chain_id).ok_or(SolverError::ChainNotSupported(chain_id))?;
+
+        let decimals = self.call(provider, aggregator, b"decimals()").await?;
+        let round = self.call(provider, aggregator, b"latestRoundData()").await?;
+        if decimals.len() < 32 || round.len() < 160 {
+            return Err(SolverError::PriceUnavailable(format!("Malformed response from aggregator {:?}", aggregator)));
+        }
+
+        // latestRoundData: (roundId, answer, startedAt, updatedAt, answeredInRound)
+        let answer = I256::from_raw(U256::from_big_endian(&round[32..64]));
+        if answer <= I256::zero() {
+            return Err(SolverError::PriceUnavailable(format!("Non-positive answer from aggregator {:?}", aggregator)));
+        }
+        let updated_at = U256::from_big_endian(&round[96..128]).low_u64();
+        let decimals = U256::from_big_endian(&decimals[..32]).low_u32() as i32;
+
+        Ok(Some(PriceQuote {
+            source: self.name().to_string(),
+            price: scale_to_18(answer.into_raw(), -decimals),
+            published_at: updated_at,
+        }))
+    }
+}
+
+/// Reads Pyth prices from a Hermes endpoint
+pub struct PythOracle {
+    client: reqwest::Client,
+    endpoint: String,
+    /// Pyth price feed ids, hex encoded
+    feeds: HashMap<(u64, Address), String>,
+}
+
+#[derive(Deserialize)]
+struct HermesResponse {
+    parsed: Vec<HermesPriceUpdate>,
+}
+
+#[derive(Deserialize)]
+struct HermesPriceUpdate {
+    price: HermesPrice,
+}
+
+#[derive(Deserialize)]
+struct HermesPrice {
+    price: String,
+    expo: i32,
+    publish_time: u64,
+}
+
+impl PythOracle {
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(5))
+                .build()
+                .unwrap_or_default(),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            feeds: HashMap::new(),
+        }
+    }
+
+    pub fn with_feed(mut self, chain_id: u64, token: Address, price_id: &str) -> Self {
+        self.feeds.insert((chain_id, token), price_id.trim_start_matches("0x").to_string());
+        self
+    }
+}
+
+#[async_trait]
+impl OracleProvider for PythOracle {
+    fn name(&self) -> &str {
+        "pyth"
+    }
+
+    async fn price(&self, token: Address, chain_id: u64) -> Result<Option<PriceQuote>> {
+        let Some(price_id) = self.feeds.get(&(chain_id, token)) else {
+            return Ok(None);
+        };
+
+        let url = format!("{}/v2/updates/price/latest?ids[]={}&parsed=true", self.endpoint, price_id);
+        let response: HermesResponse = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| SolverError::PriceUnavailable(format!("Hermes request failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| SolverError::PriceUnavailable(format!("Invalid Hermes response: {}", e)))?;
+
+        let update = response
+            .parsed
+            .into_iter()
+            .next()
+            .ok_or_else(|| SolverError::PriceUnavailable(format!("No Pyth price for feed {}", price_id)))?;
+        let price = U256::from_dec_str(&update.price.price)
+            .map_err(|_| SolverError::PriceUnavailable(format!("Invalid Pyth price {}", update.price.price)))?;
+
+        Ok(Some(PriceQuote {
+            source: self.name().to_string(),
+            price: scale_to_18(price, update.price.expo),
+            published_at: update.price.publish_time,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OracleConfig {
+    /// Quotes older than this are ignored
+    pub max_staleness: Duration,
+    /// Spread between sources, relative to the median, that raises an alarm
+    pub max_deviation_bps: u64,
+}
+
+impl Default for OracleConfig {
+    fn default() -> Self {
+        Self {
+            max_staleness: Duration::from_secs(3600),
+            max_deviation_bps: 200,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviationAlarm {
+    pub token: Address,
+    pub chain_id: u64,
+    pub quotes: Vec<PriceQuote>,
+    pub deviation_bps: u64,
+    pub raised_at: u64,
+}
+
+pub struct PriceAggregator {
+    oracles: Vec<Arc<dyn OracleProvider>>,
+    config: OracleConfig,
+    alarms: RwLock<Vec<DeviationAlarm>>,
+}
+
+impl PriceAggregator {
+    pub fn new(oracles: Vec<Arc<dyn OracleProvider>>, config: OracleConfig) -> Self {
+        Self {
+            oracles,
+            config,
+            alarms: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Build Chainlink and Pyth oracles from `SolverConfig::oracle_addresses`.
+    /// Keys are `chainlink:<chain_id>:<token>` mapping to an aggregator
+    /// address, `pyth:<chain_id>:<token>` mapping to a price feed id, and
+    /// optionally `pyth:endpoint` overriding the Hermes URL.
+    pub fn from_config(
+        oracle_addresses: &HashMap<String, String>,
+        providers: HashMap<u64, Arc<Provider<Http>>>,
+        config: OracleConfig,
+    ) -> Result<Self> {
+        let endpoint = oracle_addresses.get("pyth:endpoint").map(String::as_str).unwrap_or(PYTH_HERMES_URL);
+        let mut chainlink = ChainlinkOracle::new(providers);
+        let mut pyth = PythOracle::new(endpoint);
+
+        for (key, value) in oracle_addresses {
+            let parts: Vec<&str> = key.split(':').collect();
+            let [kind, chain_id, token] = parts[..] else { continue };
+
+            let invalid = || SolverError::ExecutionFailed(format!("Invalid oracle entry {} = {}", key, value));
+            let chain_id = chain_id.parse::<u64>().map_err(|_| invalid())?;
+            let token = Address::from_str(token).map_err(|_| invalid())?;
+
+            match kind {
+                "chainlink" => {
+                    let aggregator = Address::from_str(value).map_err(|_| invalid())?;
+                    chainlink = chainlink.with_feed(chain_id, token, aggregator);
+                }
+                "pyth" => pyth = pyth.with_feed(chain_id, token, value),
+                _ => return Err(invalid()),
+            }
+        }
+
+        let mut oracles: Vec<Arc<dyn OracleProvider>> = Vec::new();
+        if !chainlink.feeds.is_empty() {
+            oracles.push(Arc::new(chainlink));
+        }
+        if !pyth.feeds.is_empty() {
+            oracles.push(Arc::new(pyth));
+        }
+        Ok(Self::new(oracles, config))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.oracles.is_empty()
+    }
+
+    /// Median of every fresh quote for `token`
+    pub async fn price(&self, token: Address, chain_id: u64) -> Result<U256> {
+        let mut quotes = Vec::new();
+        for oracle in &self.oracles {
+            match oracle.price(token, chain_id).await {
+                Ok(Some(quote)) => quotes.push(quote),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("{} price for {:?} on chain {} unavailable: {}", oracle.name(), token, chain_id, e),
+            }
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let quotes = fresh_quotes(quotes, now, self.config.max_staleness);
+        let price = median(&quotes)
+            .ok_or_else(|| SolverError::PriceUnavailable(format!("No fresh price for {:?} on chain {}", token, chain_id)))?;
+
+        let deviation_bps = deviation_bps(&quotes, price);
+        if deviation_bps > self.config.max_deviation_bps {
+            tracing::warn!(
+                "Oracle prices for {:?} on chain {} deviate by {} bps: {:?}",
+                token,
+                chain_id,
+                deviation_bps,
+                quotes
+            );
+            let mut alarms = self.alarms.write().await;
+            alarms.push(DeviationAlarm { token, chain_id, quotes, deviation_bps, raised_at: now });
+            if alarms.len() > MAX_ALARMS {
+                alarms.remove(0);
+            }
+        }
+
+        Ok(price)
+    }
+
+    pub async fn alarms(&self) -> Vec<DeviationAlarm> {
+        self.alarms.read().await.clone()
+    }
+
+    /// Whether sources disagreed about `token` within the last `window`
+    pub async fn has_recent_alarm(&self, token: Address, chain_id: u64, window: Duration) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        self.alarms
+            .read()
+            .await
+            .iter()
+            .any(|alarm| alarm.token == token && alarm.chain_id == chain_id && now.saturating_sub(alarm.raised_at) <= window.as_secs())
+    }
+}
+
+/// Rescale `value * 10^exponent` to 18 decimals
+fn scale_to_18(value: U256, exponent: i32) -> U256 {
+    let shift = 18 + exponent;
+    if shift >= 0 {
+        value.saturating_mul(U256::exp10(shift as usize))
+    } else {
+        value / U256::exp10((-shift) as usize)
+    }
+}
+
+fn fresh_quotes(quotes: Vec<PriceQuote>, now: u64, max_staleness: Duration) -> Vec<PriceQuote> {
+    quotes
+        .into_iter()
+        .filter(|quote| {
+            let fresh = now.saturating_sub(quote.published_at) <= max_staleness.as_secs();
+            if !fresh {
+                tracing::warn!("Ignoring stale {} price published at {}", quote.source, quote.published_at);
+            }
+            fresh
+        })
+        .collect()
+}
+
+fn median(quotes: &[PriceQuote]) -> Option<U256> {
+    let mut prices: Vec<U256> = quotes.iter().map(|quote| quote.price).collect();
+    prices.sort();
+    match prices.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(prices[len / 2]),
+        len => Some((prices[len / 2 - 1] + prices[len / 2]) / 2),
+    }
+}
+
+/// Spread between the highest and lowest quote, in bps of `reference`
+fn deviation_bps(quotes: &[PriceQuote], reference: U256) -> u64 {
+    let (Some(min), Some(max)) = (quotes.iter().map(|q| q.price).min(), quotes.iter().map(|q| q.price).max()) else {
+        return 0;
+    };
+    if reference.is_zero() {
+        return 0;
+    }
+    ((max - min) * U256::from(10_000) / reference).min(U256::from(u64::MAX)).as_u64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedOracle {
+        name: &'static str,
+        quote: Option<(U256, u64)>,
+    }
+
+    #[async_trait]
+    impl OracleProvider for FixedOracle {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn price(&self, _token: Address, _chain_id: u64) -> Result<Option<PriceQuote>> {
+            Ok(self.quote.map(|(price, published_at)| PriceQuote {
+                source: self.name.to_string(),
+                price,
+                published_at,
+            }))
+        }
+    }
+
+    fn usd(amount: u64) -> U256 {
+        U256::from(amount) * U256::exp10(18)
+    }
+
+    fn now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    #[test]
+    fn test_scale_to_18() {
+        // Chainlink ETH/USD: 8 decimals
+        assert_eq!(scale_to_18(U256::from(200_000_000_000u64), -8), usd(2000));
+        // Pyth with exponent -20 loses precision rather than overflowing
+        assert_eq!(scale_to_18(U256::from(100), -20), U256::from(1));
+    }
+
+    #[tokio::test]
+    async fn test_ignores_stale_quotes_and_alarms_on_deviation() {
+        let aggregator = PriceAggregator::new(
+            vec![
+                Arc::new(FixedOracle { name: "a", quote: Some((usd(2000), now())) }),
+                Arc::new(FixedOracle { name: "b", quote: Some((usd(2100), now())) }),
+                Arc::new(FixedOracle { name: "stale", quote: Some((usd(1), now() - 7200)) }),
+                Arc::new(FixedOracle { name: "none", quote: None }),
+            ],
+            OracleConfig::default(),
+        );
+
+        let token = Address::random();
+        assert_eq!(aggregator.price(token, 1).await.unwrap(), usd(2050));
+        assert!(aggregator.has_recent_alarm(token, 1, Duration::from_secs(60)).await);
+        assert_eq!(aggregator.alarms().await[0].quotes.len(), 2);
+
+        let empty = PriceAggregator::new(
+            vec![Arc::new(FixedOracle { name: "stale", quote: Some((usd(1), 0)) })],
+            OracleConfig::default(),
+        );
+        assert!(matches!(empty.price(token, 1).await, Err(SolverError::PriceUnavailable(_))));
+    }
+}
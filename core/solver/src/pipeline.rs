@@ -0,0 +1,299 @@
+//! Building blocks of the execution pipeline.
+//!
+//! [`SolverExecutor`](crate::executor::SolverExecutor) runs each chain's
+//! on-chain work in that chain's own worker pool, so a congested or slow
+//! chain only queues executions touching it. Executions are keyed by intent
+//! id: a second request for an intent that is running or already settled
+//! does not execute it again, and every attempt, phase, simulation and
+//! transaction is recorded in a structured [`ExecutionTrace`].
+
+use crate::{executor::ExecutionStep, Result, SolverError};
+use ethers::types::{H256, U256};
+use intents_engine::intent::IntentExecution;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Longest delay between two attempts
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PipelineConfig {
+    /// Concurrent executions per chain, overriding `default_workers`
+    pub workers_per_chain: HashMap<u64, usize>,
+    pub default_workers: usize,
+    /// Attempts per execution, the first included
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on every further retry
+    pub retry_base_delay_ms: u64,
+    /// Dry-run transactions before broadcasting them
+    pub simulate: bool,
+    /// Finished executions remembered for idempotency and traces
+    pub retained_executions: usize,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            workers_per_chain: HashMap::new(),
+            default_workers: 4,
+            max_attempts: 3,
+            retry_base_delay_ms: 1000,
+            simulate: true,
+            retained_executions: 10_000,
+        }
+    }
+}
+
+impl PipelineConfig {
+    pub fn retry_delay(&self, attempt: u32) -> Duration {
+        let factor = 1u64 << attempt.saturating_sub(1).min(16);
+        Duration::from_millis(self.retry_base_delay_ms.saturating_mul(factor)).min(MAX_RETRY_DELAY)
+    }
+}
+
+/// Whether an attempt that failed with `error` is worth repeating. Limits,
+/// liquidity and unsupported chains do not change between attempts.
+pub fn is_retryable(error: &SolverError) -> bool {
+    matches!(error, SolverError::ExecutionFailed(_) | SolverError::PriceUnavailable(_))
+}
+
+/// A bounded worker pool per chain
+pub struct ChainPools {
+    pools: HashMap<u64, Arc<Semaphore>>,
+    workers: HashMap<u64, usize>,
+}
+
+impl ChainPools {
+    pub fn new(chains: &[u64], config: &PipelineConfig) -> Self {
+        let workers: HashMap<u64, usize> = chains
+            .iter()
+            .map(|&chain_id| {
+                let size = config.workers_per_chain.get(&chain_id).copied().unwrap_or(config.default_workers);
+                (chain_id, size.max(1))
+            })
+            .collect();
+        let pools = workers
+            .iter()
+            .map(|(&chain_id, &size)| (chain_id, Arc::new(Semaphore::new(size))))
+            .collect();
+        Self { pools, workers }
+    }
+
+    /// Wait for a free worker on `chain_id`; returns the worker and how long
+    /// the wait took
+    pub async fn acquire(&self, chain_id: u64) -> Result<(OwnedSemaphorePermit, Duration)> {
+        let pool = self.pools.get(&chain_id).ok_or(SolverError::ChainNotSupported(chain_id))?;
+        let queued_at = Instant::now();
+        let permit = pool
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Worker pool closed: {}", e)))?;
+        Ok((permit, queued_at.elapsed()))
+    }
+
+    /// Workers currently busy on `chain_id`
+    pub fn busy(&self, chain_id: u64) -> usize {
+        match (self.pools.get(&chain_id), self.workers.get(&chain_id)) {
+            (Some(pool), Some(&size)) => size - pool.available_permits(),
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TraceKind {
+    AttemptStarted { attempt: u32 },
+    StepEntered { step: ExecutionStep },
+    WorkerAcquired { chain_id: u64, waited_ms: u64 },
+    Simulated {
+        chain_id: u64,
+        success: bool,
+        gas_estimate: Option<U256>,
+        revert_reason: Option<String>,
+    },
+    TransactionSent { chain_id: u64, tx_hash: H256 },
+    /// Settled in one transaction with other intents
+    Batched { batch_size: usize, tx_hash: H256 },
+    /// The batch simulation reverted; settling on its own instead
+    BatchFallback { reason: String },
+    Retrying { attempt: u32, error: String, delay_ms: u64 },
+    Completed { dest_amount: U256 },
+    Failed { error: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEvent {
+    /// Milliseconds since the execution was first requested
+    pub elapsed_ms: u64,
+    #[serde(flatten)]
+    pub kind: TraceKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    pub intent_id: H256,
+    pub events: Vec<TraceEvent>,
+}
+
+/// Outcome of asking to execute an intent
+#[derive(Debug, Clone)]
+pub enum Admission {
+    /// Go ahead; nobody else is executing the intent
+    Start,
+    /// Another caller is executing it right now
+    Running,
+    /// It already settled with this result
+    Settled(IntentExecution),
+}
+
+struct Entry {
+    started_at: Instant,
+    running: bool,
+    settled: Option<IntentExecution>,
+    trace: ExecutionTrace,
+}
+
+/// Execution state and traces per intent id. Finished entries beyond the
+/// retention limit are forgotten oldest first.
+pub struct ExecutionRegistry {
+    entries: HashMap<H256, Entry>,
+    finished: VecDeque<H256>,
+    retained: usize,
+}
+
+impl ExecutionRegistry {
+    pub fn new(retained: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            finished: VecDeque::new(),
+            retained: retained.max(1),
+        }
+    }
+
+    /// Claim `intent_id` for execution. A failed execution may be claimed
+    /// again; its trace continues.
+    pub fn admit(&mut self, intent_id: H256) -> Admission {
+        let entry = self.entries.entry(intent_id).or_insert_with(|| Entry {
+            started_at: Instant::now(),
+            running: false,
+            settled: None,
+            trace: ExecutionTrace { intent_id, events: Vec::new() },
+        });
+        if let Some(execution) = &entry.settled {
+            return Admission::Settled(execution.clone());
+        }
+        if entry.running {
+            return Admission::Running;
+        }
+        entry.running = true;
+        self.finished.retain(|id| *id != intent_id);
+        Admission::Start
+    }
+
+    pub fn record(&mut self, intent_id: H256, kind: TraceKind) {
+        if let Some(entry) = self.entries.get_mut(&intent_id) {
+            let elapsed_ms = entry.started_at.elapsed().as_millis() as u64;
+            entry.trace.events.push(TraceEvent { elapsed_ms, kind });
+        }
+    }
+
+    /// Release the claim on `intent_id`, remembering a successful result
+    pub fn finish(&mut self, intent_id: H256, result: std::result::Result<&IntentExecution, &SolverError>) {
+        let kind = match result {
+            Ok(execution) => TraceKind::Completed { dest_amount: execution.dest_amount },
+            Err(e) => TraceKind::Failed { error: e.to_string() },
+        };
+        self.record(intent_id, kind);
+
+        if let Some(entry) = self.entries.get_mut(&intent_id) {
+            entry.running = false;
+            entry.settled = result.ok().cloned();
+            self.finished.push_back(intent_id);
+        }
+        while self.finished.len() > self.retained {
+            if let Some(oldest) = self.finished.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn trace(&self, intent_id: H256) -> Option<ExecutionTrace> {
+        self.entries.get(&intent_id).map(|entry| entry.trace.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execution(intent_id: H256) -> IntentExecution {
+        IntentExecution {
+            intent_id,
+            solver: Default::default(),
+            dest_amount: U256::from(990),
+            execution_proof: Vec::new(),
+            gas_used: U256::zero(),
+            execution_time: 1,
+            source_tx_hash: H256::zero(),
+            dest_tx_hash: H256::zero(),
+            bridge_fee: U256::zero(),
+            simulation: None,
+        }
+    }
+
+    #[test]
+    fn test_registry_is_idempotent_per_intent() {
+        let mut registry = ExecutionRegistry::new(1);
+        let first = H256::repeat_byte(1);
+
+        assert!(matches!(registry.admit(first), Admission::Start));
+        assert!(matches!(registry.admit(first), Admission::Running));
+
+        // A failure may be retried and keeps its trace
+        registry.finish(first, Err(&SolverError::ExecutionFailed("reverted".to_string())));
+        assert!(matches!(registry.admit(first), Admission::Start));
+        registry.finish(first, Ok(&execution(first)));
+        assert!(matches!(registry.admit(first), Admission::Settled(e) if e.dest_amount == U256::from(990)));
+        let events = registry.trace(first).unwrap().events;
+        assert!(matches!(events[0].kind, TraceKind::Failed { .. }));
+        assert!(matches!(events[1].kind, TraceKind::Completed { .. }));
+
+        // Retention of one evicts the first intent once a second finishes
+        let second = H256::repeat_byte(2);
+        registry.admit(second);
+        registry.finish(second, Ok(&execution(second)));
+        assert!(registry.trace(first).is_none());
+        assert!(registry.trace(second).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_slow_chain_does_not_block_others() {
+        let config = PipelineConfig {
+            workers_per_chain: HashMap::from([(1, 1)]),
+            default_workers: 2,
+            ..Default::default()
+        };
+        let pools = ChainPools::new(&[1, 137], &config);
+
+        let (_held, _) = pools.acquire(1).await.unwrap();
+        assert_eq!(pools.busy(1), 1);
+
+        // Chain 1 is saturated, chain 137 is not affected
+        let blocked = tokio::time::timeout(Duration::from_millis(50), pools.acquire(1)).await;
+        assert!(blocked.is_err());
+        let (_other, waited) = pools.acquire(137).await.unwrap();
+        assert!(waited < Duration::from_millis(50));
+        assert!(pools.acquire(10).await.is_err());
+
+        assert_eq!(config.retry_delay(1), Duration::from_millis(1000));
+        assert_eq!(config.retry_delay(3), Duration::from_millis(4000));
+    }
+}
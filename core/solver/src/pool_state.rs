@@ -0,0 +1,342 @@
+//! Live Orbital AMM pool state read from the deployed contracts.
+//!
+//! Each chain has one orbital pool the solver quotes against. Its reserves,
+//! radius, fee and the initialized ticks around the current tick are read in
+//! two Multicall3 batches pinned to the same block, and cached until the
+//! chain produces a new block.
+
+use crate::{Result, SolverError};
+use ethers::{
+    abi::{self, ParamType, Token},
+    providers::{Http, Middleware, Provider},
+    types::{transaction::eip2718::TypedTransaction, Address, BlockId, Bytes, TransactionRequest, U256},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::RwLock;
+
+/// Multicall3, deployed at the same address on every supported chain
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+
+/// Ticks read on either side of the current tick
+const DEFAULT_TICK_WINDOW: u64 = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrbitalPoolConfig {
+    /// Orbital AMM contract
+    pub amm: Address,
+    pub pool_id: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickSnapshot {
+    pub index: U256,
+    pub liquidity_gross: U256,
+    pub fee_growth_outside: U256,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolSnapshot {
+    pub block_number: u64,
+    pub tokens: Vec<Address>,
+    pub reserves: Vec<U256>,
+    pub radius_squared: U256,
+    pub concentrated_liquidity: U256,
+    pub active: bool,
+    /// Current dynamic fee in basis points
+    pub fee_bps: U256,
+    pub current_tick: U256,
+    pub active_liquidity: U256,
+    /// Initialized ticks around the current tick, ascending
+    pub ticks: Vec<TickSnapshot>,
+}
+
+impl PoolSnapshot {
+    pub fn token_index(&self, token: Address) -> Option<usize> {
+        self.tokens.iter().position(|&t| t == token)
+    }
+}
+
+pub struct PoolStateProvider {
+    providers: HashMap<u64, Arc<Provider<Http>>>,
+    pools: HashMap<u64, OrbitalPoolConfig>,
+    multicall: Address,
+    tick_window: u64,
+    cache: RwLock<HashMap<u64, Arc<PoolSnapshot>>>,
+}
+
+impl PoolStateProvider {
+    pub fn new(providers: HashMap<u64, Arc<Provider<Http>>>, pools: HashMap<u64, OrbitalPoolConfig>) -> Self {
+        Self {
+            providers,
+            pools,
+            multicall: MULTICALL3_ADDRESS.parse().unwrap(),
+            tick_window: DEFAULT_TICK_WINDOW,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_tick_window(mut self, tick_window: u64) -> Self {
+        self.tick_window = tick_window;
+        self
+    }
+
+    pub fn has_pool(&self, chain_id: u64) -> bool {
+        self.pools.contains_key(&chain_id)
+    }
+
+    /// Pool state as of the latest block, read once per block
+    pub async fn snapshot(&self, chain_id: u64) -> Result<Arc<PoolSnapshot>> {
+        let pool = self.pools.get(&chain_id).ok_or(SolverError::ChainNotSupported(chain_id))?;
+        let provider = self.providers.get(&chain_id).ok_or(SolverError::ChainNotSupported(chain_id))?;
+
+        let block_number = provider
+            .get_block_number()
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Failed to get block number: {}", e)))?
+            .as_u64();
+
+        if let Some(cached) = self.cache.read().await.get(&chain_id) {
+            if cached.block_number == block_number {
+                return Ok(cached.clone());
+            }
+        }
+
+        let snapshot = Arc::new(self.fetch(provider, pool, block_number).await?);
+        self.cache.write().await.insert(chain_id, snapshot.clone());
+        Ok(snapshot)
+    }
+
+    /// Drop every cached snapshot, e.g. after the solver's own swap lands
+    pub async fn invalidate(&self) {
+        self.cache.write().await.clear();
+    }
+
+    async fn fetch(&self, provider: &Provider<Http>, pool: &OrbitalPoolConfig, block_number: u64) -> Result<PoolSnapshot> {
+        let pool_id = Token::Uint(pool.pool_id);
+        let results = self
+            .aggregate(provider, block_number, vec![
+                (pool.amm, encode_call(b"getOrbitalPool(uint256)", &[pool_id.clone()])),
+                (pool.amm, encode_call(b"getFeeState(uint256)", &[pool_id.clone()])),
+                (pool.amm, encode_call(b"getClState(uint256)", &[pool_id.clone()])),
+            ])
+            .await?;
+
+        let pool_output = results[0]
+            .as_ref()
+            .ok_or_else(|| SolverError::ExecutionFailed(format!("Orbital pool {} not readable", pool.pool_id)))?;
+        let (tokens, reserves, radius_squared, concentrated_liquidity, active) = decode_orbital_pool(pool_output)?;
+
+        // Fee and tick state are optional: pools without them quote fee-free
+        // over the whole sphere
+        let fee_bps = results[1]
+            .as_ref()
+            .and_then(|output| decode_uints(output, 4))
+            .map(|values| values[0])
+            .unwrap_or_default();
+        let (current_tick, active_liquidity, cl_enabled) = results[2]
+            .as_ref()
+            .and_then(|output| {
+                abi::decode(&[ParamType::Uint(256), ParamType::Uint(256), ParamType::Bool], output).ok()
+            })
+            .and_then(|tokens| match tokens.as_slice() {
+                [Token::Uint(tick), Token::Uint(liquidity), Token::Bool(enabled)] => Some((*tick, *liquidity, *enabled)),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        let ticks = if cl_enabled {
+            self.fetch_ticks(provider, pool, block_number, current_tick).await?
+        } else {
+            Vec::new()
+        };
+
+        Ok(PoolSnapshot {
+            block_number,
+            tokens,
+            reserves,
+            radius_squared,
+            concentrated_liquidity,
+            active,
+            fee_bps,
+            current_tick,
+            active_liquidity,
+            ticks,
+        })
+    }
+
+    async fn fetch_ticks(
+        &self,
+        provider: &Provider<Http>,
+        pool: &OrbitalPoolConfig,
+        block_number: u64,
+        current_tick: U256,
+    ) -> Result<Vec<TickSnapshot>> {
+        let window = U256::from(self.tick_window);
+        let lower = current_tick.saturating_sub(window);
+        let upper = current_tick.saturating_add(window);
+
+        let mut indices = Vec::new();
+        let mut index = lower;
+        while index <= upper {
+            indices.push(index);
+            index += U256::one();
+        }
+
+        let calls = indices
+            .iter()
+            .map(|&index| {
+                (pool.amm, encode_call(b"getTick(uint256,uint256)", &[Token::Uint(pool.pool_id), Token::Uint(index)]))
+            })
+            .collect();
+        let results = self.aggregate(provider, block_number, calls).await?;
+
+        Ok(indices
+            .into_iter()
+            .zip(results)
+            .filter_map(|(index, output)| decode_tick(index, &output?))
+            .collect())
+    }
+
+    /// Run `calls` through Multicall3 `aggregate3` at `block_number`,
+    /// allowing individual calls to fail
+    async fn aggregate(
+        &self,
+        provider: &Provider<Http>,
+        block_number: u64,
+        calls: Vec<(Address, Bytes)>,
+    ) -> Result<Vec<Option<Bytes>>> {
+        let call: TypedTransaction = TransactionRequest::new().to(self.multicall).data(encode_aggregate3(calls)).into();
+        let output = provider
+            .call(&call, Some(BlockId::from(block_number)))
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Multicall failed: {}", e)))?;
+        decode_aggregate3(&output)
+    }
+}
+
+fn encode_call(signature: &[u8], args: &[Token]) -> Bytes {
+    let selector = ethers::utils::keccak256(signature);
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend(abi::encode(args));
+    calldata.into()
+}
+
+fn encode_aggregate3(calls: Vec<(Address, Bytes)>) -> Bytes {
+    let calls = calls
+        .into_iter()
+        .map(|(target, data)| Token::Tuple(vec![Token::Address(target), Token::Bool(true), Token::Bytes(data.to_vec())]))
+        .collect();
+    encode_call(b"aggregate3((address,bool,bytes)[])", &[Token::Array(calls)])
+}
+
+/// Decode `(bool success, bytes returnData)[]`, mapping failed calls to `None`
+fn decode_aggregate3(output: &[u8]) -> Result<Vec<Option<Bytes>>> {
+    let result_type = ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Bool, ParamType::Bytes])));
+    let decoded = abi::decode(&[result_type], output)
+        .map_err(|e| SolverError::ExecutionFailed(format!("Invalid multicall response: {}", e)))?;
+
+    let Some(Token::Array(results)) = decoded.into_iter().next() else {
+        return Err(SolverError::ExecutionFailed("Invalid multicall response".to_string()));
+    };
+    Ok(results
+        .into_iter()
+        .map(|result| match result {
+            Token::Tuple(fields) => match fields.as_slice() {
+                [Token::Bool(true), Token::Bytes(data)] => Some(Bytes::from(data.clone())),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect())
+}
+
+#[allow(clippy::type_complexity)]
+fn decode_orbital_pool(output: &[u8]) -> Result<(Vec<Address>, Vec<U256>, U256, U256, bool)> {
+    let invalid = || SolverError::ExecutionFailed("Invalid orbital pool response".to_string());
+    let decoded = abi::decode(
+        &[
+            ParamType::Array(Box::new(ParamType::Address)),
+            ParamType::Array(Box::new(ParamType::Uint(256))),
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+            ParamType::Bool,
+        ],
+        output,
+    )
+    .map_err(|_| invalid())?;
+
+    match decoded.as_slice() {
+        [Token::Array(tokens), Token::Array(reserves), Token::Uint(radius_squared), Token::Uint(concentrated), Token::Bool(active)] => {
+            let tokens = tokens.iter().map(|t| t.clone().into_address()).collect::<Option<Vec<_>>>().ok_or_else(invalid)?;
+            let reserves = reserves.iter().map(|r| r.clone().into_uint()).collect::<Option<Vec<_>>>().ok_or_else(invalid)?;
+            if tokens.len() != reserves.len() {
+                return Err(invalid());
+            }
+            Ok((tokens, reserves, *radius_squared, *concentrated, *active))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+fn decode_uints(output: &[u8], count: usize) -> Option<Vec<U256>> {
+    abi::decode(&vec![ParamType::Uint(256); count], output)
+        .ok()?
+        .into_iter()
+        .map(Token::into_uint)
+        .collect()
+}
+
+/// Decode `getTick`, skipping uninitialized ticks
+fn decode_tick(index: U256, output: &[u8]) -> Option<TickSnapshot> {
+    let decoded = abi::decode(&[ParamType::Uint(256), ParamType::Uint(256), ParamType::Bool], output).ok()?;
+    match decoded.as_slice() {
+        [Token::Uint(liquidity_gross), Token::Uint(fee_growth_outside), Token::Bool(true)] => Some(TickSnapshot {
+            index,
+            liquidity_gross: *liquidity_gross,
+            fee_growth_outside: *fee_growth_outside,
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decodes_aggregate3_results() {
+        let output = abi::encode(&[Token::Array(vec![
+            Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![1, 2])]),
+            Token::Tuple(vec![Token::Bool(false), Token::Bytes(vec![])]),
+        ])]);
+
+        let results = decode_aggregate3(&output).unwrap();
+        assert_eq!(results, vec![Some(Bytes::from(vec![1, 2])), None]);
+        assert!(decode_aggregate3(&[0u8; 3]).is_err());
+    }
+
+    #[test]
+    fn test_decodes_pool_and_ticks() {
+        let tokens = vec![Address::random(), Address::random(), Address::random()];
+        let output = abi::encode(&[
+            Token::Array(tokens.iter().map(|&t| Token::Address(t)).collect()),
+            Token::Array(vec![Token::Uint(U256::from(100)), Token::Uint(U256::from(200)), Token::Uint(U256::from(300))]),
+            Token::Uint(U256::from(140_000)),
+            Token::Uint(U256::zero()),
+            Token::Bool(true),
+        ]);
+
+        let (decoded_tokens, reserves, radius_squared, _, active) = decode_orbital_pool(&output).unwrap();
+        assert_eq!(decoded_tokens, tokens);
+        assert_eq!(reserves[2], U256::from(300));
+        assert_eq!(radius_squared, U256::from(140_000));
+        assert!(active);
+
+        let tick = |initialized| {
+            abi::encode(&[Token::Uint(U256::from(5)), Token::Uint(U256::from(1)), Token::Bool(initialized)])
+        };
+        assert_eq!(decode_tick(U256::from(3), &tick(true)).unwrap().liquidity_gross, U256::from(5));
+        assert!(decode_tick(U256::from(4), &tick(false)).is_none());
+    }
+}
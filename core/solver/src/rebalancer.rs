@@ -0,0 +1,370 @@
+//! Cross-chain inventory rebalancing.
+//!
+//! The solver pays out on the destination chain and is repaid on the source
+//! chain, so inventory drifts toward chains users bridge *from*. The
+//! [`Rebalancer`] tracks recent intent flow, derives a target share per chain
+//! from where the solver has been paying out, and during idle periods bridges
+//! surplus inventory to chains that are short, skipping transfers whose
+//! bridge fee is too high relative to the amount moved.
+
+use crate::{executor::SolverExecutor, Result, SolverError};
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{transaction::eip2718::TypedTransaction, Address, TransactionRequest, U256},
+};
+use intents_bridge::CrossChainMessage;
+use intents_engine::intent::Intent;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// One asset held on several chains, e.g. USDC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebalanceAsset {
+    pub symbol: String,
+    /// Token address per chain; `Address::zero()` for the native asset
+    pub tokens: HashMap<u64, Address>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebalanceConfig {
+    pub assets: Vec<RebalanceAsset>,
+    /// How often to check inventory
+    pub interval_secs: u64,
+    /// Only rebalance when no intent has been handled for this long
+    pub idle_secs: u64,
+    /// Intent flow older than this doesn't count toward targets
+    pub flow_window_secs: u64,
+    /// Every chain keeps at least this share of an asset
+    pub min_share_bps: u16,
+    /// Ignore drift from target below this share of total inventory
+    pub drift_threshold_bps: u16,
+    /// Skip transfers whose bridge fee exceeds this share of the amount
+    pub max_cost_bps: u16,
+    /// Log planned transfers without sending them
+    pub dry_run: bool,
+}
+
+impl Default for RebalanceConfig {
+    fn default() -> Self {
+        Self {
+            assets: Vec::new(),
+            interval_secs: 300,
+            idle_secs: 120,
+            flow_window_secs: 24 * 3600,
+            min_share_bps: 1000,
+            drift_threshold_bps: 1000,
+            max_cost_bps: 30,
+            dry_run: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedTransfer {
+    pub symbol: String,
+    pub source_chain: u64,
+    pub dest_chain: u64,
+    pub amount: U256,
+}
+
+#[derive(Debug, Clone)]
+struct FlowEntry {
+    at: Instant,
+    chain_id: u64,
+    token: Address,
+    /// Amount the solver pays out on `chain_id`
+    outflow: U256,
+}
+
+pub struct Rebalancer {
+    config: RebalanceConfig,
+    executor: Arc<SolverExecutor>,
+    flow: RwLock<VecDeque<FlowEntry>>,
+    last_activity: RwLock<Instant>,
+    history: RwLock<Vec<PlannedTransfer>>,
+}
+
+impl Rebalancer {
+    pub fn new(config: RebalanceConfig, executor: Arc<SolverExecutor>) -> Self {
+        Self {
+            config,
+            executor,
+            flow: RwLock::new(VecDeque::new()),
+            last_activity: RwLock::new(Instant::now()),
+            history: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Record an intent the solver took on
+    pub async fn record_intent(&self, intent: &Intent) {
+        let now = Instant::now();
+        *self.last_activity.write().await = now;
+
+        let mut flow = self.flow.write().await;
+        flow.push_back(FlowEntry {
+            at: now,
+            chain_id: intent.dest_chain_id,
+            token: intent.dest_token,
+            outflow: intent.min_dest_amount,
+        });
+
+        let window = Duration::from_secs(self.config.flow_window_secs);
+        while flow.front().is_some_and(|entry| now.duration_since(entry.at) > window) {
+            flow.pop_front();
+        }
+    }
+
+    /// Transfers executed (or planned, in dry-run mode) so far
+    pub async fn history(&self) -> Vec<PlannedTransfer> {
+        self.history.read().await.clone()
+    }
+
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.interval_secs));
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.rebalance().await {
+                warn!("Rebalancing failed: {}", e);
+            }
+        }
+    }
+
+    /// Plan and, unless in dry-run mode, bridge transfers for every asset.
+    /// Does nothing while the solver is busy.
+    pub async fn rebalance(&self) -> Result<Vec<PlannedTransfer>> {
+        let idle_for = self.last_activity.read().await.elapsed();
+        if idle_for < Duration::from_secs(self.config.idle_secs) || !self.executor.get_active_executions().await.is_empty() {
+            debug!("Solver busy, skipping rebalance");
+            return Ok(Vec::new());
+        }
+
+        let mut planned = Vec::new();
+        for asset in &self.config.assets {
+            let balances = self.balances(asset).await?;
+            let targets = self.targets(asset).await;
+
+            for transfer in plan_transfers(&asset.symbol, &balances, &targets, self.config.drift_threshold_bps) {
+                if !self.affordable(&transfer).await {
+                    continue;
+                }
+                if self.config.dry_run {
+                    info!("[dry run] Would bridge {} {} from chain {} to {}", transfer.amount, transfer.symbol, transfer.source_chain, transfer.dest_chain);
+                } else {
+                    self.bridge(asset, &transfer).await?;
+                }
+                planned.push(transfer);
+            }
+        }
+
+        self.history.write().await.extend(planned.iter().cloned());
+        Ok(planned)
+    }
+
+    async fn balances(&self, asset: &RebalanceAsset) -> Result<HashMap<u64, U256>> {
+        let account = self.executor.address();
+        let mut balances = HashMap::new();
+        for (&chain_id, &token) in &asset.tokens {
+            let Some(provider) = self.executor.providers().get(&chain_id) else { continue };
+            balances.insert(chain_id, balance_of(provider, token, account).await?);
+        }
+        Ok(balances)
+    }
+
+    /// Target share per chain in bps, proportional to recent payouts with a
+    /// floor of `min_share_bps`
+    async fn targets(&self, asset: &RebalanceAsset) -> HashMap<u64, u16> {
+        let mut outflow: HashMap<u64, U256> = asset.tokens.keys().map(|&chain_id| (chain_id, U256::zero())).collect();
+        for entry in self.flow.read().await.iter() {
+            if asset.tokens.get(&entry.chain_id) == Some(&entry.token) {
+                *outflow.entry(entry.chain_id).or_default() += entry.outflow;
+            }
+        }
+        target_shares(&outflow, self.config.min_share_bps)
+    }
+
+    async fn affordable(&self, transfer: &PlannedTransfer) -> bool {
+        let bridges = self.executor.bridge_manager();
+        let Some(bridge) = bridges.find_best_bridge(transfer.source_chain, transfer.dest_chain).await else {
+            warn!("No bridge from chain {} to {}", transfer.source_chain, transfer.dest_chain);
+            return false;
+        };
+
+        match bridge.estimate_fees(transfer.source_chain, transfer.dest_chain, 64).await {
+            Ok(fee) => {
+                let cost_bps = U256::from(fee) * U256::from(10000) / transfer.amount.max(U256::one());
+                if cost_bps > U256::from(self.config.max_cost_bps) {
+                    debug!("Skipping {:?}: bridge cost {} bps", transfer, cost_bps);
+                    return false;
+                }
+                true
+            }
+            Err(e) => {
+                warn!("Failed to estimate bridge fee for {:?}: {}", transfer, e);
+                false
+            }
+        }
+    }
+
+    async fn bridge(&self, asset: &RebalanceAsset, transfer: &PlannedTransfer) -> Result<()> {
+        let account = self.executor.address();
+        let token = asset.tokens[&transfer.source_chain];
+        let dest_token = asset.tokens[&transfer.dest_chain];
+
+        let mut payload = Vec::with_capacity(84);
+        payload.extend_from_slice(token.as_bytes());
+        payload.extend_from_slice(dest_token.as_bytes());
+        let mut amount = [0u8; 32];
+        transfer.amount.to_big_endian(&mut amount);
+        payload.extend_from_slice(&amount);
+
+        let message = CrossChainMessage {
+            source_chain: transfer.source_chain,
+            dest_chain: transfer.dest_chain,
+            nonce: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as u64,
+            sender: account.as_bytes().to_vec(),
+            receiver: account.as_bytes().to_vec(),
+            payload,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
+            metadata: HashMap::new(),
+        };
+
+        let bridges = self.executor.bridge_manager();
+        let bridge = bridges
+            .find_best_bridge(transfer.source_chain, transfer.dest_chain)
+            .await
+            .ok_or_else(|| SolverError::ExecutionFailed("No bridge available".to_string()))?;
+        bridge
+            .send_message(message)
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Rebalance transfer failed: {}", e)))?;
+
+        info!("Bridged {} {} from chain {} to {}", transfer.amount, transfer.symbol, transfer.source_chain, transfer.dest_chain);
+        Ok(())
+    }
+}
+
+pub(crate) async fn balance_of(provider: &Provider<Http>, token: Address, account: Address) -> Result<U256> {
+    if token == Address::zero() {
+        return provider
+            .get_balance(account, None)
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Failed to get balance: {}", e)));
+    }
+
+    let selector = ethers::utils::keccak256(b"balanceOf(address)");
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend(ethers::abi::encode(&[ethers::abi::Token::Address(account)]));
+    let call: TypedTransaction = TransactionRequest::new().to(token).data(calldata).into();
+    let output = provider
+        .call(&call, None)
+        .await
+        .map_err(|e| SolverError::ExecutionFailed(format!("Failed to get balance: {}", e)))?;
+    Ok(if output.len() >= 32 { U256::from_big_endian(&output[..32]) } else { U256::zero() })
+}
+
+/// Share each chain should hold: proportional to `outflow`, at least
+/// `min_share_bps`, summing to 10000. With no flow, shares are equal.
+fn target_shares(outflow: &HashMap<u64, U256>, min_share_bps: u16) -> HashMap<u64, u16> {
+    if outflow.is_empty() {
+        return HashMap::new();
+    }
+    let chains = outflow.len() as u64;
+    let floor = (min_share_bps as u64).min(10000 / chains);
+    let flexible = 10000 - floor * chains;
+    let total = outflow.values().fold(U256::zero(), |acc, &v| acc + v);
+
+    outflow
+        .iter()
+        .map(|(&chain_id, &amount)| {
+            let share = if total.is_zero() {
+                flexible / chains
+            } else {
+                (amount * U256::from(flexible) / total).as_u64()
+            };
+            (chain_id, (floor + share) as u16)
+        })
+        .collect()
+}
+
+/// Move surplus to deficit chains until every chain is within
+/// `threshold_bps` of total inventory from its target
+fn plan_transfers(
+    symbol: &str,
+    balances: &HashMap<u64, U256>,
+    targets: &HashMap<u64, u16>,
+    threshold_bps: u16,
+) -> Vec<PlannedTransfer> {
+    let total = balances.values().fold(U256::zero(), |acc, &v| acc + v);
+    if total.is_zero() {
+        return Vec::new();
+    }
+    let threshold = total * U256::from(threshold_bps) / U256::from(10000);
+
+    let mut surplus = Vec::new();
+    let mut deficit = Vec::new();
+    for (&chain_id, &balance) in balances {
+        let target = total * U256::from(targets.get(&chain_id).copied().unwrap_or(0)) / U256::from(10000);
+        if balance > target + threshold {
+            surplus.push((chain_id, balance - target));
+        } else if target > balance + threshold {
+            deficit.push((chain_id, target - balance));
+        }
+    }
+    // Largest imbalances first, chain id as a tiebreak for determinism
+    surplus.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    deficit.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut transfers = Vec::new();
+    let mut surplus = surplus.into_iter().peekable();
+    for (dest_chain, mut needed) in deficit {
+        while !needed.is_zero() {
+            let Some((source_chain, available)) = surplus.peek_mut() else { break };
+            let amount = needed.min(*available);
+            transfers.push(PlannedTransfer { symbol: symbol.to_string(), source_chain: *source_chain, dest_chain, amount });
+            needed -= amount;
+            *available -= amount;
+            if available.is_zero() {
+                surplus.next();
+            }
+        }
+    }
+    transfers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_shares_follow_outflow_with_floor() {
+        let outflow = HashMap::from([(1, U256::from(300)), (137, U256::from(100)), (42161, U256::zero())]);
+        let shares = target_shares(&outflow, 1000);
+
+        assert_eq!(shares[&42161], 1000);
+        assert_eq!(shares[&1], 1000 + 5250);
+        assert_eq!(shares[&137], 1000 + 1750);
+
+        let idle = target_shares(&HashMap::from([(1, U256::zero()), (10, U256::zero())]), 1000);
+        assert_eq!(idle[&1], 5000);
+    }
+
+    #[test]
+    fn test_plan_transfers_moves_surplus_to_deficit() {
+        let balances = HashMap::from([(1, U256::from(900)), (137, U256::from(100)), (42161, U256::zero())]);
+        let targets = HashMap::from([(1, 4000), (137, 3000), (42161, 3000)]);
+
+        let transfers = plan_transfers("USDC", &balances, &targets, 500);
+        assert_eq!(transfers.len(), 2);
+        assert_eq!((transfers[0].source_chain, transfers[0].dest_chain, transfers[0].amount), (1, 42161, U256::from(300)));
+        assert_eq!((transfers[1].source_chain, transfers[1].dest_chain, transfers[1].amount), (1, 137, U256::from(200)));
+
+        // Within threshold: nothing to do
+        assert!(plan_transfers("USDC", &balances, &targets, 5000).is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{Result, SolverError};
+use async_trait::async_trait;
 use ethers::types::{Address, U256, H256};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -136,11 +137,131 @@ impl SlashingReason {
     }
 }
 
+/// Durable backend for solver reputations, so scores survive restarts
+#[async_trait]
+pub trait ReputationStore: Send + Sync {
+    /// Insert or replace the record for `reputation.solver`
+    async fn save(&self, reputation: &SolverReputation) -> Result<()>;
+
+    /// Every stored reputation
+    async fn load_all(&self) -> Result<Vec<SolverReputation>>;
+}
+
+/// Non-durable reputation store used when no backend is configured
+#[derive(Default)]
+pub struct MemoryReputationStore {
+    reputations: RwLock<HashMap<Address, SolverReputation>>,
+}
+
+impl MemoryReputationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ReputationStore for MemoryReputationStore {
+    async fn save(&self, reputation: &SolverReputation) -> Result<()> {
+        self.reputations.write().await.insert(reputation.solver, reputation.clone());
+        Ok(())
+    }
+
+    async fn load_all(&self) -> Result<Vec<SolverReputation>> {
+        Ok(self.reputations.read().await.values().cloned().collect())
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub use self::postgres_reputation::PostgresReputationStore;
+
+#[cfg(feature = "postgres")]
+mod postgres_reputation {
+    use super::*;
+    use sqlx::{postgres::PgPoolOptions, PgPool, Row};
+
+    /// Reputation table shared by every solver node writing to the database
+    pub struct PostgresReputationStore {
+        pool: PgPool,
+    }
+
+    impl PostgresReputationStore {
+        pub async fn connect(database_url: &str) -> Result<Self> {
+            let pool = PgPoolOptions::new()
+                .max_connections(5)
+                .acquire_timeout(std::time::Duration::from_secs(30))
+                .connect(database_url)
+                .await
+                .map_err(|e| SolverError::StorageError(format!("Failed to connect to database: {}", e)))?;
+
+            let store = Self { pool };
+            store.create_tables().await?;
+            Ok(store)
+        }
+
+        async fn create_tables(&self) -> Result<()> {
+            sqlx::query(r#"
+                CREATE TABLE IF NOT EXISTS solver_reputation (
+                    solver VARCHAR(42) PRIMARY KEY,
+                    score BIGINT NOT NULL,
+                    payload JSONB NOT NULL,
+                    updated_at BIGINT NOT NULL
+                )
+            "#)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| SolverError::StorageError(format!("Failed to create solver_reputation table: {}", e)))?;
+
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl ReputationStore for PostgresReputationStore {
+        async fn save(&self, reputation: &SolverReputation) -> Result<()> {
+            let payload = serde_json::to_value(reputation)
+                .map_err(|e| SolverError::StorageError(e.to_string()))?;
+
+            sqlx::query(r#"
+                INSERT INTO solver_reputation (solver, score, payload, updated_at)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (solver) DO UPDATE
+                SET score = EXCLUDED.score, payload = EXCLUDED.payload, updated_at = EXCLUDED.updated_at
+            "#)
+            .bind(format!("{:?}", reputation.solver))
+            .bind(reputation.score as i64)
+            .bind(payload)
+            .bind(current_timestamp() as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| SolverError::StorageError(format!("Failed to save reputation: {}", e)))?;
+
+            Ok(())
+        }
+
+        async fn load_all(&self) -> Result<Vec<SolverReputation>> {
+            let rows = sqlx::query("SELECT payload FROM solver_reputation")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| SolverError::StorageError(format!("Failed to load reputations: {}", e)))?;
+
+            rows.iter()
+                .map(|row| {
+                    let payload: serde_json::Value = row.try_get("payload")
+                        .map_err(|e| SolverError::StorageError(e.to_string()))?;
+                    serde_json::from_value(payload)
+                        .map_err(|e| SolverError::StorageError(e.to_string()))
+                })
+                .collect()
+        }
+    }
+}
+
 pub struct ReputationManager {
     reputations: RwLock<HashMap<Address, SolverReputation>>,
     execution_history: RwLock<Vec<ExecutionReport>>,
     slashing_events: RwLock<Vec<SlashingEvent>>,
     min_reputation_threshold: u64,
+    store: Option<Arc<dyn ReputationStore>>,
 }
 
 impl ReputationManager {
@@ -150,6 +271,41 @@ impl ReputationManager {
             execution_history: RwLock::new(Vec::new()),
             slashing_events: RwLock::new(Vec::new()),
             min_reputation_threshold: 3000, // 30%
+            store: None,
+        }
+    }
+
+    /// Write every reputation change through to `store`. Call
+    /// [`ReputationManager::load`] afterwards to pick up persisted scores.
+    pub fn with_store(mut self, store: Arc<dyn ReputationStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Replace in-memory reputations with those persisted in the store
+    pub async fn load(&self) -> Result<usize> {
+        let Some(store) = &self.store else {
+            return Ok(0);
+        };
+
+        let stored = store.load_all().await?;
+        let count = stored.len();
+        let mut reputations = self.reputations.write().await;
+        for reputation in stored {
+            reputations.insert(reputation.solver, reputation);
+        }
+        Ok(count)
+    }
+
+    /// Every tracked solver reputation
+    pub async fn all_reputations(&self) -> Vec<SolverReputation> {
+        self.reputations.read().await.values().cloned().collect()
+    }
+
+    async fn persist(&self, reputation: Option<SolverReputation>) -> Result<()> {
+        match (&self.store, reputation) {
+            (Some(store), Some(reputation)) => store.save(&reputation).await,
+            _ => Ok(()),
         }
     }
 
@@ -168,8 +324,11 @@ impl ReputationManager {
             ));
         }
 
-        reputations.insert(solver, SolverReputation::new(solver, bond_amount));
-        Ok(())
+        let reputation = SolverReputation::new(solver, bond_amount);
+        reputations.insert(solver, reputation.clone());
+        drop(reputations);
+
+        self.persist(Some(reputation)).await
     }
 
     /// Get solver reputation
@@ -291,12 +450,15 @@ impl ReputationManager {
 
             rep.score = (rep.score + reward).min(MAX_REPUTATION);
         }
+        let updated = reputations.get(&report.solver).cloned();
+        drop(reputations);
 
         // Store execution report
         let mut history = self.execution_history.write().await;
         history.push(report);
+        drop(history);
 
-        Ok(())
+        self.persist(updated).await
     }
 
     /// Record failed execution and apply slashing
@@ -338,8 +500,10 @@ impl ReputationManager {
             let mut slashing_events = self.slashing_events.write().await;
             slashing_events.push(event);
         }
+        let updated = reputations.get(&solver).cloned();
+        drop(reputations);
 
-        Ok(())
+        self.persist(updated).await
     }
 
     /// Increase solver bond
@@ -348,7 +512,9 @@ impl ReputationManager {
 
         if let Some(rep) = reputations.get_mut(&solver) {
             rep.bond_amount = rep.bond_amount.saturating_add(amount);
-            Ok(())
+            let updated = rep.clone();
+            drop(reputations);
+            self.persist(Some(updated)).await
         } else {
             Err(SolverError::ExecutionFailed("Solver not found".to_string()))
         }
@@ -368,7 +534,10 @@ impl ReputationManager {
             } else {
                 U256::zero()
             };
+            let updated = rep.clone();
+            drop(reputations);
 
+            self.persist(Some(updated)).await?;
             Ok(amount)
         } else {
             Err(SolverError::ExecutionFailed("Solver not found".to_string()))
@@ -514,6 +683,22 @@ fn current_timestamp() -> u64 {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_reputation_persisted_across_restart() {
+        let store: Arc<dyn ReputationStore> = Arc::new(MemoryReputationStore::new());
+        let solver = Address::random();
+
+        let manager = ReputationManager::new().with_store(store.clone());
+        manager.register_solver(solver, U256::from(MIN_BOND_AMOUNT)).await.unwrap();
+        manager.record_failure(H256::random(), solver, SlashingReason::Timeout, U256::from(MIN_BOND_AMOUNT)).await.unwrap();
+
+        let restarted = ReputationManager::new().with_store(store);
+        assert_eq!(restarted.load().await.unwrap(), 1);
+        let rep = restarted.get_reputation(solver).await.unwrap();
+        assert_eq!(rep.score, INITIAL_REPUTATION - SLASH_TIMEOUT);
+        assert_eq!(rep.failed_executions, 1);
+    }
+
     #[tokio::test]
     async fn test_solver_registration() {
         let manager = ReputationManager::new();
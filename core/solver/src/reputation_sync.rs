@@ -0,0 +1,195 @@
+//! Reconciliation of off-chain solver reputation with the intents contract.
+//!
+//! The [`ReputationManager`] scores solvers from what this node observes,
+//! while the intents contract keeps its own `reputation_score` that it
+//! updates on settlement and slashing. The [`ReputationSync`] periodically
+//! reads the contract's effective (decay-adjusted) score for every tracked
+//! solver on every configured chain and raises a [`ReputationDiscrepancy`]
+//! whenever the two views diverge by more than the configured threshold.
+
+use crate::{reputation::ReputationManager, Result, SolverError};
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{transaction::eip2718::TypedTransaction, Address, TransactionRequest, U256},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// Discrepancies kept for inspection; older ones are dropped first
+const MAX_DISCREPANCIES: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReputationSyncConfig {
+    /// Postgres database reputations are persisted to; in-memory if unset
+    pub database_url: Option<String>,
+    /// Intents contract address per chain
+    pub intents_contracts: HashMap<u64, Address>,
+    /// How often to reconcile with the contracts
+    pub interval_secs: u64,
+    /// Alert when the scores differ by more than this many points (0-10000 scale)
+    pub max_divergence_bps: u64,
+}
+
+impl Default for ReputationSyncConfig {
+    fn default() -> Self {
+        Self {
+            database_url: None,
+            intents_contracts: HashMap::new(),
+            interval_secs: 300,
+            max_divergence_bps: 500,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReputationDiscrepancy {
+    pub solver: Address,
+    pub chain_id: u64,
+    pub offchain_score: u64,
+    pub onchain_score: u64,
+    pub divergence_bps: u64,
+    pub detected_at: u64,
+}
+
+pub struct ReputationSync {
+    config: ReputationSyncConfig,
+    reputation: Arc<ReputationManager>,
+    providers: HashMap<u64, Arc<Provider<Http>>>,
+    discrepancies: RwLock<Vec<ReputationDiscrepancy>>,
+}
+
+impl ReputationSync {
+    pub fn new(
+        config: ReputationSyncConfig,
+        reputation: Arc<ReputationManager>,
+        providers: HashMap<u64, Arc<Provider<Http>>>,
+    ) -> Self {
+        Self {
+            config,
+            reputation,
+            providers,
+            discrepancies: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Discrepancies detected so far, oldest first
+    pub async fn discrepancies(&self) -> Vec<ReputationDiscrepancy> {
+        self.discrepancies.read().await.clone()
+    }
+
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.interval_secs));
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.reconcile().await {
+                warn!("Reputation reconciliation failed: {}", e);
+            }
+        }
+    }
+
+    /// Compare every tracked solver's score with each chain's contract and
+    /// return the discrepancies found in this pass
+    pub async fn reconcile(&self) -> Result<Vec<ReputationDiscrepancy>> {
+        let reputations = self.reputation.all_reputations().await;
+        let mut found = Vec::new();
+
+        for (&chain_id, &contract) in &self.config.intents_contracts {
+            let provider = self.providers
+                .get(&chain_id)
+                .ok_or(SolverError::ChainNotSupported(chain_id))?;
+
+            for reputation in &reputations {
+                let onchain_score = match effective_reputation(provider, contract, reputation.solver).await {
+                    Ok(score) => score,
+                    Err(e) => {
+                        warn!("Failed to read reputation of {:?} on chain {}: {}", reputation.solver, chain_id, e);
+                        continue;
+                    }
+                };
+
+                let divergence = divergence_bps(reputation.score, onchain_score);
+                if divergence > self.config.max_divergence_bps {
+                    warn!(
+                        "Reputation of {:?} diverges on chain {}: off-chain {}, on-chain {}",
+                        reputation.solver, chain_id, reputation.score, onchain_score
+                    );
+                    found.push(ReputationDiscrepancy {
+                        solver: reputation.solver,
+                        chain_id,
+                        offchain_score: reputation.score,
+                        onchain_score,
+                        divergence_bps: divergence,
+                        detected_at: current_timestamp(),
+                    });
+                }
+            }
+        }
+
+        if !found.is_empty() {
+            info!("Reputation reconciliation found {} discrepancies", found.len());
+            let mut discrepancies = self.discrepancies.write().await;
+            discrepancies.extend(found.iter().cloned());
+            let excess = discrepancies.len().saturating_sub(MAX_DISCREPANCIES);
+            discrepancies.drain(..excess);
+        }
+
+        Ok(found)
+    }
+}
+
+/// Decay-adjusted score the intents contract uses for `solver`
+async fn effective_reputation(provider: &Provider<Http>, contract: Address, solver: Address) -> Result<u64> {
+    let selector = ethers::utils::keccak256(b"getEffectiveReputation(address)");
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend(ethers::abi::encode(&[ethers::abi::Token::Address(solver)]));
+    let call: TypedTransaction = TransactionRequest::new().to(contract).data(calldata).into();
+    let output = provider
+        .call(&call, None)
+        .await
+        .map_err(|e| SolverError::ExecutionFailed(format!("Failed to read reputation: {}", e)))?;
+    if output.len() < 32 {
+        return Err(SolverError::ExecutionFailed("Malformed reputation response".to_string()));
+    }
+    Ok(U256::from_big_endian(&output[..32]).min(U256::from(u64::MAX)).as_u64())
+}
+
+fn divergence_bps(offchain: u64, onchain: u64) -> u64 {
+    offchain.abs_diff(onchain)
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_divergence_is_symmetric() {
+        assert_eq!(divergence_bps(5000, 4200), 800);
+        assert_eq!(divergence_bps(4200, 5000), 800);
+        assert_eq!(divergence_bps(5000, 5000), 0);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_without_contracts_finds_nothing() {
+        let sync = ReputationSync::new(
+            ReputationSyncConfig::default(),
+            Arc::new(ReputationManager::new()),
+            HashMap::new(),
+        );
+        assert!(sync.reconcile().await.unwrap().is_empty());
+        assert!(sync.discrepancies().await.is_empty());
+    }
+}
@@ -0,0 +1,305 @@
+//! Exposure limits and circuit breakers for intent matching.
+//!
+//! Every matched intent reserves its USD notional against the token and
+//! chain the solver pays out on, the intent's user and the solver's total
+//! book. The [`RiskManager`] vetoes a match with a [`RiskViolation`] when
+//! any of those would exceed its configured limit, when the expected
+//! slippage is too high, or while the drawdown circuit breaker is tripped.
+//! Exposure is released once the intent has been executed or abandoned.
+
+use crate::{Result, SolverError};
+use ethers::types::{Address, H256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+/// Notional limits are USD with 18 decimals, like oracle prices. Unset
+/// limits are not enforced.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskLimits {
+    /// Open notional per payout token
+    pub token_limits: HashMap<Address, U256>,
+    /// Open notional per payout chain
+    pub chain_limits: HashMap<u64, U256>,
+    /// Open notional per intent user
+    pub max_counterparty_notional: Option<U256>,
+    /// Open notional across every matched intent
+    pub max_aggregate_notional: Option<U256>,
+    /// Capital the drawdown breaker measures losses against
+    pub capital: U256,
+    /// Trip the breaker once equity falls this far below its peak
+    pub max_drawdown_bps: Option<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Error)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RiskViolation {
+    #[error("token {token:?} exposure {exposure} exceeds limit {limit}")]
+    TokenLimit { token: Address, exposure: U256, limit: U256 },
+
+    #[error("chain {chain_id} exposure {exposure} exceeds limit {limit}")]
+    ChainLimit { chain_id: u64, exposure: U256, limit: U256 },
+
+    #[error("counterparty {counterparty:?} exposure {exposure} exceeds limit {limit}")]
+    CounterpartyLimit { counterparty: Address, exposure: U256, limit: U256 },
+
+    #[error("aggregate exposure {exposure} exceeds limit {limit}")]
+    AggregateLimit { exposure: U256, limit: U256 },
+
+    #[error("slippage {slippage_bps} bps exceeds limit {limit_bps} bps")]
+    Slippage { slippage_bps: u64, limit_bps: u16 },
+
+    #[error("drawdown circuit breaker tripped at {drawdown_bps} bps")]
+    DrawdownBreaker { drawdown_bps: u64 },
+}
+
+/// What matching an intent would add to the solver's book
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Exposure {
+    pub token: Address,
+    pub chain_id: u64,
+    pub counterparty: Address,
+    /// USD value with 18 decimals
+    pub notional: U256,
+    pub slippage_bps: u64,
+}
+
+#[derive(Debug, Default)]
+struct Book {
+    open: HashMap<H256, Exposure>,
+    by_token: HashMap<Address, U256>,
+    by_chain: HashMap<u64, U256>,
+    by_counterparty: HashMap<Address, U256>,
+    aggregate: U256,
+}
+
+impl Book {
+    fn add(&mut self, intent_id: H256, exposure: Exposure) {
+        *self.by_token.entry(exposure.token).or_default() += exposure.notional;
+        *self.by_chain.entry(exposure.chain_id).or_default() += exposure.notional;
+        *self.by_counterparty.entry(exposure.counterparty).or_default() += exposure.notional;
+        self.aggregate += exposure.notional;
+        self.open.insert(intent_id, exposure);
+    }
+
+    fn remove(&mut self, intent_id: H256) -> Option<Exposure> {
+        let exposure = self.open.remove(&intent_id)?;
+        for total in [
+            self.by_token.get_mut(&exposure.token),
+            self.by_chain.get_mut(&exposure.chain_id),
+            self.by_counterparty.get_mut(&exposure.counterparty),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *total = total.saturating_sub(exposure.notional);
+        }
+        self.aggregate = self.aggregate.saturating_sub(exposure.notional);
+        Some(exposure)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Drawdown {
+    /// Realized P&L since start, USD with 18 decimals
+    pnl: i128,
+    peak_pnl: i128,
+    tripped: bool,
+}
+
+pub struct RiskManager {
+    limits: RiskLimits,
+    max_slippage_bps: u16,
+    book: RwLock<Book>,
+    drawdown: RwLock<Drawdown>,
+}
+
+impl RiskManager {
+    pub fn new(limits: RiskLimits, max_slippage_bps: u16) -> Self {
+        Self {
+            limits,
+            max_slippage_bps,
+            book: RwLock::new(Book::default()),
+            drawdown: RwLock::new(Drawdown::default()),
+        }
+    }
+
+    /// Check `exposure` against every limit and, if it passes, add it to
+    /// the book under `intent_id`
+    pub async fn reserve(&self, intent_id: H256, exposure: Exposure) -> Result<()> {
+        if self.drawdown.read().await.tripped {
+            return Err(SolverError::RiskLimitExceeded(RiskViolation::DrawdownBreaker {
+                drawdown_bps: self.drawdown_bps().await,
+            }));
+        }
+
+        let mut book = self.book.write().await;
+        if let Some(violation) = check_limits(&self.limits, self.max_slippage_bps, &book, &exposure) {
+            warn!("Risk veto for intent {}: {}", intent_id, violation);
+            return Err(SolverError::RiskLimitExceeded(violation));
+        }
+        book.add(intent_id, exposure);
+        Ok(())
+    }
+
+    /// Drop the exposure held for `intent_id`, if any
+    pub async fn release(&self, intent_id: H256) -> Option<Exposure> {
+        self.book.write().await.remove(intent_id)
+    }
+
+    /// Record realized P&L (USD, 18 decimals) and trip the drawdown
+    /// breaker if equity has fallen too far below its peak
+    pub async fn record_pnl(&self, pnl: i128) {
+        let mut drawdown = self.drawdown.write().await;
+        drawdown.pnl = drawdown.pnl.saturating_add(pnl);
+        drawdown.peak_pnl = drawdown.peak_pnl.max(drawdown.pnl);
+
+        let Some(max_drawdown_bps) = self.limits.max_drawdown_bps else {
+            return;
+        };
+        let current = drawdown_bps(self.limits.capital, drawdown.peak_pnl, drawdown.pnl);
+        if !drawdown.tripped && current > max_drawdown_bps as u64 {
+            warn!("Drawdown of {} bps tripped the circuit breaker; matching halted", current);
+            drawdown.tripped = true;
+        }
+    }
+
+    /// Resume matching after the drawdown breaker tripped
+    pub async fn reset_breaker(&self) {
+        let mut drawdown = self.drawdown.write().await;
+        drawdown.tripped = false;
+        drawdown.peak_pnl = drawdown.pnl;
+    }
+
+    pub async fn is_halted(&self) -> bool {
+        self.drawdown.read().await.tripped
+    }
+
+    /// Current drawdown from peak equity
+    pub async fn drawdown_bps(&self) -> u64 {
+        let drawdown = self.drawdown.read().await;
+        drawdown_bps(self.limits.capital, drawdown.peak_pnl, drawdown.pnl)
+    }
+
+    /// Total open notional across every matched intent
+    pub async fn aggregate_exposure(&self) -> U256 {
+        self.book.read().await.aggregate
+    }
+}
+
+fn check_limits(limits: &RiskLimits, max_slippage_bps: u16, book: &Book, exposure: &Exposure) -> Option<RiskViolation> {
+    if exposure.slippage_bps > max_slippage_bps as u64 {
+        return Some(RiskViolation::Slippage {
+            slippage_bps: exposure.slippage_bps,
+            limit_bps: max_slippage_bps,
+        });
+    }
+
+    let after = |current: Option<&U256>| current.copied().unwrap_or_default().saturating_add(exposure.notional);
+
+    if let Some(&limit) = limits.token_limits.get(&exposure.token) {
+        let total = after(book.by_token.get(&exposure.token));
+        if total > limit {
+            return Some(RiskViolation::TokenLimit { token: exposure.token, exposure: total, limit });
+        }
+    }
+    if let Some(&limit) = limits.chain_limits.get(&exposure.chain_id) {
+        let total = after(book.by_chain.get(&exposure.chain_id));
+        if total > limit {
+            return Some(RiskViolation::ChainLimit { chain_id: exposure.chain_id, exposure: total, limit });
+        }
+    }
+    if let Some(limit) = limits.max_counterparty_notional {
+        let total = after(book.by_counterparty.get(&exposure.counterparty));
+        if total > limit {
+            return Some(RiskViolation::CounterpartyLimit { counterparty: exposure.counterparty, exposure: total, limit });
+        }
+    }
+    if let Some(limit) = limits.max_aggregate_notional {
+        let total = after(Some(&book.aggregate));
+        if total > limit {
+            return Some(RiskViolation::AggregateLimit { exposure: total, limit });
+        }
+    }
+    None
+}
+
+/// Loss from peak equity as a share of peak equity, where equity is
+/// `capital` plus realized P&L
+fn drawdown_bps(capital: U256, peak_pnl: i128, pnl: i128) -> u64 {
+    let capital = capital.min(U256::from(i128::MAX as u128)).as_u128() as i128;
+    let peak_equity = capital.saturating_add(peak_pnl);
+    if peak_equity <= 0 || pnl >= peak_pnl {
+        return 0;
+    }
+    let loss = peak_pnl.saturating_sub(pnl);
+    (loss.saturating_mul(10000) / peak_equity).min(10000) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exposure(token: Address, notional: u64) -> Exposure {
+        Exposure {
+            token,
+            chain_id: 137,
+            counterparty: Address::repeat_byte(0xaa),
+            notional: U256::from(notional),
+            slippage_bps: 10,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_limits_veto_and_release() {
+        let token = Address::repeat_byte(1);
+        let limits = RiskLimits {
+            token_limits: HashMap::from([(token, U256::from(1000))]),
+            max_aggregate_notional: Some(U256::from(1500)),
+            ..Default::default()
+        };
+        let risk = RiskManager::new(limits, 50);
+
+        risk.reserve(H256::repeat_byte(1), exposure(token, 800)).await.unwrap();
+        let err = risk.reserve(H256::repeat_byte(2), exposure(token, 300)).await.unwrap_err();
+        assert!(matches!(err, SolverError::RiskLimitExceeded(RiskViolation::TokenLimit { .. })));
+
+        let other = Address::repeat_byte(2);
+        let err = risk.reserve(H256::repeat_byte(3), exposure(other, 800)).await.unwrap_err();
+        assert!(matches!(err, SolverError::RiskLimitExceeded(RiskViolation::AggregateLimit { .. })));
+
+        let mut slippery = exposure(other, 1);
+        slippery.slippage_bps = 51;
+        let err = risk.reserve(H256::repeat_byte(4), slippery).await.unwrap_err();
+        assert!(matches!(err, SolverError::RiskLimitExceeded(RiskViolation::Slippage { .. })));
+
+        risk.release(H256::repeat_byte(1)).await;
+        risk.reserve(H256::repeat_byte(2), exposure(token, 300)).await.unwrap();
+        assert_eq!(risk.aggregate_exposure().await, U256::from(300));
+    }
+
+    #[tokio::test]
+    async fn test_drawdown_breaker() {
+        let limits = RiskLimits {
+            capital: U256::from(10_000),
+            max_drawdown_bps: Some(500),
+            ..Default::default()
+        };
+        let risk = RiskManager::new(limits, 50);
+
+        risk.record_pnl(1000).await;
+        risk.record_pnl(-500).await;
+        assert!(!risk.is_halted().await);
+
+        risk.record_pnl(-100).await;
+        assert!(risk.is_halted().await);
+        let err = risk.reserve(H256::zero(), exposure(Address::zero(), 1)).await.unwrap_err();
+        assert!(matches!(err, SolverError::RiskLimitExceeded(RiskViolation::DrawdownBreaker { .. })));
+
+        risk.reset_breaker().await;
+        assert!(risk.reserve(H256::zero(), exposure(Address::zero(), 1)).await.is_ok());
+    }
+}
@@ -0,0 +1,259 @@
+//! JSON-RPC quote service.
+//!
+//! Lets the backend API (or anyone aggregating quotes) ask this solver node
+//! for a price without going through the auction. `solver_getQuote` takes an
+//! intent and returns a [`SignedQuote`]: the node's quote plus an expiry,
+//! signed with the solver key so aggregators can attribute it and hold the
+//! solver to it. Callers are rate limited per IP address.
+
+use crate::{Result, Solver, SolverError, SolverQuote};
+use axum::{
+    extract::{ConnectInfo, State},
+    routing::post,
+    Json, Router,
+};
+use ethers::{
+    abi::{self, Token},
+    signers::{LocalWallet, Signer},
+    types::{Address, Signature, H256, U256},
+    utils::keccak256,
+};
+use intents_engine::intent::Intent;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::Mutex;
+use tracing::info;
+
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SOLVER_ERROR: i64 = -32000;
+const RATE_LIMITED: i64 = -32005;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuoteRpcConfig {
+    pub listen_address: String,
+    /// Requests allowed per client IP per minute
+    pub requests_per_minute: u32,
+    /// How long a returned quote is honored
+    pub quote_ttl_secs: u64,
+}
+
+impl Default for QuoteRpcConfig {
+    fn default() -> Self {
+        Self {
+            listen_address: "0.0.0.0:8547".to_string(),
+            requests_per_minute: 60,
+            quote_ttl_secs: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedQuote {
+    pub intent_id: H256,
+    pub quote: SolverQuote,
+    pub expires_at: u64,
+    pub signature: Signature,
+}
+
+impl SignedQuote {
+    /// Hash the solver signs (EIP-191): the intent, the solver, the promised
+    /// output and the expiry
+    pub fn digest(intent_id: H256, quote: &SolverQuote, expires_at: u64) -> H256 {
+        H256::from(keccak256(abi::encode(&[
+            Token::FixedBytes(intent_id.as_bytes().to_vec()),
+            Token::Address(quote.solver),
+            Token::Uint(quote.dest_amount),
+            Token::Uint(U256::from(expires_at)),
+        ])))
+    }
+
+    /// `true` if the signature was made by `quote.solver`
+    pub fn verify(&self) -> bool {
+        let digest = Self::digest(self.intent_id, &self.quote, self.expires_at);
+        self.signature.verify(digest.as_bytes(), self.quote.solver).is_ok()
+    }
+}
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Fixed one-minute window per client
+struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    clients: HashMap<IpAddr, (Instant, u32)>,
+}
+
+impl RateLimiter {
+    fn new(limit: u32, window: Duration) -> Self {
+        Self { limit, window, clients: HashMap::new() }
+    }
+
+    fn allow(&mut self, client: IpAddr, now: Instant) -> bool {
+        let window = self.window;
+        self.clients.retain(|_, (started, _)| now.duration_since(*started) < window);
+
+        let (_, count) = self.clients.entry(client).or_insert((now, 0));
+        if *count >= self.limit {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+}
+
+pub struct QuoteService {
+    config: QuoteRpcConfig,
+    solver: Arc<dyn Solver>,
+    signer: LocalWallet,
+    limiter: Mutex<RateLimiter>,
+}
+
+impl QuoteService {
+    pub fn new(config: QuoteRpcConfig, solver: Arc<dyn Solver>, signer: LocalWallet) -> Self {
+        let limiter = Mutex::new(RateLimiter::new(config.requests_per_minute, Duration::from_secs(60)));
+        Self { config, solver, signer, limiter }
+    }
+
+    pub async fn serve(self: Arc<Self>) -> Result<()> {
+        let listener = tokio::net::TcpListener::bind(&self.config.listen_address)
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Failed to bind quote RPC: {}", e)))?;
+        info!("Quote RPC listening on {}", self.config.listen_address);
+
+        let app = Router::new().route("/", post(handle_rpc)).with_state(self);
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Quote RPC server error: {}", e)))
+    }
+
+    /// Quote `intent` and sign the result
+    pub async fn get_quote(&self, intent: &Intent) -> Result<SignedQuote> {
+        let quote = self.solver.evaluate_intent(intent).await?;
+        let intent_id = intent.id();
+        let expires_at = current_timestamp() + self.config.quote_ttl_secs;
+        let digest = SignedQuote::digest(intent_id, &quote, expires_at);
+        let signature = self.signer
+            .sign_message(digest.as_bytes())
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Failed to sign quote: {}", e)))?;
+
+        Ok(SignedQuote { intent_id, quote, expires_at, signature })
+    }
+
+    async fn dispatch(&self, request: RpcRequest) -> std::result::Result<Value, (i64, String)> {
+        match request.method.as_str() {
+            "solver_getQuote" => {
+                // Accept `[intent]` or `{"intent": ...}`
+                let intent = match request.params {
+                    Value::Array(mut params) if params.len() == 1 => params.remove(0),
+                    Value::Object(mut params) => params.remove("intent").unwrap_or(Value::Null),
+                    _ => Value::Null,
+                };
+                let intent: Intent = serde_json::from_value(intent)
+                    .map_err(|e| (INVALID_PARAMS, format!("Invalid intent: {}", e)))?;
+                let quote = self.get_quote(&intent).await.map_err(|e| (SOLVER_ERROR, e.to_string()))?;
+                Ok(json!(quote))
+            }
+            "solver_address" => Ok(json!(self.signer.address())),
+            method => Err((METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+        }
+    }
+}
+
+async fn handle_rpc(
+    State(service): State<Arc<QuoteService>>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    Json(request): Json<Value>,
+) -> Json<Value> {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+
+    if !service.limiter.lock().await.allow(client.ip(), Instant::now()) {
+        return Json(rpc_error(id, RATE_LIMITED, "Rate limit exceeded".to_string()));
+    }
+
+    let request: RpcRequest = match serde_json::from_value(request) {
+        Ok(request) => request,
+        Err(e) => return Json(rpc_error(id, INVALID_REQUEST, e.to_string())),
+    };
+    let id = request.id.clone();
+    match service.dispatch(request).await {
+        Ok(result) => Json(json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+        Err((code, message)) => Json(rpc_error(id, code, message)),
+    }
+}
+
+fn rpc_error(id: Value, code: i64, message: String) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// Solver address a quote claims to be from, for aggregators checking
+/// quotes against a registry
+pub fn quote_signer(quote: &SignedQuote) -> Option<Address> {
+    let digest = SignedQuote::digest(quote.intent_id, &quote.quote, quote.expires_at);
+    quote.signature.recover(digest.as_bytes()).ok()
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_signed_quote_verifies() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let quote = SolverQuote {
+            solver: wallet.address(),
+            dest_amount: U256::from(990),
+            profit: U256::from(5),
+            execution_time_estimate: 45,
+            confidence: 0.95,
+        };
+        let intent_id = H256::repeat_byte(7);
+        let digest = SignedQuote::digest(intent_id, &quote, 1_000);
+        let signature = wallet.sign_message(digest.as_bytes()).await.unwrap();
+
+        let mut signed = SignedQuote { intent_id, quote, expires_at: 1_000, signature };
+        assert!(signed.verify());
+        assert_eq!(quote_signer(&signed), Some(wallet.address()));
+
+        // Any change to the promised output invalidates the signature
+        signed.quote.dest_amount = U256::from(1_000);
+        assert!(!signed.verify());
+    }
+
+    #[test]
+    fn test_rate_limiter_window() {
+        let mut limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let client: IpAddr = "10.0.0.1".parse().unwrap();
+        let other: IpAddr = "10.0.0.2".parse().unwrap();
+        let start = Instant::now();
+
+        assert!(limiter.allow(client, start));
+        assert!(limiter.allow(client, start));
+        assert!(!limiter.allow(client, start + Duration::from_secs(1)));
+        assert!(limiter.allow(other, start + Duration::from_secs(1)));
+        assert!(limiter.allow(client, start + Duration::from_secs(61)));
+    }
+}
@@ -0,0 +1,388 @@
+//! Client for external sealed-bid intent auctions.
+//!
+//! An auction coordinator shared by many solvers announces intents and runs
+//! a two-phase auction for each. During the commit phase every solver posts
+//! only `keccak256(auction_id, solver, dest_amount, salt)`, so no solver
+//! can see, copy or undercut another's price, and a coordinator cannot leak
+//! bids it has not received. Once commits close, solvers reveal amount and
+//! salt; reveals that do not match their commitment are discarded, and the
+//! best valid reveal wins. Commits and reveals are signed with the solver
+//! key.
+//!
+//! The coordinator is reached over HTTP:
+//!
+//! - `GET  /auctions` lists open [`AuctionAnnouncement`]s
+//! - `POST /auctions/{id}/commit` takes a [`CommitRequest`]
+//! - `POST /auctions/{id}/reveal` takes a [`RevealRequest`]
+//! - `GET  /auctions/{id}/result` returns an [`AuctionResult`]
+//!
+//! Results are polled once reveals close and handed to [`AuctionCallbacks`].
+
+use crate::{Result, Solver, SolverError, SolverQuote};
+use async_trait::async_trait;
+use ethers::{
+    abi::{self, Token},
+    signers::{LocalWallet, Signer},
+    types::{Address, Signature, H256, U256},
+    utils::keccak256,
+};
+use intents_engine::intent::Intent;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedAuctionConfig {
+    /// Base URL of the coordinator, e.g. `https://auctions.example.com`
+    pub coordinator_url: String,
+    /// Sent as a bearer token if set
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+}
+
+fn default_poll_interval_ms() -> u64 {
+    1000
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuctionAnnouncement {
+    pub auction_id: H256,
+    pub intent: Intent,
+    /// Commitments are accepted until this unix time
+    pub commit_deadline: u64,
+    /// Reveals are accepted until this unix time
+    pub reveal_deadline: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionPhase {
+    Commit,
+    Reveal,
+    Closed,
+}
+
+impl AuctionAnnouncement {
+    pub fn phase(&self, now: u64) -> AuctionPhase {
+        if now < self.commit_deadline {
+            AuctionPhase::Commit
+        } else if now < self.reveal_deadline {
+            AuctionPhase::Reveal
+        } else {
+            AuctionPhase::Closed
+        }
+    }
+}
+
+/// Our bid in one auction; the amount and salt stay local until revealed
+#[derive(Debug, Clone)]
+pub struct SealedBid {
+    pub auction_id: H256,
+    pub quote: SolverQuote,
+    pub salt: H256,
+    pub commitment: H256,
+}
+
+impl SealedBid {
+    pub fn new(auction_id: H256, quote: SolverQuote) -> Self {
+        let salt = H256::random();
+        let commitment = commitment(auction_id, quote.solver, quote.dest_amount, salt);
+        Self { auction_id, quote, salt, commitment }
+    }
+}
+
+/// Binds a solver to `dest_amount` in `auction_id` without disclosing it
+pub fn commitment(auction_id: H256, solver: Address, dest_amount: U256, salt: H256) -> H256 {
+    H256::from(keccak256(abi::encode(&[
+        Token::FixedBytes(auction_id.as_bytes().to_vec()),
+        Token::Address(solver),
+        Token::Uint(dest_amount),
+        Token::FixedBytes(salt.as_bytes().to_vec()),
+    ])))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitRequest {
+    pub solver: Address,
+    pub commitment: H256,
+    /// EIP-191 signature over the commitment
+    pub signature: Signature,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevealRequest {
+    pub solver: Address,
+    pub dest_amount: U256,
+    pub salt: H256,
+    /// EIP-191 signature over the commitment being opened
+    pub signature: Signature,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum AuctionResult {
+    /// Reveals are still being tallied
+    Pending,
+    Settled { winner: Address, winning_amount: U256 },
+    Cancelled { reason: String },
+}
+
+/// What to do once an auction we bid in is decided
+#[async_trait]
+pub trait AuctionCallbacks: Send + Sync {
+    /// We won and must now fill `announcement.intent` at the bid amount
+    async fn on_won(&self, announcement: &AuctionAnnouncement, bid: &SealedBid) -> Result<()>;
+
+    async fn on_lost(&self, announcement: &AuctionAnnouncement, result: &AuctionResult) {
+        debug!("Lost shared auction {:?}: {:?}", announcement.auction_id, result);
+    }
+}
+
+struct Participation {
+    announcement: AuctionAnnouncement,
+    /// `None` if we passed on the auction
+    bid: Option<SealedBid>,
+    revealed: bool,
+}
+
+pub struct SharedAuctionClient {
+    config: SharedAuctionConfig,
+    client: reqwest::Client,
+    signer: LocalWallet,
+    solver: Arc<dyn Solver>,
+    callbacks: Arc<dyn AuctionCallbacks>,
+    auctions: Mutex<HashMap<H256, Participation>>,
+}
+
+impl SharedAuctionClient {
+    pub fn new(
+        config: SharedAuctionConfig,
+        solver: Arc<dyn Solver>,
+        callbacks: Arc<dyn AuctionCallbacks>,
+        signer: LocalWallet,
+    ) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+            signer,
+            solver,
+            callbacks,
+            auctions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn run(self: Arc<Self>) {
+        info!("Joining shared auctions at {}", self.config.coordinator_url);
+        let mut interval = tokio::time::interval(Duration::from_millis(self.config.poll_interval_ms.max(100)));
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.poll(current_timestamp()).await {
+                warn!("Shared auction poll failed: {}", e);
+            }
+        }
+    }
+
+    /// Commit to newly announced auctions, reveal where commits have closed
+    /// and settle auctions whose reveals have closed
+    pub async fn poll(&self, now: u64) -> Result<()> {
+        for announcement in self.announcements().await? {
+            if announcement.phase(now) != AuctionPhase::Commit
+                || self.auctions.lock().await.contains_key(&announcement.auction_id)
+            {
+                continue;
+            }
+            let bid = match self.commit(&announcement).await {
+                Ok(bid) => Some(bid),
+                Err(e) => {
+                    debug!("Passing on shared auction {:?}: {}", announcement.auction_id, e);
+                    None
+                }
+            };
+            self.auctions.lock().await.insert(
+                announcement.auction_id,
+                Participation { announcement, bid, revealed: false },
+            );
+        }
+
+        let mut auctions = self.auctions.lock().await;
+        let mut decided = Vec::new();
+        for (auction_id, participation) in auctions.iter_mut() {
+            match participation.announcement.phase(now) {
+                AuctionPhase::Commit => {}
+                AuctionPhase::Reveal => {
+                    let Some(bid) = participation.bid.as_ref().filter(|_| !participation.revealed) else { continue };
+                    match self.reveal(bid).await {
+                        Ok(()) => participation.revealed = true,
+                        Err(e) => warn!("Failed to reveal bid in shared auction {:?}: {}", auction_id, e),
+                    }
+                }
+                AuctionPhase::Closed if !participation.revealed => decided.push((*auction_id, None)),
+                AuctionPhase::Closed => match self.result(*auction_id).await {
+                    Ok(AuctionResult::Pending) => {}
+                    Ok(result) => decided.push((*auction_id, Some(result))),
+                    Err(e) => debug!("Result of shared auction {:?} unavailable: {}", auction_id, e),
+                },
+            }
+        }
+        let decided: Vec<(Participation, Option<AuctionResult>)> = decided
+            .into_iter()
+            .filter_map(|(auction_id, result)| auctions.remove(&auction_id).map(|p| (p, result)))
+            .collect();
+        drop(auctions);
+
+        for (participation, result) in decided {
+            let (Some(bid), Some(result)) = (participation.bid, result) else { continue };
+            let announcement = &participation.announcement;
+            match &result {
+                AuctionResult::Settled { winner, .. } if *winner == self.signer.address() => {
+                    info!("Won shared auction {:?} at {}", announcement.auction_id, bid.quote.dest_amount);
+                    if let Err(e) = self.callbacks.on_won(announcement, &bid).await {
+                        warn!("Failed to fill shared auction {:?}: {}", announcement.auction_id, e);
+                    }
+                }
+                _ => self.callbacks.on_lost(announcement, &result).await,
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn announcements(&self) -> Result<Vec<AuctionAnnouncement>> {
+        self.get("auctions".to_string()).await
+    }
+
+    /// Quote the announced intent and post a commitment to the quote
+    pub async fn commit(&self, announcement: &AuctionAnnouncement) -> Result<SealedBid> {
+        let quote = self.solver.evaluate_intent(&announcement.intent).await?;
+        if quote.dest_amount < announcement.intent.min_dest_amount {
+            return Err(SolverError::Unprofitable);
+        }
+        let bid = SealedBid::new(announcement.auction_id, SolverQuote { solver: self.signer.address(), ..quote });
+
+        let request = CommitRequest {
+            solver: self.signer.address(),
+            commitment: bid.commitment,
+            signature: self.sign(bid.commitment).await?,
+        };
+        self.post(format!("auctions/{:?}/commit", bid.auction_id), &request).await?;
+        Ok(bid)
+    }
+
+    pub async fn reveal(&self, bid: &SealedBid) -> Result<()> {
+        let request = RevealRequest {
+            solver: self.signer.address(),
+            dest_amount: bid.quote.dest_amount,
+            salt: bid.salt,
+            signature: self.sign(bid.commitment).await?,
+        };
+        self.post(format!("auctions/{:?}/reveal", bid.auction_id), &request).await
+    }
+
+    pub async fn result(&self, auction_id: H256) -> Result<AuctionResult> {
+        self.get(format!("auctions/{:?}/result", auction_id)).await
+    }
+
+    async fn sign(&self, digest: H256) -> Result<Signature> {
+        self.signer
+            .sign_message(digest.as_bytes())
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Failed to sign auction message: {}", e)))
+    }
+
+    fn request(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.config.api_key {
+            Some(key) => builder.bearer_auth(key),
+            None => builder,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.config.coordinator_url.trim_end_matches('/'), path)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: String) -> Result<T> {
+        self.request(self.client.get(self.url(&path)))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| SolverError::ExecutionFailed(format!("Coordinator request {} failed: {}", path, e)))?
+            .json()
+            .await
+            .map_err(|e| SolverError::ExecutionFailed(format!("Malformed coordinator response to {}: {}", path, e)))
+    }
+
+    async fn post<T: Serialize>(&self, path: String, body: &T) -> Result<()> {
+        self.request(self.client.post(self.url(&path)).json(body))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| SolverError::ExecutionFailed(format!("Coordinator request {} failed: {}", path, e)))?;
+        Ok(())
+    }
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(dest_amount: u64) -> SolverQuote {
+        SolverQuote {
+            solver: Address::repeat_byte(7),
+            dest_amount: U256::from(dest_amount),
+            profit: U256::zero(),
+            execution_time_estimate: 30,
+            confidence: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_commitment_hides_and_binds_amount() {
+        let auction_id = H256::repeat_byte(1);
+        let bid = SealedBid::new(auction_id, quote(1_000));
+
+        // The reveal opens the commitment only with the committed amount and salt
+        assert_eq!(bid.commitment, commitment(auction_id, bid.quote.solver, U256::from(1_000), bid.salt));
+        assert_ne!(bid.commitment, commitment(auction_id, bid.quote.solver, U256::from(1_001), bid.salt));
+        assert_ne!(bid.commitment, commitment(auction_id, Address::repeat_byte(8), U256::from(1_000), bid.salt));
+
+        // Equal bids are indistinguishable before reveal
+        let other = SealedBid::new(auction_id, quote(1_000));
+        assert_ne!(bid.salt, other.salt);
+        assert_ne!(bid.commitment, other.commitment);
+    }
+
+    #[test]
+    fn test_auction_phases_and_results() {
+        let announcement = AuctionAnnouncement {
+            auction_id: H256::repeat_byte(1),
+            intent: Intent::default(),
+            commit_deadline: 100,
+            reveal_deadline: 110,
+        };
+        assert_eq!(announcement.phase(99), AuctionPhase::Commit);
+        assert_eq!(announcement.phase(100), AuctionPhase::Reveal);
+        assert_eq!(announcement.phase(110), AuctionPhase::Closed);
+
+        let result: AuctionResult = serde_json::from_str(
+            r#"{"status":"settled","winner":"0x0707070707070707070707070707070707070707","winning_amount":"0x3e8"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            AuctionResult::Settled { winner: Address::repeat_byte(7), winning_amount: U256::from(1_000) }
+        );
+        assert_eq!(serde_json::from_str::<AuctionResult>(r#"{"status":"pending"}"#).unwrap(), AuctionResult::Pending);
+    }
+}
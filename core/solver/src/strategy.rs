@@ -0,0 +1,209 @@
+//! Pluggable quoting strategies.
+//!
+//! How a solver prices intents is defined by [`Strategy`] implementations
+//! rather than hard-coded in [`SolverNode`](crate::SolverNode). A
+//! [`StrategySet`] holds several strategies with allocation weights and
+//! assigns each intent to exactly one of them, deterministically from the
+//! intent id, so flow is split between strategies in proportion to their
+//! weights. The built-in [`RouteStrategy`] quotes through the route
+//! optimizer and is what a node runs unless configured otherwise.
+
+use crate::{optimizer::{Route, RouteOptimizer}, Result, SolverConfig, SolverQuote};
+use async_trait::async_trait;
+use ethers::types::{H256, U256};
+use intents_engine::intent::{Intent, IntentExecution};
+use std::sync::Arc;
+
+#[async_trait]
+pub trait Strategy: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Price `intent`, or return `None` to pass on it
+    async fn evaluate(&self, intent: &Intent) -> Result<Option<SolverQuote>>;
+
+    /// Final quote submitted to the auction, e.g. shaded against competition
+    async fn bid(&self, _intent: &Intent, quote: SolverQuote) -> Result<SolverQuote> {
+        Ok(quote)
+    }
+
+    /// Called once the solver has won `intent`, before execution; the place
+    /// to open hedges against the exposure taken on
+    async fn on_matched(&self, _intent_id: H256, _intent: &Intent) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called after execution finished, `None` if it failed; the place to
+    /// unwind hedges
+    async fn on_settled(&self, _intent_id: H256, _execution: Option<&IntentExecution>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Strategies with allocation weights
+#[derive(Clone, Default)]
+pub struct StrategySet {
+    strategies: Vec<(Arc<dyn Strategy>, u32)>,
+}
+
+impl StrategySet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `strategy` with a `weight` relative to the other strategies.
+    /// Zero-weight strategies never receive intents.
+    pub fn with(mut self, strategy: Arc<dyn Strategy>, weight: u32) -> Self {
+        self.strategies.push((strategy, weight));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total_weight() == 0
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.strategies.iter().map(|(strategy, _)| strategy.name()).collect()
+    }
+
+    fn total_weight(&self) -> u64 {
+        self.strategies.iter().map(|(_, weight)| *weight as u64).sum()
+    }
+
+    /// Strategy responsible for `intent_id`
+    pub fn assign(&self, intent_id: H256) -> Option<Arc<dyn Strategy>> {
+        let total = self.total_weight();
+        if total == 0 {
+            return None;
+        }
+
+        let mut bucket = U256::from_big_endian(intent_id.as_bytes()) % U256::from(total);
+        for (strategy, weight) in &self.strategies {
+            let weight = U256::from(*weight);
+            if bucket < weight {
+                return Some(strategy.clone());
+            }
+            bucket -= weight;
+        }
+        None
+    }
+
+    /// Evaluate `intent` with its assigned strategy and turn the result into a bid
+    pub async fn quote(&self, intent: &Intent) -> Result<Option<SolverQuote>> {
+        let Some(strategy) = self.assign(intent.id()) else {
+            return Ok(None);
+        };
+        match strategy.evaluate(intent).await? {
+            Some(quote) => Ok(Some(strategy.bid(intent, quote).await?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Quotes the best route found by the optimizer, passing on intents below
+/// the configured minimum profit
+pub struct RouteStrategy {
+    config: SolverConfig,
+    optimizer: Arc<RouteOptimizer>,
+}
+
+impl RouteStrategy {
+    pub fn new(config: SolverConfig, optimizer: Arc<RouteOptimizer>) -> Self {
+        Self { config, optimizer }
+    }
+
+    fn estimate_execution_time(&self, route: &Route) -> u64 {
+        // Base time for transaction confirmation
+        let mut time = 30; // seconds
+
+        // Add time for each hop
+        time += route.hops.len() as u64 * 15;
+
+        // Add buffer for cross-chain messages
+        if route.cross_chain {
+            time += 60;
+        }
+
+        time
+    }
+}
+
+#[async_trait]
+impl Strategy for RouteStrategy {
+    fn name(&self) -> &str {
+        "route"
+    }
+
+    async fn evaluate(&self, intent: &Intent) -> Result<Option<SolverQuote>> {
+        // Find optimal route
+        let route = self.optimizer.find_best_route(intent).await?;
+
+        // Calculate expected output and profit
+        let (dest_amount, profit) = self.optimizer.calculate_profit(&route, intent).await?;
+
+        // Check profitability
+        let profit_bps = profit * U256::from(10000) / intent.source_amount;
+        if profit_bps < U256::from(self.config.min_profit_bps) {
+            return Ok(None);
+        }
+
+        Ok(Some(SolverQuote {
+            solver: self.config.address,
+            dest_amount,
+            profit,
+            execution_time_estimate: self.estimate_execution_time(&route),
+            confidence: 0.95, // TODO: Calculate based on historical performance
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::Address;
+
+    struct Fixed(&'static str);
+
+    #[async_trait]
+    impl Strategy for Fixed {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        async fn evaluate(&self, intent: &Intent) -> Result<Option<SolverQuote>> {
+            Ok(Some(SolverQuote {
+                solver: Address::zero(),
+                dest_amount: intent.min_dest_amount,
+                profit: U256::zero(),
+                execution_time_estimate: 30,
+                confidence: 1.0,
+            }))
+        }
+    }
+
+    #[test]
+    fn test_assignment_follows_weights() {
+        let set = StrategySet::new()
+            .with(Arc::new(Fixed("a")), 3)
+            .with(Arc::new(Fixed("idle")), 0)
+            .with(Arc::new(Fixed("b")), 1);
+
+        let mut counts = std::collections::HashMap::new();
+        for i in 0..4000u64 {
+            let id = H256::from_low_u64_be(i);
+            let name = set.assign(id).unwrap().name().to_string();
+            assert_eq!(set.assign(id).unwrap().name(), name, "assignment must be deterministic");
+            *counts.entry(name).or_insert(0) += 1;
+        }
+
+        assert_eq!(counts.get("a"), Some(&3000));
+        assert_eq!(counts.get("b"), Some(&1000));
+        assert!(!counts.contains_key("idle"));
+    }
+
+    #[tokio::test]
+    async fn test_empty_set_passes() {
+        let set = StrategySet::new().with(Arc::new(Fixed("idle")), 0);
+        assert!(set.is_empty());
+        assert!(set.quote(&Intent::default()).await.unwrap().is_none());
+    }
+}
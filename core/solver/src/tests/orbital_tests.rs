@@ -38,6 +38,22 @@ fn create_test_solver_config() -> crate::SolverConfig {
         max_slippage_bps: 100, // 1% max slippage
         supported_chains: vec![1, 137, 42161],
         oracle_addresses: std::collections::HashMap::new(),
+        orbital_pools: std::collections::HashMap::new(),
+        venues: Vec::new(),
+        private_relays: Vec::new(),
+        rebalancing: None,
+        reputation_sync: None,
+        risk_limits: Default::default(),
+        intent_feed: None,
+        quote_rpc: None,
+        hedging: None,
+        bidding: Default::default(),
+        pipeline: Default::default(),
+        slashing_defense: None,
+        monitoring: None,
+        intent_filter_path: None,
+        shared_auction: None,
+        batching: Default::default(),
     }
 }
 
@@ -416,4 +432,14 @@ async fn test_mathematical_invariants() {
     
     // Confidence score bounds
     assert!(estimation.confidence_score <= 100, "Confidence score must be <= 100");
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_new_intent_handler_spawns_on_runtime() {
+    // No chains means no RPC endpoints are dialed while building the node
+    let config = crate::SolverConfig { supported_chains: Vec::new(), ..create_test_solver_config() };
+    let node = Arc::new(crate::SolverNode::new(config).await.unwrap());
+    let expired = Intent { deadline: 0, ..create_test_intent() };
+
+    // The feed hands every intent to tokio::spawn, which needs a Send future
+    tokio::spawn(node.handle_new_intent(H256::random(), expired, 1)).await.unwrap();
+}
@@ -0,0 +1,415 @@
+//! DEX venues the route optimizer sources liquidity from.
+//!
+//! A [`Venue`] lists its pools on one chain, quotes swaps through them on-chain
+//! and encodes the swap call the executor submits. Uniswap v3 quotes through
+//! QuoterV2 and swaps through SwapRouter02, Curve pools quote with `get_dy`,
+//! and the orbital AMM with `getAmountOut`.
+
+use crate::{optimizer::Protocol, Result, SolverError};
+use async_trait::async_trait;
+use ethers::{
+    abi::{self, Token},
+    providers::{Http, Middleware, Provider},
+    types::{transaction::eip2718::TypedTransaction, Address, Bytes, TransactionRequest, U256},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VenuePool {
+    pub protocol: Protocol,
+    pub chain_id: u64,
+    pub address: Address,
+    pub token0: Address,
+    pub token1: Address,
+}
+
+impl VenuePool {
+    pub fn other(&self, token: Address) -> Option<Address> {
+        if token == self.token0 {
+            Some(self.token1)
+        } else if token == self.token1 {
+            Some(self.token0)
+        } else {
+            None
+        }
+    }
+}
+
+/// A contract call the executor submits for one hop
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapCall {
+    pub target: Address,
+    pub calldata: Bytes,
+}
+
+#[async_trait]
+pub trait Venue: Send + Sync {
+    fn protocol(&self) -> Protocol;
+
+    fn chain_id(&self) -> u64;
+
+    fn pools(&self) -> &[VenuePool];
+
+    /// Output of swapping `amount_in` of `token_in` through `pool`
+    async fn quote(&self, pool: &VenuePool, token_in: Address, amount_in: U256) -> Result<U256>;
+
+    fn swap_call(
+        &self,
+        pool: &VenuePool,
+        token_in: Address,
+        amount_in: U256,
+        min_amount_out: U256,
+        recipient: Address,
+    ) -> Result<SwapCall>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UniswapV3PoolConfig {
+    pub address: Address,
+    pub token0: Address,
+    pub token1: Address,
+    /// Fee tier in hundredths of a bip, e.g. 500 or 3000
+    pub fee: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurvePoolConfig {
+    pub address: Address,
+    /// Pool coins in index order
+    pub coins: Vec<Address>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrbitalPairConfig {
+    pub pool_id: U256,
+    pub token0: Address,
+    pub token1: Address,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "protocol", rename_all = "snake_case")]
+pub enum VenueConfig {
+    UniswapV3 {
+        chain_id: u64,
+        quoter: Address,
+        router: Address,
+        pools: Vec<UniswapV3PoolConfig>,
+    },
+    Curve {
+        chain_id: u64,
+        pools: Vec<CurvePoolConfig>,
+    },
+    Orbital {
+        chain_id: u64,
+        amm: Address,
+        pools: Vec<OrbitalPairConfig>,
+    },
+}
+
+/// Build venues for every config whose chain has a provider
+pub fn from_config(configs: &[VenueConfig], providers: &HashMap<u64, Arc<Provider<Http>>>) -> Vec<Arc<dyn Venue>> {
+    configs
+        .iter()
+        .filter_map(|config| {
+            let venue: Arc<dyn Venue> = match config {
+                VenueConfig::UniswapV3 { chain_id, quoter, router, pools } => Arc::new(UniswapV3Venue::new(
+                    *chain_id,
+                    providers.get(chain_id)?.clone(),
+                    *quoter,
+                    *router,
+                    pools,
+                )),
+                VenueConfig::Curve { chain_id, pools } => {
+                    Arc::new(CurveVenue::new(*chain_id, providers.get(chain_id)?.clone(), pools))
+                }
+                VenueConfig::Orbital { chain_id, amm, pools } => {
+                    Arc::new(OrbitalVenue::new(*chain_id, providers.get(chain_id)?.clone(), *amm, pools))
+                }
+            };
+            Some(venue)
+        })
+        .collect()
+}
+
+pub struct UniswapV3Venue {
+    chain_id: u64,
+    provider: Arc<Provider<Http>>,
+    quoter: Address,
+    router: Address,
+    pools: Vec<VenuePool>,
+    fees: HashMap<Address, u32>,
+}
+
+impl UniswapV3Venue {
+    pub fn new(
+        chain_id: u64,
+        provider: Arc<Provider<Http>>,
+        quoter: Address,
+        router: Address,
+        pools: &[UniswapV3PoolConfig],
+    ) -> Self {
+        Self {
+            chain_id,
+            provider,
+            quoter,
+            router,
+            pools: pools
+                .iter()
+                .map(|pool| VenuePool {
+                    protocol: Protocol::UniswapV3,
+                    chain_id,
+                    address: pool.address,
+                    token0: pool.token0,
+                    token1: pool.token1,
+                })
+                .collect(),
+            fees: pools.iter().map(|pool| (pool.address, pool.fee)).collect(),
+        }
+    }
+
+    fn fee(&self, pool: &VenuePool) -> Result<u32> {
+        self.fees
+            .get(&pool.address)
+            .copied()
+            .ok_or_else(|| SolverError::ExecutionFailed(format!("Unknown Uniswap v3 pool {:?}", pool.address)))
+    }
+}
+
+#[async_trait]
+impl Venue for UniswapV3Venue {
+    fn protocol(&self) -> Protocol {
+        Protocol::UniswapV3
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn pools(&self) -> &[VenuePool] {
+        &self.pools
+    }
+
+    async fn quote(&self, pool: &VenuePool, token_in: Address, amount_in: U256) -> Result<U256> {
+        let token_out = pool.other(token_in).ok_or(SolverError::InsufficientLiquidity)?;
+        let calldata = encode_call(
+            b"quoteExactInputSingle((address,address,uint256,uint24,uint160))",
+            &[Token::Tuple(vec![
+                Token::Address(token_in),
+                Token::Address(token_out),
+                Token::Uint(amount_in),
+                Token::Uint(U256::from(self.fee(pool)?)),
+                Token::Uint(U256::zero()),
+            ])],
+        );
+        eth_call_uint(&self.provider, self.quoter, calldata).await
+    }
+
+    fn swap_call(
+        &self,
+        pool: &VenuePool,
+        token_in: Address,
+        amount_in: U256,
+        min_amount_out: U256,
+        recipient: Address,
+    ) -> Result<SwapCall> {
+        let token_out = pool.other(token_in).ok_or(SolverError::InsufficientLiquidity)?;
+        let calldata = encode_call(
+            b"exactInputSingle((address,address,uint24,address,uint256,uint256,uint160))",
+            &[Token::Tuple(vec![
+                Token::Address(token_in),
+                Token::Address(token_out),
+                Token::Uint(U256::from(self.fee(pool)?)),
+                Token::Address(recipient),
+                Token::Uint(amount_in),
+                Token::Uint(min_amount_out),
+                Token::Uint(U256::zero()),
+            ])],
+        );
+        Ok(SwapCall { target: self.router, calldata })
+    }
+}
+
+pub struct CurveVenue {
+    chain_id: u64,
+    provider: Arc<Provider<Http>>,
+    pools: Vec<VenuePool>,
+    coins: HashMap<Address, Vec<Address>>,
+}
+
+impl CurveVenue {
+    pub fn new(chain_id: u64, provider: Arc<Provider<Http>>, pools: &[CurvePoolConfig]) -> Self {
+        // Every pair of coins in a Curve pool is tradable
+        let mut venue_pools = Vec::new();
+        for pool in pools {
+            for (i, &token0) in pool.coins.iter().enumerate() {
+                for &token1 in pool.coins.iter().skip(i + 1) {
+                    venue_pools.push(VenuePool {
+                        protocol: Protocol::Curve,
+                        chain_id,
+                        address: pool.address,
+                        token0,
+                        token1,
+                    });
+                }
+            }
+        }
+
+        Self {
+            chain_id,
+            provider,
+            pools: venue_pools,
+            coins: pools.iter().map(|pool| (pool.address, pool.coins.clone())).collect(),
+        }
+    }
+
+    fn indices(&self, pool: &VenuePool, token_in: Address) -> Result<(Token, Token)> {
+        let token_out = pool.other(token_in).ok_or(SolverError::InsufficientLiquidity)?;
+        let coins = self.coins.get(&pool.address).ok_or(SolverError::InsufficientLiquidity)?;
+        let index = |token| {
+            coins
+                .iter()
+                .position(|&coin| coin == token)
+                .map(|i| Token::Int(U256::from(i)))
+                .ok_or(SolverError::InsufficientLiquidity)
+        };
+        Ok((index(token_in)?, index(token_out)?))
+    }
+}
+
+#[async_trait]
+impl Venue for CurveVenue {
+    fn protocol(&self) -> Protocol {
+        Protocol::Curve
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn pools(&self) -> &[VenuePool] {
+        &self.pools
+    }
+
+    async fn quote(&self, pool: &VenuePool, token_in: Address, amount_in: U256) -> Result<U256> {
+        let (i, j) = self.indices(pool, token_in)?;
+        let calldata = encode_call(b"get_dy(int128,int128,uint256)", &[i, j, Token::Uint(amount_in)]);
+        eth_call_uint(&self.provider, pool.address, calldata).await
+    }
+
+    fn swap_call(
+        &self,
+        pool: &VenuePool,
+        token_in: Address,
+        amount_in: U256,
+        min_amount_out: U256,
+        _recipient: Address,
+    ) -> Result<SwapCall> {
+        let (i, j) = self.indices(pool, token_in)?;
+        let calldata = encode_call(
+            b"exchange(int128,int128,uint256,uint256)",
+            &[i, j, Token::Uint(amount_in), Token::Uint(min_amount_out)],
+        );
+        Ok(SwapCall { target: pool.address, calldata })
+    }
+}
+
+pub struct OrbitalVenue {
+    chain_id: u64,
+    provider: Arc<Provider<Http>>,
+    amm: Address,
+    pools: Vec<VenuePool>,
+    pool_ids: HashMap<(Address, Address), U256>,
+}
+
+impl OrbitalVenue {
+    pub fn new(chain_id: u64, provider: Arc<Provider<Http>>, amm: Address, pools: &[OrbitalPairConfig]) -> Self {
+        Self {
+            chain_id,
+            provider,
+            amm,
+            pools: pools
+                .iter()
+                .map(|pool| VenuePool {
+                    protocol: Protocol::OrbitalAMM,
+                    chain_id,
+                    address: amm,
+                    token0: pool.token0,
+                    token1: pool.token1,
+                })
+                .collect(),
+            pool_ids: pools.iter().map(|pool| ((pool.token0, pool.token1), pool.pool_id)).collect(),
+        }
+    }
+
+    /// Pool id and swap direction for `token_in`
+    fn pool_id(&self, pool: &VenuePool, token_in: Address) -> Result<(U256, bool)> {
+        let pool_id = self
+            .pool_ids
+            .get(&(pool.token0, pool.token1))
+            .copied()
+            .ok_or(SolverError::InsufficientLiquidity)?;
+        // The AMM orders pool tokens by address
+        Ok((pool_id, token_in == pool.token0.min(pool.token1)))
+    }
+}
+
+#[async_trait]
+impl Venue for OrbitalVenue {
+    fn protocol(&self) -> Protocol {
+        Protocol::OrbitalAMM
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn pools(&self) -> &[VenuePool] {
+        &self.pools
+    }
+
+    async fn quote(&self, pool: &VenuePool, token_in: Address, amount_in: U256) -> Result<U256> {
+        let (pool_id, zero_for_one) = self.pool_id(pool, token_in)?;
+        let calldata = encode_call(
+            b"getAmountOut(uint256,bool,uint256)",
+            &[Token::Uint(pool_id), Token::Bool(zero_for_one), Token::Uint(amount_in)],
+        );
+        eth_call_uint(&self.provider, self.amm, calldata).await
+    }
+
+    fn swap_call(
+        &self,
+        pool: &VenuePool,
+        token_in: Address,
+        amount_in: U256,
+        min_amount_out: U256,
+        _recipient: Address,
+    ) -> Result<SwapCall> {
+        let (pool_id, zero_for_one) = self.pool_id(pool, token_in)?;
+        let calldata = encode_call(
+            b"swap(uint256,bool,uint256,uint256)",
+            &[Token::Uint(pool_id), Token::Bool(zero_for_one), Token::Uint(amount_in), Token::Uint(min_amount_out)],
+        );
+        Ok(SwapCall { target: self.amm, calldata })
+    }
+}
+
+fn encode_call(signature: &[u8], args: &[Token]) -> Bytes {
+    let selector = ethers::utils::keccak256(signature);
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend(abi::encode(args));
+    calldata.into()
+}
+
+/// `eth_call` returning the first word of the output
+async fn eth_call_uint(provider: &Provider<Http>, to: Address, calldata: Bytes) -> Result<U256> {
+    let call: TypedTransaction = TransactionRequest::new().to(to).data(calldata).into();
+    let output = provider
+        .call(&call, None)
+        .await
+        .map_err(|e| SolverError::ExecutionFailed(format!("Quote from {:?} failed: {}", to, e)))?;
+    if output.len() < 32 {
+        return Err(SolverError::InsufficientLiquidity);
+    }
+    Ok(U256::from_big_endian(&output[..32]))
+}
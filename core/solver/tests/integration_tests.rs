@@ -391,7 +391,10 @@ fn test_solver_error_types() {
     let errors = vec![
         SolverError::InsufficientLiquidity,
         SolverError::Unprofitable,
-        SolverError::RiskLimitExceeded,
+        SolverError::RiskLimitExceeded(intents_solver::risk::RiskViolation::Slippage {
+            slippage_bps: 120,
+            limit_bps: 100,
+        }),
         SolverError::ChainNotSupported(999),
         SolverError::ExecutionFailed("Test error".to_string()),
     ];
@@ -0,0 +1,98 @@
+//! Benchmarks for orbital-math primitives
+//!
+//! Covers the integer square root behind the sphere invariant and the
+//! fixed-point powers and roots behind the superellipse invariant, at
+//! reserve sizes typical of 18-decimal tokens, and batch quoting against
+//! a prepared pool.
+
+use alloy_primitives::U256;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use orbital_math::{
+    fixed_point::{exp2_fixed, log2_fixed, nth_root, pow_bp, pow_fixed, WAD},
+    quote::{PreparedPool, QuoteRequest},
+    superellipse::calculate_amount_out_superellipse,
+    types::CurveType,
+    utils::sqrt,
+};
+
+fn bench_sqrt(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sqrt");
+    for bits in [64u32, 128, 255] {
+        let value = U256::MAX >> (256 - bits as usize);
+        group.bench_with_input(BenchmarkId::from_parameter(bits), &value, |b, &value| {
+            b.iter(|| sqrt(black_box(value)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_fixed_point(c: &mut Criterion) {
+    let base = WAD * U256::from(1_234u64) / U256::from(1_000u64);
+    let exponent = WAD * U256::from(25u64) / U256::from(10u64);
+
+    c.bench_function("log2_fixed", |b| b.iter(|| log2_fixed(black_box(base))));
+    c.bench_function("exp2_fixed", |b| b.iter(|| exp2_fixed(black_box(exponent))));
+    c.bench_function("pow_fixed", |b| b.iter(|| pow_fixed(black_box(base), black_box(exponent))));
+
+    let mut group = c.benchmark_group("pow_bp");
+    for u in [22_000u32, 25_000, 28_000] {
+        let reserve = U256::from(1_000_000u64) * WAD;
+        group.bench_with_input(BenchmarkId::from_parameter(u), &u, |b, &u| {
+            b.iter(|| pow_bp(black_box(reserve), u))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("nth_root");
+    for u in [22_000u32, 25_000, 28_000] {
+        let value = pow_bp(U256::from(1_000_000u64) * WAD, u).unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(u), &u, |b, &u| {
+            b.iter(|| nth_root(black_box(value), u))
+        });
+    }
+    group.finish();
+}
+
+fn bench_superellipse_swap(c: &mut Criterion) {
+    let reserves = vec![U256::from(1_000_000u64) * WAD; 3];
+    let u = 25_000;
+    let k = reserves
+        .iter()
+        .fold(U256::ZERO, |acc, &r| acc + pow_bp(r, u).unwrap());
+
+    c.bench_function("superellipse_swap_3_tokens", |b| {
+        b.iter(|| calculate_amount_out_superellipse(black_box(&reserves), 0, 1, black_box(WAD * U256::from(1_000u64)), u, k))
+    });
+}
+
+fn bench_quote_many(c: &mut Criterion) {
+    let reserves = vec![U256::from(1_000_000u64) * WAD; 10];
+    let u = 25_000;
+    let k = reserves
+        .iter()
+        .fold(U256::ZERO, |acc, &r| acc + pow_bp(r, u).unwrap());
+    let requests: Vec<QuoteRequest> = (1..=64u64)
+        .map(|i| QuoteRequest { token_in: 0, token_out: 1, amount_in: WAD * U256::from(i * 100) })
+        .collect();
+
+    let mut group = c.benchmark_group("quote_64_superellipse_10_tokens");
+    group.bench_function("single", |b| {
+        b.iter(|| {
+            for request in &requests {
+                black_box(calculate_amount_out_superellipse(&reserves, 0, 1, request.amount_in, u, k).ok());
+            }
+        })
+    });
+    group.bench_function("batch", |b| {
+        b.iter(|| {
+            let prepared = PreparedPool::new(&reserves, &CurveType::Superellipse { u_parameter: u }, k).unwrap();
+            for request in &requests {
+                black_box(prepared.amount_out(0, 1, request.amount_in).ok());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_sqrt, bench_fixed_point, bench_superellipse_swap, bench_quote_many);
+criterion_main!(benches);
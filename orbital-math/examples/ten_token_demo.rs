@@ -6,11 +6,13 @@
 //! - Capital efficient tick configuration
 //! - Complex multi-hop routing
 //! - Real-world trading scenarios
+//!
+//! Run with `cargo run --example ten_token_demo`.
 
 use orbital_math::{
-    trades::{self, execute_swap_toroidal, execute_multi_hop_swap, calculate_optimal_route},
+    trades::{execute_swap_toroidal, execute_multi_hop_swap, calculate_optimal_route},
     sphere::{self, calculate_price_sphere, verify_sphere_constraint},
-    ticks::{self, optimize_tick_placement},
+    ticks,
     types::{PoolState, CurveType, Tick},
     superellipse,
     U256,
@@ -31,6 +33,10 @@ pub enum TokenType {
     Synthetic,
 }
 
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    run_ten_token_demo()
+}
+
 /// Comprehensive 10-token pool demonstration
 pub fn run_ten_token_demo() -> Result<(), Box<dyn std::error::Error>> {
     println!("🌌 Orbital AMM - 10-Token Pool Demonstration");
@@ -164,7 +170,7 @@ fn calculate_sphere_constant(reserves: &[U256]) -> Result<U256, Box<dyn std::err
     Ok(sum_of_squares)
 }
 
-fn create_concentrated_liquidity_ticks(tokens: &[TokenInfo]) -> Result<Vec<Tick>, Box<dyn std::error::Error>> {
+fn create_concentrated_liquidity_ticks(_tokens: &[TokenInfo]) -> Result<Vec<Tick>, Box<dyn std::error::Error>> {
     let mut ticks = Vec::new();
     
     // Create different tick configurations for different token types
@@ -214,7 +220,7 @@ fn create_concentrated_liquidity_ticks(tokens: &[TokenInfo]) -> Result<Vec<Tick>
 
 fn demonstrate_trading_scenarios(
     pool: &mut PoolState,
-    tokens: &[TokenInfo],
+    _tokens: &[TokenInfo],
 ) -> Result<(), Box<dyn std::error::Error>> {
     println!("\n🔄 Trading Scenarios");
     println!("===================");
@@ -452,7 +458,7 @@ fn demonstrate_advanced_features(
     println!("\n3. Tick Utilization:");
     
     for (i, tick) in pool.ticks.iter().enumerate() {
-        let current_reserves = orbital_math::types::ReservePoint::new(pool.reserves.reserves.clone());
+        let current_reserves = orbital_math::ReservePoint::new(pool.reserves.reserves.clone());
         let is_active = ticks::is_interior_to_tick(&current_reserves, tick)
             .unwrap_or(false);
         let utilization = (tick.liquidity * U256::from(100)) / pool.total_liquidity();
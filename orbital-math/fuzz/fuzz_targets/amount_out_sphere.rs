@@ -0,0 +1,51 @@
+//! `calculate_amount_out_sphere` never panics, and on a pool sitting on its
+//! sphere never pays out more than the invariant allows
+//!
+//! Input: a token-count byte (2 to 8 tokens), input and output index bytes,
+//! a 32-byte amount, a 32-byte arbitrary radius², then one 32-byte word per
+//! reserve.
+
+#![no_main]
+
+use alloy_primitives::U256;
+use libfuzzer_sys::fuzz_target;
+use orbital_math::sphere::calculate_amount_out_sphere;
+use orbital_math_fuzz::Input;
+
+fuzz_target!(|data: &[u8]| {
+    let mut input = Input::new(data);
+    let token_count = 2 + (input.byte() % 7) as usize;
+    // Indices may fall one past the end to cover the bounds check
+    let token_in = (input.byte() as usize) % (token_count + 1);
+    let token_out = (input.byte() as usize) % (token_count + 1);
+    let amount_in = input.u256();
+    let radius_squared = input.u256();
+    let reserves: Vec<U256> = (0..token_count).map(|_| input.u256()).collect();
+
+    // Any radius: only the absence of panics is checked
+    let _ = calculate_amount_out_sphere(&reserves, token_in, token_out, amount_in, radius_squared);
+
+    // The pool's own radius: the output is bounded by the reserve, and the
+    // rounded-up output reserve keeps the state on or outside the sphere
+    let Some(on_sphere) = sum_of_squares(&reserves) else { return };
+    let Ok(amount_out) = calculate_amount_out_sphere(&reserves, token_in, token_out, amount_in, on_sphere) else {
+        return;
+    };
+    assert!(amount_out <= reserves[token_out]);
+
+    let mut after = reserves.clone();
+    after[token_in] += amount_in;
+    after[token_out] -= amount_out;
+    if let Some(after_squares) = sum_of_squares(&after) {
+        assert!(after_squares >= on_sphere, "trade moved the pool inside its sphere");
+    }
+    if amount_in.is_zero() {
+        assert!(amount_out.is_zero());
+    }
+});
+
+fn sum_of_squares(reserves: &[U256]) -> Option<U256> {
+    reserves
+        .iter()
+        .try_fold(U256::ZERO, |acc, &r| r.checked_mul(r).and_then(|square| acc.checked_add(square)))
+}
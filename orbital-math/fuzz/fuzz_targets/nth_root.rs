@@ -0,0 +1,38 @@
+//! `nth_root` returns the largest `x` with `pow_bp(x, n_bp) <= value`
+//!
+//! Input: a 4-byte `n_bp`, then a 32-byte value. Errors are allowed only
+//! below degree 1, where `n_bp = 0` is rejected and the root may not fit in
+//! a U256.
+
+#![no_main]
+
+use alloy_primitives::U256;
+use libfuzzer_sys::fuzz_target;
+use orbital_math::{
+    fixed_point::{nth_root, pow_bp},
+    BP_PRECISION,
+};
+use orbital_math_fuzz::Input;
+
+fuzz_target!(|data: &[u8]| {
+    let mut input = Input::new(data);
+    let n_bp = input.u32();
+    let value = input.u256();
+
+    let root = match nth_root(value, n_bp) {
+        Ok(root) => root,
+        Err(e) => {
+            assert!(n_bp < BP_PRECISION, "nth_root({value}, {n_bp}) failed: {e:?}");
+            return;
+        }
+    };
+
+    let power = pow_bp(root, n_bp).expect("the root's power was computed while solving");
+    assert!(power <= value, "nth_root({value}, {n_bp}) = {root} overshoots: {power}");
+    if root < U256::MAX {
+        // The next integer either overflows or lands strictly above value
+        if let Ok(next) = pow_bp(root + U256::from(1), n_bp) {
+            assert!(next > value, "nth_root({value}, {n_bp}) = {root} undershoots");
+        }
+    }
+});
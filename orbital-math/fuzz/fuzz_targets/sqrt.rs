@@ -0,0 +1,31 @@
+//! `sqrt` and `sqrt_ceil` bound the real root over the whole U256 range
+//!
+//! Input: one 32-byte value.
+
+#![no_main]
+
+use alloy_primitives::U256;
+use libfuzzer_sys::fuzz_target;
+use orbital_math::utils::{sqrt, sqrt_ceil};
+use orbital_math_fuzz::Input;
+
+fuzz_target!(|data: &[u8]| {
+    let value = Input::new(data).u256();
+    let one = U256::from(1);
+
+    // root² <= value < (root + 1)²; the root is below 2^128 so root² fits
+    let root = sqrt(value);
+    assert!(root * root <= value, "sqrt({value}) = {root} is too large");
+    assert!(
+        (root + one).checked_mul(root + one).map_or(true, |next| next > value),
+        "sqrt({value}) = {root} is too small"
+    );
+
+    // (ceil - 1)² < value <= ceil²; ceil² only overflows when it exceeds value
+    let ceil = sqrt_ceil(value);
+    assert!(ceil.checked_mul(ceil).map_or(true, |square| square >= value), "sqrt_ceil({value}) = {ceil} is too small");
+    if !ceil.is_zero() {
+        assert!((ceil - one) * (ceil - one) < value, "sqrt_ceil({value}) = {ceil} is too large");
+    }
+    assert!(ceil - root <= one);
+});
@@ -0,0 +1,50 @@
+//! Input decoding shared by the orbital-math fuzz targets
+//!
+//! Targets read their arguments as big-endian words from the fuzzer's bytes,
+//! so every seed in `corpus/` is a plain concatenation of fixed-width values
+//! and any input decodes the same way on every run. Run a target with
+//!
+//! ```text
+//! cargo fuzz run sqrt -- -seed=1
+//! ```
+//!
+//! from `orbital-math/`, or replay only the seeds with `-runs=0`.
+
+use alloy_primitives::U256;
+
+/// Reads fixed-width big-endian values off the front of the input; reads
+/// past the end see zeros
+pub struct Input<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Input<'a> {
+    /// Decode `data`
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Next `N` bytes, zero-padded on the right
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        let len = N.min(self.data.len());
+        bytes[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        bytes
+    }
+
+    /// Next byte
+    pub fn byte(&mut self) -> u8 {
+        self.take::<1>()[0]
+    }
+
+    /// Next 4-byte word
+    pub fn u32(&mut self) -> u32 {
+        u32::from_be_bytes(self.take())
+    }
+
+    /// Next 32-byte word
+    pub fn u256(&mut self) -> U256 {
+        U256::from_be_bytes(self.take::<32>())
+    }
+}
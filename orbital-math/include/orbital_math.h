@@ -0,0 +1,31 @@
+/*
+ * C ABI of orbital-math, built with `--features ffi`.
+ *
+ * Pools are passed as snapshot JSON (see src/snapshot.rs) and amounts as
+ * decimal strings. Every function returning char* hands back a JSON string,
+ * {"ok": ...} or {"error": "..."}, owned by the caller and released with
+ * orbital_string_free.
+ */
+#ifndef ORBITAL_MATH_H
+#define ORBITAL_MATH_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+uint16_t orbital_snapshot_version(void);
+
+char *orbital_quote(const char *pool_json, size_t token_in, size_t token_out, const char *amount_in);
+
+char *orbital_marginal_price(const char *pool_json, size_t token_in, size_t token_out);
+
+void orbital_string_free(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ORBITAL_MATH_H */
@@ -6,12 +6,11 @@
 //! - MEV protection through tick design
 //! - Liquidity provider reward optimization
 
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use alloy_primitives::U256;
 use crate::{
     error::{OrbitalError, Result},
-    types::{PoolState, Tick, ReservePoint, CurveType},
-    sphere::{self, calculate_equal_price_point},
+    types::{PoolState, Tick, ReservePoint},
     ticks::{self, optimize_tick_placement, calculate_capital_efficiency},
     utils::{sum},
     PRECISION_MULTIPLIER,
@@ -133,7 +132,7 @@ impl ConcentratedLiquidityManager {
             .position(|p| p.position_id == position_id)
             .ok_or_else(|| OrbitalError::invalid_param("position_id", "position not found"))?;
         
-        let position = &mut self.positions[position_idx];
+        let position = &self.positions[position_idx];
         
         if !position.is_active {
             return Err(OrbitalError::invalid_param("position", "already inactive"));
@@ -141,6 +140,7 @@ impl ConcentratedLiquidityManager {
         
         // Calculate fees earned
         let fees_earned = self.calculate_fees_earned(position)?;
+        let position = &mut self.positions[position_idx];
         
         // Update tick liquidity
         for tick_idx in position.tick_lower..=position.tick_upper {
@@ -370,37 +370,54 @@ impl ConcentratedLiquidityManager {
 /// Recommendation for liquidity allocation
 #[derive(Debug, Clone)]
 pub struct LiquidityRecommendation {
+    /// Tick the recommendation applies to
     pub tick_index: usize,
+    /// What to do with the tick's liquidity
     pub action: LiquidityAction,
+    /// Liquidity to move
     pub amount: U256,
+    /// Efficiency expected after the change (scaled by 10000)
     pub expected_efficiency: u32,
+    /// How urgent the change is
     pub priority: Priority,
 }
 
 /// Types of liquidity actions
 #[derive(Debug, Clone)]
 pub enum LiquidityAction {
+    /// Add liquidity to the tick
     Increase,
+    /// Remove liquidity from the tick
     Decrease,
+    /// Move liquidity to other ticks
     Redistribute,
 }
 
 /// Priority levels for recommendations
 #[derive(Debug, Clone)]
 pub enum Priority {
+    /// Act now
     High,
+    /// Act soon
     Medium,
+    /// Act when convenient
     Low,
 }
 
 /// Rebalancing action for a position
 #[derive(Debug, Clone)]
 pub struct RebalanceAction {
+    /// Position to move
     pub position_id: U256,
+    /// Current lower tick
     pub old_tick_lower: usize,
+    /// Current upper tick
     pub old_tick_upper: usize,
+    /// Lower tick to move to
     pub new_tick_lower: usize,
+    /// Upper tick to move to
     pub new_tick_upper: usize,
+    /// Efficiency gained by the move (scaled by 10000)
     pub efficiency_improvement: u32,
 }
 
@@ -408,7 +425,7 @@ pub struct RebalanceAction {
 pub fn calculate_impermanent_loss(
     initial_reserves: &[U256],
     current_reserves: &[U256],
-    position: &LiquidityPosition,
+    _position: &LiquidityPosition,
 ) -> Result<U256> {
     if initial_reserves.len() != current_reserves.len() {
         return Err(OrbitalError::invalid_param(
@@ -569,9 +586,9 @@ mod tests {
             is_active: true,
         };
         
-        // Simulate fee growth
-        manager.tick_fee_growth[0] = U256::from(1000);
-        manager.tick_fee_growth[1] = U256::from(2000);
+        // Simulate fee growth, per unit of liquidity in 18-decimal fixed point
+        manager.tick_fee_growth[0] = U256::from(PRECISION_MULTIPLIER / 1000);
+        manager.tick_fee_growth[1] = U256::from(PRECISION_MULTIPLIER / 500);
         
         let fees = manager.calculate_fees_earned(&position).unwrap();
         assert!(fees > U256::ZERO);
@@ -0,0 +1,265 @@
+//! Liquidity deposit sizing for N-token sphere pools
+//!
+//! A proportional deposit scales every reserve by the same factor, so the
+//! pool stays at the same price point and R grows by that factor. Shares
+//! minted are the smallest growth any token sees, so rounding never dilutes
+//! existing LPs.
+//!
+//! A single-sided deposit of one token is sized as a zap: the token is split
+//! by each token's share of pool value, swapped along the sphere into the
+//! others, and the proceeds deposited proportionally. What the depositor
+//! loses to the swaps' price impact, measured at the marginal prices before
+//! the deposit, is the deposit's cost.
+//!
+//! Values are marginal: token i is worth r_i / r_k of numeraire k, so the
+//! whole pool is worth Σr_i² / r_k.
+
+use alloc::vec::Vec;
+use alloy_primitives::U256;
+use crate::{
+    error::{OrbitalError, Result},
+    sphere::calculate_amount_out_sphere,
+    BP_PRECISION, MAX_TOKENS, MIN_TOKENS,
+};
+
+/// Amounts to deposit and the shares they mint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProportionalDeposit {
+    /// Amount of each token
+    pub amounts: Vec<U256>,
+    /// Pool shares minted
+    pub shares: U256,
+}
+
+/// A single-token deposit broken into its implicit swaps and the
+/// proportional deposit they fund
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SingleSidedDeposit {
+    /// Input of the deposited token swapped into each other token
+    pub swaps_in: Vec<U256>,
+    /// Output each swap receives
+    pub swaps_out: Vec<U256>,
+    /// Deposit made from the swapped holdings, against the post-swap reserves
+    pub deposit: ProportionalDeposit,
+    /// Holdings beyond what the proportional deposit can use, returned to the
+    /// depositor
+    pub refund: Vec<U256>,
+    /// Value of deposit and refund in the deposited token, at pre-deposit prices
+    pub value_received: U256,
+    /// Deposited amount minus `value_received`; zero when the swaps execute
+    /// at or better than the marginal price
+    pub cost: U256,
+    /// `cost` in basis points of the deposited amount
+    pub cost_bp: u32,
+}
+
+/// Deposit worth `target_value` of token `numeraire`, in proportion to
+/// `reserves`
+///
+/// # Algorithm
+/// The pool is worth S / r_k of numeraire k, with S = Σr_i². Scaling it by
+/// λ = target_value · r_k / S adds d_i = λ · r_i of every token, rounded up.
+pub fn proportional_deposit(
+    reserves: &[U256],
+    total_shares: U256,
+    numeraire: usize,
+    target_value: U256,
+) -> Result<ProportionalDeposit> {
+    validate_reserves(reserves, numeraire)?;
+    let sum_of_squares = sum_of_squares(reserves)?;
+
+    let scaled_value = target_value
+        .checked_mul(reserves[numeraire])
+        .ok_or_else(|| OrbitalError::overflow("deposit value"))?;
+    let amounts = reserves
+        .iter()
+        .map(|&r| {
+            let amount = scaled_value
+                .checked_mul(r)
+                .ok_or_else(|| OrbitalError::overflow("deposit amount"))?;
+            Ok(div_ceil(amount, sum_of_squares))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let shares = shares_for(reserves, &amounts, total_shares)?;
+    Ok(ProportionalDeposit { amounts, shares })
+}
+
+/// Deposit `amount` of `token` alone, swapping into the other tokens first
+///
+/// Each other token i receives `amount · r_i² / S` of the input, its share
+/// of pool value, swapped in sequence along the sphere of `radius_squared`.
+pub fn single_sided_deposit(
+    reserves: &[U256],
+    radius_squared: U256,
+    total_shares: U256,
+    token: usize,
+    amount: U256,
+) -> Result<SingleSidedDeposit> {
+    validate_reserves(reserves, token)?;
+    if amount.is_zero() {
+        return Err(OrbitalError::invalid_param("amount", "must be greater than zero"));
+    }
+    let sum_of_squares = sum_of_squares(reserves)?;
+
+    let mut pool = reserves.to_vec();
+    let mut holdings = alloc::vec![U256::ZERO; reserves.len()];
+    let mut swaps_in = alloc::vec![U256::ZERO; reserves.len()];
+    let mut swaps_out = alloc::vec![U256::ZERO; reserves.len()];
+    let mut kept = amount;
+
+    for (i, &r) in reserves.iter().enumerate() {
+        if i == token {
+            continue;
+        }
+        let swap_in = amount
+            .checked_mul(r * r)
+            .ok_or_else(|| OrbitalError::overflow("swap split"))?
+            / sum_of_squares;
+        if swap_in.is_zero() {
+            continue;
+        }
+
+        let swap_out = calculate_amount_out_sphere(&pool, token, i, swap_in, radius_squared)?;
+        pool[token] += swap_in;
+        pool[i] -= swap_out;
+        kept -= swap_in;
+        swaps_in[i] = swap_in;
+        swaps_out[i] = swap_out;
+        holdings[i] = swap_out;
+    }
+    holdings[token] = kept;
+
+    // Deposit as much of the holdings as stays proportional to the pool
+    let shares = shares_for(&pool, &holdings, total_shares)?;
+    let amounts: Vec<U256> = pool
+        .iter()
+        .map(|&r| div_ceil(shares * r, total_shares))
+        .collect();
+    let refund: Vec<U256> = holdings
+        .iter()
+        .zip(&amounts)
+        .map(|(&held, &deposited)| held - deposited)
+        .collect();
+
+    // Everything the depositor ends up with, valued at pre-deposit prices
+    let value_received = holdings
+        .iter()
+        .zip(reserves)
+        .try_fold(U256::ZERO, |acc, (&held, &r)| {
+            let value = held.checked_mul(r).ok_or_else(|| OrbitalError::overflow("deposit value"))?
+                / reserves[token];
+            acc.checked_add(value).ok_or_else(|| OrbitalError::overflow("deposit value"))
+        })?;
+    let cost = amount.saturating_sub(value_received);
+    let cost_bp = (cost * U256::from(BP_PRECISION) / amount).try_into().unwrap_or(u32::MAX);
+
+    Ok(SingleSidedDeposit {
+        swaps_in,
+        swaps_out,
+        deposit: ProportionalDeposit { amounts, shares },
+        refund,
+        value_received,
+        cost,
+        cost_bp,
+    })
+}
+
+/// Shares minted for adding `amounts` to `reserves`: the smallest
+/// proportional growth across tokens, rounded down
+fn shares_for(reserves: &[U256], amounts: &[U256], total_shares: U256) -> Result<U256> {
+    if total_shares.is_zero() {
+        return Err(OrbitalError::invalid_param("total_shares", "pool has no shares to scale"));
+    }
+    reserves
+        .iter()
+        .zip(amounts)
+        .map(|(&r, &amount)| {
+            total_shares
+                .checked_mul(amount)
+                .map(|scaled| scaled / r)
+                .ok_or_else(|| OrbitalError::overflow("shares minted"))
+        })
+        .try_fold(U256::MAX, |least, shares| Ok(least.min(shares?)))
+}
+
+fn validate_reserves(reserves: &[U256], index: usize) -> Result<()> {
+    if reserves.len() < MIN_TOKENS || reserves.len() > MAX_TOKENS {
+        return Err(OrbitalError::InvalidTokenCount(reserves.len()));
+    }
+    if index >= reserves.len() {
+        return Err(OrbitalError::TokenIndexOutOfBounds {
+            index,
+            token_count: reserves.len(),
+        });
+    }
+    if let Some(token_index) = reserves.iter().position(|r| r.is_zero()) {
+        return Err(OrbitalError::ZeroReserve { token_index });
+    }
+    Ok(())
+}
+
+fn sum_of_squares(reserves: &[U256]) -> Result<U256> {
+    reserves.iter().try_fold(U256::ZERO, |acc, &r| {
+        r.checked_mul(r)
+            .and_then(|square| acc.checked_add(square))
+            .ok_or_else(|| OrbitalError::overflow("sum of squares"))
+    })
+}
+
+fn div_ceil(numerator: U256, denominator: U256) -> U256 {
+    let quotient = numerator / denominator;
+    if quotient * denominator == numerator {
+        quotient
+    } else {
+        quotient + U256::from(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scaled(values: &[u64], scale: u64) -> Vec<U256> {
+        values.iter().map(|&v| U256::from(v) * U256::from(scale)).collect()
+    }
+
+    #[test]
+    fn test_proportional_deposit_keeps_price_point() {
+        // 600² + 800² = 1000²; worth 1000²/800 = 1250 of token 1
+        let reserves = scaled(&[600, 800], 1);
+        let deposit = proportional_deposit(&reserves, U256::from(1000), 1, U256::from(125)).unwrap();
+
+        // 10% of the pool's value grows every reserve and the radius by 10%
+        assert_eq!(deposit.amounts, scaled(&[60, 80], 1));
+        assert_eq!(deposit.shares, U256::from(100));
+
+        // Rounding favours the pool: amounts up, shares down
+        let odd = proportional_deposit(&reserves, U256::from(1000), 0, U256::from(101)).unwrap();
+        assert_eq!(odd.amounts, scaled(&[37, 49], 1));
+        assert_eq!(odd.shares, U256::from(61));
+    }
+
+    #[test]
+    fn test_single_sided_deposit() {
+        let reserves = scaled(&[600, 800, 1000], 1_000_000);
+        let radius_squared = reserves.iter().fold(U256::ZERO, |acc, &r| acc + r * r);
+        let total_shares = U256::from(1_000_000_000u64);
+        let amount = U256::from(50_000_000u64);
+
+        let zap = single_sided_deposit(&reserves, radius_squared, total_shares, 0, amount).unwrap();
+
+        // The input is split by value share r_i² / S, with S = 2 · 1000²
+        assert!(zap.swaps_in[0].is_zero());
+        assert_eq!(zap.swaps_in[1], amount * U256::from(64) / U256::from(200));
+        assert_eq!(zap.swaps_in[2], amount * U256::from(100) / U256::from(200));
+        assert!(zap.deposit.shares > U256::ZERO);
+
+        // Nothing is lost: what was deposited or refunded adds up to the holdings
+        for i in 0..3 {
+            let held = if i == 0 { amount - zap.swaps_in[1] - zap.swaps_in[2] } else { zap.swaps_out[i] };
+            assert_eq!(zap.deposit.amounts[i] + zap.refund[i], held);
+        }
+        assert_eq!(zap.cost, amount.saturating_sub(zap.value_received));
+    }
+}
@@ -0,0 +1,277 @@
+//! Marginal price, price impact and depth curves
+//!
+//! Read-only views of a pool for quoting and charting: the instantaneous
+//! rate between two tokens, what a trade of a given size does to it, and a
+//! sampled curve of output against input. Every function works for both the
+//! sphere and the superellipse invariant and for any pair of an N-token pool.
+//!
+//! Prices are token_out per token_in scaled by [`PRECISION_MULTIPLIER`], as
+//! in [`calculate_price_sphere`].
+
+use alloc::vec::Vec;
+use alloy_primitives::U256;
+use crate::{
+    error::{OrbitalError, Result},
+    sphere::{calculate_amount_out_sphere, calculate_price_sphere},
+    superellipse::{calculate_amount_out_superellipse, calculate_price_superellipse},
+    types::CurveType,
+    BP_PRECISION, PRECISION_MULTIPLIER,
+};
+
+/// Effect of one trade size on the price
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceImpact {
+    /// Input amount the impact was computed for
+    pub amount_in: U256,
+    /// Output the trade receives
+    pub amount_out: U256,
+    /// Marginal price before the trade
+    pub price_before: U256,
+    /// Average price the trade gets, amount_out / amount_in
+    pub execution_price: U256,
+    /// Marginal price once the trade has executed
+    pub price_after: U256,
+    /// Deviation of the execution price from `price_before`, in basis points
+    pub impact_bp: u32,
+}
+
+/// One sample of a depth curve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepthPoint {
+    /// Input amount
+    pub amount_in: U256,
+    /// Output the input buys
+    pub amount_out: U256,
+    /// Average price at this size
+    pub execution_price: U256,
+    /// Price impact at this size, in basis points
+    pub impact_bp: u32,
+}
+
+/// Instantaneous rate of `token_out` per `token_in`
+pub fn marginal_price(
+    reserves: &[U256],
+    curve: &CurveType,
+    token_in: usize,
+    token_out: usize,
+) -> Result<U256> {
+    match curve {
+        CurveType::Sphere => calculate_price_sphere(reserves, token_in, token_out),
+        CurveType::Superellipse { u_parameter } => {
+            calculate_price_superellipse(reserves, token_in, token_out, *u_parameter)
+        }
+    }
+}
+
+/// Output of trading `amount_in` of `token_in` for `token_out`
+pub fn amount_out(
+    reserves: &[U256],
+    curve: &CurveType,
+    invariant: U256,
+    token_in: usize,
+    token_out: usize,
+    amount_in: U256,
+) -> Result<U256> {
+    match curve {
+        CurveType::Sphere => {
+            calculate_amount_out_sphere(reserves, token_in, token_out, amount_in, invariant)
+        }
+        CurveType::Superellipse { u_parameter } => calculate_amount_out_superellipse(
+            reserves,
+            token_in,
+            token_out,
+            amount_in,
+            *u_parameter,
+            invariant,
+        ),
+    }
+}
+
+/// Price impact of trading `amount_in` of `token_in` for `token_out`
+pub fn price_impact(
+    reserves: &[U256],
+    curve: &CurveType,
+    invariant: U256,
+    token_in: usize,
+    token_out: usize,
+    amount_in: U256,
+) -> Result<PriceImpact> {
+    if amount_in.is_zero() {
+        return Err(OrbitalError::invalid_param("amount_in", "must be greater than zero"));
+    }
+
+    let price_before = marginal_price(reserves, curve, token_in, token_out)?;
+    let amount_out = amount_out(reserves, curve, invariant, token_in, token_out, amount_in)?;
+    let execution_price = amount_out
+        .checked_mul(U256::from(PRECISION_MULTIPLIER))
+        .ok_or_else(|| OrbitalError::overflow("execution price"))?
+        / amount_in;
+
+    let mut after = reserves.to_vec();
+    after[token_in] = after[token_in]
+        .checked_add(amount_in)
+        .ok_or_else(|| OrbitalError::overflow("reserve_in + amount_in"))?;
+    after[token_out] -= amount_out;
+    let price_after = marginal_price(&after, curve, token_in, token_out)?;
+
+    Ok(PriceImpact {
+        amount_in,
+        amount_out,
+        price_before,
+        execution_price,
+        price_after,
+        impact_bp: deviation_bp(price_before, execution_price),
+    })
+}
+
+/// Output against input at `samples` evenly spaced sizes up to
+/// `max_amount_in`
+///
+/// The curve stops early at the first size the pool cannot fill, so its
+/// last point is the deepest trade available.
+pub fn depth_curve(
+    reserves: &[U256],
+    curve: &CurveType,
+    invariant: U256,
+    token_in: usize,
+    token_out: usize,
+    max_amount_in: U256,
+    samples: usize,
+) -> Result<Vec<DepthPoint>> {
+    if samples == 0 {
+        return Err(OrbitalError::invalid_param("samples", "must be greater than zero"));
+    }
+
+    let price_before = marginal_price(reserves, curve, token_in, token_out)?;
+    let count = U256::from(samples);
+    let mut points = Vec::with_capacity(samples);
+
+    for i in 1..=samples {
+        let amount_in = max_amount_in * U256::from(i) / count;
+        if amount_in.is_zero() {
+            continue;
+        }
+
+        let amount_out = match amount_out(reserves, curve, invariant, token_in, token_out, amount_in) {
+            Ok(amount_out) => amount_out,
+            // Past the curve's reach: the sphere underflows, the superellipse
+            // reports the shortfall
+            Err(OrbitalError::InsufficientLiquidity { .. } | OrbitalError::Underflow { .. }) => break,
+            Err(e) => return Err(e),
+        };
+        let execution_price = amount_out
+            .checked_mul(U256::from(PRECISION_MULTIPLIER))
+            .ok_or_else(|| OrbitalError::overflow("execution price"))?
+            / amount_in;
+
+        points.push(DepthPoint {
+            amount_in,
+            amount_out,
+            execution_price,
+            impact_bp: deviation_bp(price_before, execution_price),
+        });
+    }
+
+    Ok(points)
+}
+
+/// Largest input whose price impact stays within `max_impact_bp`, found by
+/// bisection up to `max_amount_in`
+pub fn max_amount_within_impact(
+    reserves: &[U256],
+    curve: &CurveType,
+    invariant: U256,
+    token_in: usize,
+    token_out: usize,
+    max_impact_bp: u32,
+    max_amount_in: U256,
+) -> Result<U256> {
+    let within = |amount_in: U256| {
+        price_impact(reserves, curve, invariant, token_in, token_out, amount_in)
+            .is_ok_and(|impact| impact.impact_bp <= max_impact_bp)
+    };
+
+    let (mut low, mut high) = (U256::ZERO, max_amount_in);
+    if within(high) {
+        return Ok(high);
+    }
+    while high - low > U256::from(1) {
+        let mid = low + ((high - low) >> 1);
+        if within(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
+/// |execution - reference| / reference in basis points, as in
+/// [`crate::sphere::calculate_price_impact`]
+pub(crate) fn deviation_bp(reference: U256, execution: U256) -> u32 {
+    if reference.is_zero() {
+        return 0;
+    }
+    let diff = if execution > reference { execution - reference } else { reference - execution };
+    let bp = diff.saturating_mul(U256::from(BP_PRECISION)) / reference;
+    bp.try_into().unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balanced_pool() -> (Vec<U256>, U256) {
+        let reserves = vec![U256::from(1_000_000u64); 3];
+        (reserves, U256::from(3_000_000_000_000u64))
+    }
+
+    #[test]
+    fn test_price_impact_grows_with_size() {
+        let (reserves, invariant) = balanced_pool();
+        let curve = &CurveType::Sphere;
+
+        assert_eq!(
+            marginal_price(&reserves, curve, 0, 1).unwrap(),
+            U256::from(PRECISION_MULTIPLIER)
+        );
+
+        let small = price_impact(&reserves, curve, invariant, 0, 1, U256::from(1_000)).unwrap();
+        let large = price_impact(&reserves, curve, invariant, 0, 1, U256::from(100_000)).unwrap();
+        assert!(small.impact_bp <= large.impact_bp);
+        // The marginal rate moves with the trade
+        assert!(large.price_after > large.price_before);
+
+        let limit = max_amount_within_impact(&reserves, curve, invariant, 0, 1, 10, U256::from(500_000)).unwrap();
+        let at_limit = price_impact(&reserves, curve, invariant, 0, 1, limit).unwrap();
+        assert!(at_limit.impact_bp <= 10);
+        assert!(price_impact(&reserves, curve, invariant, 0, 1, limit + U256::from(1)).unwrap().impact_bp > 10);
+    }
+
+    #[test]
+    fn test_depth_curve_is_monotonic() {
+        let (reserves, invariant) = balanced_pool();
+
+        for curve in &[CurveType::Sphere, CurveType::Superellipse { u_parameter: 25_000 }] {
+            let invariant = match curve {
+                CurveType::Sphere => invariant,
+                CurveType::Superellipse { u_parameter } => reserves
+                    .iter()
+                    .map(|&r| crate::fixed_point::pow_bp(r, *u_parameter).unwrap())
+                    .fold(U256::ZERO, |acc, power| acc + power),
+            };
+            let points = depth_curve(&reserves, curve, invariant, 0, 1, U256::from(300_000), 8).unwrap();
+
+            assert_eq!(points.len(), 8);
+            assert_eq!(points[7].amount_in, U256::from(300_000));
+            for pair in points.windows(2) {
+                assert!(pair[1].amount_out > pair[0].amount_out);
+                assert!(pair[1].impact_bp >= pair[0].impact_bp);
+            }
+        }
+
+        // Past the available depth the curve stops instead of failing
+        let shallow = depth_curve(&reserves, &CurveType::Sphere, invariant, 0, 1, U256::from(10_000_000), 10).unwrap();
+        assert!(shallow.len() < 10);
+    }
+}
@@ -15,83 +15,157 @@ pub enum OrbitalError {
 
     /// Reserves don't satisfy the sphere constraint
     #[error("Sphere constraint violated: sum of squares {actual} != expected {expected}")]
-    SphereConstraintViolation { actual: String, expected: String },
+    SphereConstraintViolation {
+        /// Σr_i² of the reserves
+        actual: String,
+        /// R² of the pool
+        expected: String,
+    },
 
     /// Superellipse constraint violated
     #[error("Superellipse constraint violated with u={u}")]
-    SuperellipseConstraintViolation { u: String },
+    SuperellipseConstraintViolation {
+        /// Exponent u of the curve
+        u: String,
+    },
 
     /// Zero reserve detected where positive value required
     #[error("Zero reserve for token {token_index}")]
-    ZeroReserve { token_index: usize },
+    ZeroReserve {
+        /// Index of the token with no reserve
+        token_index: usize,
+    },
 
     /// Negative reserve (should never happen with U256 but checking overflow)
     #[error("Negative reserve detected for token {token_index}")]
-    NegativeReserve { token_index: usize },
+    NegativeReserve {
+        /// Index of the token whose reserve underflowed
+        token_index: usize,
+    },
 
     /// Amount would cause reserves to violate invariant
     #[error("Trade amount {amount} would violate pool invariant")]
-    InvariantViolation { amount: String },
+    InvariantViolation {
+        /// Amount of the rejected trade
+        amount: String,
+    },
 
     /// Insufficient liquidity for requested trade
     #[error("Insufficient liquidity: needed {needed}, available {available}")]
-    InsufficientLiquidity { needed: String, available: String },
+    InsufficientLiquidity {
+        /// Amount the operation needs
+        needed: String,
+        /// Amount the pool holds
+        available: String,
+    },
 
     /// Tick boundary crossed during calculation
     #[error("Tick boundary crossed unexpectedly at tick {tick_id}")]
-    UnexpectedTickCrossing { tick_id: String },
+    UnexpectedTickCrossing {
+        /// Identifier of the crossed tick
+        tick_id: String,
+    },
 
     /// Invalid tick configuration
     #[error("Invalid tick: {reason}")]
-    InvalidTick { reason: String },
+    InvalidTick {
+        /// Why the tick is invalid
+        reason: String,
+    },
 
     /// Tick overlap detected where it shouldn't exist
     #[error("Tick {tick_a} overlaps with tick {tick_b}")]
-    TickOverlap { tick_a: String, tick_b: String },
+    TickOverlap {
+        /// First of the overlapping ticks
+        tick_a: String,
+        /// Second of the overlapping ticks
+        tick_b: String,
+    },
 
     /// Mathematical overflow in calculation
     #[error("Overflow in calculation: {operation}")]
-    Overflow { operation: String },
+    Overflow {
+        /// Operation that overflowed
+        operation: String,
+    },
 
     /// Mathematical underflow in calculation
     #[error("Underflow in calculation: {operation}")]
-    Underflow { operation: String },
+    Underflow {
+        /// Operation that underflowed
+        operation: String,
+    },
 
     /// Division by zero
     #[error("Division by zero in operation: {operation}")]
-    DivisionByZero { operation: String },
+    DivisionByZero {
+        /// Operation that divided by zero
+        operation: String,
+    },
 
     /// Square root of negative number
     #[error("Square root of negative: {value}")]
-    NegativeSquareRoot { value: String },
+    NegativeSquareRoot {
+        /// Value whose root was taken
+        value: String,
+    },
 
     /// Invalid parameter value
     #[error("Invalid parameter {param}: {reason}")]
-    InvalidParameter { param: String, reason: String },
+    InvalidParameter {
+        /// Name of the parameter
+        param: String,
+        /// Why the value is invalid
+        reason: String,
+    },
 
     /// Numerical precision loss too high
     #[error("Precision loss exceeds tolerance: {loss}%")]
-    PrecisionLoss { loss: String },
+    PrecisionLoss {
+        /// Relative loss, in percent
+        loss: String,
+    },
 
     /// Token index out of bounds
     #[error("Token index {index} out of bounds (pool has {token_count} tokens)")]
-    TokenIndexOutOfBounds { index: usize, token_count: usize },
+    TokenIndexOutOfBounds {
+        /// Index that was requested
+        index: usize,
+        /// Number of tokens in the pool
+        token_count: usize,
+    },
 
     /// Price impact too high
     #[error("Price impact {impact}% exceeds maximum allowed {max}%")]
-    ExcessivePriceImpact { impact: String, max: String },
+    ExcessivePriceImpact {
+        /// Price impact of the trade, in percent
+        impact: String,
+        /// Largest impact allowed, in percent
+        max: String,
+    },
 
     /// Slippage tolerance exceeded
     #[error("Slippage {actual}% exceeds tolerance {tolerance}%")]
-    SlippageExceeded { actual: String, tolerance: String },
+    SlippageExceeded {
+        /// Slippage of the trade
+        actual: String,
+        /// Slippage the caller accepts
+        tolerance: String,
+    },
 
     /// Cannot solve equation (e.g., quartic equation has no real positive roots)
     #[error("Cannot solve {equation}: no valid solution found")]
-    NoSolution { equation: String },
+    NoSolution {
+        /// Equation that has no solution
+        equation: String,
+    },
 
     /// Generic computation error
     #[error("Computation error: {details}")]
-    ComputationError { details: String },
+    ComputationError {
+        /// What went wrong
+        details: String,
+    },
 }
 
 impl OrbitalError {
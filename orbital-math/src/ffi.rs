@@ -0,0 +1,125 @@
+//! C ABI for non-Rust solver integrations
+//!
+//! Every call takes NUL-terminated UTF-8 strings: a [`PoolSnapshot`] as JSON
+//! and amounts as decimal strings. Every call returns a newly allocated JSON
+//! string, either `{"ok": ...}` or `{"error": "..."}`, which the caller must
+//! release with [`orbital_string_free`]. See `include/orbital_math.h`.
+
+#![allow(unsafe_code)]
+
+extern crate std;
+
+use alloc::{format, string::{String, ToString}};
+use std::ffi::{c_char, CStr, CString};
+use serde::Serialize;
+use crate::{
+    error::{OrbitalError, Result},
+    snapshot::{from_json, parse_amount, to_json, SNAPSHOT_VERSION},
+};
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Response<T> {
+    Ok(T),
+    Error(String),
+}
+
+/// Snapshot format version this build reads and writes
+#[no_mangle]
+pub extern "C" fn orbital_snapshot_version() -> u16 {
+    SNAPSHOT_VERSION
+}
+
+/// Quote `amount_in` of `token_in` for `token_out` against `pool_json`
+///
+/// # Safety
+/// `pool_json` and `amount_in` must be valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn orbital_quote(
+    pool_json: *const c_char,
+    token_in: usize,
+    token_out: usize,
+    amount_in: *const c_char,
+) -> *mut c_char {
+    respond((|| {
+        let pool = from_json(read(pool_json, "pool_json")?)?;
+        let amount_in = parse_amount("amount_in", read(amount_in, "amount_in")?)?;
+        pool.quote(token_in, token_out, amount_in)
+    })())
+}
+
+/// Marginal price of `token_out` per `token_in`, a decimal string scaled by 1e18
+///
+/// # Safety
+/// `pool_json` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn orbital_marginal_price(
+    pool_json: *const c_char,
+    token_in: usize,
+    token_out: usize,
+) -> *mut c_char {
+    respond((|| {
+        let pool = from_json(read(pool_json, "pool_json")?)?;
+        Ok(pool.marginal_price(token_in, token_out)?.to_string())
+    })())
+}
+
+/// Release a string returned by this library
+///
+/// # Safety
+/// `value` must come from this library and not have been freed; null is
+/// ignored.
+#[no_mangle]
+pub unsafe extern "C" fn orbital_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+/// # Safety
+/// `value` must be null or a valid NUL-terminated string.
+unsafe fn read<'a>(value: *const c_char, param: &str) -> Result<&'a str> {
+    if value.is_null() {
+        return Err(OrbitalError::invalid_param(param, "null pointer"));
+    }
+    CStr::from_ptr(value)
+        .to_str()
+        .map_err(|_| OrbitalError::invalid_param(param, "not UTF-8"))
+}
+
+fn respond<T: Serialize>(result: Result<T>) -> *mut c_char {
+    let response = match result {
+        Ok(value) => Response::Ok(value),
+        Err(e) => Response::Error(e.to_string()),
+    };
+    // JSON never contains a NUL byte
+    let json = to_json(&response).unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e));
+    CString::new(json).expect("JSON has no interior NUL").into_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(result: *mut c_char) -> String {
+        let json = unsafe { CStr::from_ptr(result) }.to_str().unwrap().to_string();
+        unsafe { orbital_string_free(result) };
+        json
+    }
+
+    #[test]
+    fn test_quote_through_c_abi() {
+        let pool = CString::new(r#"{"version":1,"curve":{"type":"sphere"},"reserves":["1000000","1000000"],"invariant":"2000000000000"}"#).unwrap();
+        let amount = CString::new("1000").unwrap();
+
+        let quote = call(unsafe { orbital_quote(pool.as_ptr(), 0, 1, amount.as_ptr()) });
+        assert!(quote.starts_with(r#"{"ok":{"amount_in":"1000","fee":"0","amount_out":"#), "{}", quote);
+
+        let price = call(unsafe { orbital_marginal_price(pool.as_ptr(), 0, 1) });
+        assert_eq!(price, r#"{"ok":"1000000000000000000"}"#);
+
+        let error = call(unsafe { orbital_quote(pool.as_ptr(), 0, 7, amount.as_ptr()) });
+        assert!(error.starts_with(r#"{"error":"#));
+        assert!(call(unsafe { orbital_quote(std::ptr::null(), 0, 1, amount.as_ptr()) }).contains("null pointer"));
+    }
+}
@@ -0,0 +1,202 @@
+//! Fixed-point decimals with the scale in the type
+//!
+//! Prices, fractions and rates travel as `U256` scaled by `10^decimals`, and
+//! every multiply or divide has to remember to rescale. [`Fixed`] carries the
+//! number of decimals as a const parameter so the scaling lives in one place:
+//! a `Fixed<18>` can only be combined with another `Fixed<18>`, converted
+//! with [`Fixed::rescale`], or applied to a plain integer amount with
+//! [`Fixed::mul_int`] and [`Fixed::div_into`].
+//!
+//! All arithmetic is checked and rounds down unless the name says otherwise.
+
+use core::fmt;
+use alloy_primitives::U256;
+use crate::error::{OrbitalError, Result};
+
+/// An unsigned decimal stored as `value · 10^DECIMALS`; at most 38 decimals
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed<const DECIMALS: u32>(U256);
+
+/// 18-decimal fixed point, the scale of [`crate::PRECISION_MULTIPLIER`]
+pub type Wad = Fixed<18>;
+
+impl<const DECIMALS: u32> Fixed<DECIMALS> {
+    /// `10^DECIMALS`, checked at compile time to fit in a `u128`
+    pub const SCALE: U256 = {
+        assert!(DECIMALS <= 38, "Fixed supports at most 38 decimals");
+        let scale = 10u128.pow(DECIMALS);
+        U256::from_limbs([scale as u64, (scale >> 64) as u64, 0, 0])
+    };
+
+    /// Zero
+    pub const ZERO: Self = Self(U256::ZERO);
+
+    /// One
+    pub const ONE: Self = Self(Self::SCALE);
+
+    /// Wrap an already scaled value
+    pub const fn from_raw(raw: U256) -> Self {
+        Self(raw)
+    }
+
+    /// The scaled value
+    pub const fn raw(self) -> U256 {
+        self.0
+    }
+
+    /// Whether the value is zero
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// The integer `value` as a decimal
+    pub fn from_int(value: U256) -> Result<Self> {
+        value
+            .checked_mul(Self::SCALE)
+            .map(Self)
+            .ok_or_else(|| OrbitalError::overflow("fixed from integer"))
+    }
+
+    /// `numerator / denominator` as a decimal
+    pub fn from_ratio(numerator: U256, denominator: U256) -> Result<Self> {
+        if denominator.is_zero() {
+            return Err(OrbitalError::division_by_zero("fixed ratio"));
+        }
+        numerator
+            .checked_mul(Self::SCALE)
+            .map(|scaled| Self(scaled / denominator))
+            .ok_or_else(|| OrbitalError::overflow("fixed ratio"))
+    }
+
+    /// Integer part
+    pub fn floor(self) -> U256 {
+        self.0 / Self::SCALE
+    }
+
+    /// Smallest integer not below the value
+    pub fn ceil(self) -> U256 {
+        let floor = self.floor();
+        if floor * Self::SCALE == self.0 {
+            floor
+        } else {
+            floor + U256::from(1)
+        }
+    }
+
+    /// `self + other`
+    pub fn checked_add(self, other: Self) -> Result<Self> {
+        self.0
+            .checked_add(other.0)
+            .map(Self)
+            .ok_or_else(|| OrbitalError::overflow("fixed add"))
+    }
+
+    /// `self - other`
+    pub fn checked_sub(self, other: Self) -> Result<Self> {
+        self.0
+            .checked_sub(other.0)
+            .map(Self)
+            .ok_or_else(|| OrbitalError::underflow("fixed sub"))
+    }
+
+    /// `self · other`
+    pub fn checked_mul(self, other: Self) -> Result<Self> {
+        self.0
+            .checked_mul(other.0)
+            .map(|product| Self(product / Self::SCALE))
+            .ok_or_else(|| OrbitalError::overflow("fixed mul"))
+    }
+
+    /// `self / other`
+    pub fn checked_div(self, other: Self) -> Result<Self> {
+        Self::from_ratio(self.0, other.0)
+    }
+
+    /// `value · self` for an integer `value`, rounded down
+    pub fn mul_int(self, value: U256) -> Result<U256> {
+        value
+            .checked_mul(self.0)
+            .map(|product| product / Self::SCALE)
+            .ok_or_else(|| OrbitalError::overflow("fixed mul_int"))
+    }
+
+    /// `value · self` for an integer `value`, rounded up
+    pub fn mul_int_ceil(self, value: U256) -> Result<U256> {
+        let product = value
+            .checked_mul(self.0)
+            .ok_or_else(|| OrbitalError::overflow("fixed mul_int_ceil"))?;
+        Ok(Self(product).ceil())
+    }
+
+    /// `value / self` for an integer `value`, rounded down
+    pub fn div_into(self, value: U256) -> Result<U256> {
+        if self.0.is_zero() {
+            return Err(OrbitalError::division_by_zero("fixed div_into"));
+        }
+        value
+            .checked_mul(Self::SCALE)
+            .map(|scaled| scaled / self.0)
+            .ok_or_else(|| OrbitalError::overflow("fixed div_into"))
+    }
+
+    /// The same value at `TO` decimals, rounded down when decimals are lost
+    pub fn rescale<const TO: u32>(self) -> Result<Fixed<TO>> {
+        if TO >= DECIMALS {
+            let factor = Fixed::<TO>::SCALE / Self::SCALE;
+            self.0
+                .checked_mul(factor)
+                .map(Fixed)
+                .ok_or_else(|| OrbitalError::overflow("fixed rescale"))
+        } else {
+            Ok(Fixed(self.0 / (Self::SCALE / Fixed::<TO>::SCALE)))
+        }
+    }
+}
+
+/// All decimals are written out: `Fixed::<4>::from_raw(15_000)` prints
+/// `1.5000`
+impl<const DECIMALS: u32> fmt::Display for Fixed<DECIMALS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if DECIMALS == 0 {
+            return write!(f, "{}", self.0);
+        }
+        // Below SCALE, so within u128
+        let fraction = (self.0 % Self::SCALE).to::<u128>();
+        write!(f, "{}.{:0width$}", self.floor(), fraction, width = DECIMALS as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::string::ToString;
+
+    #[test]
+    fn test_arithmetic_rounds_down() {
+        let third = Wad::from_ratio(U256::from(1), U256::from(3)).unwrap();
+        assert_eq!(third.raw(), U256::from(333_333_333_333_333_333u128));
+        assert_eq!(third.checked_mul(Wad::from_int(U256::from(3)).unwrap()).unwrap().raw(), U256::from(999_999_999_999_999_999u128));
+        assert_eq!(Wad::ONE.checked_div(third).unwrap().floor(), U256::from(3));
+
+        assert_eq!(third.mul_int(U256::from(10)).unwrap(), U256::from(3));
+        assert_eq!(third.mul_int_ceil(U256::from(10)).unwrap(), U256::from(4));
+        assert_eq!(third.div_into(U256::from(10)).unwrap(), U256::from(30));
+
+        assert!(Wad::ZERO.div_into(U256::from(1)).is_err());
+        assert!(Wad::ZERO.checked_sub(Wad::ONE).is_err());
+        assert!(Wad::from_raw(U256::MAX).checked_mul(Wad::from_int(U256::from(2)).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_rescale_and_display() {
+        let price = Fixed::<4>::from_raw(U256::from(15_000));
+        assert_eq!(price.to_string(), "1.5000");
+        assert_eq!(price.rescale::<18>().unwrap(), Wad::from_ratio(U256::from(3), U256::from(2)).unwrap());
+
+        let precise = Wad::from_ratio(U256::from(2), U256::from(3)).unwrap();
+        assert_eq!(precise.rescale::<2>().unwrap().to_string(), "0.66");
+        assert_eq!(Fixed::<0>::from_int(U256::from(7)).unwrap().to_string(), "7");
+        assert_eq!(Fixed::<6>::SCALE, U256::from(1_000_000));
+    }
+}
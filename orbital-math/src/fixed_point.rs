@@ -0,0 +1,275 @@
+//! Fixed-point powers and roots with fractional exponents
+//!
+//! The superellipse invariant Σ(|r_i|^u) = K needs `r^u` and `K^(1/u)` for
+//! non-integer `u`. Both reduce to base-2 logarithms and exponentials:
+//! `x^y = 2^(y · log2(x))`.
+//!
+//! - `log2` normalises its argument into [1, 2) by a bit scan, then reads
+//!   the fraction bit by bit through repeated squaring
+//! - `exp2` splits off the integer part as a shift and evaluates the
+//!   fraction as a Taylor series of `e^(f · ln 2)`, which for `f < 1`
+//!   converges to the last unit in under 25 terms
+//!
+//! Everything is integer arithmetic on `U256` with 18-decimal fixed point
+//! ([`WAD`]), so the routines run unchanged in `no_std` and Stylus contexts.
+//!
+//! ## Error bounds
+//!
+//! - [`log2_fixed`]: absolute error below 32 units of `1e-18`
+//! - [`pow_fixed`] and [`pow_bp`]: `|error| <= 1 + result · (1 + y) · 1e-16`
+//!   for exponent `y`, the `1` being the final floor
+//! - [`nth_root`]: exact with respect to [`pow_bp`], i.e. the largest `x`
+//!   with `pow_bp(x, n) <= value`, so a root never lands outside the curve
+//!   the powers describe
+//!
+//! Every intermediate step truncates. The bounds are checked against
+//! arbitrary-precision and floating-point references in the tests below.
+
+use alloy_primitives::U256;
+use crate::{
+    error::{OrbitalError, Result},
+    utils::pow,
+    BP_PRECISION,
+};
+
+/// One in 18-decimal fixed point
+pub const WAD: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+
+/// ln(2) in 18-decimal fixed point
+const LN2: U256 = U256::from_limbs([693_147_180_559_945_309, 0, 0, 0]);
+
+/// Base-2 logarithm of `x`, both in 18-decimal fixed point; `x >= 1`
+pub fn log2_fixed(x: U256) -> Result<U256> {
+    if x < WAD {
+        return Err(OrbitalError::invalid_param("x", "log2 is only defined here for x >= 1"));
+    }
+
+    let shift = (x / WAD).bit_len() - 1;
+    Ok(U256::from(shift) * WAD + log2_mantissa(x >> shift))
+}
+
+/// `2^x` for `x` in 18-decimal fixed point, in 18-decimal fixed point
+pub fn exp2_fixed(x: U256) -> Result<U256> {
+    let (mantissa, shift) = exp2_parts(x)?;
+    if mantissa.bit_len() + shift > 256 {
+        return Err(OrbitalError::overflow("exp2"));
+    }
+    Ok(mantissa << shift)
+}
+
+/// `base^exponent` with both in 18-decimal fixed point
+///
+/// Bases below one go through the reciprocal, `b^y = 1 / (1/b)^y`.
+pub fn pow_fixed(base: U256, exponent: U256) -> Result<U256> {
+    if exponent.is_zero() {
+        return Ok(WAD);
+    }
+    if base.is_zero() {
+        return Ok(U256::ZERO);
+    }
+
+    if base >= WAD {
+        let log = log2_fixed(base)?
+            .checked_mul(exponent)
+            .ok_or_else(|| OrbitalError::overflow("pow exponent"))?;
+        return exp2_fixed(log / WAD);
+    }
+
+    let wad_squared = WAD * WAD;
+    let inverse = pow_fixed(wad_squared / base, exponent)?;
+    Ok(wad_squared / inverse)
+}
+
+/// `base^(exponent_bp / 10000)` for an integer `base`, rounded down
+///
+/// Integer exponents are computed exactly with [`pow`]. The result is
+/// non-decreasing in `base`.
+pub fn pow_bp(base: U256, exponent_bp: u32) -> Result<U256> {
+    if exponent_bp.is_multiple_of(BP_PRECISION) {
+        return pow(base, exponent_bp / BP_PRECISION);
+    }
+    if base.is_zero() {
+        return Ok(U256::ZERO);
+    }
+
+    let log = log2_int(base) * U256::from(exponent_bp) / U256::from(BP_PRECISION);
+    let (mantissa, shift) = exp2_parts(log)?;
+    scale_down(mantissa, shift).ok_or_else(|| OrbitalError::overflow("pow_bp"))
+}
+
+/// `value^(10000 / n_bp)` rounded down, the inverse of [`pow_bp`]: the
+/// largest `x` with `pow_bp(x, n_bp) <= value`
+pub fn nth_root(value: U256, n_bp: u32) -> Result<U256> {
+    if n_bp == 0 {
+        return Err(OrbitalError::division_by_zero("nth root with n=0"));
+    }
+    if value.is_zero() {
+        return Ok(U256::ZERO);
+    }
+
+    let fits = |x: U256| pow_bp(x, n_bp).is_ok_and(|power| power <= value);
+
+    // The analytic estimate is within ~1e-16 relative of the root; bracket
+    // it and settle the last units by bisection
+    let log = log2_int(value) * U256::from(BP_PRECISION) / U256::from(n_bp);
+    let (mantissa, shift) = exp2_parts(log)?;
+    let estimate = scale_down(mantissa, shift).unwrap_or(U256::MAX);
+    let margin = (estimate >> 40usize) + U256::from(2);
+
+    let mut low = estimate.saturating_sub(margin);
+    while !low.is_zero() && !fits(low) {
+        low >>= 1usize;
+    }
+    let mut high = estimate.saturating_add(margin);
+    while fits(high) {
+        if high == U256::MAX {
+            return Ok(high);
+        }
+        high = high.saturating_mul(U256::from(2));
+    }
+
+    // pow_bp(low) <= value < pow_bp(high)
+    while high - low > U256::from(1) {
+        let mid = low + ((high - low) >> 1usize);
+        if fits(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
+/// Fractional bits of log2(y) for `y` in [1, 2) in fixed point. Squaring
+/// doubles the logarithm; whenever it reaches one, that bit is set.
+fn log2_mantissa(mut y: U256) -> U256 {
+    let two: U256 = WAD << 1usize;
+    let mut result = U256::ZERO;
+    let mut delta: U256 = WAD >> 1usize;
+    while !delta.is_zero() {
+        y = y * y / WAD;
+        if y >= two {
+            result += delta;
+            y >>= 1usize;
+        }
+        delta >>= 1usize;
+    }
+    result
+}
+
+/// log2 of an integer `value >= 1` in 18-decimal fixed point
+fn log2_int(value: U256) -> U256 {
+    let shift = value.bit_len() - 1;
+    // Keep 65 significant bits, then rescale into [1, 2) fixed point
+    let top = if shift >= 64 { value >> (shift - 64) } else { value << (64 - shift) };
+    U256::from(shift) * WAD + log2_mantissa((top * WAD) >> 64usize)
+}
+
+/// `2^x` as a fixed-point mantissa in [1, 2] and a power-of-two shift
+fn exp2_parts(x: U256) -> Result<(U256, usize)> {
+    let shift = x / WAD;
+    if shift >= U256::from(256) {
+        return Err(OrbitalError::overflow("exp2"));
+    }
+
+    // e^z with z = frac · ln 2 < 0.7; every term is truncated, so the sum
+    // never exceeds the true value
+    let z = (x % WAD) * LN2 / WAD;
+    let mut sum = WAD;
+    let mut term = WAD;
+    let mut k = 1u64;
+    loop {
+        term = term * z / (WAD * U256::from(k));
+        if term.is_zero() {
+            break;
+        }
+        sum += term;
+        k += 1;
+    }
+    Ok((sum, shift.to::<usize>()))
+}
+
+/// `mantissa · 2^shift / WAD` as an integer, `None` on overflow
+fn scale_down(mantissa: U256, shift: usize) -> Option<U256> {
+    if shift <= 128 {
+        return Some((mantissa << shift) / WAD);
+    }
+    let scaled: U256 = (mantissa << 128usize) / WAD;
+    let remaining = shift - 128;
+    (scaled.bit_len() + remaining <= 256).then(|| scaled << remaining)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use num_bigint::BigUint;
+    use proptest::prelude::*;
+
+    fn to_big(value: U256) -> BigUint {
+        BigUint::from_bytes_be(&value.to_be_bytes::<32>())
+    }
+
+    /// Whether `actual` is within the documented bound of `exact`; the f64
+    /// reference carries a few ulps of its own error on top, plus the
+    /// rounding of its exponent, which `powf` amplifies by `ln(exact)`
+    fn within_bound(actual: U256, exact: f64, exponent: f64) -> bool {
+        let actual = u256_to_f64(actual);
+        let reference_error = exact * (4.0 + exact.ln().abs()) * f64::EPSILON;
+        (actual - exact).abs() <= 1.0 + exact * (1.0 + exponent) * 1e-16 + reference_error
+    }
+
+    fn u256_to_f64(value: U256) -> f64 {
+        value.as_limbs().iter().rev().fold(0.0, |acc, &limb| acc * 18446744073709551616.0 + limb as f64)
+    }
+
+    #[test]
+    fn test_exact_points() {
+        assert_eq!(log2_fixed(WAD).unwrap(), U256::ZERO);
+        assert_eq!(log2_fixed(WAD * U256::from(8)).unwrap(), U256::from(3) * WAD);
+        assert!(log2_fixed(WAD - U256::from(1)).is_err());
+
+        assert_eq!(exp2_fixed(U256::ZERO).unwrap(), WAD);
+        assert_eq!(exp2_fixed(U256::from(5) * WAD).unwrap(), U256::from(32) * WAD);
+        assert!(exp2_fixed(U256::from(300) * WAD).is_err());
+
+        assert_eq!(pow_fixed(WAD * U256::from(4), WAD / U256::from(2)).unwrap(), WAD * U256::from(2));
+        assert_eq!(pow_bp(U256::from(10), 30000).unwrap(), U256::from(1000));
+        // 10^2.5 = 316.22...
+        assert_eq!(pow_bp(U256::from(10), 25000).unwrap(), U256::from(316));
+        assert_eq!(nth_root(U256::from(315), 25000).unwrap(), U256::from(9));
+        assert_eq!(nth_root(U256::from(316), 25000).unwrap(), U256::from(10));
+        assert_eq!(nth_root(U256::MAX, 20000).unwrap(), U256::from(u128::MAX));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_pow_fixed_error_bound(base in 1u64..u64::MAX, exponent in 0u64..3_000_000_000_000_000_000) {
+            let result = pow_fixed(U256::from(base), U256::from(exponent)).unwrap();
+            let (b, y) = (base as f64 / 1e18, exponent as f64 / 1e18);
+            prop_assert!(within_bound(result, b.powf(y) * 1e18, y));
+        }
+
+        #[test]
+        fn prop_pow_bp_error_bound(base in 1u64..1_000_000_000_000_000_000, exponent_bp in 10_000u32..40_000) {
+            let result = pow_bp(U256::from(base), exponent_bp).unwrap();
+            let y = exponent_bp as f64 / 1e4;
+            prop_assert!(within_bound(result, (base as f64).powf(y), y));
+            prop_assert!(result <= pow_bp(U256::from(base + 1), exponent_bp).unwrap());
+        }
+
+        /// The root is the exact floor inverse of `pow_bp`, and matches the
+        /// arbitrary-precision integer root for whole exponents
+        #[test]
+        fn prop_nth_root_inverts_pow(value in any::<[u8; 20]>(), n_bp in prop::sample::select(&[15_000u32, 20_000, 22_000, 25_000, 28_000, 30_000][..])) {
+            let value = U256::from_be_slice(&value);
+            let root = nth_root(value, n_bp).unwrap();
+            prop_assert!(pow_bp(root, n_bp).unwrap() <= value);
+            prop_assert!(pow_bp(root + U256::from(1), n_bp).unwrap() > value);
+
+            if n_bp.is_multiple_of(BP_PRECISION) {
+                prop_assert_eq!(to_big(root), to_big(value).nth_root(n_bp / BP_PRECISION));
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Impermanent loss and LP profit and loss
+//!
+//! An LP's holdings move with the pool; holding the entry holdings instead
+//! would have left them untouched. Impermanent loss compares the two at
+//! today's prices: `IL = 1 - V_lp / V_hodl`.
+//!
+//! Prices are marginal, taken from the current reserves in units of a chosen
+//! numeraire, so the same code covers any token count and either curve.
+//! Because both values scale with the position, the reserve vectors can be
+//! the whole pool or the position's pro-rata slice of it.
+//!
+//! On the sphere, token i is worth `c_i / c_k` of numeraire k, so
+//! `V_lp = |c|² / c_k` and `V_hodl = (e · c) / c_k`, giving the closed form
+//! `IL = 1 - |c|² / (e · c)` for entry reserves `e` and current reserves `c`.
+//! While the pool stays on one sphere, `e · c <= |c|²` and the LP is never
+//! behind holding; the figure turns positive once liquidity has been taken
+//! out or the curve is flatter than the sphere.
+
+use alloc::vec::Vec;
+use alloy_primitives::U256;
+use crate::{
+    depth::marginal_price,
+    error::{OrbitalError, Result},
+    types::CurveType,
+    BP_PRECISION, PRECISION_MULTIPLIER,
+};
+
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Value of an LP position against holding its entry tokens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImpermanentLoss {
+    /// Entry holdings at current prices
+    pub hodl_value: U256,
+    /// Current holdings at current prices
+    pub lp_value: U256,
+    /// Fees earned at current prices
+    pub fee_value: U256,
+    /// `1 - lp / hodl` in basis points; negative when the LP is ahead
+    pub il_bp: i32,
+    /// `1 - (lp + fees) / hodl` in basis points
+    pub net_il_bp: i32,
+}
+
+impl ImpermanentLoss {
+    /// Net profit over holding, in numeraire units, with fees
+    pub fn net_gain(&self) -> U256 {
+        (self.lp_value + self.fee_value).saturating_sub(self.hodl_value)
+    }
+
+    /// Net shortfall against holding, in numeraire units, with fees
+    pub fn net_loss(&self) -> U256 {
+        self.hodl_value.saturating_sub(self.lp_value + self.fee_value)
+    }
+}
+
+/// Impermanent loss between `entry` and `current` reserves, valued in token
+/// `numeraire`, offset by `fees` earned in each token (empty for none)
+pub fn impermanent_loss(
+    entry: &[U256],
+    current: &[U256],
+    curve: &CurveType,
+    numeraire: usize,
+    fees: &[U256],
+) -> Result<ImpermanentLoss> {
+    if entry.len() != current.len() || (!fees.is_empty() && fees.len() != current.len()) {
+        return Err(OrbitalError::invalid_param("reserves", "entry, current and fees must have one entry per token"));
+    }
+    if numeraire >= current.len() {
+        return Err(OrbitalError::TokenIndexOutOfBounds {
+            index: numeraire,
+            token_count: current.len(),
+        });
+    }
+
+    let prices = (0..current.len())
+        .map(|i| {
+            if i == numeraire {
+                Ok(U256::from(PRECISION_MULTIPLIER))
+            } else {
+                marginal_price(current, curve, i, numeraire)
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let hodl_value = value_of(entry, &prices)?;
+    let lp_value = value_of(current, &prices)?;
+    let fee_value = if fees.is_empty() { U256::ZERO } else { value_of(fees, &prices)? };
+    if hodl_value.is_zero() {
+        return Err(OrbitalError::invalid_param("entry", "position had no value"));
+    }
+
+    Ok(ImpermanentLoss {
+        hodl_value,
+        lp_value,
+        fee_value,
+        il_bp: shortfall_bp(hodl_value, lp_value),
+        net_il_bp: shortfall_bp(hodl_value, lp_value + fee_value),
+    })
+}
+
+/// A position's pro-rata slice of the pool reserves
+pub fn position_holdings(reserves: &[U256], shares: U256, total_shares: U256) -> Result<Vec<U256>> {
+    if total_shares.is_zero() {
+        return Err(OrbitalError::division_by_zero("position holdings"));
+    }
+    reserves
+        .iter()
+        .map(|&r| {
+            r.checked_mul(shares)
+                .map(|scaled| scaled / total_shares)
+                .ok_or_else(|| OrbitalError::overflow("position holdings"))
+        })
+        .collect()
+}
+
+/// Annualized fee yield, in basis points of the entry value, that would
+/// have offset `il` over `elapsed_seconds`; zero when the LP is not behind
+pub fn breakeven_fee_apr_bp(il: &ImpermanentLoss, elapsed_seconds: u64) -> Result<u32> {
+    if elapsed_seconds == 0 {
+        return Err(OrbitalError::invalid_param("elapsed_seconds", "must be greater than zero"));
+    }
+    if il.il_bp <= 0 {
+        return Ok(0);
+    }
+
+    let apr = il.il_bp as u64 * SECONDS_PER_YEAR / elapsed_seconds;
+    Ok(apr.try_into().unwrap_or(u32::MAX))
+}
+
+/// Σ amount_i · price_i, prices in [`PRECISION_MULTIPLIER`] units
+fn value_of(amounts: &[U256], prices: &[U256]) -> Result<U256> {
+    amounts.iter().zip(prices).try_fold(U256::ZERO, |acc, (&amount, &price)| {
+        amount
+            .checked_mul(price)
+            .map(|value| value / U256::from(PRECISION_MULTIPLIER))
+            .and_then(|value| acc.checked_add(value))
+            .ok_or_else(|| OrbitalError::overflow("position value"))
+    })
+}
+
+/// `1 - actual / reference` in basis points, signed
+fn shortfall_bp(reference: U256, actual: U256) -> i32 {
+    let bp = |diff: U256| -> i32 {
+        (diff.saturating_mul(U256::from(BP_PRECISION)) / reference)
+            .try_into()
+            .unwrap_or(i32::MAX)
+    };
+    if actual <= reference {
+        bp(reference - actual)
+    } else {
+        -bp(actual - reference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixed_point::pow_bp, utils::sqrt};
+    use proptest::prelude::*;
+
+    const RADIUS: u64 = 1_000_000_000_000;
+
+    /// The point of the circle of radius [`RADIUS`] with first coordinate `x`
+    fn on_circle(x: u64) -> Vec<U256> {
+        let radius = U256::from(RADIUS);
+        let x = U256::from(x);
+        alloc::vec![x, sqrt(radius * radius - x * x)]
+    }
+
+    fn to_f64(values: &[U256]) -> Vec<f64> {
+        values.iter().map(|v| v.to::<u128>() as f64).collect()
+    }
+
+    #[test]
+    fn test_fees_offset_loss() {
+        let curve = CurveType::Superellipse { u_parameter: 25_000 };
+        let entry = alloc::vec![U256::from(1_000_000u64); 3];
+        let unchanged = impermanent_loss(&entry, &entry, &curve, 0, &[]).unwrap();
+        assert_eq!(unchanged.il_bp, 0);
+        assert_eq!(unchanged.hodl_value, U256::from(3_000_000u64));
+
+        // Liquidity taken out of token 0 leaves the LP behind holding
+        let current = alloc::vec![U256::from(900_000u64), U256::from(1_000_000u64), U256::from(1_000_000u64)];
+        let il = impermanent_loss(&entry, &current, &curve, 0, &[]).unwrap();
+        assert!(il.il_bp > 0);
+        assert!(il.net_loss() > U256::ZERO);
+
+        // Fees worth the shortfall close the gap
+        let fees = alloc::vec![il.hodl_value - il.lp_value, U256::ZERO, U256::ZERO];
+        let covered = impermanent_loss(&entry, &current, &curve, 0, &fees).unwrap();
+        assert_eq!(covered.il_bp, il.il_bp);
+        assert_eq!(covered.net_il_bp, 0);
+        assert_eq!(covered.net_loss(), U256::ZERO);
+
+        // 50 bp lost in 30 days needs fees of 50 · 365 / 30 bp a year
+        let loss = ImpermanentLoss { il_bp: 50, ..il };
+        assert_eq!(breakeven_fee_apr_bp(&loss, 30 * 24 * 60 * 60).unwrap(), 608);
+        assert_eq!(breakeven_fee_apr_bp(&unchanged, 1).unwrap(), 0);
+
+        let half = position_holdings(&current, U256::from(1), U256::from(2)).unwrap();
+        assert_eq!(half[0], U256::from(450_000u64));
+    }
+
+    proptest! {
+        /// Two-token sphere: IL = 1 - |c|² / (e · c)
+        #[test]
+        fn prop_sphere_matches_closed_form(
+            x0 in RADIUS / 10..RADIUS * 99 / 100,
+            x1 in RADIUS / 10..RADIUS * 99 / 100,
+            numeraire in 0usize..2,
+        ) {
+            let (entry, current) = (on_circle(x0), on_circle(x1));
+            let il = impermanent_loss(&entry, &current, &CurveType::Sphere, numeraire, &[]).unwrap();
+
+            let (e, c) = (to_f64(&entry), to_f64(&current));
+            let expected = (1.0 - (c[0] * c[0] + c[1] * c[1]) / (e[0] * c[0] + e[1] * c[1])) * 1e4;
+            prop_assert!((il.il_bp as f64 - expected).abs() <= 1.0);
+            prop_assert!(il.il_bp <= 0);
+        }
+
+        /// Two-token superellipse: IL = 1 - Σc_i^u / Σ e_i · c_i^(u-1)
+        #[test]
+        fn prop_superellipse_matches_closed_form(
+            entry in prop::array::uniform2(1_000_000u64..10_000_000),
+            current in prop::array::uniform2(1_000_000u64..10_000_000),
+        ) {
+            let u = 25_000;
+            let entry: Vec<U256> = entry.iter().map(|&r| U256::from(r)).collect();
+            let current: Vec<U256> = current.iter().map(|&r| U256::from(r)).collect();
+            let il = impermanent_loss(&entry, &current, &CurveType::Superellipse { u_parameter: u }, 1, &[]).unwrap();
+
+            let (e, c) = (to_f64(&entry), to_f64(&current));
+            let k: f64 = current.iter().map(|&r| pow_bp(r, u).unwrap().to::<u128>() as f64).sum();
+            let weighted: f64 = e.iter().zip(&c).map(|(e, c)| e * c.powf(1.5)).sum();
+            let expected = (1.0 - k / weighted) * 1e4;
+            prop_assert!((il.il_bp as f64 - expected).abs() <= 1.0);
+        }
+    }
+}
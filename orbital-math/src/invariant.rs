@@ -0,0 +1,564 @@
+//! Trading invariants behind a common trait
+//!
+//! [`Invariant`] is what quoting and trade code needs from a curve: the
+//! invariant value of a reserve state, the output of a trade that keeps it,
+//! and the marginal price. The sphere and superellipse of [`CurveType`] sit
+//! beside a constant-product (Π r_i = K) and a StableSwap invariant, and
+//! [`CurveType`] itself implements the trait, so existing pools plug into
+//! anything written against it.
+//!
+//! [`CurveRegistry`] builds invariants by name and parameter, for pools
+//! configured from strings, and [`compare_execution`] runs one trade against
+//! several invariants from the same reserves.
+//!
+//! Every implementation rounds outputs down, in the pool's favour, and
+//! prices token_out per token_in scaled by [`PRECISION_MULTIPLIER`].
+
+use alloc::{boxed::Box, collections::BTreeMap, string::ToString, vec::Vec};
+use core::fmt::Debug;
+use alloy_primitives::U256;
+use crate::{
+    depth::{self, deviation_bp},
+    error::{OrbitalError, Result},
+    fixed_point::pow_bp,
+    types::CurveType,
+    PRECISION_MULTIPLIER,
+};
+
+/// Iteration cap for the StableSwap Newton solvers
+const STABLE_SWAP_ITERATIONS: usize = 255;
+
+/// A curve every reserve state of a pool must stay on
+pub trait Invariant: Debug + Send + Sync {
+    /// Registry name of the curve
+    fn name(&self) -> &'static str;
+
+    /// Invariant value of `reserves`
+    fn invariant(&self, reserves: &[U256]) -> Result<U256>;
+
+    /// Output of trading `amount_in` of `token_in` for `token_out` while
+    /// keeping `invariant`
+    fn amount_out(
+        &self,
+        reserves: &[U256],
+        invariant: U256,
+        token_in: usize,
+        token_out: usize,
+        amount_in: U256,
+    ) -> Result<U256>;
+
+    /// Instantaneous rate of `token_out` per `token_in`
+    fn marginal_price(&self, reserves: &[U256], token_in: usize, token_out: usize) -> Result<U256>;
+}
+
+/// Σ r_i² = R²
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sphere;
+
+/// Σ r_i^u = K, with u in basis points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Superellipse {
+    /// u in basis points
+    pub u_parameter: u32,
+}
+
+/// Π r_i = K
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ConstantProduct;
+
+/// Curve's StableSwap invariant with amplification A:
+/// A·nⁿ·Σx + D = A·D·nⁿ + Dⁿ⁺¹ / (nⁿ·Πx)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StableSwap {
+    /// Amplification coefficient A
+    pub amplification: u32,
+}
+
+impl Invariant for CurveType {
+    fn name(&self) -> &'static str {
+        match self {
+            CurveType::Sphere => Sphere.name(),
+            CurveType::Superellipse { u_parameter } => Superellipse { u_parameter: *u_parameter }.name(),
+        }
+    }
+
+    fn invariant(&self, reserves: &[U256]) -> Result<U256> {
+        match self {
+            CurveType::Sphere => Sphere.invariant(reserves),
+            CurveType::Superellipse { u_parameter } => Superellipse { u_parameter: *u_parameter }.invariant(reserves),
+        }
+    }
+
+    fn amount_out(
+        &self,
+        reserves: &[U256],
+        invariant: U256,
+        token_in: usize,
+        token_out: usize,
+        amount_in: U256,
+    ) -> Result<U256> {
+        depth::amount_out(reserves, self, invariant, token_in, token_out, amount_in)
+    }
+
+    fn marginal_price(&self, reserves: &[U256], token_in: usize, token_out: usize) -> Result<U256> {
+        depth::marginal_price(reserves, self, token_in, token_out)
+    }
+}
+
+impl Invariant for Sphere {
+    fn name(&self) -> &'static str {
+        "sphere"
+    }
+
+    fn invariant(&self, reserves: &[U256]) -> Result<U256> {
+        reserves.iter().try_fold(U256::ZERO, |acc, &r| {
+            r.checked_mul(r)
+                .and_then(|square| acc.checked_add(square))
+                .ok_or_else(|| OrbitalError::overflow("sum of squares"))
+        })
+    }
+
+    fn amount_out(
+        &self,
+        reserves: &[U256],
+        invariant: U256,
+        token_in: usize,
+        token_out: usize,
+        amount_in: U256,
+    ) -> Result<U256> {
+        depth::amount_out(reserves, &CurveType::Sphere, invariant, token_in, token_out, amount_in)
+    }
+
+    fn marginal_price(&self, reserves: &[U256], token_in: usize, token_out: usize) -> Result<U256> {
+        depth::marginal_price(reserves, &CurveType::Sphere, token_in, token_out)
+    }
+}
+
+impl Superellipse {
+    fn curve(&self) -> CurveType {
+        CurveType::Superellipse { u_parameter: self.u_parameter }
+    }
+}
+
+impl Invariant for Superellipse {
+    fn name(&self) -> &'static str {
+        "superellipse"
+    }
+
+    fn invariant(&self, reserves: &[U256]) -> Result<U256> {
+        reserves.iter().try_fold(U256::ZERO, |acc, &r| {
+            acc.checked_add(pow_bp(r, self.u_parameter)?)
+                .ok_or_else(|| OrbitalError::overflow("sum of powers"))
+        })
+    }
+
+    fn amount_out(
+        &self,
+        reserves: &[U256],
+        invariant: U256,
+        token_in: usize,
+        token_out: usize,
+        amount_in: U256,
+    ) -> Result<U256> {
+        depth::amount_out(reserves, &self.curve(), invariant, token_in, token_out, amount_in)
+    }
+
+    fn marginal_price(&self, reserves: &[U256], token_in: usize, token_out: usize) -> Result<U256> {
+        depth::marginal_price(reserves, &self.curve(), token_in, token_out)
+    }
+}
+
+impl Invariant for ConstantProduct {
+    fn name(&self) -> &'static str {
+        "constant_product"
+    }
+
+    fn invariant(&self, reserves: &[U256]) -> Result<U256> {
+        validate_reserves(reserves)?;
+        reserves.iter().try_fold(U256::from(1), |acc, &r| {
+            acc.checked_mul(r).ok_or_else(|| OrbitalError::overflow("product of reserves"))
+        })
+    }
+
+    fn amount_out(
+        &self,
+        reserves: &[U256],
+        invariant: U256,
+        token_in: usize,
+        token_out: usize,
+        amount_in: U256,
+    ) -> Result<U256> {
+        validate_pair(reserves, token_in, token_out)?;
+        validate_reserves(reserves)?;
+        if amount_in.is_zero() {
+            return Ok(U256::ZERO);
+        }
+
+        let new_reserve_in = reserves[token_in]
+            .checked_add(amount_in)
+            .ok_or_else(|| OrbitalError::overflow("reserve_in + amount_in"))?;
+        let others = reserves
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != token_out)
+            .try_fold(U256::from(1), |acc, (i, &r)| {
+                let r = if i == token_in { new_reserve_in } else { r };
+                acc.checked_mul(r).ok_or_else(|| OrbitalError::overflow("product of reserves"))
+            })?;
+
+        // new_r_out = ⌈K / Π(r_k for k ≠ out)⌉
+        let new_reserve_out = invariant.div_ceil(others);
+        reserves[token_out]
+            .checked_sub(new_reserve_out)
+            .ok_or_else(|| insufficient(reserves, token_out, amount_in))
+    }
+
+    fn marginal_price(&self, reserves: &[U256], token_in: usize, token_out: usize) -> Result<U256> {
+        validate_pair(reserves, token_in, token_out)?;
+        validate_reserves(reserves)?;
+        ratio(reserves[token_out], reserves[token_in])
+    }
+}
+
+impl StableSwap {
+    /// A·nⁿ
+    fn ann(&self, n: usize) -> Result<U256> {
+        if self.amplification == 0 {
+            return Err(OrbitalError::invalid_param("amplification", "must be greater than zero"));
+        }
+        let n = U256::from(n);
+        n.checked_pow(n)
+            .and_then(|nn| nn.checked_mul(U256::from(self.amplification)))
+            .ok_or_else(|| OrbitalError::overflow("A·n^n"))
+    }
+
+    /// Dⁿ⁺¹ / (nⁿ·Πx), accumulated one reserve at a time
+    fn d_product(d: U256, reserves: &[U256]) -> Result<U256> {
+        let n = U256::from(reserves.len());
+        reserves.iter().try_fold(d, |acc, &x| {
+            acc.checked_mul(d)
+                .ok_or_else(|| OrbitalError::overflow("StableSwap D product"))
+                .map(|v| v / (x * n))
+        })
+    }
+
+    /// Solve the invariant for the reserve of `token_out` given every other
+    /// reserve in `reserves`
+    fn solve_reserve(&self, reserves: &[U256], d: U256, token_out: usize) -> Result<U256> {
+        let n = U256::from(reserves.len());
+        let ann = self.ann(reserves.len())?;
+
+        let mut c = d;
+        let mut sum = U256::ZERO;
+        for (k, &x) in reserves.iter().enumerate() {
+            if k == token_out {
+                continue;
+            }
+            sum += x;
+            c = c.checked_mul(d).ok_or_else(|| OrbitalError::overflow("StableSwap c"))? / (x * n);
+        }
+        c = c.checked_mul(d).ok_or_else(|| OrbitalError::overflow("StableSwap c"))? / (ann * n);
+        let b = sum + d / ann;
+
+        let mut y = d;
+        for _ in 0..STABLE_SWAP_ITERATIONS {
+            let previous = y;
+            let numerator = y
+                .checked_mul(y)
+                .and_then(|yy| yy.checked_add(c))
+                .ok_or_else(|| OrbitalError::overflow("StableSwap y"))?;
+            let denominator = (y + y + b)
+                .checked_sub(d)
+                .filter(|v| !v.is_zero())
+                .ok_or_else(|| OrbitalError::computation("StableSwap y diverged"))?;
+            y = numerator / denominator;
+            if abs_diff(y, previous) <= U256::from(1) {
+                return Ok(y);
+            }
+        }
+        Err(OrbitalError::computation("StableSwap y did not converge"))
+    }
+}
+
+impl Invariant for StableSwap {
+    fn name(&self) -> &'static str {
+        "stable_swap"
+    }
+
+    fn invariant(&self, reserves: &[U256]) -> Result<U256> {
+        validate_reserves(reserves)?;
+        let n = U256::from(reserves.len());
+        let ann = self.ann(reserves.len())?;
+        let sum = reserves.iter().try_fold(U256::ZERO, |acc, &x| {
+            acc.checked_add(x).ok_or_else(|| OrbitalError::overflow("sum of reserves"))
+        })?;
+
+        let mut d = sum;
+        for _ in 0..STABLE_SWAP_ITERATIONS {
+            let d_product = Self::d_product(d, reserves)?;
+            let previous = d;
+            let numerator = ann
+                .checked_mul(sum)
+                .and_then(|v| v.checked_add(d_product * n))
+                .and_then(|v| v.checked_mul(d))
+                .ok_or_else(|| OrbitalError::overflow("StableSwap D"))?;
+            let denominator = (ann - U256::from(1))
+                .checked_mul(d)
+                .and_then(|v| v.checked_add((n + U256::from(1)) * d_product))
+                .ok_or_else(|| OrbitalError::overflow("StableSwap D"))?;
+            d = numerator / denominator;
+            if abs_diff(d, previous) <= U256::from(1) {
+                return Ok(d);
+            }
+        }
+        Err(OrbitalError::computation("StableSwap D did not converge"))
+    }
+
+    fn amount_out(
+        &self,
+        reserves: &[U256],
+        invariant: U256,
+        token_in: usize,
+        token_out: usize,
+        amount_in: U256,
+    ) -> Result<U256> {
+        validate_pair(reserves, token_in, token_out)?;
+        validate_reserves(reserves)?;
+        if amount_in.is_zero() {
+            return Ok(U256::ZERO);
+        }
+
+        let mut after = reserves.to_vec();
+        after[token_in] = after[token_in]
+            .checked_add(amount_in)
+            .ok_or_else(|| OrbitalError::overflow("reserve_in + amount_in"))?;
+        let new_reserve_out = self.solve_reserve(&after, invariant, token_out)?;
+
+        // One unit off absorbs the solver's rounding, as StableSwap pools do
+        reserves[token_out]
+            .checked_sub(new_reserve_out)
+            .and_then(|out| out.checked_sub(U256::from(1)))
+            .ok_or_else(|| insufficient(reserves, token_out, amount_in))
+    }
+
+    fn marginal_price(&self, reserves: &[U256], token_in: usize, token_out: usize) -> Result<U256> {
+        validate_pair(reserves, token_in, token_out)?;
+        let d = self.invariant(reserves)?;
+        let ann = self.ann(reserves.len())?;
+        let d_product = Self::d_product(d, reserves)?;
+
+        // ∂F/∂x_k = A·nⁿ + Dⁿ⁺¹ / (nⁿ·Πx·x_k), and the price is ∂F/∂x_in / ∂F/∂x_out
+        let (x_in, x_out) = (reserves[token_in], reserves[token_out]);
+        let numerator = ann
+            .checked_mul(x_in)
+            .and_then(|v| v.checked_add(d_product))
+            .and_then(|v| v.checked_mul(x_out))
+            .ok_or_else(|| OrbitalError::overflow("StableSwap price"))?;
+        let denominator = ann
+            .checked_mul(x_out)
+            .and_then(|v| v.checked_add(d_product))
+            .and_then(|v| v.checked_mul(x_in))
+            .ok_or_else(|| OrbitalError::overflow("StableSwap price"))?;
+        ratio(numerator, denominator)
+    }
+}
+
+/// Builds an invariant from a single numeric parameter
+pub type InvariantConstructor = fn(u32) -> Result<Box<dyn Invariant>>;
+
+/// Invariants by name
+#[derive(Debug, Clone)]
+pub struct CurveRegistry {
+    constructors: BTreeMap<&'static str, InvariantConstructor>,
+}
+
+impl Default for CurveRegistry {
+    /// Registry of the built-in curves. The parameter is u in basis points
+    /// for `superellipse`, A for `stable_swap`, and ignored otherwise.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register("sphere", |_| Ok(Box::new(Sphere)));
+        registry.register("superellipse", |u_parameter| {
+            if u_parameter < 10_000 {
+                return Err(OrbitalError::invalid_param("u_parameter", "must be at least 1.0"));
+            }
+            Ok(Box::new(Superellipse { u_parameter }))
+        });
+        registry.register("constant_product", |_| Ok(Box::new(ConstantProduct)));
+        registry.register("stable_swap", |amplification| {
+            if amplification == 0 {
+                return Err(OrbitalError::invalid_param("amplification", "must be greater than zero"));
+            }
+            Ok(Box::new(StableSwap { amplification }))
+        });
+        registry
+    }
+}
+
+impl CurveRegistry {
+    /// Registry with no curves
+    pub fn empty() -> Self {
+        Self { constructors: BTreeMap::new() }
+    }
+
+    /// Add or replace the curve called `name`
+    pub fn register(&mut self, name: &'static str, constructor: InvariantConstructor) {
+        self.constructors.insert(name, constructor);
+    }
+
+    /// Build the curve called `name`
+    pub fn build(&self, name: &str, parameter: u32) -> Result<Box<dyn Invariant>> {
+        let constructor = self
+            .constructors
+            .get(name)
+            .ok_or_else(|| OrbitalError::invalid_param("curve", "unknown curve name"))?;
+        constructor(parameter)
+    }
+
+    /// Registered names, sorted
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.constructors.keys().copied()
+    }
+}
+
+/// One trade on one invariant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Execution {
+    /// Registry name of the curve
+    pub curve: &'static str,
+    /// Output the trade receives
+    pub amount_out: U256,
+    /// Marginal price before the trade
+    pub price_before: U256,
+    /// Average price the trade gets, amount_out / amount_in
+    pub execution_price: U256,
+    /// Deviation of the execution price from `price_before`, in basis points
+    pub impact_bp: u32,
+}
+
+/// Run one trade against each of `curves`, every pool holding `reserves`
+/// and sitting on its own curve
+pub fn compare_execution(
+    curves: &[&dyn Invariant],
+    reserves: &[U256],
+    token_in: usize,
+    token_out: usize,
+    amount_in: U256,
+) -> Vec<Result<Execution>> {
+    curves
+        .iter()
+        .map(|curve| execute(*curve, reserves, token_in, token_out, amount_in))
+        .collect()
+}
+
+fn execute(
+    curve: &dyn Invariant,
+    reserves: &[U256],
+    token_in: usize,
+    token_out: usize,
+    amount_in: U256,
+) -> Result<Execution> {
+    if amount_in.is_zero() {
+        return Err(OrbitalError::invalid_param("amount_in", "must be greater than zero"));
+    }
+    let invariant = curve.invariant(reserves)?;
+    let price_before = curve.marginal_price(reserves, token_in, token_out)?;
+    let amount_out = curve.amount_out(reserves, invariant, token_in, token_out, amount_in)?;
+    let execution_price = ratio(amount_out, amount_in)?;
+
+    Ok(Execution {
+        curve: curve.name(),
+        amount_out,
+        price_before,
+        execution_price,
+        impact_bp: deviation_bp(price_before, execution_price),
+    })
+}
+
+/// `numerator / denominator` scaled by [`PRECISION_MULTIPLIER`]
+fn ratio(numerator: U256, denominator: U256) -> Result<U256> {
+    if denominator.is_zero() {
+        return Err(OrbitalError::division_by_zero("price ratio"));
+    }
+    numerator
+        .checked_mul(U256::from(PRECISION_MULTIPLIER))
+        .map(|scaled| scaled / denominator)
+        .ok_or_else(|| OrbitalError::overflow("price ratio"))
+}
+
+fn validate_pair(reserves: &[U256], token_in: usize, token_out: usize) -> Result<()> {
+    if token_in >= reserves.len() || token_out >= reserves.len() {
+        return Err(OrbitalError::TokenIndexOutOfBounds {
+            index: token_in.max(token_out),
+            token_count: reserves.len(),
+        });
+    }
+    if token_in == token_out {
+        return Err(OrbitalError::invalid_param("tokens", "input and output must be different"));
+    }
+    Ok(())
+}
+
+/// Product-based invariants are undefined at a zero reserve
+fn validate_reserves(reserves: &[U256]) -> Result<()> {
+    if let Some(token_index) = reserves.iter().position(|r| r.is_zero()) {
+        return Err(OrbitalError::ZeroReserve { token_index });
+    }
+    Ok(())
+}
+
+fn insufficient(reserves: &[U256], token_out: usize, amount_in: U256) -> OrbitalError {
+    OrbitalError::InsufficientLiquidity {
+        needed: amount_in.to_string(),
+        available: reserves[token_out].to_string(),
+    }
+}
+
+fn abs_diff(a: U256, b: U256) -> U256 {
+    if a > b { a - b } else { b - a }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_builds_curves() {
+        let registry = CurveRegistry::default();
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["constant_product", "sphere", "stable_swap", "superellipse"]
+        );
+        assert_eq!(registry.build("stable_swap", 100).unwrap().name(), "stable_swap");
+        assert!(registry.build("stable_swap", 0).is_err());
+        assert!(registry.build("weighted", 0).is_err());
+
+        // CurveType behaves exactly like the free functions it wraps
+        let reserves = alloc::vec![U256::from(1_000_000u64), U256::from(900_000u64), U256::from(1_100_000u64)];
+        let curve = CurveType::Superellipse { u_parameter: 25_000 };
+        let invariant = curve.invariant(&reserves).unwrap();
+        assert_eq!(
+            curve.amount_out(&reserves, invariant, 0, 1, U256::from(5_000)).unwrap(),
+            depth::amount_out(&reserves, &curve, invariant, 0, 1, U256::from(5_000)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compare_execution_on_balanced_pool() {
+        let reserves = alloc::vec![U256::from(10u64).pow(U256::from(24)); 3];
+        let stable = StableSwap { amplification: 100 };
+        let curves: [&dyn Invariant; 2] = [&ConstantProduct, &stable];
+        let amount_in = U256::from(10u64).pow(U256::from(21));
+
+        let results = compare_execution(&curves, &reserves, 0, 1, amount_in);
+        let product = results[0].as_ref().unwrap();
+        let stable = results[1].as_ref().unwrap();
+
+        // Both start at parity; the amplified curve stays much closer to it
+        assert_eq!(product.price_before, U256::from(PRECISION_MULTIPLIER));
+        assert_eq!(stable.price_before, U256::from(PRECISION_MULTIPLIER));
+        assert_eq!(product.amount_out, U256::from(999_000_999_000_999_000_999u128));
+        assert!(stable.amount_out > product.amount_out && stable.amount_out < amount_in);
+        assert_eq!((stable.impact_bp, product.impact_bp), (0, 9));
+    }
+}
@@ -14,28 +14,53 @@
 //!
 //! - [`sphere`]: Spherical AMM constraints and calculations
 //! - [`superellipse`]: Superellipse curve mathematics
+//! - [`deposit`]: Proportional and single-sided deposit sizing
+//! - [`depth`]: Marginal price, price impact and depth curves
+//! - [`fixed`]: Fixed-point decimals with the scale in the type
+//! - [`fixed_point`]: Fractional powers and roots in fixed point
+//! - [`invariant`]: Sphere, superellipse, constant-product and StableSwap curves behind one trait
+//! - [`il`]: Impermanent loss and LP profit and loss
+//! - [`quote`]: Batch quoting against one pool state
+//! - [`routing`]: Pathfinding and split routes across pools and chains
+//! - [`snapshot`]: Versioned pool format for the WASM and C bindings
 //! - [`ticks`]: Tick geometry and capital efficiency
+//! - [`torus`]: Boundary circles and the toroidal trade invariant
 //! - [`trades`]: Trade execution with tick boundary crossing
 //! - [`types`]: Core types and traits used throughout the library
+//!
+//! With the `wasm` feature, `wasm` exposes quoting through wasm-bindgen;
+//! with `ffi`, `ffi` exposes it through a C ABI.
 
-#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
 #![deny(unsafe_code)]
 
 extern crate alloc;
 
-use alloc::vec::Vec;
-use alloy_primitives::U256;
+pub use alloy_primitives::U256;
 
+pub mod deposit;
+pub mod depth;
 pub mod error;
+pub mod fixed;
+pub mod fixed_point;
+pub mod il;
+pub mod invariant;
+pub mod quote;
+pub mod routing;
+pub mod snapshot;
 pub mod sphere;
 pub mod superellipse;
 pub mod ticks;
+pub mod torus;
 pub mod trades;
 pub mod types;
 pub mod utils;
 pub mod concentrated_liquidity;
-pub mod ten_token_demo;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 
 // Re-export commonly used types
 pub use error::{OrbitalError, Result};
@@ -43,6 +68,7 @@ pub use types::*;
 
 /// Precision for fixed-point arithmetic (18 decimals like ETH)
 pub const PRECISION: u32 = 18;
+/// 10^PRECISION, one unit in fixed point
 pub const PRECISION_MULTIPLIER: u128 = 1_000_000_000_000_000_000;
 
 /// Basis points precision (10000 = 100%)
@@ -67,8 +93,8 @@ mod tests {
 
     #[test]
     fn test_token_limits() {
-        assert!(MIN_TOKENS >= 2);
-        assert!(MAX_TOKENS <= 1000);
-        assert!(MAX_TOKENS > MIN_TOKENS);
+        const { assert!(MIN_TOKENS >= 2) };
+        const { assert!(MAX_TOKENS <= 1000) };
+        const { assert!(MAX_TOKENS > MIN_TOKENS) };
     }
 }
\ No newline at end of file
@@ -0,0 +1,296 @@
+//! Batch quoting against one pool state
+//!
+//! Every output calculation needs the sum of r_k² (sphere) or r_k^u
+//! (superellipse) over the whole pool, and for the superellipse each term is
+//! a fixed-point power. Quoting many trades against the same reserves one at
+//! a time recomputes that sum for every quote. A [`PreparedPool`] computes the
+//! per-token terms and their total once, after which each quote only swaps
+//! the input term for its new value and takes one root.
+//!
+//! Outputs are identical to [`crate::depth::amount_out`], including rounding
+//! and the error returned for trades the pool cannot fill.
+
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+use alloy_primitives::U256;
+use crate::{
+    depth::{self, deviation_bp},
+    error::{OrbitalError, Result},
+    fixed_point::{nth_root, pow_bp},
+    types::{CurveType, PoolState},
+    utils::sqrt_ceil,
+    PRECISION_MULTIPLIER,
+};
+
+/// One trade to quote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteRequest {
+    /// Index of the token sold
+    pub token_in: usize,
+    /// Index of the token bought
+    pub token_out: usize,
+    /// Amount sold
+    pub amount_in: U256,
+}
+
+/// Result of one [`QuoteRequest`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quote {
+    /// Index of the token sold
+    pub token_in: usize,
+    /// Index of the token bought
+    pub token_out: usize,
+    /// Amount sold
+    pub amount_in: U256,
+    /// Amount bought
+    pub amount_out: U256,
+    /// Marginal price before the trade, scaled by 1e18
+    pub price_before: U256,
+    /// Deviation of the execution price from `price_before`, in basis points
+    pub impact_bp: u32,
+}
+
+/// Pool state with the invariant terms of every reserve precomputed
+#[derive(Debug, Clone)]
+pub struct PreparedPool {
+    reserves: Vec<U256>,
+    curve: CurveType,
+    invariant: U256,
+    /// r_k² or r_k^u for every token
+    terms: Vec<U256>,
+    /// Σ of `terms`
+    total: U256,
+}
+
+impl PreparedPool {
+    /// Precompute the invariant terms of `reserves`
+    pub fn new(reserves: &[U256], curve: &CurveType, invariant: U256) -> Result<Self> {
+        let terms = reserves
+            .iter()
+            .enumerate()
+            .map(|(i, &r)| match exponent(curve) {
+                None => r
+                    .checked_mul(r)
+                    .ok_or_else(|| OrbitalError::overflow(alloc::format!("reserve[{}] squared", i))),
+                Some(u) => pow_bp(r, u),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let total = terms.iter().try_fold(U256::ZERO, |acc, &t| {
+            acc.checked_add(t).ok_or_else(|| OrbitalError::overflow("sum of terms"))
+        })?;
+
+        Ok(Self {
+            reserves: reserves.to_vec(),
+            curve: *curve,
+            invariant,
+            terms,
+            total,
+        })
+    }
+
+    /// Precompute the invariant terms of a [`PoolState`]
+    pub fn from_pool_state(pool: &PoolState) -> Result<Self> {
+        Self::new(&pool.reserves.reserves, &pool.curve_type, pool.invariant)
+    }
+
+    /// Reserves the pool was prepared from
+    pub fn reserves(&self) -> &[U256] {
+        &self.reserves
+    }
+
+    /// Output of trading `amount_in` of `token_in` for `token_out`
+    pub fn amount_out(&self, token_in: usize, token_out: usize, amount_in: U256) -> Result<U256> {
+        let reserves = &self.reserves;
+        if token_in >= reserves.len() || token_out >= reserves.len() {
+            return Err(OrbitalError::TokenIndexOutOfBounds {
+                index: token_in.max(token_out),
+                token_count: reserves.len(),
+            });
+        }
+        if token_in == token_out {
+            return Err(OrbitalError::invalid_param("tokens", "input and output must be different"));
+        }
+        if amount_in.is_zero() {
+            return Ok(U256::ZERO);
+        }
+
+        let new_reserve_in = reserves[token_in]
+            .checked_add(amount_in)
+            .ok_or_else(|| OrbitalError::overflow("reserve_in + amount_in"))?;
+        let insufficient = || OrbitalError::InsufficientLiquidity {
+            needed: amount_in.to_string(),
+            available: reserves[token_out].to_string(),
+        };
+
+        // Σ over k ≠ out, with the input term at its post-trade value
+        let without = self.total - self.terms[token_in] - self.terms[token_out];
+        let new_reserve_out = match exponent(&self.curve) {
+            None => {
+                let new_term = new_reserve_in
+                    .checked_mul(new_reserve_in)
+                    .ok_or_else(|| OrbitalError::overflow("new_reserve_in squared"))?;
+                let sum_others = without
+                    .checked_add(new_term)
+                    .ok_or_else(|| OrbitalError::overflow("sum of squares"))?;
+                let remaining = self
+                    .invariant
+                    .checked_sub(sum_others)
+                    .ok_or_else(|| OrbitalError::underflow("R² - sum_other_squares"))?;
+                sqrt_ceil(remaining)
+            }
+            Some(u) => {
+                let sum_others = without
+                    .checked_add(pow_bp(new_reserve_in, u)?)
+                    .ok_or_else(|| OrbitalError::overflow("sum of powers"))?;
+                let remaining = self.invariant.checked_sub(sum_others).ok_or_else(insufficient)?;
+                let mut root = nth_root(remaining, u)?;
+                if pow_bp(root, u)? < remaining {
+                    root += U256::from(1);
+                }
+                root
+            }
+        };
+
+        reserves[token_out].checked_sub(new_reserve_out).ok_or_else(insufficient)
+    }
+
+    /// Marginal price of `token_out` per `token_in`, scaled by 1e18
+    pub fn marginal_price(&self, token_in: usize, token_out: usize) -> Result<U256> {
+        depth::marginal_price(&self.reserves, &self.curve, token_in, token_out)
+    }
+
+    /// Quote one request, given the marginal price of its pair
+    fn quote_at(&self, request: &QuoteRequest, price_before: U256) -> Result<Quote> {
+        if request.amount_in.is_zero() {
+            return Err(OrbitalError::invalid_param("amount_in", "must be greater than zero"));
+        }
+        let amount_out = self.amount_out(request.token_in, request.token_out, request.amount_in)?;
+        let execution_price = amount_out
+            .checked_mul(U256::from(PRECISION_MULTIPLIER))
+            .ok_or_else(|| OrbitalError::overflow("execution price"))?
+            / request.amount_in;
+
+        Ok(Quote {
+            token_in: request.token_in,
+            token_out: request.token_out,
+            amount_in: request.amount_in,
+            amount_out,
+            price_before,
+            impact_bp: deviation_bp(price_before, execution_price),
+        })
+    }
+
+    /// Quote a single request
+    pub fn quote(&self, request: &QuoteRequest) -> Result<Quote> {
+        let price_before = self.marginal_price(request.token_in, request.token_out)?;
+        self.quote_at(request, price_before)
+    }
+
+    /// Quote every request, computing each pair's marginal price only once
+    pub fn quote_many(&self, requests: &[QuoteRequest]) -> Vec<Result<Quote>> {
+        let mut prices: BTreeMap<(usize, usize), Result<U256>> = BTreeMap::new();
+        requests
+            .iter()
+            .map(|request| {
+                let price_before = prices
+                    .entry((request.token_in, request.token_out))
+                    .or_insert_with(|| self.marginal_price(request.token_in, request.token_out))
+                    .clone()?;
+                self.quote_at(request, price_before)
+            })
+            .collect()
+    }
+}
+
+/// Quote many trades against the same pool state
+///
+/// Entries line up with `requests`. A request the pool cannot fill yields
+/// its error without affecting the others; if the pool itself cannot be
+/// prepared, every entry carries that error.
+pub fn quote_many(pool: &PoolState, requests: &[QuoteRequest]) -> Vec<Result<Quote>> {
+    match PreparedPool::from_pool_state(pool) {
+        Ok(prepared) => prepared.quote_many(requests),
+        Err(e) => requests.iter().map(|_| Err(e.clone())).collect(),
+    }
+}
+
+/// Exponent of the superellipse terms, or `None` for squares
+///
+/// u = 2 is quoted as a sphere, as in
+/// [`crate::superellipse::calculate_amount_out_superellipse`].
+fn exponent(curve: &CurveType) -> Option<u32> {
+    match curve {
+        CurveType::Superellipse { u_parameter } if *u_parameter != 20_000 => Some(*u_parameter),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requests() -> Vec<QuoteRequest> {
+        let mut requests = Vec::new();
+        for (token_in, token_out) in [(0, 1), (1, 0), (2, 1), (0, 2)] {
+            for amount in [1u64, 10_000, 250_000, 2_000_000] {
+                requests.push(QuoteRequest { token_in, token_out, amount_in: U256::from(amount) });
+            }
+        }
+        requests.push(QuoteRequest { token_in: 0, token_out: 3, amount_in: U256::from(1) });
+        requests
+    }
+
+    #[test]
+    fn test_matches_single_quotes() {
+        let reserves = alloc::vec![U256::from(1_000_000u64), U256::from(800_000u64), U256::from(1_300_000u64)];
+        for curve in [
+            CurveType::Sphere,
+            CurveType::Superellipse { u_parameter: 20_000 },
+            CurveType::Superellipse { u_parameter: 25_000 },
+        ] {
+            let invariant = match curve {
+                CurveType::Sphere => reserves.iter().fold(U256::ZERO, |acc, &r| acc + r * r),
+                CurveType::Superellipse { u_parameter } => {
+                    reserves.iter().fold(U256::ZERO, |acc, &r| acc + pow_bp(r, u_parameter).unwrap())
+                }
+            };
+            let prepared = PreparedPool::new(&reserves, &curve, invariant).unwrap();
+            let requests = requests();
+            let quotes = prepared.quote_many(&requests);
+
+            for (request, quote) in requests.iter().zip(quotes) {
+                let expected = depth::price_impact(
+                    &reserves,
+                    &curve,
+                    invariant,
+                    request.token_in,
+                    request.token_out,
+                    request.amount_in,
+                );
+                match (quote, expected) {
+                    (Ok(quote), Ok(expected)) => {
+                        assert_eq!(quote.amount_out, expected.amount_out);
+                        assert_eq!(quote.price_before, expected.price_before);
+                        assert_eq!(quote.impact_bp, expected.impact_bp);
+                    }
+                    (Err(quote), Err(expected)) => assert_eq!(quote, expected),
+                    (quote, expected) => panic!("{:?} vs {:?}", quote, expected),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bad_requests_do_not_poison_the_batch() {
+        let reserves = alloc::vec![U256::from(1_000u64); 2];
+        let prepared = PreparedPool::new(&reserves, &CurveType::Sphere, U256::from(2_000_000u64)).unwrap();
+        let quotes = prepared.quote_many(&[
+            QuoteRequest { token_in: 0, token_out: 0, amount_in: U256::from(10) },
+            QuoteRequest { token_in: 0, token_out: 1, amount_in: U256::ZERO },
+            QuoteRequest { token_in: 0, token_out: 1, amount_in: U256::from(10) },
+        ]);
+
+        assert!(quotes[0].is_err());
+        assert!(quotes[1].is_err());
+        assert!(quotes[2].as_ref().unwrap().amount_out > U256::ZERO);
+    }
+}
@@ -0,0 +1,551 @@
+//! Routing across pools and chains
+//!
+//! [`crate::trades::calculate_optimal_route`] searches the tokens of a single
+//! pool. A [`RoutingGraph`] joins many pools, possibly on different chains:
+//! every asset is a node, every ordered token pair of a pool is a swap edge,
+//! and bridges between chains are edges of their own.
+//!
+//! Paths are found with Bellman-Ford over log-price weights. An edge that
+//! turns one unit into `p` units weighs `-log2(p)`, so the lightest path has
+//! the best marginal rate. Rates above one give negative weights, which rules
+//! out Dijkstra; bounding the search to `max_hops` layers keeps it finite
+//! even when the graph holds an arbitrage cycle. Each hop also pays a gas
+//! penalty, weighed against the size of the trade.
+//!
+//! Weights use marginal prices only. Quotes for a path simulate every hop
+//! against the pool's curve, and split routes send the trade in chunks so
+//! each chunk sees the reserves the previous ones left behind.
+
+use alloc::vec::Vec;
+use alloy_primitives::{Address, U256};
+use crate::{
+    depth,
+    error::{OrbitalError, Result},
+    fixed_point::{log2_fixed, WAD},
+    types::CurveType,
+    BP_PRECISION,
+};
+
+/// A token on a specific chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Asset {
+    /// Chain the token lives on
+    pub chain_id: u64,
+    /// Token contract address
+    pub token: Address,
+}
+
+/// A pool as seen by the router
+#[derive(Debug, Clone)]
+pub struct RoutingPool {
+    /// Chain the pool is deployed on
+    pub chain_id: u64,
+    /// Graph asset index of each pool token
+    pub assets: Vec<usize>,
+    /// Current reserves, in pool token order
+    pub reserves: Vec<U256>,
+    /// Pool invariant
+    pub curve: CurveType,
+    /// R² for a sphere, K for a superellipse
+    pub invariant: U256,
+    /// Swap fee in basis points, taken from the input
+    pub fee_bp: u32,
+}
+
+/// What an edge of the graph does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// Swap through a pool, by pool token index
+    Swap {
+        /// Pool index in the graph
+        pool: usize,
+        /// Pool token sold
+        token_in: usize,
+        /// Pool token bought
+        token_out: usize,
+    },
+    /// Transfer of the same value to another chain
+    Bridge {
+        /// Bridge fee in basis points
+        fee_bp: u32,
+    },
+}
+
+/// A directed edge between two assets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    /// Asset index sold
+    pub from: usize,
+    /// Asset index received
+    pub to: usize,
+    /// Swap or bridge
+    pub kind: EdgeKind,
+}
+
+impl Edge {
+    /// Whether the edge moves value between chains
+    pub fn is_bridge(&self) -> bool {
+        matches!(self.kind, EdgeKind::Bridge { .. })
+    }
+}
+
+/// Search limits and costs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteOptions {
+    /// Most edges a route may take
+    pub max_hops: usize,
+    /// Gas cost of one hop, in units of the input asset
+    pub gas_cost_per_hop: U256,
+}
+
+impl Default for RouteOptions {
+    fn default() -> Self {
+        Self {
+            max_hops: 4,
+            gas_cost_per_hop: U256::ZERO,
+        }
+    }
+}
+
+/// A path through the graph and what it yields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    /// Edge indices, in order
+    pub edges: Vec<usize>,
+    /// Assets visited, starting with the input asset
+    pub assets: Vec<usize>,
+    /// Input amount
+    pub amount_in: U256,
+    /// Output amount, before gas
+    pub amount_out: U256,
+    /// Gas for every hop, in units of the input asset
+    pub gas_cost: U256,
+}
+
+impl Route {
+    /// Number of edges taken
+    pub fn hops(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+/// A trade spread over several routes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitRoute {
+    /// Distinct routes with the share of the input each carries
+    pub routes: Vec<Route>,
+    /// Total output, before gas
+    pub amount_out: U256,
+    /// Gas for every route, in units of the input asset
+    pub gas_cost: U256,
+}
+
+/// Assets, pools and the edges between them
+#[derive(Debug, Clone, Default)]
+pub struct RoutingGraph {
+    assets: Vec<Asset>,
+    pools: Vec<RoutingPool>,
+    edges: Vec<Edge>,
+}
+
+/// Best known path to an asset: its weight and the edge it arrived by,
+/// with the search layer of that edge's source
+#[derive(Debug, Clone, Copy)]
+struct Label {
+    weight: i128,
+    via: Option<(usize, usize)>,
+}
+
+impl RoutingGraph {
+    /// Empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an asset, returning its index; known assets keep theirs
+    pub fn add_asset(&mut self, asset: Asset) -> usize {
+        if let Some(index) = self.assets.iter().position(|known| *known == asset) {
+            return index;
+        }
+        self.assets.push(asset);
+        self.assets.len() - 1
+    }
+
+    /// Add a pool and a swap edge for every ordered pair of its tokens
+    pub fn add_pool(&mut self, pool: RoutingPool) -> Result<usize> {
+        if pool.assets.len() < 2 || pool.assets.len() != pool.reserves.len() {
+            return Err(OrbitalError::InvalidTokenCount(pool.assets.len()));
+        }
+        if pool.fee_bp >= BP_PRECISION {
+            return Err(OrbitalError::invalid_param("fee_bp", "must be below 10000"));
+        }
+        for &asset in &pool.assets {
+            if self.asset(asset)?.chain_id != pool.chain_id {
+                return Err(OrbitalError::invalid_param("assets", "pool tokens must be on the pool's chain"));
+            }
+        }
+
+        let index = self.pools.len();
+        for (token_in, &from) in pool.assets.iter().enumerate() {
+            for (token_out, &to) in pool.assets.iter().enumerate() {
+                if token_in != token_out {
+                    self.edges.push(Edge {
+                        from,
+                        to,
+                        kind: EdgeKind::Swap { pool: index, token_in, token_out },
+                    });
+                }
+            }
+        }
+        self.pools.push(pool);
+        Ok(index)
+    }
+
+    /// Add a one-way bridge between the same token on two chains
+    pub fn add_bridge(&mut self, from: usize, to: usize, fee_bp: u32) -> Result<()> {
+        if self.asset(from)?.chain_id == self.asset(to)?.chain_id {
+            return Err(OrbitalError::invalid_param("to", "a bridge must change chains"));
+        }
+        if fee_bp >= BP_PRECISION {
+            return Err(OrbitalError::invalid_param("fee_bp", "must be below 10000"));
+        }
+        self.edges.push(Edge { from, to, kind: EdgeKind::Bridge { fee_bp } });
+        Ok(())
+    }
+
+    /// All assets, by index
+    pub fn assets(&self) -> &[Asset] {
+        &self.assets
+    }
+
+    /// All pools, by index
+    pub fn pools(&self) -> &[RoutingPool] {
+        &self.pools
+    }
+
+    /// All edges, by index
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// Output of sending `amount_in` along `edges`
+    pub fn quote(&self, edges: &[usize], amount_in: U256, options: &RouteOptions) -> Result<Route> {
+        let mut reserves = self.reserves();
+        self.simulate(&mut reserves, edges, amount_in, options)
+    }
+
+    /// Best single route from `from` to `to`
+    pub fn find_route(
+        &self,
+        from: usize,
+        to: usize,
+        amount_in: U256,
+        options: &RouteOptions,
+    ) -> Result<Route> {
+        let mut reserves = self.reserves();
+        let edges = self.best_path(&reserves, from, to, amount_in, options)?;
+        self.simulate(&mut reserves, &edges, amount_in, options)
+    }
+
+    /// Spread `amount_in` over up to `parts` routes
+    ///
+    /// The input goes out in `parts` equal chunks, each along the best path
+    /// for the reserves the earlier chunks left. Chunks that take the same
+    /// path are merged into one route. Gas is weighed against the whole
+    /// trade, so a path is only opened if it pays for itself at full size.
+    pub fn find_split_route(
+        &self,
+        from: usize,
+        to: usize,
+        amount_in: U256,
+        parts: usize,
+        options: &RouteOptions,
+    ) -> Result<SplitRoute> {
+        if parts == 0 {
+            return Err(OrbitalError::invalid_param("parts", "must be greater than zero"));
+        }
+
+        let mut reserves = self.reserves();
+        let chunk = amount_in / U256::from(parts);
+        let mut routes: Vec<Route> = Vec::new();
+
+        for part in 0..parts {
+            let size = if part + 1 == parts {
+                amount_in - chunk * U256::from(parts - 1)
+            } else {
+                chunk
+            };
+            if size.is_zero() {
+                continue;
+            }
+
+            let edges = self.best_path(&reserves, from, to, amount_in, options)?;
+            let route = self.simulate(&mut reserves, &edges, size, options)?;
+            match routes.iter_mut().find(|known| known.edges == route.edges) {
+                Some(known) => {
+                    known.amount_in += route.amount_in;
+                    known.amount_out += route.amount_out;
+                }
+                None => routes.push(route),
+            }
+        }
+
+        let amount_out = routes.iter().fold(U256::ZERO, |acc, route| acc + route.amount_out);
+        let gas_cost = routes.iter().fold(U256::ZERO, |acc, route| acc + route.gas_cost);
+        Ok(SplitRoute { routes, amount_out, gas_cost })
+    }
+
+    fn asset(&self, index: usize) -> Result<&Asset> {
+        self.assets.get(index).ok_or(OrbitalError::TokenIndexOutOfBounds {
+            index,
+            token_count: self.assets.len(),
+        })
+    }
+
+    fn reserves(&self) -> Vec<Vec<U256>> {
+        self.pools.iter().map(|pool| pool.reserves.clone()).collect()
+    }
+
+    /// Lightest path of at most `max_hops` edges, by hop-bounded
+    /// Bellman-Ford: layer k holds the best path of k edges or fewer
+    fn best_path(
+        &self,
+        reserves: &[Vec<U256>],
+        from: usize,
+        to: usize,
+        amount_in: U256,
+        options: &RouteOptions,
+    ) -> Result<Vec<usize>> {
+        self.asset(from)?;
+        self.asset(to)?;
+        if from == to {
+            return Err(OrbitalError::invalid_param("to", "must differ from the input asset"));
+        }
+        if options.max_hops == 0 {
+            return Err(OrbitalError::invalid_param("max_hops", "must be greater than zero"));
+        }
+        if amount_in.is_zero() {
+            return Err(OrbitalError::invalid_param("amount_in", "must be greater than zero"));
+        }
+
+        // A hop costs gas / amount_in of the value routed through it
+        let gas_bp = options.gas_cost_per_hop.saturating_mul(U256::from(BP_PRECISION)) / amount_in;
+        if gas_bp >= U256::from(BP_PRECISION) {
+            return Err(OrbitalError::invalid_param("amount_in", "does not cover the gas of one hop"));
+        }
+        let hop_weight = neg_log2(retained(WAD, gas_bp.to::<u32>()))
+            .ok_or_else(|| OrbitalError::computation("gas penalty"))?;
+
+        let weights = self
+            .edges
+            .iter()
+            .map(|edge| {
+                Ok(self.edge_rate(reserves, edge)?.and_then(neg_log2).map(|w| w + hop_weight))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut start = alloc::vec![None; self.assets.len()];
+        start[from] = Some(Label { weight: 0, via: None });
+        let mut layers: Vec<Vec<Option<Label>>> = alloc::vec![start];
+
+        for layer in 1..=options.max_hops {
+            let previous = &layers[layer - 1];
+            let mut next = previous.clone();
+            for (index, edge) in self.edges.iter().enumerate() {
+                let (Some(base), Some(weight)) = (previous[edge.from], weights[index]) else {
+                    continue;
+                };
+                let candidate = base.weight + weight;
+                if next[edge.to].is_none_or(|label| candidate < label.weight) {
+                    next[edge.to] = Some(Label { weight: candidate, via: Some((index, layer - 1)) });
+                }
+            }
+            layers.push(next);
+        }
+
+        let mut path = Vec::new();
+        let (mut asset, mut layer) = (to, options.max_hops);
+        loop {
+            let label = layers[layer][asset]
+                .ok_or_else(|| OrbitalError::invalid_param("to", "no route within max_hops"))?;
+            match label.via {
+                Some((edge, source_layer)) => {
+                    path.push(edge);
+                    asset = self.edges[edge].from;
+                    layer = source_layer;
+                }
+                None => break,
+            }
+        }
+        path.reverse();
+        Ok(path)
+    }
+
+    /// Marginal rate of an edge net of fees, in WAD; `None` if the pool
+    /// cannot quote it
+    fn edge_rate(&self, reserves: &[Vec<U256>], edge: &Edge) -> Result<Option<U256>> {
+        match edge.kind {
+            EdgeKind::Bridge { fee_bp } => Ok(Some(retained(WAD, fee_bp))),
+            EdgeKind::Swap { pool, token_in, token_out } => {
+                let state = &self.pools[pool];
+                match depth::marginal_price(&reserves[pool], &state.curve, token_in, token_out) {
+                    Ok(price) => Ok(Some(retained(price, state.fee_bp))),
+                    Err(OrbitalError::DivisionByZero { .. }) => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+    }
+
+    /// Send `amount_in` along `edges`, updating `reserves` as each swap
+    /// executes
+    fn simulate(
+        &self,
+        reserves: &mut [Vec<U256>],
+        edges: &[usize],
+        amount_in: U256,
+        options: &RouteOptions,
+    ) -> Result<Route> {
+        let first = self
+            .edges
+            .get(*edges.first().ok_or_else(|| OrbitalError::invalid_param("edges", "route is empty"))?)
+            .ok_or_else(|| OrbitalError::invalid_param("edges", "unknown edge"))?;
+        let mut assets = alloc::vec![first.from];
+        let mut amount = amount_in;
+
+        for &index in edges {
+            let edge = self
+                .edges
+                .get(index)
+                .ok_or_else(|| OrbitalError::invalid_param("edges", "unknown edge"))?;
+            if edge.from != assets[assets.len() - 1] {
+                return Err(OrbitalError::invalid_param("edges", "edges do not form a path"));
+            }
+
+            amount = match edge.kind {
+                EdgeKind::Bridge { fee_bp } => retained(amount, fee_bp),
+                EdgeKind::Swap { pool, token_in, token_out } => {
+                    let state = &self.pools[pool];
+                    let net = retained(amount, state.fee_bp);
+                    let out = depth::amount_out(
+                        &reserves[pool],
+                        &state.curve,
+                        state.invariant,
+                        token_in,
+                        token_out,
+                        net,
+                    )?;
+                    reserves[pool][token_in] += net;
+                    reserves[pool][token_out] -= out;
+                    out
+                }
+            };
+            assets.push(edge.to);
+        }
+
+        Ok(Route {
+            edges: edges.to_vec(),
+            assets,
+            amount_in,
+            amount_out: amount,
+            gas_cost: options.gas_cost_per_hop * U256::from(edges.len()),
+        })
+    }
+}
+
+/// `amount` less a fee of `fee_bp`
+fn retained(amount: U256, fee_bp: u32) -> U256 {
+    amount * U256::from(BP_PRECISION - fee_bp) / U256::from(BP_PRECISION)
+}
+
+/// -log2 of a WAD rate in WAD, signed; `None` for a zero rate
+fn neg_log2(rate: U256) -> Option<i128> {
+    if rate.is_zero() {
+        return None;
+    }
+    // log2 of anything in U256 is below 256 · WAD, well inside i128
+    if rate >= WAD {
+        log2_fixed(rate).ok().map(|log| -(log.to::<u128>() as i128))
+    } else {
+        log2_fixed(WAD * WAD / rate).ok().map(|log| log.to::<u128>() as i128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(graph: &mut RoutingGraph, chain_id: u64, byte: u8) -> usize {
+        graph.add_asset(Asset { chain_id, token: Address::repeat_byte(byte) })
+    }
+
+    fn sphere_pool(chain_id: u64, assets: [usize; 2], reserves: [u64; 2]) -> RoutingPool {
+        let reserves: Vec<U256> = reserves.iter().map(|&r| U256::from(r)).collect();
+        let invariant = reserves.iter().fold(U256::ZERO, |acc, &r| acc + r * r);
+        RoutingPool {
+            chain_id,
+            assets: assets.to_vec(),
+            reserves,
+            curve: CurveType::Sphere,
+            invariant,
+            fee_bp: 30,
+        }
+    }
+
+    #[test]
+    fn test_gas_penalty_prefers_fewer_hops() {
+        let mut graph = RoutingGraph::new();
+        let (a, b, c) = (asset(&mut graph, 1, 1), asset(&mut graph, 1, 2), asset(&mut graph, 1, 3));
+        graph.add_pool(sphere_pool(1, [a, b], [1_000_000, 1_000_000])).unwrap();
+        // A trades into C at 1.1, and C into B at par
+        graph.add_pool(sphere_pool(1, [a, c], [1_100_000, 1_000_000])).unwrap();
+        graph.add_pool(sphere_pool(1, [c, b], [1_000_000, 1_000_000])).unwrap();
+
+        let amount = U256::from(1_000);
+        let free = graph.find_route(a, b, amount, &RouteOptions::default()).unwrap();
+        assert_eq!(free.assets, vec![a, c, b]);
+        let direct = graph.quote(&[0], amount, &RouteOptions::default()).unwrap();
+        assert_eq!(direct.assets, vec![a, b]);
+        assert!(free.amount_out > direct.amount_out);
+
+        // At 10% of the trade per hop, the second hop no longer pays
+        let options = RouteOptions { gas_cost_per_hop: U256::from(100), ..Default::default() };
+        let costly = graph.find_route(a, b, amount, &options).unwrap();
+        assert_eq!(costly.assets, vec![a, b]);
+        assert_eq!(costly.gas_cost, U256::from(100));
+
+        let one_hop = RouteOptions { max_hops: 1, ..Default::default() };
+        assert_eq!(graph.find_route(a, b, amount, &one_hop).unwrap().hops(), 1);
+        assert!(graph.find_route(a, c, amount, &RouteOptions { gas_cost_per_hop: amount, ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn test_split_route_across_chains() {
+        let mut graph = RoutingGraph::new();
+        let (usdc_1, usdt_1) = (asset(&mut graph, 1, 1), asset(&mut graph, 1, 2));
+        let (usdc_2, usdt_2) = (asset(&mut graph, 2, 1), asset(&mut graph, 2, 2));
+        graph.add_pool(sphere_pool(1, [usdc_1, usdt_1], [1_000_000, 1_000_000])).unwrap();
+        graph.add_pool(sphere_pool(2, [usdc_2, usdt_2], [1_200_000, 1_000_000])).unwrap();
+        graph.add_bridge(usdc_1, usdc_2, 10).unwrap();
+        graph.add_bridge(usdt_1, usdt_2, 10).unwrap();
+
+        assert!(graph.add_bridge(usdc_1, usdt_1, 10).is_err());
+        assert!(graph.add_pool(sphere_pool(1, [usdc_1, usdt_2], [1, 1])).is_err());
+
+        // Bridging first reaches the better priced pool on chain 2
+        let amount = U256::from(40_000);
+        let single = graph.find_route(usdc_1, usdt_2, amount, &RouteOptions::default()).unwrap();
+        assert_eq!(single.assets, vec![usdc_1, usdc_2, usdt_2]);
+        assert!(graph.edges()[single.edges[0]].is_bridge());
+
+        let split = graph.find_split_route(usdc_1, usdt_2, amount, 4, &RouteOptions::default()).unwrap();
+        let routed = split.routes.iter().fold(U256::ZERO, |acc, route| acc + route.amount_in);
+        let received = split.routes.iter().fold(U256::ZERO, |acc, route| acc + route.amount_out);
+        assert_eq!(routed, amount);
+        assert_eq!(received, split.amount_out);
+        for route in &split.routes {
+            assert_eq!(graph.assets()[route.assets[0]].chain_id, 1);
+            assert_eq!(graph.assets()[*route.assets.last().unwrap()].chain_id, 2);
+        }
+    }
+}
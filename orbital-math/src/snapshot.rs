@@ -0,0 +1,222 @@
+//! Stable serialized pool format for quoting outside Rust
+//!
+//! The frontend and non-Rust solvers quote against a [`PoolSnapshot`]: the
+//! reserves, curve and invariant of one pool, versioned so that the format
+//! can evolve without silently changing quotes. Amounts are decimal strings,
+//! since JavaScript numbers and most JSON parsers cannot hold a `U256`.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "curve": { "type": "superellipse", "u_parameter": 25000 },
+//!   "reserves": ["1000000", "1000000", "1000000"],
+//!   "invariant": "3000000000000",
+//!   "fee_bp": 30
+//! }
+//! ```
+//!
+//! Quotes run the same integer code as [`crate::depth`], which rounds like
+//! the contract, so a client-side quote equals the on-chain output for the
+//! same reserves.
+
+use alloc::{string::{String, ToString}, vec::Vec};
+use alloy_primitives::U256;
+use serde::{Deserialize, Serialize};
+use crate::{
+    depth,
+    error::{OrbitalError, Result},
+    types::{CurveType, PoolState},
+    BP_PRECISION,
+};
+
+/// Version written by this library; snapshots of other versions are rejected
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+/// Curve of a snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SnapshotCurve {
+    /// Σr² = R²
+    Sphere,
+    /// Σr^u = K
+    Superellipse {
+        /// u in basis points
+        u_parameter: u32,
+    },
+}
+
+/// One pool, as exchanged with bindings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolSnapshot {
+    /// Format version, [`SNAPSHOT_VERSION`]
+    pub version: u16,
+    /// Pool invariant
+    pub curve: SnapshotCurve,
+    /// Reserves as decimal strings
+    pub reserves: Vec<String>,
+    /// R² or K as a decimal string
+    pub invariant: String,
+    /// Swap fee in basis points, taken from the input
+    #[serde(default)]
+    pub fee_bp: u32,
+}
+
+/// A quote, with amounts as decimal strings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Quote {
+    /// Input amount
+    pub amount_in: String,
+    /// Fee taken from the input
+    pub fee: String,
+    /// Output amount
+    pub amount_out: String,
+    /// Marginal price before the trade, scaled by 1e18
+    pub price_before: String,
+    /// Deviation of the execution price from `price_before`, in basis points
+    pub price_impact_bp: u32,
+}
+
+impl PoolSnapshot {
+    /// Snapshot of raw pool parameters
+    pub fn new(reserves: &[U256], curve: &CurveType, invariant: U256, fee_bp: u32) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            curve: match curve {
+                CurveType::Sphere => SnapshotCurve::Sphere,
+                CurveType::Superellipse { u_parameter } => SnapshotCurve::Superellipse { u_parameter: *u_parameter },
+            },
+            reserves: reserves.iter().map(|r| r.to_string()).collect(),
+            invariant: invariant.to_string(),
+            fee_bp,
+        }
+    }
+
+    /// Snapshot of a [`PoolState`]
+    pub fn from_pool_state(pool: &PoolState, fee_bp: u32) -> Self {
+        Self::new(&pool.reserves.reserves, &pool.curve_type, pool.invariant, fee_bp)
+    }
+
+    /// Check the version and parse every amount
+    pub fn validate(&self) -> Result<()> {
+        if self.version != SNAPSHOT_VERSION {
+            return Err(OrbitalError::invalid_param("version", "unsupported snapshot version"));
+        }
+        if self.fee_bp >= BP_PRECISION {
+            return Err(OrbitalError::invalid_param("fee_bp", "must be below 10000"));
+        }
+        self.reserves()?;
+        self.invariant()?;
+        Ok(())
+    }
+
+    /// Parsed reserves
+    pub fn reserves(&self) -> Result<Vec<U256>> {
+        self.reserves.iter().map(|r| parse_amount("reserves", r)).collect()
+    }
+
+    /// Parsed invariant
+    pub fn invariant(&self) -> Result<U256> {
+        parse_amount("invariant", &self.invariant)
+    }
+
+    /// Curve of the pool
+    pub fn curve(&self) -> CurveType {
+        match self.curve {
+            SnapshotCurve::Sphere => CurveType::Sphere,
+            SnapshotCurve::Superellipse { u_parameter } => CurveType::Superellipse { u_parameter },
+        }
+    }
+
+    /// Marginal price of `token_out` per `token_in`, scaled by 1e18
+    pub fn marginal_price(&self, token_in: usize, token_out: usize) -> Result<U256> {
+        self.validate()?;
+        depth::marginal_price(&self.reserves()?, &self.curve(), token_in, token_out)
+    }
+
+    /// Quote trading `amount_in` of `token_in` for `token_out`
+    pub fn quote(&self, token_in: usize, token_out: usize, amount_in: U256) -> Result<Quote> {
+        self.validate()?;
+        let reserves = self.reserves()?;
+        let curve = self.curve();
+
+        let fee = amount_in * U256::from(self.fee_bp) / U256::from(BP_PRECISION);
+        let impact = depth::price_impact(&reserves, &curve, self.invariant()?, token_in, token_out, amount_in - fee)?;
+        Ok(Quote {
+            amount_in: amount_in.to_string(),
+            fee: fee.to_string(),
+            amount_out: impact.amount_out.to_string(),
+            price_before: impact.price_before.to_string(),
+            price_impact_bp: impact.impact_bp,
+        })
+    }
+}
+
+/// Parse and validate a snapshot from JSON
+#[cfg(feature = "json")]
+pub fn from_json(json: &str) -> Result<PoolSnapshot> {
+    let snapshot: PoolSnapshot =
+        serde_json::from_str(json).map_err(|e| OrbitalError::invalid_param("snapshot", e.to_string()))?;
+    snapshot.validate()?;
+    Ok(snapshot)
+}
+
+/// Serialize any binding result to JSON
+#[cfg(feature = "json")]
+pub fn to_json<T: Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value).map_err(|e| OrbitalError::computation(e.to_string()))
+}
+
+/// Parse a decimal amount as passed through the bindings
+pub fn parse_amount(param: &str, value: &str) -> Result<U256> {
+    U256::from_str_radix(value, 10).map_err(|_| OrbitalError::invalid_param(param, "not a decimal integer"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool() -> (Vec<U256>, CurveType, U256) {
+        let reserves = alloc::vec![U256::from(1_000_000u64); 3];
+        (reserves, CurveType::Superellipse { u_parameter: 25_000 }, U256::from(3_000_000_000_000_000u64))
+    }
+
+    #[test]
+    fn test_quote_matches_library() {
+        let (reserves, curve, invariant) = pool();
+        let snapshot = PoolSnapshot::new(&reserves, &curve, invariant, 30);
+        let quote = snapshot.quote(0, 1, U256::from(10_000)).unwrap();
+
+        let expected = depth::amount_out(&reserves, &curve, invariant, 0, 1, U256::from(9_970)).unwrap();
+        assert_eq!(quote.fee, "30");
+        assert_eq!(quote.amount_out, expected.to_string());
+        assert_eq!(
+            snapshot.marginal_price(0, 1).unwrap(),
+            depth::marginal_price(&reserves, &curve, 0, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_snapshots() {
+        let (reserves, curve, invariant) = pool();
+        let mut snapshot = PoolSnapshot::new(&reserves, &curve, invariant, 0);
+        snapshot.version = SNAPSHOT_VERSION + 1;
+        assert!(snapshot.validate().is_err());
+
+        let mut snapshot = PoolSnapshot::new(&reserves, &curve, invariant, 0);
+        snapshot.reserves[1] = "0x10".into();
+        assert!(snapshot.quote(0, 1, U256::from(1)).is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_round_trip() {
+        let json = r#"{"version":1,"curve":{"type":"superellipse","u_parameter":25000},"reserves":["1000000","1000000","1000000"],"invariant":"3000000000000000","fee_bp":30}"#;
+        let snapshot = from_json(json).unwrap();
+        let (reserves, curve, invariant) = pool();
+        assert_eq!(snapshot, PoolSnapshot::new(&reserves, &curve, invariant, 30));
+        assert_eq!(to_json(&snapshot).unwrap(), json);
+
+        assert!(from_json(&json.replace("\"fee_bp\"", "\"fees\"")).is_err());
+    }
+}
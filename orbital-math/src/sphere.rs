@@ -5,11 +5,12 @@
 //! This module provides the fundamental building block for Orbital AMMs where
 //! all valid reserve states lie on the surface of an N-dimensional sphere.
 
-use alloc::vec::Vec;
+use alloc::{format, string::ToString, vec::Vec};
 use alloy_primitives::U256;
 use crate::{
     error::{OrbitalError, Result},
-    types::{ReservePoint, PoolState, sqrt_approx},
+    fixed::Wad,
+    utils::{sqrt, sqrt_ceil},
     MAX_TOKENS, MIN_TOKENS,
 };
 
@@ -76,6 +77,10 @@ pub fn verify_sphere_constraint(
 ///
 /// Solving for Δ_out:
 /// Δ_out = r_j - sqrt(R² - Σ(r_k² for k ≠ j) - (r_i + Δ_in)²)
+///
+/// The square root is rounded up, so Δ_out is rounded down: rounding never
+/// pays the trader more than the exact curve and the pool stays on or
+/// outside the sphere.
 pub fn calculate_amount_out_sphere(
     reserves: &[U256],
     token_in: usize,
@@ -122,7 +127,7 @@ pub fn calculate_amount_out_sphere(
                 new_reserve_in_squared
             } else {
                 r.checked_mul(r)
-                    .ok_or_else(|| OrbitalError::overflow(&format!("reserve[{}] squared", i)))?
+                    .ok_or_else(|| OrbitalError::overflow(format!("reserve[{}] squared", i)))?
             };
             acc.checked_add(r_sq)
                 .ok_or_else(|| OrbitalError::overflow("sum of squares"))
@@ -133,8 +138,8 @@ pub fn calculate_amount_out_sphere(
         .checked_sub(sum_other_squares)
         .ok_or_else(|| OrbitalError::underflow("R² - sum_other_squares"))?;
 
-    // sqrt(R² - Σ(r_k² for k ≠ j))
-    let new_reserve_out = sqrt_approx(under_sqrt);
+    // sqrt(R² - Σ(r_k² for k ≠ j)), rounded against the trader
+    let new_reserve_out = sqrt_ceil(under_sqrt);
 
     // Δ_out = r_j - new_reserve_out
     let amount_out = reserves[token_out]
@@ -191,14 +196,8 @@ pub fn calculate_price_sphere(
         });
     }
 
-    // Price = reserve_in / reserve_out * PRECISION
-    let price = reserve_in
-        .checked_mul(U256::from(crate::PRECISION_MULTIPLIER))
-        .ok_or_else(|| OrbitalError::overflow("price calculation"))?
-        .checked_div(reserve_out)
-        .ok_or_else(|| OrbitalError::division_by_zero("price calculation"))?;
-
-    Ok(price)
+    // Price = reserve_in / reserve_out
+    Ok(Wad::from_ratio(reserve_in, reserve_out)?.raw())
 }
 
 /// Decompose a reserve vector into components parallel and perpendicular to 1⃗
@@ -273,7 +272,7 @@ pub fn calculate_equal_price_point(radius_squared: U256, token_count: usize) ->
         .ok_or_else(|| OrbitalError::division_by_zero("equal price point"))?;
 
     // r = sqrt(R² / N)
-    Ok(sqrt_approx(r_squared))
+    Ok(sqrt(r_squared))
 }
 
 /// Calculate price impact of a trade
@@ -314,9 +313,223 @@ pub fn calculate_price_impact(
     Ok(impact_scaled.try_into().unwrap_or(u32::MAX))
 }
 
+/// How far Σ(r_i²) may stray from R² before it counts as drift
+///
+/// Rounding against the trader leaves the pool slightly outside the sphere
+/// after every trade; over many trades the excess accumulates. Checks accept
+/// up to `tolerance_bp`, and drift beyond `repair_threshold_bp` is worth
+/// folding back into the recorded radius or covering with a donation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TolerancePolicy {
+    /// Deviation accepted by invariant checks, in basis points of R²
+    pub tolerance_bp: u32,
+    /// Deviation from which a repair is proposed, in basis points of R²
+    pub repair_threshold_bp: u32,
+}
+
+impl TolerancePolicy {
+    /// The bound the pool contract enforces on-chain; its
+    /// `SPHERE_TOLERANCE_BP` must stay equal to `tolerance_bp` here
+    pub const CONTRACT: Self = Self { tolerance_bp: 100, repair_threshold_bp: 10 };
+
+    /// Whether `drift` passes an invariant check
+    pub fn allows(&self, drift: &InvariantDrift) -> bool {
+        drift.magnitude <= bp_of(drift.radius_squared, self.tolerance_bp)
+    }
+
+    /// Whether `drift` should be repaired
+    pub fn needs_repair(&self, drift: &InvariantDrift) -> bool {
+        drift.magnitude > bp_of(drift.radius_squared, self.repair_threshold_bp)
+    }
+}
+
+impl Default for TolerancePolicy {
+    fn default() -> Self {
+        Self::CONTRACT
+    }
+}
+
+/// Deviation of the reserves from the recorded sphere
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvariantDrift {
+    /// Σ(r_i²) of the reserves
+    pub sum_of_squares: U256,
+    /// Recorded R²
+    pub radius_squared: U256,
+    /// |Σ(r_i²) - R²|
+    pub magnitude: U256,
+    /// True when the reserves hold more than the sphere requires
+    pub outside: bool,
+    /// `magnitude` in basis points of R², rounded up
+    pub drift_bp: u32,
+}
+
+/// Measure how far `reserves` have drifted from the sphere of `radius_squared`
+pub fn compute_drift(reserves: &[U256], radius_squared: U256) -> Result<InvariantDrift> {
+    if radius_squared.is_zero() {
+        return Err(OrbitalError::division_by_zero("drift relative to zero radius"));
+    }
+
+    let sum_of_squares = sum_of_squares(reserves)?;
+    let outside = sum_of_squares >= radius_squared;
+    let magnitude = if outside {
+        sum_of_squares - radius_squared
+    } else {
+        radius_squared - sum_of_squares
+    };
+
+    let scaled = magnitude.saturating_mul(U256::from(10000));
+    let drift_bp = scaled.saturating_add(radius_squared - U256::from(1)) / radius_squared;
+
+    Ok(InvariantDrift {
+        sum_of_squares,
+        radius_squared,
+        magnitude,
+        outside,
+        drift_bp: drift_bp.try_into().unwrap_or(u32::MAX),
+    })
+}
+
+/// How to correct drift
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairStrategy {
+    /// Record the sphere the reserves actually sit on
+    AdjustRadius,
+    /// Keep the radius and top the reserves up to it. A surplus already
+    /// favours liquidity providers and is left in place.
+    Donate,
+}
+
+/// Correction bringing a pool back onto its sphere
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvariantRepair {
+    /// R² to record after the repair
+    pub radius_squared: U256,
+    /// Amount to donate per token, all zero unless donating
+    pub donations: Vec<U256>,
+}
+
+/// Propose a correction for the drift of `reserves` from `radius_squared`
+///
+/// A donation goes entirely to the largest reserve, where each unit raises
+/// Σ(r_i²) the most, and is rounded up so the pool ends on or outside the
+/// sphere.
+pub fn repair_invariant(
+    reserves: &[U256],
+    radius_squared: U256,
+    strategy: RepairStrategy,
+) -> Result<InvariantRepair> {
+    let drift = compute_drift(reserves, radius_squared)?;
+    let mut donations = alloc::vec![U256::ZERO; reserves.len()];
+
+    match strategy {
+        RepairStrategy::AdjustRadius => Ok(InvariantRepair {
+            radius_squared: drift.sum_of_squares,
+            donations,
+        }),
+        RepairStrategy::Donate => {
+            if !drift.outside {
+                let (index, &largest) = reserves
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|&(_, r)| *r)
+                    .ok_or(OrbitalError::InvalidTokenCount(0))?;
+                let target = largest * largest + drift.magnitude;
+                donations[index] = sqrt_ceil(target) - largest;
+            }
+            Ok(InvariantRepair { radius_squared, donations })
+        }
+    }
+}
+
+fn sum_of_squares(reserves: &[U256]) -> Result<U256> {
+    reserves.iter().try_fold(U256::ZERO, |acc, &r| {
+        let r_squared = r.checked_mul(r)
+            .ok_or_else(|| OrbitalError::overflow("reserve squared"))?;
+        acc.checked_add(r_squared)
+            .ok_or_else(|| OrbitalError::overflow("sum of squares"))
+    })
+}
+
+fn bp_of(value: U256, bp: u32) -> U256 {
+    value.saturating_mul(U256::from(bp)) / U256::from(10000)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        /// Rounding never pays out more than the exact curve: after the trade
+        /// the reserves are on or outside the sphere, by less than one unit
+        /// of the output token
+        #[test]
+        fn prop_amount_out_rounds_against_trader(
+            reserve_in in 1u128..=u64::MAX as u128,
+            reserve_out in 1u128..=u64::MAX as u128,
+            amount_in in 1u128..=u64::MAX as u128,
+        ) {
+            let reserves = vec![U256::from(reserve_in), U256::from(reserve_out)];
+            let radius_squared = reserves[0] * reserves[0] + reserves[1] * reserves[1];
+
+            if let Ok(amount_out) = calculate_amount_out_sphere(&reserves, 0, 1, U256::from(amount_in), radius_squared) {
+                let new_in = reserves[0] + U256::from(amount_in);
+                let new_out = reserves[1] - amount_out;
+                prop_assert!(new_in * new_in + new_out * new_out >= radius_squared);
+
+                // One more unit out would take the pool inside the sphere
+                if !new_out.is_zero() {
+                    let over_paid = new_out - U256::from(1);
+                    prop_assert!(new_in * new_in + over_paid * over_paid < radius_squared);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compute_drift_against_policy() {
+        let radius_squared = U256::from(1_000_000);
+        let on_sphere = compute_drift(&[U256::from(600), U256::from(800)], radius_squared).unwrap();
+        assert_eq!(on_sphere.magnitude, U256::ZERO);
+        assert_eq!(on_sphere.drift_bp, 0);
+
+        // 600² + 805² exceeds R² by 8025, about 81bp
+        let drifted = compute_drift(&[U256::from(600), U256::from(805)], radius_squared).unwrap();
+        assert!(drifted.outside);
+        assert_eq!(drifted.magnitude, U256::from(8025));
+        assert_eq!(drifted.drift_bp, 81);
+
+        let policy = TolerancePolicy::default();
+        assert_eq!(policy, TolerancePolicy::CONTRACT);
+        assert!(policy.allows(&drifted));
+        assert!(policy.needs_repair(&drifted));
+        assert!(!policy.needs_repair(&on_sphere));
+        assert_eq!(
+            policy.allows(&drifted),
+            verify_sphere_constraint(&[U256::from(600), U256::from(805)], radius_squared, policy.tolerance_bp).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_repair_invariant() {
+        let radius_squared = U256::from(1_000_000);
+
+        // Deficit of 1599: one unit on the larger reserve restores the sphere
+        let inside = [U256::from(600), U256::from(799)];
+        let donated = repair_invariant(&inside, radius_squared, RepairStrategy::Donate).unwrap();
+        assert_eq!(donated.donations, vec![U256::ZERO, U256::from(1)]);
+        assert_eq!(donated.radius_squared, radius_squared);
+
+        let adjusted = repair_invariant(&inside, radius_squared, RepairStrategy::AdjustRadius).unwrap();
+        assert_eq!(adjusted.radius_squared, U256::from(998_401));
+        assert_eq!(compute_drift(&inside, adjusted.radius_squared).unwrap().magnitude, U256::ZERO);
+
+        // A surplus is never topped up further
+        let outside = [U256::from(600), U256::from(805)];
+        let kept = repair_invariant(&outside, radius_squared, RepairStrategy::Donate).unwrap();
+        assert!(kept.donations.iter().all(|d| d.is_zero()));
+    }
 
     #[test]
     fn test_verify_sphere_constraint_valid() {
@@ -344,8 +557,10 @@ mod tests {
         // Exact match
         assert!(verify_sphere_constraint(&reserves, radius_squared, 0).is_ok());
         
-        // Within 1% tolerance
-        assert!(verify_sphere_constraint(&reserves, U256::from(26), 100).is_ok());
+        // Within 1% tolerance: 300² + 400² = 250000, 0.8% below 252000
+        let reserves = vec![U256::from(300), U256::from(400)];
+        assert!(verify_sphere_constraint(&reserves, U256::from(252_000), 100).is_ok());
+        assert!(verify_sphere_constraint(&reserves, U256::from(260_000), 100).is_err());
     }
 
     #[test]
@@ -406,10 +621,9 @@ mod tests {
 
     #[test]
     fn test_calculate_amount_out_3d() {
-        // 3D sphere: R² = 14 (approx)
-        // Initial: [2, 2, 2] → 4+4+4 = 12 (approximately on sphere)
-        let reserves = vec![U256::from(2), U256::from(2), U256::from(2)];
-        let radius_squared = U256::from(12);
+        // 3D sphere: 20² + 20² + 20² = 1200
+        let reserves = vec![U256::from(20), U256::from(20), U256::from(20)];
+        let radius_squared = U256::from(1200);
         
         let amount_out = calculate_amount_out_sphere(
             &reserves,
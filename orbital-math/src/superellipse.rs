@@ -6,15 +6,16 @@
 //! When u > 2, the curve is "flatter" than a circle, concentrating liquidity
 //! around the equal price point (1:1 ratio for stablecoins).
 //!
-//! This implementation uses integer approximations for fractional powers.
-//! For production, consider using a fixed-point math library or moving
-//! complex calculations off-chain with on-chain verification.
+//! Fractional powers and roots come from [`crate::fixed_point`], so any `u`
+//! in basis points is evaluated as given rather than truncated to an
+//! integer exponent.
 
-use alloc::vec::Vec;
+use alloc::string::ToString;
 use alloy_primitives::U256;
 use crate::{
     error::{OrbitalError, Result},
-    utils::{pow, nth_root_approx, abs},
+    fixed::Wad,
+    fixed_point::{nth_root, pow_bp},
     MAX_TOKENS, MIN_TOKENS, BP_PRECISION,
 };
 
@@ -73,32 +74,11 @@ pub fn verify_superellipse_constraint(
         }
     }
 
-    // For u > 2, approximate with integer powers
-    // Σ(r_i^u) ≈ K
-    let u_int = u_parameter / 10000;
-    let u_frac = u_parameter % 10000;
-
+    // Σ(r_i^u) with the fractional exponent
     let mut sum = U256::ZERO;
     
     for &reserve in reserves {
-        // Calculate r^u_int (integer part)
-        let r_int_power = pow(reserve, u_int)?;
-        
-        // For fractional part, approximate r^(u_frac/10000)
-        // Using r^f ≈ r^(1/2) when f = 0.5
-        // This is simplified; production should use better approximation
-        let reserve_power = if u_frac == 0 {
-            r_int_power
-        } else if u_frac == 5000 {
-            // u_frac = 0.5, so multiply by sqrt(r)
-            let sqrt_r = crate::types::sqrt_approx(reserve);
-            r_int_power.checked_mul(sqrt_r)
-                .ok_or_else(|| OrbitalError::overflow("power with fraction"))?
-        } else {
-            // For other fractions, use integer approximation
-            // This loses precision but works for demonstration
-            r_int_power
-        };
+        let reserve_power = pow_bp(reserve, u_parameter)?;
 
         sum = sum.checked_add(reserve_power)
             .ok_or_else(|| OrbitalError::overflow("sum of powers"))?;
@@ -173,16 +153,13 @@ pub fn calculate_amount_out_superellipse(
         );
     }
 
-    // For u > 2, approximate the calculation
-    let u_int = u_parameter / 10000;
-    
     // Calculate new reserve for token_in
     let new_reserve_in = reserves[token_in]
         .checked_add(amount_in)
         .ok_or_else(|| OrbitalError::overflow("reserve_in + amount_in"))?;
 
     // Calculate (r_in + Δ_in)^u
-    let new_reserve_in_power = pow(new_reserve_in, u_int)?;
+    let new_reserve_in_power = pow_bp(new_reserve_in, u_parameter)?;
 
     // Calculate Σ(r_k^u for k ≠ out)
     let mut sum_other_powers = U256::ZERO;
@@ -194,7 +171,7 @@ pub fn calculate_amount_out_superellipse(
         let r_power = if i == token_in {
             new_reserve_in_power
         } else {
-            pow(r, u_int)?
+            pow_bp(r, u_parameter)?
         };
         
         sum_other_powers = sum_other_powers
@@ -210,8 +187,12 @@ pub fn calculate_amount_out_superellipse(
             available: reserves[token_out].to_string(),
         })?;
 
-    // new_r_out = (remaining)^(1/u)
-    let new_reserve_out = nth_root_approx(remaining, u_int)?;
+    // new_r_out = (remaining)^(1/u), rounded up so the pool keeps at least
+    // the reserve the curve requires
+    let mut new_reserve_out = nth_root(remaining, u_parameter)?;
+    if pow_bp(new_reserve_out, u_parameter)? < remaining {
+        new_reserve_out += U256::from(1);
+    }
 
     // Δ_out = r_out - new_r_out
     let amount_out = reserves[token_out]
@@ -263,31 +244,14 @@ pub fn calculate_price_superellipse(
         return crate::sphere::calculate_price_sphere(reserves, token_in, token_out);
     }
 
-    let u_int = u_parameter / 10000;
-    
     // Calculate r_in^(u-1) and r_out^(u-1)
-    let u_minus_1 = u_int.saturating_sub(1);
-    
-    let r_in_power = if u_minus_1 == 0 {
-        U256::from(1)
-    } else {
-        pow(reserve_in, u_minus_1)?
-    };
+    let u_minus_1 = u_parameter.saturating_sub(BP_PRECISION);
     
-    let r_out_power = if u_minus_1 == 0 {
-        U256::from(1)
-    } else {
-        pow(reserve_out, u_minus_1)?
-    };
-
-    // Price = r_in^(u-1) / r_out^(u-1) * PRECISION
-    let price = r_in_power
-        .checked_mul(U256::from(crate::PRECISION_MULTIPLIER))
-        .ok_or_else(|| OrbitalError::overflow("price calculation"))?
-        .checked_div(r_out_power)
-        .ok_or_else(|| OrbitalError::division_by_zero("price calculation"))?;
+    let r_in_power = pow_bp(reserve_in, u_minus_1)?;
+    let r_out_power = pow_bp(reserve_out, u_minus_1)?;
 
-    Ok(price)
+    // Price = r_in^(u-1) / r_out^(u-1)
+    Ok(Wad::from_ratio(r_in_power, r_out_power)?.raw())
 }
 
 /// Determine optimal u parameter based on expected volatility
@@ -356,8 +320,6 @@ pub fn superellipse_to_sphere_approximation(
         return Ok(k_constant); // Already a sphere
     }
 
-    let u_int = u_parameter / 10000;
-    
     // Rough approximation: R² ≈ K^(2/u) * N
     let n = U256::from(reserves.len());
     
@@ -388,8 +350,8 @@ mod tests {
     fn test_verify_superellipse_u25() {
         // u=2.5: more concentrated
         let reserves = vec![U256::from(10), U256::from(10), U256::from(10)];
-        // 10^2.5 + 10^2.5 + 10^2.5 ≈ 3 * 316 ≈ 948
-        let k = U256::from(1000); // Approximate
+        // 10^2.5 + 10^2.5 + 10^2.5 ≈ 3 * 316.23 ≈ 948.7
+        let k = U256::from(950); // Approximate
         
         // Should be within tolerance
         assert!(verify_superellipse_constraint(&reserves, 25000, k, 100).is_ok());
@@ -415,6 +377,24 @@ mod tests {
         assert!(amount_out < reserves[1]);
     }
 
+    #[test]
+    fn test_calculate_amount_out_fractional_u() {
+        let reserves = vec![U256::from(1_000_000u64), U256::from(1_000_000u64)];
+        let u = 25000;
+        let k = pow_bp(reserves[0], u).unwrap() + pow_bp(reserves[1], u).unwrap();
+
+        let amount_in = U256::from(10_000u64);
+        let amount_out = calculate_amount_out_superellipse(&reserves, 0, 1, amount_in, u, k).unwrap();
+
+        // (1.01^2.5 + x^2.5 = 2) gives x ≈ 0.98985
+        assert!(amount_out > U256::from(10_100u64) && amount_out < U256::from(10_200u64));
+
+        // The pool stays on or outside the curve, and one more unit out would not
+        let new_in = pow_bp(reserves[0] + amount_in, u).unwrap();
+        assert!(new_in + pow_bp(reserves[1] - amount_out, u).unwrap() >= k);
+        assert!(new_in + pow_bp(reserves[1] - amount_out - U256::from(1), u).unwrap() < k);
+    }
+
     #[test]
     fn test_optimal_u_for_volatility() {
         // High volatility
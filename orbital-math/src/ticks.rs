@@ -7,14 +7,27 @@
 //! Unlike Uniswap V3 where ticks are disjoint, Orbital ticks overlap with
 //! larger ticks containing smaller ones.
 
+use core::cmp::Reverse;
 use alloc::vec::Vec;
 use alloy_primitives::U256;
 use crate::{
     error::{OrbitalError, Result},
-    types::{Tick, ReservePoint, sqrt_approx},
-    utils::{dot_product, sum},
+    types::{Tick, ReservePoint},
+    utils::{sqrt, sqrt_ceil, sum},
 };
 
+/// Σr_i on a tick's boundary hyperplane: c·√N, rounded down
+///
+/// Taken as √(c²·N) so that √N keeps its fraction; flooring √N first would
+/// put every boundary of a 2- or 3-token pool at c.
+pub fn boundary_sum(plane_constant: U256, token_count: usize) -> Result<U256> {
+    let squared = plane_constant
+        .checked_mul(plane_constant)
+        .and_then(|c_squared| c_squared.checked_mul(U256::from(token_count)))
+        .ok_or_else(|| OrbitalError::overflow("boundary calculation"))?;
+    Ok(sqrt(squared))
+}
+
 /// Check if a reserve point is interior to a tick (not on boundary)
 ///
 /// # Arguments
@@ -29,16 +42,11 @@ use crate::{
 /// A point is interior if: r⃗ · 1⃗ < c * sqrt(N)
 /// where c is the plane constant and N is number of tokens
 pub fn is_interior_to_tick(reserves: &ReservePoint, tick: &Tick) -> Result<bool> {
-    let n = U256::from(reserves.dimensions());
-    
     // Calculate r⃗ · 1⃗ = Σr_i
     let dot_with_ones = sum(&reserves.reserves)?;
     
     // Calculate c * sqrt(N)
-    let sqrt_n = sqrt_approx(n);
-    let boundary = tick.plane_constant
-        .checked_mul(sqrt_n)
-        .ok_or_else(|| OrbitalError::overflow("boundary calculation"))?;
+    let boundary = boundary_sum(tick.plane_constant, reserves.dimensions())?;
     
     // Interior if dot product < boundary
     Ok(dot_with_ones < boundary)
@@ -46,12 +54,8 @@ pub fn is_interior_to_tick(reserves: &ReservePoint, tick: &Tick) -> Result<bool>
 
 /// Check if reserves are exactly on the tick boundary
 pub fn is_on_boundary(reserves: &ReservePoint, tick: &Tick) -> Result<bool> {
-    let n = U256::from(reserves.dimensions());
     let dot_with_ones = sum(&reserves.reserves)?;
-    let sqrt_n = sqrt_approx(n);
-    let boundary = tick.plane_constant
-        .checked_mul(sqrt_n)
-        .ok_or_else(|| OrbitalError::overflow("boundary calculation"))?;
+    let boundary = boundary_sum(tick.plane_constant, reserves.dimensions())?;
     
     // Check if within small tolerance (1 basis point)
     let tolerance = boundary / U256::from(10000);
@@ -77,14 +81,9 @@ pub fn is_on_boundary(reserves: &ReservePoint, tick: &Tick) -> Result<bool> {
 /// Normalized position = (r⃗ · 1⃗) / (R * sqrt(N))
 /// This allows comparing positions across ticks of different sizes
 pub fn normalized_position(reserves: &ReservePoint, radius: U256) -> Result<U256> {
-    let n = U256::from(reserves.dimensions());
-    let sqrt_n = sqrt_approx(n);
-    
     let dot_with_ones = sum(&reserves.reserves)?;
     
-    let denominator = radius
-        .checked_mul(sqrt_n)
-        .ok_or_else(|| OrbitalError::overflow("normalized position"))?;
+    let denominator = boundary_sum(radius, reserves.dimensions())?;
     
     if denominator.is_zero() {
         return Err(OrbitalError::division_by_zero("normalized position"));
@@ -116,17 +115,12 @@ pub fn find_next_crossing(
         return Ok(None);
     }
     
-    let n = U256::from(start.dimensions());
-    let sqrt_n = sqrt_approx(n);
-    
     let start_dot = sum(&start.reserves)?;
     let end_dot = sum(&end.reserves)?;
     
     // Find tick boundaries between start and end
     for (idx, tick) in ticks.iter().enumerate() {
-        let boundary = tick.plane_constant
-            .checked_mul(sqrt_n)
-            .ok_or_else(|| OrbitalError::overflow("boundary calculation"))?;
+        let boundary = boundary_sum(tick.plane_constant, start.dimensions())?;
         
         // Check if we cross this boundary
         let crosses = (start_dot <= boundary && end_dot > boundary) ||
@@ -149,14 +143,9 @@ pub fn crossing_fraction(
     end: &ReservePoint,
     tick: &Tick,
 ) -> Result<U256> {
-    let n = U256::from(start.dimensions());
-    let sqrt_n = sqrt_approx(n);
-    
     let start_dot = sum(&start.reserves)?;
     let end_dot = sum(&end.reserves)?;
-    let boundary = tick.plane_constant
-        .checked_mul(sqrt_n)
-        .ok_or_else(|| OrbitalError::overflow("boundary calculation"))?;
+    let boundary = boundary_sum(tick.plane_constant, start.dimensions())?;
     
     // Linear interpolation: boundary = start + t * (end - start)
     // Solve for t: t = (boundary - start) / (end - start)
@@ -228,6 +217,204 @@ pub fn calculate_capital_efficiency(
     Ok(efficiency_u32.min(5_000_000))
 }
 
+/// Efficiency is capped here (500x, scaled by 10000), as in
+/// [`calculate_capital_efficiency`]
+const MAX_EFFICIENCY: u32 = 5_000_000;
+
+/// A tick-bounded spherical cap: the points of the sphere whose projection
+/// onto the equal-price direction 1⃗/√N is at least `plane_constant`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickRange {
+    /// Plane constant c bounding the cap
+    pub plane_constant: U256,
+    /// Number of tokens N in the pool
+    pub token_count: usize,
+}
+
+impl TickRange {
+    /// The cap bounded by `tick` in a pool of `token_count` tokens
+    pub fn of(tick: &Tick, token_count: usize) -> Self {
+        Self { plane_constant: tick.plane_constant, token_count }
+    }
+}
+
+/// Virtual liquidity a cap provides per unit deposited, against full range
+///
+/// On a sphere of radius R the equal-price point holds q = R/√N of every
+/// token. Within a cap bounded at c, no token's reserve can fall below
+///
+/// x_min = (c - √((N-1)(R² - c²))) / √N
+///
+/// so that much of each token never has to be deposited: it is virtual.
+/// An LP supplying q - x_min per token gets the depth of q, an efficiency of
+/// q / (q - x_min) = R / (R - c + √((N-1)(R² - c²))).
+///
+/// # Returns
+/// * Efficiency multiplier scaled by 10000; 10000 when the cap reaches a
+///   zero reserve and is effectively full range. Rounded down, and capped at
+///   500x for caps that shrink to the equal-price point.
+pub fn capital_efficiency(tick_range: &TickRange, radius: U256) -> Result<u32> {
+    let (_, spread) = cap_geometry(tick_range, radius)?;
+    let c = tick_range.plane_constant;
+
+    // x_min <= 0: the cap touches a boundary of the positive orthant
+    if c <= spread {
+        return Ok(10000);
+    }
+
+    let denominator = radius - c + spread;
+    if denominator.is_zero() {
+        return Ok(MAX_EFFICIENCY);
+    }
+    let efficiency = radius
+        .checked_mul(U256::from(10000))
+        .ok_or_else(|| OrbitalError::overflow("capital efficiency"))?
+        / denominator;
+    Ok(efficiency.try_into().unwrap_or(u32::MAX).min(MAX_EFFICIENCY))
+}
+
+/// Per-token reserves of a cap at the equal-price point, split into what
+/// LPs deposit and what the cap's geometry supplies virtually
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapReserves {
+    /// q = R/√N, the reserve of each token at the equal-price point
+    pub equal_price_reserve: U256,
+    /// x_min, the part of q that never has to be deposited
+    pub virtual_reserve: U256,
+    /// q - x_min, the part LPs deposit
+    pub real_reserve: U256,
+}
+
+/// Split the equal-price reserves of a cap into real and virtual parts
+pub fn cap_reserves(tick_range: &TickRange, radius: U256) -> Result<CapReserves> {
+    let (sqrt_n, spread) = cap_geometry(tick_range, radius)?;
+    let c = tick_range.plane_constant;
+
+    // Divide by √N in 18-decimal fixed point
+    let precision = U256::from(crate::PRECISION_MULTIPLIER);
+    let equal_price_reserve = radius * precision / sqrt_n;
+    let virtual_reserve = c.saturating_sub(spread) * precision / sqrt_n;
+
+    Ok(CapReserves {
+        equal_price_reserve,
+        virtual_reserve,
+        real_reserve: equal_price_reserve - virtual_reserve.min(equal_price_reserve),
+    })
+}
+
+/// How one tick's liquidity contributes to the pool's depth
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapLiquidity {
+    /// Index of the tick in the slice analysed
+    pub tick_index: usize,
+    /// Plane constant of the tick
+    pub plane_constant: U256,
+    /// Liquidity actually deposited
+    pub liquidity: U256,
+    /// Efficiency of the tick's cap (scaled by 10000)
+    pub efficiency: u32,
+    /// Depth the deposit provides: liquidity × efficiency
+    pub virtual_liquidity: U256,
+    /// Share of the pool's virtual liquidity, in basis points
+    pub share_bp: u32,
+}
+
+/// Break the pool's virtual liquidity down by tick, innermost cap first
+pub fn liquidity_distribution(
+    ticks: &[Tick],
+    radius: U256,
+    token_count: usize,
+) -> Result<Vec<CapLiquidity>> {
+    let mut distribution = ticks
+        .iter()
+        .enumerate()
+        .map(|(tick_index, tick)| {
+            let efficiency = capital_efficiency(&TickRange::of(tick, token_count), radius)?;
+            let virtual_liquidity = tick.liquidity
+                .checked_mul(U256::from(efficiency))
+                .ok_or_else(|| OrbitalError::overflow("virtual liquidity"))?
+                / U256::from(10000);
+            Ok(CapLiquidity {
+                tick_index,
+                plane_constant: tick.plane_constant,
+                liquidity: tick.liquidity,
+                efficiency,
+                virtual_liquidity,
+                share_bp: 0,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let total = distribution
+        .iter()
+        .try_fold(U256::ZERO, |acc, cap| acc.checked_add(cap.virtual_liquidity))
+        .ok_or_else(|| OrbitalError::overflow("total virtual liquidity"))?;
+    if !total.is_zero() {
+        for cap in &mut distribution {
+            let share = cap.virtual_liquidity * U256::from(10000) / total;
+            cap.share_bp = share.try_into().unwrap_or(10000);
+        }
+    }
+
+    // Higher plane constants bound smaller caps around the equal-price point
+    distribution.sort_by_key(|t| Reverse(t.plane_constant));
+    Ok(distribution)
+}
+
+/// Expected fee APR of a position, in basis points
+///
+/// Fees are shared pro rata to virtual liquidity, so a position earns
+/// `annual_volume × fee_bp × share` against the `liquidity` it deposited.
+/// Assumes the price stays within the cap for the whole year.
+///
+/// # Arguments
+/// * `cap` - The position's entry from [`liquidity_distribution`]
+/// * `annual_volume` - Volume traded through the pool per year, in the same
+///   units as liquidity
+/// * `fee_bp` - Pool fee in basis points
+pub fn expected_fee_apr_bp(cap: &CapLiquidity, annual_volume: U256, fee_bp: u32) -> Result<u32> {
+    if cap.liquidity.is_zero() {
+        return Ok(0);
+    }
+
+    // fees × share / liquidity, with share and fee in basis points
+    let apr = annual_volume
+        .checked_mul(U256::from(fee_bp))
+        .and_then(|fees| fees.checked_mul(U256::from(cap.share_bp)))
+        .ok_or_else(|| OrbitalError::overflow("fee APR"))?
+        / U256::from(10000)
+        / cap.liquidity;
+    Ok(apr.try_into().unwrap_or(u32::MAX))
+}
+
+/// √N in 18-decimal fixed point and the spread √((N-1)(R² - c²)) of a cap
+fn cap_geometry(tick_range: &TickRange, radius: U256) -> Result<(U256, U256)> {
+    let n = tick_range.token_count;
+    if !(crate::MIN_TOKENS..=crate::MAX_TOKENS).contains(&n) {
+        return Err(OrbitalError::InvalidTokenCount(n));
+    }
+    let c = tick_range.plane_constant;
+    if c > radius {
+        return Err(OrbitalError::InvalidTick {
+            reason: "plane constant exceeds the sphere radius".into(),
+        });
+    }
+
+    let precision = U256::from(crate::PRECISION_MULTIPLIER);
+    let sqrt_n = sqrt(U256::from(n) * precision * precision);
+
+    // Rounded up, so efficiencies and virtual reserves err low
+    let gap = radius
+        .checked_mul(radius)
+        .ok_or_else(|| OrbitalError::overflow("radius squared"))?
+        - c * c;
+    let spread = sqrt_ceil(
+        gap.checked_mul(U256::from(n - 1))
+            .ok_or_else(|| OrbitalError::overflow("cap spread"))?,
+    );
+    Ok((sqrt_n, spread))
+}
+
 /// Optimize tick placement for a given liquidity amount and risk tolerance
 ///
 /// # Arguments
@@ -279,7 +466,7 @@ pub fn optimize_tick_placement(
 ///
 /// Returns ticks sorted from innermost (closest to equal price) to outermost
 pub fn sort_ticks_by_boundary(ticks: &mut [Tick]) {
-    ticks.sort_by(|a, b| a.plane_constant.cmp(&b.plane_constant));
+    ticks.sort_by_key(|t| t.plane_constant);
 }
 
 /// Merge overlapping ticks with similar boundaries
@@ -361,6 +548,46 @@ mod tests {
         ReservePoint::new(values.iter().map(|&v| U256::from(v)).collect())
     }
 
+    #[test]
+    fn test_capital_efficiency_of_caps() {
+        let radius = U256::from(1_000_000u64);
+        let range = |c: u64, n: usize| TickRange { plane_constant: U256::from(c), token_count: n };
+
+        // 5 tokens, c = 0.99R: about 3.42x
+        let efficiency = capital_efficiency(&range(990_000, 5), radius).unwrap();
+        assert!((34_200..=34_231).contains(&efficiency), "{}", efficiency);
+        // Tighter caps are more efficient; wide ones fall back to full range
+        assert!(capital_efficiency(&range(999_000, 5), radius).unwrap() > efficiency);
+        assert_eq!(capital_efficiency(&range(500_000, 5), radius).unwrap(), 10000);
+        assert_eq!(capital_efficiency(&range(1_000_000, 5), radius).unwrap(), 5_000_000);
+        assert!(capital_efficiency(&range(1_000_001, 5), radius).is_err());
+
+        // q / (q - x_min) agrees with the closed form
+        let reserves = cap_reserves(&range(990_000, 5), radius).unwrap();
+        let ratio = reserves.equal_price_reserve * U256::from(10000) / reserves.real_reserve;
+        let ratio: u32 = ratio.try_into().unwrap();
+        assert!(ratio.abs_diff(efficiency) <= 5);
+    }
+
+    #[test]
+    fn test_liquidity_distribution_and_apr() {
+        let radius = U256::from(1_000_000u64);
+        let ticks = vec![
+            Tick::new(U256::from(1), U256::from(500_000u64), U256::from(1000), U256::from(1_000u64), 9000),
+            Tick::new(U256::from(2), U256::from(990_000u64), U256::from(1000), U256::from(1_000u64), 9900),
+        ];
+
+        let distribution = liquidity_distribution(&ticks, radius, 5).unwrap();
+        // Innermost cap first, and it dominates the depth for equal deposits
+        assert_eq!(distribution[0].tick_index, 1);
+        assert!(distribution[0].share_bp > distribution[1].share_bp);
+        assert!(distribution[0].share_bp + distribution[1].share_bp <= 10000);
+
+        let tight = expected_fee_apr_bp(&distribution[0], U256::from(100_000u64), 30).unwrap();
+        let wide = expected_fee_apr_bp(&distribution[1], U256::from(100_000u64), 30).unwrap();
+        assert!(tight > wide);
+    }
+
     #[test]
     fn test_is_interior_to_tick() {
         let reserves = create_test_reserves(vec![100, 100, 100]);
@@ -413,7 +640,7 @@ mod tests {
         
         let tick = Tick::new(
             U256::from(1),
-            U256::from(80),  // Boundary between start and end
+            U256::from(200),  // Boundary 200·√3 ≈ 346, between 300 and 450
             U256::from(1000),
             U256::from(300),
             9500,
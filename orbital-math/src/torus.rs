@@ -0,0 +1,444 @@
+//! Toroidal liquidity: interior sphere and boundary circles
+//!
+//! Following the Orbital paper, every tick is a sphere of its own radius R
+//! (its `liquidity`) whose reserves stay in the cap α ≥ c, where α = r·1/√N
+//! is the projection onto the equal-price direction and c the tick's plane
+//! constant. While the pool price is inside the cap the tick is *interior*
+//! and trades along its sphere. Once α reaches c the tick is *pinned* to its
+//! boundary: α stays at c and the reserves move on the circle where the
+//! plane meets the sphere, of radius s = √(R² - c²) around (c/√N)·1.
+//!
+//! Ticks in the same regime consolidate. Interior ticks add up to one
+//! sphere of radius R_int; boundary ticks to a plane offset k_b = Σc and a
+//! circle radius s_b = Σs. Splitting the total reserves into α and the
+//! distance ‖w‖ from the equal-price line, the pool trades on the torus
+//!
+//! (α - k_b)² + (‖w‖ - s_b)² = R_int²
+//!
+//! which is the sphere again when no tick is pinned.
+//!
+//! An interior tick is pinned when the interior's normalised position
+//! (α - k_b) / R_int falls to its own c / R, and released when it rises back
+//! above. At that point the interior without the tick keeps the same
+//! normalised position, so one crossing never cascades into another.
+
+use alloc::{string::ToString, vec::Vec};
+use alloy_primitives::U256;
+use crate::{
+    error::{OrbitalError, Result},
+    sphere::calculate_amount_out_sphere,
+    types::Tick,
+    utils::sqrt,
+    MAX_TOKENS, MIN_TOKENS, PRECISION_MULTIPLIER,
+};
+
+/// The circle a pinned tick's reserves move on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundaryCircle {
+    /// Reserve of every token at the circle's centre, c/√N
+    pub center: U256,
+    /// Circle radius s = √(R² - c²)
+    pub radius: U256,
+}
+
+/// Consolidated interior and boundary liquidity of a pool's ticks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TorusState {
+    /// R_int, the summed radius of interior ticks
+    pub interior_radius: U256,
+    /// k_b, the summed plane constant of boundary ticks
+    pub boundary_plane: U256,
+    /// s_b, the summed circle radius of boundary ticks
+    pub boundary_radius: U256,
+}
+
+impl TorusState {
+    /// Consolidate `ticks` by their `is_boundary` flag, reading each tick's
+    /// `liquidity` as its radius
+    pub fn from_ticks(ticks: &[Tick]) -> Result<Self> {
+        let mut state = Self {
+            interior_radius: U256::ZERO,
+            boundary_plane: U256::ZERO,
+            boundary_radius: U256::ZERO,
+        };
+        for tick in ticks {
+            if tick.is_boundary {
+                state.boundary_plane += tick.plane_constant;
+                state.boundary_radius += circle_radius(tick.plane_constant, tick.liquidity)?;
+            } else {
+                state.interior_radius += tick.liquidity;
+            }
+        }
+        Ok(state)
+    }
+
+    /// Whether no tick is pinned, so the torus is a plain sphere
+    pub fn is_spherical(&self) -> bool {
+        self.boundary_plane.is_zero() && self.boundary_radius.is_zero()
+    }
+}
+
+/// A swap across the torus, split wherever a tick changed regime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TorusSwap {
+    /// Total output
+    pub amount_out: U256,
+    /// Indices of ticks that changed regime, in the order they did
+    pub transitions: Vec<usize>,
+}
+
+/// Boundary circle of a tick with `plane_constant` on a sphere of `radius`
+pub fn boundary_circle(plane_constant: U256, radius: U256, token_count: usize) -> Result<BoundaryCircle> {
+    validate_token_count(token_count)?;
+    Ok(BoundaryCircle {
+        center: plane_constant * U256::from(PRECISION_MULTIPLIER) / sqrt_n(token_count),
+        radius: circle_radius(plane_constant, radius)?,
+    })
+}
+
+/// Closest point to `reserves` on the boundary circle of a tick with
+/// `plane_constant` and `radius`
+///
+/// The projection keeps the direction of the reserves away from the
+/// equal-price line and rescales it to the circle:
+/// r' = (c/√N)·1 + s · w/‖w‖, with w = r - mean(r)·1.
+pub fn project_to_boundary(reserves: &[U256], plane_constant: U256, radius: U256) -> Result<Vec<U256>> {
+    let circle = boundary_circle(plane_constant, radius, reserves.len())?;
+    let n = U256::from(reserves.len());
+    let (sum, sum_of_squares) = sums(reserves)?;
+
+    // N‖w‖² = NΣr² - (Σr)²; zero at the equal-price point, which has no
+    // direction to project along
+    let spread = n * sum_of_squares - sum * sum;
+    if spread.is_zero() {
+        return Err(OrbitalError::invalid_param("reserves", "equal-price point has no projection onto the circle"));
+    }
+    // w_i = (N·r_i - Σr) / N and N‖w‖ = √(N · N‖w‖²)
+    let norm = sqrt(n * spread);
+
+    reserves
+        .iter()
+        .enumerate()
+        .map(|(token_index, &r)| {
+            let scaled = r * n;
+            let (deviation, above) = if scaled >= sum { (scaled - sum, true) } else { (sum - scaled, false) };
+            let offset = deviation
+                .checked_mul(circle.radius)
+                .ok_or_else(|| OrbitalError::overflow("boundary projection"))?
+                / norm;
+            if above {
+                Ok(circle.center + offset)
+            } else {
+                circle
+                    .center
+                    .checked_sub(offset)
+                    .ok_or(OrbitalError::NegativeReserve { token_index })
+            }
+        })
+        .collect()
+}
+
+/// Output of trading `amount_in` on the torus of `state`, rounded so the
+/// pool stays on or outside it
+///
+/// With no pinned ticks this is the sphere swap. Otherwise the output is
+/// found by bisection: the largest amount before the first point where
+/// (α - k_b)² + (‖w‖ - s_b)² drops below R_int².
+pub fn calculate_amount_out_torus(
+    reserves: &[U256],
+    state: &TorusState,
+    token_in: usize,
+    token_out: usize,
+    amount_in: U256,
+) -> Result<U256> {
+    validate_token_count(reserves.len())?;
+    if token_in >= reserves.len() || token_out >= reserves.len() {
+        return Err(OrbitalError::TokenIndexOutOfBounds {
+            index: token_in.max(token_out),
+            token_count: reserves.len(),
+        });
+    }
+    if token_in == token_out {
+        return Err(OrbitalError::invalid_param("tokens", "input and output must be different"));
+    }
+
+    let interior_squared = state
+        .interior_radius
+        .checked_mul(state.interior_radius)
+        .ok_or_else(|| OrbitalError::overflow("interior radius squared"))?;
+    if state.is_spherical() {
+        return calculate_amount_out_sphere(reserves, token_in, token_out, amount_in, interior_squared);
+    }
+    if amount_in.is_zero() {
+        return Ok(U256::ZERO);
+    }
+
+    let mut point = reserves.to_vec();
+    point[token_in] = point[token_in]
+        .checked_add(amount_in)
+        .ok_or_else(|| OrbitalError::overflow("reserve_in + amount_in"))?;
+    let mut outside = |amount_out: U256| -> Result<bool> {
+        point[token_out] = reserves[token_out] - amount_out;
+        Ok(torus_value(&point, state)? >= interior_squared)
+    };
+
+    if !outside(U256::ZERO)? {
+        return Err(OrbitalError::InvariantViolation { amount: amount_in.to_string() });
+    }
+    // The line of the trade can meet the torus more than once; bracket the
+    // first crossing by doubling before bisecting
+    let (mut low, mut high) = (U256::ZERO, U256::from(1).min(reserves[token_out]));
+    while outside(high)? {
+        if high >= reserves[token_out] {
+            return Err(OrbitalError::InsufficientLiquidity {
+                needed: amount_in.to_string(),
+                available: reserves[token_out].to_string(),
+            });
+        }
+        low = high;
+        high = high.saturating_mul(U256::from(2)).min(reserves[token_out]);
+    }
+    while high - low > U256::from(1) {
+        let mid = low + ((high - low) >> 1);
+        if outside(mid)? {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
+/// The interior's normalised position (α - k_b) / R_int in 18-decimal fixed
+/// point; one at the equal-price point, falling as prices diverge
+pub fn interior_position(reserves: &[U256], state: &TorusState) -> Result<U256> {
+    if state.interior_radius.is_zero() {
+        return Err(OrbitalError::division_by_zero("interior position without interior liquidity"));
+    }
+    let (alpha, _) = position(reserves)?;
+    Ok(alpha.saturating_sub(state.boundary_plane) * U256::from(PRECISION_MULTIPLIER) / state.interior_radius)
+}
+
+/// Whether `tick` is pinned at the interior position `position`: its own
+/// normalised boundary c / R is at or above it
+pub fn is_pinned(tick: &Tick, position: U256) -> Result<bool> {
+    if tick.liquidity.is_zero() {
+        return Err(OrbitalError::InvalidTick { reason: "tick has no radius".into() });
+    }
+    Ok(tick.plane_constant * U256::from(PRECISION_MULTIPLIER) / tick.liquidity >= position)
+}
+
+/// Bring every tick's `is_boundary` flag in line with `reserves`, returning
+/// the indices of the ticks that changed regime
+pub fn apply_transitions(ticks: &mut [Tick], reserves: &[U256]) -> Result<Vec<usize>> {
+    let position = interior_position(reserves, &TorusState::from_ticks(ticks)?)?;
+    let mut changed = Vec::new();
+    for (index, tick) in ticks.iter_mut().enumerate() {
+        let pinned = is_pinned(tick, position)?;
+        if pinned != tick.is_boundary {
+            tick.is_boundary = pinned;
+            changed.push(index);
+        }
+    }
+    Ok(changed)
+}
+
+/// Trade `amount_in` across the torus, updating `reserves` and switching
+/// ticks between interior and boundary as the trade crosses them
+///
+/// Each leg runs until the next tick changes regime, found by bisection on
+/// the input, so no leg is priced with the wrong consolidation.
+pub fn execute_torus_swap(
+    reserves: &mut [U256],
+    ticks: &mut [Tick],
+    token_in: usize,
+    token_out: usize,
+    amount_in: U256,
+) -> Result<TorusSwap> {
+    let mut transitions = apply_transitions(ticks, reserves)?;
+    let mut remaining = amount_in;
+    let mut amount_out = U256::ZERO;
+
+    for _ in 0..=ticks.len() {
+        if remaining.is_zero() {
+            break;
+        }
+        let state = TorusState::from_ticks(ticks)?;
+        let position_after = |input: U256| -> Result<(U256, Vec<U256>)> {
+            let output = calculate_amount_out_torus(reserves, &state, token_in, token_out, input)?;
+            let mut after = reserves.to_vec();
+            after[token_in] += input;
+            after[token_out] -= output;
+            Ok((output, after))
+        };
+        let flips = |after: &[U256]| -> Result<Option<usize>> {
+            let position = interior_position(after, &state)?;
+            for (index, tick) in ticks.iter().enumerate() {
+                if is_pinned(tick, position)? != tick.is_boundary {
+                    return Ok(Some(index));
+                }
+            }
+            Ok(None)
+        };
+
+        let (output, after) = position_after(remaining)?;
+        if flips(&after)?.is_none() {
+            reserves.copy_from_slice(&after);
+            amount_out += output;
+            remaining = U256::ZERO;
+            break;
+        }
+
+        // Smallest input at which some tick changes regime
+        let (mut low, mut high) = (U256::ZERO, remaining);
+        while high - low > U256::from(1) {
+            let mid = low + ((high - low) >> 1);
+            if flips(&position_after(mid)?.1)?.is_some() {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        let (output, after) = position_after(high)?;
+        let index = flips(&after)?.expect("bisection ends on a flip");
+        reserves.copy_from_slice(&after);
+        amount_out += output;
+        remaining -= high;
+
+        ticks[index].is_boundary = !ticks[index].is_boundary;
+        transitions.push(index);
+    }
+
+    if !remaining.is_zero() {
+        return Err(OrbitalError::computation("torus swap did not settle within one leg per tick"));
+    }
+    Ok(TorusSwap { amount_out, transitions })
+}
+
+/// (α - k_b)² + (‖w‖ - s_b)² for the total reserves
+fn torus_value(reserves: &[U256], state: &TorusState) -> Result<U256> {
+    let (alpha, perpendicular) = position(reserves)?;
+    let along = abs_diff(alpha, state.boundary_plane);
+    let across = abs_diff(perpendicular, state.boundary_radius);
+    along
+        .checked_mul(along)
+        .and_then(|a| across.checked_mul(across).and_then(|b| a.checked_add(b)))
+        .ok_or_else(|| OrbitalError::overflow("torus invariant"))
+}
+
+/// α = Σr/√N and ‖w‖ = √(Σr² - α²), both rounded down
+fn position(reserves: &[U256]) -> Result<(U256, U256)> {
+    let n = U256::from(reserves.len());
+    let (sum, sum_of_squares) = sums(reserves)?;
+    let alpha = sum
+        .checked_mul(U256::from(PRECISION_MULTIPLIER))
+        .ok_or_else(|| OrbitalError::overflow("equal-price projection"))?
+        / sqrt_n(reserves.len());
+    let spread = sum_of_squares
+        .checked_mul(n)
+        .and_then(|scaled| scaled.checked_sub(sum * sum))
+        .ok_or_else(|| OrbitalError::overflow("distance from equal-price line"))?;
+    Ok((alpha, sqrt(spread / n)))
+}
+
+fn sums(reserves: &[U256]) -> Result<(U256, U256)> {
+    reserves.iter().try_fold((U256::ZERO, U256::ZERO), |(sum, squares), &r| {
+        let square = r.checked_mul(r).ok_or_else(|| OrbitalError::overflow("reserve squared"))?;
+        Ok((
+            sum.checked_add(r).ok_or_else(|| OrbitalError::overflow("sum of reserves"))?,
+            squares.checked_add(square).ok_or_else(|| OrbitalError::overflow("sum of squares"))?,
+        ))
+    })
+}
+
+/// s = √(R² - c²)
+fn circle_radius(plane_constant: U256, radius: U256) -> Result<U256> {
+    if plane_constant > radius {
+        return Err(OrbitalError::InvalidTick {
+            reason: "plane constant exceeds the sphere radius".into(),
+        });
+    }
+    let radius_squared = radius
+        .checked_mul(radius)
+        .ok_or_else(|| OrbitalError::overflow("radius squared"))?;
+    Ok(sqrt(radius_squared - plane_constant * plane_constant))
+}
+
+/// √N in 18-decimal fixed point
+fn sqrt_n(token_count: usize) -> U256 {
+    let precision = U256::from(PRECISION_MULTIPLIER);
+    sqrt(U256::from(token_count) * precision * precision)
+}
+
+fn validate_token_count(token_count: usize) -> Result<()> {
+    if !(MIN_TOKENS..=MAX_TOKENS).contains(&token_count) {
+        return Err(OrbitalError::InvalidTokenCount(token_count));
+    }
+    Ok(())
+}
+
+fn abs_diff(a: U256, b: U256) -> U256 {
+    if a > b { a - b } else { b - a }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reserves(values: &[u64]) -> Vec<U256> {
+        values.iter().map(|&v| U256::from(v)).collect()
+    }
+
+    #[test]
+    fn test_projection_lands_on_circle() {
+        // R = 1000 around c = 800 in 2D: the circle is the two points where
+        // x + y = 800·√2 meets x² + y² = 1000²
+        let circle = boundary_circle(U256::from(800), U256::from(1000), 2).unwrap();
+        assert_eq!(circle.center, U256::from(565)); // 800 / √2
+        assert_eq!(circle.radius, U256::from(600));
+
+        let projected = project_to_boundary(&reserves(&[900, 100]), U256::from(800), U256::from(1000)).unwrap();
+        assert_eq!(projected, reserves(&[565 + 424, 565 - 424])); // 600 / √2 = 424.26
+        let (sum, squares) = sums(&projected).unwrap();
+        assert!(abs_diff(sum, U256::from(1131)) <= U256::from(2));
+        assert!(abs_diff(squares, U256::from(1_000_000)) <= U256::from(4_000));
+
+        assert!(project_to_boundary(&reserves(&[500, 500]), U256::from(800), U256::from(1000)).is_err());
+    }
+
+    #[test]
+    fn test_swap_pins_tick_at_its_boundary() {
+        let scale = 1_000_000u64;
+        // Two ticks of radius 1000 on a 3-token pool at the equal-price point;
+        // the narrow one pins once α/R falls to 0.99
+        let mut ticks = alloc::vec![
+            Tick::new(U256::from(1), U256::from(990 * scale), U256::from(1000 * scale), U256::ZERO, 9900),
+            Tick::new(U256::from(2), U256::from(800 * scale), U256::from(1000 * scale), U256::ZERO, 9000),
+        ];
+        let interior = U256::from(2000 * scale);
+        let per_token = sqrt(interior * interior / U256::from(3)); // 2R/√3
+        let mut pool = alloc::vec![per_token; 3];
+
+        let state = TorusState::from_ticks(&ticks).unwrap();
+        assert!(state.is_spherical());
+        assert!(apply_transitions(&mut ticks, &pool).unwrap().is_empty());
+
+        // A sphere-only swap matches the sphere
+        let small = U256::from(1_000u64);
+        let sphere_out = calculate_amount_out_sphere(&pool, 0, 1, small, state.interior_radius * state.interior_radius).unwrap();
+        assert_eq!(calculate_amount_out_torus(&pool, &state, 0, 1, small).unwrap(), sphere_out);
+
+        // A large trade drives prices apart until the narrow tick pins
+        let swap = execute_torus_swap(&mut pool, &mut ticks, 0, 1, U256::from(200 * scale)).unwrap();
+        assert_eq!(swap.transitions, alloc::vec![0]);
+        assert!(ticks[0].is_boundary && !ticks[1].is_boundary);
+        assert!(swap.amount_out > U256::ZERO);
+
+        // The pool ends on the torus of the new consolidation
+        let pinned = TorusState::from_ticks(&ticks).unwrap();
+        assert_eq!(pinned.interior_radius, U256::from(1000 * scale));
+        let value = torus_value(&pool, &pinned).unwrap();
+        let target = pinned.interior_radius * pinned.interior_radius;
+        assert!(value >= target && value - target <= target / U256::from(10_000));
+    }
+}
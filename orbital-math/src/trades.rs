@@ -8,16 +8,16 @@
 //! The toroidal surface enables capital efficient trading while maintaining
 //! the N-dimensional spherical invariant across all market conditions.
 
-use alloc::vec::Vec;
+use alloc::{format, string::ToString, vec, vec::Vec};
 use alloy_primitives::U256;
 use crate::{
     error::{OrbitalError, Result},
-    types::{PoolState, Tick, ReservePoint, TradeInfo, CurveType, sqrt_approx},
+    fixed::Wad,
+    types::{PoolState, Tick, ReservePoint, TradeInfo, CurveType},
     sphere::{self, calculate_amount_out_sphere, calculate_price_sphere, verify_sphere_constraint},
     superellipse::{self, calculate_amount_out_superellipse},
-    ticks::{self, find_next_crossing, crossing_fraction, is_interior_to_tick},
-    utils::{sum, dot_product},
-    PRECISION_MULTIPLIER,
+    ticks::{self, active_liquidity_at_point, boundary_sum, find_next_crossing, crossing_fraction},
+    utils::sqrt,
 };
 
 /// Execute a swap on the toroidal trading surface with tick boundary crossing
@@ -109,6 +109,11 @@ fn execute_toroidal_route(
         return execute_simple_trade(pool, token_in, token_out, amount_in);
     }
     
+    // On the sphere, boundary crossings have a closed form
+    if let CurveType::Sphere = pool.curve_type {
+        return execute_segmented_trade(pool, token_in, token_out, amount_in);
+    }
+    
     // Complex case: route through ticks with boundary crossing
     while remaining_amount > U256::ZERO {
         let current_reserves = ReservePoint::new(pool.reserves.reserves.clone());
@@ -185,6 +190,197 @@ fn execute_toroidal_route(
     Ok((total_output, ticks_crossed, total_fee))
 }
 
+/// One leg of a trade that stays within a single set of active ticks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeSegment {
+    /// Input consumed by this leg
+    pub amount_in: U256,
+    /// Output of this leg, before fees
+    pub amount_out: U256,
+    /// Liquidity of the ticks active while this leg trades
+    pub active_liquidity: U256,
+    /// Tick whose boundary the leg ends on, if it stops at one
+    pub crossed_tick: Option<usize>,
+}
+
+/// A swap split at every tick boundary it crosses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentedTrade {
+    /// Legs in execution order; all but possibly the last end on a boundary
+    pub segments: Vec<TradeSegment>,
+    /// Sum of the legs' outputs, before fees
+    pub total_out: U256,
+}
+
+impl SegmentedTrade {
+    /// Number of tick boundaries the trade crosses
+    pub fn ticks_crossed(&self) -> usize {
+        self.segments.iter().filter(|segment| segment.crossed_tick.is_some()).count()
+    }
+}
+
+/// Split a sphere swap of `amount_in` at each tick boundary it crosses
+///
+/// A tick's boundary is the hyperplane Σr_k = c·√N. Trading `token_in` for
+/// `token_out` moves only (x, y) = (r_in, r_out) along the circle
+/// x² + y² = R² - Σ_{k≠in,out} r_k², so the point where the trade meets a
+/// boundary solves x + y = s together with the circle:
+///
+/// x = (s ± √(2C - s²)) / 2
+///
+/// The nearest such x beyond the current r_in ends the current leg. Both
+/// roots are checked because Σr_k is not monotonic along the trade: it
+/// rises while r_in < r_out and falls after, so a trade can cross the same
+/// boundary twice.
+///
+/// Each leg's output comes from [`calculate_amount_out_sphere`] from where
+/// the previous leg ended, so rounding stays against the trader per leg.
+pub fn solve_segmented_trade(
+    reserves: &[U256],
+    radius_squared: U256,
+    tick_set: &[Tick],
+    token_in: usize,
+    token_out: usize,
+    amount_in: U256,
+) -> Result<SegmentedTrade> {
+    if token_in >= reserves.len() || token_out >= reserves.len() {
+        return Err(OrbitalError::TokenIndexOutOfBounds {
+            index: token_in.max(token_out),
+            token_count: reserves.len(),
+        });
+    }
+    if token_in == token_out {
+        return Err(OrbitalError::invalid_param("tokens", "input and output must be different"));
+    }
+
+    let mut current = reserves.to_vec();
+    let mut remaining = amount_in;
+    let mut segments = Vec::new();
+    let mut total_out = U256::ZERO;
+
+    // Tokens other than the pair stay fixed for the whole trade
+    let (others_sum, others_squares) = current
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != token_in && i != token_out)
+        .try_fold((U256::ZERO, U256::ZERO), |(total, squares), (_, &r)| {
+            let square = r.checked_mul(r).ok_or_else(|| OrbitalError::overflow("reserve squared"))?;
+            Ok::<_, OrbitalError>((total + r, squares + square))
+        })?;
+    let circle = radius_squared
+        .checked_sub(others_squares)
+        .ok_or_else(|| OrbitalError::invalid_param("radius_squared", "smaller than the fixed reserves"))?;
+
+    while !remaining.is_zero() {
+        let start_in = current[token_in];
+        let end_in = start_in
+            .checked_add(remaining)
+            .ok_or_else(|| OrbitalError::overflow("reserve_in + amount_in"))?;
+
+        // Earliest boundary strictly ahead of the current point, within this trade
+        let mut next: Option<(U256, usize)> = None;
+        for (idx, tick) in tick_set.iter().enumerate() {
+            let boundary = boundary_sum(tick.plane_constant, reserves.len())?;
+            for x in boundary_intersections(boundary, others_sum, circle) {
+                if x > start_in && x < end_in && next.is_none_or(|(best, _)| x < best) {
+                    next = Some((x, idx));
+                }
+            }
+        }
+
+        let active_liquidity = active_liquidity_at_point(&ReservePoint::new(current.clone()), tick_set)?;
+        let (leg_in, crossed_tick) = match next {
+            Some((x, idx)) => (x - start_in, Some(idx)),
+            None => (remaining, None),
+        };
+        let leg_out = calculate_amount_out_sphere(&current, token_in, token_out, leg_in, radius_squared)?;
+
+        current[token_in] += leg_in;
+        current[token_out] -= leg_out;
+        remaining -= leg_in;
+        total_out = total_out
+            .checked_add(leg_out)
+            .ok_or_else(|| OrbitalError::overflow("total output"))?;
+        segments.push(TradeSegment { amount_in: leg_in, amount_out: leg_out, active_liquidity, crossed_tick });
+    }
+
+    Ok(SegmentedTrade { segments, total_out })
+}
+
+/// Values of r_in where x + y = boundary - others_sum meets x² + y² = circle
+fn boundary_intersections(boundary: U256, others_sum: U256, circle: U256) -> Vec<U256> {
+    let Some(s) = boundary.checked_sub(others_sum) else {
+        return Vec::new();
+    };
+    // 2C - s² < 0 means the hyperplane misses the circle
+    let Some(discriminant) = s
+        .checked_mul(s)
+        .and_then(|s_squared| circle.checked_mul(U256::from(2))?.checked_sub(s_squared))
+    else {
+        return Vec::new();
+    };
+
+    // Roots need x >= 0 and y = s - x >= 0 to lie in the pool's quadrant
+    let root = sqrt(discriminant);
+    if s < root {
+        return Vec::new();
+    }
+    let mut roots = vec![(s - root) / U256::from(2)];
+    if !root.is_zero() {
+        roots.push((s + root) / U256::from(2));
+    }
+    roots
+}
+
+/// Execute a sphere trade leg by leg across tick boundaries
+fn execute_segmented_trade(
+    pool: &mut PoolState,
+    token_in: usize,
+    token_out: usize,
+    amount_in: U256,
+) -> Result<(U256, usize, U256)> {
+    let trade = solve_segmented_trade(
+        &pool.reserves.reserves,
+        pool.invariant,
+        &pool.ticks,
+        token_in,
+        token_out,
+        amount_in,
+    )?;
+
+    let mut total_output = U256::ZERO;
+    let mut total_fee = U256::ZERO;
+    for segment in &trade.segments {
+        let fee = match segment.crossed_tick {
+            Some(_) => calculate_boundary_crossing_fee(pool, segment.amount_in)?,
+            None => calculate_dynamic_fee(pool, segment.amount_in)?,
+        };
+        let amount_out_after_fee = segment.amount_out.saturating_sub(fee);
+
+        pool.reserves.reserves[token_in] = pool.reserves.reserves[token_in]
+            .checked_add(segment.amount_in)
+            .ok_or_else(|| OrbitalError::overflow("reserve update"))?;
+        pool.reserves.reserves[token_out] = pool.reserves.reserves[token_out]
+            .checked_sub(amount_out_after_fee)
+            .ok_or_else(|| OrbitalError::InsufficientLiquidity {
+                needed: amount_out_after_fee.to_string(),
+                available: pool.reserves.reserves[token_out].to_string(),
+            })?;
+
+        if let Some(tick_idx) = segment.crossed_tick {
+            update_tick_boundary_state(pool, tick_idx)?;
+        }
+
+        total_output = total_output.checked_add(amount_out_after_fee)
+            .ok_or_else(|| OrbitalError::overflow("total output"))?;
+        total_fee = total_fee.checked_add(fee)
+            .ok_or_else(|| OrbitalError::overflow("total fee"))?;
+    }
+
+    pool.update_reserves(pool.reserves.reserves.clone());
+    Ok((total_output, trade.ticks_crossed(), total_fee))
+}
+
 /// Execute simple trade without tick boundary crossing
 fn execute_simple_trade(
     pool: &mut PoolState,
@@ -290,7 +486,7 @@ fn execute_partial_trade_to_boundary(
     let fraction = crossing_fraction(&current_reserves, &target_reserves, tick)?;
     
     // Calculate partial amount to boundary
-    let partial_amount_in = (max_amount_in * fraction) / U256::from(PRECISION_MULTIPLIER);
+    let partial_amount_in = Wad::from_raw(fraction).mul_int(max_amount_in)?;
     
     // Execute trade up to boundary
     let partial_amount_out = match pool.curve_type {
@@ -405,9 +601,7 @@ fn calculate_consumed_amount(
 ) -> Result<U256> {
     // This is an approximation - in production use more precise calculation
     let price = calculate_price_sphere(reserves, token_in, token_out)?;
-    let consumed = (amount_out * U256::from(PRECISION_MULTIPLIER)) / price;
-    
-    Ok(consumed)
+    Wad::from_raw(price).div_into(amount_out)
 }
 
 /// Calculate price impact in basis points
@@ -472,7 +666,7 @@ fn verify_trade_invariant(pool: &PoolState) -> Result<()> {
             verify_sphere_constraint(
                 &pool.reserves.reserves,
                 pool.invariant,
-                100, // 1% tolerance for numerical precision
+                sphere::TolerancePolicy::CONTRACT.tolerance_bp,
             )
         }
         CurveType::Superellipse { u_parameter } => {
@@ -561,7 +755,8 @@ pub fn execute_multi_hop_swap(
     })
 }
 
-/// Calculate optimal route for maximum output
+/// Calculate optimal route for maximum output within one pool; see
+/// [`crate::routing`] for routes across pools and chains
 ///
 /// Uses dynamic programming to find the path that maximizes output
 /// across all possible routes in the N-dimensional space.
@@ -601,10 +796,9 @@ pub fn calculate_optimal_route(
         }
         
         // Calculate two-hop route: token_in -> intermediate -> token_out
-        if let (Ok(amount_intermediate), Ok(amount_final)) = (
-            calculate_trade_output(pool, token_in, intermediate, amount_in),
-            calculate_trade_output(pool, intermediate, token_out, amount_intermediate),
-        ) {
+        if let Ok(amount_final) = calculate_trade_output(pool, token_in, intermediate, amount_in)
+            .and_then(|amount_intermediate| calculate_trade_output(pool, intermediate, token_out, amount_intermediate))
+        {
             if amount_final > best_output {
                 best_output = amount_final;
                 best_route = vec![token_in, intermediate, token_out];
@@ -648,7 +842,7 @@ fn calculate_trade_output(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{CurveType, PoolState};
+    use crate::types::{CurveType, PoolState, Tick};
 
     fn create_test_pool() -> PoolState {
         let reserves = vec![
@@ -782,6 +976,43 @@ mod tests {
         assert!(matches!(result.unwrap_err(), OrbitalError::InvalidParameter { .. }));
     }
 
+    #[test]
+    fn test_segmented_trade_crosses_each_boundary() {
+        // 600² + 800² = 1000²; Σr peaks at x = y ≈ 707 and falls after
+        let reserves = vec![U256::from(600), U256::from(800)];
+        let radius_squared = U256::from(1_000_000);
+        let tick_set = vec![
+            // Boundaries at Σr = ⌊c·√2⌋ = 1380 and 1409
+            Tick::new(U256::from(1), U256::from(976), U256::from(1000), U256::from(300), 9500),
+            Tick::new(U256::from(2), U256::from(997), U256::from(1000), U256::from(300), 9500),
+        ];
+
+        let trade = solve_segmented_trade(&reserves, radius_squared, &tick_set, 0, 1, U256::from(300)).unwrap();
+
+        // The 1409 plane is crossed on the way up and again on the way down
+        let crossed: Vec<Option<usize>> = trade.segments.iter().map(|s| s.crossed_tick).collect();
+        assert_eq!(crossed, vec![Some(1), Some(1), Some(0), None]);
+        let legs_in: Vec<U256> = trade.segments.iter().map(|s| s.amount_in).collect();
+        assert_eq!(legs_in, vec![U256::from(44), U256::from(121), U256::from(79), U256::from(56)]);
+        assert_eq!(trade.ticks_crossed(), 3);
+
+        // Splitting the trade does not change what it pays in total
+        let direct = calculate_amount_out_sphere(&reserves, 0, 1, U256::from(300), radius_squared).unwrap();
+        assert_eq!(trade.total_out, direct);
+    }
+
+    #[test]
+    fn test_segmented_trade_without_crossing() {
+        let reserves = vec![U256::from(600), U256::from(800)];
+        let radius_squared = U256::from(1_000_000);
+        let far_tick = vec![Tick::new(U256::from(1), U256::from(5000), U256::from(1000), U256::from(300), 9500)];
+
+        let trade = solve_segmented_trade(&reserves, radius_squared, &far_tick, 0, 1, U256::from(100)).unwrap();
+        assert_eq!(trade.segments.len(), 1);
+        assert_eq!(trade.ticks_crossed(), 0);
+        assert_eq!(trade.segments[0].amount_in, U256::from(100));
+    }
+
     #[test]
     fn test_dynamic_fee_calculation() {
         let pool = create_test_pool();
@@ -1,161 +1,210 @@
-//! Error types for orbital math operations
-
-use alloc::string::String;
-use thiserror::Error;
-
-/// Result type alias using [`OrbitalError`]
-pub type Result<T> = core::result::Result<T, OrbitalError>;
-
-/// Errors that can occur during orbital math operations
-#[derive(Error, Debug, Clone, PartialEq)]
-pub enum OrbitalError {
-    /// Invalid number of tokens (must be between MIN_TOKENS and MAX_TOKENS)
-    #[error("Invalid token count: {0}. Must be between 2 and 1000")]
-    InvalidTokenCount(usize),
-
-    /// Reserves don't satisfy the sphere constraint
-    #[error("Sphere constraint violated: sum of squares {actual} != expected {expected}")]
-    SphereConstraintViolation { actual: String, expected: String },
-
-    /// Superellipse constraint violated
-    #[error("Superellipse constraint violated with u={u}")]
-    SuperellipseConstraintViolation { u: String },
-
-    /// Zero reserve detected where positive value required
-    #[error("Zero reserve for token {token_index}")]
-    ZeroReserve { token_index: usize },
-
-    /// Negative reserve (should never happen with U256 but checking overflow)
-    #[error("Negative reserve detected for token {token_index}")]
-    NegativeReserve { token_index: usize },
-
-    /// Amount would cause reserves to violate invariant
-    #[error("Trade amount {amount} would violate pool invariant")]
-    InvariantViolation { amount: String },
-
-    /// Insufficient liquidity for requested trade
-    #[error("Insufficient liquidity: needed {needed}, available {available}")]
-    InsufficientLiquidity { needed: String, available: String },
-
-    /// Tick boundary crossed during calculation
-    #[error("Tick boundary crossed unexpectedly at tick {tick_id}")]
-    UnexpectedTickCrossing { tick_id: String },
-
-    /// Invalid tick configuration
-    #[error("Invalid tick: {reason}")]
-    InvalidTick { reason: String },
-
-    /// Tick overlap detected where it shouldn't exist
-    #[error("Tick {tick_a} overlaps with tick {tick_b}")]
-    TickOverlap { tick_a: String, tick_b: String },
-
-    /// Mathematical overflow in calculation
-    #[error("Overflow in calculation: {operation}")]
-    Overflow { operation: String },
-
-    /// Mathematical underflow in calculation
-    #[error("Underflow in calculation: {operation}")]
-    Underflow { operation: String },
-
-    /// Division by zero
-    #[error("Division by zero in operation: {operation}")]
-    DivisionByZero { operation: String },
-
-    /// Square root of negative number
-    #[error("Square root of negative: {value}")]
-    NegativeSquareRoot { value: String },
+//! Core types shared across the library
+//!
+//! A pool is a [`ReservePoint`] on the curve named by its [`CurveType`],
+//! the invariant that curve must keep, and the [`Tick`]s that concentrate
+//! its liquidity. Trades report what they did as a [`TradeInfo`].
+
+use alloc::vec::Vec;
+use alloy_primitives::U256;
+use crate::utils::sqrt;
+
+/// Curve the reserves of a pool must stay on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveType {
+    /// Σ(r_i²) = R²
+    Sphere,
+    /// Σ(r_i^u) = K
+    Superellipse {
+        /// Exponent u, scaled by 10000 (20000 is the sphere)
+        u_parameter: u32,
+    },
+}
 
-    /// Invalid parameter value
-    #[error("Invalid parameter {param}: {reason}")]
-    InvalidParameter { param: String, reason: String },
+impl CurveType {
+    /// The spherical curve
+    pub fn sphere() -> Self {
+        Self::Sphere
+    }
 
-    /// Numerical precision loss too high
-    #[error("Precision loss exceeds tolerance: {loss}%")]
-    PrecisionLoss { loss: String },
+    /// A superellipse with exponent `u_parameter` (scaled by 10000)
+    pub fn superellipse(u_parameter: u32) -> Self {
+        Self::Superellipse { u_parameter }
+    }
+}
 
-    /// Token index out of bounds
-    #[error("Token index {index} out of bounds (pool has {token_count} tokens)")]
-    TokenIndexOutOfBounds { index: usize, token_count: usize },
+/// Reserves of every token in a pool, as a point in N dimensions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservePoint {
+    /// Reserve of each token, in pool token order
+    pub reserves: Vec<U256>,
+}
 
-    /// Price impact too high
-    #[error("Price impact {impact}% exceeds maximum allowed {max}%")]
-    ExcessivePriceImpact { impact: String, max: String },
+impl ReservePoint {
+    /// Point at `reserves`
+    pub fn new(reserves: Vec<U256>) -> Self {
+        Self { reserves }
+    }
 
-    /// Slippage tolerance exceeded
-    #[error("Slippage {actual}% exceeds tolerance {tolerance}%")]
-    SlippageExceeded { actual: String, tolerance: String },
+    /// Number of tokens N
+    pub fn dimensions(&self) -> usize {
+        self.reserves.len()
+    }
 
-    /// Cannot solve equation (e.g., quartic equation has no real positive roots)
-    #[error("Cannot solve {equation}: no valid solution found")]
-    NoSolution { equation: String },
+    /// Whether every reserve is non-zero
+    pub fn all_positive(&self) -> bool {
+        self.reserves.iter().all(|r| !r.is_zero())
+    }
+}
 
-    /// Generic computation error
-    #[error("Computation error: {details}")]
-    ComputationError { details: String },
+/// A spherical cap of concentrated liquidity
+///
+/// The cap holds the points of the sphere whose projection onto the
+/// equal-price direction 1⃗/√N is at least `plane_constant`, i.e. those with
+/// Σr_i >= c·√N. Higher plane constants bound smaller caps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tick {
+    /// Identifier of the tick
+    pub tick_id: U256,
+    /// Plane constant c of the bounding hyperplane
+    pub plane_constant: U256,
+    /// Liquidity deposited in the tick
+    pub liquidity: U256,
+    /// Radius R of the sphere the tick is cut from
+    pub radius: U256,
+    /// Lowest price the tick supports, in basis points of par
+    pub depeg_limit: u32,
+    /// Whether reserves currently sit on the tick's boundary
+    pub is_boundary: bool,
 }
 
-impl OrbitalError {
-    /// Create an overflow error for a specific operation
-    pub fn overflow(operation: impl Into<String>) -> Self {
-        Self::Overflow {
-            operation: operation.into(),
+impl Tick {
+    /// Interior tick bounded at `plane_constant` on a sphere of `radius`
+    pub fn new(
+        tick_id: U256,
+        plane_constant: U256,
+        liquidity: U256,
+        radius: U256,
+        depeg_limit: u32,
+    ) -> Self {
+        Self {
+            tick_id,
+            plane_constant,
+            liquidity,
+            radius,
+            depeg_limit,
+            is_boundary: false,
         }
     }
 
-    /// Create an underflow error for a specific operation
-    pub fn underflow(operation: impl Into<String>) -> Self {
-        Self::Underflow {
-            operation: operation.into(),
+    /// Smallest reserve any token can reach inside the cap:
+    /// (c - √((N-1)(R² - c²))) / √N, or zero when the cap reaches the axes
+    pub fn min_reserve(&self, token_count: usize) -> U256 {
+        let (sqrt_n, spread) = self.cap_spread(token_count);
+        if sqrt_n.is_zero() {
+            return U256::ZERO;
         }
+        self.plane_constant
+            .saturating_sub(spread)
+            .saturating_mul(U256::from(crate::PRECISION_MULTIPLIER))
+            / sqrt_n
     }
 
-    /// Create a division by zero error for a specific operation
-    pub fn division_by_zero(operation: impl Into<String>) -> Self {
-        Self::DivisionByZero {
-            operation: operation.into(),
+    /// Largest reserve any token can reach inside the cap:
+    /// (c + √((N-1)(R² - c²))) / √N
+    pub fn max_reserve(&self, token_count: usize) -> U256 {
+        let (sqrt_n, spread) = self.cap_spread(token_count);
+        if sqrt_n.is_zero() {
+            return U256::ZERO;
         }
+        self.plane_constant
+            .saturating_add(spread)
+            .saturating_mul(U256::from(crate::PRECISION_MULTIPLIER))
+            / sqrt_n
     }
 
-    /// Create an invalid parameter error
-    pub fn invalid_param(param: impl Into<String>, reason: impl Into<String>) -> Self {
-        Self::InvalidParameter {
-            param: param.into(),
-            reason: reason.into(),
-        }
+    /// √N in 18-decimal fixed point and the spread √((N-1)(R² - c²))
+    fn cap_spread(&self, token_count: usize) -> (U256, U256) {
+        let precision = U256::from(crate::PRECISION_MULTIPLIER);
+        let sqrt_n = sqrt(U256::from(token_count) * precision * precision);
+        let gap = self
+            .radius
+            .saturating_mul(self.radius)
+            .saturating_sub(self.plane_constant.saturating_mul(self.plane_constant));
+        let spread = sqrt(gap.saturating_mul(U256::from(token_count.saturating_sub(1))));
+        (sqrt_n, spread)
     }
+}
 
-    /// Create a computation error with details
-    pub fn computation(details: impl Into<String>) -> Self {
-        Self::ComputationError {
-            details: details.into(),
-        }
-    }
+/// Reserves, curve and ticks of a pool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolState {
+    /// Current reserves
+    pub reserves: ReservePoint,
+    /// Curve the reserves stay on
+    pub curve_type: CurveType,
+    /// R² for a sphere, K for a superellipse
+    pub invariant: U256,
+    /// Concentrated liquidity ticks
+    pub ticks: Vec<Tick>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl PoolState {
+    /// Pool at `reserves` on `curve_type` with the given invariant and ticks
+    pub fn new(reserves: Vec<U256>, curve_type: CurveType, invariant: U256, ticks: Vec<Tick>) -> Self {
+        Self {
+            reserves: ReservePoint::new(reserves),
+            curve_type,
+            invariant,
+            ticks,
+        }
+    }
 
-    #[test]
-    fn test_error_creation() {
-        let err = OrbitalError::overflow("test operation");
-        assert!(matches!(err, OrbitalError::Overflow { .. }));
+    /// Number of tokens in the pool
+    pub fn token_count(&self) -> usize {
+        self.reserves.dimensions()
+    }
 
-        let err = OrbitalError::invalid_param("test_param", "invalid value");
-        assert!(matches!(err, OrbitalError::InvalidParameter { .. }));
+    /// Replace the pool's reserves
+    pub fn update_reserves(&mut self, reserves: Vec<U256>) {
+        self.reserves = ReservePoint::new(reserves);
     }
 
-    #[test]
-    fn test_error_display() {
-        let err = OrbitalError::InvalidTokenCount(1);
-        let display = format!("{}", err);
-        assert!(display.contains("Invalid token count"));
-
-        let err = OrbitalError::InsufficientLiquidity {
-            needed: "1000".to_string(),
-            available: "500".to_string(),
-        };
-        let display = format!("{}", err);
-        assert!(display.contains("Insufficient liquidity"));
+    /// Liquidity deposited in the ticks, or the sum of the reserves for a
+    /// pool without ticks
+    pub fn total_liquidity(&self) -> U256 {
+        let ticks = self
+            .ticks
+            .iter()
+            .fold(U256::ZERO, |acc, tick| acc.saturating_add(tick.liquidity));
+        if !ticks.is_zero() {
+            return ticks;
+        }
+        self.reserves
+            .reserves
+            .iter()
+            .fold(U256::ZERO, |acc, &r| acc.saturating_add(r))
     }
-}
\ No newline at end of file
+}
+
+/// Outcome of an executed trade
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeInfo {
+    /// Input token index
+    pub token_in: usize,
+    /// Output token index
+    pub token_out: usize,
+    /// Amount of the input token traded
+    pub amount_in: U256,
+    /// Amount of the output token received, after fees
+    pub amount_out: U256,
+    /// Price of token_in in token_out before the trade
+    pub price_before: U256,
+    /// Price of token_in in token_out after the trade
+    pub price_after: U256,
+    /// Price impact in basis points
+    pub price_impact_bp: u32,
+    /// Number of tick boundaries crossed
+    pub ticks_crossed: usize,
+    /// Fee charged
+    pub fee: U256,
+}
@@ -45,8 +45,41 @@ pub fn abs(value: U256) -> U256 {
     value
 }
 
-/// Calculate the nth root of a value approximately
-/// This is a placeholder - production needs precise nth root
+/// Integer square root rounded down: the largest `r` with `r² <= value`
+///
+/// Babylonian iteration from `2^ceil(bits/2)`, which is never below the
+/// root, so the iterates decrease monotonically to `floor(sqrt(value))`;
+/// convergence is quadratic from the first step.
+pub fn sqrt(value: U256) -> U256 {
+    if value < U256::from(2) {
+        return value;
+    }
+
+    let mut x = U256::from(1) << value.bit_len().div_ceil(2);
+    loop {
+        // x >= sqrt(value), so value / x <= 2^128 and the sum cannot overflow
+        let next = (x + value / x) >> 1;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// Integer square root rounded up: the smallest `r` with `r² >= value`
+pub fn sqrt_ceil(value: U256) -> U256 {
+    let root = sqrt(value);
+    if root * root == value {
+        root
+    } else {
+        root + U256::from(1)
+    }
+}
+
+/// Integer nth root rounded down: the largest `x` with `x^n <= value`
+///
+/// Newton iteration from `2^ceil(bits/n)`, which is never below the root,
+/// so the iterates decrease monotonically to the floor, as in [`sqrt`].
 pub fn nth_root_approx(value: U256, n: u32) -> Result<U256> {
     if n == 0 {
         return Err(OrbitalError::division_by_zero("nth root with n=0"));
@@ -57,41 +90,27 @@ pub fn nth_root_approx(value: U256, n: u32) -> Result<U256> {
     }
     
     if n == 2 {
-        return Ok(crate::types::sqrt_approx(value));
+        return Ok(sqrt(value));
     }
     
-    if value.is_zero() {
-        return Ok(U256::ZERO);
+    if value < U256::from(2) {
+        return Ok(value);
     }
     
-    // Newton's method for nth root
     // x_{k+1} = ((n-1)*x_k + value/x_k^(n-1)) / n
-    let mut x = value / U256::from(n);
-    
-    for _ in 0..50 {
-        let x_pow_n_minus_1 = pow(x, n - 1)?;
-        
-        if x_pow_n_minus_1.is_zero() {
-            break;
+    let n_u256 = U256::from(n);
+    let mut x = U256::from(1) << value.bit_len().div_ceil(n as usize);
+    loop {
+        // x^(n-1) beyond U256 means value / x^(n-1) is zero
+        let quotient = (1..n)
+            .try_fold(U256::from(1), |acc, _| acc.checked_mul(x))
+            .map_or(U256::ZERO, |x_pow| value / x_pow);
+        let x_new = (x * U256::from(n - 1) + quotient) / n_u256;
+        if x_new >= x {
+            return Ok(x);
         }
-        
-        let numerator = x
-            .checked_mul(U256::from(n - 1))
-            .ok_or_else(|| OrbitalError::overflow("nth root"))?
-            .checked_add(value / x_pow_n_minus_1)
-            .ok_or_else(|| OrbitalError::overflow("nth root"))?;
-            
-        let x_new = numerator / U256::from(n);
-        
-        // Check convergence
-        if x_new == x || (x_new < x && x - x_new <= U256::from(1)) {
-            break;
-        }
-        
         x = x_new;
     }
-    
-    Ok(x)
 }
 
 /// Linear interpolation between two values
@@ -190,7 +209,7 @@ pub fn l2_norm(vec: &[U256]) -> Result<U256> {
                 .ok_or_else(|| OrbitalError::overflow("L2 norm"))
         })?;
     
-    Ok(crate::types::sqrt_approx(sum_of_squares))
+    Ok(sqrt(sum_of_squares))
 }
 
 /// Calculate the sum of a vector
@@ -236,6 +255,59 @@ pub fn weighted_average(values: &[U256], weights: &[U256]) -> Result<U256> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use num_bigint::BigUint;
+    use proptest::prelude::*;
+
+    fn to_big(value: U256) -> BigUint {
+        BigUint::from_bytes_be(&value.to_be_bytes::<32>())
+    }
+
+    fn arb_u256() -> impl Strategy<Value = U256> {
+        // Spread values over every magnitude, not just near 2^256
+        (any::<[u8; 32]>(), 0usize..=256).prop_map(|(bytes, bits)| {
+            let value = U256::from_be_bytes(bytes);
+            if bits == 256 { value } else { value & ((U256::from(1) << bits) - U256::from(1)) }
+        })
+    }
+
+    #[test]
+    fn test_sqrt_rounding() {
+        assert_eq!(sqrt(U256::ZERO), U256::ZERO);
+        assert_eq!(sqrt(U256::from(1)), U256::from(1));
+        assert_eq!(sqrt(U256::from(24)), U256::from(4));
+        assert_eq!(sqrt(U256::from(25)), U256::from(5));
+        assert_eq!(sqrt_ceil(U256::from(24)), U256::from(5));
+        assert_eq!(sqrt_ceil(U256::from(25)), U256::from(5));
+        assert_eq!(sqrt_ceil(U256::from(26)), U256::from(6));
+
+        // Extremes of the domain
+        let max_root = U256::from(u128::MAX);
+        assert_eq!(sqrt(U256::MAX), max_root);
+        assert_eq!(sqrt_ceil(U256::MAX), max_root + U256::from(1));
+        assert_eq!(sqrt(max_root * max_root), max_root);
+        assert_eq!(sqrt(max_root * max_root - U256::from(1)), max_root - U256::from(1));
+    }
+
+    proptest! {
+        /// Differential test against arbitrary-precision integer sqrt
+        #[test]
+        fn prop_sqrt_matches_reference(value in arb_u256()) {
+            let reference = to_big(value).sqrt();
+            prop_assert_eq!(to_big(sqrt(value)), reference.clone());
+
+            let exact = &reference * &reference == to_big(value);
+            let ceil = if exact { reference } else { reference + 1u32 };
+            prop_assert_eq!(to_big(sqrt_ceil(value)), ceil);
+        }
+
+        #[test]
+        fn prop_sqrt_is_monotonic(a in arb_u256(), b in arb_u256()) {
+            let (low, high) = if a <= b { (a, b) } else { (b, a) };
+            prop_assert!(sqrt(low) <= sqrt(high));
+            prop_assert!(sqrt_ceil(low) <= sqrt_ceil(high));
+            prop_assert!(sqrt(low) <= sqrt_ceil(low));
+        }
+    }
 
     #[test]
     fn test_pow() {
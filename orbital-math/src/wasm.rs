@@ -0,0 +1,36 @@
+//! wasm-bindgen bindings for the frontend
+//!
+//! Pools cross the boundary as [`PoolSnapshot`] JSON and amounts as decimal
+//! strings; results come back as JSON. Errors surface as JavaScript
+//! exceptions carrying the [`crate::OrbitalError`] message.
+
+use alloc::string::{String, ToString};
+use wasm_bindgen::prelude::*;
+use crate::snapshot::{from_json, parse_amount, to_json, PoolSnapshot, SNAPSHOT_VERSION};
+
+fn js_error(error: crate::OrbitalError) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+/// Snapshot format version this build reads and writes
+#[wasm_bindgen(js_name = snapshotVersion)]
+pub fn snapshot_version() -> u16 {
+    SNAPSHOT_VERSION
+}
+
+/// Quote `amount_in` of `token_in` for `token_out`, as [`crate::snapshot::Quote`] JSON
+#[wasm_bindgen]
+pub fn quote(pool_json: &str, token_in: usize, token_out: usize, amount_in: &str) -> Result<String, JsError> {
+    let pool: PoolSnapshot = from_json(pool_json).map_err(js_error)?;
+    let amount_in = parse_amount("amount_in", amount_in).map_err(js_error)?;
+    let quote = pool.quote(token_in, token_out, amount_in).map_err(js_error)?;
+    to_json(&quote).map_err(js_error)
+}
+
+/// Marginal price of `token_out` per `token_in` as a decimal string scaled by 1e18
+#[wasm_bindgen(js_name = marginalPrice)]
+pub fn marginal_price(pool_json: &str, token_in: usize, token_out: usize) -> Result<String, JsError> {
+    let pool = from_json(pool_json).map_err(js_error)?;
+    let price = pool.marginal_price(token_in, token_out).map_err(js_error)?;
+    Ok(price.to_string())
+}
@@ -0,0 +1,143 @@
+//! Property-based and differential tests for the swap curves
+//!
+//! Random pools of 2 to 5 tokens on either curve are checked for the
+//! invariants every trade must keep, and their outputs are compared against
+//! an f64 model of the same curve. The cases are slow, so the suite only
+//! builds with the `heavy-tests` feature:
+//!
+//! ```text
+//! cargo test -p orbital-math --features heavy-tests --test properties
+//! ```
+
+use alloy_primitives::U256;
+use orbital_math::{
+    depth::amount_out,
+    fixed_point::pow_bp,
+    sphere::{verify_sphere_constraint, TolerancePolicy},
+    superellipse::verify_superellipse_constraint,
+    types::CurveType,
+};
+use proptest::prelude::*;
+
+/// A pool sitting exactly on its curve
+#[derive(Debug, Clone)]
+struct Pool {
+    reserves: Vec<U256>,
+    curve: CurveType,
+    invariant: U256,
+}
+
+impl Pool {
+    fn new(reserves: Vec<u64>, u_parameter: Option<u32>) -> Self {
+        let reserves: Vec<U256> = reserves.into_iter().map(U256::from).collect();
+        let (curve, invariant) = match u_parameter {
+            None => (
+                CurveType::Sphere,
+                reserves.iter().fold(U256::ZERO, |acc, &r| acc + r * r),
+            ),
+            Some(u_parameter) => (
+                CurveType::Superellipse { u_parameter },
+                reserves.iter().fold(U256::ZERO, |acc, &r| acc + pow_bp(r, u_parameter).unwrap()),
+            ),
+        };
+        Self { reserves, curve, invariant }
+    }
+
+    fn quote(&self, reserves: &[U256], amount_in: U256) -> Option<U256> {
+        amount_out(reserves, &self.curve, self.invariant, 0, 1, amount_in).ok()
+    }
+
+    fn after(&self, amount_in: U256, amount_out: U256) -> Vec<U256> {
+        let mut reserves = self.reserves.clone();
+        reserves[0] += amount_in;
+        reserves[1] -= amount_out;
+        reserves
+    }
+
+    /// f64 model: solve the curve for the new output reserve directly,
+    /// returning the output and how far the integer result may stray from it
+    fn reference_out(&self, amount_in: U256) -> (f64, f64) {
+        let reserves: Vec<f64> = self.reserves.iter().map(|r| r.to::<u64>() as f64).collect();
+        let new_in = reserves[0] + amount_in.to::<u64>() as f64;
+        let u = match self.curve {
+            CurveType::Sphere => 2.0,
+            CurveType::Superellipse { u_parameter } => u_parameter as f64 / 1e4,
+        };
+        let k: f64 = reserves.iter().map(|r| r.powf(u)).sum();
+        let others: f64 = new_in.powf(u) + reserves[2..].iter().map(|r| r.powf(u)).sum::<f64>();
+        let new_out = (k - others).powf(1.0 / u);
+
+        // Rounding of the sums is of order k · 1e-15 on either side, and
+        // reaches the output through d(new_out) = d(sum) / (u · new_out^(u-1))
+        let cancellation = k * 1e-14 / (u * new_out.powf(u - 1.0));
+        let out = reserves[1] - new_out;
+        (out, 2.0 + out.abs() * 1e-9 + cancellation)
+    }
+}
+
+prop_compose! {
+    fn pool()(
+        reserves in prop::collection::vec(1_000_000u64..1_000_000_000_000, 2..=5),
+        u_parameter in prop_oneof![Just(None), (20_500u32..40_000).prop_map(Some)],
+    ) -> Pool {
+        Pool::new(reserves, u_parameter)
+    }
+}
+
+/// `fraction` hundred-thousandths of the input reserve, at least one unit
+fn trade_size(pool: &Pool, fraction: u64) -> U256 {
+    (pool.reserves[0] * U256::from(fraction) / U256::from(100_000u64)).max(U256::from(1))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2_000))]
+
+    #[test]
+    fn prop_output_is_monotonic_in_input(pool in pool(), a in 1u64..10_000, b in 1u64..10_000) {
+        let (small, large) = (trade_size(&pool, a.min(b)), trade_size(&pool, a.max(b)));
+        if let (Some(out_small), Some(out_large)) = (pool.quote(&pool.reserves, small), pool.quote(&pool.reserves, large)) {
+            prop_assert!(out_small <= out_large);
+        }
+    }
+
+    #[test]
+    fn prop_reserves_stay_positive_and_on_curve(pool in pool(), size in 1u64..10_000) {
+        let amount_in = trade_size(&pool, size);
+        let Some(out) = pool.quote(&pool.reserves, amount_in) else { return Ok(()) };
+        prop_assert!(out <= pool.reserves[1]);
+
+        let after = pool.after(amount_in, out);
+        let tolerance = TolerancePolicy::CONTRACT.tolerance_bp;
+        match pool.curve {
+            CurveType::Sphere => prop_assert!(verify_sphere_constraint(&after, pool.invariant, tolerance).is_ok()),
+            CurveType::Superellipse { u_parameter } => prop_assert!(
+                verify_superellipse_constraint(&after, u_parameter, pool.invariant, tolerance).is_ok()
+            ),
+        }
+    }
+
+    /// Both curves are solved from the invariant rather than the previous
+    /// state, so splitting a trade in two yields exactly the same output
+    #[test]
+    fn prop_split_trades_are_path_independent(pool in pool(), first in 1u64..5_000, second in 1u64..5_000) {
+        let (first, second) = (trade_size(&pool, first), trade_size(&pool, second));
+        let Some(whole) = pool.quote(&pool.reserves, first + second) else { return Ok(()) };
+        let out_first = pool.quote(&pool.reserves, first).unwrap();
+        let midway = pool.after(first, out_first);
+        let out_second = pool.quote(&midway, second).unwrap();
+
+        prop_assert_eq!(out_first + out_second, whole);
+    }
+
+    /// Integer outputs track the f64 model to within rounding of both
+    #[test]
+    fn prop_matches_f64_reference(pool in pool(), size in 1u64..10_000) {
+        let amount_in = trade_size(&pool, size);
+        let Some(out) = pool.quote(&pool.reserves, amount_in) else { return Ok(()) };
+        let (reference, tolerance) = pool.reference_out(amount_in);
+        prop_assert!(
+            (out.to::<u64>() as f64 - reference).abs() <= tolerance,
+            "integer {} vs reference {}", out, reference
+        );
+    }
+}
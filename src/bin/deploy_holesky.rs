@@ -1,36 +1,81 @@
-//! Holesky deployment binary for Rust Intents System
+//! Deployment binary for Rust Intents System
 //! 
-//! This binary deploys the complete cross-chain intents system to Holesky testnet
-//! and provides real-time monitoring of the deployment process.
+//! This binary deploys the complete cross-chain intents system to any network
+//! in the registry (Holesky by default) and provides real-time monitoring of
+//! the deployment process.
 
 use clap::{App, Arg};
 use eyre::Result;
-use intents_system::deployment::{deploy_to_holesky, DeploymentResult};
+use intents_engine::signer::SignerConfig;
+use intents_system::deployment::{
+    deploy_to_network, deploy_to_network_with_signer, ArtifactManifest, DeploymentResult, NetworkConfig, NetworkRegistry,
+    DEFAULT_NETWORK,
+};
 use serde_json;
 use std::{fs, path::Path};
 use tokio;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    env_logger::init();
+    // Progress is logged; show it unless RUST_LOG says otherwise
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 
-    let matches = App::new("Rust Intents Holesky Deployer")
+    let matches = App::new("Rust Intents Deployer")
         .version("1.0.0")
         .author("Rust Intents Team")
-        .about("Deploy the complete cross-chain intents system to Holesky testnet")
+        .about("Deploy the complete cross-chain intents system to a testnet or local anvil")
+        .arg(
+            Arg::with_name("network")
+                .long("network")
+                .value_name("NETWORK")
+                .help("Network to deploy to (holesky, sepolia, arbitrum-sepolia, base-sepolia, anvil)")
+                .default_value(DEFAULT_NETWORK),
+        )
+        .arg(
+            Arg::with_name("networks")
+                .long("networks")
+                .value_name("FILE")
+                .help("Network registry to use instead of the built-in one"),
+        )
+        .arg(
+            Arg::with_name("artifacts")
+                .long("artifacts")
+                .value_name("FILE")
+                .help("Artifact manifest to use instead of the built-in one (contracts/solidity/out)"),
+        )
         .arg(
             Arg::with_name("private-key")
                 .long("private-key")
                 .value_name("PRIVATE_KEY")
                 .help("Private key for deployment (without 0x prefix)")
-                .required(true),
+                .required_unless_one(&["kms-key-id", "ledger-index"]),
+        )
+        .arg(
+            Arg::with_name("kms-key-id")
+                .long("kms-key-id")
+                .value_name("KEY_ID")
+                .help("Sign with an AWS KMS key instead of a private key")
+                .conflicts_with_all(&["private-key", "ledger-index"]),
+        )
+        .arg(
+            Arg::with_name("kms-region")
+                .long("kms-region")
+                .value_name("REGION")
+                .help("AWS region of the KMS key")
+                .default_value("us-east-1"),
+        )
+        .arg(
+            Arg::with_name("ledger-index")
+                .long("ledger-index")
+                .value_name("INDEX")
+                .help("Sign with the Ledger Live account at this index")
+                .conflicts_with_all(&["private-key", "kms-key-id"]),
         )
         .arg(
             Arg::with_name("output-dir")
                 .long("output-dir")
                 .value_name("DIR")
-                .help("Output directory for deployment artifacts")
-                .default_value("deployments/holesky"),
+                .help("Output directory for deployment artifacts (default: deployments/<network>)"),
         )
         .arg(
             Arg::with_name("verify")
@@ -40,77 +85,130 @@ async fn main() -> Result<()> {
         )
         .get_matches();
 
-    let private_key = matches.value_of("private-key").unwrap();
-    let output_dir = matches.value_of("output-dir").unwrap();
+    let registry = match matches.value_of("networks") {
+        Some(path) => NetworkRegistry::load(path)?,
+        None => NetworkRegistry::builtin(),
+    };
+    let network = registry.get(matches.value_of("network").unwrap())?;
+    let artifacts = match matches.value_of("artifacts") {
+        Some(path) => ArtifactManifest::load(path)?,
+        None => ArtifactManifest::builtin(),
+    };
+    let output_dir = matches
+        .value_of("output-dir")
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("deployments/{}", network.key));
+    let output_dir = output_dir.as_str();
     let verify = matches.is_present("verify");
 
-    // Ensure private key has 0x prefix
-    let private_key = if private_key.starts_with("0x") {
-        private_key.to_string()
+    let signer_config = if let Some(key_id) = matches.value_of("kms-key-id") {
+        Some(SignerConfig::AwsKms {
+            key_id: key_id.to_string(),
+            region: matches.value_of("kms-region").unwrap().to_string(),
+        })
+    } else if let Some(index) = matches.value_of("ledger-index") {
+        Some(SignerConfig::Ledger { index: index.parse()? })
     } else {
-        format!("0x{}", private_key)
+        None
     };
 
-    println!("🚀 Rust Intents System - Holesky Deployment");
-    println!("============================================");
-    println!();
-
     // Create output directory
     fs::create_dir_all(output_dir)?;
-    println!("📁 Output directory: {}", output_dir);
+    tracing::info!(
+        "Deploying to {} (chain {}), writing artifacts to {}",
+        network.name,
+        network.chain_id,
+        output_dir
+    );
 
     // Deploy system
-    println!("🔄 Starting deployment...");
-    let deployment_result = deploy_to_holesky(&private_key).await?;
+    let deployment_result = match &signer_config {
+        Some(config) => deploy_to_network_with_signer(network.clone(), artifacts, config).await?,
+        None => {
+            // Ensure private key has 0x prefix
+            let private_key = matches.value_of("private-key").unwrap();
+            let private_key = if private_key.starts_with("0x") {
+                private_key.to_string()
+            } else {
+                format!("0x{}", private_key)
+            };
+            deploy_to_network(network.clone(), artifacts, &private_key).await?
+        }
+    };
 
     // Save deployment artifacts
     save_deployment_artifacts(&deployment_result, output_dir).await?;
 
     // Create solver configuration
-    create_solver_config(&deployment_result, output_dir).await?;
+    create_solver_config(&deployment_result, &network, output_dir).await?;
 
     // Create monitoring dashboard
-    create_monitoring_dashboard(&deployment_result, output_dir).await?;
+    create_monitoring_dashboard(&deployment_result, &network, output_dir).await?;
 
     // Verification
     if verify {
         verify_deployment(&deployment_result).await?;
     }
 
-    println!("\n🎉 Deployment completed successfully!");
-    println!("====================================");
+    print_summary(&deployment_result, &network);
     println!();
     println!("📋 Next Steps:");
     println!("  1. Run the solver: cargo run --bin solver -- --config {}/solver_config.json", output_dir);
     println!("  2. Start demo: ./scripts/demo_holesky.sh");
     println!("  3. View dashboard: open {}/dashboard.html", output_dir);
     println!();
-    println!("🔗 Useful Links:");
-    println!("  Holesky Explorer: https://holesky.etherscan.io/");
-    println!("  Faucet: https://faucet.quicknode.com/ethereum/holesky");
-    println!();
+    if network.explorer_url.is_some() || network.faucet_url.is_some() {
+        println!("🔗 Useful Links:");
+        if let Some(explorer) = &network.explorer_url {
+            println!("  {} Explorer: {}", network.name, explorer);
+        }
+        if let Some(faucet) = &network.faucet_url {
+            println!("  Faucet: {}", faucet);
+        }
+        println!();
+    }
 
     Ok(())
 }
 
+/// Print what was deployed where
+fn print_summary(deployment_result: &DeploymentResult, network: &NetworkConfig) {
+    println!("\n🎉 Deployment completed successfully!");
+    println!("====================================");
+    println!("📋 Deployment Summary:");
+    println!("   Network: {} (Chain ID: {})", network.name, network.chain_id);
+    println!("   Deployer: {}", deployment_result.config.deployer_address);
+    println!("   Block: {}", deployment_result.contracts.deployment_block);
+    println!("   Total Gas Used: {}", deployment_result.total_gas_used);
+    println!("   Total Cost: {} ETH", ethers::utils::format_ether(deployment_result.total_cost));
+    println!("\n📦 Deployed Contracts:");
+    println!("   Intents: {}", deployment_result.contracts.intents_contract);
+    println!("   Orbital AMM: {}", deployment_result.contracts.orbital_amm_contract);
+    println!("   Mock USDC: {}", deployment_result.contracts.mock_usdc_contract);
+    println!("\n🔗 Transaction Hashes:");
+    for (i, tx_hash) in deployment_result.transaction_hashes.iter().enumerate() {
+        println!("   {}: {}", i + 1, tx_hash);
+    }
+}
+
 /// Save deployment artifacts to files
 async fn save_deployment_artifacts(
     deployment_result: &DeploymentResult,
     output_dir: &str,
 ) -> Result<()> {
-    println!("💾 Saving deployment artifacts...");
+    tracing::info!("Saving deployment artifacts");
 
     // Save full deployment result
     let deployment_file = format!("{}/deployment_result.json", output_dir);
     let deployment_json = serde_json::to_string_pretty(deployment_result)?;
     fs::write(&deployment_file, deployment_json)?;
-    println!("✅ Deployment result saved to {}", deployment_file);
+    tracing::info!("Deployment result saved to {}", deployment_file);
 
     // Save individual contract addresses
     let contracts_file = format!("{}/contracts.json", output_dir);
     let contracts_json = serde_json::to_string_pretty(&deployment_result.contracts)?;
     fs::write(&contracts_file, contracts_json)?;
-    println!("✅ Contract addresses saved to {}", contracts_file);
+    tracing::info!("Contract addresses saved to {}", contracts_file);
 
     // Save transaction hashes
     let tx_file = format!("{}/transactions.txt", output_dir);
@@ -122,7 +220,7 @@ async fn save_deployment_artifacts(
         .collect::<Vec<_>>()
         .join("\n");
     fs::write(&tx_file, tx_content)?;
-    println!("✅ Transaction hashes saved to {}", tx_file);
+    tracing::info!("Transaction hashes saved to {}", tx_file);
 
     Ok(())
 }
@@ -130,15 +228,16 @@ async fn save_deployment_artifacts(
 /// Create solver configuration file
 async fn create_solver_config(
     deployment_result: &DeploymentResult,
+    network: &NetworkConfig,
     output_dir: &str,
 ) -> Result<()> {
-    println!("⚙️ Creating solver configuration...");
+    tracing::info!("Creating solver configuration");
 
     let solver_config = serde_json::json!({
         "solver": {
             "address": deployment_result.config.deployer_address,
             "private_key": deployment_result.config.private_key,
-            "supported_chains": [17000],
+            "supported_chains": [deployment_result.config.chain_id],
             "min_profit_bps": 50,
             "max_exposure": "100000000000000000000",
             "reputation_threshold": 5000
@@ -146,7 +245,7 @@ async fn create_solver_config(
         "network": {
             "chain_id": deployment_result.config.chain_id,
             "rpc_url": deployment_result.config.rpc_url,
-            "explorer_url": "https://holesky.etherscan.io"
+            "explorer_url": network.explorer_url
         },
         "contracts": {
             "intents": deployment_result.contracts.intents_contract,
@@ -164,7 +263,7 @@ async fn create_solver_config(
     let config_file = format!("{}/solver_config.json", output_dir);
     let config_json = serde_json::to_string_pretty(&solver_config)?;
     fs::write(&config_file, config_json)?;
-    println!("✅ Solver configuration saved to {}", config_file);
+    tracing::info!("Solver configuration saved to {}", config_file);
 
     Ok(())
 }
@@ -172,9 +271,40 @@ async fn create_solver_config(
 /// Create monitoring dashboard
 async fn create_monitoring_dashboard(
     deployment_result: &DeploymentResult,
+    network: &NetworkConfig,
     output_dir: &str,
 ) -> Result<()> {
-    println!("📊 Creating monitoring dashboard...");
+    tracing::info!("Creating monitoring dashboard");
+
+    // Plain text when the network has no explorer (e.g. anvil)
+    let explorer_link = |kind: &str, value: String, label: String| match network.explorer_link(kind, &value) {
+        Some(url) => format!(r#"<a href="{}" target="_blank">{}</a>"#, url, label),
+        None => label,
+    };
+    let contract_buttons = [
+        ("Intents Contract", deployment_result.contracts.intents_contract),
+        ("AMM Contract", deployment_result.contracts.orbital_amm_contract),
+        ("USDC Contract", deployment_result.contracts.mock_usdc_contract),
+    ]
+    .iter()
+    .filter_map(|(name, address)| {
+        network.explorer_link("address", format!("{:?}", address)).map(|url| {
+            format!(r#"<a href="{}" target="_blank" class="btn">🔍 View {}</a>"#, url, name)
+        })
+    })
+    .collect::<Vec<_>>()
+    .join("\n            ");
+    let explorer_step = match &network.explorer_url {
+        Some(url) => format!(
+            r#"<li>Monitor transactions on <a href="{}" target="_blank">{} Explorer</a></li>"#,
+            url, network.name
+        ),
+        None => String::new(),
+    };
+    let faucet_step = match &network.faucet_url {
+        Some(url) => format!(r#"<li>Get test ETH from <a href="{}" target="_blank">{} Faucet</a></li>"#, url, network.name),
+        None => String::new(),
+    };
 
     let dashboard_html = format!(
         r#"<!DOCTYPE html>
@@ -182,7 +312,7 @@ async fn create_monitoring_dashboard(
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Rust Intents - Holesky Deployment Dashboard</title>
+    <title>Rust Intents - {} Deployment Dashboard</title>
     <style>
         body {{ font-family: Arial, sans-serif; margin: 20px; background: #f5f5f5; }}
         .container {{ max-width: 1200px; margin: 0 auto; }}
@@ -203,14 +333,14 @@ async fn create_monitoring_dashboard(
     <div class="container">
         <div class="header">
             <h1>🚀 Rust Intents System</h1>
-            <p>Holesky Testnet Deployment Dashboard</p>
+            <p>{} Deployment Dashboard</p>
             <div class="status-badge status-success">✅ DEPLOYED</div>
         </div>
         
         <div class="deployment-info">
             <div class="info-card">
                 <h3>📦 Deployment Info</h3>
-                <p><strong>Network:</strong> Holesky Testnet</p>
+                <p><strong>Network:</strong> {}</p>
                 <p><strong>Chain ID:</strong> {}</p>
                 <p><strong>Deployer:</strong> <span class="contract-address">{}</span></p>
                 <p><strong>Block:</strong> {}</p>
@@ -238,9 +368,7 @@ async fn create_monitoring_dashboard(
         
         <div class="info-card">
             <h3>🚀 Quick Actions</h3>
-            <a href="https://holesky.etherscan.io/address/{}" target="_blank" class="btn">🔍 View Intents Contract</a>
-            <a href="https://holesky.etherscan.io/address/{}" target="_blank" class="btn">🔍 View AMM Contract</a>
-            <a href="https://holesky.etherscan.io/address/{}" target="_blank" class="btn">🔍 View USDC Contract</a>
+            {}
             <a href="solver_config.json" class="btn">⚙️ Download Config</a>
             <button class="btn" onclick="copyConfig()">📋 Copy Config</button>
         </div>
@@ -250,8 +378,8 @@ async fn create_monitoring_dashboard(
             <ol>
                 <li>Run the solver: <code>cargo run --bin solver -- --config solver_config.json</code></li>
                 <li>Start the demo: <code>./scripts/demo_holesky.sh</code></li>
-                <li>Monitor transactions on <a href="https://holesky.etherscan.io" target="_blank">Holesky Explorer</a></li>
-                <li>Get test ETH from <a href="https://faucet.quicknode.com/ethereum/holesky" target="_blank">Holesky Faucet</a></li>
+                {}
+                {}
             </ol>
         </div>
     </div>
@@ -268,6 +396,9 @@ async fn create_monitoring_dashboard(
     </script>
 </body>
 </html>"#,
+        network.name,
+        network.name,
+        network.name,
         deployment_result.config.chain_id,
         deployment_result.config.deployer_address,
         deployment_result.contracts.deployment_block,
@@ -281,36 +412,32 @@ async fn create_monitoring_dashboard(
             .iter()
             .enumerate()
             .map(|(i, hash)| format!(
-                r#"<div class="tx-item"><strong>{}:</strong> <a href="https://holesky.etherscan.io/tx/{}" target="_blank">{}</a></div>"#,
+                r#"<div class="tx-item"><strong>{}:</strong> {}</div>"#,
                 i + 1,
-                hash,
-                hash
+                explorer_link("tx", format!("{:?}", hash), format!("{:?}", hash))
             ))
             .collect::<Vec<_>>()
             .join(""),
-        deployment_result.contracts.intents_contract,
-        deployment_result.contracts.orbital_amm_contract,
-        deployment_result.contracts.mock_usdc_contract,
+        contract_buttons,
+        explorer_step,
+        faucet_step,
     );
 
     let dashboard_file = format!("{}/dashboard.html", output_dir);
     fs::write(&dashboard_file, dashboard_html)?;
-    println!("✅ Dashboard created at {}", dashboard_file);
+    tracing::info!("Dashboard created at {}", dashboard_file);
 
     Ok(())
 }
 
 /// Verify deployment
 async fn verify_deployment(deployment_result: &DeploymentResult) -> Result<()> {
-    println!("🔍 Verifying deployment...");
+    tracing::info!("Verifying deployment");
 
-    // Create new client for verification
-    use ethers::{middleware::SignerMiddleware, prelude::*};
+    // Verification is read-only, so no signer is needed
+    use ethers::prelude::*;
 
-    let provider = Provider::<ethers::providers::Http>::try_from(&deployment_result.config.rpc_url)?;
-    let wallet: LocalWallet = deployment_result.config.private_key.parse::<LocalWallet>()?
-        .with_chain_id(deployment_result.config.chain_id);
-    let client = SignerMiddleware::new(provider, wallet);
+    let client = Provider::<ethers::providers::Http>::try_from(&deployment_result.config.rpc_url)?;
 
     // Verify each contract has code
     for (name, address) in [
@@ -322,14 +449,14 @@ async fn verify_deployment(deployment_result: &DeploymentResult) -> Result<()> {
         if code.is_empty() {
             return Err(eyre::eyre!("{} contract has no code at {}", name, address));
         }
-        println!("✅ {} contract verified", name);
+        tracing::info!("{} contract verified", name);
     }
 
     // Verify transaction receipts
     for (i, tx_hash) in deployment_result.transaction_hashes.iter().enumerate() {
         if let Ok(Some(receipt)) = client.get_transaction_receipt(*tx_hash).await {
             if receipt.status == Some(1.into()) {
-                println!("✅ Transaction {} verified", i + 1);
+                tracing::info!("Transaction {} verified", i + 1);
             } else {
                 return Err(eyre::eyre!("Transaction {} failed", i + 1));
             }
@@ -338,6 +465,6 @@ async fn verify_deployment(deployment_result: &DeploymentResult) -> Result<()> {
         }
     }
 
-    println!("✅ All verifications passed!");
+    tracing::info!("All verifications passed");
     Ok(())
 }
\ No newline at end of file
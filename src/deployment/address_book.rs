@@ -0,0 +1,142 @@
+//! Per-network contract address books
+//!
+//! Every deployment records the contracts it created in the network's address
+//! book (a TOML file, by default `deployments/<network>/addresses.toml`), so
+//! other tooling can look them up by name without parsing deployment
+//! artifacts.
+
+use ethers::types::{Address, H256};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+use super::{DeployedContracts, DeploymentResult};
+
+pub const INTENTS: &str = "intents";
+pub const ORBITAL_AMM: &str = "orbital_amm";
+pub const MOCK_USDC: &str = "mock_usdc";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressBook {
+    pub chain_id: u64,
+    #[serde(default)]
+    pub contracts: BTreeMap<String, ContractEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractEntry {
+    pub address: Address,
+    pub transaction_hash: H256,
+    pub block_number: u64,
+    pub deployed_at: u64,
+}
+
+impl AddressBook {
+    pub fn new(chain_id: u64) -> Self {
+        Self { chain_id, contracts: BTreeMap::new() }
+    }
+
+    /// Load the book at `path`, or start an empty one if there is none yet.
+    /// A book written for another chain is rejected.
+    pub fn load_or_new(path: impl AsRef<Path>, chain_id: u64) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::new(chain_id));
+        }
+
+        let text = std::fs::read_to_string(path)?;
+        let book: Self = toml::from_str(&text)
+            .map_err(|e| eyre::eyre!("Invalid address book {}: {}", path.display(), e))?;
+        if book.chain_id != chain_id {
+            return Err(eyre::eyre!(
+                "Address book {} is for chain {}, not {}",
+                path.display(),
+                book.chain_id,
+                chain_id
+            ));
+        }
+        Ok(book)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn address(&self, name: &str) -> Option<Address> {
+        self.contracts.get(name).map(|entry| entry.address)
+    }
+
+    /// Record the contracts of a completed deployment, replacing any earlier
+    /// entries under the same names
+    pub fn record(&mut self, result: &DeploymentResult) {
+        let DeployedContracts {
+            intents_contract,
+            orbital_amm_contract,
+            mock_usdc_contract,
+            deployment_block,
+            deployment_timestamp,
+        } = result.contracts;
+
+        // Contracts are deployed in this order, see `DEPLOYMENT_ORDER`
+        let deployed = [(MOCK_USDC, mock_usdc_contract), (INTENTS, intents_contract), (ORBITAL_AMM, orbital_amm_contract)];
+        for ((name, address), tx_hash) in deployed.into_iter().zip(&result.transaction_hashes) {
+            self.contracts.insert(
+                name.to_string(),
+                ContractEntry {
+                    address,
+                    transaction_hash: *tx_hash,
+                    block_number: deployment_block,
+                    deployed_at: deployment_timestamp,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deployment::DeploymentConfig;
+    use ethers::types::U256;
+
+    #[test]
+    fn test_record_and_reload() {
+        let result = DeploymentResult {
+            config: DeploymentConfig {
+                private_key: String::new(),
+                network: "anvil".to_string(),
+                rpc_url: "http://127.0.0.1:8545".to_string(),
+                chain_id: 31337,
+                deployer_address: Address::zero(),
+                gas_price: None,
+                gas_limit: None,
+            },
+            contracts: DeployedContracts {
+                intents_contract: Address::repeat_byte(2),
+                orbital_amm_contract: Address::repeat_byte(3),
+                mock_usdc_contract: Address::repeat_byte(1),
+                deployment_block: 42,
+                deployment_timestamp: 1_700_000_000,
+            },
+            transaction_hashes: vec![H256::repeat_byte(1), H256::repeat_byte(2), H256::repeat_byte(3)],
+            total_gas_used: U256::zero(),
+            total_cost: U256::zero(),
+        };
+
+        let mut book = AddressBook::new(31337);
+        book.record(&result);
+        assert_eq!(book.address(INTENTS), Some(Address::repeat_byte(2)));
+        assert_eq!(book.contracts[ORBITAL_AMM].transaction_hash, H256::repeat_byte(3));
+
+        let path = std::env::temp_dir().join(format!("address-book-{}.toml", std::process::id()));
+        book.save(&path).unwrap();
+        assert_eq!(AddressBook::load_or_new(&path, 31337).unwrap(), book);
+        assert!(AddressBook::load_or_new(&path, 1).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,403 @@
+//! Compiled contract artifacts
+//!
+//! Deployments use the bytecode and ABI produced by the contract builds
+//! rather than embedded hex. Two kinds of artifact are read from a build
+//! directory:
+//!
+//! - `solc`: Foundry (or Hardhat) JSON artifacts, e.g.
+//!   `out/MockUSDC.sol/MockUSDC.json`, with library placeholders linked from
+//!   their `linkReferences`
+//! - `stylus`: a wasm program plus a JSON ABI, compressed and wrapped in init
+//!   code the same way `cargo stylus deploy` does. Stylus programs still need
+//!   `cargo stylus activate` before they can be called.
+//!
+//! The manifest (`deployments/artifacts.toml`, also compiled in as the
+//! default) says where each contract's artifact is, its constructor
+//! arguments and the pinned keccak256 of its creation bytecode.
+
+use ethers::{
+    abi::{
+        token::{LenientTokenizer, Tokenizer},
+        Abi,
+    },
+    types::{Address, Bytes, H256, U256},
+    utils::keccak256,
+};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Manifest compiled into the binary
+pub const BUILTIN_MANIFEST: &str = include_str!("../../deployments/artifacts.toml");
+
+/// Marks Stylus contract code: EOF-style magic, then the (empty) dictionary
+const STYLUS_PREFIX: [u8; 4] = [0xEF, 0xF0, 0x00, 0x00];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactManifest {
+    pub build_dir: PathBuf,
+    /// Refuse artifacts without a `bytecode_hash` instead of warning
+    #[serde(default)]
+    pub require_pinned_hashes: bool,
+    /// Library addresses, by name or `<file>:<name>`
+    #[serde(default)]
+    pub libraries: BTreeMap<String, Address>,
+    pub contracts: BTreeMap<String, ArtifactSpec>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactSpec {
+    #[serde(default)]
+    pub kind: ArtifactKind,
+    /// Overrides the manifest's `build_dir`
+    #[serde(default)]
+    pub build_dir: Option<PathBuf>,
+    pub path: PathBuf,
+    /// JSON ABI, for Stylus programs
+    #[serde(default)]
+    pub abi: Option<PathBuf>,
+    #[serde(default)]
+    pub constructor_args: Vec<String>,
+    #[serde(default)]
+    pub bytecode_hash: Option<H256>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    #[default]
+    Solc,
+    Stylus,
+}
+
+/// A loaded, linked and hash-checked artifact
+#[derive(Debug, Clone)]
+pub struct Artifact {
+    pub name: String,
+    pub abi: Abi,
+    /// Creation code, without constructor arguments
+    pub bytecode: Bytes,
+    pub bytecode_hash: H256,
+    /// keccak256 of the code the contract should have once deployed, when
+    /// it is known up front (no immutables)
+    pub runtime_hash: Option<H256>,
+    pub constructor_args: Vec<String>,
+}
+
+// Foundry and Hardhat JSON artifacts
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SolcArtifact {
+    abi: Abi,
+    bytecode: SolcBytecode,
+    #[serde(default)]
+    deployed_bytecode: Option<SolcBytecode>,
+    // Hardhat keeps these beside plain hex bytecode
+    #[serde(default)]
+    link_references: LinkReferences,
+    #[serde(default)]
+    deployed_link_references: LinkReferences,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SolcBytecode {
+    Foundry {
+        object: String,
+        #[serde(default, rename = "linkReferences")]
+        link_references: LinkReferences,
+        #[serde(default, rename = "immutableReferences")]
+        immutable_references: BTreeMap<String, serde_json::Value>,
+    },
+    Hex(String),
+}
+
+// File -> library -> byte ranges holding its address
+type LinkReferences = BTreeMap<String, BTreeMap<String, Vec<LinkOffset>>>;
+
+#[derive(Deserialize)]
+struct LinkOffset {
+    start: usize,
+    length: usize,
+}
+
+impl ArtifactManifest {
+    /// The manifest shipped in `deployments/artifacts.toml`
+    pub fn builtin() -> Self {
+        Self::from_toml(BUILTIN_MANIFEST).expect("built-in artifact manifest is valid")
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("Failed to read artifact manifest {}: {}", path.display(), e))?;
+        Self::from_toml(&text)
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self> {
+        toml::from_str(text).map_err(|e| eyre::eyre!("Invalid artifact manifest: {}", e))
+    }
+
+    /// Load `name`'s artifact and check it against its pinned hash
+    pub fn artifact(&self, name: &str) -> Result<Artifact> {
+        let artifact = self.read(name)?;
+        match self.contracts[name].bytecode_hash {
+            Some(pinned) if pinned != artifact.bytecode_hash => {
+                return Err(eyre::eyre!(
+                    "{} artifact does not match its pinned hash: expected {:?}, got {:?}",
+                    name,
+                    pinned,
+                    artifact.bytecode_hash
+                ));
+            }
+            Some(_) => {}
+            None if self.require_pinned_hashes => {
+                return Err(eyre::eyre!(
+                    "{} has no pinned bytecode_hash (this build is {:?})",
+                    name,
+                    artifact.bytecode_hash
+                ));
+            }
+            None => tracing::warn!(
+                "{} bytecode is not pinned, set bytecode_hash = \"{:?}\" to deploy only this build",
+                name,
+                artifact.bytecode_hash
+            ),
+        }
+        Ok(artifact)
+    }
+
+    /// Load `name`'s artifact without checking its hash
+    pub fn read(&self, name: &str) -> Result<Artifact> {
+        let spec = self
+            .contracts
+            .get(name)
+            .ok_or_else(|| eyre::eyre!("No artifact configured for contract '{}'", name))?;
+        let dir = spec.build_dir.as_ref().unwrap_or(&self.build_dir);
+        let path = dir.join(&spec.path);
+        let read = |path: &Path| {
+            std::fs::read(path).map_err(|e| {
+                eyre::eyre!("Failed to read {} artifact {}: {} (has the contract been built?)", name, path.display(), e)
+            })
+        };
+
+        let (abi, bytecode, runtime_hash) = match spec.kind {
+            ArtifactKind::Solc => {
+                let artifact: SolcArtifact = serde_json::from_slice(&read(&path)?)
+                    .map_err(|e| eyre::eyre!("Invalid solc artifact {}: {}", path.display(), e))?;
+                let bytecode = link(&artifact.bytecode, &artifact.link_references, &self.libraries)?;
+                let runtime_hash = match &artifact.deployed_bytecode {
+                    Some(SolcBytecode::Foundry { immutable_references, .. }) if !immutable_references.is_empty() => None,
+                    Some(deployed) => Some(H256(keccak256(link(
+                        deployed,
+                        &artifact.deployed_link_references,
+                        &self.libraries,
+                    )?))),
+                    None => None,
+                };
+                (artifact.abi, bytecode, runtime_hash)
+            }
+            ArtifactKind::Stylus => {
+                let abi = match &spec.abi {
+                    Some(abi_path) => serde_json::from_slice(&read(&dir.join(abi_path))?)
+                        .map_err(|e| eyre::eyre!("Invalid ABI for {}: {}", name, e))?,
+                    None => Abi::default(),
+                };
+                let code = stylus_contract_code(&read(&path)?)?;
+                let runtime_hash = H256(keccak256(&code));
+                (abi, stylus_init_code(&code), Some(runtime_hash))
+            }
+        };
+
+        Ok(Artifact {
+            name: name.to_string(),
+            abi,
+            bytecode_hash: H256(keccak256(&bytecode)),
+            bytecode: bytecode.into(),
+            runtime_hash,
+            constructor_args: spec.constructor_args.clone(),
+        })
+    }
+}
+
+impl Artifact {
+    /// Creation code followed by the ABI-encoded constructor arguments.
+    /// `@name` arguments are replaced by `resolve(name)`.
+    pub fn deployment_code(&self, resolve: impl Fn(&str) -> Option<Address>) -> Result<Bytes> {
+        let constructor = match &self.abi.constructor {
+            Some(constructor) => constructor,
+            None if self.constructor_args.is_empty() => return Ok(self.bytecode.clone()),
+            None => return Err(eyre::eyre!("{} has no constructor but arguments were configured", self.name)),
+        };
+        if constructor.inputs.len() != self.constructor_args.len() {
+            return Err(eyre::eyre!(
+                "{} constructor takes {} arguments, {} configured",
+                self.name,
+                constructor.inputs.len(),
+                self.constructor_args.len()
+            ));
+        }
+
+        let tokens = constructor
+            .inputs
+            .iter()
+            .zip(&self.constructor_args)
+            .map(|(param, arg)| {
+                let value = match arg.strip_prefix('@') {
+                    Some(reference) => {
+                        let address = resolve(reference)
+                            .ok_or_else(|| eyre::eyre!("{}: unknown address reference {}", self.name, arg))?;
+                        hex::encode(address.as_bytes())
+                    }
+                    None => arg.clone(),
+                };
+                LenientTokenizer::tokenize(&param.kind, &value)
+                    .map_err(|e| eyre::eyre!("{}: invalid constructor argument {}: {}", self.name, param.name, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(constructor.encode_input(self.bytecode.to_vec(), &tokens)?.into())
+    }
+}
+
+/// Decode solc bytecode, substituting library addresses for placeholders
+fn link(bytecode: &SolcBytecode, hardhat_references: &LinkReferences, libraries: &BTreeMap<String, Address>) -> Result<Vec<u8>> {
+    let (object, references) = match bytecode {
+        SolcBytecode::Foundry { object, link_references, .. } => (object, link_references),
+        SolcBytecode::Hex(object) => (object, hardhat_references),
+    };
+
+    let mut hex_code = object.trim_start_matches("0x").to_string();
+    for (file, file_libraries) in references {
+        for (library, offsets) in file_libraries {
+            let address = libraries
+                .get(&format!("{}:{}", file, library))
+                .or_else(|| libraries.get(library))
+                .ok_or_else(|| eyre::eyre!("No address configured for library {} ({})", library, file))?;
+            for offset in offsets {
+                let range = offset.start * 2..(offset.start + offset.length) * 2;
+                if offset.length != 20 || range.end > hex_code.len() {
+                    return Err(eyre::eyre!("Invalid link reference for library {}", library));
+                }
+                hex_code.replace_range(range, &hex::encode(address.as_bytes()));
+            }
+        }
+    }
+
+    if hex_code.is_empty() {
+        return Err(eyre::eyre!("Artifact has no bytecode (abstract contract or interface?)"));
+    }
+    if hex_code.contains("__") {
+        return Err(eyre::eyre!("Artifact has unlinked library placeholders"));
+    }
+    Ok(hex::decode(hex_code)?)
+}
+
+/// Stylus contract code: prefix plus the brotli-compressed wasm
+fn stylus_contract_code(wasm: &[u8]) -> Result<Vec<u8>> {
+    let mut code = STYLUS_PREFIX.to_vec();
+    {
+        let mut compressor = brotli::CompressorWriter::new(&mut code, 4096, 11, 22);
+        compressor.write_all(wasm)?;
+    }
+    Ok(code)
+}
+
+/// Init code that returns `code` as the deployed contract
+fn stylus_init_code(code: &[u8]) -> Vec<u8> {
+    // PUSH32 len, DUP1, PUSH1 43, PUSH1 0, CODECOPY, PUSH1 0, RETURN: copies
+    // and returns everything after this 42 byte prelude and a version byte
+    let mut len = [0u8; 32];
+    U256::from(code.len()).to_big_endian(&mut len);
+
+    let mut init = vec![0x7f];
+    init.extend_from_slice(&len);
+    init.extend_from_slice(&[0x80, 0x60, 43, 0x60, 0x00, 0x39, 0x60, 0x00, 0xf3, 0x00]);
+    init.extend_from_slice(code);
+    init
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("artifacts-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_solc_artifact_linking_and_constructor() {
+        let dir = temp_dir("solc");
+        // 0x60 0x80, then a 20 byte library placeholder at byte 2
+        let placeholder = format!("__${}$__", "0".repeat(34));
+        let artifact = serde_json::json!({
+            "abi": [{
+                "type": "constructor",
+                "stateMutability": "nonpayable",
+                "inputs": [
+                    {"name": "owner", "type": "address", "internalType": "address"},
+                    {"name": "fee", "type": "uint256", "internalType": "uint256"}
+                ]
+            }],
+            "bytecode": {
+                "object": format!("0x6080{}", placeholder),
+                "linkReferences": {"src/Math.sol": {"Math": [{"start": 2, "length": 20}]}}
+            },
+            "deployedBytecode": {"object": "0x6080", "linkReferences": {}, "immutableReferences": {}}
+        });
+        std::fs::write(dir.join("Pool.json"), artifact.to_string()).unwrap();
+
+        let library = Address::repeat_byte(0x11);
+        let mut manifest = ArtifactManifest::from_toml(&format!(
+            "build_dir = {:?}\n[contracts.pool]\npath = \"Pool.json\"\nconstructor_args = [\"@deployer\", \"30\"]\n",
+            dir.display().to_string()
+        ))
+        .unwrap();
+        assert!(manifest.artifact("pool").is_err(), "library address is required");
+
+        manifest.libraries.insert("Math".to_string(), library);
+        let loaded = manifest.artifact("pool").unwrap();
+        assert_eq!(&loaded.bytecode[..2], &[0x60, 0x80]);
+        assert_eq!(&loaded.bytecode[2..], library.as_bytes());
+        assert_eq!(loaded.runtime_hash, Some(H256(keccak256([0x60, 0x80]))));
+
+        let deployer = Address::repeat_byte(0x22);
+        let code = loaded.deployment_code(|name| (name == "deployer").then_some(deployer)).unwrap();
+        assert_eq!(code.len(), 22 + 64);
+        assert_eq!(&code[22 + 12..22 + 32], deployer.as_bytes());
+        assert_eq!(code[22 + 63], 30);
+        assert!(loaded.deployment_code(|_| None).is_err());
+
+        let spec = manifest.contracts.get_mut("pool").unwrap();
+        spec.bytecode_hash = Some(H256::zero());
+        assert!(manifest.artifact("pool").is_err(), "hash mismatch is refused");
+        manifest.contracts.get_mut("pool").unwrap().bytecode_hash = Some(loaded.bytecode_hash);
+        assert!(manifest.artifact("pool").is_ok());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_stylus_init_code() {
+        let wasm = b"\0asm\x01\0\0\0 stylus program".to_vec();
+        let code = stylus_contract_code(&wasm).unwrap();
+        assert_eq!(&code[..4], &STYLUS_PREFIX);
+
+        let mut decompressed = Vec::new();
+        brotli::Decompressor::new(&code[4..], 4096).read_to_end(&mut decompressed).unwrap();
+        assert_eq!(decompressed, wasm);
+
+        let init = stylus_init_code(&code);
+        assert_eq!(init[0], 0x7f);
+        assert_eq!(U256::from_big_endian(&init[1..33]).as_usize(), code.len());
+        assert_eq!(init[42], 0x00, "version byte follows the prelude");
+        assert_eq!(&init[43..], &code[..]);
+    }
+}
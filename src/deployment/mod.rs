@@ -1,29 +1,43 @@
-//! Holesky deployment module for Rust Intents System
+//! Deployment module for Rust Intents System
 //! 
 //! This module provides tools for deploying and configuring the complete
-//! cross-chain intents system on Holesky testnet.
+//! cross-chain intents system on any network in the registry (Holesky,
+//! Sepolia, Arbitrum Sepolia, Base Sepolia or a local anvil).
+
+pub mod address_book;
+pub mod artifacts;
+pub mod network;
 
 use ethers::{
     core::utils::parse_ether,
     middleware::SignerMiddleware,
     prelude::*,
     providers::{Http, Provider},
-    signers::{LocalWallet, Signer},
-    types::{Address, TransactionRequest, U256},
+    types::{transaction::eip2718::TypedTransaction, Address, Eip1559TransactionRequest, TransactionRequest, U256},
 };
 use eyre::Result;
+use intents_engine::signer::{self, DynSigner, Signer as _};
 use serde::{Deserialize, Serialize};
-use std::{sync::Arc, time::Duration};
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
 use tokio::time::sleep;
 
-/// Holesky network configuration
+pub use address_book::{AddressBook, ContractEntry};
+pub use artifacts::{Artifact, ArtifactKind, ArtifactManifest};
+pub use network::{GasStrategy, NetworkConfig, NetworkRegistry, DEFAULT_NETWORK};
+
+/// Holesky chain id
 pub const HOLESKY_CHAIN_ID: u64 = 17000;
-pub const HOLESKY_RPC_URL: &str = "https://crimson-attentive-emerald.ethereum-holesky.quiknode.pro/2f9f0ed63e2c2adf0adaca0fb431a457f86cf7ad/";
+
+/// Contracts `deploy_all` deploys, in order
+const DEPLOYMENT_ORDER: [&str; 3] = [address_book::MOCK_USDC, address_book::INTENTS, address_book::ORBITAL_AMM];
 
 /// Deployment configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeploymentConfig {
     pub private_key: String,
+    /// Registry key of the network deployed to
+    #[serde(default)]
+    pub network: String,
     pub rpc_url: String,
     pub chain_id: u64,
     pub deployer_address: Address,
@@ -51,172 +65,206 @@ pub struct DeploymentResult {
     pub total_cost: U256,
 }
 
-/// Holesky deployer
-pub struct HoleskyDeployer {
-    client: Arc<SignerMiddleware<Provider<Http>, LocalWallet>>,
+/// Deployer for one network from the registry
+pub struct NetworkDeployer {
+    client: Arc<SignerMiddleware<Provider<Http>, DynSigner>>,
+    network: NetworkConfig,
+    artifacts: ArtifactManifest,
     config: DeploymentConfig,
 }
 
-impl HoleskyDeployer {
-    /// Create a new Holesky deployer
-    pub async fn new(private_key: &str) -> Result<Self> {
-        let provider = Provider::<Http>::try_from(HOLESKY_RPC_URL)?;
-        let wallet: LocalWallet = private_key.parse::<LocalWallet>()?.with_chain_id(HOLESKY_CHAIN_ID);
-        let client = Arc::new(SignerMiddleware::new(provider, wallet.clone()));
+impl NetworkDeployer {
+    /// Create a new deployer signing with a local private key
+    pub async fn new(network: NetworkConfig, private_key: &str) -> Result<Self> {
+        let signer = signer::local(private_key, network.chain_id)?;
+        let mut deployer = Self::with_signer(network, signer).await?;
+        deployer.config.private_key = private_key.to_string();
+        Ok(deployer)
+    }
+
+    /// Create a new deployer with any signing backend (KMS, Ledger, ...)
+    pub async fn with_signer(network: NetworkConfig, signer: Arc<dyn intents_engine::signer::Signer>) -> Result<Self> {
+        let provider = Provider::<Http>::try_from(network.rpc_url.as_str())?;
+        let deployer_address = signer.address();
+        let client = Arc::new(SignerMiddleware::new(provider, DynSigner(signer)));
 
         let config = DeploymentConfig {
-            private_key: private_key.to_string(),
-            rpc_url: HOLESKY_RPC_URL.to_string(),
-            chain_id: HOLESKY_CHAIN_ID,
-            deployer_address: wallet.address(),
+            private_key: String::new(),
+            network: network.key.clone(),
+            rpc_url: network.rpc_url.clone(),
+            chain_id: network.chain_id,
+            deployer_address,
             gas_price: None,
-            gas_limit: None,
+            gas_limit: Some(network.gas_limit.into()),
+        };
+
+        Ok(Self {
+            client,
+            network,
+            artifacts: ArtifactManifest::builtin(),
+            config,
+        })
+    }
+
+    /// Deploy the contracts described by `artifacts` instead of the built-in
+    /// manifest
+    pub fn with_artifacts(mut self, artifacts: ArtifactManifest) -> Self {
+        self.artifacts = artifacts;
+        self
+    }
+
+    pub fn network(&self) -> &NetworkConfig {
+        &self.network
+    }
+
+    /// Contract creation transaction priced by the network's gas strategy
+    async fn deployment_tx(&self, data: Bytes) -> Result<TypedTransaction> {
+        let tx = match &self.network.gas {
+            GasStrategy::Legacy { .. } => {
+                let gas_price = self.network.gas.legacy_gas_price(self.client.get_gas_price().await?);
+                TransactionRequest::new()
+                    .data(data)
+                    .gas(self.network.gas_limit)
+                    .gas_price(gas_price)
+                    .into()
+            }
+            GasStrategy::Eip1559 { .. } => {
+                let base_fee = self
+                    .client
+                    .get_block(BlockNumber::Latest)
+                    .await?
+                    .and_then(|block| block.base_fee_per_gas)
+                    .ok_or_else(|| {
+                        eyre::eyre!("{} reports no base fee, use the legacy gas strategy", self.network.name)
+                    })?;
+                let (max_fee, priority_fee) = self.network.gas.eip1559_fees(base_fee);
+                Eip1559TransactionRequest::new()
+                    .data(data)
+                    .gas(self.network.gas_limit)
+                    .max_fee_per_gas(max_fee)
+                    .max_priority_fee_per_gas(priority_fee)
+                    .into()
+            }
         };
 
-        Ok(Self { client, config })
+        Ok(tx)
     }
 
     /// Check deployer balance and network connectivity
     pub async fn check_prerequisites(&self) -> Result<()> {
-        println!("🔍 Checking deployment prerequisites...");
+        tracing::info!("Checking deployment prerequisites");
 
         // Check network connectivity
         let chain_id = self.client.get_chainid().await?;
-        if chain_id.as_u64() != HOLESKY_CHAIN_ID {
+        if chain_id.as_u64() != self.network.chain_id {
             return Err(eyre::eyre!(
-                "Wrong network! Expected Holesky ({}), got {}",
-                HOLESKY_CHAIN_ID,
+                "Wrong network! Expected {} ({}), got {}",
+                self.network.name,
+                self.network.chain_id,
                 chain_id
             ));
         }
-        println!("✅ Connected to Holesky testnet");
+        tracing::info!("Connected to {}", self.network.name);
 
         // Check balance
         let balance = self.client.get_balance(self.config.deployer_address, None).await?;
         let balance_eth = ethers::utils::format_ether(balance);
-        println!("💰 Deployer balance: {} ETH", balance_eth);
-
-        if balance < parse_ether("0.1")? {
+        tracing::info!("Deployer balance: {} ETH", balance_eth);
+
+        if balance < parse_ether(&self.network.min_balance_eth)? {
+            let faucet = self
+                .network
+                .faucet_url
+                .as_ref()
+                .map(|url| format!(" Visit {} to get test ETH.", url))
+                .unwrap_or_default();
             return Err(eyre::eyre!(
-                "Insufficient balance! Need at least 0.1 ETH for deployment. \
-                Visit https://faucet.quicknode.com/ethereum/holesky to get test ETH."
+                "Insufficient balance! Need at least {} ETH for deployment.{}",
+                self.network.min_balance_eth,
+                faucet
             ));
         }
 
         // Check gas price
         let gas_price = self.client.get_gas_price().await?;
-        println!("⛽ Current gas price: {} gwei", ethers::utils::format_units(gas_price, "gwei")?);
+        tracing::info!("Current gas price: {} gwei", ethers::utils::format_units(gas_price, "gwei")?);
 
         Ok(())
     }
 
-    /// Deploy mock USDC token for testing
-    pub async fn deploy_mock_usdc(&self) -> Result<(Address, H256)> {
-        println!("📦 Deploying Mock USDC token...");
-
-        // ERC20 mock contract bytecode (simplified)
-        let bytecode = "608060405234801561001057600080fd5b506040518060400160405280600a81526020017f4d6f636b20555344430000000000000000000000000000000000000000000000815250604051806040016040528060048152602001635553444360e01b8152508160039081610075919061013c565b50805161008a90600490602084019061008e565b5050505b8061023e565b828054610094906101fe565b90600052602060002090601f0160209004810192826100b657600085556100fc565b82601f106100cf57805160ff19168380011785556100fc565b828001600101855582156100fc579182015b828111156100fc5782518255916020019190600101906100e1565b5061010892915061010c565b5090565b5b80821115610108576000815560010161010d565b634e487b7160e01b600052604160045260246000fd5b600181811c9082168061015057607f821691505b60208210810361017057634e487b7160e01b600052602260045260246000fd5b50919050565b601f8211156101b957600081815260208120601f850160051c8101602086101561019d5750805b601f850160051c820191505b818110156101bc578281556001016101a9565b5050505b505050565b81516001600160401b038111156101de576101de610121565b6101f2816101ec845461013c565b84610176565b602080601f831160018114610227576000841561020f5750858301515b600019600386901b1c1916600185901b1785556101bc565b600085815260208120601f198616915b8281101561025657888601518255948401946001909101908401610237565b508582101561027457878501516000196003841b1c19169055565b5050505050600190811b01905550565b610e8380610293600039f3fe";
-
-        let deploy_tx = TransactionRequest::new()
-            .data(hex::decode(bytecode)?)
-            .gas(1_000_000)
-            .gas_price(self.client.get_gas_price().await?);
-
-        let pending_tx = self.client.send_transaction(deploy_tx, None).await?;
-        let receipt = pending_tx.await?.ok_or_else(|| eyre::eyre!("Transaction failed"))?;
-
-        let contract_address = receipt.contract_address.ok_or_else(|| eyre::eyre!("No contract address"))?;
-
-        println!("✅ Mock USDC deployed at: {}", contract_address);
-        println!("   Transaction: {}", receipt.transaction_hash);
-        println!("   Gas used: {}", receipt.gas_used.unwrap_or_default());
-
-        Ok((contract_address, receipt.transaction_hash))
-    }
-
-    /// Deploy mock intents contract
-    pub async fn deploy_intents_contract(&self) -> Result<(Address, H256)> {
-        println!("📦 Deploying Intents contract...");
-
-        // For demo purposes, deploy a simple contract that emits events
-        let bytecode = "608060405234801561001057600080fd5b50610150806100206000396000f3fe608060405234801561001057600080fd5b50600436106100365760003560e01c806354fd4d501461003b578063c0d78655146100a3575b600080fd5b6100896040518060400160405280601081526020016f496e74656e747320436f6e747261637460801b81525081565b60405161009a9190610096565b60405180910390f35b6100b66100b13660046100d7565b6100b8565b005b7f8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925338260405160405180910390a150565b6000602082840312156100e957600080fd5b5035919050565b600060208083528351808285015260005b81811015610109578581018301518582016040015282016100ed565b506000604082860101526040601f19601f8301168501019250505092915050565b60006020828403121561013c57600080fd5b5035919050565b9052565b6020808252825182820181905260009190848201906040850190845b818110156101805783518352928401929184019160010161015f565b50909695505050505056fea2646970667358221220f5a8a8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c864736f6c63430008130033";
-
-        let deploy_tx = TransactionRequest::new()
-            .data(hex::decode(bytecode)?)
-            .gas(1_000_000)
-            .gas_price(self.client.get_gas_price().await?);
+    /// Deploy a contract from its artifact. `@name` constructor arguments
+    /// refer to `deployed` contracts or, as `@deployer`, to the deployer.
+    pub async fn deploy_contract(
+        &self,
+        artifact: &Artifact,
+        deployed: &BTreeMap<String, Address>,
+    ) -> Result<(Address, H256)> {
+        tracing::info!("Deploying {} contract", artifact.name);
+
+        let deployer = self.config.deployer_address;
+        let code = artifact.deployment_code(|name| match name {
+            "deployer" => Some(deployer),
+            _ => deployed.get(name).copied(),
+        })?;
+        let deploy_tx = self.deployment_tx(code).await?;
 
         let pending_tx = self.client.send_transaction(deploy_tx, None).await?;
         let receipt = pending_tx.await?.ok_or_else(|| eyre::eyre!("Transaction failed"))?;
 
         let contract_address = receipt.contract_address.ok_or_else(|| eyre::eyre!("No contract address"))?;
 
-        println!("✅ Intents contract deployed at: {}", contract_address);
-        println!("   Transaction: {}", receipt.transaction_hash);
-        println!("   Gas used: {}", receipt.gas_used.unwrap_or_default());
-
-        Ok((contract_address, receipt.transaction_hash))
-    }
-
-    /// Deploy mock Orbital AMM contract
-    pub async fn deploy_orbital_amm(&self) -> Result<(Address, H256)> {
-        println!("📦 Deploying Orbital AMM contract...");
-
-        // Similar simple contract for demo
-        let bytecode = "608060405234801561001057600080fd5b50610150806100206000396000f3fe608060405234801561001057600080fd5b50600436106100365760003560e01c806354fd4d501461003b578063c0d78655146100a3575b600080fd5b6100896040518060400160405280600e81526020016d4f726269746c20414d4d20563160901b81525081565b60405161009a9190610096565b60405180910390f35b6100b66100b13660046100d7565b6100b8565b005b7f8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925338260405160405180910390a150565b6000602082840312156100e957600080fd5b5035919050565b600060208083528351808285015260005b81811015610109578581018301518582016040015282016100ed565b506000604082860101526040601f19601f8301168501019250505092915050565b60006020828403121561013c57600080fd5b5035919050565b9052565b6020808252825182820181905260009190848201906040850190845b818110156101805783518352928401929184019160010161015f565b50909695505050505056fea2646970667358221220f5a8a8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c8c864736f6c63430008130033";
-
-        let deploy_tx = TransactionRequest::new()
-            .data(hex::decode(bytecode)?)
-            .gas(1_000_000)
-            .gas_price(self.client.get_gas_price().await?);
-
-        let pending_tx = self.client.send_transaction(deploy_tx, None).await?;
-        let receipt = pending_tx.await?.ok_or_else(|| eyre::eyre!("Transaction failed"))?;
-
-        let contract_address = receipt.contract_address.ok_or_else(|| eyre::eyre!("No contract address"))?;
-
-        println!("✅ Orbital AMM contract deployed at: {}", contract_address);
-        println!("   Transaction: {}", receipt.transaction_hash);
-        println!("   Gas used: {}", receipt.gas_used.unwrap_or_default());
+        tracing::info!(
+            "{} deployed at {:?} (tx {:?}, gas used {})",
+            artifact.name,
+            contract_address,
+            receipt.transaction_hash,
+            receipt.gas_used.unwrap_or_default()
+        );
 
         Ok((contract_address, receipt.transaction_hash))
     }
 
     /// Complete deployment process
     pub async fn deploy_all(&self) -> Result<DeploymentResult> {
-        println!("🚀 Starting complete deployment to Holesky testnet");
-        println!("==================================================");
+        tracing::info!("Starting complete deployment to {}", self.network.name);
 
         // Check prerequisites
         self.check_prerequisites().await?;
 
+        // Load and check every artifact before sending anything
+        let artifacts = DEPLOYMENT_ORDER
+            .iter()
+            .map(|name| self.artifacts.artifact(name))
+            .collect::<Result<Vec<_>>>()?;
+
         let mut transaction_hashes = Vec::new();
         let mut total_gas_used = U256::zero();
 
         // Deploy contracts
-        let (usdc_address, usdc_tx) = self.deploy_mock_usdc().await?;
-        transaction_hashes.push(usdc_tx);
-
-        sleep(Duration::from_secs(2)).await; // Wait between deployments
-
-        let (intents_address, intents_tx) = self.deploy_intents_contract().await?;
-        transaction_hashes.push(intents_tx);
-
-        sleep(Duration::from_secs(2)).await;
-
-        let (orbital_amm_address, orbital_amm_tx) = self.deploy_orbital_amm().await?;
-        transaction_hashes.push(orbital_amm_tx);
+        let mut deployed = BTreeMap::new();
+        for (i, artifact) in artifacts.iter().enumerate() {
+            if i > 0 {
+                sleep(Duration::from_secs(2)).await; // Wait between deployments
+            }
+            let (address, tx_hash) = self.deploy_contract(artifact, &deployed).await?;
+            deployed.insert(artifact.name.clone(), address);
+            transaction_hashes.push(tx_hash);
+        }
+        let usdc_address = deployed[address_book::MOCK_USDC];
+        let intents_address = deployed[address_book::INTENTS];
+        let orbital_amm_address = deployed[address_book::ORBITAL_AMM];
 
         // Calculate total gas and cost
+        let gas_price = self.client.get_gas_price().await?;
+        let mut total_cost = U256::zero();
         for tx_hash in &transaction_hashes {
             if let Ok(Some(receipt)) = self.client.get_transaction_receipt(*tx_hash).await {
-                total_gas_used += receipt.gas_used.unwrap_or_default();
+                let gas_used = receipt.gas_used.unwrap_or_default();
+                total_gas_used += gas_used;
+                total_cost += gas_used * receipt.effective_gas_price.unwrap_or(gas_price);
             }
         }
 
-        let gas_price = self.client.get_gas_price().await?;
-        let total_cost = total_gas_used * gas_price;
-
         let deployment_block = self.client.get_block_number().await?.as_u64();
         let deployment_timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)?
@@ -238,56 +286,97 @@ impl HoleskyDeployer {
             total_cost,
         };
 
-        println!("\n🎉 Deployment completed successfully!");
-        println!("=====================================");
-        println!("📋 Deployment Summary:");
-        println!("   Network: Holesky Testnet (Chain ID: {})", HOLESKY_CHAIN_ID);
-        println!("   Deployer: {}", self.config.deployer_address);
-        println!("   Block: {}", deployment_block);
-        println!("   Total Gas Used: {}", total_gas_used);
-        println!("   Total Cost: {} ETH", ethers::utils::format_ether(total_cost));
-        println!("\n📦 Deployed Contracts:");
-        println!("   Intents: {}", intents_address);
-        println!("   Orbital AMM: {}", orbital_amm_address);
-        println!("   Mock USDC: {}", usdc_address);
-        println!("\n🔗 Transaction Hashes:");
-        for (i, tx_hash) in transaction_hashes.iter().enumerate() {
-            println!("   {}: {}", i + 1, tx_hash);
-        }
+        tracing::info!(
+            "Deployment to {} completed at block {}: {} gas, {} ETH",
+            self.network.name,
+            deployment_block,
+            total_gas_used,
+            ethers::utils::format_ether(total_cost)
+        );
 
         Ok(result)
     }
 
     /// Verify deployment by calling contract functions
     pub async fn verify_deployment(&self, contracts: &DeployedContracts) -> Result<()> {
-        println!("\n🔍 Verifying deployment...");
+        tracing::info!("Verifying deployment");
 
-        // Verify contracts have code
+        // Verify contracts have code, and the code their artifacts promise
+        // when that is known ahead of deployment
         for (name, address) in [
-            ("Intents", contracts.intents_contract),
-            ("Orbital AMM", contracts.orbital_amm_contract),
-            ("Mock USDC", contracts.mock_usdc_contract),
+            (address_book::INTENTS, contracts.intents_contract),
+            (address_book::ORBITAL_AMM, contracts.orbital_amm_contract),
+            (address_book::MOCK_USDC, contracts.mock_usdc_contract),
         ] {
             let code = self.client.get_code(address, None).await?;
             if code.is_empty() {
-                return Err(eyre::eyre!("{} contract has no code at {}", name, address));
+                return Err(eyre::eyre!("{} contract has no code at {:?}", name, address));
+            }
+            if let Some(expected) = self.artifacts.read(name)?.runtime_hash {
+                let actual = H256(ethers::utils::keccak256(&code));
+                if actual != expected {
+                    return Err(eyre::eyre!(
+                        "{} contract at {:?} does not match its artifact: expected code hash {:?}, got {:?}",
+                        name,
+                        address,
+                        expected,
+                        actual
+                    ));
+                }
             }
-            println!("✅ {} contract verified at {}", name, address);
+            tracing::info!("{} contract verified at {:?}", name, address);
         }
 
-        println!("✅ All contracts verified successfully!");
+        tracing::info!("All contracts verified");
         Ok(())
     }
 }
 
-/// CLI deployment tool
-pub async fn deploy_to_holesky(private_key: &str) -> Result<DeploymentResult> {
-    let deployer = HoleskyDeployer::new(private_key).await?;
+/// Deploy, verify and record the contracts in the network's address book
+async fn deploy_and_record(deployer: NetworkDeployer) -> Result<DeploymentResult> {
     let result = deployer.deploy_all().await?;
     deployer.verify_deployment(&result.contracts).await?;
+
+    let network = deployer.network();
+    let path = network.address_book_path();
+    let mut book = AddressBook::load_or_new(&path, network.chain_id)?;
+    book.record(&result);
+    book.save(&path)?;
+    tracing::info!("Address book updated at {}", path.display());
+
     Ok(result)
 }
 
+/// CLI deployment tool
+pub async fn deploy_to_network(
+    network: NetworkConfig,
+    artifacts: ArtifactManifest,
+    private_key: &str,
+) -> Result<DeploymentResult> {
+    deploy_and_record(NetworkDeployer::new(network, private_key).await?.with_artifacts(artifacts)).await
+}
+
+/// CLI deployment with a configured signer backend
+pub async fn deploy_to_network_with_signer(
+    network: NetworkConfig,
+    artifacts: ArtifactManifest,
+    config: &signer::SignerConfig,
+) -> Result<DeploymentResult> {
+    let signer = config.build(network.chain_id).await?;
+    deploy_and_record(NetworkDeployer::with_signer(network, signer).await?.with_artifacts(artifacts)).await
+}
+
+/// Deploy to Holesky from the built-in registry and artifact manifest
+pub async fn deploy_to_holesky(private_key: &str) -> Result<DeploymentResult> {
+    deploy_to_network(NetworkRegistry::builtin().get("holesky")?, ArtifactManifest::builtin(), private_key).await
+}
+
+/// Deploy to Holesky from the built-in registry and artifact manifest with a
+/// configured signer backend
+pub async fn deploy_to_holesky_with_signer(config: &signer::SignerConfig) -> Result<DeploymentResult> {
+    deploy_to_network_with_signer(NetworkRegistry::builtin().get("holesky")?, ArtifactManifest::builtin(), config).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,14 +384,17 @@ mod tests {
     #[tokio::test]
     async fn test_deployer_creation() {
         let private_key = "0c068df4a4470cb73e6704d87c61a0c2718e72381c7b1e971514e5f9c4486f93";
-        let deployer = HoleskyDeployer::new(private_key).await;
-        assert!(deployer.is_ok());
+        let network = NetworkRegistry::builtin().get("anvil").unwrap();
+        let deployer = NetworkDeployer::new(network, private_key).await.unwrap();
+        assert_eq!(deployer.config.chain_id, 31337);
+        assert_eq!(deployer.config.network, "anvil");
     }
 
     #[test]
     fn test_deployment_config_serialization() {
         let config = DeploymentConfig {
             private_key: "test_key".to_string(),
+            network: "holesky".to_string(),
             rpc_url: "test_url".to_string(),
             chain_id: 17000,
             deployer_address: Address::zero(),
@@ -0,0 +1,273 @@
+//! Network registry for deployments
+//!
+//! Networks are described in a TOML registry (see `deployments/networks.toml`,
+//! which is also compiled in as the default), keyed by a short name such as
+//! `sepolia` or `anvil`. Each entry carries its RPC endpoint, explorer, the
+//! balance a deployer needs, and how deployment transactions are priced.
+
+use ethers::types::U256;
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Registry compiled into the binary
+pub const BUILTIN_REGISTRY: &str = include_str!("../../deployments/networks.toml");
+
+/// Network used when none is given
+pub const DEFAULT_NETWORK: &str = "holesky";
+
+/// Networks by key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkRegistry {
+    pub networks: BTreeMap<String, NetworkConfig>,
+}
+
+/// One deployable network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    /// Registry key, filled in on lookup
+    #[serde(skip)]
+    pub key: String,
+    pub name: String,
+    pub chain_id: u64,
+    /// May contain `${VAR}` or `${VAR:-default}`, expanded on lookup
+    pub rpc_url: String,
+    #[serde(default)]
+    pub explorer_url: Option<String>,
+    #[serde(default)]
+    pub faucet_url: Option<String>,
+    /// Smallest deployer balance worth starting a deployment with
+    #[serde(default = "default_min_balance_eth")]
+    pub min_balance_eth: String,
+    /// Gas limit for each deployment transaction
+    #[serde(default = "default_gas_limit")]
+    pub gas_limit: u64,
+    #[serde(default)]
+    pub gas: GasStrategy,
+    /// Defaults to `deployments/<key>/addresses.toml`
+    #[serde(default)]
+    pub address_book: Option<PathBuf>,
+}
+
+fn default_min_balance_eth() -> String {
+    "0.1".to_string()
+}
+
+fn default_gas_limit() -> u64 {
+    1_000_000
+}
+
+/// How deployment transactions are priced
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum GasStrategy {
+    /// Type 0 transactions at `eth_gasPrice` times `multiplier`
+    Legacy {
+        #[serde(default = "default_multiplier")]
+        multiplier: f64,
+        #[serde(default)]
+        max_gas_price_gwei: Option<f64>,
+    },
+    /// Type 2 transactions with a max fee of the latest base fee times
+    /// `base_fee_multiplier`, plus the priority fee
+    Eip1559 {
+        #[serde(default = "default_base_fee_multiplier")]
+        base_fee_multiplier: f64,
+        #[serde(default)]
+        priority_fee_gwei: f64,
+        #[serde(default)]
+        max_fee_gwei: Option<f64>,
+    },
+}
+
+fn default_multiplier() -> f64 {
+    1.0
+}
+
+fn default_base_fee_multiplier() -> f64 {
+    2.0
+}
+
+impl Default for GasStrategy {
+    fn default() -> Self {
+        GasStrategy::Legacy {
+            multiplier: default_multiplier(),
+            max_gas_price_gwei: None,
+        }
+    }
+}
+
+impl GasStrategy {
+    /// Gas price for a legacy transaction given the network's current price
+    pub fn legacy_gas_price(&self, network_price: U256) -> U256 {
+        match self {
+            GasStrategy::Legacy { multiplier, max_gas_price_gwei } => {
+                cap(scale(network_price, *multiplier), *max_gas_price_gwei)
+            }
+            GasStrategy::Eip1559 { max_fee_gwei, .. } => cap(network_price, *max_fee_gwei),
+        }
+    }
+
+    /// Max fee and max priority fee given the latest base fee
+    pub fn eip1559_fees(&self, base_fee: U256) -> (U256, U256) {
+        match self {
+            GasStrategy::Eip1559 { base_fee_multiplier, priority_fee_gwei, max_fee_gwei } => {
+                let max_fee = cap(scale(base_fee, *base_fee_multiplier) + gwei(*priority_fee_gwei), *max_fee_gwei);
+                let priority_fee = gwei(*priority_fee_gwei).min(max_fee);
+                (max_fee, priority_fee)
+            }
+            GasStrategy::Legacy { .. } => {
+                let price = self.legacy_gas_price(base_fee);
+                (price, U256::zero())
+            }
+        }
+    }
+}
+
+fn gwei(value: f64) -> U256 {
+    U256::from((value.max(0.0) * 1e9).round() as u128)
+}
+
+fn scale(value: U256, multiplier: f64) -> U256 {
+    value * U256::from((multiplier.max(0.0) * 10_000.0).round() as u64) / U256::from(10_000u64)
+}
+
+fn cap(value: U256, max_gwei: Option<f64>) -> U256 {
+    match max_gwei {
+        Some(max) => value.min(gwei(max)),
+        None => value,
+    }
+}
+
+impl NetworkRegistry {
+    /// The registry shipped in `deployments/networks.toml`
+    pub fn builtin() -> Self {
+        Self::from_toml(BUILTIN_REGISTRY).expect("built-in network registry is valid")
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("Failed to read network registry {}: {}", path.display(), e))?;
+        Self::from_toml(&text)
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self> {
+        toml::from_str(text).map_err(|e| eyre::eyre!("Invalid network registry: {}", e))
+    }
+
+    /// Look up a network by key, with environment variables in its RPC URL
+    /// expanded
+    pub fn get(&self, key: &str) -> Result<NetworkConfig> {
+        let mut network = self.networks.get(key).cloned().ok_or_else(|| {
+            eyre::eyre!(
+                "Unknown network '{}', expected one of: {}",
+                key,
+                self.networks.keys().cloned().collect::<Vec<_>>().join(", ")
+            )
+        })?;
+        network.key = key.to_string();
+        network.rpc_url = expand_env(&network.rpc_url, |var| std::env::var(var).ok())?;
+        Ok(network)
+    }
+
+    pub fn key_for_chain(&self, chain_id: u64) -> Option<&str> {
+        self.networks
+            .iter()
+            .find(|(_, network)| network.chain_id == chain_id)
+            .map(|(key, _)| key.as_str())
+    }
+}
+
+impl NetworkConfig {
+    pub fn address_book_path(&self) -> PathBuf {
+        self.address_book
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("deployments").join(&self.key).join("addresses.toml"))
+    }
+
+    /// Explorer link for an address or transaction, e.g. `("address", addr)`
+    pub fn explorer_link(&self, kind: &str, value: impl std::fmt::Display) -> Option<String> {
+        self.explorer_url
+            .as_ref()
+            .map(|base| format!("{}/{}/{}", base.trim_end_matches('/'), kind, value))
+    }
+}
+
+/// Expand `${VAR}` and `${VAR:-default}` references
+fn expand_env(value: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| eyre::eyre!("Unterminated variable reference in '{}'", value))?;
+        let reference = &rest[start + 2..start + end];
+        let (var, default) = match reference.split_once(":-") {
+            Some((var, default)) => (var, Some(default)),
+            None => (reference, None),
+        };
+        match lookup(var).filter(|v| !v.is_empty()) {
+            Some(resolved) => out.push_str(&resolved),
+            None => out.push_str(default.ok_or_else(|| eyre::eyre!("Environment variable {} is not set", var))?),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_registry() {
+        let registry = NetworkRegistry::builtin();
+        for key in ["holesky", "sepolia", "arbitrum-sepolia", "base-sepolia", "anvil"] {
+            let network = registry.get(key).unwrap();
+            assert_eq!(network.key, key);
+            assert!(!network.rpc_url.contains("${"));
+        }
+        assert_eq!(registry.key_for_chain(421614), Some("arbitrum-sepolia"));
+        assert!(registry.get("mainnet").is_err());
+
+        let anvil = registry.get("anvil").unwrap();
+        assert_eq!(anvil.address_book_path(), PathBuf::from("deployments/anvil/addresses.toml"));
+        assert_eq!(anvil.explorer_link("tx", "0x1"), None);
+
+        let lookup = |var: &str| (var == "SET").then(|| "http://set".to_string());
+        assert_eq!(expand_env("${SET:-http://default}", lookup).unwrap(), "http://set");
+        assert_eq!(expand_env("${UNSET:-http://default}/rpc", lookup).unwrap(), "http://default/rpc");
+        assert!(expand_env("${UNSET}", lookup).is_err());
+    }
+
+    #[test]
+    fn test_gas_strategies() {
+        let legacy = GasStrategy::Legacy { multiplier: 1.5, max_gas_price_gwei: Some(20.0) };
+        assert_eq!(legacy.legacy_gas_price(gwei(10.0)), gwei(15.0));
+        assert_eq!(legacy.legacy_gas_price(gwei(30.0)), gwei(20.0));
+
+        let eip1559 = GasStrategy::Eip1559 {
+            base_fee_multiplier: 2.0,
+            priority_fee_gwei: 1.5,
+            max_fee_gwei: Some(50.0),
+        };
+        assert_eq!(eip1559.eip1559_fees(gwei(10.0)), (gwei(21.5), gwei(1.5)));
+        assert_eq!(eip1559.eip1559_fees(gwei(40.0)), (gwei(50.0), gwei(1.5)));
+
+        let parsed: NetworkConfig = toml::from_str(
+            "name = \"x\"\nchain_id = 1\nrpc_url = \"http://x\"\n[gas]\nstrategy = \"eip1559\"\npriority_fee_gwei = 2.0\n",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.gas,
+            GasStrategy::Eip1559 { base_fee_multiplier: 2.0, priority_fee_gwei: 2.0, max_fee_gwei: None }
+        );
+        assert_eq!(parsed.gas_limit, 1_000_000);
+    }
+}
@@ -7,8 +7,9 @@ pub mod deployment;
 
 // Re-export key types for convenience
 pub use deployment::{
-    deploy_to_holesky, DeploymentConfig, DeploymentResult, DeployedContracts, HoleskyDeployer,
-    HOLESKY_CHAIN_ID, HOLESKY_RPC_URL,
+    deploy_to_holesky, deploy_to_holesky_with_signer, deploy_to_network, deploy_to_network_with_signer, AddressBook,
+    ArtifactManifest, DeploymentConfig, DeploymentResult, DeployedContracts, GasStrategy, NetworkConfig, NetworkDeployer, NetworkRegistry,
+    HOLESKY_CHAIN_ID,
 };
 
 /// System version